@@ -0,0 +1,374 @@
+//! Administration API Routes
+//!
+//! ## Endpoints
+//!
+//! | Method | Endpoint | Description |
+//! |--------|----------|-------------|
+//! | PUT | /admin/config | Update and persist site settings (`allow_registration`, `invite_only`, `archive_after_days`) |
+//! | POST | /admin/config/reload | Reload configuration from disk (admin) |
+//! | GET | /admin/comments/search | Search comments by content or GitHub username (`?q=&limit=&offset=`) |
+//! | GET | /admin/users/search | Search users by username (`?q=alice&limit=20&offset=0`), with the total match count |
+//! | GET | /admin/files | List files of all users (`?limit=&offset=&content_type=`) |
+//! | POST | /admin/files/{id}/move | Reassign a file to another user (`{"new_owner_id": "..."}`) |
+//! | GET | /admin/stats/words | Total, average, longest and shortest post word counts (`?published_only=true`) |
+//! | GET | /admin/stats/export | Download daily traffic as CSV (`?start=2025-01-01&end=2025-12-31`) |
+//! | POST | /admin/stats/posts/{id}/reset | Reset a post's view count to zero |
+//! | POST | /admin/stats/reset-all | Reset the view counts of all posts to zero |
+//! | GET | /admin/invites | List registration invite codes |
+//! | POST | /admin/invites | Create an invite code (`{"expires_in_hours": 72}`) |
+
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+    Json, Router,
+};
+use chrono::NaiveDate;
+use domain::{
+    CreateInviteRequest, CreateInviteResponse, UpdateSiteConfig, EDITABLE_SITE_FIELDS, USER_MANAGE,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    error::ApiError, middleware::auth::Claims, response::helpers as resp, state::AppState,
+};
+
+/// Create admin routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/config", axum::routing::put(update_site_config))
+        .route("/config/reload", axum::routing::post(reload_config))
+        .route("/comments/search", axum::routing::get(search_comments))
+        .route("/users/search", axum::routing::get(search_users))
+        .route("/files", axum::routing::get(list_all_files))
+        .route("/files/{id}/move", axum::routing::post(move_file))
+        .route("/stats/words", axum::routing::get(word_count_stats))
+        .route("/stats/export", axum::routing::get(export_stats))
+        .route(
+            "/stats/posts/{id}/reset",
+            axum::routing::post(reset_post_stats),
+        )
+        .route(
+            "/stats/reset-all",
+            axum::routing::post(reset_all_post_stats),
+        )
+        .route(
+            "/invites",
+            axum::routing::get(list_invites).post(create_invite),
+        )
+}
+
+/// Query parameters for the admin comment search
+#[derive(Debug, Deserialize)]
+pub struct CommentSearchQuery {
+    /// Text to look for in comment content or GitHub usernames
+    #[serde(default)]
+    q: String,
+    /// Page size
+    #[serde(default = "default_search_limit")]
+    limit: u64,
+    /// Number of matches to skip
+    #[serde(default)]
+    offset: u64,
+}
+
+/// Request body for moving a file to another user
+#[derive(Debug, Deserialize)]
+pub struct MoveFileRequest {
+    new_owner_id: Uuid,
+}
+
+/// Query parameters for the admin user search
+#[derive(Debug, Deserialize)]
+pub struct UserSearchQuery {
+    /// Text to look for in usernames
+    #[serde(default)]
+    q: String,
+    /// Page size
+    #[serde(default = "default_search_limit")]
+    limit: u64,
+    /// Number of matches to skip
+    #[serde(default)]
+    offset: u64,
+}
+
+fn default_search_limit() -> u64 {
+    20
+}
+
+/// Query parameters for the word count stats
+#[derive(Debug, Deserialize)]
+pub struct WordCountQuery {
+    /// Leave drafts out of the counts
+    #[serde(default)]
+    published_only: bool,
+}
+
+/// Query parameters for listing all files
+#[derive(Debug, Deserialize)]
+pub struct ListFilesQuery {
+    /// Page size
+    #[serde(default = "default_limit")]
+    limit: u64,
+    /// Number of files to skip
+    #[serde(default)]
+    offset: u64,
+    /// Exact content type, or a prefix ending in `/` such as `image/`
+    content_type: Option<String>,
+}
+
+fn default_limit() -> u64 {
+    50
+}
+
+/// Query parameters for the stats CSV export
+#[derive(Debug, Deserialize)]
+pub struct StatsExportQuery {
+    /// First day to include, `YYYY-MM-DD`
+    start: NaiveDate,
+    /// Last day to include, `YYYY-MM-DD`
+    end: NaiveDate,
+}
+
+/// POST /admin/config/reload
+/// Re-read the configuration file and apply it without a restart (admin only)
+///
+/// Values read per request (registration, comment moderation, GitHub OAuth,
+/// post limits and allowed HTML) take effect immediately, and the SMTP,
+/// IndexNow and webhook clients are rebuilt. Database, server (bind address,
+/// CORS, compression) and storage settings and the JWT secret still require
+/// a restart.
+async fn reload_config(
+    State(state): State<AppState>,
+    user: Claims,
+) -> Result<impl IntoResponse, ApiError> {
+    domain::check_permission(user.permissions, USER_MANAGE)
+        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+
+    let config = state
+        .config_service
+        .reload()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(config))
+}
+
+/// PUT /admin/config
+/// Update site settings and write them to the configuration file (admin only)
+///
+/// Only fields listed in [`EDITABLE_SITE_FIELDS`] are accepted; any other
+/// key rejects the whole request so nothing is written.
+async fn update_site_config(
+    State(state): State<AppState>,
+    user: Claims,
+    Json(input): Json<serde_json::Map<String, serde_json::Value>>,
+) -> Result<impl IntoResponse, ApiError> {
+    if let Some(field) = input
+        .keys()
+        .find(|field| !EDITABLE_SITE_FIELDS.contains(&field.as_str()))
+    {
+        return Err(ApiError::Validation(format!(
+            "Field '{}' cannot be changed here; editable fields: {}",
+            field,
+            EDITABLE_SITE_FIELDS.join(", ")
+        )));
+    }
+    let update: UpdateSiteConfig = serde_json::from_value(input.into())
+        .map_err(|e| ApiError::Validation(format!("Invalid site config: {}", e)))?;
+
+    let config = state
+        .config_service
+        .update_site_config(update, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(config))
+}
+
+/// GET /admin/comments/search
+/// Find comments across all posts, newest first, with their post titles (admin only)
+async fn search_comments(
+    State(state): State<AppState>,
+    user: Claims,
+    Query(params): Query<CommentSearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let results = state
+        .comment_service
+        .search(&params.q, user.permissions, params.limit, params.offset)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(results))
+}
+
+/// GET /admin/users/search
+/// Search users by username, returning a page of matches and the total (admin only)
+async fn search_users(
+    State(state): State<AppState>,
+    user: Claims,
+    Query(params): Query<UserSearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let results = state
+        .user_service
+        .search(&params.q, user.permissions, params.limit, params.offset)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(results))
+}
+
+/// GET /admin/stats/words
+/// Word counts across the blog (admin only)
+async fn word_count_stats(
+    State(state): State<AppState>,
+    user: Claims,
+    Query(params): Query<WordCountQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stats = state
+        .post_service
+        .get_word_count_stats(params.published_only, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(stats))
+}
+
+/// GET /admin/files
+/// List files uploaded by all users, newest first (admin only)
+///
+/// The response carries the total count and combined size of every file
+/// matching the filter, not just the returned page.
+async fn list_all_files(
+    State(state): State<AppState>,
+    user: Claims,
+    Query(params): Query<ListFilesQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let listing = state
+        .file_service
+        .list_all_files(
+            user.permissions,
+            params.limit,
+            params.offset,
+            params.content_type.as_deref(),
+        )
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(listing))
+}
+
+/// POST /admin/files/{id}/move
+/// Reassign a file to another user, e.g. one uploaded to the wrong account (admin only)
+async fn move_file(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+    Json(input): Json<MoveFileRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let requester_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let file = state
+        .file_service
+        .move_file(id, input.new_owner_id, requester_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(file))
+}
+
+/// POST /admin/stats/posts/{id}/reset
+/// Reset a post's view count, e.g. after crawler traffic (admin only)
+async fn reset_post_stats(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    state
+        .stats_service
+        .reset_post_stats(id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::no_content())
+}
+
+/// POST /admin/stats/reset-all
+/// Reset the view counts of all posts (admin only)
+async fn reset_all_post_stats(
+    State(state): State<AppState>,
+    user: Claims,
+) -> Result<impl IntoResponse, ApiError> {
+    let reset = state
+        .stats_service
+        .reset_all_post_stats(user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(serde_json::json!({ "reset": reset })))
+}
+
+/// GET /admin/stats/export
+/// Download one CSV row of traffic per day in the range (admin only)
+async fn export_stats(
+    State(state): State<AppState>,
+    user: Claims,
+    Query(params): Query<StatsExportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    domain::check_permission(user.permissions, USER_MANAGE)
+        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+
+    let csv = state
+        .stats_service
+        .export_stats_csv(params.start, params.end)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let disposition = format!(
+        "attachment; filename=\"stats-{}-{}.csv\"",
+        params.start, params.end
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    ))
+}
+
+/// GET /admin/invites
+/// List every invite code, newest first (admin only)
+async fn list_invites(
+    State(state): State<AppState>,
+    user: Claims,
+) -> Result<impl IntoResponse, ApiError> {
+    let invites = state
+        .user_service
+        .list_invites(user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(invites))
+}
+
+/// POST /admin/invites
+/// Create a single-use invite code for invite-only registration (admin only)
+async fn create_invite(
+    State(state): State<AppState>,
+    user: Claims,
+    Json(input): Json<CreateInviteRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let admin_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let code = state
+        .user_service
+        .create_invite(admin_id, user.permissions, input.expires_in_hours)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::created(CreateInviteResponse { code }))
+}
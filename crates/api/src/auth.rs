@@ -1,7 +1,8 @@
 //! Authentication API Routes
 //!
 //! This module provides HTTP handlers for authentication operations.
-//! All auth routes are public except `/auth/me`.
+//! All auth routes are public except `/auth/me`, `/auth/email`,
+//! `/auth/impersonate` and the TOTP setup routes.
 //!
 //! ## Endpoints
 //!
@@ -9,14 +10,37 @@
 //! |--------|----------|-------------|
 //! | POST | /auth/register | Register new user |
 //! | POST | /auth/login | Login with credentials |
+//! | POST | /auth/totp | Finish login with a TOTP code |
+//! | POST | /auth/totp/setup | Start TOTP setup for current user |
+//! | POST | /auth/totp/confirm | Confirm TOTP setup with a code |
+//! | POST | /auth/refresh | Exchange a refresh token for a new JWT |
 //! | POST | /auth/logout | Logout (client-side token removal) |
 //! | GET | /auth/me | Get current user info |
+//! | PUT | /auth/email | Set email address and send a verification link |
+//! | GET | /auth/verify-email?token=... | Confirm an email address |
+//! | POST | /auth/forgot-password | Email a password reset link |
+//! | POST | /auth/reset-password | Set a new password with a reset token |
+//! | POST | /auth/impersonate/{user_id} | Get a read-only token for another user (admin) |
 
-use axum::{extract::State, response::IntoResponse, Json, Router};
-use domain::{LoginRequest, LoginResponse, RegisterRequest, UserInfo};
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    Json, Router,
+};
+use domain::{
+    LoginRequest, LoginResponse, LoginResult, OtpRequiredResponse, PasswordResetRequest,
+    RefreshTokenRequest, RegisterRequest, ResetPasswordRequest, TotpCodeRequest, TotpLoginRequest,
+    TotpSetupResponse, UpdateEmailRequest, User, UserInfo,
+};
+use serde::Deserialize;
 
 use crate::{
-    error::ApiError, middleware::auth::Claims, response::helpers as resp, state::AppState,
+    error::ApiError,
+    middleware::{auth::Claims, ClientIp},
+    response::helpers as resp,
+    session::user_agent,
+    state::AppState,
 };
 
 /// Create auth routes
@@ -24,14 +48,31 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/register", axum::routing::post(register))
         .route("/login", axum::routing::post(login))
+        .route("/totp", axum::routing::post(totp_login))
+        .route("/totp/setup", axum::routing::post(totp_setup))
+        .route("/totp/confirm", axum::routing::post(totp_confirm))
+        .route("/refresh", axum::routing::post(refresh))
         .route("/logout", axum::routing::post(logout))
         .route("/me", axum::routing::get(me))
+        .route("/email", axum::routing::put(update_email))
+        .route("/verify-email", axum::routing::get(verify_email))
+        .route("/forgot-password", axum::routing::post(forgot_password))
+        .route("/reset-password", axum::routing::post(reset_password))
+        .route("/impersonate/{user_id}", axum::routing::post(impersonate))
+}
+
+/// Query parameters of the link in verification emails
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailQuery {
+    token: String,
 }
 
 /// POST /auth/register
-/// Register a new user
+/// Register a new user and sign them in with a new session
 async fn register(
     State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     Json(input): Json<RegisterRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     validate_username(&input.username)?;
@@ -39,19 +80,12 @@ async fn register(
 
     let user = state
         .user_service
-        .register(input.username, input.password)
+        .register(input.username, input.password, input.invite_code.as_deref())
         .await
         .map_err(ApiError::Domain)?;
 
-    let token = state.auth_state.create_token(
-        user.id.to_string(),
-        user.username.clone(),
-        user.permissions,
-        None,
-    )?;
-
     let response = LoginResponse {
-        token,
+        token: issue_login_token(&state, &user, user_agent(&headers), &ip).await?,
         user: UserInfo::from(&user),
     };
 
@@ -59,31 +93,91 @@ async fn register(
 }
 
 /// POST /auth/login
-/// Login with username and password
+/// Login with username or email and password
+///
+/// Users with TOTP enabled receive a partial token instead of a JWT.
 async fn login(
     State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     Json(input): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    if input.username.trim().is_empty() || input.password.trim().is_empty() {
+    if input.username_or_email.trim().is_empty() || input.password.trim().is_empty() {
         return Err(ApiError::Validation(
-            "Username and password required".to_string(),
+            "Username or email and password required".to_string(),
         ));
     }
 
+    let result = state
+        .user_service
+        .login(input.username_or_email, input.password)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let user = match result {
+        LoginResult::Authenticated(user) => *user,
+        LoginResult::RequiresOtp(partial_token) => {
+            return Ok(resp::ok(OtpRequiredResponse {
+                requires_otp: true,
+                partial_token,
+            }));
+        }
+    };
+
+    let response = LoginResponse {
+        token: issue_login_token(&state, &user, user_agent(&headers), &ip).await?,
+        user: UserInfo::from(&user),
+    };
+
+    Ok(resp::ok(response))
+}
+
+/// POST /auth/totp
+/// Exchange a partial login token and TOTP code for a JWT
+async fn totp_login(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    Json(input): Json<TotpLoginRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = state
+        .user_service
+        .complete_totp_login(&input.partial_token, &input.code)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let response = LoginResponse {
+        token: issue_login_token(&state, &user, user_agent(&headers), &ip).await?,
+        user: UserInfo::from(&user),
+    };
+
+    Ok(resp::ok(response))
+}
+
+/// POST /auth/refresh
+/// Exchange a refresh token for a new short-lived access JWT
+async fn refresh(
+    State(state): State<AppState>,
+    Json(input): Json<RefreshTokenRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let session = state
+        .session_service
+        .refresh_session(&input.refresh_token)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    // Re-read the user so permission changes take effect on refresh
     let user = state
         .user_service
-        .login(input.username, input.password)
+        .get(session.user_id)
         .await
-        .map_err(|e| match e {
-            domain::Error::NotFound(msg) => ApiError::Unauthorized(msg),
-            _ => ApiError::Domain(e),
-        })?;
+        .map_err(|_| ApiError::Unauthorized("Invalid or expired refresh token".to_string()))?;
 
-    let token = state.auth_state.create_token(
+    let token = state.auth_state.create_access_token(
         user.id.to_string(),
         user.username.clone(),
         user.permissions,
-        None,
+        session.refresh_token,
     )?;
 
     let response = LoginResponse {
@@ -94,6 +188,45 @@ async fn login(
     Ok(resp::ok(response))
 }
 
+/// POST /auth/totp/setup
+/// Generate a TOTP secret for the current user (requires authentication)
+///
+/// Returns 409 when TOTP is already enabled.
+async fn totp_setup(
+    user: Claims,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = uuid::Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let provisioning_uri = state
+        .user_service
+        .enable_totp(user_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(TotpSetupResponse { provisioning_uri }))
+}
+
+/// POST /auth/totp/confirm
+/// Enable TOTP after verifying a code from the authenticator app
+async fn totp_confirm(
+    user: Claims,
+    State(state): State<AppState>,
+    Json(input): Json<TotpCodeRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = uuid::Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    state
+        .user_service
+        .confirm_totp(user_id, &input.code)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(serde_json::json!({ "totp_enabled": true })))
+}
+
 /// POST /auth/logout
 /// Logout (informative endpoint - actual logout is client-side)
 async fn logout() -> impl IntoResponse {
@@ -114,11 +247,135 @@ async fn me(user: Claims, State(_state): State<AppState>) -> Result<impl IntoRes
         id: user_id,
         username: user.username,
         permissions: user.permissions,
+        bio: None,
+        avatar_url: None,
     };
 
     Ok(resp::ok(user_info))
 }
 
+/// PUT /auth/email
+/// Set the current user's email address and send a verification link
+async fn update_email(
+    user: Claims,
+    State(state): State<AppState>,
+    Json(input): Json<UpdateEmailRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = uuid::Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    state
+        .user_service
+        .update_email(user_id, input.email)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(serde_json::json!({ "email_verified": false })))
+}
+
+/// GET /auth/verify-email?token=...
+/// Confirm an email address from the link in the verification email
+async fn verify_email(
+    State(state): State<AppState>,
+    Query(params): Query<VerifyEmailQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    state
+        .user_service
+        .verify_email(&params.token)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(serde_json::json!({ "email_verified": true })))
+}
+
+/// POST /auth/forgot-password
+/// Email a password reset link if the address belongs to a verified account
+async fn forgot_password(
+    State(state): State<AppState>,
+    Json(input): Json<PasswordResetRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    state
+        .user_service
+        .request_password_reset(&input.email)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(serde_json::json!({
+        "message": "If the address belongs to an account, a reset link has been sent."
+    })))
+}
+
+/// POST /auth/reset-password
+/// Set a new password using the token from a reset email
+async fn reset_password(
+    State(state): State<AppState>,
+    Json(input): Json<ResetPasswordRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    validate_password(&input.new_password)?;
+
+    state
+        .user_service
+        .reset_password(&input.token, input.new_password)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(
+        serde_json::json!({ "message": "Password has been reset." }),
+    ))
+}
+
+/// POST /auth/impersonate/{user_id}
+/// Issue a one-hour, read-only token for another user (requires USER_MANAGE)
+///
+/// Impersonating an admin additionally requires SUPER_ADMIN.
+async fn impersonate(
+    user: Claims,
+    State(state): State<AppState>,
+    Path(user_id): Path<uuid::Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let admin_id = uuid::Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let target = state
+        .user_service
+        .impersonate(user_id, admin_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let token = state.auth_state.create_impersonation_token(
+        target.id.to_string(),
+        target.username.clone(),
+        target.permissions,
+        admin_id,
+    )?;
+    tracing::info!("Admin {} is impersonating user {}", admin_id, target.id);
+
+    Ok(resp::ok(LoginResponse {
+        token,
+        user: UserInfo::from(&target),
+    }))
+}
+
+/// Create a 30-day session for the user and sign a JWT carrying its refresh token
+async fn issue_login_token(
+    state: &AppState,
+    user: &User,
+    user_agent: &str,
+    ip_address: &str,
+) -> Result<String, ApiError> {
+    let session = state
+        .session_service
+        .create_session(user.id, true, user_agent, ip_address)
+        .await?;
+
+    Ok(state.auth_state.create_token_with_refresh(
+        user.id.to_string(),
+        user.username.clone(),
+        user.permissions,
+        session.refresh_token,
+    )?)
+}
+
 // ============================================================================
 // Validation Helpers
 // ============================================================================
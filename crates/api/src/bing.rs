@@ -1,7 +1,31 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+//! Bing Daily Image API Routes
+//!
+//! ## Endpoints
+//!
+//! | Method | Endpoint | Description |
+//! |--------|----------|-------------|
+//! | GET | /bing/daily-image | Today's Bing wallpaper |
+//! | GET | /bing/history | Wallpapers cached on previous days, newest first (`?limit=7`, max 30) |
+
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::{ApiError, ApiResponse, AppState};
+use crate::{file_cache::FileCache, ApiError, ApiResponse, AppState};
+
+/// Cache key of the wallpaper currently served
+const DAILY_IMAGE_KEY: &str = "bing_daily_image";
+
+/// Prefix of the cache keys of dated wallpapers, followed by `YYYYMMDD`
+const HISTORY_KEY_PREFIX: &str = "bing_history_";
+
+/// Days of history returned when `limit` is not given
+const DEFAULT_HISTORY_LIMIT: u64 = 7;
+
+/// Most days of history returned at once
+const MAX_HISTORY_LIMIT: u64 = 30;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BingDailyImageResponse {
@@ -10,6 +34,31 @@ pub struct BingDailyImageResponse {
     pub copyright_link: Option<String>,
 }
 
+/// A wallpaper kept in the cache for the day Bing showed it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BingCacheEntry {
+    /// Day the wallpaper was shown, `YYYY-MM-DD`
+    pub date: String,
+    pub url: String,
+    pub title: String,
+    pub copyright: String,
+    /// Whether this is the wallpaper `/bing/daily-image` currently serves
+    #[serde(default)]
+    pub is_current: bool,
+}
+
+/// Query parameters for the wallpaper history
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Number of days, clamped to 1..=30
+    #[serde(default = "default_history_limit")]
+    limit: u64,
+}
+
+fn default_history_limit() -> u64 {
+    DEFAULT_HISTORY_LIMIT
+}
+
 #[derive(Debug, Deserialize)]
 struct BingImageResponse {
     images: Vec<BingImage>,
@@ -20,38 +69,85 @@ struct BingImage {
     url: String,
     copyright: String,
     copyright_link: Option<String>,
+    /// Day the image is shown, `YYYYMMDD`
+    #[serde(default)]
+    startdate: String,
+    #[serde(default)]
+    title: String,
 }
 
 pub fn routes() -> axum::Router<AppState> {
-    axum::Router::new().route("/daily-image", axum::routing::get(get_bing_daily_image))
+    axum::Router::new()
+        .route("/daily-image", axum::routing::get(get_bing_daily_image))
+        .route("/history", axum::routing::get(get_bing_history))
 }
 
 pub async fn get_bing_daily_image(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let cache_key = "bing_daily_image";
-
-    if state.bing_cache.is_valid(cache_key, 12).await {
+    if state.bing_cache.is_valid(DAILY_IMAGE_KEY, 12).await {
         tracing::debug!("Returning cached Bing image from file");
 
         if let Ok(Some(cached)) = state
             .bing_cache
-            .get::<BingDailyImageResponse>(cache_key)
+            .get::<BingDailyImageResponse>(DAILY_IMAGE_KEY)
             .await
         {
-            return Ok((StatusCode::OK, Json(ApiResponse::success(cached))).into_response());
+            return Ok(ApiResponse::ok(cached));
         }
     }
 
     tracing::info!("Cache expired or empty, fetching from Bing API");
-    let data = fetch_bing_image_from_api().await?;
+    let (data, entry) = fetch_bing_image_from_api().await?;
 
-    state.bing_cache.set(cache_key, &data).await?;
+    store_bing_image(&state.bing_cache, &data, entry.as_ref()).await?;
 
-    Ok((StatusCode::OK, Json(ApiResponse::success(data))).into_response())
+    Ok(ApiResponse::ok(data))
 }
 
-async fn fetch_bing_image_from_api() -> Result<BingDailyImageResponse, ApiError> {
+/// GET /bing/history
+/// Wallpapers of the last `limit` days that were cached, newest first
+pub async fn get_bing_history(
+    State(state): State<AppState>,
+    Query(params): Query<HistoryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let entries = bing_history(&state.bing_cache, params.limit).await?;
+    Ok(ApiResponse::ok(entries))
+}
+
+/// Cached wallpapers, newest first, marking the one currently served
+async fn bing_history(cache: &FileCache, limit: u64) -> Result<Vec<BingCacheEntry>, ApiError> {
+    let limit = limit.clamp(1, MAX_HISTORY_LIMIT);
+    let current_url = cache
+        .get::<BingDailyImageResponse>(DAILY_IMAGE_KEY)
+        .await?
+        .map(|current| current.url);
+
+    let mut entries: Vec<BingCacheEntry> = cache.list_entries(HISTORY_KEY_PREFIX, limit).await?;
+    for entry in &mut entries {
+        entry.is_current = current_url.as_deref() == Some(entry.url.as_str());
+    }
+    Ok(entries)
+}
+
+/// Save the wallpaper as the one currently served and, when Bing gave its
+/// date, as that day's history entry
+async fn store_bing_image(
+    cache: &FileCache,
+    data: &BingDailyImageResponse,
+    entry: Option<&BingCacheEntry>,
+) -> Result<(), ApiError> {
+    cache.set(DAILY_IMAGE_KEY, data).await?;
+    if let Some(entry) = entry {
+        let key = format!("{}{}", HISTORY_KEY_PREFIX, entry.date.replace('-', ""));
+        cache.set(&key, entry).await?;
+    }
+    Ok(())
+}
+
+/// Fetch today's wallpaper, with its history entry when Bing gave its date
+async fn fetch_bing_image_from_api(
+) -> Result<(BingDailyImageResponse, Option<BingCacheEntry>), ApiError> {
     let client = reqwest::Client::builder()
         .build()
         .map_err(|e| ApiError::internal(format!("Failed to create HTTP client: {}", e)))?;
@@ -87,11 +183,24 @@ async fn fetch_bing_image_from_api() -> Result<BingDailyImageResponse, ApiError>
         format!("https://www.bing.com{}", bing_image.url)
     };
 
-    Ok(BingDailyImageResponse {
-        url: full_url,
-        copyright: bing_image.copyright.clone(),
-        copyright_link: bing_image.copyright_link.clone(),
-    })
+    let entry = chrono::NaiveDate::parse_from_str(&bing_image.startdate, "%Y%m%d")
+        .ok()
+        .map(|date| BingCacheEntry {
+            date: date.format("%Y-%m-%d").to_string(),
+            url: full_url.clone(),
+            title: bing_image.title.clone(),
+            copyright: bing_image.copyright.clone(),
+            is_current: false,
+        });
+
+    Ok((
+        BingDailyImageResponse {
+            url: full_url,
+            copyright: bing_image.copyright.clone(),
+            copyright_link: bing_image.copyright_link.clone(),
+        },
+        entry,
+    ))
 }
 
 pub async fn start_bing_cache_refresh_task(state: AppState) {
@@ -102,9 +211,9 @@ pub async fn start_bing_cache_refresh_task(state: AppState) {
             tracing::info!("Refreshing Bing daily image cache");
 
             match fetch_bing_image_from_api().await {
-                Ok(data) => {
-                    let cache_key = "bing_daily_image";
-                    if let Err(e) = state.bing_cache.set(cache_key, &data).await {
+                Ok((data, entry)) => {
+                    if let Err(e) = store_bing_image(&state.bing_cache, &data, entry.as_ref()).await
+                    {
                         tracing::error!("Failed to save Bing cache: {}", e);
                     } else {
                         tracing::info!("Bing daily image cache refreshed successfully");
@@ -117,3 +226,52 @@ pub async fn start_bing_cache_refresh_task(state: AppState) {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(date: &str) -> BingCacheEntry {
+        BingCacheEntry {
+            date: date.to_string(),
+            url: format!("https://www.bing.com/th?id={}.jpg", date),
+            title: format!("Wallpaper of {}", date),
+            copyright: "© Photographer".to_string(),
+            is_current: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_lists_cached_days_newest_first() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("test_bing_history_{}", uuid::Uuid::new_v4()));
+        let cache = FileCache::new(&temp_dir).unwrap();
+        cache.initialize().await.unwrap();
+
+        for date in ["2025-10-14", "2025-10-16", "2025-10-15"] {
+            let today = BingDailyImageResponse {
+                url: entry(date).url,
+                copyright: entry(date).copyright,
+                copyright_link: None,
+            };
+            store_bing_image(&cache, &today, Some(&entry(date)))
+                .await
+                .unwrap();
+        }
+        cache.set("unrelated", &"data").await.unwrap();
+
+        let history = bing_history(&cache, 7).await.unwrap();
+        let dates: Vec<&str> = history.iter().map(|e| e.date.as_str()).collect();
+        assert_eq!(dates, ["2025-10-16", "2025-10-15", "2025-10-14"]);
+        assert_eq!(history[1].title, "Wallpaper of 2025-10-15");
+        // 2025-10-15 was stored last, so it is the image currently served
+        let current: Vec<bool> = history.iter().map(|e| e.is_current).collect();
+        assert_eq!(current, [false, true, false]);
+
+        let latest = bing_history(&cache, 2).await.unwrap();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].date, "2025-10-16");
+
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+}
@@ -1,25 +1,28 @@
 //! Category API Routes
 //!
 //! This module provides HTTP handlers for category management.
-//! Categories are managed by admins and used to organize posts.
+//! Categories are managed by users with `CATEGORY_MANAGE` and used to organize posts.
 //!
 //! ## Endpoints
 //!
 //! | Method | Endpoint | Description |
 //! |--------|----------|-------------|
 //! | GET | /categories | List all categories |
-//! | POST | /categories | Create category (admin) |
+//! | POST | /categories | Create category (category manager) |
+//! | GET | /categories/tree | Get all categories nested under their parents |
 //! | GET | /categories/{id} | Get category details |
 //! | GET | /categories/{id}/posts | Get posts in category |
-//! | PATCH | /categories/{id} | Update category (admin) |
-//! | DELETE | /categories/{id} | Delete category (admin) |
+//! | GET | /categories/{id}/breadcrumb | Get ancestor chain, root first |
+//! | GET | /categories/{id}/feed.xml | RSS feed of the category's posts (`?recursive=true` to include subcategories, cached for 1 hour) |
+//! | PATCH | /categories/{id} | Update category (category manager) |
+//! | DELETE | /categories/{id} | Delete category (category manager) |
 
 use axum::{
     extract::{Path, Query, State},
     response::IntoResponse,
     Json, Router,
 };
-use domain::{CreateCategory, UpdateCategory, USER_MANAGE};
+use domain::{CreateCategory, UpdateCategory};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -63,8 +66,14 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         // Public routes
         .route("/", axum::routing::get(list_categories))
+        .route("/tree", axum::routing::get(get_category_tree))
         .route("/{id}", axum::routing::get(get_category))
         .route("/{id}/posts", axum::routing::get(list_category_posts))
+        .route("/{id}/breadcrumb", axum::routing::get(get_breadcrumb))
+        .route(
+            "/{id}/feed.xml",
+            axum::routing::get(crate::rss::get_category_feed),
+        )
         // Admin routes
         .route("/", axum::routing::post(create_category))
         .route("/{id}", axum::routing::patch(update_category))
@@ -91,18 +100,18 @@ async fn list_categories(
 }
 
 /// POST /categories
-/// Create a new category (admin only)
+/// Create a new category (requires CATEGORY_MANAGE)
 async fn create_category(
     State(state): State<AppState>,
     user: Claims,
     Json(input): Json<CreateCategory>,
 ) -> Result<impl IntoResponse, ApiError> {
-    domain::check_permission(user.permissions, USER_MANAGE)
-        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
 
     let category = state
         .category_service
-        .create(input)
+        .create(user_id, input)
         .await
         .map_err(ApiError::Domain)?;
 
@@ -124,6 +133,33 @@ async fn get_category(
     Ok(resp::ok(category))
 }
 
+/// GET /categories/tree
+/// Get the whole category hierarchy, for rendering a nested sidebar
+async fn get_category_tree(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
+    let tree = state
+        .category_service
+        .get_tree()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(tree))
+}
+
+/// GET /categories/{id}/breadcrumb
+/// Get the category's ancestor chain for navigation, root first
+async fn get_breadcrumb(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let breadcrumb = state
+        .category_service
+        .get_breadcrumb(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(breadcrumb))
+}
+
 /// GET /categories/{id}/posts
 /// Get posts in a category
 async fn list_category_posts(
@@ -133,7 +169,7 @@ async fn list_category_posts(
 ) -> Result<impl IntoResponse, ApiError> {
     let posts = state
         .post_service
-        .list_by_category(id, Some(params.per_page))
+        .list_by_category(id, Some(params.per_page), false)
         .await
         .map_err(ApiError::Domain)?;
 
@@ -145,19 +181,19 @@ async fn list_category_posts(
 }
 
 /// PATCH /categories/{id}
-/// Update a category (admin only)
+/// Update a category (requires CATEGORY_MANAGE)
 async fn update_category(
     State(state): State<AppState>,
     user: Claims,
     Path(id): Path<Uuid>,
     Json(input): Json<UpdateCategory>,
 ) -> Result<impl IntoResponse, ApiError> {
-    domain::check_permission(user.permissions, USER_MANAGE)
-        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
 
     let category = state
         .category_service
-        .update(id, input)
+        .update(user_id, id, input)
         .await
         .map_err(ApiError::Domain)?;
 
@@ -165,18 +201,18 @@ async fn update_category(
 }
 
 /// DELETE /categories/{id}
-/// Delete a category (admin only)
+/// Delete a category (requires CATEGORY_MANAGE)
 async fn delete_category(
     State(state): State<AppState>,
     user: Claims,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    domain::check_permission(user.permissions, USER_MANAGE)
-        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
 
     state
         .category_service
-        .delete(id)
+        .delete(user_id, id)
         .await
         .map_err(ApiError::Domain)?;
 
@@ -10,15 +10,19 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::{IntoResponse, Json, Redirect},
     Router,
 };
-use domain::{CreateComment, CreateCommentGitHub};
+use domain::{AddReactionRequest, CreateComment, CreateCommentGitHub};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::{error::ApiError, middleware::auth::Claims, state::AppState};
+use crate::{
+    error::ApiError,
+    middleware::auth::{Claims, OptionalClaims},
+    response::helpers as resp,
+    state::AppState,
+};
 
 // ============================================================================
 // Routes
@@ -34,6 +38,19 @@ pub fn routes() -> Router<AppState> {
         .route("/github", axum::routing::post(create_comment_github))
         // GET /api/comments/posts/{id} - Get comments for a post
         .route("/posts/{id}", axum::routing::get(list_post_comments))
+        // GET /api/comments/pending - Moderation queue (moderator)
+        .route("/pending", axum::routing::get(list_pending_comments))
+        // POST /api/comments/{id}/approve - Approve comment (moderator)
+        .route("/{id}/approve", axum::routing::post(approve_comment))
+        // POST /api/comments/{id}/reject - Reject comment (moderator)
+        .route("/{id}/reject", axum::routing::post(reject_comment))
+        // POST /api/comments/{id}/reactions - React with an emoji
+        .route("/{id}/reactions", axum::routing::post(add_reaction))
+        // DELETE /api/comments/{id}/reactions/{type} - Take back a reaction
+        .route(
+            "/{id}/reactions/{reaction_type}",
+            axum::routing::delete(remove_reaction),
+        )
         // POST /api/comments - Create comment (registered user)
         .route("/", axum::routing::post(create_comment))
         // GET /api/comments/{id} - Get single comment
@@ -68,13 +85,10 @@ pub async fn github_auth_url(State(state): State<AppState>) -> Result<impl IntoR
         .comment_service
         .github_auth_url(&state_param, &redirect_uri);
 
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({
-            "auth_url": auth_url,
-            "state": state_param
-        })),
-    ))
+    Ok(resp::ok(serde_json::json!({
+        "auth_url": auth_url,
+        "state": state_param
+    })))
 }
 
 #[derive(Deserialize)]
@@ -130,12 +144,13 @@ pub async fn github_callback(
         .ok_or_else(|| ApiError::Validation("Missing authorization code".to_string()))?;
 
     // Exchange code for access token
+    let github = state.config.read().unwrap().github.clone();
     let client = Client::new();
     let token_response: GitHubTokenResponse = client
         .post("https://github.com/login/oauth/access_token")
         .form(&[
-            ("client_id", &state.config.github.client_id),
-            ("client_secret", &state.config.github.client_secret),
+            ("client_id", &github.client_id),
+            ("client_secret", &github.client_secret),
             ("code", &code),
         ])
         .header("Accept", "application/json")
@@ -213,7 +228,7 @@ pub async fn create_comment(
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((StatusCode::CREATED, Json(response)))
+    Ok(resp::created(response))
 }
 
 /// POST /api/comments/github
@@ -240,32 +255,178 @@ pub async fn create_comment_github(
             _ => ApiError::Domain(e),
         })?;
 
-    Ok((StatusCode::CREATED, Json(response)))
+    Ok(resp::created(response))
 }
 
 /// GET /api/comments/posts/:id?limit=50
-/// Get comments for a specific post
+/// Get comment threads for a specific post
 ///
 /// Query parameters:
 /// - limit: Maximum number of comments to return (default: 50)
 ///
 /// This endpoint is public - no authentication required.
+/// Anonymous users and non-moderators only see approved comments.
 pub async fn list_post_comments(
     State(state): State<AppState>,
+    OptionalClaims(user): OptionalClaims,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let post_id = Uuid::parse_str(&id)
         .map_err(|e| ApiError::Validation(format!("Invalid post ID: {}", e)))?;
 
     let limit = 50; // Default limit
+    let viewer_id = user.and_then(|u| Uuid::parse_str(&u.sub).ok());
 
     let comments = state
         .comment_service
-        .list_post_comments(post_id, limit)
+        .list_post_comments_threaded(post_id, limit, viewer_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(comments))
+}
+
+/// GET /api/comments/pending
+/// List comments awaiting moderation
+///
+/// Requires JWT authentication with COMMENT_MODERATE permission.
+pub async fn list_pending_comments(
+    user: Claims,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limit = 100; // Default limit
+    let moderator_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let comments = state
+        .comment_service
+        .list_pending_comments(moderator_id, limit)
+        .await
+        .map_err(|e| match e {
+            domain::Error::Validation(msg) => ApiError::Unauthorized(msg),
+            _ => ApiError::Domain(e),
+        })?;
+
+    Ok(resp::ok(comments))
+}
+
+/// POST /api/comments/:id/approve
+/// Approve a pending comment
+///
+/// Requires JWT authentication with COMMENT_MODERATE permission.
+pub async fn approve_comment(
+    user: Claims,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let comment_id = Uuid::parse_str(&id)
+        .map_err(|e| ApiError::Validation(format!("Invalid comment ID: {}", e)))?;
+    let moderator_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let response = state
+        .comment_service
+        .approve_comment(comment_id, moderator_id)
+        .await
+        .map_err(|e| match e {
+            domain::Error::NotFound(msg) => ApiError::NotFound(msg),
+            domain::Error::Validation(msg) => ApiError::Unauthorized(msg),
+            _ => ApiError::Domain(e),
+        })?;
+
+    Ok(resp::ok(response))
+}
+
+/// POST /api/comments/:id/reject
+/// Reject a pending comment
+///
+/// Requires JWT authentication with COMMENT_MODERATE permission.
+pub async fn reject_comment(
+    user: Claims,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let comment_id = Uuid::parse_str(&id)
+        .map_err(|e| ApiError::Validation(format!("Invalid comment ID: {}", e)))?;
+    let moderator_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let response = state
+        .comment_service
+        .reject_comment(comment_id, moderator_id)
+        .await
+        .map_err(|e| match e {
+            domain::Error::NotFound(msg) => ApiError::NotFound(msg),
+            domain::Error::Validation(msg) => ApiError::Unauthorized(msg),
+            _ => ApiError::Domain(e),
+        })?;
+
+    Ok(resp::ok(response))
+}
+
+/// POST /api/comments/:id/reactions
+/// React to a comment with an emoji
+///
+/// Request body:
+/// - reaction_type: One of 👍 👎 ❤️ 🎉 😕 🚀 👀
+///
+/// Reacting twice with the same emoji is a no-op. Responds with the
+/// updated reaction counts. Requires JWT authentication.
+pub async fn add_reaction(
+    user: Claims,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(input): Json<AddReactionRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let comment_id = Uuid::parse_str(&id)
+        .map_err(|e| ApiError::Validation(format!("Invalid comment ID: {}", e)))?;
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    state
+        .comment_service
+        .add_reaction(comment_id, user_id, &input.reaction_type)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let reactions = state
+        .comment_service
+        .get_reactions(comment_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(reactions))
+}
+
+/// DELETE /api/comments/:id/reactions/:type
+/// Take back a reaction
+///
+/// The emoji in the path must be percent-encoded. Removing a reaction that
+/// is not there succeeds. Responds with the updated reaction counts.
+/// Requires JWT authentication.
+pub async fn remove_reaction(
+    user: Claims,
+    State(state): State<AppState>,
+    Path((id, reaction_type)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let comment_id = Uuid::parse_str(&id)
+        .map_err(|e| ApiError::Validation(format!("Invalid comment ID: {}", e)))?;
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    state
+        .comment_service
+        .remove_reaction(comment_id, user_id, &reaction_type)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let reactions = state
+        .comment_service
+        .get_reactions(comment_id)
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((StatusCode::OK, Json(comments)))
+    Ok(resp::ok(reactions))
 }
 
 /// GET /api/comments/:id
@@ -286,7 +447,7 @@ pub async fn get_comment(
         .map_err(ApiError::Domain)?;
 
     match response {
-        Some(comment) => Ok((StatusCode::OK, Json(comment))),
+        Some(comment) => Ok(resp::ok(comment)),
         None => Err(ApiError::validation("Comment not found".to_string())),
     }
 }
@@ -329,7 +490,7 @@ pub async fn update_comment(
             _ => ApiError::Domain(e),
         })?;
 
-    Ok((StatusCode::OK, Json(response)))
+    Ok(resp::ok(response))
 }
 
 /// DELETE /api/comments/:id
@@ -360,8 +521,7 @@ pub async fn delete_comment(
             _ => ApiError::Domain(e),
         })?;
 
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({ "message": "Comment deleted successfully" })),
+    Ok(resp::ok(
+        serde_json::json!({ "message": "Comment deleted successfully" }),
     ))
 }
@@ -1,11 +1,10 @@
 use crate::middleware::auth::AuthError;
+use crate::response::ApiResponse;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    Json,
 };
-use domain::Error as DomainError;
-use serde_json::json;
+use domain::{Error as DomainError, ErrorCode};
 use thiserror::Error;
 
 /// API error types
@@ -27,6 +26,10 @@ pub enum ApiError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    /// Request body larger than the route accepts
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
     /// Internal server errors
     #[error("Internal error: {0}")]
     Internal(String),
@@ -60,38 +63,47 @@ impl ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, error_type, message) = match self {
-            ApiError::Domain(DomainError::NotFound(msg)) => {
-                (StatusCode::NOT_FOUND, "not_found", msg)
-            }
-            ApiError::Domain(DomainError::Validation(msg)) => {
-                (StatusCode::BAD_REQUEST, "validation", msg)
-            }
-            ApiError::Domain(DomainError::Unauthorized(msg)) => {
-                (StatusCode::UNAUTHORIZED, "unauthorized", msg)
-            }
-            ApiError::Domain(DomainError::Conflict(msg)) => (StatusCode::CONFLICT, "conflict", msg),
-            ApiError::Domain(DomainError::Internal(msg)) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, "internal", msg)
+        let err = match self {
+            ApiError::Domain(err) => err,
+            ApiError::Validation(msg) => DomainError::Validation(msg),
+            ApiError::NotFound(msg) => DomainError::NotFound(msg),
+            ApiError::Unauthorized(msg) => DomainError::Unauthorized(msg),
+            ApiError::Internal(msg) => DomainError::Internal(msg),
+            ApiError::PayloadTooLarge(msg) => {
+                return ApiResponse::error(
+                    StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+                    "payload_too_large",
+                    msg,
+                )
+                .into_response();
             }
-            ApiError::Validation(msg) => (StatusCode::BAD_REQUEST, "validation", msg),
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, "not_found", msg),
-            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "unauthorized", msg),
-            ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "internal", msg),
             ApiError::Auth(auth_err) => {
                 // Let AuthError handle its own response
                 return auth_err.into_response();
             }
         };
 
-        let body = json!({
-            "error": {
-                "type": error_type,
-                "message": message
-            }
-        });
+        let code = err.code();
+        let (status, error_type) = code_status(code);
+        ApiResponse::error(status.as_u16(), error_type, err.message())
+            .with_error_code(code.as_str(), err.field().map(str::to_string))
+            .into_response()
+    }
+}
 
-        (status, Json(body)).into_response()
+/// HTTP status and error type reported for an error code
+fn code_status(code: ErrorCode) -> (StatusCode, &'static str) {
+    match code {
+        ErrorCode::NotFound
+        | ErrorCode::PostNotFound
+        | ErrorCode::UserNotFound
+        | ErrorCode::CommentNotFound => (StatusCode::NOT_FOUND, "not_found"),
+        ErrorCode::PermissionDenied | ErrorCode::ValidationFailed | ErrorCode::UsernameExists => {
+            (StatusCode::BAD_REQUEST, "validation")
+        }
+        ErrorCode::Unauthorized => (StatusCode::UNAUTHORIZED, "unauthorized"),
+        ErrorCode::Conflict => (StatusCode::CONFLICT, "conflict"),
+        ErrorCode::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
     }
 }
 
@@ -109,3 +121,80 @@ impl From<jsonwebtoken::errors::Error> for ApiError {
         Self::Internal(format!("JWT error: {}", err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    async fn error_body(err: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = err.into_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_coded_errors_report_code_and_field() {
+        let cases = [
+            (
+                DomainError::coded(ErrorCode::PostNotFound, "Post not found"),
+                StatusCode::NOT_FOUND,
+                "POST_NOT_FOUND",
+                None,
+            ),
+            (
+                DomainError::coded(ErrorCode::UserNotFound, "User not found"),
+                StatusCode::NOT_FOUND,
+                "USER_NOT_FOUND",
+                None,
+            ),
+            (
+                DomainError::coded(ErrorCode::PermissionDenied, "Permission denied"),
+                StatusCode::BAD_REQUEST,
+                "PERMISSION_DENIED",
+                None,
+            ),
+            (
+                DomainError::invalid_field("title", "Title cannot be empty"),
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_FAILED",
+                Some("title"),
+            ),
+            (
+                DomainError::coded(ErrorCode::UsernameExists, "Username already exists")
+                    .with_field("username"),
+                StatusCode::BAD_REQUEST,
+                "USERNAME_EXISTS",
+                Some("username"),
+            ),
+        ];
+
+        for (err, expected_status, code, field) in cases {
+            let message = err.message().to_string();
+            let (status, body) = error_body(ApiError::Domain(err)).await;
+
+            assert_eq!(status, expected_status, "{}", code);
+            assert_eq!(body["error"]["code"], code);
+            assert_eq!(body["error"]["message"], message);
+            assert_eq!(body["error"]["field"].as_str(), field, "{}", code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_uncoded_errors_derive_code_from_kind() {
+        let (status, body) =
+            error_body(ApiError::Domain(DomainError::not_found("Tag not found"))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["type"], "not_found");
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+
+        let (status, body) = error_body(ApiError::validation("Invalid post ID")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "VALIDATION_FAILED");
+        assert!(body["error"].get("field").is_none());
+
+        let (_, body) = error_body(ApiError::PayloadTooLarge("Too big".to_string())).await;
+        assert_eq!(body["error"]["code"], "PAYLOAD_TOO_LARGE");
+    }
+}
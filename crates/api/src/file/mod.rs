@@ -11,14 +11,19 @@
 
 use axum::{
     extract::{Path, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::IntoResponse,
-    Json, Router,
+    Router,
 };
 use domain::UploadFile;
 use uuid::Uuid;
 
-use crate::{error::ApiError, middleware::auth::Claims, state::AppState};
+use crate::{
+    error::ApiError,
+    middleware::{auth::Claims, etag},
+    response::helpers as resp,
+    state::AppState,
+};
 
 // ============================================================================
 // Routes
@@ -32,8 +37,15 @@ pub fn routes() -> Router<AppState> {
         // GET /api/files/download/{filename} - Download file by filename (MUST be before /{id})
         .route(
             "/download/{filename}",
-            axum::routing::get(download_file_by_name),
+            axum::routing::get(download_file_by_name).layer(axum::middleware::from_fn(etag)),
+        )
+        // GET /api/files/by-name/{filename} - File metadata by stored filename
+        .route(
+            "/by-name/{filename}",
+            axum::routing::get(get_file_by_filename),
         )
+        // GET /api/files/{id}/download - Download file by ID, with range support
+        .route("/{id}/download", axum::routing::get(download_file))
         // DELETE /api/files/{id} - Delete a file
         .route("/{id}", axum::routing::delete(delete_file))
 }
@@ -87,7 +99,7 @@ pub async fn upload_file(
                 .await
                 .map_err(ApiError::Domain)?;
 
-            return Ok((StatusCode::CREATED, Json(response)));
+            return Ok(resp::created(response));
         }
     }
 
@@ -110,11 +122,29 @@ pub async fn get_file(
         .map_err(ApiError::Domain)?;
 
     match response {
-        Some(file) => Ok((StatusCode::OK, Json(file))),
+        Some(file) => Ok(resp::ok(file)),
         None => Err(ApiError::NotFound("File not found".to_string())),
     }
 }
 
+/// GET /api/files/by-name/:filename
+/// Get file metadata by the name the file is stored under
+///
+/// Lets editors resolve file URLs in post content back to their records.
+pub async fn get_file_by_filename(
+    _user: Claims,
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let file = state
+        .file_service
+        .get_file_by_filename(&filename)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(file))
+}
+
 /// GET /api/files/download/:filename
 /// Download file by filename (for use in markdown content)
 pub async fn download_file_by_name(
@@ -130,6 +160,41 @@ pub async fn download_file_by_name(
     Ok(([(header::CONTENT_TYPE, "image/jpeg")], file_content))
 }
 
+/// GET /api/files/:id/download
+/// Download file by ID, honoring `Range: bytes=...` requests
+pub async fn download_file(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let file_id = Uuid::parse_str(&id)
+        .map_err(|e| ApiError::Validation(format!("Invalid file ID: {}", e)))?;
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+
+    let file = state
+        .file_service
+        .serve_file(file_id, range)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let status = StatusCode::from_u16(file.status).unwrap_or(StatusCode::OK);
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Ok(content_type) = HeaderValue::from_str(&file.content_type) {
+        response_headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    if let Some(content_range) = file
+        .content_range
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        response_headers.insert(header::CONTENT_RANGE, content_range);
+    }
+
+    Ok((status, response_headers, file.data))
+}
+
 /// GET /api/files?limit=50
 /// List files uploaded by the current user
 pub async fn list_files(
@@ -147,8 +212,7 @@ pub async fn list_files(
         .await
         .map_err(ApiError::Domain)?;
 
-    use crate::response::helpers;
-    Ok(helpers::ok(files))
+    Ok(resp::ok(files))
 }
 
 /// DELETE /api/files/:id
@@ -173,8 +237,7 @@ pub async fn delete_file(
             _ => ApiError::Domain(e),
         })?;
 
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({ "message": "File deleted successfully" })),
+    Ok(resp::ok(
+        serde_json::json!({ "message": "File deleted successfully" }),
     ))
 }
@@ -141,6 +141,59 @@ impl FileCache {
         !age.num_hours().ge(&max_age_hours)
     }
 
+    /// Retrieve the entries whose keys start with `prefix`, highest key first
+    ///
+    /// Keys ending in a date such as `20250101` therefore come newest first.
+    /// At most `limit` entries are read; files that cannot be deserialized
+    /// are skipped.
+    pub async fn list_entries<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+        limit: u64,
+    ) -> Result<Vec<T>, ApiError> {
+        let mut dir = match fs::read_dir(&self.cache_dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(ApiError::internal(format!(
+                    "Failed to read cache directory: {}",
+                    e
+                )))
+            }
+        };
+
+        let mut keys = Vec::new();
+        while let Some(entry) = dir
+            .next_entry()
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to read cache entry: {}", e)))?
+        {
+            let file_name = entry.file_name();
+            let Some(key) = file_name
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+                .filter(|key| key.starts_with(prefix))
+            else {
+                continue;
+            };
+            keys.push(key.to_string());
+        }
+        keys.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut entries = Vec::new();
+        for key in keys
+            .into_iter()
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        {
+            match self.get(&key).await {
+                Ok(Some(entry)) => entries.push(entry),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Skipping cache entry {}: {}", key, e),
+            }
+        }
+        Ok(entries)
+    }
+
     /// Delete cache file
     ///
     /// # Arguments
@@ -0,0 +1,72 @@
+//! Health Check Endpoint
+//!
+//! `GET /health` reports whether the server and its database are up. It
+//! needs no authentication so load balancers and monitors can poll it.
+//!
+//! The response is `200 OK` while the database answers and
+//! `503 Service Unavailable` otherwise, so probes can rely on the status
+//! code alone.
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use serde::Serialize;
+
+use crate::{response::ApiResponse, state::AppState};
+
+/// Health check response body
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    /// "ok" when every dependency is reachable, otherwise "degraded"
+    pub status: &'static str,
+    /// "ok" or "error"
+    pub database: &'static str,
+    pub uptime_seconds: u64,
+    pub version: &'static str,
+}
+
+/// GET /health
+/// Ping the database and report service status
+pub async fn get_health(State(state): State<AppState>) -> impl IntoResponse {
+    let database_ok = state.health_service.database_ok().await;
+    health_response(database_ok, state.start_time.elapsed().as_secs())
+}
+
+/// Build the response for a health check result
+fn health_response(database_ok: bool, uptime_seconds: u64) -> ApiResponse<HealthResponse> {
+    let (status_code, status, database) = if database_ok {
+        (StatusCode::OK, "ok", "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "degraded", "error")
+    };
+
+    ApiResponse::custom(
+        status_code.as_u16(),
+        status,
+        HealthResponse {
+            status,
+            database,
+            uptime_seconds,
+            version: env!("CARGO_PKG_VERSION"),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_failure_returns_503() {
+        let response = health_response(false, 42);
+        assert_eq!(response.code, StatusCode::SERVICE_UNAVAILABLE.as_u16());
+        let body = response.data.unwrap();
+        assert_eq!(body.status, "degraded");
+        assert_eq!(body.database, "error");
+        assert_eq!(body.uptime_seconds, 42);
+
+        let response = health_response(true, 42);
+        assert_eq!(response.code, StatusCode::OK.as_u16());
+        let body = response.data.unwrap();
+        assert_eq!(body.status, "ok");
+        assert_eq!(body.version, env!("CARGO_PKG_VERSION"));
+    }
+}
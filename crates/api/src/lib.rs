@@ -4,6 +4,7 @@
 //! It uses Axum as the web framework and handles authentication,
 //! request validation, and response formatting.
 
+pub mod admin;
 pub mod auth;
 pub mod bing;
 pub mod category;
@@ -12,11 +13,14 @@ pub mod config;
 pub mod error;
 pub mod file;
 pub mod file_cache;
+pub mod health;
+pub mod metrics;
 pub mod middleware;
 pub mod post;
 pub mod response;
 pub mod rss;
 pub mod session;
+pub mod sitemap;
 pub mod state;
 pub mod stats;
 pub mod tag;
@@ -24,7 +28,10 @@ pub mod user;
 
 // Re-export commonly used types for convenience
 pub use error::{ApiError, ApiResult};
-pub use response::{helpers as resp, ApiResponse, ErrorResponse, Pagination, SuccessResponse};
+pub use response::{
+    helpers as resp, ApiErrorBody, ApiResponse, ErrorResponse, Pagination, ResponseMeta,
+    SuccessResponse,
+};
 pub use state::AppState;
 
 // Re-export middleware types
@@ -39,6 +46,9 @@ pub use middleware::auth::{AuthState, Claims};
 /// This function encapsulates routing structure, keeping main.rs clean.
 /// Routes are organized by resource type: auth, posts, users, sessions, files, comments, stats, categories, and tags.
 ///
+/// JSON endpoints are limited to a 1 MB request body, upload endpoints to
+/// `storage.max_file_size_mb` (see [`middleware::body_limit`]).
+///
 /// Returns a router that requires application state to be provided via `with_state()`.
 ///
 /// # Example
@@ -49,17 +59,33 @@ pub use middleware::auth::{AuthState, Claims};
 ///     .with_state(app_state);
 /// ```
 pub fn routes() -> axum::Router<AppState> {
+    let uploads = axum::Router::new()
+        .nest("/files", file::routes())
+        .nest("/posts", post::upload_routes())
+        .layer(axum::middleware::from_fn(middleware::limit_upload_body));
+
     axum::Router::new()
         .nest("/auth", auth::routes())
         .nest("/posts", post::routes())
         .nest("/users", user::routes())
         .nest("/sessions", session::routes())
-        .nest("/files", file::routes())
         .nest("/comments", comment::routes())
         .nest("/stats", stats::routes())
         .nest("/categories", category::routes())
         .nest("/tags", tag::routes())
         .nest("/bing", bing::routes())
         .nest("/config", config::routes())
+        .nest("/admin", admin::routes())
+        .route("/health", axum::routing::get(health::get_health))
         .route("/rss", axum::routing::get(rss::get_rss_feed))
+        .route(
+            "/sitemap.xml",
+            axum::routing::get(sitemap::get_sitemap_index),
+        )
+        .route(
+            "/sitemap-{file}",
+            axum::routing::get(sitemap::get_sitemap_page),
+        )
+        .layer(axum::middleware::from_fn(middleware::limit_json_body))
+        .merge(uploads)
 }
@@ -0,0 +1,114 @@
+//! Prometheus Metrics Endpoint
+//!
+//! `GET /metrics` serves every registered metric in the Prometheus text
+//! exposition format. It is mounted at the server root, outside `/api`.
+//!
+//! Scrapes are allowed from the loopback interface, or from anywhere with
+//! `Authorization: Bearer <server.metrics_token>`. Requests relayed by a
+//! local reverse proxy carry `X-Forwarded-For`/`X-Real-IP` and therefore
+//! need the token too.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use std::net::SocketAddr;
+
+use crate::response::ApiResponse;
+use crate::state::AppState;
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// GET /metrics
+/// Refresh the active session gauge and render all metrics
+pub async fn get_metrics(State(state): State<AppState>, request: Request) -> Response {
+    let token = state.config.read().unwrap().server.metrics_token.clone();
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    if !is_authorized(peer, request.headers(), token.as_deref()) {
+        return ApiResponse::error(
+            StatusCode::UNAUTHORIZED.as_u16(),
+            "unauthorized",
+            "Unauthorized",
+        )
+        .into_response();
+    }
+
+    match state.session_service.count_active().await {
+        Ok(count) => infrastructure::metrics::set_active_sessions(count),
+        Err(e) => tracing::warn!("Failed to count active sessions: {}", e),
+    }
+
+    (
+        [(CONTENT_TYPE, CONTENT_TYPE_TEXT)],
+        infrastructure::metrics::gather(),
+    )
+        .into_response()
+}
+
+/// Check if a scrape comes from localhost or carries the metrics token
+fn is_authorized(peer: Option<SocketAddr>, headers: &HeaderMap, token: Option<&str>) -> bool {
+    let proxied = headers.contains_key("X-Forwarded-For") || headers.contains_key("X-Real-IP");
+    if !proxied && peer.is_some_and(|addr| addr.ip().is_loopback()) {
+        return true;
+    }
+
+    let Some(token) = token.filter(|token| !token.is_empty()) else {
+        return false;
+    };
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|bearer| constant_time_eq(bearer.as_bytes(), token.as_bytes()))
+}
+
+/// Compare two byte strings without short-circuiting on the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn addr(ip: [u8; 4]) -> Option<SocketAddr> {
+        Some(SocketAddr::from((ip, 40000)))
+    }
+
+    #[test]
+    fn test_localhost_is_allowed_unless_proxied() {
+        let mut headers = HeaderMap::new();
+        assert!(is_authorized(addr([127, 0, 0, 1]), &headers, None));
+        assert!(!is_authorized(addr([203, 0, 113, 7]), &headers, None));
+        assert!(!is_authorized(None, &headers, None));
+
+        headers.insert("X-Forwarded-For", HeaderValue::from_static("203.0.113.7"));
+        assert!(!is_authorized(addr([127, 0, 0, 1]), &headers, None));
+    }
+
+    #[test]
+    fn test_bearer_token_is_required_from_elsewhere() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer s3cret"));
+        assert!(is_authorized(
+            addr([203, 0, 113, 7]),
+            &headers,
+            Some("s3cret")
+        ));
+        assert!(!is_authorized(
+            addr([203, 0, 113, 7]),
+            &headers,
+            Some("other")
+        ));
+        assert!(!is_authorized(addr([203, 0, 113, 7]), &headers, Some("")));
+    }
+}
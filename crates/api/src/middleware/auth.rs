@@ -6,28 +6,64 @@
 //! - AuthState: JWT token creation and validation
 //! - AuthError: Authentication error types
 //! - FromRequestParts implementation for automatic token extraction
+//! - OptionalClaims: extractor for routes open to anonymous callers
+//!
+//! Bearer tokens starting with `pk_live_` are treated as API keys instead of
+//! JWTs and resolved to claims by hash lookup. API keys only reach the routes
+//! their scopes list in [`API_KEY_SCOPE_ROUTES`]; account, auth, session and
+//! admin endpoints are always rejected with `403 Forbidden`.
+//!
+//! JWTs carrying a session refresh token are only accepted while that
+//! session is active, so logging out or revoking sessions takes effect
+//! immediately rather than when the token expires.
+//!
+//! Impersonation tokens, issued to admins acting as another user, are
+//! read-only: they are rejected with `403 Forbidden` on any request that
+//! may change data.
 
+use crate::response::ApiResponse;
 use axum::{
-    extract::FromRequestParts,
-    http::{request::Parts, StatusCode},
+    extract::{FromRequestParts, OriginalUri},
+    http::{request::Parts, Method, StatusCode},
     response::{IntoResponse, Response},
-    Json,
 };
-use domain::USER_MANAGE;
+use domain::{
+    ApiKey, API_KEY_PREFIX, SCOPE_READ_POSTS, SCOPE_WRITE_COMMENTS, SCOPE_WRITE_POSTS, USER_MANAGE,
+};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
+use service::{SessionService, UserService};
+use std::convert::Infallible;
+use std::sync::{Arc, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Global JWT secret key - set at application startup
 static JWT_SECRET: OnceLock<String> = OnceLock::new();
 
+/// Global user service used to resolve API keys - set at application startup
+static API_KEY_SERVICE: OnceLock<Arc<UserService>> = OnceLock::new();
+
+/// Global session service used to reject JWTs of ended sessions - set at application startup
+static SESSION_SERVICE: OnceLock<Arc<SessionService>> = OnceLock::new();
+
 /// Set the global JWT secret key
 /// This should be called once at application startup
 pub fn set_jwt_secret(secret: String) {
     let _ = JWT_SECRET.set(secret);
 }
 
+/// Set the user service used to resolve API keys
+/// This should be called once at application startup
+pub fn set_api_key_service(service: Arc<UserService>) {
+    let _ = API_KEY_SERVICE.set(service);
+}
+
+/// Set the session service used to check the session behind a JWT
+/// This should be called once at application startup
+pub fn set_session_service(service: Arc<SessionService>) {
+    let _ = SESSION_SERVICE.set(service);
+}
+
 /// Get the global JWT secret key
 fn get_jwt_secret() -> &'static str {
     JWT_SECRET
@@ -52,8 +88,46 @@ pub struct Claims {
     pub iat: usize,
     /// User permissions as bit flags
     pub permissions: u64,
+    /// Session refresh token, exchanged at `/api/auth/refresh` for a new JWT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// Scopes of the API key used to authenticate (`None` for JWTs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
+    /// Admin acting as this user (`None` unless this is an impersonation token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<uuid::Uuid>,
+}
+
+impl Claims {
+    /// Check if the request was authenticated with an API key
+    pub fn is_api_key(&self) -> bool {
+        self.scopes.is_some()
+    }
 }
 
+/// Route prefixes below `/api` that each API key scope may call
+///
+/// Every key may read the `read:posts` routes; the write scopes also allow
+/// changes on their own routes. Anything not listed is off limits to keys.
+pub const API_KEY_SCOPE_ROUTES: [(&str, &[&str]); 3] = [
+    (
+        SCOPE_READ_POSTS,
+        &["/posts", "/categories", "/tags", "/comments"],
+    ),
+    (SCOPE_WRITE_POSTS, &["/posts", "/files"]),
+    (SCOPE_WRITE_COMMENTS, &["/comments"]),
+];
+
+/// Lifetime of tokens issued at login, in seconds (24 hours)
+const TOKEN_LIFETIME_SECS: usize = 60 * 60 * 24;
+
+/// Lifetime of access tokens issued by a refresh, in seconds (15 minutes)
+const ACCESS_TOKEN_LIFETIME_SECS: usize = 60 * 15;
+
+/// Lifetime of impersonation tokens, in seconds (1 hour)
+const IMPERSONATION_TOKEN_LIFETIME_SECS: usize = 60 * 60;
+
 /// Authentication state for JWT operations
 #[derive(Clone)]
 pub struct AuthState {
@@ -76,22 +150,108 @@ impl AuthState {
         username: String,
         permissions: u64,
         avatar_url: Option<String>,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.sign(
+            Claims {
+                sub: user_id.into(),
+                username,
+                avatar_url,
+                exp: 0,
+                iat: 0,
+                permissions,
+                refresh_token: None,
+                scopes: None,
+                impersonated_by: None,
+            },
+            TOKEN_LIFETIME_SECS,
+        )
+    }
+
+    /// Create a login JWT carrying the session's refresh token
+    pub fn create_token_with_refresh(
+        &self,
+        user_id: impl Into<String>,
+        username: String,
+        permissions: u64,
+        refresh_token: String,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.sign(
+            Claims {
+                sub: user_id.into(),
+                username,
+                avatar_url: None,
+                exp: 0,
+                iat: 0,
+                permissions,
+                refresh_token: Some(refresh_token),
+                scopes: None,
+                impersonated_by: None,
+            },
+            TOKEN_LIFETIME_SECS,
+        )
+    }
+
+    /// Create a short-lived (15 minute) access JWT after a token refresh
+    pub fn create_access_token(
+        &self,
+        user_id: impl Into<String>,
+        username: String,
+        permissions: u64,
+        refresh_token: String,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.sign(
+            Claims {
+                sub: user_id.into(),
+                username,
+                avatar_url: None,
+                exp: 0,
+                iat: 0,
+                permissions,
+                refresh_token: Some(refresh_token),
+                scopes: None,
+                impersonated_by: None,
+            },
+            ACCESS_TOKEN_LIFETIME_SECS,
+        )
+    }
+
+    /// Create a short-lived (1 hour), read-only JWT for an admin acting as a user
+    pub fn create_impersonation_token(
+        &self,
+        user_id: impl Into<String>,
+        username: String,
+        permissions: u64,
+        impersonated_by: uuid::Uuid,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.sign(
+            Claims {
+                sub: user_id.into(),
+                username,
+                avatar_url: None,
+                exp: 0,
+                iat: 0,
+                permissions,
+                refresh_token: None,
+                scopes: None,
+                impersonated_by: Some(impersonated_by),
+            },
+            IMPERSONATION_TOKEN_LIFETIME_SECS,
+        )
+    }
+
+    /// Stamp issue and expiration times onto the claims and sign them
+    fn sign(
+        &self,
+        mut claims: Claims,
+        lifetime_secs: usize,
     ) -> Result<String, jsonwebtoken::errors::Error> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs())
             .unwrap_or(0) as usize;
 
-        let expiration = now + 60 * 60 * 24; // 24 hours
-
-        let claims = Claims {
-            sub: user_id.into(),
-            username,
-            avatar_url,
-            exp: expiration,
-            iat: now,
-            permissions,
-        };
+        claims.iat = now;
+        claims.exp = now + lifetime_secs;
 
         encode(
             &Header::default(),
@@ -123,6 +283,9 @@ pub enum AuthError {
     MissingToken,
     InvalidToken,
     ExpiredToken,
+    InsufficientScope,
+    ImpersonationReadOnly,
+    SessionEnded,
 }
 
 impl std::fmt::Display for AuthError {
@@ -131,6 +294,9 @@ impl std::fmt::Display for AuthError {
             AuthError::MissingToken => write!(f, "Missing authentication token"),
             AuthError::InvalidToken => write!(f, "Invalid authentication token"),
             AuthError::ExpiredToken => write!(f, "Token has expired"),
+            AuthError::InsufficientScope => write!(f, "API key lacks the required scope"),
+            AuthError::ImpersonationReadOnly => write!(f, "Impersonation tokens are read-only"),
+            AuthError::SessionEnded => write!(f, "Session has been revoked or has expired"),
         }
     }
 }
@@ -139,17 +305,17 @@ impl std::error::Error for AuthError {}
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "Missing authentication token"),
-            AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid authentication token"),
-            AuthError::ExpiredToken => (StatusCode::UNAUTHORIZED, "Token has expired"),
+        let (status, kind) = match self {
+            AuthError::MissingToken
+            | AuthError::InvalidToken
+            | AuthError::ExpiredToken
+            | AuthError::SessionEnded => (StatusCode::UNAUTHORIZED, "unauthorized"),
+            AuthError::InsufficientScope | AuthError::ImpersonationReadOnly => {
+                (StatusCode::FORBIDDEN, "forbidden")
+            }
         };
 
-        let body = Json(serde_json::json!({
-            "error": message
-        }));
-
-        (status, body).into_response()
+        ApiResponse::error(status.as_u16(), kind, self.to_string()).into_response()
     }
 }
 
@@ -190,6 +356,14 @@ where
             .strip_prefix("Bearer ")
             .ok_or(AuthError::InvalidToken)?;
 
+        if token.starts_with(API_KEY_PREFIX) {
+            let path = parts
+                .extensions
+                .get::<OriginalUri>()
+                .map_or(parts.uri.path(), |uri| uri.path());
+            return claims_from_api_key(token, &parts.method, path).await;
+        }
+
         // Decode token with the global JWT secret key
         let token_data = decode::<Claims>(
             token,
@@ -207,28 +381,121 @@ where
         if token_data.claims.exp < now {
             return Err(AuthError::ExpiredToken);
         }
+        check_impersonation(&token_data.claims, &parts.method)?;
+        if let Some(service) = SESSION_SERVICE.get() {
+            check_session(&token_data.claims, service).await?;
+        }
 
         Ok(token_data.claims)
     }
 }
 
-// Implement OptionalFromRequestParts to allow Option<Claims> as an extractor
-// This enables routes that work with or without authentication
-impl<S> axum::extract::OptionalFromRequestParts<S> for Claims
+/// Reject a JWT whose session was revoked, deleted or has expired
+///
+/// Tokens without a refresh token, such as impersonation tokens, have no
+/// session and rely on their short lifetime instead.
+async fn check_session(claims: &Claims, service: &SessionService) -> Result<(), AuthError> {
+    match &claims.refresh_token {
+        Some(refresh_token) => service
+            .refresh_session(refresh_token)
+            .await
+            .map(|_| ())
+            .map_err(|_| AuthError::SessionEnded),
+        None => Ok(()),
+    }
+}
+
+/// Resolve an API key to claims limited by the key's scopes
+async fn claims_from_api_key(key: &str, method: &Method, path: &str) -> Result<Claims, AuthError> {
+    let service = API_KEY_SERVICE.get().ok_or(AuthError::InvalidToken)?;
+    let (user, api_key) = service
+        .authenticate_api_key(key)
+        .await
+        .map_err(|_| AuthError::InvalidToken)?;
+    check_api_key_scope(&api_key, method, path)?;
+
+    // API key claims are minted per request and never signed
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0) as usize;
+
+    Ok(Claims {
+        sub: user.id.to_string(),
+        username: user.username,
+        avatar_url: None,
+        exp: now,
+        iat: now,
+        permissions: api_key.effective_permissions(user.permissions),
+        refresh_token: None,
+        scopes: Some(api_key.scopes),
+        impersonated_by: None,
+    })
+}
+
+/// Reject requests to routes the key's scopes do not list
+fn check_api_key_scope(api_key: &ApiKey, method: &Method, path: &str) -> Result<(), AuthError> {
+    let path = path.strip_prefix("/api").unwrap_or(path);
+    let allowed = API_KEY_SCOPE_ROUTES.iter().any(|(scope, routes)| {
+        let granted = if method.is_safe() {
+            *scope == SCOPE_READ_POSTS || api_key.has_scope(scope)
+        } else {
+            *scope != SCOPE_READ_POSTS && api_key.has_scope(scope)
+        };
+        granted
+            && routes.iter().any(|route| {
+                path.strip_prefix(route)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+    });
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(AuthError::InsufficientScope)
+    }
+}
+
+/// Reject requests that change data when an admin is impersonating the user
+fn check_impersonation(claims: &Claims, method: &Method) -> Result<(), AuthError> {
+    if method.is_safe() || claims.impersonated_by.is_none() {
+        Ok(())
+    } else {
+        Err(AuthError::ImpersonationReadOnly)
+    }
+}
+
+/// Claims of the caller if the request is authenticated
+///
+/// For routes that work with or without authentication. Where [`Claims`]
+/// rejects the request, e.g. for a missing, malformed or expired token, this
+/// extracts `OptionalClaims(None)` and the caller is treated as anonymous.
+///
+/// # Example
+///
+/// ```ignore
+/// async fn get_post(OptionalClaims(user): OptionalClaims) -> String {
+///     match user {
+///         Some(claims) => format!("Hello {}", claims.username),
+///         None => "Hello guest".to_string(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OptionalClaims(pub Option<Claims>);
+
+impl<S> FromRequestParts<S> for OptionalClaims
 where
     S: Send + Sync,
 {
-    type Rejection = AuthError;
+    type Rejection = Infallible;
 
-    async fn from_request_parts(
-        parts: &mut Parts,
-        state: &S,
-    ) -> Result<Option<Self>, Self::Rejection> {
-        Ok(
-            <Self as FromRequestParts<S>>::from_request_parts(parts, state)
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            <Claims as FromRequestParts<S>>::from_request_parts(parts, state)
                 .await
                 .ok(),
-        )
+        ))
     }
 }
 
@@ -246,3 +513,297 @@ pub fn require_permission(user: &Claims, permission: u64) -> Result<(), AuthErro
 pub fn require_admin(user: &Claims) -> Result<(), AuthError> {
     require_permission(user, USER_MANAGE)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_token_is_short_lived_and_carries_refresh_token() {
+        let auth = AuthState::new("test-secret");
+        let token = auth
+            .create_access_token("user-id", "alice".to_string(), 0, "refresh".to_string())
+            .unwrap();
+
+        let claims = auth.decode_token(&token).unwrap();
+        assert_eq!(claims.exp - claims.iat, ACCESS_TOKEN_LIFETIME_SECS);
+        assert_eq!(claims.refresh_token.as_deref(), Some("refresh"));
+    }
+
+    #[test]
+    fn test_plain_token_has_no_refresh_token() {
+        let auth = AuthState::new("test-secret");
+        let token = auth
+            .create_token("user-id", "alice".to_string(), 0, None)
+            .unwrap();
+
+        let claims = auth.decode_token(&token).unwrap();
+        assert_eq!(claims.exp - claims.iat, TOKEN_LIFETIME_SECS);
+        assert!(claims.refresh_token.is_none());
+    }
+
+    #[test]
+    fn test_read_only_api_key_cannot_write() {
+        let api_key = ApiKey {
+            key_hash: "hash".to_string(),
+            user_id: uuid::Uuid::new_v4(),
+            label: "feed".to_string(),
+            scopes: vec![domain::SCOPE_READ_POSTS.to_string()],
+            created_at: chrono::Utc::now(),
+            last_used_at: None,
+        };
+
+        assert!(check_api_key_scope(&api_key, &Method::GET, "/api/posts").is_ok());
+        assert!(matches!(
+            check_api_key_scope(&api_key, &Method::POST, "/api/posts"),
+            Err(AuthError::InsufficientScope)
+        ));
+
+        let writer = ApiKey {
+            scopes: vec![domain::SCOPE_WRITE_POSTS.to_string()],
+            ..api_key
+        };
+        assert!(check_api_key_scope(&writer, &Method::POST, "/api/posts").is_ok());
+        assert!(check_api_key_scope(&writer, &Method::PUT, "/api/posts/123").is_ok());
+        assert!(matches!(
+            check_api_key_scope(&writer, &Method::POST, "/api/comments"),
+            Err(AuthError::InsufficientScope)
+        ));
+    }
+
+    #[test]
+    fn test_api_key_cannot_reach_account_routes() {
+        let api_key = ApiKey {
+            key_hash: "hash".to_string(),
+            user_id: uuid::Uuid::new_v4(),
+            label: "comment bot".to_string(),
+            scopes: vec![domain::SCOPE_WRITE_COMMENTS.to_string()],
+            created_at: chrono::Utc::now(),
+            last_used_at: None,
+        };
+        let user_id = uuid::Uuid::new_v4();
+
+        assert!(check_api_key_scope(&api_key, &Method::POST, "/api/comments").is_ok());
+        for (method, path) in [
+            (Method::PUT, "/api/auth/email".to_string()),
+            (Method::POST, "/api/auth/totp/setup".to_string()),
+            (Method::POST, "/api/auth/password".to_string()),
+            (Method::PATCH, format!("/api/users/{}", user_id)),
+            (Method::POST, format!("/api/users/{}/api-keys", user_id)),
+            (Method::GET, "/api/users/me".to_string()),
+            (Method::GET, "/api/sessions".to_string()),
+            (Method::DELETE, "/api/sessions".to_string()),
+            (Method::POST, "/api/admin/config/reload".to_string()),
+            (Method::POST, "/api/commentsx".to_string()),
+        ] {
+            let err = check_api_key_scope(&api_key, &method, &path).unwrap_err();
+            assert!(
+                matches!(err, AuthError::InsufficientScope),
+                "{} {}",
+                method,
+                path
+            );
+            assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[test]
+    fn test_impersonation_token_is_short_lived_and_read_only() {
+        let auth = AuthState::new("test-secret");
+        let admin_id = uuid::Uuid::new_v4();
+        let token = auth
+            .create_impersonation_token("user-id", "alice".to_string(), 0, admin_id)
+            .unwrap();
+
+        let claims = auth.decode_token(&token).unwrap();
+        assert_eq!(claims.exp - claims.iat, IMPERSONATION_TOKEN_LIFETIME_SECS);
+        assert_eq!(claims.impersonated_by, Some(admin_id));
+        assert!(claims.refresh_token.is_none());
+
+        assert!(check_impersonation(&claims, &Method::GET).is_ok());
+        for method in [Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+            assert!(matches!(
+                check_impersonation(&claims, &method),
+                Err(AuthError::ImpersonationReadOnly)
+            ));
+        }
+
+        let own = Claims {
+            impersonated_by: None,
+            ..claims
+        };
+        assert!(check_impersonation(&own, &Method::POST).is_ok());
+    }
+
+    /// Session store that only answers refresh token lookups
+    struct RefreshTokenSessions(Vec<domain::Session>);
+
+    #[async_trait::async_trait]
+    impl domain::SessionRepository for RefreshTokenSessions {
+        async fn create_session(
+            &self,
+            _user_id: uuid::Uuid,
+            _remember_me: bool,
+            _user_agent: &str,
+            _ip_address: &str,
+        ) -> domain::Result<domain::Session> {
+            unimplemented!()
+        }
+
+        async fn get_session(&self, _token: &str) -> domain::Result<Option<domain::Session>> {
+            unimplemented!()
+        }
+
+        async fn get_session_by_refresh_token(
+            &self,
+            refresh_token: &str,
+        ) -> domain::Result<Option<domain::Session>> {
+            Ok(self
+                .0
+                .iter()
+                .find(|session| session.refresh_token == refresh_token)
+                .cloned())
+        }
+
+        async fn revoke_refresh_token(&self, _refresh_token: &str) -> domain::Result<()> {
+            unimplemented!()
+        }
+
+        async fn extend_session(
+            &self,
+            _token: &str,
+            _new_expires_at: chrono::DateTime<chrono::Utc>,
+            _new_refresh_at: chrono::DateTime<chrono::Utc>,
+        ) -> domain::Result<()> {
+            unimplemented!()
+        }
+
+        async fn delete_session(&self, _token: &str) -> domain::Result<()> {
+            unimplemented!()
+        }
+
+        async fn list_user_sessions(
+            &self,
+            _user_id: uuid::Uuid,
+        ) -> domain::Result<Vec<domain::Session>> {
+            unimplemented!()
+        }
+
+        async fn delete_user_sessions(
+            &self,
+            _user_id: uuid::Uuid,
+            _except_session_id: Option<&str>,
+        ) -> domain::Result<u64> {
+            unimplemented!()
+        }
+
+        async fn cleanup_expired_sessions(&self) -> domain::Result<u64> {
+            unimplemented!()
+        }
+
+        async fn count_active_sessions(&self) -> domain::Result<u64> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jwt_of_ended_session_is_rejected() {
+        let user_id = uuid::Uuid::new_v4();
+        let active = domain::Session::new(user_id);
+        let revoked = domain::Session {
+            revoked: true,
+            ..domain::Session::new(user_id)
+        };
+        let service = SessionService::new(Arc::new(RefreshTokenSessions(vec![
+            active.clone(),
+            revoked.clone(),
+        ])));
+        let auth = AuthState::new("test-secret");
+        let claims_for = |refresh_token: Option<String>| {
+            let token = match refresh_token {
+                Some(refresh_token) => auth.create_token_with_refresh(
+                    user_id.to_string(),
+                    "alice".to_string(),
+                    0,
+                    refresh_token,
+                ),
+                None => auth.create_token(user_id.to_string(), "alice".to_string(), 0, None),
+            };
+            auth.decode_token(&token.unwrap()).unwrap()
+        };
+
+        assert!(
+            check_session(&claims_for(Some(active.refresh_token)), &service)
+                .await
+                .is_ok()
+        );
+        assert!(check_session(&claims_for(None), &service).await.is_ok());
+        for refresh_token in [revoked.refresh_token, "deleted".to_string()] {
+            let err = check_session(&claims_for(Some(refresh_token)), &service)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, AuthError::SessionEnded));
+            assert_eq!(err.into_response().status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    async fn optional_claims(authorization: Option<String>) -> Option<Claims> {
+        let mut builder = axum::http::Request::builder().uri("/posts");
+        if let Some(authorization) = authorization {
+            builder = builder.header("Authorization", authorization);
+        }
+        let (mut parts, _) = builder.body(()).unwrap().into_parts();
+
+        let Ok(OptionalClaims(claims)) = OptionalClaims::from_request_parts(&mut parts, &()).await;
+        claims
+    }
+
+    #[tokio::test]
+    async fn test_optional_claims_with_valid_token() {
+        let auth = AuthState::new(get_jwt_secret());
+        let token = auth
+            .create_token("user-id", "alice".to_string(), 0, None)
+            .unwrap();
+
+        let claims = optional_claims(Some(format!("Bearer {}", token))).await;
+        assert_eq!(claims.unwrap().username, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_optional_claims_without_token_is_anonymous() {
+        assert!(optional_claims(None).await.is_none());
+        assert!(optional_claims(Some("Bearer not-a-jwt".to_string()))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_optional_claims_with_expired_token_is_anonymous() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as usize;
+        // Well past the decoder's leeway for clock skew
+        let expired = Claims {
+            sub: "user-id".to_string(),
+            username: "alice".to_string(),
+            avatar_url: None,
+            exp: now - 3600,
+            iat: now - 7200,
+            permissions: 0,
+            refresh_token: None,
+            scopes: None,
+            impersonated_by: None,
+        };
+        let token = encode(
+            &Header::default(),
+            &expired,
+            &EncodingKey::from_secret(get_jwt_secret().as_bytes()),
+        )
+        .unwrap();
+
+        assert!(optional_claims(Some(format!("Bearer {}", token)))
+            .await
+            .is_none());
+    }
+}
@@ -0,0 +1,158 @@
+//! Request Body Size Limits
+//!
+//! JSON endpoints accept at most [`JSON_BODY_LIMIT_BYTES`]; upload endpoints
+//! accept up to `storage.max_file_size_mb` from the live configuration.
+//! Both wrap the route in `tower_http`'s `RequestBodyLimit`, which rejects a
+//! too-large `Content-Length` up front and cuts off streamed bodies once they
+//! pass the limit. Either way the client gets a JSON `413 Payload Too Large`.
+//!
+//! Apply them per route group with `axum::middleware::from_fn`.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use service::{file::DEFAULT_MAX_FILE_SIZE_MB, SharedConfig};
+use std::sync::OnceLock;
+use tower::{util::MapRequest, Layer, ServiceExt};
+use tower_http::{body::Limited, limit::RequestBodyLimitLayer};
+
+use crate::error::ApiError;
+
+/// Largest body accepted by JSON endpoints
+pub const JSON_BODY_LIMIT_BYTES: usize = 1024 * 1024;
+
+/// Global configuration read for the upload limit - set at application startup
+static BODY_LIMIT_CONFIG: OnceLock<SharedConfig> = OnceLock::new();
+
+/// Set the configuration the upload limit is read from
+/// This should be called once at application startup
+pub fn set_body_limit_config(config: SharedConfig) {
+    let _ = BODY_LIMIT_CONFIG.set(config);
+}
+
+/// Upload limit in bytes, falling back to the default before startup sets it
+fn upload_limit_bytes() -> usize {
+    let megabytes = match BODY_LIMIT_CONFIG.get() {
+        Some(config) => config
+            .read()
+            .unwrap()
+            .storage
+            .max_file_size_mb
+            .unwrap_or(DEFAULT_MAX_FILE_SIZE_MB),
+        None => DEFAULT_MAX_FILE_SIZE_MB,
+    };
+    usize::try_from(megabytes.saturating_mul(1024 * 1024)).unwrap_or(usize::MAX)
+}
+
+/// Middleware limiting request bodies of JSON endpoints
+pub async fn limit_json_body(request: Request, next: Next) -> Response {
+    limit_body(JSON_BODY_LIMIT_BYTES, request, next).await
+}
+
+/// Middleware limiting request bodies of upload endpoints
+pub async fn limit_upload_body(request: Request, next: Next) -> Response {
+    limit_body(upload_limit_bytes(), request, next).await
+}
+
+async fn limit_body(limit: usize, request: Request, next: Next) -> Response {
+    let inner = MapRequest::new(next, |request: Request<Limited<Body>>| {
+        request.map(Body::new)
+    });
+    let service = RequestBodyLimitLayer::new(limit).layer(inner);
+
+    let response = match service.oneshot(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    };
+
+    // Both the early rejection and extractors hitting the limit mid-stream
+    // answer with a plain-text 413
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return ApiError::PayloadTooLarge(format!("Request body exceeds the {} byte limit", limit))
+            .into_response();
+    }
+
+    response.map(Body::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::to_bytes,
+        http::{header::CONTENT_TYPE, Method},
+        routing::post,
+        Json, Router,
+    };
+    use tokio_util::io::ReaderStream;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/api/posts",
+                post(|Json(body): Json<serde_json::Value>| async move { Json(body) }),
+            )
+            .layer(axum::middleware::from_fn(limit_json_body))
+    }
+
+    fn post_request(body: Body) -> Request {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/posts")
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap()
+    }
+
+    fn oversized_post() -> String {
+        let content = "a".repeat(JSON_BODY_LIMIT_BYTES);
+        serde_json::json!({ "title": "Big", "content": content }).to_string()
+    }
+
+    async fn error_kind(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], 413);
+        body["error"]["type"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_over_limit_post_is_rejected_with_json_413() {
+        let response = app()
+            .oneshot(post_request(Body::from(oversized_post())))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_kind(response).await, "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_over_limit_post_is_rejected_with_json_413() {
+        let chunks = ReaderStream::with_capacity(
+            std::io::Cursor::new(oversized_post().into_bytes()),
+            64 * 1024,
+        );
+        let response = app()
+            .oneshot(post_request(Body::from_stream(chunks)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_kind(response).await, "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_small_post_passes_through() {
+        let response = app()
+            .oneshot(post_request(Body::from(r#"{"title":"Small"}"#)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,84 @@
+//! Client IP Extraction
+//!
+//! Resolves the address of the client making a request. When the blog runs
+//! behind a reverse proxy, the first `X-Forwarded-For` entry (or
+//! `X-Real-IP`) is used; otherwise the socket peer address is used.
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+/// Extractor for the client's IP address
+///
+/// Falls back to `"unknown"` when no address can be determined, e.g. when
+/// the server was not started with connect info.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientIp(pub String);
+
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let ip = header("X-Forwarded-For")
+            .or_else(|| header("X-Real-IP"))
+            .or_else(|| {
+                parts
+                    .extensions
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip().to_string())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Ok(ClientIp(ip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn extract(request: Request<()>) -> String {
+        let (mut parts, _) = request.into_parts();
+        let ClientIp(ip) = ClientIp::from_request_parts(&mut parts, &()).await.unwrap();
+        ip
+    }
+
+    #[tokio::test]
+    async fn test_prefers_first_forwarded_address() {
+        let request = Request::builder()
+            .header("X-Forwarded-For", "203.0.113.7, 10.0.0.1")
+            .header("X-Real-IP", "10.0.0.2")
+            .body(())
+            .unwrap();
+        assert_eq!(extract(request).await, "203.0.113.7");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_peer_address() {
+        let mut request = Request::builder().body(()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([192, 0, 2, 1], 4000))));
+        assert_eq!(extract(request).await, "192.0.2.1");
+
+        assert_eq!(extract(Request::new(())).await, "unknown");
+    }
+}
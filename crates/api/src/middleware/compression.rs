@@ -0,0 +1,129 @@
+//! Response Compression
+//!
+//! Text, JSON and XML responses are compressed with gzip or Brotli,
+//! whichever the client's `Accept-Encoding` prefers. Images and other
+//! binary content are sent as they are, since they are already compressed.
+//! Compressible responses carry `Vary: Accept-Encoding` so caches keep the
+//! encodings apart.
+
+use axum::http::{header::CONTENT_TYPE, Response};
+use tower_http::compression::{
+    predicate::{And, Predicate, SizeAbove},
+    CompressionLayer, CompressionLevel,
+};
+
+/// Compression level used when `server.compression_level` is not set
+pub const DEFAULT_COMPRESSION_LEVEL: u8 = 6;
+
+/// Responses smaller than this are not worth compressing
+const MIN_COMPRESS_SIZE_BYTES: u16 = 256;
+
+/// Compress responses whose media type is `text/*`, JSON or XML
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompressibleContentType;
+
+impl Predicate for CompressibleContentType {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: axum::body::HttpBody,
+    {
+        let Some(content_type) = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return false;
+        };
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        // Server-sent events must reach the client as they are written
+        (media_type.starts_with("text/") && media_type != "text/event-stream")
+            || media_type == "application/json"
+            || media_type == "application/xml"
+    }
+}
+
+/// Compression layer for the whole app
+///
+/// `level` runs from 1 (fastest) to 9 (smallest); values outside that range
+/// are clamped and `None` uses [`DEFAULT_COMPRESSION_LEVEL`].
+pub fn compression_layer(
+    level: Option<u8>,
+) -> CompressionLayer<And<SizeAbove, CompressibleContentType>> {
+    let level = level.unwrap_or(DEFAULT_COMPRESSION_LEVEL).clamp(1, 9);
+
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .quality(CompressionLevel::Precise(i32::from(level)))
+        .compress_when(SizeAbove::new(MIN_COMPRESS_SIZE_BYTES).and(CompressibleContentType))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        extract::Request,
+        http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, VARY},
+        response::IntoResponse,
+        routing::get,
+        Json, Router,
+    };
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/api/posts",
+                get(|| async {
+                    let posts: Vec<_> = (0..100)
+                        .map(|i| serde_json::json!({ "id": i, "title": "Post title" }))
+                        .collect();
+                    Json(posts)
+                }),
+            )
+            .route(
+                "/image.png",
+                get(|| async { ([(CONTENT_TYPE, "image/png")], vec![0u8; 4096]).into_response() }),
+            )
+            .layer(compression_layer(None))
+    }
+
+    fn request(uri: &str, accept_encoding: &str) -> Request {
+        Request::builder()
+            .uri(uri)
+            .header(ACCEPT_ENCODING, accept_encoding)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_large_json_response_is_gzipped() {
+        let response = app().oneshot(request("/api/posts", "gzip")).await.unwrap();
+
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+    }
+
+    #[tokio::test]
+    async fn test_images_and_identity_requests_are_not_compressed() {
+        let image = app()
+            .oneshot(request("/image.png", "gzip, br"))
+            .await
+            .unwrap();
+        assert!(image.headers().get(CONTENT_ENCODING).is_none());
+
+        let plain = app()
+            .oneshot(request("/api/posts", "identity"))
+            .await
+            .unwrap();
+        assert!(plain.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(plain.headers()[VARY], "accept-encoding");
+    }
+}
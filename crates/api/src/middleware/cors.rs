@@ -0,0 +1,169 @@
+//! Cross-Origin Resource Sharing
+//!
+//! Only origins listed in `server.allowed_origins` may call the API from a
+//! browser. [`CorsPolicy::layer`] answers preflight requests for those
+//! origins, and [`reject_unlisted_origin`] refuses cross-origin requests from
+//! anywhere else with `403 Forbidden`. Same-origin requests, where the
+//! `Origin` matches the `Host` header, are always allowed so the embedded
+//! frontend keeps working.
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE, HOST, ORIGIN},
+        HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use super::request_id::REQUEST_ID_HEADER;
+use crate::response::ApiResponse;
+
+/// Allowlist of origins permitted to make cross-origin requests
+#[derive(Debug, Clone)]
+pub struct CorsPolicy {
+    origins: Arc<Vec<HeaderValue>>,
+    max_age: Duration,
+}
+
+impl CorsPolicy {
+    /// Build a policy from configured origins, e.g. `"https://myblog.com"`
+    ///
+    /// Entries that are not valid header values are skipped; the config
+    /// loader has already rejected malformed URLs.
+    pub fn new(origins: &[String], max_age_seconds: u64) -> Self {
+        let origins = origins
+            .iter()
+            .filter_map(|origin| HeaderValue::from_str(origin.trim_end_matches('/')).ok())
+            .collect();
+
+        Self {
+            origins: Arc::new(origins),
+            max_age: Duration::from_secs(max_age_seconds),
+        }
+    }
+
+    /// Check if an origin is on the allowlist
+    pub fn allows(&self, origin: &HeaderValue) -> bool {
+        self.origins.contains(origin)
+    }
+
+    /// CORS layer answering preflight requests for allowlisted origins
+    pub fn layer(&self) -> CorsLayer {
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(self.origins.iter().cloned()))
+            .allow_methods([
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::DELETE,
+                Method::PATCH,
+            ])
+            .allow_headers([CONTENT_TYPE, AUTHORIZATION, REQUEST_ID_HEADER])
+            .expose_headers([REQUEST_ID_HEADER])
+            .max_age(self.max_age)
+    }
+}
+
+/// Reject cross-origin requests from origins that are not allowlisted
+pub async fn reject_unlisted_origin(
+    State(policy): State<CorsPolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(origin) = request.headers().get(ORIGIN) {
+        if !policy.allows(origin) && !is_same_origin(origin, request.headers().get(HOST)) {
+            tracing::debug!("Rejected request from unlisted origin {:?}", origin);
+            return ApiResponse::error(
+                StatusCode::FORBIDDEN.as_u16(),
+                "forbidden",
+                "Origin not allowed",
+            )
+            .into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Check if the origin points at the host that received the request
+fn is_same_origin(origin: &HeaderValue, host: Option<&HeaderValue>) -> bool {
+    let (Ok(origin), Some(Ok(host))) = (origin.to_str(), host.map(HeaderValue::to_str)) else {
+        return false;
+    };
+
+    origin
+        .strip_prefix("https://")
+        .or_else(|| origin.strip_prefix("http://"))
+        .is_some_and(|authority| authority.eq_ignore_ascii_case(host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request as HttpRequest, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let policy = CorsPolicy::new(&["https://myblog.com".to_string()], 600);
+        Router::new()
+            .route("/api/posts", get(|| async { "posts" }))
+            .layer(axum::middleware::from_fn_with_state(
+                policy.clone(),
+                reject_unlisted_origin,
+            ))
+            .layer(policy.layer())
+    }
+
+    async fn send(method: Method, origin: Option<&str>) -> Response {
+        let mut request = HttpRequest::builder()
+            .method(method)
+            .uri("/api/posts")
+            .header(HOST, "blog.internal:3000");
+        if let Some(origin) = origin {
+            request = request.header(ORIGIN, origin);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_origin_is_forbidden() {
+        let response = send(Method::GET, Some("https://evil.example")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "FORBIDDEN");
+    }
+
+    #[tokio::test]
+    async fn test_listed_origin_gets_cors_headers() {
+        let response = send(Method::GET, Some("https://myblog.com")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            "https://myblog.com"
+        );
+
+        let preflight = send(Method::OPTIONS, Some("https://myblog.com")).await;
+        assert_eq!(preflight.headers()["access-control-max-age"], "600");
+    }
+
+    #[tokio::test]
+    async fn test_same_origin_and_non_browser_requests_pass() {
+        let response = send(Method::GET, Some("http://blog.internal:3000")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(Method::GET, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,158 @@
+//! ETag and Conditional GET
+//!
+//! [`etag`] buffers successful `GET` responses, tags them with an `ETag`
+//! derived from the body, and answers `304 Not Modified` when the client's
+//! `If-None-Match` already names that tag. The handler still runs, so the
+//! savings are in bandwidth rather than server work.
+//!
+//! Apply it per route with `axum::middleware::from_fn(etag)`; only routes
+//! whose bodies are built in memory should use it.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Compute a strong ETag (quoted hex CRC32) for a response body
+pub fn compute_etag(content: &[u8]) -> String {
+    format!("\"{:08x}\"", crc32fast::hash(content))
+}
+
+/// Check if any `If-None-Match` value matches the given ETag
+///
+/// Weak comparison is used, as RFC 9110 requires for `If-None-Match`.
+fn if_none_match(conditions: &[HeaderValue], etag: &str) -> bool {
+    let strip_weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = strip_weak(etag);
+
+    conditions
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || strip_weak(tag) == etag)
+}
+
+/// Middleware adding `ETag` headers and `304 Not Modified` responses
+///
+/// Responses that already carry an `ETag` keep it.
+pub async fn etag(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+
+    let conditions: Vec<HeaderValue> = request
+        .headers()
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .cloned()
+        .collect();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = if parts.headers.contains_key(ETAG) {
+        body
+    } else {
+        let bytes = match to_bytes(body, usize::MAX).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::error!("Failed to buffer response for ETag: {}", e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+        if let Ok(value) = HeaderValue::from_str(&compute_etag(&bytes)) {
+            parts.headers.insert(ETAG, value);
+        }
+        Body::from(bytes)
+    };
+
+    if let Some(etag) = parts.headers.get(ETAG) {
+        if etag
+            .to_str()
+            .is_ok_and(|etag| if_none_match(&conditions, etag))
+        {
+            let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+            not_modified.headers_mut().insert(ETAG, etag.clone());
+            return not_modified;
+        }
+    }
+
+    Response::from_parts(parts, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, routing::get, Router};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    fn app(post: Arc<Mutex<String>>) -> Router {
+        Router::new()
+            .route(
+                "/posts/1",
+                get(|State(post): State<Arc<Mutex<String>>>| async move {
+                    post.lock().unwrap().clone()
+                })
+                .layer(axum::middleware::from_fn(etag)),
+            )
+            .with_state(post)
+    }
+
+    async fn get_post(post: &Arc<Mutex<String>>, if_none_match: Option<&str>) -> Response {
+        let mut request = Request::builder().uri("/posts/1");
+        if let Some(tag) = if_none_match {
+            request = request.header(IF_NONE_MATCH, tag);
+        }
+        app(post.clone())
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compute_etag_is_quoted_and_stable() {
+        let etag = compute_etag(b"hello");
+        assert_eq!(etag, compute_etag(b"hello"));
+        assert_ne!(etag, compute_etag(b"hello!"));
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+    }
+
+    #[tokio::test]
+    async fn test_matching_etag_returns_not_modified() {
+        let post = Arc::new(Mutex::new("First version".to_string()));
+
+        let response = get_post(&post, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+
+        let response = get_post(&post, Some(&format!("\"other\", W/{}", etag))).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], etag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_changed_content_invalidates_etag() {
+        let post = Arc::new(Mutex::new("First version".to_string()));
+        let response = get_post(&post, None).await;
+        let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+
+        *post.lock().unwrap() = "Edited version".to_string();
+
+        let response = get_post(&post, Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[ETAG], etag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"Edited version");
+    }
+}
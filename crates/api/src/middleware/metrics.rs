@@ -0,0 +1,105 @@
+//! Request Metrics
+//!
+//! [`MetricsLayer`] counts every request and records its latency in the
+//! Prometheus registry. Requests are labelled with the matched route
+//! template (e.g. `/api/posts/{id}`) rather than the raw URI, so label
+//! values stay bounded; requests that match no route, such as frontend
+//! assets, share the `unmatched` label.
+//!
+//! Add it with `Router::layer` so it runs after routing and can see the
+//! matched path.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    response::Response,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+/// Layer recording `http_requests_total` and `http_request_duration_seconds`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsLayer;
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService { inner }
+    }
+}
+
+/// Service produced by [`MetricsLayer`]
+#[derive(Debug, Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for MetricsService<S>
+where
+    S: Service<Request, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let method = request.method().to_string();
+        let path = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str().to_string())
+            .unwrap_or_else(|| "unmatched".to_string());
+        let start = Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+            infrastructure::metrics::record_http_request(
+                &method,
+                &path,
+                response.status().as_u16(),
+                start.elapsed(),
+            );
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_requests_are_labelled_with_route_template() {
+        let app = Router::new()
+            .route(
+                "/metrics-test/posts/{id}",
+                get(|| async { StatusCode::NO_CONTENT }),
+            )
+            .layer(MetricsLayer);
+
+        for id in ["1", "2"] {
+            let request = Request::builder()
+                .uri(format!("/metrics-test/posts/{}", id))
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        let text = infrastructure::metrics::gather();
+        assert!(text.contains(
+            r#"http_requests_total{method="GET",path="/metrics-test/posts/{id}",status="204"} 2"#
+        ));
+        assert!(!text.contains("/metrics-test/posts/1"));
+    }
+}
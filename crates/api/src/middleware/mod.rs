@@ -1,3 +1,20 @@
 pub mod auth;
+pub mod body_limit;
+pub mod client_ip;
+pub mod compression;
+pub mod cors;
+pub mod etag;
+pub mod metrics;
+pub mod request_id;
 
-pub use auth::{require_permission, set_jwt_secret, AuthState, Claims};
+pub use auth::{
+    require_permission, set_api_key_service, set_jwt_secret, set_session_service, AuthState,
+    Claims, OptionalClaims,
+};
+pub use body_limit::{limit_json_body, limit_upload_body, set_body_limit_config};
+pub use client_ip::ClientIp;
+pub use compression::compression_layer;
+pub use cors::{reject_unlisted_origin, CorsPolicy};
+pub use etag::{compute_etag, etag};
+pub use metrics::MetricsLayer;
+pub use request_id::{request_span, RequestId, RequestIdLayer};
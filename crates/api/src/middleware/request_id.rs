@@ -0,0 +1,188 @@
+//! Request IDs
+//!
+//! [`RequestIdLayer`] gives every request an ID: the client's `X-Request-Id`
+//! when it is at most [`MAX_REQUEST_ID_LENGTH`] printable ASCII characters,
+//! otherwise a new UUID. The ID is stored in the request extensions as
+//! [`RequestId`], recorded as `request_id` on the current tracing span, and
+//! echoed in the `X-Request-Id` response header. Error bodies built while
+//! the request is handled carry it as well, see [`current_request_id`].
+//!
+//! Add it inside the `TraceLayer` so the span created by [`request_span`]
+//! is current when the ID is recorded.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    response::Response,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+/// Header carrying the request ID in both directions
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied request ID that is accepted, the length of a UUID
+pub const MAX_REQUEST_ID_LENGTH: usize = 36;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the request being handled, available as a request extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// ID of the request the current task is handling, if any
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Root span for a request, with an empty `request_id` field to record into
+pub fn request_span(request: &Request) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = tracing::field::Empty,
+    )
+}
+
+/// Layer assigning each request an ID
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// Service produced by [`RequestIdLayer`]
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for RequestIdService<S>
+where
+    S: Service<Request, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        let id = request
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| is_valid_request_id(id))
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        tracing::Span::current().record("request_id", id.as_str());
+        request.extensions_mut().insert(RequestId(id.clone()));
+        let future = REQUEST_ID.scope(id.clone(), self.inner.call(request));
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            // Valid IDs and UUIDs are printable ASCII, so this cannot fail
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                response.headers_mut().insert(REQUEST_ID_HEADER, value);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Accept non-empty IDs of printable ASCII up to [`MAX_REQUEST_ID_LENGTH`]
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use axum::{
+        body::{to_bytes, Body},
+        routing::get,
+        Extension, Router,
+    };
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/ok",
+                get(|Extension(id): Extension<RequestId>| async move { id.0 }),
+            )
+            .route(
+                "/missing",
+                get(|| async { ApiError::not_found("Post not found") }),
+            )
+            .layer(RequestIdLayer)
+    }
+
+    fn request(uri: &str, id: Option<&str>) -> Request {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(id) = id {
+            builder = builder.header(&REQUEST_ID_HEADER, id);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    async fn body_text(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id_is_propagated() {
+        let response = app()
+            .oneshot(request("/ok", Some("abc-123")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[&REQUEST_ID_HEADER], "abc-123");
+        assert_eq!(body_text(response).await, "abc-123");
+    }
+
+    #[tokio::test]
+    async fn test_missing_or_invalid_request_id_is_replaced_with_uuid() {
+        let too_long = "a".repeat(MAX_REQUEST_ID_LENGTH + 1);
+        for id in [None, Some(too_long.as_str()), Some("has space")] {
+            let response = app().oneshot(request("/ok", id)).await.unwrap();
+
+            let header = response.headers()[&REQUEST_ID_HEADER]
+                .to_str()
+                .unwrap()
+                .to_string();
+            assert!(Uuid::parse_str(&header).is_ok(), "{:?}", id);
+            assert_eq!(body_text(response).await, header);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_response_carries_request_id() {
+        let response = app()
+            .oneshot(request("/missing", Some("req-42")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[&REQUEST_ID_HEADER], "req-42");
+        let body: serde_json::Value = serde_json::from_str(&body_text(response).await).unwrap();
+        assert_eq!(body["request_id"], "req-42");
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+    }
+}
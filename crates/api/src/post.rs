@@ -7,35 +7,72 @@
 //!
 //! | Method | Endpoint | Description |
 //! |--------|----------|-------------|
-//! | GET | /posts | List posts with filters |
-//! | GET | /posts/search | Search posts |
+//! | GET | /posts | List posts with filters (`?sort=views` for most viewed first, `?category={id}&recursive=true` to include subcategories); unfiltered published posts include `author_username`, `author_avatar_url` and `tags` |
+//! | GET | /posts/search | Search posts by `q`, or by field filters (title, author, tags, dates...) |
+//! | GET | /posts/archive | List months with published posts and their post counts |
+//! | GET | /posts/archive/{year}/{month} | List posts published in a month |
 //! | POST | /posts | Create new post |
+//! | POST | /posts/import | Import Markdown files with front-matter |
+//! | POST | /posts/bulk-publish | Publish several posts, optionally at `schedule_at` |
+//! | POST | /posts/bulk-unpublish | Unpublish several posts |
+//! | GET | /posts/export | Download all posts as a ZIP of Markdown files (admin only) |
 //! | GET | /posts/{id} | Get post details |
 //! | PUT | /posts/{id} | Full update post |
-//! | PATCH | /posts/{id} | Partial update (title, content, category, status) |
-//! | DELETE | /posts/{id} | Delete post |
+//! | PATCH | /posts/{id} | Partial update (title, content, summary, category, status) |
+//! | DELETE | /posts/{id} | Delete post (soft delete) |
+//! | POST | /posts/{id}/restore | Restore deleted post (admin only) |
+//! | POST | /posts/{id}/archive | Move post to the archive |
+//! | POST | /posts/{id}/unarchive | Move post back from the archive |
+//! | POST | /posts/{id}/duplicate | Copy post into a new draft |
+//! | POST | /posts/{id}/pin | Pin post to the top of listings (admin only) |
+//! | DELETE | /posts/{id}/pin | Unpin post (admin only) |
+//! | POST | /posts/{id}/schedule | Schedule post to publish at `publish_at` |
+//! | DELETE | /posts/{id}/schedule | Cancel scheduled publication |
+//! | POST | /posts/{id}/feature-image | Set feature image from `file_id` or an https `url` |
+//! | DELETE | /posts/{id}/feature-image | Remove feature image |
+//! | POST | /posts/{id}/validate-links | Check the post's external https links, returning each URL's HTTP status (0 if unreachable) |
+//! | POST | /posts/{id}/like | Like a post (once per IP) |
 //! | GET | /posts/{id}/comments | Get post comments |
 //! | POST | /posts/{id}/comments | Add comment to post |
+//! | GET | /posts/{id}/toc | Get table of contents from the post's headings |
+//! | GET | /posts/{id}/og | Get OpenGraph sharing preview data (cached for 5 minutes) |
+//! | GET | /posts/{id}/related | Get related posts by shared tags |
+//! | GET | /posts/{id}/adjacent | Get the previous and next published posts |
+//! | GET | /posts/{id}/prev | Get the previously published post, or `null` |
+//! | GET | /posts/{id}/next | Get the next published post, or `null` |
 //! | GET | /posts/{id}/tags | Get post tags |
 //! | POST | /posts/{id}/tags | Add tag to post |
+//! | PUT | /posts/{id}/tags | Replace all tags of a post |
 //! | DELETE | /posts/{id}/tags/{tag_id} | Remove tag from post |
 
 use axum::{
     extract::{Path, Query, State},
+    http::header,
     response::IntoResponse,
     Json, Router,
 };
-use domain::post::{CreatePost, SearchPostsRequest, UpdatePost};
+use chrono::{DateTime, Utc};
+use domain::post::{
+    BulkPublishRequest, CreatePost, FeatureImageSource, SchedulePostRequest, SearchPostsRequest,
+    SetFeatureImageRequest, UpdatePost,
+};
 use serde::Deserialize;
+use std::io::Write;
 use uuid::Uuid;
 
 use crate::{
     error::ApiError,
-    middleware::auth::Claims,
-    response::{helpers as resp, Pagination},
+    middleware::{
+        auth::{Claims, OptionalClaims},
+        etag, ClientIp,
+    },
+    response::{helpers as resp, ApiResponse, Pagination},
     state::AppState,
 };
-use domain::{POST_CREATE, USER_MANAGE};
+use domain::{
+    ExportPost, ImportError, ImportPostsResponse, PostDetail, PostSearchRequest, SortDir,
+    SortField, POST_CREATE, USER_MANAGE,
+};
 
 /// Query parameters for listing posts
 #[derive(Debug, Deserialize)]
@@ -50,18 +87,38 @@ pub struct ListPostsQuery {
     author: Option<String>,
     /// Filter by category ID
     category: Option<String>,
+    /// Include posts of the category's descendants (with `category` only)
+    #[serde(default)]
+    recursive: bool,
     /// Filter by tag ID
     tag: Option<String>,
     /// Filter by status: "published", "draft", or "all" (admin/owner only)
     #[serde(default = "default_status")]
     status: String,
+    /// Also list archived posts (admin only, ignored with other filters)
+    #[serde(default)]
+    include_archived: bool,
+    /// "views" to list the most viewed published posts first (ignored with other filters)
+    sort: Option<String>,
 }
 
 /// Query parameters for searching posts
+///
+/// Either `q` or at least one field filter must be given, but not both.
 #[derive(Debug, Deserialize)]
 pub struct SearchQuery {
-    /// Search query string
-    q: String,
+    /// Search query string, matched against title and content
+    q: Option<String>,
+    title_contains: Option<String>,
+    content_contains: Option<String>,
+    author_id: Option<Uuid>,
+    category_id: Option<Uuid>,
+    /// Comma-separated tag IDs; posts must carry all of them
+    tag_ids: Option<String>,
+    published_after: Option<DateTime<Utc>>,
+    published_before: Option<DateTime<Utc>>,
+    sort_by: Option<SortField>,
+    sort_dir: Option<SortDir>,
     /// Page number
     #[serde(default = "default_page")]
     page: u64,
@@ -70,6 +127,21 @@ pub struct SearchQuery {
     per_page: u64,
 }
 
+/// Query parameters for related posts
+#[derive(Debug, Deserialize)]
+pub struct RelatedPostsQuery {
+    /// Maximum number of posts
+    #[serde(default = "default_related_limit")]
+    limit: u64,
+}
+
+/// Query parameters for a month of the archive
+#[derive(Debug, Deserialize)]
+pub struct ArchiveQuery {
+    /// Maximum number of posts (default: 20, max: 100)
+    limit: Option<u64>,
+}
+
 /// Request body for partial post update (PATCH)
 #[derive(Debug, Deserialize)]
 pub struct PatchPostRequest {
@@ -77,6 +149,8 @@ pub struct PatchPostRequest {
     title: Option<String>,
     /// Post content
     content: Option<String>,
+    /// Post summary (empty to generate it from the content)
+    summary: Option<String>,
     /// Category ID (null to remove)
     category_id: Option<String>,
     /// Post status: "published" or "draft"
@@ -89,6 +163,12 @@ pub struct AddTagRequest {
     tag_id: String,
 }
 
+/// Request body for replacing all tags of a post
+#[derive(Debug, Deserialize)]
+pub struct SetTagsRequest {
+    tag_ids: Vec<String>,
+}
+
 fn default_page() -> u64 {
     1
 }
@@ -101,22 +181,71 @@ fn default_status() -> String {
     "published".to_string()
 }
 
+fn default_related_limit() -> u64 {
+    5
+}
+
+/// Upper bound on `limit` for related posts
+const MAX_RELATED_POSTS: u64 = 20;
+
+/// Upper bound on `limit` for a month of the archive
+const MAX_ARCHIVE_POSTS: u64 = 100;
+
 /// Create post routes
 pub fn routes() -> Router<AppState> {
     Router::new()
         // Public routes
-        .route("/", axum::routing::get(list_posts))
+        .route(
+            "/",
+            axum::routing::get(list_posts).layer(axum::middleware::from_fn(etag)),
+        )
         .route("/search", axum::routing::get(search_posts))
-        .route("/{id}", axum::routing::get(get_post))
+        .route("/archive", axum::routing::get(list_archive_months))
+        .route(
+            "/archive/{year}/{month}",
+            axum::routing::get(list_posts_for_month),
+        )
+        .route(
+            "/{id}",
+            axum::routing::get(get_post).layer(axum::middleware::from_fn(etag)),
+        )
         .route("/{id}/comments", axum::routing::get(list_post_comments))
         .route("/{id}/tags", axum::routing::get(get_post_tags))
+        .route("/{id}/toc", axum::routing::get(get_post_toc))
+        .route("/{id}/og", axum::routing::get(get_post_og_data))
+        .route("/{id}/adjacent", axum::routing::get(get_adjacent_posts))
+        .route("/{id}/prev", axum::routing::get(get_prev_post))
+        .route("/{id}/next", axum::routing::get(get_next_post))
+        .route("/{id}/related", axum::routing::get(get_related_posts))
+        .route("/{id}/like", axum::routing::post(like_post))
         // Protected routes
         .route("/", axum::routing::post(create_post))
+        .route("/bulk-publish", axum::routing::post(bulk_publish_posts))
+        .route("/bulk-unpublish", axum::routing::post(bulk_unpublish_posts))
+        .route("/export", axum::routing::get(export_posts))
         .route("/{id}", axum::routing::put(update_post))
         .route("/{id}", axum::routing::patch(patch_post))
         .route("/{id}", axum::routing::delete(delete_post))
+        .route("/{id}/restore", axum::routing::post(restore_post))
+        .route("/{id}/archive", axum::routing::post(archive_post))
+        .route("/{id}/unarchive", axum::routing::post(unarchive_post))
+        .route("/{id}/duplicate", axum::routing::post(duplicate_post))
+        .route(
+            "/{id}/pin",
+            axum::routing::post(pin_post).delete(unpin_post),
+        )
+        .route(
+            "/{id}/schedule",
+            axum::routing::post(schedule_post).delete(cancel_post_schedule),
+        )
+        .route(
+            "/{id}/feature-image",
+            axum::routing::post(set_feature_image).delete(clear_feature_image),
+        )
+        .route("/{id}/validate-links", axum::routing::post(validate_links))
         .route("/{id}/comments", axum::routing::post(create_comment))
         .route("/{id}/tags", axum::routing::post(add_post_tag))
+        .route("/{id}/tags", axum::routing::put(set_post_tags))
         .route(
             "/{id}/tags/{tag_id}",
             axum::routing::delete(remove_post_tag),
@@ -124,6 +253,14 @@ pub fn routes() -> Router<AppState> {
         .route("/{id}/indexnow", axum::routing::post(submit_to_indexnow))
 }
 
+/// Create post routes that accept file uploads
+///
+/// Kept apart from [`routes`] so they get the upload body limit instead of
+/// the JSON one.
+pub fn upload_routes() -> Router<AppState> {
+    Router::new().route("/import", axum::routing::post(import_posts))
+}
+
 /// GET /posts
 /// List posts with optional filtering
 ///
@@ -134,13 +271,23 @@ pub fn routes() -> Router<AppState> {
 /// - category: Filter by category UUID
 /// - tag: Filter by tag UUID
 /// - status: "published", "draft", or "all" (admin/owner only)
+/// - include_archived: Append archived posts (admin only, unfiltered lists)
+///
+/// Without filters, sorting or drafts, each published post also carries
+/// `author_username`, `author_avatar_url` and its `tags`.
 async fn list_posts(
     State(state): State<AppState>,
-    user: Option<Claims>,
+    OptionalClaims(user): OptionalClaims,
     Query(params): Query<ListPostsQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Calculate offset from page
-    let _offset = (params.page - 1) * params.per_page;
+    let offset = params.page.saturating_sub(1) * params.per_page;
+
+    if params.sort.as_deref().is_some_and(|sort| sort != "views") {
+        return Err(ApiError::Validation(
+            "Invalid sort: expected \"views\"".to_string(),
+        ));
+    }
 
     // Check if user is admin
     let is_admin = user
@@ -149,9 +296,37 @@ async fn list_posts(
 
     // Determine which posts to show based on status filter
     let show_drafts = params.status == "draft" || params.status == "all";
+    let show_archived = params.include_archived
+        && is_admin
+        && params.author.is_none()
+        && params.category.is_none()
+        && params.tag.is_none();
+
+    // The plain listing of published posts comes with author details and tags
+    let lists_drafts = is_admin && show_drafts;
+    if params.author.is_none()
+        && params.category.is_none()
+        && params.tag.is_none()
+        && params.sort.is_none()
+        && !lists_drafts
+        && !show_archived
+    {
+        let posts = state
+            .post_service
+            .list_published_with_tags(Some(params.per_page), offset)
+            .await
+            .map_err(ApiError::Domain)?;
+        let total = state
+            .post_service
+            .count_published()
+            .await
+            .map_err(ApiError::Domain)?;
+        let pagination = Pagination::new(params.page, params.per_page, total);
+        return Ok(resp::list(posts, pagination));
+    }
 
     // Get posts based on filters
-    let posts = if let Some(author_id) = params.author {
+    let mut posts = if let Some(author_id) = params.author {
         let author_uuid = Uuid::parse_str(&author_id)
             .map_err(|e| ApiError::Validation(format!("Invalid author ID: {}", e)))?;
 
@@ -178,7 +353,7 @@ async fn list_posts(
             .map_err(|e| ApiError::Validation(format!("Invalid category ID: {}", e)))?;
         state
             .post_service
-            .list_by_category(category_uuid, Some(params.per_page))
+            .list_by_category(category_uuid, Some(params.per_page), params.recursive)
             .await
             .map_err(ApiError::Domain)?
     } else if let Some(tag_id) = params.tag {
@@ -205,14 +380,30 @@ async fn list_posts(
                 .into_iter()
                 .filter(|p| !p.is_published())
                 .collect(),
+            _ if params.sort.is_some() => state
+                .post_service
+                .list_published_by_views(Some(params.per_page))
+                .await
+                .map_err(ApiError::Domain)?,
             _ => state
                 .post_service
-                .list_published(Some(params.per_page))
+                .list_published(Some(params.per_page), offset)
                 .await
                 .map_err(ApiError::Domain)?,
         }
     };
 
+    // Archived posts live in their own table and are only listed on request
+    if show_archived {
+        posts.extend(
+            state
+                .post_service
+                .list_archived(Some(params.per_page))
+                .await
+                .map_err(ApiError::Domain)?,
+        );
+    }
+
     // TODO: Get total count for pagination
     let total = posts.len() as u64;
 
@@ -235,7 +426,7 @@ async fn create_post(
 
     // Verify user exists before creating post
     state.user_service.get(user_id).await.map_err(|e| match e {
-        domain::Error::NotFound(_) => {
+        e if e.is_not_found() => {
             ApiError::Unauthorized("User not found. Please log in again.".to_string())
         }
         _ => ApiError::Domain(e),
@@ -243,7 +434,13 @@ async fn create_post(
 
     let post = state
         .post_service
-        .create(user_id, input.title, input.content)
+        .create(
+            user_id,
+            input.title,
+            input.content,
+            input.summary,
+            user.permissions,
+        )
         .await
         .map_err(ApiError::Domain)?;
 
@@ -256,34 +453,152 @@ async fn create_post(
     Ok(resp::created(post))
 }
 
+/// POST /posts/import
+/// Import one or more Markdown files with YAML front-matter (multipart)
+///
+/// Files without a `.md` extension are skipped; each failing file is
+/// reported without aborting the others.
+async fn import_posts(
+    State(state): State<AppState>,
+    user: Claims,
+    mut multipart: axum_extra::extract::Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    domain::check_permission(user.permissions, POST_CREATE)
+        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let mut summary = ImportPostsResponse::default();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read multipart: {}", e)))?
+    {
+        let Some(filename) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        if !filename.to_lowercase().ends_with(".md") {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let data = field
+            .bytes()
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to read file data: {}", e)))?;
+        let result = match String::from_utf8(data.to_vec()) {
+            Ok(markdown) => state
+                .post_service
+                .import(user_id, &markdown, user.permissions)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(_) => Err("File is not valid UTF-8".to_string()),
+        };
+
+        match result {
+            Ok(_) => summary.imported += 1,
+            Err(error) => summary.errors.push(ImportError { filename, error }),
+        }
+    }
+
+    if summary.imported > 0 {
+        state
+            .rss_service
+            .refresh_cache()
+            .await
+            .map_err(ApiError::Domain)?;
+    }
+
+    Ok(resp::ok(summary))
+}
+
+/// GET /posts/export
+/// Download every post as a ZIP archive of Markdown files (admin only)
+async fn export_posts(
+    State(state): State<AppState>,
+    user: Claims,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let files = state
+        .post_service
+        .export(user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+    let archive = build_zip(&files)
+        .map_err(|e| ApiError::Internal(format!("Failed to build export archive: {}", e)))?;
+
+    let disposition = format!(
+        "attachment; filename=\"posts-{}.zip\"",
+        chrono::Utc::now().format("%Y-%m-%d")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        archive,
+    ))
+}
+
+/// Pack exported posts into an in-memory ZIP archive
+fn build_zip(files: &[ExportPost]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+
+    for file in files {
+        zip.start_file(file.filename.as_str(), options)?;
+        zip.write_all(file.content.as_bytes())?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
 /// GET /posts/{id}
 /// Get a single post by ID
 async fn get_post(
     State(state): State<AppState>,
-    user: Option<Claims>,
+    OptionalClaims(user): OptionalClaims,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
     let post = state.post_service.get(id).await.map_err(ApiError::Domain)?;
 
-    // Check visibility
-    if post.is_published() {
-        return Ok(resp::ok(post));
-    }
-
     // Draft posts: only owner or admin can view
-    match user {
-        Some(current_user) => {
-            let current_user_id = Uuid::parse_str(&current_user.sub)
-                .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
-
-            if current_user_id == post.user_id || (current_user.permissions & USER_MANAGE) != 0 {
-                Ok(resp::ok(post))
-            } else {
-                Err(ApiError::NotFound("Post not found".to_string()))
-            }
+    if !post.is_published() {
+        let current_user = user.ok_or_else(|| ApiError::NotFound("Post not found".to_string()))?;
+        let current_user_id = Uuid::parse_str(&current_user.sub)
+            .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+        if current_user_id != post.user_id && (current_user.permissions & USER_MANAGE) == 0 {
+            return Err(ApiError::NotFound("Post not found".to_string()));
         }
-        None => Err(ApiError::NotFound("Post not found".to_string())),
     }
+
+    let likes = state
+        .post_service
+        .count_likes(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(PostDetail { post, likes }))
+}
+
+/// POST /posts/{id}/like
+/// Like a published post (no auth; counted once per client IP)
+async fn like_post(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let response = state
+        .post_service
+        .like(id, &ip)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(response))
 }
 
 /// PUT /posts/{id}
@@ -299,7 +614,14 @@ async fn update_post(
 
     let post = state
         .post_service
-        .update(id, input.title, input.content, user_id, user.permissions)
+        .update(
+            id,
+            input.title,
+            input.content,
+            input.summary,
+            user_id,
+            user.permissions,
+        )
         .await
         .map_err(ApiError::Domain)?;
 
@@ -338,6 +660,7 @@ async fn patch_post(
     let has_category_update = input.category_id.is_some();
     let has_title_update = input.title.is_some();
     let has_content_update = input.content.is_some();
+    let has_summary_update = input.summary.is_some();
     let _has_status_update = input.status.is_some();
 
     // Handle status change (publish/unpublish)
@@ -388,13 +711,20 @@ async fn patch_post(
             .map_err(ApiError::Domain)?;
     }
 
-    // Handle title/content update
-    if has_title_update || has_content_update {
+    // Handle title/content/summary update
+    if has_title_update || has_content_update || has_summary_update {
         let title = input.title.unwrap_or_else(|| post.title.clone());
         let content = input.content.unwrap_or_else(|| post.content.clone());
         post = state
             .post_service
-            .update(id, Some(title), Some(content), user_id, user.permissions)
+            .update(
+                id,
+                Some(title),
+                Some(content),
+                input.summary,
+                user_id,
+                user.permissions,
+            )
             .await
             .map_err(ApiError::Domain)?;
 
@@ -406,7 +736,7 @@ async fn patch_post(
     }
 
     // Refresh post data if we only changed category
-    if has_category_update && !has_title_update && !has_content_update {
+    if has_category_update && !has_title_update && !has_content_update && !has_summary_update {
         post = state.post_service.get(id).await.map_err(ApiError::Domain)?;
     }
 
@@ -438,26 +768,370 @@ async fn delete_post(
     Ok(resp::no_content())
 }
 
+/// POST /posts/bulk-publish
+/// Publish several posts at once, reporting per-post failures
+async fn bulk_publish_posts(
+    State(state): State<AppState>,
+    user: Claims,
+    Json(input): Json<BulkPublishRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let result = state
+        .post_service
+        .bulk_publish(input.post_ids, input.schedule_at, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    state
+        .rss_service
+        .refresh_cache()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(result))
+}
+
+/// POST /posts/bulk-unpublish
+/// Unpublish several posts at once; `schedule_at` is ignored
+async fn bulk_unpublish_posts(
+    State(state): State<AppState>,
+    user: Claims,
+    Json(input): Json<BulkPublishRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let result = state
+        .post_service
+        .bulk_unpublish(input.post_ids, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    state
+        .rss_service
+        .refresh_cache()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(result))
+}
+
+/// POST /posts/{id}/archive
+/// Move a post to the archive (owner or admin, requires POST_DELETE)
+async fn archive_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    state
+        .post_service
+        .archive(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    state
+        .rss_service
+        .refresh_cache()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::no_content())
+}
+
+/// POST /posts/{id}/unarchive
+/// Move an archived post back to the live posts
+async fn unarchive_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .unarchive(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    state
+        .rss_service
+        .refresh_cache()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// POST /posts/{id}/duplicate
+/// Copy a post into a new draft owned by the current user
+async fn duplicate_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    domain::check_permission(user.permissions, POST_CREATE)
+        .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .duplicate(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::created(post))
+}
+
+/// POST /posts/{id}/pin
+/// Pin a post to the top of listings (admin only)
+async fn pin_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .pin(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// DELETE /posts/{id}/pin
+/// Unpin a post (admin only)
+async fn unpin_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .unpin(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// POST /posts/{id}/schedule
+/// Schedule an unpublished post to go live at `publish_at`
+async fn schedule_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SchedulePostRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .schedule(id, input.publish_at, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// DELETE /posts/{id}/schedule
+/// Cancel a scheduled publication, leaving the post as a draft
+async fn cancel_post_schedule(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .cancel_schedule(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// POST /posts/{id}/feature-image
+/// Set the post's hero image from an uploaded file or an external https URL
+async fn set_feature_image(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetFeatureImageRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+    let source = FeatureImageSource::try_from(input).map_err(ApiError::Domain)?;
+
+    let post = state
+        .post_service
+        .set_feature_image(id, source, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// DELETE /posts/{id}/feature-image
+/// Remove the post's hero image
+async fn clear_feature_image(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .clear_feature_image(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
+/// POST /posts/{id}/validate-links
+/// Check the external links in the post's content for broken URLs
+async fn validate_links(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let results = state
+        .post_service
+        .validate_links(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(results))
+}
+
+/// POST /posts/{id}/restore
+/// Restore a soft-deleted post (admin only)
+async fn restore_post(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let post = state
+        .post_service
+        .restore(id, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    state
+        .rss_service
+        .refresh_cache()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(post))
+}
+
 /// GET /posts/search
 /// Search posts
+///
+/// Query parameters:
+/// - q: Text matched against title or content
+/// - title_contains, content_contains, author_id, category_id,
+///   tag_ids (comma-separated), published_after, published_before:
+///   field filters, all of which must match
+/// - sort_by: published_at (default), created_at, views or title
+/// - sort_dir: desc (default) or asc
+/// - page, per_page: Pagination
 async fn search_posts(
     State(state): State<AppState>,
     Query(params): Query<SearchQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let request = SearchPostsRequest {
-        query: params.q,
-        limit: Some(params.per_page),
-        offset: Some((params.page - 1) * params.per_page),
-    };
+    let started = std::time::Instant::now();
+    let limit = params.per_page;
+    let offset = params.page.saturating_sub(1) * params.per_page;
+
+    let mut builder = PostSearchRequest::builder()
+        .tag_ids(parse_tag_ids(params.tag_ids.as_deref())?)
+        .sort_by(params.sort_by.unwrap_or_default())
+        .sort_dir(params.sort_dir.unwrap_or_default())
+        .limit(limit)
+        .offset(offset);
+    if let Some(text) = params.title_contains {
+        builder = builder.title_contains(text);
+    }
+    if let Some(text) = params.content_contains {
+        builder = builder.content_contains(text);
+    }
+    if let Some(author_id) = params.author_id {
+        builder = builder.author_id(author_id);
+    }
+    if let Some(category_id) = params.category_id {
+        builder = builder.category_id(category_id);
+    }
+    if let Some(after) = params.published_after {
+        builder = builder.published_after(after);
+    }
+    if let Some(before) = params.published_before {
+        builder = builder.published_before(before);
+    }
+    let advanced = builder.build();
 
-    let response = state
-        .post_service
-        .search(request)
-        .await
-        .map_err(ApiError::Domain)?;
+    let response = match (params.q, advanced.has_filter()) {
+        (Some(_), true) => {
+            return Err(ApiError::Validation(
+                "q cannot be combined with field filters".to_string(),
+            ))
+        }
+        (None, true) => state.post_service.search_advanced(advanced).await,
+        (q, false) => {
+            state
+                .post_service
+                .search(SearchPostsRequest {
+                    query: q.unwrap_or_default(),
+                    limit: Some(limit),
+                    offset: Some(offset),
+                })
+                .await
+        }
+    }
+    .map_err(ApiError::Domain)?;
 
     let pagination = Pagination::new(params.page, params.per_page, response.total);
-    Ok(resp::list(response.posts, pagination))
+    Ok(ApiResponse::list(response.posts, pagination)
+        .with_took_ms(started.elapsed().as_millis() as u64))
+}
+
+/// Parse a comma-separated list of tag IDs, ignoring blank entries
+fn parse_tag_ids(raw: Option<&str>) -> Result<Vec<Uuid>, ApiError> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            Uuid::parse_str(id)
+                .map_err(|e| ApiError::Validation(format!("Invalid tag ID '{}': {}", id, e)))
+        })
+        .collect()
 }
 
 /// GET /posts/{id}/tags
@@ -475,6 +1149,166 @@ async fn get_post_tags(
     Ok(resp::ok(tags))
 }
 
+/// GET /posts/{id}/toc
+/// Get the headings of a published post with anchors to link to
+async fn get_post_toc(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let toc = state
+        .post_service
+        .extract_toc(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(toc))
+}
+
+/// GET /posts/{id}/adjacent
+/// Get the published posts just before and after a published post
+async fn get_adjacent_posts(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let adjacent = state
+        .post_service
+        .get_adjacent(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(adjacent))
+}
+
+/// GET /posts/{id}/prev
+/// Get the post published just before a published post
+async fn get_prev_post(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let prev = state
+        .post_service
+        .get_prev(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(prev))
+}
+
+/// GET /posts/{id}/next
+/// Get the post published just after a published post
+async fn get_next_post(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let next = state
+        .post_service
+        .get_next(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(next))
+}
+
+/// GET /posts/{id}/og
+/// Get title, description, image, author and tags of a published post for
+/// social sharing previews
+async fn get_post_og_data(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let og_data = state
+        .post_service
+        .get_og_data(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok((
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        resp::ok(og_data),
+    ))
+}
+
+/// GET /posts/{id}/related
+/// Get published posts sharing tags with this post
+///
+/// Query parameters:
+/// - limit: Maximum number of posts (default: 5, max: 20)
+async fn get_related_posts(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<RelatedPostsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let posts = state
+        .post_service
+        .get_related(id, params.limit.clamp(1, MAX_RELATED_POSTS))
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(posts))
+}
+
+/// GET /posts/archive
+/// List months that have published posts, newest first
+async fn list_archive_months(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
+    let months = state
+        .post_service
+        .list_archive_months()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(months))
+}
+
+/// GET /posts/archive/{year}/{month}
+/// List posts published in a calendar month (UTC), newest first
+///
+/// Query parameters:
+/// - limit: Maximum number of posts (default: 20, max: 100)
+async fn list_posts_for_month(
+    State(state): State<AppState>,
+    Path((year, month)): Path<(i32, u32)>,
+    Query(params): Query<ArchiveQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let posts = state
+        .post_service
+        .list_for_month(
+            year,
+            month,
+            params.limit.map(|limit| limit.clamp(1, MAX_ARCHIVE_POSTS)),
+        )
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(posts))
+}
+
+/// PUT /posts/{id}/tags
+/// Replace all tags of a post
+async fn set_post_tags(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetTagsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let tag_ids = input
+        .tag_ids
+        .iter()
+        .map(|tag_id| Uuid::parse_str(tag_id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ApiError::Validation(format!("Invalid tag ID: {}", e)))?;
+
+    let tags = state
+        .post_service
+        .set_tags(id, tag_ids, user_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(tags))
+}
+
 /// POST /posts/{id}/tags
 /// Add a tag to a post
 async fn add_post_tag(
@@ -530,14 +1364,17 @@ async fn remove_post_tag(
 }
 
 /// GET /posts/{id}/comments
-/// Get comments for a post
+/// Get comment threads for a post (moderators also see unmoderated comments)
 async fn list_post_comments(
     State(state): State<AppState>,
+    OptionalClaims(user): OptionalClaims,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
+    let viewer_id = user.and_then(|u| Uuid::parse_str(&u.sub).ok());
+
     let comments = state
         .comment_service
-        .list_post_comments(id, 100)
+        .list_post_comments_threaded(id, 100, viewer_id)
         .await
         .map_err(ApiError::Domain)?;
 
@@ -556,6 +1393,13 @@ async fn create_comment(
         .get("content")
         .and_then(|v| v.as_str())
         .ok_or_else(|| ApiError::Validation("Content is required".to_string()))?;
+    let parent_id = match input.get("parent_id").and_then(|v| v.as_str()) {
+        Some(raw) => Some(
+            Uuid::parse_str(raw)
+                .map_err(|_| ApiError::Validation("Invalid parent_id".to_string()))?,
+        ),
+        None => None,
+    };
 
     // Check if user is registered user (UUID) or GitHub user (username)
     let comment = if let Ok(user_id) = Uuid::parse_str(&user.sub) {
@@ -567,6 +1411,7 @@ async fn create_comment(
                 domain::CreateComment {
                     post_id: id,
                     content: content.to_string(),
+                    parent_id,
                 },
             )
             .await
@@ -580,6 +1425,7 @@ async fn create_comment(
                 user.avatar_url.clone(),
                 id,
                 content.to_string(),
+                parent_id,
             )
             .await
             .map_err(ApiError::Domain)?
@@ -616,3 +1462,48 @@ async fn submit_to_indexnow(
 
     Ok(resp::ok(post))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::FrontMatter;
+    use std::io::Read;
+
+    #[test]
+    fn test_build_zip_contains_every_post() {
+        let files: Vec<ExportPost> = ["first", "second"]
+            .iter()
+            .map(|slug| ExportPost {
+                filename: format!("{}.md", slug),
+                content: format!("---\ntitle: \"{}\"\nslug: \"{}\"\n---\n\nBody", slug, slug),
+            })
+            .collect();
+
+        let archive = build_zip(&files).unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive)).unwrap();
+        assert_eq!(zip.len(), 2);
+
+        let mut content = String::new();
+        zip.by_name("second.md")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        let (front_matter, body) = FrontMatter::parse(&content).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("second"));
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_parse_tag_ids_skips_blanks_and_rejects_garbage() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let ids = parse_tag_ids(Some(&format!("{}, ,{}", first, second))).unwrap();
+        assert_eq!(ids, vec![first, second]);
+        assert!(parse_tag_ids(None).unwrap().is_empty());
+        assert!(matches!(
+            parse_tag_ids(Some("not-a-uuid")),
+            Err(ApiError::Validation(_))
+        ));
+    }
+}
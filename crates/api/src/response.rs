@@ -20,11 +20,14 @@
 //!   "code": 200,
 //!   "message": "success",
 //!   "data": [ ... ],
-//!   "pagination": {
-//!     "page": 1,
-//!     "per_page": 20,
-//!     "total": 100,
-//!     "total_pages": 5
+//!   "meta": {
+//!     "pagination": {
+//!       "page": 1,
+//!       "per_page": 20,
+//!       "total": 100,
+//!       "total_pages": 5
+//!     },
+//!     "total": 100
 //!   }
 //! }
 //! ```
@@ -32,13 +35,20 @@
 //! ## Error Response
 //! ```json
 //! {
-//!   "code": 400,
-//!   "message": "Validation failed",
-//!   "errors": {
-//!     "field": ["error message"]
+//!   "code": 404,
+//!   "message": "Post not found",
+//!   "error": {
+//!     "type": "not_found",
+//!     "code": "POST_NOT_FOUND",
+//!     "message": "Post not found"
 //!   }
 //! }
 //! ```
+//!
+//! `error.code` is a stable machine-readable code clients can localize;
+//! validation errors about a single input also name it in `error.field`.
+//! Error responses sent while a request ID is assigned include it as
+//! `request_id`.
 
 use axum::{
     http::StatusCode,
@@ -47,6 +57,8 @@ use axum::{
 };
 use serde::Serialize;
 
+use crate::middleware::request_id::current_request_id;
+
 /// Pagination information for list responses
 #[derive(Debug, Serialize, Clone)]
 pub struct Pagination {
@@ -79,6 +91,33 @@ impl Pagination {
     }
 }
 
+/// Extra information attached to a response
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ResponseMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+    /// Total number of matching items, for lists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    /// Time spent producing the response, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub took_ms: Option<u64>,
+}
+
+/// Machine-readable error details
+#[derive(Debug, Serialize, Clone)]
+pub struct ApiErrorBody {
+    /// Error category such as `not_found` or `validation`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Specific error code such as `POST_NOT_FOUND`
+    pub code: String,
+    pub message: String,
+    /// Input field the error is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
 /// Standard API response wrapper
 #[derive(Debug, Serialize)]
 pub struct ApiResponse<T> {
@@ -87,37 +126,40 @@ pub struct ApiResponse<T> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pagination: Option<Pagination>,
+    pub meta: Option<ResponseMeta>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ApiErrorBody>,
+    /// ID of the failed request, for matching it with the server logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl<T: Serialize> ApiResponse<T> {
     /// Create a successful response with data
     pub fn success(data: T) -> Self {
-        Self {
-            code: 200,
-            message: "success".to_string(),
-            data: Some(data),
-            pagination: None,
-        }
+        Self::custom(200, "success", data)
+    }
+
+    /// Alias of [`Self::success`]
+    pub fn ok(data: T) -> Self {
+        Self::success(data)
     }
 
     /// Create a created response (201)
     pub fn created(data: T) -> Self {
-        Self {
-            code: 201,
-            message: "created".to_string(),
-            data: Some(data),
-            pagination: None,
-        }
+        Self::custom(201, "created", data)
     }
 
     /// Create a successful response with pagination
     pub fn list(data: T, pagination: Pagination) -> Self {
+        let total = pagination.total;
         Self {
-            code: 200,
-            message: "success".to_string(),
-            data: Some(data),
-            pagination: Some(pagination),
+            meta: Some(ResponseMeta {
+                pagination: Some(pagination),
+                total: Some(total),
+                took_ms: None,
+            }),
+            ..Self::success(data)
         }
     }
 
@@ -127,12 +169,54 @@ impl<T: Serialize> ApiResponse<T> {
             code,
             message: message.into(),
             data: Some(data),
-            pagination: None,
+            meta: None,
+            error: None,
+            request_id: None,
+        }
+    }
+
+    /// Record how long the request took in `meta.took_ms`
+    pub fn with_took_ms(mut self, took_ms: u64) -> Self {
+        self.meta.get_or_insert_with(ResponseMeta::default).took_ms = Some(took_ms);
+        self
+    }
+}
+
+impl ApiResponse<()> {
+    /// Create an error response without data
+    ///
+    /// The error code defaults to the upper-cased `kind`.
+    pub fn error(code: u16, kind: impl Into<String>, message: impl Into<String>) -> Self {
+        let kind = kind.into();
+        let message = message.into();
+        Self {
+            code,
+            message: message.clone(),
+            data: None,
+            meta: None,
+            error: Some(ApiErrorBody {
+                code: kind.to_uppercase(),
+                kind,
+                message,
+                field: None,
+            }),
+            request_id: current_request_id(),
         }
     }
 
+    /// Replace the error code and set the input field the error is about
+    pub fn with_error_code(mut self, code: impl Into<String>, field: Option<String>) -> Self {
+        if let Some(error) = self.error.as_mut() {
+            error.code = code.into();
+            error.field = field;
+        }
+        self
+    }
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
     /// Convert to HTTP response with appropriate status code
-    pub fn into_response(self) -> Response {
+    fn into_response(self) -> Response {
         let status = StatusCode::from_u16(self.code).unwrap_or(StatusCode::OK);
         (status, Json(self)).into_response()
     }
@@ -281,22 +365,23 @@ pub mod helpers {
 
     /// 400 Bad Request
     pub fn bad_request(message: impl Into<String>) -> Response {
-        ErrorResponse::new(400, message).into_response()
+        ApiResponse::error(400, "validation", message).into_response()
     }
 
     /// 401 Unauthorized
     pub fn unauthorized(message: impl Into<String>) -> Response {
-        ErrorResponse::unauthorized(message).into_response()
+        ApiResponse::error(401, "unauthorized", message).into_response()
     }
 
     /// 403 Forbidden
     pub fn forbidden(message: impl Into<String>) -> Response {
-        ErrorResponse::forbidden(message).into_response()
+        ApiResponse::error(403, "forbidden", message).into_response()
     }
 
     /// 404 Not Found
     pub fn not_found(resource: impl Into<String>) -> Response {
-        ErrorResponse::not_found(resource).into_response()
+        ApiResponse::error(404, "not_found", format!("{} not found", resource.into()))
+            .into_response()
     }
 
     /// 422 Validation Error
@@ -330,4 +415,27 @@ mod tests {
         let p = Pagination::from_limit_offset(20, 40, 100);
         assert_eq!(p.page, 3);
     }
+
+    #[test]
+    fn test_list_puts_pagination_in_meta() {
+        let body =
+            serde_json::to_value(ApiResponse::list(vec![1, 2], Pagination::new(1, 2, 5))).unwrap();
+        assert_eq!(body["data"], serde_json::json!([1, 2]));
+        assert_eq!(body["meta"]["pagination"]["total_pages"], 3);
+        assert_eq!(body["meta"]["total"], 5);
+        assert!(body.get("error").is_none());
+    }
+
+    #[test]
+    fn test_error_has_no_data() {
+        let response = ApiResponse::error(404, "not_found", "Post not found");
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["code"], 404);
+        assert_eq!(body["error"]["type"], "not_found");
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+        assert_eq!(body["error"]["message"], "Post not found");
+        assert!(body["error"].get("field").is_none());
+        assert!(body.get("data").is_none());
+        assert_eq!(response.into_response().status(), StatusCode::NOT_FOUND);
+    }
 }
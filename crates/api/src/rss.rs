@@ -1,8 +1,13 @@
 use axum::{
-    extract::State,
-    http::{header::CONTENT_TYPE, HeaderMap},
+    extract::{Path, Query, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderMap,
+    },
     response::{IntoResponse, Response},
 };
+use serde::Deserialize;
+use uuid::Uuid;
 
 use crate::{error::ApiError, state::AppState};
 
@@ -23,3 +28,40 @@ pub async fn get_rss_feed(State(state): State<AppState>) -> Result<Response, Api
 
     Ok((headers, feed).into_response())
 }
+
+/// Query parameters for a category feed
+#[derive(Debug, Deserialize)]
+pub struct CategoryFeedQuery {
+    /// Also include posts of subcategories
+    #[serde(default)]
+    recursive: bool,
+}
+
+/// GET /categories/{id}/feed.xml
+/// Get RSS feed of a category's published posts
+pub async fn get_category_feed(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<CategoryFeedQuery>,
+) -> Result<Response, ApiError> {
+    let category = state
+        .category_service
+        .get(id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let feed = state
+        .rss_service
+        .generate_category_rss(&category, params.recursive)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        "application/rss+xml; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(CACHE_CONTROL, "public, max-age=3600".parse().unwrap());
+
+    Ok((headers, feed).into_response())
+}
@@ -9,8 +9,8 @@
 //! - No special cases - all sessions follow the same rules
 
 use axum::{
-    extract::State,
-    http::{header, StatusCode},
+    extract::{Path, State},
+    http::{header, HeaderMap},
     response::IntoResponse,
     Json, Router,
 };
@@ -18,7 +18,15 @@ use domain::UserInfo;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{error::ApiError, middleware::auth::Claims, state::AppState};
+use crate::{
+    error::ApiError,
+    middleware::{
+        auth::{Claims, OptionalClaims},
+        ClientIp,
+    },
+    response::{helpers as resp, ApiResponse},
+    state::AppState,
+};
 
 // ============================================================================
 // Routes
@@ -30,6 +38,10 @@ pub fn routes() -> Router<AppState> {
         .route("/", axum::routing::post(create_session))
         // DELETE /api/sessions - Delete session (logout)
         .route("/", axum::routing::delete(delete_session))
+        // GET /api/sessions - List the current user's active sessions
+        .route("/", axum::routing::get(list_sessions))
+        // DELETE /api/sessions/{id} - Revoke one of the current user's sessions
+        .route("/{id}", axum::routing::delete(revoke_session))
         // GET /api/sessions/info - Get current session info
         .route("/info", axum::routing::get(get_session_info))
         // POST /api/sessions/github - Create session via GitHub OAuth
@@ -43,7 +55,9 @@ pub fn routes() -> Router<AppState> {
 /// Request to create a session
 #[derive(Debug, Deserialize)]
 pub struct CreateSessionRequest {
-    pub username: String,
+    /// Username, or the account's email address when it contains `@`
+    #[serde(alias = "username")]
+    pub username_or_email: String,
     pub password: String,
     pub remember_me: bool,
 }
@@ -59,33 +73,54 @@ pub struct SessionResponse {
 // Handlers
 // ============================================================================
 
+/// The client's `User-Agent` header, or `"unknown"` if missing or not text
+pub(crate) fn user_agent(headers: &HeaderMap) -> &str {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or("unknown")
+}
+
 /// POST /api/sessions
 /// Create a new session and set cookie
 pub async fn create_session(
     State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     Json(input): Json<CreateSessionRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Validate input
-    if input.username.trim().is_empty() || input.password.trim().is_empty() {
+    if input.username_or_email.trim().is_empty() || input.password.trim().is_empty() {
         return Err(ApiError::Validation(
-            "Username and password required".to_string(),
+            "Username or email and password required".to_string(),
         ));
     }
 
     // Login user
-    let user = state
+    let result = state
         .user_service
-        .login(input.username, input.password)
+        .login(input.username_or_email, input.password)
         .await
         .map_err(|e| match e {
             domain::Error::NotFound(msg) => ApiError::Unauthorized(msg),
             _ => ApiError::Domain(e),
         })?;
 
+    // Cookie sessions have no second step, so TOTP users must use /api/auth/login
+    let user = match result {
+        domain::LoginResult::Authenticated(user) => *user,
+        domain::LoginResult::RequiresOtp(_) => {
+            return Err(ApiError::Unauthorized(
+                "Two-factor authentication required".to_string(),
+            ));
+        }
+    };
+
     // Create session
     let session = state
         .session_service
-        .create_session(user.id, input.remember_me)
+        .create_session(user.id, input.remember_me, user_agent(&headers), &ip)
         .await?;
 
     // Set cookie
@@ -105,24 +140,71 @@ pub async fn create_session(
     };
 
     Ok((
-        StatusCode::CREATED,
         [(header::SET_COOKIE, cookie_value)],
-        Json(response),
+        ApiResponse::created(response),
     ))
 }
 
 /// DELETE /api/sessions
 /// Delete current session (logout)
-pub async fn delete_session(State(_state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
-    // Delete session (we'll get the token from cookie in a real implementation)
-    // For now, just return success - the cookie will be cleared on client side
+///
+/// Revokes the refresh token carried by the caller's JWT, if any, so it can
+/// no longer be exchanged for new access tokens.
+pub async fn delete_session(
+    OptionalClaims(user): OptionalClaims,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    if let Some(refresh_token) = user.and_then(|claims| claims.refresh_token) {
+        state
+            .session_service
+            .revoke_refresh_token(&refresh_token)
+            .await?;
+    }
 
     let cookie_value = "session_token=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0";
 
     Ok((
-        StatusCode::OK,
         [(header::SET_COOKIE, cookie_value)],
-        Json(serde_json::json!({ "message": "Logged out successfully" })),
+        ApiResponse::ok(serde_json::json!({ "message": "Logged out successfully" })),
+    ))
+}
+
+/// GET /api/sessions
+/// List the current user's active sessions with the device that opened them
+pub async fn list_sessions(
+    user: Claims,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let sessions = state
+        .session_service
+        .list_user_sessions(user_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(sessions))
+}
+
+/// DELETE /api/sessions/{id}
+/// Revoke one of the current user's sessions, e.g. on a lost device
+pub async fn revoke_session(
+    user: Claims,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    state
+        .session_service
+        .revoke_user_session(user_id, &id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(
+        serde_json::json!({ "message": "Session revoked" }),
     ))
 }
 
@@ -137,9 +219,11 @@ pub async fn get_session_info(
             .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?,
         username: user.username,
         permissions: user.permissions,
+        bio: None,
+        avatar_url: None,
     };
 
-    Ok((StatusCode::OK, Json(user_info)))
+    Ok(resp::ok(user_info))
 }
 
 /// POST /api/sessions/github
@@ -150,8 +234,28 @@ pub async fn github_callback(
 ) -> Result<impl IntoResponse, ApiError> {
     // This will be implemented to handle GitHub OAuth callback
     // For now, return placeholder
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({ "message": "GitHub OAuth not implemented yet" })),
+    Ok(resp::ok(
+        serde_json::json!({ "message": "GitHub OAuth not implemented yet" }),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_user_agent_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(user_agent(&headers), "unknown");
+
+        headers.insert(
+            header::USER_AGENT,
+            HeaderValue::from_static("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0"),
+        );
+        assert_eq!(
+            user_agent(&headers),
+            "Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Path, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderMap,
+    },
+    response::{IntoResponse, Response},
+};
+
+use crate::{error::ApiError, state::AppState};
+
+/// GET /sitemap.xml
+/// Get the sitemap index listing every child sitemap
+pub async fn get_sitemap_index(State(state): State<AppState>) -> Result<Response, ApiError> {
+    let xml = state
+        .sitemap_service
+        .generate_index()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(xml_response(xml))
+}
+
+/// GET /sitemap-{n}.xml
+/// Get one child sitemap of at most 1000 URLs
+pub async fn get_sitemap_page(
+    State(state): State<AppState>,
+    Path(file): Path<String>,
+) -> Result<Response, ApiError> {
+    let page = file
+        .strip_suffix(".xml")
+        .and_then(|n| n.parse::<usize>().ok())
+        .ok_or_else(|| ApiError::NotFound("Sitemap not found".to_string()))?;
+
+    let xml = state
+        .sitemap_service
+        .generate_page(page)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(xml_response(xml))
+}
+
+fn xml_response(xml: String) -> Response {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        "application/xml; charset=utf-8".parse().unwrap(),
+    );
+    headers.insert(CACHE_CONTROL, "max-age=3600".parse().unwrap());
+
+    (headers, xml).into_response()
+}
@@ -4,12 +4,13 @@
 //! The state uses trait objects for services, allowing for easy testing
 //! and swapping of different database backends.
 
-use config::AppConfig;
 use service::{
-    CategoryService, CommentService, ConfigService, FileService, PostService, RssService,
-    SessionService, StatsService, TagService, UserService,
+    CategoryService, CommentService, ConfigService, FileService, HealthService, PostService,
+    RssService, SessionService, SharedConfig, SitemapService, StatsService, TagService,
+    UserService,
 };
 use std::sync::Arc;
+use std::time::Instant;
 
 use crate::file_cache::FileCache;
 use crate::middleware::auth::AuthState;
@@ -21,8 +22,8 @@ use crate::middleware::auth::AuthState;
 /// (e.g., real database vs. mock for testing) without generic type parameters.
 #[derive(Clone)]
 pub struct AppState {
-    /// Application configuration
-    pub config: AppConfig,
+    /// Live application configuration, replaced on reload
+    pub config: SharedConfig,
 
     /// Config service with business logic for configuration operations
     pub config_service: Arc<ConfigService>,
@@ -54,6 +55,12 @@ pub struct AppState {
     /// RSS service for generating RSS feeds
     pub rss_service: Arc<dyn RssService>,
 
+    /// Sitemap service for generating XML sitemaps
+    pub sitemap_service: Arc<SitemapService>,
+
+    /// Health service for status probes
+    pub health_service: Arc<HealthService>,
+
     /// Authentication state for JWT token operations
     pub auth_state: AuthState,
 
@@ -65,6 +72,9 @@ pub struct AppState {
 
     /// Base URL for the application (used for OAuth callbacks)
     pub base_url: String,
+
+    /// When the state was built, used to report uptime
+    pub start_time: Instant,
 }
 
 impl AppState {
@@ -85,10 +95,11 @@ impl AppState {
 /// use api::{AppState, AuthState};
 /// use service::{PostService, UserService, SessionService, FileService, CommentService, StatsService, CategoryService, TagService};
 /// use infrastructure::{PostRepositoryImpl, UserRepositoryImpl, SessionRepositoryImpl, FileRepositoryImpl, CommentRepositoryImpl, StatsRepositoryImpl, CategoryRepositoryImpl, TagRepositoryImpl};
-/// use std::sync::Arc;
+/// use std::sync::{Arc, RwLock};
 ///
 /// // Initialize database connection
 /// let db = establish_connection(&database_url).await?;
+/// let config = Arc::new(RwLock::new(domain::Config::from(load_config()?)));
 /// let post_repo = Arc::new(PostRepositoryImpl::new(db.clone()));
 /// let user_repo = Arc::new(UserRepositoryImpl::new(db.clone()));
 /// let session_repo = Arc::new(SessionRepositoryImpl::new(db.clone()));
@@ -99,16 +110,18 @@ impl AppState {
 /// let tag_repo = Arc::new(TagRepositoryImpl::new(db));
 ///
 /// let post_service = PostService::new(post_repo);
-/// let user_service = UserService::new(user_repo.clone());
+/// let permission_service = Arc::new(PermissionService::new(user_repo.clone()));
+/// let user_service = UserService::new(user_repo.clone(), config.clone());
 /// let session_service = SessionService::new(session_repo);
-/// let file_service = FileService::new(file_repo, "/uploads".to_string(), "http://example.com".to_string());
-/// let comment_service = CommentService::new(comment_repo, user_repo, "client_id".to_string(), "client_secret".to_string());
+/// let file_service = FileService::new(file_repo, permission_service.clone(), "/uploads".to_string(), "http://example.com".to_string());
+/// let comment_service = CommentService::new(comment_repo, user_repo, permission_service.clone(), config.clone());
 /// let stats_service = StatsService::new(stats_repo);
-/// let category_service = CategoryService::new(category_repo);
+/// let category_service = CategoryService::new(category_repo, permission_service);
 /// let tag_service = TagService::new(tag_repo);
 /// let auth_state = AuthState::new("your-secret-key");
 ///
 /// let state = AppState::builder()
+///     .config(config)
 ///     .post_service(post_service)
 ///     .user_service(user_service)
 ///     .session_service(session_service)
@@ -124,7 +137,7 @@ impl AppState {
 /// ```
 #[derive(Default)]
 pub struct AppStateBuilder {
-    config: Option<AppConfig>,
+    config: Option<SharedConfig>,
     config_service: Option<ConfigService>,
     post_service: Option<PostService>,
     user_service: Option<UserService>,
@@ -135,6 +148,8 @@ pub struct AppStateBuilder {
     category_service: Option<CategoryService>,
     tag_service: Option<TagService>,
     rss_service: Option<Arc<dyn RssService>>,
+    sitemap_service: Option<SitemapService>,
+    health_service: Option<HealthService>,
     auth_state: Option<AuthState>,
     upload_dir: Option<String>,
     bing_cache: Option<FileCache>,
@@ -142,7 +157,7 @@ pub struct AppStateBuilder {
 }
 
 impl AppStateBuilder {
-    pub fn config(mut self, config: AppConfig) -> Self {
+    pub fn config(mut self, config: SharedConfig) -> Self {
         self.config = Some(config);
         self
     }
@@ -197,6 +212,16 @@ impl AppStateBuilder {
         self
     }
 
+    pub fn sitemap_service(mut self, service: SitemapService) -> Self {
+        self.sitemap_service = Some(service);
+        self
+    }
+
+    pub fn health_service(mut self, service: HealthService) -> Self {
+        self.health_service = Some(service);
+        self
+    }
+
     pub fn auth_state(mut self, state: AuthState) -> Self {
         self.auth_state = Some(state);
         self
@@ -237,10 +262,13 @@ impl AppStateBuilder {
             ),
             tag_service: Arc::new(self.tag_service.expect("tag_service must be set")),
             rss_service: self.rss_service.expect("rss_service must be set"),
+            sitemap_service: Arc::new(self.sitemap_service.expect("sitemap_service must be set")),
+            health_service: Arc::new(self.health_service.expect("health_service must be set")),
             auth_state: self.auth_state.expect("auth_state must be set"),
             upload_dir: self.upload_dir.expect("upload_dir must be set"),
             bing_cache: self.bing_cache.expect("bing_cache must be set"),
             base_url: self.base_url.expect("base_url must be set"),
+            start_time: Instant::now(),
         }
     }
 }
@@ -10,16 +10,18 @@
 //! - No special cases - all stats follow the same rules
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header::USER_AGENT, HeaderMap},
     response::{IntoResponse, Json},
     Router,
 };
 #[allow(unused_imports)]
 use domain::{RecordViewRequest, StatsResponse};
+use serde::Deserialize;
+use service::StatsService;
 use uuid::Uuid;
 
-use crate::{error::ApiError, state::AppState};
+use crate::{error::ApiError, middleware::ClientIp, response::helpers as resp, state::AppState};
 
 // ============================================================================
 // Routes
@@ -37,6 +39,32 @@ pub fn routes() -> Router<AppState> {
         .route("/posts/{id}/views", axum::routing::post(record_post_view))
         // GET /api/stats/total - Get total stats (admin)
         .route("/total", axum::routing::get(get_total_stats))
+        // GET /api/stats/daily - Get daily visit counts
+        .route("/daily", axum::routing::get(get_daily_stats))
+        // GET /api/stats/top-posts - Get the most viewed posts
+        .route("/top-posts", axum::routing::get(get_top_posts))
+}
+
+/// Query parameters for the daily visit series
+#[derive(Debug, Deserialize)]
+pub struct DailyStatsQuery {
+    #[serde(default = "default_days")]
+    days: u32,
+}
+
+fn default_days() -> u32 {
+    30
+}
+
+/// Query parameters for the most viewed posts
+#[derive(Debug, Deserialize)]
+pub struct TopPostsQuery {
+    #[serde(default = "default_top_posts_limit")]
+    limit: u64,
+}
+
+fn default_top_posts_limit() -> u64 {
+    10
 }
 
 // ============================================================================
@@ -54,7 +82,7 @@ pub async fn get_visits(State(state): State<AppState>) -> Result<impl IntoRespon
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((StatusCode::OK, Json(stats)))
+    Ok(resp::ok(stats))
 }
 
 /// POST /api/stats/visits
@@ -62,20 +90,24 @@ pub async fn get_visits(State(state): State<AppState>) -> Result<impl IntoRespon
 ///
 /// This endpoint is public - no authentication required.
 /// Request body: {"post_id": "uuid"} (optional)
+///
+/// Post views are deduplicated per visitor, identified by a hash of the
+/// client IP (`X-Forwarded-For`) and `User-Agent`.
 pub async fn record_visit(
     State(state): State<AppState>,
-    Json(input): Json<RecordViewRequest>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    Json(mut input): Json<RecordViewRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    input.visitor_fingerprint = Some(visitor_fingerprint(&ip, &headers));
+
     state
         .stats_service
         .record_view(input)
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({ "message": "Visit recorded" })),
-    ))
+    Ok(resp::ok(serde_json::json!({ "message": "Visit recorded" })))
 }
 
 /// GET /api/stats/posts/:id/views
@@ -95,7 +127,7 @@ pub async fn get_post_views(
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((StatusCode::OK, Json(stats)))
+    Ok(resp::ok(stats))
 }
 
 /// POST /api/stats/posts/:id/views
@@ -104,6 +136,8 @@ pub async fn get_post_views(
 /// This endpoint is public - no authentication required.
 pub async fn record_post_view(
     State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let post_id = Uuid::parse_str(&id)
@@ -111,6 +145,7 @@ pub async fn record_post_view(
 
     let request = RecordViewRequest {
         post_id: Some(post_id),
+        visitor_fingerprint: Some(visitor_fingerprint(&ip, &headers)),
     };
 
     state
@@ -119,10 +154,7 @@ pub async fn record_post_view(
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({ "message": "View recorded" })),
-    ))
+    Ok(resp::ok(serde_json::json!({ "message": "View recorded" })))
 }
 
 /// GET /api/stats/total
@@ -139,5 +171,47 @@ pub async fn get_total_stats(State(state): State<AppState>) -> Result<impl IntoR
         .await
         .map_err(ApiError::Domain)?;
 
-    Ok((StatusCode::OK, Json(stats)))
+    Ok(resp::ok(stats))
+}
+
+/// GET /api/stats/daily?days=30
+/// Get visit counts for each of the last `days` days (max 365), oldest first
+///
+/// This endpoint is public - no authentication required.
+/// Response: `[{"date": "2025-01-01", "visits": 12}, ...]`
+pub async fn get_daily_stats(
+    State(state): State<AppState>,
+    Query(params): Query<DailyStatsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stats = state
+        .stats_service
+        .get_daily_stats(params.days)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(stats))
+}
+
+/// GET /api/stats/top-posts?limit=10
+/// Get the most viewed published posts (max 50), highest first
+///
+/// This endpoint is public - no authentication required.
+/// Response: `[{"post_id": "...", "views": 42, "last_viewed_at": "..."}, ...]`
+pub async fn get_top_posts(
+    State(state): State<AppState>,
+    Query(params): Query<TopPostsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let top_posts = state
+        .stats_service
+        .get_top_posts(params.limit)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(top_posts))
+}
+
+/// Anonymous visitor fingerprint from the client IP and User-Agent
+fn visitor_fingerprint(ip: &str, headers: &HeaderMap) -> String {
+    let user_agent = headers.get(USER_AGENT).and_then(|h| h.to_str().ok());
+    StatsService::visitor_fingerprint(ip, user_agent)
 }
@@ -9,9 +9,13 @@
 //! |--------|----------|-------------|
 //! | GET | /tags | List all tags |
 //! | POST | /tags | Create tag (admin) |
+//! | POST | /tags/suggest | Suggest existing tags for post content |
+//! | GET | /tags/cloud | Tags with published post counts and weights, most used first |
 //! | GET | /tags/{id} | Get tag details |
 //! | GET | /tags/{id}/posts | Get posts with tag |
+//! | PUT | /tags/{id} | Rename tag (admin) |
 //! | DELETE | /tags/{id} | Delete tag (admin) |
+//! | POST | /tags/{id}/merge | Merge tag into another (admin) |
 
 use axum::{
     extract::{Path, Query, State},
@@ -49,6 +53,27 @@ pub struct ListPostsQuery {
     per_page: u64,
 }
 
+/// Request body for suggesting tags
+#[derive(Debug, Deserialize)]
+pub struct SuggestTagsRequest {
+    pub content: String,
+    /// Maximum number of suggestions (default 5, max 20)
+    #[serde(default = "default_suggestion_limit")]
+    pub limit: u64,
+}
+
+/// Request body for renaming a tag
+#[derive(Debug, Deserialize)]
+pub struct RenameTagRequest {
+    pub name: String,
+}
+
+/// Request body for merging a tag into another
+#[derive(Debug, Deserialize)]
+pub struct MergeTagsRequest {
+    pub target_id: Uuid,
+}
+
 fn default_page() -> u64 {
     1
 }
@@ -57,16 +82,23 @@ fn default_per_page() -> u64 {
     50
 }
 
+fn default_suggestion_limit() -> u64 {
+    5
+}
+
 /// Create tag routes
 pub fn routes() -> Router<AppState> {
     Router::new()
         // Public routes
         .route("/", axum::routing::get(list_tags))
+        .route("/cloud", axum::routing::get(get_tag_cloud))
         .route("/{id}", axum::routing::get(get_tag))
         .route("/{id}/posts", axum::routing::get(list_tag_posts))
+        .route("/suggest", axum::routing::post(suggest_tags))
         // Admin routes
         .route("/", axum::routing::post(create_tag))
-        .route("/{id}", axum::routing::delete(delete_tag))
+        .route("/{id}", axum::routing::put(rename_tag).delete(delete_tag))
+        .route("/{id}/merge", axum::routing::post(merge_tag))
 }
 
 /// GET /tags
@@ -84,6 +116,33 @@ async fn list_tags(
     Ok(resp::list(tags, pagination))
 }
 
+/// GET /tags/cloud
+/// List tags used by published posts with counts and tag cloud weights
+async fn get_tag_cloud(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
+    let cloud = state
+        .tag_service
+        .get_cloud()
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(cloud))
+}
+
+/// POST /tags/suggest
+/// Suggest existing tags that appear frequently in the given content
+async fn suggest_tags(
+    State(state): State<AppState>,
+    Json(input): Json<SuggestTagsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let tags = state
+        .tag_service
+        .suggest_tags_for_content(&input.content, input.limit)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(tags))
+}
+
 /// POST /tags
 /// Create a new tag (admin only)
 async fn create_tag(
@@ -134,6 +193,23 @@ async fn list_tag_posts(
     Ok(resp::list(posts, pagination))
 }
 
+/// PUT /tags/{id}
+/// Rename a tag, deriving its slug from the new name (admin only)
+async fn rename_tag(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+    Json(input): Json<RenameTagRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let tag = state
+        .tag_service
+        .rename(id, &input.name, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(tag))
+}
+
 /// DELETE /tags/{id}
 /// Delete a tag (admin only)
 async fn delete_tag(
@@ -152,3 +228,20 @@ async fn delete_tag(
 
     Ok(resp::no_content())
 }
+
+/// POST /tags/{id}/merge
+/// Move all posts from this tag to `target_id` and delete it (admin only)
+async fn merge_tag(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(id): Path<Uuid>,
+    Json(input): Json<MergeTagsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    state
+        .tag_service
+        .merge(id, input.target_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::no_content())
+}
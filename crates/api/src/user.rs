@@ -7,23 +7,33 @@
 //! | Method | Endpoint | Description |
 //! |--------|----------|-------------|
 //! | GET | /users | List all users (admin) |
+//! | GET | /users/me | Get the caller's profile with post and comment counts |
+//! | GET | /users/me/storage | Get the caller's upload storage use against their quota |
 //! | GET | /users/{id} | Get user info |
-//! | PATCH | /users/{id} | Update user (self/admin) |
+//! | PATCH | /users/{id} | Update username, bio, avatar_url (self/admin) or permissions (admin) |
 //! | DELETE | /users/{id} | Delete user (self/admin) |
-//! | GET | /users/{id}/posts | Get user's posts |
+//! | GET | /users/{id}/posts | Get user's posts (`?stats=true` adds views and comment counts, self/admin) |
+//! | GET | /users/{id}/comments | Get user's comments with post titles (self/admin) |
+//! | DELETE | /users/{id}/sessions | Revoke all sessions, keeping the caller's own (self/admin) |
+//! | GET | /users/{id}/api-keys | List API keys (self/admin) |
+//! | POST | /users/{id}/api-keys | Create an API key (self/admin) |
+//! | DELETE | /users/{id}/api-keys/{key_id} | Revoke an API key (self/admin) |
+//! | POST | /users/{id}/api-keys/{key_id}/rotate | Replace an API key (self/admin) |
 
 use axum::{
     extract::{Path, Query, State},
     response::IntoResponse,
     Json, Router,
 };
-use domain::{check_ownership_or_admin, USER_MANAGE};
+use domain::{
+    check_ownership_or_admin, CreateApiKey, CreateApiKeyResponse, UpdateUser, USER_MANAGE,
+};
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
     error::ApiError,
-    middleware::auth::Claims,
+    middleware::auth::{Claims, OptionalClaims},
     response::{helpers as resp, Pagination},
     state::AppState,
 };
@@ -47,12 +57,18 @@ pub struct ListPostsQuery {
     /// Include draft posts (only for self or admin)
     #[serde(default)]
     include: Option<String>,
+    /// Return drafts too, each with view and comment counts (only for self or admin)
+    #[serde(default)]
+    stats: bool,
 }
 
-/// Request body for updating user permissions
+/// Query parameters for listing a user's comments
 #[derive(Debug, Deserialize)]
-pub struct UpdateUserRequest {
-    permissions: Option<u64>,
+pub struct ListCommentsQuery {
+    #[serde(default = "default_comment_limit")]
+    limit: u64,
+    #[serde(default)]
+    offset: u64,
 }
 
 fn default_page() -> u64 {
@@ -63,14 +79,37 @@ fn default_per_page() -> u64 {
     50
 }
 
+fn default_comment_limit() -> u64 {
+    20
+}
+
 /// Create user routes
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", axum::routing::get(list_users))
+        .route("/me", axum::routing::get(get_own_profile))
+        .route("/me/storage", axum::routing::get(get_own_storage))
         .route("/{id}", axum::routing::get(get_user))
         .route("/{id}", axum::routing::patch(update_user))
         .route("/{id}", axum::routing::delete(delete_user))
         .route("/{id}/posts", axum::routing::get(list_user_posts))
+        .route("/{id}/comments", axum::routing::get(list_user_comments))
+        .route(
+            "/{id}/sessions",
+            axum::routing::delete(revoke_user_sessions),
+        )
+        .route(
+            "/{id}/api-keys",
+            axum::routing::get(list_api_keys).post(create_api_key),
+        )
+        .route(
+            "/{id}/api-keys/{key_id}",
+            axum::routing::delete(revoke_api_key),
+        )
+        .route(
+            "/{id}/api-keys/{key_id}/rotate",
+            axum::routing::post(rotate_api_key),
+        )
 }
 
 /// GET /users
@@ -96,6 +135,42 @@ async fn list_users(
     Ok(resp::list(users, pagination))
 }
 
+/// GET /users/me
+/// Get the caller's profile, including permission names and activity counts
+async fn get_own_profile(
+    State(state): State<AppState>,
+    user: Claims,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let profile = state
+        .user_service
+        .get_profile(user_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(profile))
+}
+
+/// GET /users/me/storage
+/// Get the bytes the caller's uploads use, their quota and the share in use
+async fn get_own_storage(
+    State(state): State<AppState>,
+    user: Claims,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let usage = state
+        .file_service
+        .get_storage_usage(user_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(usage))
+}
+
 /// GET /users/{id}
 /// Get user info (self or admin)
 async fn get_user(
@@ -119,36 +194,33 @@ async fn get_user(
 }
 
 /// PATCH /users/{id}
-/// Update user (permissions - admin only)
+/// Update user (username and profile - self or admin, permissions - admin only)
 async fn update_user(
     State(state): State<AppState>,
     user: Claims,
     Path(user_id): Path<Uuid>,
-    Json(input): Json<UpdateUserRequest>,
+    Json(input): Json<UpdateUser>,
 ) -> Result<impl IntoResponse, ApiError> {
     let requester_id = Uuid::parse_str(&user.sub)
         .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
 
     // Only admin can update permissions
-    if let Some(permissions) = input.permissions {
+    if input.permissions.is_some() {
         domain::check_permission(user.permissions, USER_MANAGE)
             .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
-
-        let updated_user = state
-            .user_service
-            .update_permissions(requester_id, user.permissions, user_id, permissions)
-            .await
-            .map_err(ApiError::Domain)?;
-
-        return Ok(resp::ok(updated_user));
+    }
+    if input.changes_account() {
+        check_ownership_or_admin(user_id, requester_id, user.permissions, USER_MANAGE)
+            .map_err(|e| ApiError::Unauthorized(e.to_string()))?;
     }
 
-    // For now, only permissions updates are supported via PATCH
-    // Other user profile updates can be added here later
+    let updated_user = state
+        .user_service
+        .update_user(user_id, input, requester_id, user.permissions)
+        .await
+        .map_err(ApiError::Domain)?;
 
-    Err(ApiError::Validation(
-        "No valid fields to update".to_string(),
-    ))
+    Ok(resp::ok(updated_user))
 }
 
 /// DELETE /users/{id}
@@ -170,14 +242,62 @@ async fn delete_user(
     Ok(resp::no_content())
 }
 
+/// GET /users/{id}/comments
+/// Get a user's comments across all posts, newest first (self or admin)
+async fn list_user_comments(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(user_id): Path<Uuid>,
+    Query(params): Query<ListCommentsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let requester_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let comments = state
+        .comment_service
+        .get_user_comments(
+            user_id,
+            requester_id,
+            user.permissions,
+            params.limit,
+            params.offset,
+        )
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(comments))
+}
+
 /// GET /users/{id}/posts
 /// Get posts by a specific user
 async fn list_user_posts(
     State(state): State<AppState>,
-    user: Option<Claims>,
+    OptionalClaims(user): OptionalClaims,
     Path(user_id): Path<Uuid>,
     Query(params): Query<ListPostsQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
+    if params.stats {
+        let current_user = user
+            .ok_or_else(|| ApiError::Unauthorized("Sign in to view post statistics".to_string()))?;
+        let current_user_id = Uuid::parse_str(&current_user.sub)
+            .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+        let posts = state
+            .post_service
+            .list_user_posts_with_stats(
+                user_id,
+                current_user_id,
+                current_user.permissions,
+                Some(params.per_page),
+            )
+            .await
+            .map_err(ApiError::Domain)?;
+
+        let total = posts.len() as u64;
+        let pagination = Pagination::new(params.page, params.per_page, total);
+        return Ok(resp::list(posts, pagination));
+    }
+
     let include_drafts = params.include.as_ref().is_some_and(|v| v == "drafts");
 
     // Check permissions for viewing drafts
@@ -215,3 +335,129 @@ async fn list_user_posts(
 
     Ok(resp::list(posts, pagination))
 }
+
+/// Only the key owner or an admin, signed in with a JWT, may manage API keys
+///
+/// API keys are refused so that a leaked key cannot mint broader ones.
+fn authorize_api_key_management(user: &Claims, user_id: Uuid) -> Result<(), ApiError> {
+    if user.is_api_key() {
+        return Err(ApiError::Unauthorized(
+            "API keys cannot be used to manage API keys".to_string(),
+        ));
+    }
+    let requester_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    check_ownership_or_admin(user_id, requester_id, user.permissions, USER_MANAGE)
+        .map_err(|e| ApiError::Unauthorized(e.to_string()))
+}
+
+/// DELETE /users/{id}/sessions
+/// Log a user out everywhere (self or admin)
+///
+/// Users revoking their own sessions stay signed in with the session their
+/// token was issued for.
+async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(target_user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let requester_id = Uuid::parse_str(&user.sub)
+        .map_err(|e| ApiError::Internal(format!("Invalid user ID: {}", e)))?;
+
+    let current_session = match &user.refresh_token {
+        Some(refresh_token) => state
+            .session_service
+            .refresh_session(refresh_token)
+            .await
+            .ok()
+            .map(|session| session.id),
+        None => None,
+    };
+
+    let revoked = state
+        .session_service
+        .destroy_all_for_user(
+            target_user_id,
+            requester_id,
+            user.permissions,
+            current_session.as_deref(),
+        )
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(serde_json::json!({ "revoked": revoked })))
+}
+
+/// GET /users/{id}/api-keys
+/// List a user's API keys (metadata only)
+async fn list_api_keys(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize_api_key_management(&user, user_id)?;
+
+    let keys = state
+        .user_service
+        .list_api_keys(user_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::ok(keys))
+}
+
+/// POST /users/{id}/api-keys
+/// Create an API key; the plaintext key is only returned here
+async fn create_api_key(
+    State(state): State<AppState>,
+    user: Claims,
+    Path(user_id): Path<Uuid>,
+    Json(input): Json<CreateApiKey>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize_api_key_management(&user, user_id)?;
+
+    let (key, api_key) = state
+        .user_service
+        .create_api_key(user_id, input.label, input.scopes)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::created(CreateApiKeyResponse { key, api_key }))
+}
+
+/// DELETE /users/{id}/api-keys/{key_id}
+/// Revoke an API key
+async fn revoke_api_key(
+    State(state): State<AppState>,
+    user: Claims,
+    Path((user_id, key_id)): Path<(Uuid, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize_api_key_management(&user, user_id)?;
+
+    state
+        .user_service
+        .revoke_api_key(user_id, &key_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::no_content())
+}
+
+/// POST /users/{id}/api-keys/{key_id}/rotate
+/// Replace an API key with a new one; the old key stops working
+async fn rotate_api_key(
+    State(state): State<AppState>,
+    user: Claims,
+    Path((user_id, key_id)): Path<(Uuid, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize_api_key_management(&user, user_id)?;
+
+    let (key, api_key) = state
+        .user_service
+        .rotate_api_key(user_id, &key_id)
+        .await
+        .map_err(ApiError::Domain)?;
+
+    Ok(resp::created(CreateApiKeyResponse { key, api_key }))
+}
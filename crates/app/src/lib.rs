@@ -1,26 +1,40 @@
-use api::{file_cache::FileCache, middleware::auth::set_jwt_secret, routes, AppState, AuthState};
+mod meta;
+
+use api::{
+    file_cache::FileCache,
+    middleware::{
+        auth::{set_api_key_service, set_jwt_secret, set_session_service},
+        compression_layer, reject_unlisted_origin, request_span, set_body_limit_config, CorsPolicy,
+        MetricsLayer, RequestIdLayer,
+    },
+    routes, AppState, AuthState,
+};
 use axum::{
     body::Body,
-    extract::Request,
+    extract::{Request, State},
     http::{Response, StatusCode},
     response::IntoResponse,
 };
 use config::load_config;
+use domain::Post;
 use infrastructure::{
-    establish_connection, CategoryRepositoryImpl, CommentRepositoryImpl, ConfigRepositoryImpl,
-    FileRepositoryImpl, IndexNowClient, Migrator, MigratorTrait, PostRepositoryImpl,
-    SessionRepositoryImpl, StatsRepositoryImpl, TagRepositoryImpl, UserRepositoryImpl,
+    establish_connection, ApiKeyRepositoryImpl, CategoryRepositoryImpl, CommentRepositoryImpl,
+    ConfigRepositoryImpl, FileRepositoryImpl, HealthRepositoryImpl, InviteRepositoryImpl,
+    LikeRepositoryImpl, Migrator, MigratorTrait, PostRepositoryImpl, SessionRepositoryImpl,
+    StatsRepositoryImpl, TagRepositoryImpl, UserRepositoryImpl,
 };
 #[cfg(not(debug_assertions))]
 use rust_embed::RustEmbed;
 use service::{
-    CategoryService, CommentService, ConfigService, FileService, PostService, RssService,
-    RssServiceImpl, SessionService, StatsService, TagService, UserService,
+    CategoryService, Clients, CommentService, ConfigService, FileService, HealthService,
+    NotificationService, PermissionService, PostService, RssService, RssServiceImpl,
+    SessionService, SharedConfig, SitemapService, StatsService, TagService, UserService,
 };
-use std::sync::Arc;
-use tower_http::cors::CorsLayer;
+use std::sync::{Arc, RwLock};
 use tower_http::trace::TraceLayer;
 
+use meta::MetaInjector;
+
 pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv().ok();
 
@@ -31,8 +45,9 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing_subscriber::fmt()
         .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "peng_blog=debug,tower_http=debug,axum=trace".into()),
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                "peng_blog=debug,service=debug,tower_http=debug,axum=trace".into()
+            }),
         )
         .init();
 
@@ -69,33 +84,6 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
         tracing::warn!("ALLOW_REGISTRATION is overridden by environment variable");
     }
 
-    // IndexNow configuration
-    let indexnow_client = if config.indexnow.enabled {
-        if config.indexnow.api_key.is_empty() {
-            tracing::warn!(
-                "IndexNow is enabled but API key is empty. IndexNow notifications will be disabled."
-            );
-            None
-        } else {
-            tracing::info!(
-                "IndexNow enabled with endpoint: {}",
-                config.indexnow.endpoint
-            );
-            Some(Arc::new(IndexNowClient::new(
-                config.indexnow.endpoint.clone(),
-            )))
-        }
-    } else {
-        tracing::info!("IndexNow is disabled");
-        None
-    };
-
-    let indexnow_key = if config.indexnow.api_key.is_empty() {
-        None
-    } else {
-        Some(config.indexnow.api_key.clone())
-    };
-
     set_jwt_secret(config.auth.jwt_secret.clone());
 
     let db = establish_connection(&config.database.url).await?;
@@ -110,36 +98,75 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
     let post_repo = Arc::new(PostRepositoryImpl::new(db_clone.clone()));
     let user_repo = Arc::new(UserRepositoryImpl::new(db_clone.clone()));
     let session_repo = Arc::new(SessionRepositoryImpl::new(db_clone.clone()));
+    let api_key_repo = Arc::new(ApiKeyRepositoryImpl::new(db_clone.clone()));
+    let invite_repo = Arc::new(InviteRepositoryImpl::new(db_clone.clone()));
     let file_repo = Arc::new(FileRepositoryImpl::new(db_clone.clone()));
     let comment_repo = Arc::new(CommentRepositoryImpl::new(db_clone.clone()));
     let stats_repo = Arc::new(StatsRepositoryImpl::new(db_clone.clone()));
     let category_repo = Arc::new(CategoryRepositoryImpl::new(db_clone.clone()));
-    let tag_repo = Arc::new(TagRepositoryImpl::new(db_clone));
-
-    let post_service = PostService::new(
-        post_repo.clone(),
-        indexnow_client,
-        base_url.clone(),
-        indexnow_key,
-    );
-    let user_service = UserService::new(user_repo.clone(), config.site.allow_registration);
+    let tag_repo = Arc::new(TagRepositoryImpl::new(db_clone.clone()));
+    let like_repo = Arc::new(LikeRepositoryImpl::new(db_clone.clone()));
+    let health_repo = Arc::new(HealthRepositoryImpl::new(db_clone));
+
+    let permission_service = Arc::new(PermissionService::new(user_repo.clone()));
+    let shared_config = Arc::new(RwLock::new(domain::Config::from(config.clone())));
+    let clients = Clients::from_config(&shared_config.read().unwrap())?.shared();
+    let user_service = UserService::new(user_repo.clone(), shared_config.clone())
+        .with_api_keys(api_key_repo)
+        .with_invites(invite_repo)
+        .with_sessions(session_repo.clone())
+        .with_email(clients.clone(), base_url.clone())
+        .with_activity_counts(post_repo.clone(), comment_repo.clone());
     let session_service = SessionService::new(session_repo);
     let file_service = FileService::new(
         file_repo,
+        permission_service.clone(),
         config.storage.upload_dir.clone(),
         base_url.clone(),
-    );
+    )
+    .with_max_image_dimension(config.storage.max_image_dimension)
+    .with_max_file_size_mb(config.storage.max_file_size_mb)
+    .with_max_storage_per_user_mb(config.storage.max_storage_per_user_mb)
+    .with_allowed_extensions(config.storage.allowed_extensions.clone())
+    .with_user_repository(user_repo.clone());
+    let post_service = PostService::new(post_repo.clone(), base_url.clone())
+        .with_clients(clients.clone())
+        .with_likes(like_repo, config.auth.jwt_secret.clone())
+        .with_tag_repository(tag_repo.clone())
+        .with_category_repository(category_repo.clone())
+        .with_comment_repository(comment_repo.clone())
+        .with_user_repository(user_repo.clone())
+        .with_feature_images(file_service.clone())
+        .with_config(shared_config.clone());
+    let notification_service = Arc::new(NotificationService::new(
+        post_repo.clone(),
+        comment_repo.clone(),
+        user_repo.clone(),
+        clients.clone(),
+        base_url.clone(),
+    ));
     let comment_service = CommentService::new(
         comment_repo,
         user_repo,
-        config.github.client_id.clone(),
-        config.github.client_secret.clone(),
-    );
+        permission_service.clone(),
+        shared_config.clone(),
+    )
+    .with_notification_service(Some(notification_service))
+    .with_post_repository(post_repo.clone());
     let stats_service = StatsService::new(stats_repo);
-    let category_service = CategoryService::new(category_repo);
+    let sitemap_service = SitemapService::new(
+        post_repo.clone(),
+        category_repo.clone(),
+        tag_repo.clone(),
+        base_url.clone(),
+    );
+    let category_service = CategoryService::new(category_repo, permission_service)
+        .with_max_depth(config.site.max_category_depth);
     let tag_service = TagService::new(tag_repo);
+    let health_service = HealthService::new(health_repo);
     let config_repo = Arc::new(ConfigRepositoryImpl::new());
-    let config_service = ConfigService::new(config_repo);
+    let config_service =
+        ConfigService::new(config_repo, shared_config.clone()).with_clients(clients.clone());
 
     let rss_service = Arc::new(RssServiceImpl::new(
         post_repo.clone(),
@@ -151,7 +178,7 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
     let auth_state = AuthState::new(&config.auth.jwt_secret);
 
     let state = AppState::builder()
-        .config(config.clone())
+        .config(shared_config)
         .config_service(config_service)
         .post_service(post_service)
         .user_service(user_service)
@@ -162,19 +189,41 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
         .category_service(category_service)
         .tag_service(tag_service)
         .rss_service(rss_service)
+        .sitemap_service(sitemap_service)
+        .health_service(health_service)
         .auth_state(auth_state)
         .upload_dir(config.storage.upload_dir.clone())
         .bing_cache(bing_cache)
         .base_url(base_url.clone())
         .build();
 
+    set_api_key_service(state.user_service.clone());
+    set_session_service(state.session_service.clone());
+    set_body_limit_config(state.config.clone());
     api::bing::start_bing_cache_refresh_task(state.clone()).await;
+    start_post_purge_task(state.post_service.clone());
+    start_post_archive_task(state.post_service.clone(), state.config.clone());
+    start_post_schedule_task(state.post_service.clone(), state.rss_service.clone());
+
+    let cors_policy = CorsPolicy::new(
+        &config.server.allowed_origins,
+        config.server.cors_max_age_seconds,
+    );
+    tracing::info!("CORS allowed origins: {:?}", config.server.allowed_origins);
 
     let app = axum::Router::new()
         .nest("/api", routes())
+        .route("/metrics", axum::routing::get(api::metrics::get_metrics))
         .fallback(frontend_handler)
-        .layer(TraceLayer::new_for_http())
-        .layer(CorsLayer::permissive())
+        .layer(MetricsLayer)
+        .layer(axum::middleware::from_fn_with_state(
+            cors_policy.clone(),
+            reject_unlisted_origin,
+        ))
+        .layer(compression_layer(config.server.compression_level))
+        .layer(RequestIdLayer)
+        .layer(TraceLayer::new_for_http().make_span_with(request_span))
+        .layer(cors_policy.layer())
         .with_state(state);
     let listener =
         tokio::net::TcpListener::bind((config.server.host.as_str(), config.server.port)).await?;
@@ -186,19 +235,88 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
         config.server.port
     );
 
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
 
+/// Periodically purge posts that have been soft-deleted for longer than the retention window
+fn start_post_purge_task(post_service: Arc<PostService>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+
+            match post_service
+                .purge_deleted(service::post::SOFT_DELETE_RETENTION_DAYS)
+                .await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {} soft-deleted posts", count),
+                Err(e) => tracing::error!("Failed to purge soft-deleted posts: {}", e),
+            }
+        }
+    });
+}
+
+/// Monthly move published posts older than `site.archive_after_days` to the archive
+///
+/// The threshold is read on every run, so a config reload takes effect at the next one.
+fn start_post_archive_task(post_service: Arc<PostService>, config: SharedConfig) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(30 * 24 * 60 * 60));
+        loop {
+            interval.tick().await;
+
+            let Some(days) = config.read().unwrap().site.archive_after_days else {
+                continue;
+            };
+            match post_service.archive_older_than(days).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Archived {} posts older than {} days", count, days),
+                Err(e) => tracing::error!("Failed to archive old posts: {}", e),
+            }
+        }
+    });
+}
+
+/// Every minute publish posts whose scheduled time has passed
+fn start_post_schedule_task(post_service: Arc<PostService>, rss_service: Arc<dyn RssService>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+
+            match post_service.publish_due_scheduled(chrono::Utc::now()).await {
+                Ok(0) => {}
+                Ok(count) => {
+                    tracing::info!("Published {} scheduled posts", count);
+                    if let Err(e) = rss_service.refresh_cache().await {
+                        tracing::error!("Failed to refresh RSS cache: {}", e);
+                    }
+                }
+                Err(e) => tracing::error!("Failed to publish scheduled posts: {}", e),
+            }
+        }
+    });
+}
+
 /// Embedded frontend static files (only in release builds)
 #[cfg(not(debug_assertions))]
 #[derive(RustEmbed)]
 #[folder = "../../dist/"]
 struct FrontendAssets;
 
+/// Post rendered by an SPA route, with the injector for its preview tags
+type PostPreview = (MetaInjector, Post);
+
 /// Handler for serving frontend assets
-async fn frontend_handler(req: Request) -> impl IntoResponse {
+async fn frontend_handler(State(state): State<AppState>, req: Request) -> impl IntoResponse {
     let path = req.uri().path().to_string();
 
     // Skip API routes
@@ -209,6 +327,18 @@ async fn frontend_handler(req: Request) -> impl IntoResponse {
             .unwrap();
     }
 
+    // Post pages get OpenGraph/Twitter tags; unknown or unpublished posts get the plain page
+    let preview = match MetaInjector::post_id_from_path(&path) {
+        Some(id) => state
+            .post_service
+            .get(id)
+            .await
+            .ok()
+            .filter(|post| post.is_published())
+            .map(|post| (MetaInjector::new(&state.base_url), post)),
+        None => None,
+    };
+
     // Remove leading slash for asset lookup
     let asset_path = path.trim_start_matches('/');
     let asset_path = if asset_path.is_empty() {
@@ -244,12 +374,15 @@ async fn frontend_handler(req: Request) -> impl IntoResponse {
                             return Response::builder()
                                 .status(StatusCode::OK)
                                 .header("content-type", "text/html; charset=utf-8")
-                                .body(Body::from(content.data.to_vec()))
+                                .body(Body::from(render_index(
+                                    content.data.to_vec(),
+                                    preview.as_ref(),
+                                )))
                                 .unwrap();
                         }
                         None => {
                             // This shouldn't happen in release mode, but fallback just in case
-                            return serve_from_filesystem(asset_path, true).await;
+                            return serve_from_filesystem(asset_path, true, preview.as_ref()).await;
                         }
                     }
                 }
@@ -259,21 +392,35 @@ async fn frontend_handler(req: Request) -> impl IntoResponse {
 
     // Debug mode or fallback: serve from filesystem
     let has_extension = path.contains('.') && !path.ends_with('/');
-    serve_from_filesystem(asset_path, !has_extension).await
+    serve_from_filesystem(asset_path, !has_extension, preview.as_ref()).await
+}
+
+/// Inject post preview tags into index.html when serving a post page
+fn render_index(html: Vec<u8>, preview: Option<&PostPreview>) -> Vec<u8> {
+    match preview {
+        Some((injector, post)) => injector
+            .inject(&String::from_utf8_lossy(&html), post)
+            .into_bytes(),
+        None => html,
+    }
 }
 
 /// Fallback handler to serve from filesystem during development
-async fn serve_from_filesystem(path: &str, is_route: bool) -> Response<Body> {
+async fn serve_from_filesystem(
+    path: &str,
+    is_route: bool,
+    preview: Option<&PostPreview>,
+) -> Response<Body> {
     let dist_path = std::path::Path::new("dist");
 
     if is_route {
         // Serve index.html for SPA routes
         let index_path = dist_path.join("index.html");
-        if let Ok(content) = tokio::fs::read_to_string(&index_path).await {
+        if let Ok(content) = tokio::fs::read(&index_path).await {
             return Response::builder()
                 .status(StatusCode::OK)
                 .header("content-type", "text/html; charset=utf-8")
-                .body(Body::from(content))
+                .body(Body::from(render_index(content, preview)))
                 .unwrap();
         }
     } else {
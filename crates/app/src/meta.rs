@@ -0,0 +1,204 @@
+//! OpenGraph / Twitter Card meta tag injection
+//!
+//! The frontend is a single-page app, so crawlers fetching `/post/{id}` would
+//! otherwise only see the static `index.html`. [`MetaInjector`] renders
+//! per-post preview tags and splices them into the page's `<head>`.
+
+use domain::{post::first_image_url, Post};
+use uuid::Uuid;
+
+/// Maximum length of `og:description`, in characters
+const DESCRIPTION_LENGTH: usize = 160;
+
+/// Image used when a post does not contain one
+const DEFAULT_IMAGE_PATH: &str = "/icon.png";
+
+/// Builds and injects social preview meta tags for post pages
+pub struct MetaInjector {
+    base_url: String,
+}
+
+impl MetaInjector {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Extract the post ID from a `/post/{id}` route
+    pub fn post_id_from_path(path: &str) -> Option<Uuid> {
+        let id = path.strip_prefix("/post/")?.trim_end_matches('/');
+        Uuid::parse_str(id).ok()
+    }
+
+    /// Insert the post's meta tags just before `</head>`
+    ///
+    /// HTML without a `</head>` is returned unchanged.
+    pub fn inject(&self, html: &str, post: &Post) -> String {
+        match html.find("</head>") {
+            Some(index) => {
+                let mut output = String::with_capacity(html.len() + 1024);
+                output.push_str(&html[..index]);
+                output.push_str(&self.meta_tags(post));
+                output.push_str(&html[index..]);
+                output
+            }
+            None => html.to_string(),
+        }
+    }
+
+    /// Render the OpenGraph and Twitter Card tags for a post
+    pub fn meta_tags(&self, post: &Post) -> String {
+        let description = description(&post.content);
+        let url = format!("{}/post/{}", self.base_url, post.id);
+        let image = first_image_url(&post.content)
+            .map(|src| self.absolute_url(src))
+            .unwrap_or_else(|| format!("{}{}", self.base_url, DEFAULT_IMAGE_PATH));
+
+        let properties = [
+            ("og:title", post.title.as_str()),
+            ("og:description", description.as_str()),
+            ("og:url", url.as_str()),
+            ("og:type", "article"),
+            ("og:image", image.as_str()),
+        ];
+        let names = [
+            ("twitter:card", "summary_large_image"),
+            ("twitter:title", post.title.as_str()),
+            ("twitter:description", description.as_str()),
+            ("twitter:image", image.as_str()),
+        ];
+
+        let mut tags = String::new();
+        for (property, content) in properties {
+            tags.push_str(&format!(
+                "<meta property=\"{}\" content=\"{}\" />\n",
+                property,
+                escape_attribute(content)
+            ));
+        }
+        for (name, content) in names {
+            tags.push_str(&format!(
+                "<meta name=\"{}\" content=\"{}\" />\n",
+                name,
+                escape_attribute(content)
+            ));
+        }
+        tags
+    }
+
+    /// Resolve site-relative image paths against the base URL
+    fn absolute_url(&self, src: &str) -> String {
+        if src.starts_with("http://") || src.starts_with("https://") {
+            src.to_string()
+        } else {
+            format!("{}/{}", self.base_url, src.trim_start_matches('/'))
+        }
+    }
+}
+
+/// First characters of the content with whitespace collapsed
+fn description(content: &str) -> String {
+    content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(DESCRIPTION_LENGTH)
+        .collect()
+}
+
+/// Escape a value for use inside a double-quoted HTML attribute
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX_HTML: &str = "<html><head><title>Peng Blog</title></head><body></body></html>";
+
+    fn post(title: &str, content: &str) -> Post {
+        Post::new(Uuid::new_v4(), title.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_post_id_from_path() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            MetaInjector::post_id_from_path(&format!("/post/{}", id)),
+            Some(id)
+        );
+        assert_eq!(MetaInjector::post_id_from_path("/post/not-a-uuid"), None);
+        assert_eq!(MetaInjector::post_id_from_path("/posts"), None);
+    }
+
+    #[test]
+    fn test_inject_adds_tags_inside_head() {
+        let injector = MetaInjector::new("https://blog.example.com/");
+        let post = post("Hello \"World\"", "Some   content\nhere");
+
+        let html = injector.inject(INDEX_HTML, &post);
+
+        let head_end = html.find("</head>").unwrap();
+        let title_tag = html
+            .find(r#"<meta property="og:title" content="Hello &quot;World&quot;" />"#)
+            .unwrap();
+        assert!(title_tag < head_end);
+        assert!(html.contains(r#"<meta property="og:description" content="Some content here" />"#));
+        assert!(html.contains(&format!(
+            r#"<meta property="og:url" content="https://blog.example.com/post/{}" />"#,
+            post.id
+        )));
+        assert!(html.contains(r#"<meta property="og:type" content="article" />"#));
+        assert!(html.contains(
+            r#"<meta property="og:image" content="https://blog.example.com/icon.png" />"#
+        ));
+        assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image" />"#));
+    }
+
+    #[test]
+    fn test_description_is_truncated() {
+        let injector = MetaInjector::new("https://blog.example.com");
+        let tags = injector.meta_tags(&post("Long", &"a".repeat(500)));
+
+        let expected = format!(
+            r#"<meta property="og:description" content="{}" />"#,
+            "a".repeat(DESCRIPTION_LENGTH)
+        );
+        assert!(tags.contains(&expected));
+    }
+
+    #[test]
+    fn test_first_image_is_used() {
+        let injector = MetaInjector::new("https://blog.example.com");
+
+        let tags = injector.meta_tags(&post(
+            "Images",
+            "Intro\n![cover](/uploads/cover.png \"Cover\")\n<img src=\"https://cdn.example.com/b.png\">",
+        ));
+        assert!(tags.contains(
+            r#"<meta property="og:image" content="https://blog.example.com/uploads/cover.png" />"#
+        ));
+
+        let tags = injector.meta_tags(&post(
+            "Html",
+            "<p><img alt='x' src='https://cdn.example.com/a.png'></p>",
+        ));
+        assert!(tags
+            .contains(r#"<meta property="og:image" content="https://cdn.example.com/a.png" />"#));
+    }
+
+    #[test]
+    fn test_html_without_head_is_unchanged() {
+        let injector = MetaInjector::new("https://blog.example.com");
+        let html = "<p>no head</p>";
+
+        assert_eq!(injector.inject(html, &post("Title", "content")), html);
+    }
+}
@@ -1,11 +1,17 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use config::load_config;
 use console::style;
 use dialoguer::{Confirm, Input, Password};
-use domain::UserRepository;
+use domain::{FileRepository, PostRepository, StatsRepository, UserRepository};
 use domain::{ADMIN_PERMISSIONS, DEFAULT_USER_PERMISSIONS};
-use infrastructure::{establish_connection, Migrator, MigratorTrait, UserRepositoryImpl};
-use std::sync::Arc;
+use infrastructure::{
+    establish_connection, FileRepositoryImpl, MigrationStatus, Migrator, MigratorTrait,
+    PostRepositoryImpl, StatsRepositoryImpl, UserRepositoryImpl,
+};
+use service::{PostService, UserService};
+use std::collections::{hash_map::Entry, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
 mod update;
@@ -27,11 +33,21 @@ enum Commands {
         #[command(subcommand)]
         user_command: UserCommands,
     },
+    /// Post management commands
+    Post {
+        #[command(subcommand)]
+        post_command: PostCommands,
+    },
     /// Database management commands
     Db {
         #[command(subcommand)]
         db_command: DbCommands,
     },
+    /// Site statistics
+    Stats {
+        #[command(subcommand)]
+        stats_command: StatsCommands,
+    },
     /// Update peng-blog to latest version
     Update {
         /// Skip confirmation
@@ -50,8 +66,13 @@ enum UserCommands {
         id: String,
     },
     /// Create a new user
+    ///
+    /// Web registration refuses the names listed in `auth.reserved_usernames`
+    /// (by default admin, root, api, support, help, www and mail) as well as
+    /// usernames that look like a UUID. This command skips that check so an
+    /// operator can still create accounts with those names on purpose.
     Create {
-        /// Username
+        /// Username (reserved names are only accepted here, not at sign-up)
         #[arg(short, long)]
         username: Option<String>,
         /// Password
@@ -64,6 +85,16 @@ enum UserCommands {
         #[arg(long, conflicts_with_all = ["username", "password"])]
         non_interactive: bool,
     },
+    /// Create users in bulk from a CSV file
+    ///
+    /// The file starts with a `username,password,admin` header row; `admin`
+    /// is `true` or `false` and may be left empty. Rows that cannot be
+    /// created are reported and the rest are still created.
+    BatchCreate {
+        /// CSV file with one user per row
+        #[arg(short, long)]
+        file: PathBuf,
+    },
     /// Delete a user
     Delete {
         /// User ID
@@ -101,20 +132,85 @@ enum UserCommands {
     },
 }
 
+/// Upper bound on posts fetched when counting posts
+const MAX_COUNTED_POSTS: u64 = 100_000;
+
+#[derive(Subcommand)]
+enum PostCommands {
+    /// List posts, newest first
+    List {
+        /// Maximum number of posts to show (default 50)
+        #[arg(short, long)]
+        limit: Option<u32>,
+        /// Only show published posts
+        #[arg(long)]
+        published_only: bool,
+    },
+    /// Show post details
+    Show {
+        /// Post ID
+        id: String,
+    },
+    /// Delete a post (it can be restored for 30 days)
+    Delete {
+        /// Post ID
+        id: String,
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Restore a soft-deleted post
+    Restore {
+        /// Post ID
+        id: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum DbCommands {
     /// Run database migrations
-    Migrate,
+    Migrate {
+        /// List pending migrations without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Reset database (WARNING: This will delete all data!)
     Reset {
         /// Skip confirmation
         #[arg(short, long)]
         force: bool,
+        /// List the migrations that would be rolled back and reapplied, without
+        /// touching the data (takes precedence over --force)
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Show database status
     Status,
 }
 
+#[derive(Subcommand)]
+enum StatsCommands {
+    /// Print a summary of posts, users, comments, files and visits
+    Summary {
+        /// Only print one section
+        #[arg(long, value_enum)]
+        filter: Option<StatsSection>,
+    },
+}
+
+/// Section of the stats summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum StatsSection {
+    Posts,
+    Users,
+    Comments,
+    Files,
+    Visits,
+}
+
+/// Number of most viewed posts listed in the visits section
+const TOP_POSTS_SHOWN: u64 = 5;
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -140,20 +236,27 @@ async fn main() -> anyhow::Result<()> {
                 .init();
 
             match command {
-                Commands::User { user_command } => {
-                    handle_user_command(user_command, &config.database.url).await
+                Commands::User { user_command } => handle_user_command(user_command, &config).await,
+                Commands::Post { post_command } => {
+                    handle_post_command(post_command, &config.database.url).await
                 }
                 Commands::Db { db_command } => {
                     handle_db_command(db_command, &config.database.url).await
                 }
+                Commands::Stats { stats_command } => {
+                    handle_stats_command(stats_command, &config.database.url).await
+                }
                 Commands::Update { force } => handle_update_command(force).await,
             }
         }
     }
 }
 
-async fn handle_user_command(command: UserCommands, database_url: &str) -> anyhow::Result<()> {
-    let db = establish_connection(database_url).await?;
+async fn handle_user_command(
+    command: UserCommands,
+    config: &config::AppConfig,
+) -> anyhow::Result<()> {
+    let db = establish_connection(&config.database.url).await?;
     let user_repo = Arc::new(UserRepositoryImpl::new(db));
 
     match command {
@@ -165,6 +268,11 @@ async fn handle_user_command(command: UserCommands, database_url: &str) -> anyho
             admin,
             non_interactive,
         } => create_user(&user_repo, username, password, admin, non_interactive).await,
+        UserCommands::BatchCreate { file } => {
+            let shared_config = Arc::new(RwLock::new(domain::Config::from(config.clone())));
+            let user_service = UserService::new(user_repo, shared_config);
+            batch_create_users(&user_service, &file).await
+        }
         UserCommands::Delete { id, force } => delete_user(&user_repo, &id, force).await,
         UserCommands::ResetPassword {
             id,
@@ -176,9 +284,32 @@ async fn handle_user_command(command: UserCommands, database_url: &str) -> anyho
     }
 }
 
+async fn handle_post_command(command: PostCommands, database_url: &str) -> anyhow::Result<()> {
+    let db = establish_connection(database_url).await?;
+    let post_repo = Arc::new(PostRepositoryImpl::new(db.clone()));
+    let user_repo = Arc::new(UserRepositoryImpl::new(db));
+    let post_service = PostService::new(post_repo.clone(), String::new());
+
+    match command {
+        PostCommands::List {
+            limit,
+            published_only,
+        } => list_posts(&post_service, &user_repo, limit, published_only).await,
+        PostCommands::Show { id } => show_post(&post_service, &user_repo, &id).await,
+        PostCommands::Delete { id, force } => delete_post(&post_service, &id, force).await,
+        PostCommands::Restore { id } => restore_post(&post_repo, &id).await,
+    }
+}
+
 async fn handle_db_command(command: DbCommands, database_url: &str) -> anyhow::Result<()> {
     match command {
-        DbCommands::Migrate => {
+        DbCommands::Migrate { dry_run: true } => {
+            let db = establish_connection(database_url).await?;
+            let migrations = migration_statuses(&db).await?;
+            print_dry_run(&dry_run_plan(&migrations, false));
+            Ok(())
+        }
+        DbCommands::Migrate { dry_run: false } => {
             let db = establish_connection(database_url).await?;
             Migrator::up(&*db, None).await?;
             println!(
@@ -187,7 +318,16 @@ async fn handle_db_command(command: DbCommands, database_url: &str) -> anyhow::R
             );
             Ok(())
         }
-        DbCommands::Reset { force } => {
+        DbCommands::Reset { dry_run: true, .. } => {
+            let db = establish_connection(database_url).await?;
+            let migrations = migration_statuses(&db).await?;
+            print_dry_run(&dry_run_plan(&migrations, true));
+            Ok(())
+        }
+        DbCommands::Reset {
+            force,
+            dry_run: false,
+        } => {
             if !force
                 && !confirm_action(
                     "Are you sure you want to reset database? This will delete ALL data.",
@@ -216,6 +356,13 @@ async fn handle_db_command(command: DbCommands, database_url: &str) -> anyhow::R
             println!("Database URL: {}", database_url);
             println!("Connection: {}", style("✓ Connected").green());
 
+            // Count posts
+            let post_repo = Arc::new(PostRepositoryImpl::new(db.clone()));
+            let posts = post_repo.list_all_posts(MAX_COUNTED_POSTS).await?;
+            let published_count = posts.iter().filter(|p| p.is_published()).count();
+            println!("Total posts: {}", style(posts.len()).cyan());
+            println!("Published posts: {}", style(published_count).cyan());
+
             // Count users
             let user_repo = Arc::new(UserRepositoryImpl::new(db));
             let users = user_repo.list_users(1000).await?;
@@ -247,6 +394,63 @@ async fn find_user(user_repo: &Arc<UserRepositoryImpl>, id: &str) -> anyhow::Res
         .ok_or_else(|| anyhow::anyhow!("User not found"))
 }
 
+/// Name and status of every known migration, oldest first
+async fn migration_statuses(
+    db: &sea_orm::DatabaseConnection,
+) -> anyhow::Result<Vec<(String, MigrationStatus)>> {
+    Ok(Migrator::get_migration_with_status(db)
+        .await?
+        .iter()
+        .map(|migration| (migration.name().to_string(), migration.status()))
+        .collect())
+}
+
+/// Lines describing what `db migrate` (or `db reset` when `reset` is set)
+/// would do to a database whose migrations have the given statuses
+///
+/// A reset rolls back the applied migrations newest first, then applies
+/// every migration from scratch.
+fn dry_run_plan(migrations: &[(String, MigrationStatus)], reset: bool) -> Vec<String> {
+    let applied = migrations
+        .iter()
+        .filter(|(_, status)| *status == MigrationStatus::Applied);
+    let mut plan: Vec<String> = if reset {
+        applied
+            .rev()
+            .map(|(name, _)| format!("Would drop: {}", name))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    plan.extend(
+        migrations
+            .iter()
+            .filter(|(_, status)| reset || *status == MigrationStatus::Pending)
+            .map(|(name, _)| format!("Would apply: {}", name)),
+    );
+    plan
+}
+
+/// Helper: Print a dry-run plan
+fn print_dry_run(plan: &[String]) {
+    println!(
+        "\n{}",
+        style("Dry run - the database is not modified")
+            .bold()
+            .cyan()
+    );
+    if plan.is_empty() {
+        println!(
+            "{}",
+            style("Nothing to do, all migrations are applied").green()
+        );
+    }
+    for line in plan {
+        println!("{}", line);
+    }
+}
+
 /// Helper: Prompt user for confirmation (returns true if confirmed, false if cancelled)
 fn confirm_action(prompt: &str, default: bool) -> anyhow::Result<bool> {
     Confirm::new()
@@ -365,6 +569,30 @@ async fn create_user(
     Ok(())
 }
 
+async fn batch_create_users(user_service: &UserService, file: &Path) -> anyhow::Result<()> {
+    let data = std::fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+    let entries = service::user::parse_users_csv(&data)?;
+
+    let result = user_service
+        .batch_create(entries, ADMIN_PERMISSIONS)
+        .await?;
+
+    for user in &result.created {
+        println!("{} {} ({})", style("✓").green(), user.username, user.id);
+    }
+    for (username, reason) in &result.failed {
+        println!("{} {}: {}", style("✗").red(), username, reason);
+    }
+    println!(
+        "\n{} created, {} failed",
+        style(result.created.len()).green().bold(),
+        style(result.failed.len()).red().bold()
+    );
+
+    Ok(())
+}
+
 async fn delete_user(
     user_repo: &Arc<UserRepositoryImpl>,
     id: &str,
@@ -393,6 +621,175 @@ async fn delete_user(
     Ok(())
 }
 
+/// Helper: Parse post ID from string
+fn parse_post_id(id: &str) -> anyhow::Result<Uuid> {
+    Uuid::parse_str(id).map_err(|e| anyhow::anyhow!("Invalid post ID: {}", e))
+}
+
+/// Helper: Look up usernames of post authors, once per author
+async fn author_names(
+    user_repo: &Arc<UserRepositoryImpl>,
+    posts: &[domain::Post],
+) -> anyhow::Result<HashMap<Uuid, String>> {
+    let mut names = HashMap::new();
+    for post in posts {
+        if let Entry::Vacant(entry) = names.entry(post.user_id) {
+            let name = user_repo
+                .find_by_id(post.user_id)
+                .await?
+                .map(|user| user.username)
+                .unwrap_or_else(|| "(deleted)".to_string());
+            entry.insert(name);
+        }
+    }
+    Ok(names)
+}
+
+fn post_status(post: &domain::Post) -> &'static str {
+    if post.is_published() {
+        "PUBLISHED"
+    } else {
+        "DRAFT"
+    }
+}
+
+/// Cut text to at most `width` characters, marking truncation with `…`
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(width - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+/// Format one row of the post table
+fn post_row(id: &str, title: &str, status: &str, author: &str, created: &str) -> String {
+    format!(
+        "{:<8}  {:<36}  {:<9}  {:<16}  {}",
+        id,
+        truncate(title, 36),
+        status,
+        truncate(author, 16),
+        created
+    )
+}
+
+async fn list_posts(
+    post_service: &PostService,
+    user_repo: &Arc<UserRepositoryImpl>,
+    limit: Option<u32>,
+    published_only: bool,
+) -> anyhow::Result<()> {
+    let limit = limit.map(u64::from);
+    let posts = if published_only {
+        post_service.list_published(limit, 0).await?
+    } else {
+        post_service.list_all(limit).await?
+    };
+
+    println!("\n{}", style("Posts").bold().cyan());
+
+    if posts.is_empty() {
+        println!("{}", style("No posts found").yellow());
+        return Ok(());
+    }
+
+    let authors = author_names(user_repo, &posts).await?;
+    println!(
+        "{}",
+        style(post_row("ID", "Title", "Status", "Author", "Created")).bold()
+    );
+    println!("{}", "─".repeat(90));
+    for post in &posts {
+        println!(
+            "{}",
+            post_row(
+                &post.id.to_string()[..8],
+                &post.title,
+                post_status(post),
+                &authors[&post.user_id],
+                &post.created_at.format("%Y-%m-%d %H:%M").to_string(),
+            )
+        );
+    }
+    println!("{}", "─".repeat(90));
+    println!("Total: {}", style(posts.len()).cyan());
+
+    Ok(())
+}
+
+async fn show_post(
+    post_service: &PostService,
+    user_repo: &Arc<UserRepositoryImpl>,
+    id: &str,
+) -> anyhow::Result<()> {
+    let post = post_service.get(parse_post_id(id)?).await?;
+    let authors = author_names(user_repo, std::slice::from_ref(&post)).await?;
+    let tags = post_service.get_tags(post.id).await?;
+
+    println!("\n{}", style("Post Details").bold().cyan());
+    println!("{}", "─".repeat(40));
+    println!("ID: {}", post.id);
+    println!("Title: {}", style(&post.title).bold());
+    println!("Status: {}", post_status(&post));
+    println!("Author: {}", authors[&post.user_id]);
+    println!("Created: {}", post.created_at.format("%Y-%m-%d %H:%M:%S"));
+    if let Some(published_at) = post.published_at {
+        println!("Published: {}", published_at.format("%Y-%m-%d %H:%M:%S"));
+    }
+    if !tags.is_empty() {
+        let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        println!("Tags: {}", names.join(", "));
+    }
+    println!("Views: {}", post.views);
+    println!(
+        "Words: {} (~{} min read)",
+        post.word_count, post.reading_time_minutes
+    );
+
+    Ok(())
+}
+
+async fn delete_post(post_service: &PostService, id: &str, force: bool) -> anyhow::Result<()> {
+    let post = post_service.get(parse_post_id(id)?).await?;
+
+    if !force
+        && !confirm_action(
+            &format!("Are you sure you want to delete post '{}'?", post.title),
+            false,
+        )?
+    {
+        println!("{}", style("Operation cancelled").yellow());
+        return Ok(());
+    }
+
+    // The CLI runs with operator rights, so act as an admin
+    post_service
+        .delete(post.id, Uuid::nil(), ADMIN_PERMISSIONS)
+        .await?;
+
+    println!("\n{}", style("✓ Post deleted successfully").green());
+    println!(
+        "Restore it with: peng-blog post restore {} (within {} days)",
+        post.id,
+        service::post::SOFT_DELETE_RETENTION_DAYS
+    );
+
+    Ok(())
+}
+
+async fn restore_post(post_repo: &Arc<PostRepositoryImpl>, id: &str) -> anyhow::Result<()> {
+    let post = post_repo.restore_post(parse_post_id(id)?).await?;
+
+    println!("\n{}", style("✓ Post restored successfully").green());
+    println!("ID: {}", post.id);
+    println!("Title: {}", post.title);
+
+    Ok(())
+}
+
 async fn reset_password(
     user_repo: &Arc<UserRepositoryImpl>,
     id: &str,
@@ -495,19 +892,7 @@ async fn demote_user(
 }
 
 fn format_permissions(permissions: u64) -> String {
-    const PERMISSION_NAMES: [(u64, &str); 5] = [
-        (1 << 0, "POST_CREATE"),
-        (1 << 1, "POST_UPDATE"),
-        (1 << 2, "POST_DELETE"),
-        (1 << 3, "POST_PUBLISH"),
-        (1 << 4, "USER_MANAGE"),
-    ];
-
-    let flags: Vec<&str> = PERMISSION_NAMES
-        .iter()
-        .filter(|(mask, _)| permissions & mask != 0)
-        .map(|(_, name)| *name)
-        .collect();
+    let flags = domain::permission_names(permissions);
 
     if flags.is_empty() {
         "NONE".to_string()
@@ -516,6 +901,146 @@ fn format_permissions(permissions: u64) -> String {
     }
 }
 
+async fn handle_stats_command(command: StatsCommands, database_url: &str) -> anyhow::Result<()> {
+    let db = establish_connection(database_url).await?;
+
+    match command {
+        StatsCommands::Summary { filter } => {
+            let summary = gather_stats(db).await?;
+            print_stats_summary(&summary, filter);
+            Ok(())
+        }
+    }
+}
+
+/// Figures shown by `stats summary`
+struct StatsSummary {
+    published_posts: usize,
+    draft_posts: usize,
+    admin_users: usize,
+    regular_users: usize,
+    total_comments: u64,
+    file_count: u64,
+    storage_bytes: u64,
+    total_visits: u64,
+    today_visits: u64,
+    /// Title and view count of the most viewed posts
+    top_posts: Vec<(String, u64)>,
+}
+
+async fn gather_stats(db: Arc<sea_orm::DatabaseConnection>) -> anyhow::Result<StatsSummary> {
+    let post_repo = PostRepositoryImpl::new(db.clone());
+    let user_repo = UserRepositoryImpl::new(db.clone());
+    let file_repo = FileRepositoryImpl::new(db.clone());
+    let stats_repo = StatsRepositoryImpl::new(db);
+
+    let posts = post_repo.list_all_posts(MAX_COUNTED_POSTS).await?;
+    let published_posts = posts.iter().filter(|p| p.is_published()).count();
+
+    let users = user_repo.list_users(1000).await?;
+    let admin_users = users.iter().filter(|u| u.is_admin()).count();
+
+    let totals = stats_repo.get_total_stats().await?;
+    let (_, file_count) = file_repo.list_all_files(1, 0, None).await?;
+    let storage_bytes = file_repo.total_file_size(None).await?;
+
+    let mut top_posts = Vec::new();
+    for (post_id, views) in stats_repo.get_top_posts(TOP_POSTS_SHOWN).await? {
+        let title = match post_repo.get_post(post_id).await {
+            Ok(post) => post.title,
+            Err(_) => post_id.to_string(),
+        };
+        top_posts.push((title, views));
+    }
+
+    Ok(StatsSummary {
+        published_posts,
+        draft_posts: posts.len() - published_posts,
+        admin_users,
+        regular_users: users.len() - admin_users,
+        total_comments: totals.total_comments,
+        file_count,
+        storage_bytes,
+        total_visits: totals.total_visits,
+        today_visits: totals.today_visits,
+        top_posts,
+    })
+}
+
+fn print_stats_summary(summary: &StatsSummary, filter: Option<StatsSection>) {
+    let sections = match filter {
+        Some(section) => vec![section],
+        None => StatsSection::value_variants().to_vec(),
+    };
+
+    for section in sections {
+        println!("\n{}", style(stats_section_title(section)).bold().cyan());
+        println!("{}", "─".repeat(40));
+        for line in stats_section_lines(summary, section) {
+            println!("{}", line);
+        }
+    }
+}
+
+fn stats_section_title(section: StatsSection) -> &'static str {
+    match section {
+        StatsSection::Posts => "Posts",
+        StatsSection::Users => "Users",
+        StatsSection::Comments => "Comments",
+        StatsSection::Files => "Files",
+        StatsSection::Visits => "Visits",
+    }
+}
+
+/// Lines of one summary section, without styling
+fn stats_section_lines(summary: &StatsSummary, section: StatsSection) -> Vec<String> {
+    match section {
+        StatsSection::Posts => vec![
+            format!("Published: {}", summary.published_posts),
+            format!("Drafts: {}", summary.draft_posts),
+        ],
+        StatsSection::Users => vec![
+            format!("Admins: {}", summary.admin_users),
+            format!("Regular: {}", summary.regular_users),
+        ],
+        StatsSection::Comments => vec![format!("Total: {}", summary.total_comments)],
+        StatsSection::Files => vec![
+            format!("Files: {}", summary.file_count),
+            format!("Storage used: {}", format_bytes(summary.storage_bytes)),
+        ],
+        StatsSection::Visits => {
+            let mut lines = vec![
+                format!("All time: {}", summary.total_visits),
+                format!("Today: {}", summary.today_visits),
+                format!("Top {} posts:", TOP_POSTS_SHOWN),
+            ];
+            if summary.top_posts.is_empty() {
+                lines.push("  (no views yet)".to_string());
+            }
+            for (title, views) in &summary.top_posts {
+                lines.push(format!("  {:<36}  {:>8}", truncate(title, 36), views));
+            }
+            lines
+        }
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 MB`
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
 async fn handle_update_command(force: bool) -> anyhow::Result<()> {
     if !force
         && !confirm_action(
@@ -529,3 +1054,166 @@ async fn handle_update_command(force: bool) -> anyhow::Result<()> {
 
     update::perform_update(force).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses() -> Vec<(String, MigrationStatus)> {
+        vec![
+            ("m1_create_user".to_string(), MigrationStatus::Applied),
+            ("m2_create_post".to_string(), MigrationStatus::Applied),
+            ("m3_add_tags".to_string(), MigrationStatus::Pending),
+        ]
+    }
+
+    #[test]
+    fn test_dry_run_flag_overrides_force() {
+        let cli =
+            Cli::try_parse_from(["peng-blog", "db", "reset", "--force", "--dry-run"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Db {
+                db_command: DbCommands::Reset {
+                    force: true,
+                    dry_run: true
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["peng-blog", "db", "migrate", "--dry-run"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Db {
+                db_command: DbCommands::Migrate { dry_run: true }
+            })
+        ));
+    }
+
+    #[test]
+    fn test_dry_run_plan_for_migrate_lists_pending() {
+        assert_eq!(
+            dry_run_plan(&statuses(), false),
+            vec!["Would apply: m3_add_tags"]
+        );
+    }
+
+    #[test]
+    fn test_dry_run_plan_for_reset_drops_then_applies_all() {
+        assert_eq!(
+            dry_run_plan(&statuses(), true),
+            vec![
+                "Would drop: m2_create_post",
+                "Would drop: m1_create_user",
+                "Would apply: m1_create_user",
+                "Would apply: m2_create_post",
+                "Would apply: m3_add_tags",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_permissions_names_each_flag() {
+        assert_eq!(format_permissions(0), "NONE");
+        assert_eq!(
+            format_permissions(DEFAULT_USER_PERMISSIONS),
+            "POST_CREATE | POST_UPDATE | POST_PUBLISH"
+        );
+        assert_eq!(
+            format_permissions(ADMIN_PERMISSIONS | domain::SUPER_ADMIN)
+                .split(" | ")
+                .count(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_truncate_marks_cut_text() {
+        assert_eq!(truncate("short", 8), "short");
+        assert_eq!(truncate("a-very-long-title", 8), "a-very-…");
+        assert_eq!(truncate("文章标题很长很长", 4), "文章标…");
+    }
+
+    #[test]
+    fn test_post_row_aligns_columns() {
+        let title = "A title that is far too long to fit in the table column";
+        let row = post_row("3f2504e0", title, "DRAFT", "alice", "2024-01-01 10:00");
+
+        assert!(row.starts_with("3f2504e0  A title that is far too long to fit…  "));
+        assert!(row.ends_with("DRAFT      alice             2024-01-01 10:00"));
+    }
+
+    fn seeded_summary() -> StatsSummary {
+        StatsSummary {
+            published_posts: 12,
+            draft_posts: 3,
+            admin_users: 1,
+            regular_users: 7,
+            total_comments: 42,
+            file_count: 5,
+            storage_bytes: 3 * 1024 * 1024 / 2,
+            total_visits: 1_000,
+            today_visits: 25,
+            top_posts: vec![
+                ("Hello world".to_string(), 300),
+                ("Second post".to_string(), 120),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_stats_summary_parses_filter() {
+        let cli =
+            Cli::try_parse_from(["peng-blog", "stats", "summary", "--filter", "files"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                stats_command: StatsCommands::Summary {
+                    filter: Some(StatsSection::Files)
+                }
+            })
+        ));
+
+        assert!(
+            Cli::try_parse_from(["peng-blog", "stats", "summary", "--filter", "tags"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_stats_sections_render_seeded_counts() {
+        let summary = seeded_summary();
+        let lines = |section| stats_section_lines(&summary, section);
+
+        assert_eq!(lines(StatsSection::Posts), ["Published: 12", "Drafts: 3"]);
+        assert_eq!(lines(StatsSection::Users), ["Admins: 1", "Regular: 7"]);
+        assert_eq!(lines(StatsSection::Comments), ["Total: 42"]);
+        assert_eq!(
+            lines(StatsSection::Files),
+            ["Files: 5", "Storage used: 1.5 MB"]
+        );
+
+        let visits = lines(StatsSection::Visits);
+        assert_eq!(visits[..3], ["All time: 1000", "Today: 25", "Top 5 posts:"]);
+        assert!(visits[3].starts_with("  Hello world"));
+        assert!(visits[3].ends_with("     300"));
+        assert_eq!(visits.len(), 5);
+    }
+
+    #[test]
+    fn test_stats_visits_without_views() {
+        let summary = StatsSummary {
+            top_posts: Vec::new(),
+            ..seeded_summary()
+        };
+
+        let visits = stats_section_lines(&summary, StatsSection::Visits);
+        assert_eq!(visits.last().unwrap(), "  (no views yet)");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+}
@@ -6,8 +6,8 @@ pub use loader::load_config;
 pub use loader::load_config_from_path;
 pub use loader::save_config;
 pub use types::{
-    AuthConfig, Config, DatabaseConfig, GitHubConfig, IndexNowConfig, ServerConfig, SiteConfig,
-    StorageConfig,
+    AuthConfig, CommentsConfig, Config, DatabaseConfig, GitHubConfig, IndexNowConfig, ServerConfig,
+    SiteConfig, SmtpConfig, StorageConfig, WebhookConfig, DEFAULT_RESERVED_USERNAMES,
 };
 
 pub use load_config as load;
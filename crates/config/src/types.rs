@@ -1,5 +1,6 @@
 use super::ConfigError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
@@ -10,6 +11,12 @@ pub struct Config {
     pub github: GitHubConfig,
     pub site: SiteConfig,
     pub indexnow: IndexNowConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub comments: CommentsConfig,
+    #[serde(default)]
+    pub smtp: SmtpConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -27,6 +34,22 @@ pub struct ServerConfig {
     pub port: u16,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port_env_override: Option<bool>,
+    /// Origins allowed to make cross-origin requests, e.g. "https://myblog.com"
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// How long browsers may cache CORS preflight responses (default 3600)
+    #[serde(default = "default_cors_max_age_seconds")]
+    pub cors_max_age_seconds: u64,
+    /// Bearer token for `/metrics` requests not coming from localhost
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_token: Option<String>,
+    /// gzip/Brotli response compression level from 1 (fastest) to 9 (default 6)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_level: Option<u8>,
+}
+
+fn default_cors_max_age_seconds() -> u64 {
+    3600
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -34,6 +57,20 @@ pub struct AuthConfig {
     pub jwt_secret: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jwt_secret_env_override: Option<bool>,
+    /// Usernames nobody may register, compared case-insensitively
+    #[serde(default = "default_reserved_usernames")]
+    pub reserved_usernames: Vec<String>,
+}
+
+/// Usernames reserved when the config does not list its own
+pub const DEFAULT_RESERVED_USERNAMES: &[&str] =
+    &["admin", "root", "api", "support", "help", "www", "mail"];
+
+pub fn default_reserved_usernames() -> Vec<String> {
+    DEFAULT_RESERVED_USERNAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -44,6 +81,32 @@ pub struct StorageConfig {
     pub cache_dir: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_dir_env_override: Option<bool>,
+    /// Bounding box for generated image thumbnails, in pixels (default 400)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_image_dimension: Option<u32>,
+    /// Largest accepted upload, in megabytes (default 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size_mb: Option<u64>,
+    /// Total upload storage per user, in megabytes; admins are exempt (default unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_storage_per_user_mb: Option<u64>,
+    /// Download external feature images and serve them from local storage
+    #[serde(default)]
+    pub mirror_external_images: bool,
+    /// File extensions accepted for upload, without the leading dot
+    #[serde(default = "default_allowed_extensions")]
+    pub allowed_extensions: Vec<String>,
+}
+
+/// Upload extensions accepted when the config does not list its own
+pub const DEFAULT_ALLOWED_EXTENSIONS: &[&str] =
+    &["jpg", "jpeg", "png", "gif", "webp", "pdf", "txt", "md"];
+
+pub fn default_allowed_extensions() -> Vec<String> {
+    DEFAULT_ALLOWED_EXTENSIONS
+        .iter()
+        .map(|extension| extension.to_string())
+        .collect()
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -61,6 +124,49 @@ pub struct SiteConfig {
     pub allow_registration: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_registration_env_override: Option<bool>,
+    /// Published posts older than this many days are moved to the archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_after_days: Option<u32>,
+    /// With registration closed, still let people register with an invite code
+    #[serde(default)]
+    pub invite_only: bool,
+    /// Longest post content in characters; 100 000 when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_post_chars: Option<usize>,
+    /// Longest post content in characters for admins; unlimited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_post_chars_admin: Option<usize>,
+    /// Deepest category nesting, the root counting as the first level; 5 when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_category_depth: Option<u32>,
+    /// HTML tags kept in rendered posts on top of the sanitizer's safe set
+    #[serde(default = "default_allowed_html_tags")]
+    pub allowed_html_tags: Vec<String>,
+    /// Attributes kept on each tag in rendered posts, by tag name
+    #[serde(default = "default_allowed_html_attributes")]
+    pub allowed_html_attributes: HashMap<String, Vec<String>>,
+}
+
+pub const DEFAULT_ALLOWED_HTML_TAGS: &[&str] = &["iframe", "div", "span"];
+
+pub const DEFAULT_ALLOWED_HTML_ATTRIBUTES: &[(&str, &[&str])] =
+    &[("iframe", &["src", "width", "height", "allowfullscreen"])];
+
+pub fn default_allowed_html_tags() -> Vec<String> {
+    DEFAULT_ALLOWED_HTML_TAGS
+        .iter()
+        .map(|tag| tag.to_string())
+        .collect()
+}
+
+pub fn default_allowed_html_attributes() -> HashMap<String, Vec<String>> {
+    DEFAULT_ALLOWED_HTML_ATTRIBUTES
+        .iter()
+        .map(|(tag, attributes)| {
+            let attributes = attributes.iter().map(|a| a.to_string()).collect();
+            (tag.to_string(), attributes)
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -72,6 +178,53 @@ pub struct IndexNowConfig {
     pub endpoint: String,
 }
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct WebhookConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CommentsConfig {
+    #[serde(default)]
+    pub require_moderation: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SmtpConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub from_address: String,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: default_smtp_port(),
+            username: None,
+            password: None,
+            from_address: String::new(),
+        }
+    }
+}
+
 impl Config {
     pub fn validate(&self) -> Result<(), ConfigError> {
         if self.database.url.is_empty() {
@@ -84,6 +237,16 @@ impl Config {
             tracing::warn!("Using default JWT secret. Change this in production!");
         }
 
+        if self.smtp.enabled && (self.smtp.host.is_empty() || self.smtp.from_address.is_empty()) {
+            return Err(ConfigError::Validation(
+                "SMTP host and from_address are required when SMTP is enabled".to_string(),
+            ));
+        }
+
+        for origin in &self.server.allowed_origins {
+            validate_origin(origin)?;
+        }
+
         if self.server.port == 0 {
             return Err(ConfigError::Validation(
                 "Server port cannot be zero".to_string(),
@@ -93,3 +256,20 @@ impl Config {
         Ok(())
     }
 }
+
+/// Check that a CORS origin is a bare `http(s)://host[:port]` URL
+fn validate_origin(origin: &str) -> Result<(), ConfigError> {
+    let invalid = || ConfigError::Validation(format!("Invalid allowed origin: {}", origin));
+    let url = url::Url::parse(origin).map_err(|_| invalid())?;
+
+    let is_origin = matches!(url.scheme(), "http" | "https")
+        && url.host().is_some()
+        && url.path() == "/"
+        && url.query().is_none()
+        && url.fragment().is_none()
+        && url.username().is_empty();
+    if !is_origin {
+        return Err(invalid());
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{COMMENT_MODERATE, POST_CREATE, POST_DELETE, POST_PUBLISH, POST_UPDATE};
+
+/// Prefix of every plaintext API key, used to tell keys apart from JWTs
+pub const API_KEY_PREFIX: &str = "pk_live_";
+
+/// Read posts, including the owner's drafts
+pub const SCOPE_READ_POSTS: &str = "read:posts";
+
+/// Create, edit, publish and delete posts
+pub const SCOPE_WRITE_POSTS: &str = "write:posts";
+
+/// Write and moderate comments
+pub const SCOPE_WRITE_COMMENTS: &str = "write:comments";
+
+/// Every scope an API key may be granted
+pub const API_KEY_SCOPES: [&str; 3] = [SCOPE_READ_POSTS, SCOPE_WRITE_POSTS, SCOPE_WRITE_COMMENTS];
+
+/// A long-lived API key for scripts and feed readers
+///
+/// Only the SHA-256 hash of the key is stored; the plaintext is shown once
+/// on creation. The hash doubles as the key's identifier.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiKey {
+    pub key_hash: String,
+    pub user_id: Uuid,
+    pub label: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Check if the key was granted a scope
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    /// Check if the key may be used for requests that change data
+    pub fn can_write(&self) -> bool {
+        self.scopes.iter().any(|s| s.starts_with("write:"))
+    }
+
+    /// Restrict the owner's permissions to what the key's scopes allow
+    pub fn effective_permissions(&self, user_permissions: u64) -> u64 {
+        let mut allowed = 0;
+        if self.has_scope(SCOPE_WRITE_POSTS) {
+            allowed |= POST_CREATE | POST_UPDATE | POST_DELETE | POST_PUBLISH;
+        }
+        if self.has_scope(SCOPE_WRITE_COMMENTS) {
+            allowed |= COMMENT_MODERATE;
+        }
+        user_permissions & allowed
+    }
+}
+
+/// Request to create an API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiKey {
+    pub label: String,
+    /// Defaults to read-only access
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// Response returned once after creating an API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiKeyResponse {
+    /// Plaintext key; it cannot be retrieved again
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ADMIN_PERMISSIONS;
+
+    fn key(scopes: &[&str]) -> ApiKey {
+        ApiKey {
+            key_hash: "hash".to_string(),
+            user_id: Uuid::new_v4(),
+            label: "feed".to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            created_at: Utc::now(),
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn test_read_only_key_has_no_permissions() {
+        let key = key(&[SCOPE_READ_POSTS]);
+        assert!(!key.can_write());
+        assert_eq!(key.effective_permissions(ADMIN_PERMISSIONS), 0);
+    }
+
+    #[test]
+    fn test_write_scope_is_capped_by_owner_permissions() {
+        let key = key(&[SCOPE_READ_POSTS, SCOPE_WRITE_POSTS]);
+        assert!(key.can_write());
+        assert_eq!(key.effective_permissions(POST_CREATE), POST_CREATE);
+        assert_eq!(
+            key.effective_permissions(ADMIN_PERMISSIONS) & COMMENT_MODERATE,
+            0
+        );
+    }
+}
@@ -2,12 +2,17 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Deepest category nesting, counting the root as the first level
+pub const MAX_CATEGORY_DEPTH: usize = 10;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Category {
     pub id: Uuid,
     pub name: String,
     pub slug: String,
     pub parent_id: Option<Uuid>,
+    /// Number of non-deleted posts in this category, maintained on write
+    pub post_count: u32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -18,6 +23,7 @@ impl Category {
             name,
             slug,
             parent_id,
+            post_count: 0,
             created_at: Utc::now(),
         }
     }
@@ -27,6 +33,17 @@ impl Category {
     }
 }
 
+/// A category with its subcategories, for rendering the hierarchy
+///
+/// The category fields, `post_count` included, sit next to `children`
+/// in the serialized node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: Category,
+    pub children: Vec<CategoryNode>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateCategory {
     pub name: String,
@@ -1,7 +1,45 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Emoji accepted as comment reactions
+pub const REACTION_TYPES: [&str; 7] = ["👍", "👎", "❤️", "🎉", "😕", "🚀", "👀"];
+
+/// Moderation status of a comment
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    /// Awaiting moderation, hidden from the public
+    #[default]
+    Pending,
+    /// Visible to everyone
+    Approved,
+    /// Rejected by a moderator, hidden from the public
+    Rejected,
+}
+
+impl CommentStatus {
+    /// Database representation of the status
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+        }
+    }
+
+    /// Parse a status from its database representation
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(CommentStatus::Pending),
+            "approved" => Some(CommentStatus::Approved),
+            "rejected" => Some(CommentStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
 /// Represents a comment on a post
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Comment {
@@ -11,6 +49,9 @@ pub struct Comment {
     pub github_username: Option<String>, // Set for GitHub users
     pub github_avatar_url: Option<String>,
     pub content: String,
+    pub status: CommentStatus,
+    /// Comment this one replies to; replies are never nested further
+    pub parent_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -25,6 +66,8 @@ impl Comment {
             github_username: None,
             github_avatar_url: None,
             content,
+            status: CommentStatus::Pending,
+            parent_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -39,6 +82,8 @@ impl Comment {
             github_username: Some(github_user.login.clone()),
             github_avatar_url: Some(github_user.avatar_url.clone()),
             content,
+            status: CommentStatus::Pending,
+            parent_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -58,6 +103,8 @@ impl Comment {
             github_username: Some(github_username.to_string()),
             github_avatar_url: github_avatar_url.map(|s| s.to_string()),
             content,
+            status: CommentStatus::Pending,
+            parent_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -78,6 +125,22 @@ impl Comment {
     pub fn is_from_github(&self) -> bool {
         self.user_id.is_none() && self.github_username.is_some()
     }
+
+    /// Check if comment is a reply to another comment
+    pub fn is_reply(&self) -> bool {
+        self.parent_id.is_some()
+    }
+
+    /// Check if comment is publicly visible
+    pub fn is_approved(&self) -> bool {
+        self.status == CommentStatus::Approved
+    }
+
+    /// Set the moderation status
+    pub fn set_status(&mut self, status: CommentStatus) {
+        self.status = status;
+        self.updated_at = Utc::now();
+    }
 }
 
 /// Request to create a comment
@@ -85,6 +148,9 @@ impl Comment {
 pub struct CreateComment {
     pub post_id: Uuid,
     pub content: String,
+    /// Top-level comment being replied to
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
 }
 
 /// Request to create comment with GitHub user
@@ -93,6 +159,9 @@ pub struct CreateCommentGitHub {
     pub post_id: Uuid,
     pub github_code: String, // OAuth authorization code
     pub content: String,
+    /// Top-level comment being replied to
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
 }
 
 /// Response for comment operations
@@ -103,9 +172,37 @@ pub struct CommentResponse {
     pub username: String,           // Either user.username or github_username
     pub avatar_url: Option<String>, // User avatar or GitHub avatar
     pub content: String,
+    pub status: CommentStatus,
+    pub parent_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub is_github_user: bool,
+    /// Title of the commented post, filled in for a user's comment history
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_title: Option<String>,
+    /// Number of reactions per emoji; emoji nobody reacted with are left out
+    #[serde(default)]
+    pub reactions: HashMap<String, u64>,
+}
+
+/// Request to react to a comment with one of [`REACTION_TYPES`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddReactionRequest {
+    pub reaction_type: String,
+}
+
+/// One page of comments with the total across all pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedComments {
+    pub items: Vec<CommentResponse>,
+    pub total: u64,
+}
+
+/// A top-level comment together with its replies, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentThread {
+    pub comment: CommentResponse,
+    pub replies: Vec<CommentResponse>,
 }
 
 impl From<&Comment> for CommentResponse {
@@ -128,9 +225,13 @@ impl From<&Comment> for CommentResponse {
             username,
             avatar_url,
             content: comment.content.clone(),
+            status: comment.status,
+            parent_id: comment.parent_id,
             created_at: comment.created_at,
             updated_at: comment.updated_at,
             is_github_user,
+            post_title: None,
+            reactions: HashMap::new(),
         }
     }
 }
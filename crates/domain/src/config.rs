@@ -3,9 +3,12 @@
 //! This module defines types for configuration management,
 //! including domain types and update requests.
 
-use crate::Result;
+use crate::{HtmlAllowList, PostLengthLimits, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub use config::types::DEFAULT_ALLOWED_EXTENSIONS;
 
 /// Configuration domain type
 ///
@@ -20,18 +23,27 @@ pub struct Config {
     pub github: GitHubConfig,
     pub site: SiteConfig,
     pub indexnow: IndexNowConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub comments: CommentsConfig,
+    #[serde(default)]
+    pub smtp: SmtpConfig,
 }
 
 /// Public configuration exposed to frontend without authentication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublicConfig {
     pub allow_registration: bool,
+    /// Registration is closed but accepts invite codes
+    pub invite_only: bool,
 }
 
 impl From<&Config> for PublicConfig {
     fn from(config: &Config) -> Self {
         Self {
             allow_registration: config.site.allow_registration,
+            invite_only: config.site.is_invite_only(),
         }
     }
 }
@@ -51,6 +63,22 @@ pub struct ServerConfig {
     pub port: u16,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port_env_override: Option<bool>,
+    /// Origins allowed to make cross-origin requests
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// How long browsers may cache CORS preflight responses
+    #[serde(default = "default_cors_max_age_seconds")]
+    pub cors_max_age_seconds: u64,
+    /// Bearer token for `/metrics` requests not coming from localhost
+    #[serde(default)]
+    pub metrics_token: Option<String>,
+    /// Response compression level from 1 to 9
+    #[serde(default)]
+    pub compression_level: Option<u8>,
+}
+
+fn default_cors_max_age_seconds() -> u64 {
+    3600
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +86,9 @@ pub struct AuthConfig {
     pub jwt_secret: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jwt_secret_env_override: Option<bool>,
+    /// Usernames nobody may register, compared case-insensitively
+    #[serde(default = "config::types::default_reserved_usernames")]
+    pub reserved_usernames: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +99,21 @@ pub struct StorageConfig {
     pub cache_dir: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_dir_env_override: Option<bool>,
+    /// Bounding box for generated image thumbnails, in pixels (default 400)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_image_dimension: Option<u32>,
+    /// Largest accepted upload, in megabytes (default 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size_mb: Option<u64>,
+    /// Total upload storage per user, in megabytes; admins are exempt (default unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_storage_per_user_mb: Option<u64>,
+    /// Download external feature images and serve them from local storage
+    #[serde(default)]
+    pub mirror_external_images: bool,
+    /// File extensions accepted for upload, without the leading dot
+    #[serde(default = "config::types::default_allowed_extensions")]
+    pub allowed_extensions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +131,46 @@ pub struct SiteConfig {
     pub allow_registration: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_registration_env_override: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_after_days: Option<u32>,
+    #[serde(default)]
+    pub invite_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_post_chars: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_post_chars_admin: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_category_depth: Option<u32>,
+    #[serde(default = "config::types::default_allowed_html_tags")]
+    pub allowed_html_tags: Vec<String>,
+    #[serde(default = "config::types::default_allowed_html_attributes")]
+    pub allowed_html_attributes: HashMap<String, Vec<String>>,
+}
+
+impl SiteConfig {
+    /// Check if registration is closed except for holders of an invite code
+    pub fn is_invite_only(&self) -> bool {
+        !self.allow_registration && self.invite_only
+    }
+
+    /// Post content limits, with defaults for the settings left unset
+    pub fn post_length_limits(&self) -> PostLengthLimits {
+        let defaults = PostLengthLimits::default();
+        PostLengthLimits {
+            default_max_chars: self.max_post_chars.unwrap_or(defaults.default_max_chars),
+            admin_max_chars: self
+                .max_post_chars_admin
+                .unwrap_or(defaults.admin_max_chars),
+        }
+    }
+
+    /// HTML kept when rendering post content
+    pub fn html_allow_list(&self) -> HtmlAllowList {
+        HtmlAllowList {
+            tags: self.allowed_html_tags.clone(),
+            attributes: self.allowed_html_attributes.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +182,32 @@ pub struct IndexNowConfig {
     pub endpoint: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommentsConfig {
+    #[serde(default)]
+    pub require_moderation: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub from_address: String,
+}
+
 /// Repository for configuration persistence operations
 #[async_trait]
 pub trait ConfigRepository: Send + Sync {
@@ -109,7 +221,7 @@ pub trait ConfigRepository: Send + Sync {
 /// Configuration update request
 ///
 /// All fields are optional to support partial updates.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct UpdateConfigRequest {
     pub database: Option<UpdateDatabaseConfig>,
     pub server: Option<UpdateServerConfig>,
@@ -118,6 +230,9 @@ pub struct UpdateConfigRequest {
     pub github: Option<UpdateGitHubConfig>,
     pub site: Option<UpdateSiteConfig>,
     pub indexnow: Option<UpdateIndexNowConfig>,
+    pub webhook: Option<UpdateWebhookConfig>,
+    pub comments: Option<UpdateCommentsConfig>,
+    pub smtp: Option<UpdateSmtpConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -129,17 +244,27 @@ pub struct UpdateDatabaseConfig {
 pub struct UpdateServerConfig {
     pub host: Option<String>,
     pub port: Option<u16>,
+    pub allowed_origins: Option<Vec<String>>,
+    pub cors_max_age_seconds: Option<u64>,
+    pub metrics_token: Option<String>,
+    pub compression_level: Option<u8>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct UpdateAuthConfig {
     pub jwt_secret: Option<String>,
+    pub reserved_usernames: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct UpdateStorageConfig {
     pub upload_dir: Option<String>,
     pub cache_dir: Option<String>,
+    pub max_image_dimension: Option<u32>,
+    pub max_file_size_mb: Option<u64>,
+    pub max_storage_per_user_mb: Option<u64>,
+    pub mirror_external_images: Option<bool>,
+    pub allowed_extensions: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -148,9 +273,16 @@ pub struct UpdateGitHubConfig {
     pub client_secret: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Site settings an admin may change through `PUT /admin/config`
+pub const EDITABLE_SITE_FIELDS: &[&str] =
+    &["allow_registration", "invite_only", "archive_after_days"];
+
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct UpdateSiteConfig {
     pub allow_registration: Option<bool>,
+    /// `0` disables automatic archiving
+    pub archive_after_days: Option<u32>,
+    pub invite_only: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -160,6 +292,27 @@ pub struct UpdateIndexNowConfig {
     pub endpoint: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateWebhookConfig {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateCommentsConfig {
+    pub require_moderation: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateSmtpConfig {
+    pub enabled: Option<bool>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from_address: Option<String>,
+}
+
 impl From<config::AppConfig> for Config {
     fn from(app_config: config::AppConfig) -> Self {
         Self {
@@ -172,16 +325,26 @@ impl From<config::AppConfig> for Config {
                 host_env_override: app_config.server.host_env_override,
                 port: app_config.server.port,
                 port_env_override: app_config.server.port_env_override,
+                allowed_origins: app_config.server.allowed_origins,
+                cors_max_age_seconds: app_config.server.cors_max_age_seconds,
+                metrics_token: app_config.server.metrics_token,
+                compression_level: app_config.server.compression_level,
             },
             auth: AuthConfig {
                 jwt_secret: app_config.auth.jwt_secret,
                 jwt_secret_env_override: app_config.auth.jwt_secret_env_override,
+                reserved_usernames: app_config.auth.reserved_usernames,
             },
             storage: StorageConfig {
                 upload_dir: app_config.storage.upload_dir,
                 upload_dir_env_override: app_config.storage.upload_dir_env_override,
                 cache_dir: app_config.storage.cache_dir,
                 cache_dir_env_override: app_config.storage.cache_dir_env_override,
+                max_image_dimension: app_config.storage.max_image_dimension,
+                max_file_size_mb: app_config.storage.max_file_size_mb,
+                max_storage_per_user_mb: app_config.storage.max_storage_per_user_mb,
+                mirror_external_images: app_config.storage.mirror_external_images,
+                allowed_extensions: app_config.storage.allowed_extensions,
             },
             github: GitHubConfig {
                 client_id: app_config.github.client_id,
@@ -192,6 +355,13 @@ impl From<config::AppConfig> for Config {
             site: SiteConfig {
                 allow_registration: app_config.site.allow_registration,
                 allow_registration_env_override: app_config.site.allow_registration_env_override,
+                archive_after_days: app_config.site.archive_after_days,
+                invite_only: app_config.site.invite_only,
+                max_post_chars: app_config.site.max_post_chars,
+                max_post_chars_admin: app_config.site.max_post_chars_admin,
+                max_category_depth: app_config.site.max_category_depth,
+                allowed_html_tags: app_config.site.allowed_html_tags,
+                allowed_html_attributes: app_config.site.allowed_html_attributes,
             },
             indexnow: IndexNowConfig {
                 enabled: app_config.indexnow.enabled,
@@ -199,6 +369,21 @@ impl From<config::AppConfig> for Config {
                 api_key_env_override: app_config.indexnow.api_key_env_override,
                 endpoint: app_config.indexnow.endpoint,
             },
+            webhook: WebhookConfig {
+                url: app_config.webhook.url,
+                secret: app_config.webhook.secret,
+            },
+            comments: CommentsConfig {
+                require_moderation: app_config.comments.require_moderation,
+            },
+            smtp: SmtpConfig {
+                enabled: app_config.smtp.enabled,
+                host: app_config.smtp.host,
+                port: app_config.smtp.port,
+                username: app_config.smtp.username,
+                password: app_config.smtp.password,
+                from_address: app_config.smtp.from_address,
+            },
         }
     }
 }
@@ -215,16 +400,26 @@ impl From<Config> for config::AppConfig {
                 host_env_override: domain_config.server.host_env_override,
                 port: domain_config.server.port,
                 port_env_override: domain_config.server.port_env_override,
+                allowed_origins: domain_config.server.allowed_origins,
+                cors_max_age_seconds: domain_config.server.cors_max_age_seconds,
+                metrics_token: domain_config.server.metrics_token,
+                compression_level: domain_config.server.compression_level,
             },
             auth: config::AuthConfig {
                 jwt_secret: domain_config.auth.jwt_secret,
                 jwt_secret_env_override: domain_config.auth.jwt_secret_env_override,
+                reserved_usernames: domain_config.auth.reserved_usernames,
             },
             storage: config::StorageConfig {
                 upload_dir: domain_config.storage.upload_dir,
                 upload_dir_env_override: domain_config.storage.upload_dir_env_override,
                 cache_dir: domain_config.storage.cache_dir,
                 cache_dir_env_override: domain_config.storage.cache_dir_env_override,
+                max_image_dimension: domain_config.storage.max_image_dimension,
+                max_file_size_mb: domain_config.storage.max_file_size_mb,
+                max_storage_per_user_mb: domain_config.storage.max_storage_per_user_mb,
+                mirror_external_images: domain_config.storage.mirror_external_images,
+                allowed_extensions: domain_config.storage.allowed_extensions,
             },
             github: config::GitHubConfig {
                 client_id: domain_config.github.client_id,
@@ -235,6 +430,13 @@ impl From<Config> for config::AppConfig {
             site: config::SiteConfig {
                 allow_registration: domain_config.site.allow_registration,
                 allow_registration_env_override: domain_config.site.allow_registration_env_override,
+                archive_after_days: domain_config.site.archive_after_days,
+                invite_only: domain_config.site.invite_only,
+                max_post_chars: domain_config.site.max_post_chars,
+                max_post_chars_admin: domain_config.site.max_post_chars_admin,
+                max_category_depth: domain_config.site.max_category_depth,
+                allowed_html_tags: domain_config.site.allowed_html_tags,
+                allowed_html_attributes: domain_config.site.allowed_html_attributes,
             },
             indexnow: config::IndexNowConfig {
                 enabled: domain_config.indexnow.enabled,
@@ -242,6 +444,21 @@ impl From<Config> for config::AppConfig {
                 api_key_env_override: domain_config.indexnow.api_key_env_override,
                 endpoint: domain_config.indexnow.endpoint,
             },
+            webhook: config::WebhookConfig {
+                url: domain_config.webhook.url,
+                secret: domain_config.webhook.secret,
+            },
+            comments: config::CommentsConfig {
+                require_moderation: domain_config.comments.require_moderation,
+            },
+            smtp: config::SmtpConfig {
+                enabled: domain_config.smtp.enabled,
+                host: domain_config.smtp.host,
+                port: domain_config.smtp.port,
+                username: domain_config.smtp.username,
+                password: domain_config.smtp.password,
+                from_address: domain_config.smtp.from_address,
+            },
         }
     }
 }
@@ -1,6 +1,51 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Machine-readable error code sent to API clients
+///
+/// Serialized in `SCREAMING_SNAKE_CASE`, e.g. `POST_NOT_FOUND`, so clients
+/// can pick a localized message instead of parsing the English one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    NotFound,
+    PostNotFound,
+    UserNotFound,
+    CommentNotFound,
+    PermissionDenied,
+    ValidationFailed,
+    UsernameExists,
+    Unauthorized,
+    Conflict,
+    Internal,
+}
+
+impl ErrorCode {
+    /// The code as sent to clients
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "NOT_FOUND",
+            Self::PostNotFound => "POST_NOT_FOUND",
+            Self::UserNotFound => "USER_NOT_FOUND",
+            Self::CommentNotFound => "COMMENT_NOT_FOUND",
+            Self::PermissionDenied => "PERMISSION_DENIED",
+            Self::ValidationFailed => "VALIDATION_FAILED",
+            Self::UsernameExists => "USERNAME_EXISTS",
+            Self::Unauthorized => "UNAUTHORIZED",
+            Self::Conflict => "CONFLICT",
+            Self::Internal => "INTERNAL",
+        }
+    }
+
+    /// Check if the code reports a missing resource
+    pub fn is_not_found(self) -> bool {
+        matches!(
+            self,
+            Self::NotFound | Self::PostNotFound | Self::UserNotFound | Self::CommentNotFound
+        )
+    }
+}
+
 /// Domain errors that can occur throughout the application
 #[derive(Debug, Error, Clone, Serialize, Deserialize)]
 pub enum Error {
@@ -18,6 +63,14 @@ pub enum Error {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// Error with a specific code and, for invalid input, the offending field
+    #[error("{message}")]
+    CodedError {
+        code: ErrorCode,
+        message: String,
+        field: Option<String>,
+    },
 }
 
 /// Result type alias for domain operations
@@ -48,4 +101,63 @@ impl Error {
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    /// Create an error with a specific code
+    pub fn coded(code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self::CodedError {
+            code,
+            message: msg.into(),
+            field: None,
+        }
+    }
+
+    /// Create a validation error about one input field
+    pub fn invalid_field(field: impl Into<String>, msg: impl Into<String>) -> Self {
+        Self::coded(ErrorCode::ValidationFailed, msg).with_field(field)
+    }
+
+    /// Name the input field a coded error is about; other errors are unchanged
+    pub fn with_field(mut self, name: impl Into<String>) -> Self {
+        if let Self::CodedError { field, .. } = &mut self {
+            *field = Some(name.into());
+        }
+        self
+    }
+
+    /// Code of the error, derived from the variant for uncoded errors
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::Validation(_) => ErrorCode::ValidationFailed,
+            Self::Unauthorized(_) => ErrorCode::Unauthorized,
+            Self::Conflict(_) => ErrorCode::Conflict,
+            Self::Internal(_) => ErrorCode::Internal,
+            Self::CodedError { code, .. } => *code,
+        }
+    }
+
+    /// Input field the error is about, if any
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            Self::CodedError { field, .. } => field.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Message without the variant prefix added by `Display`
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(msg)
+            | Self::Validation(msg)
+            | Self::Unauthorized(msg)
+            | Self::Conflict(msg)
+            | Self::Internal(msg)
+            | Self::CodedError { message: msg, .. } => msg,
+        }
+    }
+
+    /// Check if the error reports a missing resource, coded or not
+    pub fn is_not_found(&self) -> bool {
+        self.code().is_not_found()
+    }
 }
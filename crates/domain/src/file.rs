@@ -12,6 +12,10 @@ pub struct File {
     pub content_type: String,
     pub size_bytes: u64,
     pub url: String,
+    /// Hex-encoded SHA-256 of the file content, used for deduplication
+    pub sha256_hash: Option<String>,
+    /// Download URL of the generated thumbnail, for image uploads
+    pub thumbnail_url: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -33,10 +37,30 @@ impl File {
             content_type,
             size_bytes,
             url,
+            sha256_hash: None,
+            thumbnail_url: None,
             created_at: Utc::now(),
         }
     }
 
+    /// Attach the content hash to this file record
+    pub fn with_hash(mut self, sha256_hash: String) -> Self {
+        self.sha256_hash = Some(sha256_hash);
+        self
+    }
+
+    /// Check if the content type matches a filter
+    ///
+    /// A filter ending in `/` (e.g. `image/`) matches every subtype; any
+    /// other filter must match the content type exactly.
+    pub fn matches_content_type(&self, filter: &str) -> bool {
+        if filter.ends_with('/') {
+            self.content_type.starts_with(filter)
+        } else {
+            self.content_type == filter
+        }
+    }
+
     /// Check if file is owned by a specific user
     pub fn is_owned_by(&self, user_id: Uuid) -> bool {
         self.user_id == user_id
@@ -50,6 +74,31 @@ impl File {
             filename
         )
     }
+
+    /// Stored filename in a download URL made by [`File::generate_url`]
+    ///
+    /// URLs without the `base_url` prefix, e.g. `/api/files/{filename}/download`
+    /// in post content, are accepted too.
+    pub fn filename_from_url<'a>(base_url: &str, url: &'a str) -> Option<&'a str> {
+        let path = url
+            .strip_prefix(base_url.trim_end_matches('/'))
+            .unwrap_or(url);
+        path.strip_prefix("/api/files/")?
+            .strip_suffix("/download")
+            .filter(|filename| !filename.is_empty() && !filename.contains('/'))
+    }
+}
+
+/// File content to send for a download, possibly a single byte range
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileServeResponse {
+    /// The requested bytes; empty when the range cannot be satisfied
+    pub data: Vec<u8>,
+    pub content_type: String,
+    /// `Content-Range` header value for partial or unsatisfiable responses
+    pub content_range: Option<String>,
+    /// HTTP status: 200 (full), 206 (partial) or 416 (range not satisfiable)
+    pub status: u16,
 }
 
 /// Request to upload a file
@@ -60,6 +109,41 @@ pub struct UploadFile {
     pub data: Vec<u8>,
 }
 
+/// One page of all uploaded files, for administrators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileListing {
+    pub files: Vec<File>,
+    /// Number of files matching the filter, across all pages
+    pub total: u64,
+    /// Combined size of all files matching the filter
+    pub total_size_bytes: u64,
+}
+
+/// Upload storage a user has used against their quota
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorageUsage {
+    pub used_bytes: u64,
+    /// `None` when the user has no quota
+    pub quota_bytes: Option<u64>,
+    /// Share of the quota in use, 0 without a quota; above 100 once over it
+    pub percent_used: f64,
+}
+
+impl StorageUsage {
+    pub fn new(used_bytes: u64, quota_bytes: Option<u64>) -> Self {
+        let percent_used = match quota_bytes {
+            Some(0) => 100.0,
+            Some(quota) => used_bytes as f64 / quota as f64 * 100.0,
+            None => 0.0,
+        };
+        Self {
+            used_bytes,
+            quota_bytes,
+            percent_used,
+        }
+    }
+}
+
 /// Response for file upload
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileResponse {
@@ -69,4 +153,5 @@ pub struct FileResponse {
     pub original_filename: String,
     pub content_type: String,
     pub size_bytes: u64,
+    pub thumbnail_url: Option<String>,
 }
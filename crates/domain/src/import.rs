@@ -0,0 +1,289 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// Metadata from the YAML front-matter of an imported Markdown file
+///
+/// Only the subset of YAML that blog front-matter uses is understood:
+/// `key: value` scalars (optionally quoted), inline `[a, b]` lists and
+/// block lists of `- item` lines. Unknown keys are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    /// Accepted for compatibility; post slugs are derived from the title
+    pub slug: Option<String>,
+    /// Tag slugs
+    pub tags: Vec<String>,
+    /// Category slug
+    pub category: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Written on export; not restored on import
+    pub created_at: Option<DateTime<Utc>>,
+    pub summary: Option<String>,
+}
+
+impl FrontMatter {
+    /// Split a Markdown document into its front-matter and body
+    ///
+    /// Documents that do not open with a `---` line have no front-matter
+    /// and are returned whole as the body.
+    pub fn parse(document: &str) -> Result<(FrontMatter, &str)> {
+        let document = document.trim_start_matches('\u{feff}');
+        let Some(rest) = strip_delimiter(document) else {
+            return Ok((FrontMatter::default(), document));
+        };
+
+        let mut header_end = None;
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            let trimmed = line.trim_end();
+            if trimmed == "---" || trimmed == "..." {
+                header_end = Some((offset, offset + line.len()));
+                break;
+            }
+            offset += line.len();
+        }
+        let (header_end, body_start) = header_end
+            .ok_or_else(|| Error::Validation("Front-matter is not closed with ---".to_string()))?;
+
+        let front_matter = Self::parse_header(&rest[..header_end])?;
+        Ok((
+            front_matter,
+            rest[body_start..].trim_start_matches(['\r', '\n']),
+        ))
+    }
+
+    /// Render as a `---` delimited YAML block followed by a blank line
+    pub fn render(&self) -> String {
+        let mut yaml = String::from("---\n");
+        let mut scalar = |key: &str, value: &str| {
+            yaml.push_str(&format!("{}: {}\n", key, quote(value)));
+        };
+        if let Some(title) = &self.title {
+            scalar("title", title);
+        }
+        if let Some(slug) = &self.slug {
+            scalar("slug", slug);
+        }
+        if let Some(category) = &self.category {
+            scalar("category", category);
+        }
+        if let Some(published_at) = self.published_at {
+            scalar("published_at", &published_at.to_rfc3339());
+        }
+        if let Some(created_at) = self.created_at {
+            scalar("created_at", &created_at.to_rfc3339());
+        }
+        if let Some(summary) = &self.summary {
+            scalar("summary", summary);
+        }
+        if !self.tags.is_empty() {
+            yaml.push_str("tags:\n");
+            for tag in &self.tags {
+                yaml.push_str(&format!("  - {}\n", quote(tag)));
+            }
+        }
+        yaml.push_str("---\n\n");
+        yaml
+    }
+
+    fn parse_header(header: &str) -> Result<FrontMatter> {
+        let mut front_matter = FrontMatter::default();
+        let mut lines = header.lines().peekable();
+
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| Error::Validation(format!("Invalid front-matter line: {}", line)))?;
+            let key = key.trim();
+            let value = value.trim();
+
+            let mut items = Vec::new();
+            if value.is_empty() {
+                while let Some(item) = lines.peek().and_then(|l| l.trim().strip_prefix('-')) {
+                    items.push(unquote(item.trim()));
+                    lines.next();
+                }
+            } else if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                items.extend(list.split(',').map(|item| unquote(item.trim())));
+            } else {
+                items.push(unquote(value));
+            }
+            items.retain(|item| !item.is_empty());
+
+            match key {
+                "title" => front_matter.title = items.pop(),
+                "slug" => front_matter.slug = items.pop(),
+                "category" => front_matter.category = items.pop(),
+                "summary" | "description" => front_matter.summary = items.pop(),
+                "tags" => {
+                    front_matter.tags = items
+                        .iter()
+                        .flat_map(|item| item.split(','))
+                        .map(|tag| tag.trim().to_string())
+                        .filter(|tag| !tag.is_empty())
+                        .collect();
+                }
+                "published_at" | "date" => {
+                    front_matter.published_at = items.pop().map(|v| parse_date(&v)).transpose()?;
+                }
+                "created_at" => {
+                    front_matter.created_at = items.pop().map(|v| parse_date(&v)).transpose()?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(front_matter)
+    }
+}
+
+/// Strip the opening `---` line, if present
+fn strip_delimiter(document: &str) -> Option<&str> {
+    let rest = document.strip_prefix("---")?;
+    let rest = rest.trim_start_matches([' ', '\t']);
+    rest.strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+}
+
+/// Remove quotes around a scalar, unescaping double-quoted ones
+fn unquote(value: &str) -> String {
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return inner.to_string();
+    }
+    let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        return value.to_string();
+    };
+
+    let mut unescaped = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Double-quote a scalar so it reads back unchanged
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// Parse an RFC 3339 timestamp, `YYYY-MM-DD HH:MM:SS` or a bare date (UTC)
+fn parse_date(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S") {
+        return Ok(date.and_utc());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| Error::Validation(format!("Invalid published_at date: {}", value)))
+}
+
+/// A file that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportError {
+    pub filename: String,
+    pub error: String,
+}
+
+/// Outcome of a Markdown import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportPostsResponse {
+    pub imported: usize,
+    /// Files that were not Markdown
+    pub skipped: usize,
+    pub errors: Vec<ImportError>,
+}
+
+/// Outcome of importing comments from another platform
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ImportResult {
+    pub imported: u64,
+    /// Deleted, spam and already imported comments, and comments on pages
+    /// without a matching post
+    pub skipped: u64,
+    /// One message per comment that could not be imported
+    pub errors: Vec<String>,
+}
+
+/// A post rendered as a Markdown file with front-matter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPost {
+    pub filename: String,
+    pub content: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_front_matter() {
+        let document = "---\ntitle: \"Hello: World\"\nslug: hello-world\ntags: [rust, 'web']\n\
+                        category: programming\npublished_at: 2024-03-01\nsummary: A greeting\n\
+                        draft: true\n---\n\n# Hello\n\nBody text.\n";
+
+        let (front_matter, body) = FrontMatter::parse(document).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Hello: World"));
+        assert_eq!(front_matter.slug.as_deref(), Some("hello-world"));
+        assert_eq!(front_matter.tags, vec!["rust", "web"]);
+        assert_eq!(front_matter.category.as_deref(), Some("programming"));
+        assert_eq!(
+            front_matter.published_at.unwrap().to_rfc3339(),
+            "2024-03-01T00:00:00+00:00"
+        );
+        assert_eq!(front_matter.summary.as_deref(), Some("A greeting"));
+        assert_eq!(body, "# Hello\n\nBody text.\n");
+
+        let (front_matter, _) =
+            FrontMatter::parse("---\ntags:\n  - rust\n  - async\ntitle: Lists\n---\nBody").unwrap();
+        assert_eq!(front_matter.tags, vec!["rust", "async"]);
+        assert_eq!(front_matter.title.as_deref(), Some("Lists"));
+    }
+
+    #[test]
+    fn test_parse_without_or_with_broken_front_matter() {
+        let (front_matter, body) = FrontMatter::parse("# Just Markdown\n---\n").unwrap();
+        assert_eq!(front_matter, FrontMatter::default());
+        assert_eq!(body, "# Just Markdown\n---\n");
+
+        assert!(FrontMatter::parse("---\ntitle: Open\n\nBody").is_err());
+        assert!(FrontMatter::parse("---\ndate: yesterday\n---\nBody").is_err());
+    }
+
+    #[test]
+    fn test_render_round_trips() {
+        let front_matter = FrontMatter {
+            title: Some("Quotes \"and\" \\ slashes".to_string()),
+            slug: Some("quotes-and-slashes".to_string()),
+            tags: vec!["rust".to_string(), "web".to_string()],
+            category: Some("programming".to_string()),
+            published_at: Some(Utc::now()),
+            created_at: Some(Utc::now()),
+            summary: Some("Two\nlines".to_string()),
+        };
+
+        let document = format!("{}Body", front_matter.render());
+        let (parsed, body) = FrontMatter::parse(&document).unwrap();
+        assert_eq!(parsed, front_matter);
+        assert_eq!(body, "Body");
+    }
+}
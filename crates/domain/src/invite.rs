@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Length of a generated invite code
+pub const INVITE_CODE_LENGTH: usize = 16;
+
+/// A single-use code that allows registering on an invite-only site
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InviteCode {
+    pub code: String,
+    /// Admin who created the code; `None` once that account is deleted
+    pub created_by: Option<Uuid>,
+    /// User who registered with the code
+    pub used_by: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InviteCode {
+    /// Check if someone already registered with the code
+    pub fn is_used(&self) -> bool {
+        self.used_by.is_some()
+    }
+
+    /// Check if the code has expired at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Request to create an invite code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInviteRequest {
+    pub expires_in_hours: u64,
+}
+
+/// Response carrying a newly created invite code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInviteResponse {
+    pub code: String,
+}
@@ -6,42 +6,72 @@
 //! The domain layer defines the core business entities and rules that are
 //! independent of any infrastructure or API concerns.
 
+pub mod api_key;
 pub mod category;
 pub mod comment;
 pub mod config;
 pub mod error;
 pub mod file;
+pub mod import;
 pub mod indexnow;
+pub mod invite;
 pub mod post;
 pub mod repository;
+pub mod search;
 pub mod session;
 pub mod stats;
 pub mod tag;
 pub mod user;
+pub mod webhook;
 
 // Re-export commonly used types for convenience
-pub use category::{Category, CreateCategory, UpdateCategory};
+pub use api_key::{
+    ApiKey, CreateApiKey, CreateApiKeyResponse, API_KEY_PREFIX, API_KEY_SCOPES, SCOPE_READ_POSTS,
+    SCOPE_WRITE_COMMENTS, SCOPE_WRITE_POSTS,
+};
+pub use category::{Category, CategoryNode, CreateCategory, UpdateCategory, MAX_CATEGORY_DEPTH};
 pub use comment::{
-    Comment, CommentResponse, CreateComment, CreateCommentGitHub, GitHubAuthRequest, GitHubUser,
+    AddReactionRequest, Comment, CommentResponse, CommentStatus, CommentThread, CreateComment,
+    CreateCommentGitHub, GitHubAuthRequest, GitHubUser, PaginatedComments, REACTION_TYPES,
 };
 pub use config::{
-    AuthConfig, Config, ConfigRepository, DatabaseConfig, GitHubConfig, IndexNowConfig,
-    PublicConfig, ServerConfig, SiteConfig, StorageConfig, UpdateAuthConfig, UpdateConfigRequest,
-    UpdateDatabaseConfig, UpdateGitHubConfig, UpdateIndexNowConfig, UpdateServerConfig,
-    UpdateSiteConfig, UpdateStorageConfig,
+    AuthConfig, CommentsConfig, Config, ConfigRepository, DatabaseConfig, GitHubConfig,
+    IndexNowConfig, PublicConfig, ServerConfig, SiteConfig, SmtpConfig, StorageConfig,
+    UpdateAuthConfig, UpdateCommentsConfig, UpdateConfigRequest, UpdateDatabaseConfig,
+    UpdateGitHubConfig, UpdateIndexNowConfig, UpdateServerConfig, UpdateSiteConfig,
+    UpdateSmtpConfig, UpdateStorageConfig, UpdateWebhookConfig, WebhookConfig,
+    DEFAULT_ALLOWED_EXTENSIONS, EDITABLE_SITE_FIELDS,
 };
-pub use error::{Error, Result};
-pub use file::{File, FileResponse, UploadFile};
+pub use error::{Error, ErrorCode, Result};
+pub use file::{File, FileListing, FileResponse, FileServeResponse, StorageUsage, UploadFile};
+pub use import::{ExportPost, FrontMatter, ImportError, ImportPostsResponse, ImportResult};
 pub use indexnow::{IndexNowRequest, IndexNowResponse};
-pub use post::{CreatePost, Post, SearchPostsRequest, SearchPostsResponse, UpdatePost};
+pub use invite::{CreateInviteRequest, CreateInviteResponse, InviteCode, INVITE_CODE_LENGTH};
+pub use post::{
+    AdjacentPosts, ArchiveMonth, BulkPublishRequest, BulkResult, CreatePost, FeatureImageSource,
+    HtmlAllowList, LikeResponse, LinkCheckResult, NewScheduledPost, OgData, Post, PostDetail,
+    PostLengthLimits, PostWithAuthor, PostWithStats, PostWithTags, SchedulePostRequest,
+    SearchPostsRequest, SearchPostsResponse, SetFeatureImageRequest, TocEntry, UpdatePost,
+    WordCountStats,
+};
 pub use repository::{
-    CategoryRepository, CommentRepository, FileRepository, PostRepository, SessionRepository,
-    StatsRepository, TagRepository, UserRepository,
+    ApiKeyRepository, CategoryRepository, CommentRepository, FileRepository, HealthRepository,
+    InviteRepository, LikeRepository, PostRepository, SessionRepository, StatsRepository,
+    TagRepository, UserRepository,
+};
+pub use search::{PostSearchRequest, PostSearchRequestBuilder, SortDir, SortField};
+pub use session::{CreateSession, RefreshTokenRequest, Session};
+pub use stats::{
+    DailyStats, DailyTopPost, PostStats, RecordViewRequest, StatsResponse, VisitStats,
 };
-pub use session::{CreateSession, Session};
-pub use stats::{DailyStats, PostStats, RecordViewRequest, StatsResponse, VisitStats};
-pub use tag::{CreateTag, Tag};
-pub use user::{LoginRequest, LoginResponse, RegisterRequest, User, UserInfo};
+pub use tag::{CreateTag, Tag, TagCloudEntry};
+pub use user::{
+    BatchCreateResult, LoginRequest, LoginResponse, LoginResult, NewUserEntry, OtpRequiredResponse,
+    PaginatedUsers, PasswordResetRequest, RegisterRequest, ResetPasswordRequest, TotpCodeRequest,
+    TotpLoginRequest, TotpSetupResponse, UpdateEmailRequest, UpdateUser, User, UserChanges,
+    UserInfo, UserProfile,
+};
+pub use webhook::{WebhookEvent, WebhookPayload};
 
 // ============================================================================
 // Permission Constants (Bit Flags)
@@ -65,13 +95,55 @@ pub const POST_PUBLISH: u64 = 1 << 3;
 /// Permission to manage users (admin only)
 pub const USER_MANAGE: u64 = 1 << 4;
 
+/// Permission to create, update, and delete categories
+pub const CATEGORY_MANAGE: u64 = 1 << 5;
+
+/// Permission to approve, reject, and view pending comments
+pub const COMMENT_MODERATE: u64 = 1 << 6;
+
+/// Permission to manage files uploaded by other users
+pub const FILE_MANAGE: u64 = 1 << 7;
+
+/// Permission to impersonate other admins
+///
+/// Not part of [`ADMIN_PERMISSIONS`]; it must be granted explicitly.
+pub const SUPER_ADMIN: u64 = 1 << 8;
+
 /// Default permissions for regular users
 /// Can create, update, and publish their own posts
 pub const DEFAULT_USER_PERMISSIONS: u64 = POST_CREATE | POST_UPDATE | POST_PUBLISH;
 
 /// Admin permissions - all permissions combined
-pub const ADMIN_PERMISSIONS: u64 =
-    POST_CREATE | POST_UPDATE | POST_DELETE | POST_PUBLISH | USER_MANAGE;
+pub const ADMIN_PERMISSIONS: u64 = POST_CREATE
+    | POST_UPDATE
+    | POST_DELETE
+    | POST_PUBLISH
+    | USER_MANAGE
+    | CATEGORY_MANAGE
+    | COMMENT_MODERATE
+    | FILE_MANAGE;
+
+/// Every permission flag with its name, lowest bit first
+pub const PERMISSION_NAMES: [(u64, &str); 9] = [
+    (POST_CREATE, "POST_CREATE"),
+    (POST_UPDATE, "POST_UPDATE"),
+    (POST_DELETE, "POST_DELETE"),
+    (POST_PUBLISH, "POST_PUBLISH"),
+    (USER_MANAGE, "USER_MANAGE"),
+    (CATEGORY_MANAGE, "CATEGORY_MANAGE"),
+    (COMMENT_MODERATE, "COMMENT_MODERATE"),
+    (FILE_MANAGE, "FILE_MANAGE"),
+    (SUPER_ADMIN, "SUPER_ADMIN"),
+];
+
+/// Names of the flags set in `permissions`, lowest bit first
+pub fn permission_names(permissions: u64) -> Vec<&'static str> {
+    PERMISSION_NAMES
+        .iter()
+        .filter(|(flag, _)| permissions & flag != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
 
 // ============================================================================
 // Permission Checking Helpers
@@ -90,7 +162,7 @@ pub const ADMIN_PERMISSIONS: u64 =
 ///
 /// # Returns
 /// * `Ok(())` if the requester is the owner OR has admin permission
-/// * `Err(Error::Validation)` if neither condition is met
+/// * `Err(Error::CodedError)` with `PermissionDenied` if neither condition is met
 pub fn check_ownership_or_admin(
     resource_owner_id: uuid::Uuid,
     requester_id: uuid::Uuid,
@@ -105,8 +177,15 @@ pub fn check_ownership_or_admin(
         return Ok(());
     }
 
-    Err(Error::Validation(
-        "Permission denied: you must be the resource owner or have admin privileges".to_string(),
+    tracing::warn!(
+        %resource_owner_id,
+        %requester_id,
+        "Permission denied: requester neither owns the resource nor has flag {:#x}",
+        admin_permission
+    );
+    Err(Error::coded(
+        ErrorCode::PermissionDenied,
+        "Permission denied: you must be the resource owner or have admin privileges",
     ))
 }
 
@@ -118,17 +197,21 @@ pub fn check_ownership_or_admin(
 ///
 /// # Returns
 /// * `Ok(())` if the user has the required permission
-/// * `Err(Error::Validation)` if the user lacks the permission
+/// * `Err(Error::CodedError)` with `PermissionDenied` if the user lacks the permission
 pub fn check_permission(user_permissions: u64, required_permission: u64) -> Result<()> {
     if (user_permissions & required_permission) != 0 {
         Ok(())
     } else {
-        Err(Error::Validation(
+        tracing::warn!(
+            "Permission denied: requires permission flag {:#x}",
+            required_permission
+        );
+        Err(Error::coded(
+            ErrorCode::PermissionDenied,
             format!(
                 "Permission denied: requires permission flag {:#x}",
                 required_permission
-            )
-            .to_string(),
+            ),
         ))
     }
 }
@@ -1,7 +1,65 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Average adult reading speed used for reading time estimates
+pub const WORDS_PER_MINUTE: u32 = 238;
+
+/// Maximum length of a generated summary, in characters
+pub const SUMMARY_MAX_CHARS: usize = 300;
+
+/// Maximum length of an OpenGraph description, in characters
+pub const OG_DESCRIPTION_MAX_CHARS: usize = 160;
+
+/// Default maximum post content length for non-admins, in characters
+pub const DEFAULT_MAX_POST_CHARS: usize = 100_000;
+
+/// Maximum post content length by permission level, in characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostLengthLimits {
+    pub default_max_chars: usize,
+    /// Applies to callers with `USER_MANAGE`
+    pub admin_max_chars: usize,
+}
+
+impl Default for PostLengthLimits {
+    fn default() -> Self {
+        Self {
+            default_max_chars: DEFAULT_MAX_POST_CHARS,
+            admin_max_chars: usize::MAX,
+        }
+    }
+}
+
+impl PostLengthLimits {
+    /// Limit that applies to a caller with `permissions`
+    pub fn max_chars(&self, permissions: u64) -> usize {
+        if permissions & crate::USER_MANAGE != 0 {
+            self.admin_max_chars
+        } else {
+            self.default_max_chars
+        }
+    }
+}
+
+/// HTML a post may contain besides the sanitizer's safe defaults
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlAllowList {
+    pub tags: Vec<String>,
+    /// Attributes kept per tag name
+    pub attributes: HashMap<String, Vec<String>>,
+}
+
+impl Default for HtmlAllowList {
+    fn default() -> Self {
+        Self {
+            tags: config::types::default_allowed_html_tags(),
+            attributes: config::types::default_allowed_html_attributes(),
+        }
+    }
+}
+
 /// Represents a blog post
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Post {
@@ -17,6 +75,29 @@ pub struct Post {
     pub indexnow_submitted_at: Option<DateTime<Utc>>,
     pub indexnow_last_status: Option<String>,
     pub indexnow_last_error: Option<String>,
+    /// Set when the post is soft-deleted; such posts are hidden from all queries
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Set while the post is pinned to the top of listings
+    #[serde(default)]
+    pub pinned_at: Option<DateTime<Utc>>,
+    /// Short plain-text preview shown in listings
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Whether `summary` is generated from the content rather than set by the author
+    #[serde(default = "default_use_auto_summary")]
+    pub use_auto_summary: bool,
+    /// When an unpublished post should go live automatically
+    #[serde(default)]
+    pub scheduled_publish_at: Option<DateTime<Utc>>,
+    /// Hero image shown in listings and social previews
+    #[serde(default)]
+    pub feature_image_url: Option<String>,
+    /// Words in the content, ignoring Markdown syntax; not stored
+    #[serde(default)]
+    pub word_count: u32,
+    /// Estimated reading time at [`WORDS_PER_MINUTE`]; not stored
+    #[serde(default)]
+    pub reading_time_minutes: u32,
 }
 
 impl Post {
@@ -25,8 +106,6 @@ impl Post {
         Self {
             id: Uuid::new_v4(),
             user_id,
-            title,
-            content,
             category_id: None,
             published_at: None,
             created_at: Utc::now(),
@@ -35,6 +114,16 @@ impl Post {
             indexnow_submitted_at: None,
             indexnow_last_status: None,
             indexnow_last_error: None,
+            deleted_at: None,
+            pinned_at: None,
+            summary: auto_summary(&content),
+            use_auto_summary: true,
+            scheduled_publish_at: None,
+            feature_image_url: None,
+            title,
+            content,
+            word_count: 0,
+            reading_time_minutes: 0,
         }
     }
 
@@ -46,6 +135,7 @@ impl Post {
     /// Publish the post
     pub fn publish(&mut self) {
         self.published_at = Some(Utc::now());
+        self.scheduled_publish_at = None;
     }
 
     /// Unpublish the post
@@ -53,6 +143,39 @@ impl Post {
         self.published_at = None;
     }
 
+    /// Check if the post is waiting for a scheduled publication
+    pub fn is_scheduled(&self) -> bool {
+        self.scheduled_publish_at.is_some() && !self.is_published()
+    }
+
+    /// Check if the post is pinned to the top of listings
+    pub fn is_pinned(&self) -> bool {
+        self.pinned_at.is_some()
+    }
+
+    /// Set a manual summary, or go back to the generated one with `None`
+    ///
+    /// A blank summary counts as `None`.
+    pub fn set_summary(&mut self, summary: Option<String>) {
+        match summary.filter(|s| !s.trim().is_empty()) {
+            Some(summary) => {
+                self.summary = Some(summary.trim().to_string());
+                self.use_auto_summary = false;
+            }
+            None => {
+                self.use_auto_summary = true;
+                self.refresh_auto_summary();
+            }
+        }
+    }
+
+    /// Regenerate the summary from the content unless it was set manually
+    pub fn refresh_auto_summary(&mut self) {
+        if self.use_auto_summary {
+            self.summary = auto_summary(&self.content);
+        }
+    }
+
     /// Check if the post is owned by a specific user
     pub fn is_owned_by(&self, user_id: Uuid) -> bool {
         self.user_id == user_id
@@ -62,6 +185,199 @@ impl Post {
     pub fn increment_view(&mut self) {
         self.views += 1;
     }
+
+    /// Fill in `word_count` and `reading_time_minutes` from the content
+    pub fn compute_reading_stats(&mut self) {
+        self.word_count = count_words(&self.content);
+        self.reading_time_minutes = self.word_count.div_ceil(WORDS_PER_MINUTE);
+    }
+
+    /// URL-friendly slug derived from the title
+    ///
+    /// Non-alphanumeric runs collapse into a single `-`. Falls back to the
+    /// post ID when the title contains no ASCII alphanumerics.
+    pub fn slug(&self) -> String {
+        let mut slug = String::with_capacity(self.title.len());
+        for c in self.title.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let slug = slug.trim_end_matches('-');
+
+        if slug.is_empty() {
+            self.id.to_string()
+        } else {
+            slug.to_string()
+        }
+    }
+}
+
+/// Count the words of Markdown content
+///
+/// Code fence lines, link targets and table separators are dropped, and
+/// tokens made only of syntax characters (`#`, `-`, `|`, `>` ...) are not
+/// counted as words.
+fn count_words(content: &str) -> u32 {
+    let mut count = 0;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            continue;
+        }
+        count += strip_link_targets(line, " ")
+            .split(|c: char| c.is_whitespace() || c == '|')
+            .filter(|token| token.chars().any(char::is_alphanumeric))
+            .count() as u32;
+    }
+    count
+}
+
+/// Plain text of the first paragraph, cut at [`SUMMARY_MAX_CHARS`]
+///
+/// Headings and code blocks are skipped, Markdown syntax is stripped and
+/// whitespace collapsed. Returns `None` when there is no prose at all.
+fn auto_summary(content: &str) -> Option<String> {
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut in_code = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || line.starts_with('#') {
+            continue;
+        }
+        if line.is_empty() {
+            if paragraph.is_empty() {
+                continue;
+            }
+            break;
+        }
+
+        let text = strip_inline_markdown(line);
+        if text.chars().any(char::is_alphanumeric) {
+            paragraph.push(text);
+        }
+    }
+
+    let text = paragraph.join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= SUMMARY_MAX_CHARS {
+        return Some(text);
+    }
+
+    let mut summary: String = text.chars().take(SUMMARY_MAX_CHARS - 1).collect();
+    summary.truncate(summary.trim_end().len());
+    summary.push('…');
+    Some(summary)
+}
+
+/// Plain text of Markdown content with whitespace collapsed
+///
+/// Code blocks, images and table separator rows are dropped; heading, list
+/// and quote markers, emphasis and link targets are stripped.
+pub fn plain_text(content: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut in_code = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        let text = strip_inline_markdown(line.trim_start_matches('#'));
+        words.extend(
+            text.split(|c: char| c.is_whitespace() || c == '|')
+                .filter(|token| token.chars().any(char::is_alphanumeric))
+                .map(String::from),
+        );
+    }
+    words.join(" ")
+}
+
+/// URL of the first Markdown (`![alt](src)`) or HTML (`<img src="...">`) image
+pub fn first_image_url(content: &str) -> Option<&str> {
+    let markdown = content.find("![").and_then(|start| {
+        let rest = &content[start..];
+        let open = rest.find("](")? + 2;
+        let close = rest[open..].find(')')? + open;
+        let src = rest[open..close].split_whitespace().next()?;
+        Some((start, src))
+    });
+
+    let html = content.find("<img").and_then(|start| {
+        let rest = &content[start..];
+        let tag = &rest[..rest.find('>')?];
+        let attr = tag.find("src=")? + 4;
+        let quote = tag[attr..]
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')?;
+        let value = &tag[attr + 1..];
+        Some((start, &value[..value.find(quote)?]))
+    });
+
+    match (markdown, html) {
+        (Some(md), Some(img)) => Some(if md.0 < img.0 { md.1 } else { img.1 }),
+        (Some((_, src)), None) | (None, Some((_, src))) => Some(src),
+        (None, None) => None,
+    }
+    .filter(|src| !src.is_empty())
+}
+
+/// Strip inline Markdown from one line: images, link targets, leading
+/// quote/list markers and emphasis characters
+fn strip_inline_markdown(line: &str) -> String {
+    let text = strip_images(line);
+    let text = strip_link_targets(&text, "");
+    text.trim_start_matches(['>', '-', '+', ' '])
+        .replace(['*', '_', '`', '~', '[', ']'], "")
+}
+
+/// Remove Markdown images (`![alt](src)`) entirely
+fn strip_images(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("![") {
+        output.push_str(&rest[..start]);
+        rest = match rest[start..].find(')') {
+            Some(close) => &rest[start + close + 1..],
+            None => "",
+        };
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Replace the `](url)` part of Markdown links and images with `separator`
+fn strip_link_targets(line: &str, separator: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(index) = rest.find("](") {
+        output.push_str(&rest[..index]);
+        output.push_str(separator);
+        rest = match rest[index..].find(')') {
+            Some(close) => &rest[index + close + 1..],
+            None => "",
+        };
+    }
+    output.push_str(rest);
+    output
+}
+
+fn default_use_auto_summary() -> bool {
+    true
 }
 
 /// Request to create a new post
@@ -69,6 +385,9 @@ impl Post {
 pub struct CreatePost {
     pub title: String,
     pub content: String,
+    /// Generated from the content when omitted
+    #[serde(default)]
+    pub summary: Option<String>,
 }
 
 /// Request to update an existing post
@@ -76,6 +395,9 @@ pub struct CreatePost {
 pub struct UpdatePost {
     pub title: Option<String>,
     pub content: Option<String>,
+    /// A blank summary switches back to the generated one
+    #[serde(default)]
+    pub summary: Option<String>,
 }
 
 /// Request to search posts
@@ -93,3 +415,255 @@ pub struct SearchPostsResponse {
     pub total: u64,
     pub query: String,
 }
+
+/// A single post together with its reader engagement counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostDetail {
+    #[serde(flatten)]
+    pub post: Post,
+    pub likes: u64,
+}
+
+/// Social sharing preview data of a published post
+///
+/// Lets a single-page frontend set OpenGraph meta tags itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OgData {
+    pub title: String,
+    /// Plain text start of the content, at most [`OG_DESCRIPTION_MAX_CHARS`]
+    pub description: String,
+    /// Feature image, or else the first image in the content
+    pub image_url: Option<String>,
+    pub url: String,
+    /// Author's username, or [`DELETED_AUTHOR_USERNAME`]
+    pub author: String,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Tag names
+    pub tags: Vec<String>,
+}
+
+/// The published posts either side of a post, by publication date
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AdjacentPosts {
+    /// Published just before the post
+    pub prev: Option<Post>,
+    /// Published just after the post
+    pub next: Option<Post>,
+}
+
+/// Username shown for posts whose author account no longer exists
+pub const DELETED_AUTHOR_USERNAME: &str = "[deleted]";
+
+/// A post together with the author details shown in listings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostWithAuthor {
+    #[serde(flatten)]
+    pub post: Post,
+    /// [`DELETED_AUTHOR_USERNAME`] when the author was deleted
+    pub author_username: String,
+    pub author_avatar_url: Option<String>,
+}
+
+/// A post with its author details and tags, as shown in listings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostWithTags {
+    #[serde(flatten)]
+    pub post: PostWithAuthor,
+    /// Tags of the post, ordered by name
+    pub tags: Vec<crate::Tag>,
+}
+
+/// A post with the counts shown on its author's dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostWithStats {
+    pub post: Post,
+    pub views: u64,
+    pub comment_count: u64,
+}
+
+/// Blog-wide word counts shown on the admin dashboard
+///
+/// Words are approximated as the number of spaces plus one. The post IDs
+/// are `None` when no post matches.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WordCountStats {
+    pub total_words: u64,
+    pub average_words_per_post: f64,
+    pub longest_post_id: Option<Uuid>,
+    pub shortest_post_id: Option<Uuid>,
+}
+
+/// Number of posts published in one calendar month (UTC)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchiveMonth {
+    pub year: i32,
+    pub month: u32,
+    pub count: u32,
+}
+
+/// Request to publish or unpublish several posts at once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkPublishRequest {
+    pub post_ids: Vec<Uuid>,
+    /// Future publish time; omitted to publish immediately
+    #[serde(default)]
+    pub schedule_at: Option<DateTime<Utc>>,
+}
+
+/// Request to publish a post automatically at a later time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulePostRequest {
+    pub publish_at: DateTime<Utc>,
+}
+
+/// A post to create unpublished and publish automatically later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewScheduledPost {
+    pub title: String,
+    pub content: String,
+    /// Tag slugs; missing tags are created
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Category slug; a missing category is created
+    #[serde(default)]
+    pub category: Option<String>,
+    pub scheduled_publish_at: DateTime<Utc>,
+}
+
+/// Request to set a post's feature image; exactly one field must be set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetFeatureImageRequest {
+    /// ID of an image already uploaded through `/api/files`
+    pub file_id: Option<Uuid>,
+    /// External `https://` image URL
+    pub url: Option<String>,
+}
+
+/// Where a feature image comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeatureImageSource {
+    /// An uploaded file, referenced by ID
+    File(Uuid),
+    /// An external image URL
+    Url(String),
+}
+
+impl TryFrom<SetFeatureImageRequest> for FeatureImageSource {
+    type Error = crate::Error;
+
+    fn try_from(request: SetFeatureImageRequest) -> crate::Result<Self> {
+        match (request.file_id, request.url) {
+            (Some(file_id), None) => Ok(Self::File(file_id)),
+            (None, Some(url)) => Ok(Self::Url(url)),
+            _ => Err(crate::Error::Validation(
+                "Provide either file_id or url".to_string(),
+            )),
+        }
+    }
+}
+
+/// Outcome of a bulk operation on posts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkResult {
+    pub succeeded: Vec<Uuid>,
+    /// Posts that were left unchanged, with the reason
+    pub failed: Vec<(Uuid, String)>,
+}
+
+/// One heading in a post's table of contents
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TocEntry {
+    /// Heading level, 1 for `#` through 6 for `######`
+    pub level: u8,
+    /// Heading text without Markdown syntax
+    pub text: String,
+    /// Fragment identifier for linking to the heading, unique within the post
+    pub anchor: String,
+}
+
+/// Outcome of checking one external link in a post
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LinkCheckResult {
+    pub url: String,
+    /// HTTP status answering a HEAD request, 0 when the URL was not reachable
+    pub status: u16,
+}
+
+/// Response after liking a post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LikeResponse {
+    /// Whether this request added a new like (false if already liked)
+    pub liked: bool,
+    pub likes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED_MARKDOWN: &str = r#"# Getting Started
+
+Install the **CLI** with `cargo install peng-blog` and read the [docs](https://example.com/docs).
+
+```rust
+fn main() {}
+```
+
+| Command | Purpose |
+|---------|---------|
+| init | Create config |
+
+- One item
+> Quoted ![logo](/logo.png) text
+"#;
+
+    #[test]
+    fn test_auto_summary_uses_first_paragraph_as_plain_text() {
+        let post = Post::new(Uuid::new_v4(), "Title".to_string(), MIXED_MARKDOWN.into());
+        assert_eq!(
+            post.summary.as_deref(),
+            Some("Install the CLI with cargo install peng-blog and read the docs.")
+        );
+
+        let long = format!("{}\n\nSecond paragraph", "word ".repeat(100));
+        let summary = auto_summary(&long).unwrap();
+        assert_eq!(summary.chars().count(), SUMMARY_MAX_CHARS);
+        assert!(summary.ends_with("word…"));
+        assert_eq!(auto_summary("```\ncode only\n```"), None);
+    }
+
+    #[test]
+    fn test_compute_reading_stats_ignores_markdown_syntax() {
+        let mut post = Post::new(Uuid::new_v4(), "Title".to_string(), MIXED_MARKDOWN.into());
+        post.compute_reading_stats();
+
+        // Getting Started (2), Install ... docs (11), fn main() {} (2),
+        // Command Purpose (2), init Create config (3), One item (2),
+        // Quoted logo text (3)
+        assert_eq!(post.word_count, 25);
+        assert_eq!(post.reading_time_minutes, 1);
+    }
+
+    #[test]
+    fn test_plain_text_strips_markdown() {
+        assert_eq!(
+            plain_text(MIXED_MARKDOWN),
+            "Getting Started Install the CLI with cargo install peng-blog and read the docs. \
+             Command Purpose init Create config One item Quoted text"
+        );
+    }
+
+    #[test]
+    fn test_reading_time_rounds_up() {
+        let words = vec!["word"; WORDS_PER_MINUTE as usize + 1].join(" ");
+        let mut post = Post::new(Uuid::new_v4(), "Title".to_string(), words);
+        post.compute_reading_stats();
+
+        assert_eq!(post.word_count, WORDS_PER_MINUTE + 1);
+        assert_eq!(post.reading_time_minutes, 2);
+
+        let mut empty = Post::new(Uuid::new_v4(), "Empty".to_string(), String::new());
+        empty.compute_reading_stats();
+        assert_eq!(empty.reading_time_minutes, 0);
+    }
+}
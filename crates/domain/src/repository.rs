@@ -12,10 +12,14 @@
 
 use crate::stats::StatsResponse;
 use crate::{
-    Category, Comment, File, Post, PostStats, Result, SearchPostsResponse, Session, Tag, User,
-    VisitStats,
+    ApiKey, ArchiveMonth, Category, Comment, CommentStatus, DailyStats, DailyTopPost, File,
+    InviteCode, Post, PostSearchRequest, PostStats, PostWithAuthor, PostWithTags, Result,
+    SearchPostsResponse, Session, Tag, TagCloudEntry, User, UserChanges, VisitStats,
+    WordCountStats,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 // ============================================================================
@@ -31,36 +35,183 @@ pub trait PostRepository: Send + Sync {
     /// Create a new post
     async fn create_post(&self, user_id: Uuid, title: String, content: String) -> Result<Post>;
 
+    /// Insert or fully overwrite several posts by ID in one transaction
+    ///
+    /// Either every post is stored or, on any failure, none is.
+    async fn batch_upsert_posts(&self, posts: Vec<Post>) -> Result<Vec<Post>>;
+
     /// Get a post by ID
     async fn get_post(&self, id: Uuid) -> Result<Post>;
 
     /// Update an existing post
     async fn update_post(&self, post: Post) -> Result<Post>;
 
-    /// List published posts with a limit
-    async fn list_published_posts(&self, limit: u64) -> Result<Vec<Post>>;
+    /// List published posts, skipping the first `offset` of them
+    async fn list_published_posts(&self, limit: u64, offset: u64) -> Result<Vec<Post>>;
+
+    /// List published posts like [`Self::list_published_posts`], each with
+    /// its author's username and avatar in the same query
+    async fn list_published_posts_with_authors(
+        &self,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<PostWithAuthor>>;
+
+    /// List published posts like [`Self::list_published_posts_with_authors`],
+    /// each also with its tags
+    ///
+    /// Posts, authors and tags come from one query, however many posts the
+    /// page holds, instead of a tags query per post.
+    async fn list_posts_with_tags(&self, limit: u64, offset: u64) -> Result<Vec<PostWithTags>>;
+
+    /// Count published posts, excluding deleted ones
+    async fn count_published(&self) -> Result<u64>;
+
+    /// List published posts with the most viewed first
+    async fn list_published_posts_by_views(&self, limit: u64) -> Result<Vec<Post>>;
 
-    /// Delete a post by ID
+    /// List posts published in `[start, end)`, newest first
+    async fn list_published_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<Post>>;
+
+    /// Count published posts per month, newest month first
+    async fn count_published_by_month(&self) -> Result<Vec<ArchiveMonth>>;
+
+    /// Set `published_at` and `scheduled_publish_at` on many posts in one statement
+    ///
+    /// No ownership checks are made; callers must filter `ids` first.
+    /// Returns the number of posts updated.
+    async fn bulk_update_publication(
+        &self,
+        ids: Vec<Uuid>,
+        published_at: Option<DateTime<Utc>>,
+        scheduled_publish_at: Option<DateTime<Utc>>,
+    ) -> Result<u64>;
+
+    /// Soft-delete a post by ID, hiding it from all queries
     async fn delete_post(&self, id: Uuid) -> Result<()>;
 
+    /// Permanently remove a post by ID
+    async fn hard_delete_post(&self, id: Uuid) -> Result<()>;
+
+    /// Restore a soft-deleted post
+    async fn restore_post(&self, id: Uuid) -> Result<Post>;
+
+    /// Permanently remove posts soft-deleted before the given time
+    async fn purge_deleted_posts(&self, before: DateTime<Utc>) -> Result<u64>;
+
+    /// Move a post with its tags, comments and likes to the archive tables
+    async fn archive_post(&self, id: Uuid) -> Result<()>;
+
+    /// Move an archived post back to the live tables
+    async fn unarchive_post(&self, id: Uuid) -> Result<()>;
+
+    /// Get an archived post by ID
+    async fn get_archived_post(&self, id: Uuid) -> Result<Post>;
+
+    /// List archived posts, most recently published first
+    async fn list_archived_posts(&self, limit: u64) -> Result<Vec<Post>>;
+
+    /// Published posts sharing at least one of the tags, with the number shared
+    ///
+    /// Sorted by shared tag count, then by publication date, newest first.
+    async fn get_posts_sharing_tags(
+        &self,
+        tag_ids: Vec<Uuid>,
+        exclude_post_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<(Post, u32)>>;
+
+    /// The live published post following one, by publication date
+    ///
+    /// Posts published at the same instant are ordered by ID.
+    async fn get_next_published_post(
+        &self,
+        after_published_at: DateTime<Utc>,
+        current_id: Uuid,
+    ) -> Result<Option<Post>>;
+
+    /// The live published post preceding one, by publication date
+    ///
+    /// Posts published at the same instant are ordered by ID.
+    async fn get_prev_published_post(
+        &self,
+        before_published_at: DateTime<Utc>,
+        current_id: Uuid,
+    ) -> Result<Option<Post>>;
+
+    /// IDs of live, unpinned posts published before the given time
+    async fn list_published_post_ids_before(&self, before: DateTime<Utc>) -> Result<Vec<Uuid>>;
+
+    /// IDs of live, unpublished posts scheduled to go live at or before `now`
+    async fn list_due_scheduled_post_ids(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>>;
+
     /// Get posts by user ID (including unpublished)
     async fn get_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>>;
 
+    /// Count a user's posts, drafts included
+    async fn count_posts_by_user(&self, user_id: Uuid) -> Result<u64>;
+
     /// List published posts by a specific user
     async fn list_published_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>>;
 
+    /// Posts by a user (including unpublished), newest first, each with its
+    /// view count from the stats table; posts never viewed count 0
+    async fn list_posts_with_view_counts(
+        &self,
+        user_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<(Post, u64)>>;
+
+    /// Total and average word counts of live posts, with the longest and shortest post
+    async fn aggregate_word_counts(&self, published_only: bool) -> Result<WordCountStats>;
+
     /// List all posts (including unpublished) - admin only
     async fn list_all_posts(&self, limit: u64) -> Result<Vec<Post>>;
 
     /// Update post category
+    ///
+    /// Implementations must move the post between the old and new category's
+    /// `post_count` in the same transaction as the update.
     async fn update_post_category(&self, post_id: Uuid, category_id: Option<Uuid>) -> Result<()>;
 
+    /// Increase a category's cached post count by one
+    async fn increment_category_post_count(&self, category_id: Uuid) -> Result<()>;
+
+    /// Decrease a category's cached post count by one, never below zero
+    async fn decrement_category_post_count(&self, category_id: Uuid) -> Result<()>;
+
     /// Get posts by category
     async fn get_posts_by_category(&self, category_id: Uuid, limit: u64) -> Result<Vec<Post>>;
 
+    /// Get published posts of a category and of all its descendant categories
+    async fn get_posts_by_category_recursive(
+        &self,
+        category_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<Post>>;
+
+    /// Page of published posts of a category, newest first, with the total
+    ///
+    /// With `recursive`, posts of all its descendant categories are included.
+    async fn list_published_by_category_paginated(
+        &self,
+        category_id: Uuid,
+        recursive: bool,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<Post>, u64)>;
+
     /// Add tag to post
     async fn add_tag_to_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()>;
 
+    /// Replace all tags of a post in a single transaction
+    async fn replace_post_tags(&self, post_id: Uuid, tag_ids: Vec<Uuid>) -> Result<()>;
+
     /// Remove tag from post
     async fn remove_tag_from_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()>;
 
@@ -77,6 +228,14 @@ pub trait PostRepository: Send + Sync {
         limit: u64,
         offset: u64,
     ) -> Result<SearchPostsResponse>;
+
+    /// Search published posts with every filter in `request` applied
+    ///
+    /// `request.limit` and `request.offset` are used as given.
+    async fn search_posts_advanced(
+        &self,
+        request: &PostSearchRequest,
+    ) -> Result<SearchPostsResponse>;
 }
 
 // ============================================================================
@@ -112,11 +271,74 @@ pub trait UserRepository: Send + Sync {
     /// Update user password
     async fn update_password(&self, user_id: Uuid, new_password: String) -> Result<()>;
 
+    /// Change a user's username, recording the change in the audit log
+    async fn update_username(&self, user_id: Uuid, new_username: String) -> Result<User>;
+
+    /// Replace a user's bio and avatar URL
+    async fn update_profile(
+        &self,
+        user_id: Uuid,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+    ) -> Result<User>;
+
+    /// Apply several changes to a user in a single transaction
+    ///
+    /// A username change is recorded in the audit log like
+    /// [`Self::update_username`].
+    async fn update_user(&self, user_id: Uuid, changes: UserChanges) -> Result<User>;
+
+    /// Append an entry to a user's audit log
+    async fn add_audit_log(
+        &self,
+        user_id: Uuid,
+        action: &str,
+        detail: Option<String>,
+    ) -> Result<()>;
+
+    /// Store the TOTP secret and whether it is enforced at login
+    async fn update_totp(&self, user_id: Uuid, secret: Option<String>, enabled: bool)
+        -> Result<()>;
+
+    /// Record the last accepted TOTP time step
+    ///
+    /// Only a step later than the stored one is written, so two logins
+    /// cannot both use one code; returns whether the step was recorded.
+    async fn update_totp_last_step(&self, user_id: Uuid, step: u64) -> Result<bool>;
+
+    /// Find a user by email address
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>>;
+
+    /// Store a new, unverified email address and its verification token
+    async fn update_email(
+        &self,
+        user_id: Uuid,
+        email: String,
+        verification_token: String,
+    ) -> Result<()>;
+
+    /// Mark the email awaiting `token` as verified, returning its user
+    async fn verify_email(&self, token: &str) -> Result<Option<User>>;
+
+    /// Store or clear the password reset token
+    async fn set_password_reset_token(
+        &self,
+        user_id: Uuid,
+        token: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+
+    /// Find the user a password reset token was issued to
+    async fn find_by_password_reset_token(&self, token: &str) -> Result<Option<User>>;
+
     /// Delete a user by ID
     async fn delete_user(&self, user_id: Uuid) -> Result<()>;
 
     /// List all users (admin only)
     async fn list_users(&self, limit: u64) -> Result<Vec<User>>;
+
+    /// Users whose username contains `query`, by username, with the total match count
+    async fn search_users(&self, query: &str, limit: u64, offset: u64) -> Result<(Vec<User>, u64)>;
 }
 
 // ============================================================================
@@ -129,20 +351,92 @@ pub trait UserRepository: Send + Sync {
 /// Sessions are used for cookie-based authentication.
 #[async_trait]
 pub trait SessionRepository: Send + Sync {
-    /// Create a new session
-    async fn create_session(&self, user_id: Uuid, remember_me: bool) -> Result<Session>;
+    /// Create a new session, recording the client that opened it
+    async fn create_session(
+        &self,
+        user_id: Uuid,
+        remember_me: bool,
+        user_agent: &str,
+        ip_address: &str,
+    ) -> Result<Session>;
 
     /// Get a session by token
     async fn get_session(&self, token: &str) -> Result<Option<Session>>;
 
+    /// Get a session by its refresh token
+    async fn get_session_by_refresh_token(&self, refresh_token: &str) -> Result<Option<Session>>;
+
+    /// Mark the session owning a refresh token as revoked
+    async fn revoke_refresh_token(&self, refresh_token: &str) -> Result<()>;
+
+    /// Move a session's expiry and refresh point forward
+    async fn extend_session(
+        &self,
+        token: &str,
+        new_expires_at: DateTime<Utc>,
+        new_refresh_at: DateTime<Utc>,
+    ) -> Result<()>;
+
     /// Delete a session by token
     async fn delete_session(&self, token: &str) -> Result<()>;
 
-    /// Delete all sessions for a user
-    async fn delete_user_sessions(&self, user_id: Uuid) -> Result<()>;
+    /// List all sessions of a user, newest first
+    async fn list_user_sessions(&self, user_id: Uuid) -> Result<Vec<Session>>;
+
+    /// Delete all sessions for a user, optionally keeping one, returning how many were deleted
+    async fn delete_user_sessions(
+        &self,
+        user_id: Uuid,
+        except_session_id: Option<&str>,
+    ) -> Result<u64>;
 
     /// Clean up expired sessions
     async fn cleanup_expired_sessions(&self) -> Result<u64>;
+
+    /// Count sessions that are neither revoked nor expired
+    async fn count_active_sessions(&self) -> Result<u64>;
+}
+
+// ============================================================================
+// API Key Repository Trait
+// ============================================================================
+
+/// Repository interface for API key operations
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// Store a newly issued API key
+    async fn create_api_key(&self, api_key: ApiKey) -> Result<ApiKey>;
+
+    /// Get an API key by the SHA-256 hash of its plaintext
+    async fn get_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>>;
+
+    /// List a user's API keys, newest first
+    async fn list_user_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>>;
+
+    /// Record that a key was just used
+    async fn touch_api_key(&self, key_hash: &str, used_at: DateTime<Utc>) -> Result<()>;
+
+    /// Delete one of a user's API keys, returning whether it existed
+    async fn delete_api_key(&self, user_id: Uuid, key_hash: &str) -> Result<bool>;
+}
+
+/// Repository interface for registration invite codes
+#[async_trait]
+pub trait InviteRepository: Send + Sync {
+    /// Store a newly created invite code
+    async fn create_invite(&self, invite: InviteCode) -> Result<InviteCode>;
+
+    /// Get an invite by its code
+    async fn get_invite(&self, code: &str) -> Result<Option<InviteCode>>;
+
+    /// List every invite code, newest first
+    async fn list_invites(&self) -> Result<Vec<InviteCode>>;
+
+    /// Record that `user_id` registered with the code
+    ///
+    /// Only an unused code is updated, so two registrations cannot share
+    /// one; returns whether the code was claimed.
+    async fn mark_invite_used(&self, code: &str, user_id: Uuid) -> Result<bool>;
 }
 
 // ============================================================================
@@ -166,6 +460,41 @@ pub trait FileRepository: Send + Sync {
 
     /// Delete a file by ID
     async fn delete_file(&self, id: Uuid, user_id: Uuid) -> Result<()>;
+
+    /// Reassign a file record to another user, leaving the stored file as it is
+    async fn update_file_owner(&self, file_id: Uuid, new_user_id: Uuid) -> Result<File>;
+
+    /// Find the oldest file record stored under `filename`
+    ///
+    /// Deduplicated uploads share one stored file, so several records may match.
+    async fn get_file_by_filename(&self, filename: &str) -> Result<Option<File>>;
+
+    /// Find the oldest file record with the given content hash
+    async fn find_by_hash(&self, hash: &str) -> Result<Option<File>>;
+
+    /// Find a user's file record with the given content hash
+    async fn find_by_hash_for_user(&self, hash: &str, user_id: Uuid) -> Result<Option<File>>;
+
+    /// Count file records sharing the given content hash
+    async fn count_by_hash(&self, hash: &str) -> Result<u64>;
+
+    /// List files of all users, newest first, with the total matching count
+    ///
+    /// `content_type_filter` follows [`File::matches_content_type`].
+    async fn list_all_files(
+        &self,
+        limit: u64,
+        offset: u64,
+        content_type_filter: Option<&str>,
+    ) -> Result<(Vec<File>, u64)>;
+
+    /// Sum the sizes of all files matching the content type filter
+    async fn total_file_size(&self, content_type_filter: Option<&str>) -> Result<u64>;
+
+    /// Sum the sizes of a user's file records
+    ///
+    /// Deduplicated uploads count for every user holding a record of them.
+    async fn total_storage_used_by_user(&self, user_id: Uuid) -> Result<u64>;
 }
 
 // ============================================================================
@@ -184,8 +513,37 @@ pub trait CommentRepository: Send + Sync {
     /// Get a comment by ID
     async fn get_comment(&self, id: Uuid) -> Result<Option<Comment>>;
 
-    /// List comments for a post
-    async fn list_post_comments(&self, post_id: Uuid, limit: u64) -> Result<Vec<Comment>>;
+    /// List comments for a post, optionally restricted to one moderation status
+    async fn list_post_comments(
+        &self,
+        post_id: Uuid,
+        status: Option<CommentStatus>,
+        limit: u64,
+    ) -> Result<Vec<Comment>>;
+
+    /// List comments across all posts with the given moderation status
+    async fn list_comments_by_status(
+        &self,
+        status: CommentStatus,
+        limit: u64,
+    ) -> Result<Vec<Comment>>;
+
+    /// List a user's comments across all posts, newest first, with their total count
+    async fn list_comments_by_user(
+        &self,
+        user_id: Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<Comment>, u64)>;
+
+    /// Search comments whose content or GitHub username contains `query`,
+    /// newest first, each with its post's title, plus the total match count
+    async fn search_comments(
+        &self,
+        query: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<(Comment, Option<String>)>, u64)>;
 
     /// Update a comment
     async fn update_comment(&self, comment: Comment) -> Result<Comment>;
@@ -200,6 +558,40 @@ pub trait CommentRepository: Send + Sync {
 
     /// Get comment count for a post
     async fn get_post_comment_count(&self, post_id: Uuid) -> Result<u64>;
+
+    /// Comment counts of every post written by `author_id` that has comments
+    async fn count_comments_by_post_author(&self, author_id: Uuid) -> Result<HashMap<Uuid, u64>>;
+
+    /// Count comments written by a user
+    async fn count_comments_by_user(&self, user_id: Uuid) -> Result<u64>;
+
+    /// Record a user's reaction to a comment, returning `false` if the user
+    /// already reacted with this emoji
+    async fn add_reaction(
+        &self,
+        comment_id: Uuid,
+        user_id: Uuid,
+        reaction_type: &str,
+    ) -> Result<bool>;
+
+    /// Remove a user's reaction, returning `false` if there was none
+    async fn remove_reaction(
+        &self,
+        comment_id: Uuid,
+        user_id: Uuid,
+        reaction_type: &str,
+    ) -> Result<bool>;
+
+    /// Count the reactions to a comment per emoji
+    async fn count_reactions(&self, comment_id: Uuid) -> Result<HashMap<String, u64>>;
+
+    /// Count the reactions to several comments per emoji in one query
+    ///
+    /// Comments without reactions are left out of the map.
+    async fn count_reactions_for(
+        &self,
+        comment_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, HashMap<String, u64>>>;
 }
 
 // ============================================================================
@@ -227,8 +619,56 @@ pub trait StatsRepository: Send + Sync {
     /// Increment post view count
     async fn increment_post_view(&self, post_id: Uuid) -> Result<()>;
 
+    /// Set a post's view count to zero
+    async fn reset_post_stats(&self, post_id: Uuid) -> Result<()>;
+
+    /// Set the view counts of all posts to zero, returning the number of
+    /// post stats rows reset
+    async fn reset_all_post_stats(&self) -> Result<u64>;
+
+    /// Log a visitor's view of a post
+    ///
+    /// Returns `false` when the visitor already viewed the post at or after
+    /// `since`; otherwise records the view time and returns `true`.
+    async fn log_post_view(
+        &self,
+        post_id: Uuid,
+        visitor_hash: &str,
+        since: DateTime<Utc>,
+    ) -> Result<bool>;
+
     /// Get total statistics (admin only)
     async fn get_total_stats(&self) -> Result<StatsResponse>;
+
+    /// Get per-day visit counts for the last `days` days (including today), oldest first
+    ///
+    /// Days without visits have no row and are omitted.
+    async fn get_daily_stats(&self, days: u32) -> Result<Vec<DailyStats>>;
+
+    /// Get per-day visit counts from `start` to `end` inclusive, oldest first
+    ///
+    /// Days without visits have no row and are omitted.
+    async fn get_daily_stats_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyStats>>;
+
+    /// Get the most viewed non-deleted post of each day from `start` to `end`
+    /// inclusive, oldest first
+    ///
+    /// Days without logged post views are omitted.
+    async fn get_daily_top_posts(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyTopPost>>;
+
+    /// Count a visit on a date formatted as `YYYY-MM-DD`
+    async fn record_daily_visit(&self, date: &str) -> Result<()>;
+
+    /// `(post_id, views)` of the most viewed published posts, highest first
+    async fn get_top_posts(&self, limit: u64) -> Result<Vec<(Uuid, u64)>>;
 }
 
 /// Repository interface for Category operations
@@ -257,6 +697,19 @@ pub trait CategoryRepository: Send + Sync {
     async fn delete_category(&self, id: Uuid) -> Result<()>;
 
     async fn get_children(&self, parent_id: Option<Uuid>) -> Result<Vec<Category>>;
+
+    /// Get a category followed by its parent, grandparent and so on
+    ///
+    /// Returns an empty list if the category does not exist. At most
+    /// `MAX_CATEGORY_DEPTH + 1` categories are returned, so a cycle in the
+    /// `parent_id` chain shows up as an overlong list instead of looping.
+    async fn get_category_with_ancestors(&self, id: Uuid) -> Result<Vec<Category>>;
+
+    /// Count the levels from the root down to a category, the root being 1
+    ///
+    /// Returns 0 if the category does not exist. The count stops past
+    /// `MAX_CATEGORY_DEPTH`, so a cycle cannot make it loop.
+    async fn get_category_depth(&self, id: Uuid) -> Result<u32>;
 }
 
 /// Repository interface for Tag operations
@@ -268,7 +721,46 @@ pub trait TagRepository: Send + Sync {
 
     async fn get_tag_by_slug(&self, slug: &str) -> Result<Option<Tag>>;
 
+    /// Insert a tag, or return the existing one with the same slug
+    ///
+    /// Runs as a single statement, so concurrent callers with the same slug
+    /// all get the one stored row.
+    async fn upsert_tag(&self, name: &str, slug: &str) -> Result<Tag>;
+
     async fn list_tags(&self) -> Result<Vec<Tag>>;
 
+    /// Change the name and slug of a tag
+    async fn update_tag(&self, id: Uuid, name: String, slug: String) -> Result<Tag>;
+
     async fn delete_tag(&self, id: Uuid) -> Result<()>;
+
+    /// Re-point every post from `source_id` to `target_id`, then delete the source tag
+    ///
+    /// Posts that already carry the target tag keep a single reference to it.
+    async fn merge_tags(&self, source_id: Uuid, target_id: Uuid) -> Result<()>;
+
+    /// List tags used by published posts with their post counts, most used first
+    ///
+    /// `normalized_weight` is left at zero for the service to fill in.
+    async fn get_tag_cloud(&self) -> Result<Vec<TagCloudEntry>>;
+}
+
+/// Repository interface for post likes
+///
+/// Likes are stored per post and hashed IP address; raw IPs never reach
+/// the repository.
+#[async_trait]
+pub trait LikeRepository: Send + Sync {
+    /// Record a like, returning `false` if this IP hash already liked the post
+    async fn add_like(&self, post_id: Uuid, ip_hash: &str) -> Result<bool>;
+
+    /// Count the likes of a post
+    async fn count_likes(&self, post_id: Uuid) -> Result<u64>;
+}
+
+/// Repository interface for health checks
+#[async_trait]
+pub trait HealthRepository: Send + Sync {
+    /// Run a trivial query to confirm the database answers
+    async fn ping(&self) -> Result<()>;
 }
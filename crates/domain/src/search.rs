@@ -0,0 +1,175 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Field to order post search results by
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    PublishedAt,
+    CreatedAt,
+    Views,
+    Title,
+}
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDir {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Search over published posts with several independent filters
+///
+/// Every filter that is set must match. `tag_ids` matches posts carrying
+/// all of the given tags. Build one with [`PostSearchRequest::builder`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PostSearchRequest {
+    pub title_contains: Option<String>,
+    pub content_contains: Option<String>,
+    pub author_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    #[serde(default)]
+    pub tag_ids: Vec<Uuid>,
+    pub published_after: Option<DateTime<Utc>>,
+    pub published_before: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sort_by: SortField,
+    #[serde(default)]
+    pub sort_dir: SortDir,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+impl PostSearchRequest {
+    /// Start building a request with no filters
+    pub fn builder() -> PostSearchRequestBuilder {
+        PostSearchRequestBuilder::default()
+    }
+
+    /// Check if at least one filter is set
+    ///
+    /// Sorting and paging alone do not count; blank text filters are ignored.
+    pub fn has_filter(&self) -> bool {
+        let has_text =
+            |text: &Option<String>| text.as_deref().is_some_and(|t| !t.trim().is_empty());
+
+        has_text(&self.title_contains)
+            || has_text(&self.content_contains)
+            || self.author_id.is_some()
+            || self.category_id.is_some()
+            || !self.tag_ids.is_empty()
+            || self.published_after.is_some()
+            || self.published_before.is_some()
+    }
+}
+
+/// Builder for [`PostSearchRequest`]
+#[derive(Debug, Default)]
+pub struct PostSearchRequestBuilder {
+    request: PostSearchRequest,
+}
+
+impl PostSearchRequestBuilder {
+    pub fn title_contains(mut self, text: impl Into<String>) -> Self {
+        self.request.title_contains = Some(text.into());
+        self
+    }
+
+    pub fn content_contains(mut self, text: impl Into<String>) -> Self {
+        self.request.content_contains = Some(text.into());
+        self
+    }
+
+    pub fn author_id(mut self, author_id: Uuid) -> Self {
+        self.request.author_id = Some(author_id);
+        self
+    }
+
+    pub fn category_id(mut self, category_id: Uuid) -> Self {
+        self.request.category_id = Some(category_id);
+        self
+    }
+
+    pub fn tag_ids(mut self, tag_ids: Vec<Uuid>) -> Self {
+        self.request.tag_ids = tag_ids;
+        self
+    }
+
+    pub fn published_after(mut self, after: DateTime<Utc>) -> Self {
+        self.request.published_after = Some(after);
+        self
+    }
+
+    pub fn published_before(mut self, before: DateTime<Utc>) -> Self {
+        self.request.published_before = Some(before);
+        self
+    }
+
+    pub fn sort_by(mut self, field: SortField) -> Self {
+        self.request.sort_by = field;
+        self
+    }
+
+    pub fn sort_dir(mut self, dir: SortDir) -> Self {
+        self.request.sort_dir = dir;
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.request.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.request.offset = Some(offset);
+        self
+    }
+
+    pub fn build(self) -> PostSearchRequest {
+        self.request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorting_and_paging_are_not_filters() {
+        let request = PostSearchRequest::builder()
+            .sort_by(SortField::Views)
+            .sort_dir(SortDir::Asc)
+            .limit(5)
+            .offset(10)
+            .title_contains("  ")
+            .build();
+        assert!(!request.has_filter());
+
+        let filtered = [
+            PostSearchRequest::builder().title_contains("rust").build(),
+            PostSearchRequest::builder()
+                .content_contains("axum")
+                .build(),
+            PostSearchRequest::builder()
+                .author_id(Uuid::new_v4())
+                .build(),
+            PostSearchRequest::builder()
+                .category_id(Uuid::new_v4())
+                .build(),
+            PostSearchRequest::builder()
+                .tag_ids(vec![Uuid::new_v4()])
+                .build(),
+            PostSearchRequest::builder()
+                .published_after(Utc::now())
+                .build(),
+            PostSearchRequest::builder()
+                .published_before(Utc::now())
+                .build(),
+        ];
+        assert!(filtered.iter().all(PostSearchRequest::has_filter));
+    }
+}
@@ -7,36 +7,76 @@ use uuid::Uuid;
 pub struct Session {
     pub id: String,
     pub user_id: Uuid,
+    /// Token exchanged at `/api/auth/refresh` for a new access JWT
+    #[serde(skip_serializing, default)]
+    pub refresh_token: String,
+    pub revoked: bool,
+    /// `User-Agent` of the client that created the session
+    pub user_agent: Option<String>,
+    /// IP address of the client that created the session
+    pub ip_address: Option<String>,
     pub expires_at: DateTime<Utc>,
+    /// Activity after this point extends the session, half way to `expires_at`
+    pub refresh_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
 impl Session {
     /// Create a new session with default expiration (24 hours)
     pub fn new(user_id: Uuid) -> Self {
-        Self {
-            id: Self::generate_token(),
-            user_id,
-            expires_at: Utc::now() + Duration::hours(24),
-            created_at: Utc::now(),
-        }
+        Self::with_lifetime(user_id, Duration::hours(24))
     }
 
     /// Create a long-lived session (30 days)
     pub fn with_remember(user_id: Uuid) -> Self {
+        Self::with_lifetime(user_id, Duration::days(30))
+    }
+
+    fn with_lifetime(user_id: Uuid, lifetime: Duration) -> Self {
+        let now = Utc::now();
         Self {
             id: Self::generate_token(),
             user_id,
-            expires_at: Utc::now() + Duration::days(30),
-            created_at: Utc::now(),
+            refresh_token: Self::generate_token(),
+            revoked: false,
+            user_agent: None,
+            ip_address: None,
+            expires_at: now + lifetime,
+            refresh_at: now + lifetime / 2,
+            created_at: now,
         }
     }
 
+    /// How long the session lasts from creation or its latest extension
+    ///
+    /// `refresh_at` always sits half a lifetime before `expires_at`, so the
+    /// original 24 hours or 30 days survive any number of extensions.
+    pub fn lifetime(&self) -> Duration {
+        (self.expires_at - self.refresh_at) * 2
+    }
+
+    /// Check if activity at `now` should push the expiry forward
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        now > self.refresh_at && now < self.expires_at
+    }
+
+    /// Push the expiry a full lifetime past `now`
+    pub fn extend(&mut self, now: DateTime<Utc>) {
+        let lifetime = self.lifetime();
+        self.expires_at = now + lifetime;
+        self.refresh_at = now + lifetime / 2;
+    }
+
     /// Check if session is expired
     pub fn is_expired(&self) -> bool {
         self.expires_at < Utc::now()
     }
 
+    /// Check if session can still be used (not expired or revoked)
+    pub fn is_active(&self) -> bool {
+        !self.revoked && !self.is_expired()
+    }
+
     /// Generate a cryptographically random session token
     fn generate_token() -> String {
         Uuid::new_v4().to_string()
@@ -49,3 +89,9 @@ pub struct CreateSession {
     pub user_id: Uuid,
     pub remember_me: bool,
 }
+
+/// Request to exchange a refresh token for a new access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
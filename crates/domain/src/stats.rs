@@ -67,33 +67,35 @@ impl PostStats {
     }
 }
 
-/// Daily statistics snapshot
+/// Visit count for a single calendar day (UTC)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DailyStats {
     pub date: NaiveDate,
     pub visits: u64,
-    pub unique_visitors: u64,
-    pub page_views: u64,
 }
 
 impl DailyStats {
     /// Create new daily stats
     pub fn new(date: NaiveDate) -> Self {
-        Self {
-            date,
-            visits: 0,
-            unique_visitors: 0,
-            page_views: 0,
-        }
+        Self { date, visits: 0 }
     }
 
     /// Increment visit counter
     pub fn increment_visits(&mut self) {
         self.visits += 1;
-        self.page_views += 1;
     }
 }
 
+/// Most viewed post of a single calendar day (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyTopPost {
+    pub date: NaiveDate,
+    pub post_id: Uuid,
+    pub title: String,
+    /// Visitors whose latest logged view of the post fell on `date`
+    pub views: u64,
+}
+
 /// Response for statistics query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatsResponse {
@@ -107,4 +109,10 @@ pub struct StatsResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordViewRequest {
     pub post_id: Option<Uuid>, // None for homepage, Some for specific post
+    /// Hash identifying the visitor, used to count each post view once per day
+    ///
+    /// Set by the API layer from request headers; client-supplied values are
+    /// overwritten.
+    #[serde(default)]
+    pub visitor_fingerprint: Option<String>,
 }
@@ -26,3 +26,13 @@ pub struct CreateTag {
     pub name: String,
     pub slug: String,
 }
+
+/// A tag with the number of published posts carrying it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagCloudEntry {
+    pub tag: Tag,
+    pub post_count: u64,
+    /// `post_count` relative to the most used tag, from 0.0 to 1.0
+    #[serde(default)]
+    pub normalized_weight: f32,
+}
@@ -10,6 +10,26 @@ pub struct User {
     pub password_hash: String,
     pub permissions: u64,
     pub created_at: DateTime<Utc>,
+    /// Base32 TOTP secret, present once two-factor setup has started
+    pub totp_secret: Option<String>,
+    /// Whether login requires a TOTP code
+    pub totp_enabled: bool,
+    /// Last accepted TOTP time step, used to reject replayed codes
+    pub totp_last_step: Option<u64>,
+    /// Address used for notifications such as new comments on the user's posts
+    pub email: Option<String>,
+    /// Whether `email` was confirmed through the verification link
+    pub email_verified: bool,
+    /// Token from the pending verification email
+    pub email_verification_token: Option<String>,
+    /// Token from the latest password reset email
+    pub password_reset_token: Option<String>,
+    /// When `password_reset_token` stops being accepted
+    pub password_reset_expires_at: Option<DateTime<Utc>>,
+    /// Short self-description shown on the user's profile
+    pub bio: Option<String>,
+    /// HTTPS URL of the user's profile picture
+    pub avatar_url: Option<String>,
 }
 
 impl User {
@@ -21,6 +41,16 @@ impl User {
             password_hash,
             permissions,
             created_at: Utc::now(),
+            totp_secret: None,
+            totp_enabled: false,
+            totp_last_step: None,
+            email: None,
+            email_verified: false,
+            email_verification_token: None,
+            password_reset_token: None,
+            password_reset_expires_at: None,
+            bio: None,
+            avatar_url: None,
         }
     }
 
@@ -35,12 +65,31 @@ impl User {
     }
 }
 
+/// The signed-in user's own profile with activity counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub id: Uuid,
+    pub username: String,
+    pub email: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub permissions: u64,
+    /// Names of the flags set in `permissions`, e.g. `POST_CREATE`
+    pub permission_names: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Posts written by the user, drafts included
+    pub post_count: u64,
+    pub comment_count: u64,
+}
+
 /// Public user information (without password hash)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
     pub id: Uuid,
     pub username: String,
     pub permissions: u64,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
 }
 
 impl From<&User> for UserInfo {
@@ -49,21 +98,110 @@ impl From<&User> for UserInfo {
             id: user.id,
             username: user.username.clone(),
             permissions: user.permissions,
+            bio: user.bio.clone(),
+            avatar_url: user.avatar_url.clone(),
         }
     }
 }
 
+/// A page of user search results with the total number of matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedUsers {
+    pub items: Vec<UserInfo>,
+    pub total: u64,
+}
+
+/// One account of a bulk user import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewUserEntry {
+    pub username: String,
+    pub password: String,
+    /// Create the account with admin permissions
+    #[serde(default)]
+    pub admin: bool,
+}
+
+/// Outcome of a bulk user import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchCreateResult {
+    pub created: Vec<User>,
+    /// Username and reason of every entry that was not created
+    pub failed: Vec<(String, String)>,
+}
+
 /// Request to register a new user
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub username: String,
     pub password: String,
+    /// Required while the site is invite-only
+    #[serde(default)]
+    pub invite_code: Option<String>,
+}
+
+/// Request to change the current user's email address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateEmailRequest {
+    pub email: String,
+}
+
+/// Request to update a user; fields left out keep their value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateUser {
+    pub username: Option<String>,
+    /// New permission bits (admin only)
+    pub permissions: Option<u64>,
+    /// Profile bio; an empty string clears it
+    pub bio: Option<String>,
+    /// HTTPS avatar URL; an empty string clears it
+    pub avatar_url: Option<String>,
+}
+
+impl UpdateUser {
+    /// Whether the request changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.username.is_none()
+            && self.permissions.is_none()
+            && self.bio.is_none()
+            && self.avatar_url.is_none()
+    }
+
+    /// Whether the request touches the username or profile
+    pub fn changes_account(&self) -> bool {
+        self.username.is_some() || self.bio.is_some() || self.avatar_url.is_some()
+    }
+}
+
+/// Validated changes written to a user in one repository call
+///
+/// `None` leaves a field unchanged; `Some(None)` clears the bio or avatar.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserChanges {
+    pub username: Option<String>,
+    pub permissions: Option<u64>,
+    pub bio: Option<Option<String>>,
+    pub avatar_url: Option<Option<String>>,
+}
+
+/// Request to email a password reset link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+/// Request to set a new password with a reset token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
 }
 
 /// Request to login
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginRequest {
-    pub username: String,
+    /// Username, or the account's email address when it contains `@`
+    #[serde(alias = "username")]
+    pub username_or_email: String,
     pub password: String,
 }
 
@@ -73,3 +211,39 @@ pub struct LoginResponse {
     pub token: String,
     pub user: UserInfo,
 }
+
+/// Outcome of a password login
+#[derive(Debug, Clone)]
+pub enum LoginResult {
+    /// Credentials are valid and no second factor is required
+    Authenticated(Box<User>),
+    /// Password accepted; the partial token must be exchanged with a TOTP code
+    RequiresOtp(String),
+}
+
+/// Response returned by login when a TOTP code is still required
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtpRequiredResponse {
+    pub requires_otp: bool,
+    pub partial_token: String,
+}
+
+/// Request to finish a login with a TOTP code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpLoginRequest {
+    pub partial_token: String,
+    pub code: String,
+}
+
+/// Request carrying a TOTP code for the current user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+/// Response for starting TOTP setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpSetupResponse {
+    /// `otpauth://` URI to render as a QR code
+    pub provisioning_uri: String,
+}
@@ -0,0 +1,42 @@
+//! # Webhook Domain Types
+//!
+//! Outgoing webhook payloads sent when post content changes, so that
+//! external build pipelines (Netlify, custom CI, ...) can react to them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::Post;
+
+/// Event types delivered to the configured webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "post.published")]
+    PostPublished,
+    #[serde(rename = "post.unpublished")]
+    PostUnpublished,
+}
+
+/// JSON body POSTed to the webhook URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub post_id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl WebhookPayload {
+    /// Build a payload describing an event on the given post
+    pub fn for_post(event: WebhookEvent, post: &Post) -> Self {
+        Self {
+            event,
+            post_id: post.id,
+            slug: post.slug(),
+            title: post.title.clone(),
+            timestamp: Utc::now(),
+        }
+    }
+}
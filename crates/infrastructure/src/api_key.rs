@@ -0,0 +1,128 @@
+//! API Key Repository Implementation
+//!
+//! This module provides the concrete implementation of ApiKeyRepository
+//! using SeaORM. Only key hashes are stored; scopes are kept as a
+//! comma-separated list.
+
+use crate::entity::api_key;
+use crate::entity::prelude::*;
+use crate::error::DbResultExt;
+use crate::metrics;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::{ApiKey, Result};
+use sea_orm::prelude::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::sync::Arc;
+
+/// Concrete implementation of ApiKeyRepository
+pub struct ApiKeyRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl Clone for ApiKeyRepositoryImpl {
+    fn clone(&self) -> Self {
+        Self {
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+impl ApiKeyRepositoryImpl {
+    /// Create a new API key repository
+    ///
+    /// # Arguments
+    /// * `db` - Database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl domain::ApiKeyRepository for ApiKeyRepositoryImpl {
+    async fn create_api_key(&self, api_key: ApiKey) -> Result<ApiKey> {
+        let _timer = metrics::db_timer("api_key.create_api_key");
+        let active_model = api_key::ActiveModel {
+            key_hash: Set(api_key.key_hash.clone()),
+            user_id: Set(api_key.user_id.to_string()),
+            label: Set(api_key.label.clone()),
+            created_at: Set(api_key.created_at.to_rfc3339()),
+            last_used_at: Set(api_key.last_used_at.map(|t| t.to_rfc3339())),
+            scopes: Set(api_key.scopes.join(",")),
+        };
+
+        active_model
+            .insert(&*self.db)
+            .await
+            .context("Failed to create API key")?;
+
+        Ok(api_key)
+    }
+
+    async fn get_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        let _timer = metrics::db_timer("api_key.get_api_key");
+        let model = ApiKeyEntity::find_by_id(key_hash.to_string())
+            .one(&*self.db)
+            .await
+            .context("Failed to get API key")?;
+
+        Ok(model.map(model_to_api_key))
+    }
+
+    async fn list_user_api_keys(&self, user_id: uuid::Uuid) -> Result<Vec<ApiKey>> {
+        let _timer = metrics::db_timer("api_key.list_user_api_keys");
+        let models = ApiKeyEntity::find()
+            .filter(api_key::Column::UserId.eq(user_id.to_string()))
+            .order_by_desc(api_key::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .context("Failed to list API keys")?;
+
+        Ok(models.into_iter().map(model_to_api_key).collect())
+    }
+
+    async fn touch_api_key(&self, key_hash: &str, used_at: DateTime<Utc>) -> Result<()> {
+        let _timer = metrics::db_timer("api_key.touch_api_key");
+        ApiKeyEntity::update_many()
+            .col_expr(
+                api_key::Column::LastUsedAt,
+                Expr::value(used_at.to_rfc3339()),
+            )
+            .filter(api_key::Column::KeyHash.eq(key_hash))
+            .exec(&*self.db)
+            .await
+            .context("Failed to update API key")?;
+
+        Ok(())
+    }
+
+    async fn delete_api_key(&self, user_id: uuid::Uuid, key_hash: &str) -> Result<bool> {
+        let _timer = metrics::db_timer("api_key.delete_api_key");
+        let result = ApiKeyEntity::delete_many()
+            .filter(api_key::Column::KeyHash.eq(key_hash))
+            .filter(api_key::Column::UserId.eq(user_id.to_string()))
+            .exec(&*self.db)
+            .await
+            .context("Failed to delete API key")?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
+
+fn model_to_api_key(m: api_key::Model) -> ApiKey {
+    ApiKey {
+        key_hash: m.key_hash,
+        user_id: uuid::Uuid::parse_str(&m.user_id).unwrap_or_default(),
+        label: m.label,
+        scopes: m
+            .scopes
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+        created_at: m.created_at.parse().unwrap_or_else(|_| Utc::now()),
+        last_used_at: m.last_used_at.and_then(|t| t.parse().ok()),
+    }
+}
@@ -1,5 +1,7 @@
+use crate::error::InfrastructureError;
+use crate::metrics;
 use async_trait::async_trait;
-use domain::{Category, CategoryRepository, Result};
+use domain::{Category, CategoryRepository, Result, MAX_CATEGORY_DEPTH};
 use sea_orm::*;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -29,6 +31,7 @@ impl CategoryRepositoryImpl {
             name: entity.name,
             slug: entity.slug,
             parent_id: entity.parent_id.map(|id| Uuid::parse_str(&id).unwrap()),
+            post_count: entity.post_count.max(0) as u32,
             created_at: chrono::DateTime::parse_from_rfc3339(&entity.created_at)
                 .unwrap()
                 .with_timezone(&chrono::Utc),
@@ -44,54 +47,58 @@ impl CategoryRepository for CategoryRepositoryImpl {
         slug: String,
         parent_id: Option<Uuid>,
     ) -> Result<Category> {
+        let _timer = metrics::db_timer("category.create_category");
         let category = category::ActiveModel {
             id: Set(Uuid::new_v4().to_string()),
             name: Set(name),
             slug: Set(slug),
             parent_id: Set(parent_id.map(|id| id.to_string())),
+            post_count: Set(0),
             created_at: Set(chrono::Utc::now().to_rfc3339()),
         };
 
-        let result = category
-            .insert(self.db.as_ref())
-            .await
-            .map_err(|e| match e {
-                DbErr::RecordNotFound(_) => {
+        let result = category.insert(self.db.as_ref()).await.map_err(|e| {
+            match InfrastructureError::db("Failed to create category", e) {
+                InfrastructureError::NotFound(_) => {
                     domain::Error::NotFound("Category not found".to_string())
                 }
-                DbErr::Custom(err) if err.contains("UNIQUE") => {
+                InfrastructureError::Constraint(_) => {
                     domain::Error::Validation("Slug already exists".to_string())
                 }
-                _ => domain::Error::Internal(e.to_string()),
-            })?;
+                other => other.into(),
+            }
+        })?;
 
         Ok(Self::entity_to_domain(result))
     }
 
     async fn get_category(&self, id: Uuid) -> Result<Option<Category>> {
+        let _timer = metrics::db_timer("category.get_category");
         let result = category::Entity::find_by_id(id.to_string())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(result.map(Self::entity_to_domain))
     }
 
     async fn get_category_by_slug(&self, slug: &str) -> Result<Option<Category>> {
+        let _timer = metrics::db_timer("category.get_category_by_slug");
         let result = category::Entity::find()
             .filter(category::Column::Slug.eq(slug))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(result.map(Self::entity_to_domain))
     }
 
     async fn list_categories(&self) -> Result<Vec<Category>> {
+        let _timer = metrics::db_timer("category.list_categories");
         let result = category::Entity::find()
             .all(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(result.into_iter().map(Self::entity_to_domain).collect())
     }
@@ -102,10 +109,11 @@ impl CategoryRepository for CategoryRepositoryImpl {
         name: Option<String>,
         parent_id: Option<Uuid>,
     ) -> Result<Category> {
+        let _timer = metrics::db_timer("category.update_category");
         let category = category::Entity::find_by_id(id.to_string())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?
+            .map_err(InfrastructureError::from)?
             .ok_or_else(|| domain::Error::NotFound("Category not found".to_string()))?;
 
         let mut active: category::ActiveModel = category.into();
@@ -127,6 +135,7 @@ impl CategoryRepository for CategoryRepositoryImpl {
     }
 
     async fn delete_category(&self, id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("category.delete_category");
         // First, set all posts' category_id to NULL
         use sea_orm::{ActiveModelTrait, EntityTrait, Set};
 
@@ -134,7 +143,7 @@ impl CategoryRepository for CategoryRepositoryImpl {
             .filter(post::Column::CategoryId.eq(id.to_string()))
             .all(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         for post_model in posts {
             let mut active: post::ActiveModel = post_model.into();
@@ -142,19 +151,20 @@ impl CategoryRepository for CategoryRepositoryImpl {
             active
                 .update(self.db.as_ref())
                 .await
-                .map_err(|e| domain::Error::Internal(e.to_string()))?;
+                .map_err(InfrastructureError::from)?;
         }
 
         // Then delete the category
         category::Entity::delete_by_id(id.to_string())
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(())
     }
 
     async fn get_children(&self, parent_id: Option<Uuid>) -> Result<Vec<Category>> {
+        let _timer = metrics::db_timer("category.get_children");
         let result = if let Some(parent_id) = parent_id {
             category::Entity::find()
                 .filter(category::Column::ParentId.eq(parent_id.to_string()))
@@ -167,10 +177,71 @@ impl CategoryRepository for CategoryRepositoryImpl {
                 .await
         };
 
-        let result = result.map_err(|e| domain::Error::Internal(e.to_string()))?;
+        let result = result.map_err(InfrastructureError::from)?;
 
         Ok(result.into_iter().map(Self::entity_to_domain).collect())
     }
+
+    async fn get_category_with_ancestors(&self, id: Uuid) -> Result<Vec<Category>> {
+        let _timer = metrics::db_timer("category.get_category_with_ancestors");
+        // depth bounds the recursion in case the parent_id chain has a cycle
+        let sql = r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT c.*, 0 AS depth FROM category c WHERE c.id = $1
+                UNION ALL
+                SELECT p.*, a.depth + 1 FROM category p
+                JOIN ancestors a ON p.id = a.parent_id
+                WHERE a.depth < $2
+            )
+            SELECT id, name, slug, parent_id, post_count, created_at
+            FROM ancestors
+            ORDER BY depth
+        "#;
+        let rows = category::Model::find_by_statement(Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            sql,
+            [id.to_string().into(), (MAX_CATEGORY_DEPTH as i32).into()],
+        ))
+        .all(self.db.as_ref())
+        .await
+        .map_err(InfrastructureError::from)?;
+
+        Ok(rows.into_iter().map(Self::entity_to_domain).collect())
+    }
+
+    async fn get_category_depth(&self, id: Uuid) -> Result<u32> {
+        let _timer = metrics::db_timer("category.get_category_depth");
+        let row = DepthRow::find_by_statement(Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            CATEGORY_DEPTH_SQL,
+            [id.to_string().into(), (MAX_CATEGORY_DEPTH as i32).into()],
+        ))
+        .one(self.db.as_ref())
+        .await
+        .map_err(InfrastructureError::from)?;
+
+        Ok(row.map_or(0, |row| row.depth.max(0) as u32))
+    }
+}
+
+/// Levels from the root down to category `$1`; 0 when it does not exist
+///
+/// `depth` bounds the recursion in case the `parent_id` chain has a cycle.
+const CATEGORY_DEPTH_SQL: &str = r#"
+    WITH RECURSIVE ancestors AS (
+        SELECT c.parent_id, 1 AS depth FROM category c WHERE c.id = $1
+        UNION ALL
+        SELECT p.parent_id, a.depth + 1 FROM category p
+        JOIN ancestors a ON p.id = a.parent_id
+        WHERE a.depth <= $2
+    )
+    SELECT CAST(COALESCE(MAX(depth), 0) AS INTEGER) AS depth FROM ancestors
+"#;
+
+/// Row returned by [`CATEGORY_DEPTH_SQL`]
+#[derive(Debug, FromQueryResult)]
+struct DepthRow {
+    depth: i32,
 }
 
 #[cfg(test)]
@@ -198,6 +269,16 @@ mod tests {
         assert!(!category.is_root());
     }
 
+    #[test]
+    fn test_category_depth_counts_ancestors_with_bounded_recursion() {
+        let sql = CATEGORY_DEPTH_SQL;
+
+        assert!(sql.contains("SELECT c.parent_id, 1 AS depth FROM category c WHERE c.id = $1"));
+        assert!(sql.contains("JOIN ancestors a ON p.id = a.parent_id"));
+        assert!(sql.contains("WHERE a.depth <= $2"));
+        assert!(sql.contains("COALESCE(MAX(depth), 0)"));
+    }
+
     #[tokio::test]
     async fn test_category_entity_to_domain() {
         let entity = category::Model {
@@ -205,6 +286,7 @@ mod tests {
             name: "Programming".to_string(),
             slug: "programming".to_string(),
             parent_id: None,
+            post_count: 3,
             created_at: chrono::Utc::now().to_rfc3339(),
         };
 
@@ -214,6 +296,7 @@ mod tests {
         assert_eq!(domain.name, entity.name);
         assert_eq!(domain.slug, entity.slug);
         assert!(domain.parent_id.is_none());
+        assert_eq!(domain.post_count, 3);
     }
 
     #[tokio::test]
@@ -224,6 +307,7 @@ mod tests {
             name: "Programming".to_string(),
             slug: "programming".to_string(),
             parent_id: Some(parent_id.to_string()),
+            post_count: 0,
             created_at: chrono::Utc::now().to_rfc3339(),
         };
 
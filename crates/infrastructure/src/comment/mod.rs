@@ -8,13 +8,18 @@
 //! - Clear error mapping
 //! - No special cases
 
-use crate::entity::comment;
 use crate::entity::prelude::*;
+use crate::entity::{comment, comment_reaction};
+use crate::error::{DbResultExt, InfrastructureError};
+use crate::metrics;
 use async_trait::async_trait;
-use domain::{Comment, Error, Result};
+use domain::{Comment, CommentStatus, Error, Result};
+use sea_orm::sea_query::OnConflict;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select, Set,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Concrete implementation of CommentRepository
@@ -33,6 +38,44 @@ impl Clone for CommentRepositoryImpl {
     }
 }
 
+/// Comments whose content or GitHub username contains `query`, newest first
+fn comment_search_query(query: &str) -> Select<comment::Entity> {
+    let pattern = format!("%{}%", query);
+    CommentEntity::find()
+        .filter(
+            Condition::any()
+                .add(comment::Column::Content.like(&pattern))
+                .add(comment::Column::GithubUsername.like(&pattern)),
+        )
+        .order_by_desc(comment::Column::CreatedAt)
+        .order_by_desc(comment::Column::Id)
+}
+
+/// `(post_id, count)` rows for the commented posts of one author
+fn comments_by_post_author_query(author_id: uuid::Uuid) -> Select<comment::Entity> {
+    CommentEntity::find()
+        .select_only()
+        .column(comment::Column::PostId)
+        .column_as(comment::Column::Id.count(), "count")
+        .inner_join(PostEntity)
+        .filter(crate::entity::post::Column::UserId.eq(author_id.to_string()))
+        .group_by(comment::Column::PostId)
+}
+
+/// `(comment_id, reaction_type, count)` rows for a set of comments
+fn reaction_counts_query(comment_ids: &[uuid::Uuid]) -> Select<comment_reaction::Entity> {
+    CommentReactionEntity::find()
+        .select_only()
+        .column(comment_reaction::Column::CommentId)
+        .column(comment_reaction::Column::ReactionType)
+        .column_as(comment_reaction::Column::UserId.count(), "count")
+        .filter(
+            comment_reaction::Column::CommentId.is_in(comment_ids.iter().map(|id| id.to_string())),
+        )
+        .group_by(comment_reaction::Column::CommentId)
+        .group_by(comment_reaction::Column::ReactionType)
+}
+
 impl CommentRepositoryImpl {
     /// Create a new comment repository
     ///
@@ -47,6 +90,7 @@ impl CommentRepositoryImpl {
 impl domain::CommentRepository for CommentRepositoryImpl {
     /// Create a new comment
     async fn create_comment(&self, comment: Comment) -> Result<Comment> {
+        let _timer = metrics::db_timer("comment.create_comment");
         let active_model = comment::ActiveModel {
             id: Set(comment.id.to_string()),
             post_id: Set(comment.post_id.to_string()),
@@ -54,6 +98,8 @@ impl domain::CommentRepository for CommentRepositoryImpl {
             github_username: Set(comment.github_username.clone()),
             github_avatar_url: Set(comment.github_avatar_url.clone()),
             content: Set(comment.content.clone()),
+            status: Set(comment.status.as_str().to_string()),
+            parent_comment_id: Set(comment.parent_id.map(|id| id.to_string())),
             created_at: Set(comment.created_at.to_rfc3339()),
             updated_at: Set(comment.updated_at.to_rfc3339()),
         };
@@ -61,29 +107,64 @@ impl domain::CommentRepository for CommentRepositoryImpl {
         active_model
             .insert(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create comment: {}", e)))?;
+            .context("Failed to create comment")?;
 
         Ok(comment)
     }
 
     /// Get a comment by ID
     async fn get_comment(&self, id: uuid::Uuid) -> Result<Option<Comment>> {
+        let _timer = metrics::db_timer("comment.get_comment");
         let model = CommentEntity::find_by_id(id.to_string())
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get comment: {}", e)))?;
+            .context("Failed to get comment")?;
 
         Ok(model.map(|m| self.model_to_domain(m)))
     }
 
     /// List comments for a post
-    async fn list_post_comments(&self, post_id: uuid::Uuid, limit: u64) -> Result<Vec<Comment>> {
+    async fn list_post_comments(
+        &self,
+        post_id: uuid::Uuid,
+        status: Option<CommentStatus>,
+        limit: u64,
+    ) -> Result<Vec<Comment>> {
+        let _timer = metrics::db_timer("comment.list_post_comments");
+        let mut query =
+            CommentEntity::find().filter(comment::Column::PostId.eq(post_id.to_string()));
+
+        if let Some(status) = status {
+            query = query.filter(comment::Column::Status.eq(status.as_str()));
+        }
+
+        let models = query
+            .order_by_asc(comment::Column::CreatedAt)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .context("Failed to list comments")?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| self.model_to_domain(m))
+            .collect())
+    }
+
+    /// List comments across all posts with the given status, oldest first
+    async fn list_comments_by_status(
+        &self,
+        status: CommentStatus,
+        limit: u64,
+    ) -> Result<Vec<Comment>> {
+        let _timer = metrics::db_timer("comment.list_comments_by_status");
         let models = CommentEntity::find()
-            .filter(comment::Column::PostId.eq(post_id.to_string()))
+            .filter(comment::Column::Status.eq(status.as_str()))
+            .order_by_asc(comment::Column::CreatedAt)
             .limit(limit)
             .all(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to list comments: {}", e)))?;
+            .context("Failed to list comments")?;
 
         Ok(models
             .into_iter()
@@ -91,8 +172,70 @@ impl domain::CommentRepository for CommentRepositoryImpl {
             .collect())
     }
 
+    /// List a user's comments across all posts, newest first
+    async fn list_comments_by_user(
+        &self,
+        user_id: uuid::Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<Comment>, u64)> {
+        let _timer = metrics::db_timer("comment.list_comments_by_user");
+        let total = CommentEntity::find()
+            .filter(comment::Column::UserId.eq(user_id.to_string()))
+            .count(&*self.db)
+            .await
+            .context("Failed to count comments")?;
+
+        let models = CommentEntity::find()
+            .filter(comment::Column::UserId.eq(user_id.to_string()))
+            .order_by_desc(comment::Column::CreatedAt)
+            .offset(offset)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .context("Failed to list comments")?;
+
+        Ok((
+            models
+                .into_iter()
+                .map(|m| self.model_to_domain(m))
+                .collect(),
+            total,
+        ))
+    }
+
+    /// Search comments, joining each page row with its post's title
+    async fn search_comments(
+        &self,
+        query: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<(Comment, Option<String>)>, u64)> {
+        let _timer = metrics::db_timer("comment.search_comments");
+        let total = comment_search_query(query)
+            .count(&*self.db)
+            .await
+            .context("Failed to count comments")?;
+
+        let rows = comment_search_query(query)
+            .find_also_related(PostEntity)
+            .offset(offset)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .context("Failed to search comments")?;
+
+        Ok((
+            rows.into_iter()
+                .map(|(model, post)| (self.model_to_domain(model), post.map(|p| p.title)))
+                .collect(),
+            total,
+        ))
+    }
+
     /// Update a comment
     async fn update_comment(&self, comment: Comment) -> Result<Comment> {
+        let _timer = metrics::db_timer("comment.update_comment");
         let active_model = comment::ActiveModel {
             id: Set(comment.id.to_string()),
             post_id: Set(comment.post_id.to_string()),
@@ -100,6 +243,8 @@ impl domain::CommentRepository for CommentRepositoryImpl {
             github_username: Set(comment.github_username.clone()),
             github_avatar_url: Set(comment.github_avatar_url.clone()),
             content: Set(comment.content.clone()),
+            status: Set(comment.status.as_str().to_string()),
+            parent_comment_id: Set(comment.parent_id.map(|id| id.to_string())),
             created_at: Set(comment.created_at.to_rfc3339()),
             updated_at: Set(comment.updated_at.to_rfc3339()),
         };
@@ -107,7 +252,7 @@ impl domain::CommentRepository for CommentRepositoryImpl {
         active_model
             .update(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to update comment: {}", e)))?;
+            .context("Failed to update comment")?;
 
         Ok(comment)
     }
@@ -119,11 +264,12 @@ impl domain::CommentRepository for CommentRepositoryImpl {
         user_id: Option<uuid::Uuid>,
         is_github_user: bool,
     ) -> Result<()> {
+        let _timer = metrics::db_timer("comment.delete_comment");
         // Verify ownership before deleting
         let model = CommentEntity::find_by_id(id.to_string())
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find comment: {}", e)))?
+            .context("Failed to find comment")?
             .ok_or_else(|| Error::NotFound("Comment not found".to_string()))?;
 
         // Check ownership
@@ -144,21 +290,136 @@ impl domain::CommentRepository for CommentRepositoryImpl {
         CommentEntity::delete_by_id(id.to_string())
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to delete comment: {}", e)))?;
+            .context("Failed to delete comment")?;
 
         Ok(())
     }
 
     /// Get comment count for a post
     async fn get_post_comment_count(&self, post_id: uuid::Uuid) -> Result<u64> {
+        let _timer = metrics::db_timer("comment.get_post_comment_count");
         let count = CommentEntity::find()
             .filter(comment::Column::PostId.eq(post_id.to_string()))
             .count(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to count comments: {}", e)))?;
+            .context("Failed to count comments")?;
 
         Ok(count)
     }
+
+    async fn count_comments_by_post_author(
+        &self,
+        author_id: uuid::Uuid,
+    ) -> Result<HashMap<uuid::Uuid, u64>> {
+        let _timer = metrics::db_timer("comment.count_comments_by_post_author");
+        let rows: Vec<(String, i64)> = comments_by_post_author_query(author_id)
+            .into_tuple()
+            .all(&*self.db)
+            .await
+            .context("Failed to count comments")?;
+
+        rows.into_iter()
+            .map(|(post_id, count)| {
+                let post_id = uuid::Uuid::parse_str(&post_id).map_err(|e| {
+                    InfrastructureError::Serialize(format!("Invalid post ID: {}", e))
+                })?;
+                Ok((post_id, count.max(0) as u64))
+            })
+            .collect()
+    }
+
+    async fn count_comments_by_user(&self, user_id: uuid::Uuid) -> Result<u64> {
+        let _timer = metrics::db_timer("comment.count_comments_by_user");
+        Ok(CommentEntity::find()
+            .filter(comment::Column::UserId.eq(user_id.to_string()))
+            .count(&*self.db)
+            .await
+            .context("Failed to count comments")?)
+    }
+
+    async fn add_reaction(
+        &self,
+        comment_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        reaction_type: &str,
+    ) -> Result<bool> {
+        let _timer = metrics::db_timer("comment.add_reaction");
+        let reaction = comment_reaction::ActiveModel {
+            comment_id: Set(comment_id.to_string()),
+            user_id: Set(user_id.to_string()),
+            reaction_type: Set(reaction_type.to_string()),
+            created_at: Set(chrono::Utc::now().to_rfc3339()),
+        };
+
+        let inserted = CommentReactionEntity::insert(reaction)
+            .on_conflict(
+                OnConflict::columns([
+                    comment_reaction::Column::CommentId,
+                    comment_reaction::Column::UserId,
+                    comment_reaction::Column::ReactionType,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&*self.db)
+            .await
+            .context("Failed to add reaction")?;
+
+        Ok(inserted > 0)
+    }
+
+    async fn remove_reaction(
+        &self,
+        comment_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        reaction_type: &str,
+    ) -> Result<bool> {
+        let _timer = metrics::db_timer("comment.remove_reaction");
+        let result = CommentReactionEntity::delete_many()
+            .filter(comment_reaction::Column::CommentId.eq(comment_id.to_string()))
+            .filter(comment_reaction::Column::UserId.eq(user_id.to_string()))
+            .filter(comment_reaction::Column::ReactionType.eq(reaction_type))
+            .exec(&*self.db)
+            .await
+            .context("Failed to remove reaction")?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn count_reactions(&self, comment_id: uuid::Uuid) -> Result<HashMap<String, u64>> {
+        Ok(self
+            .count_reactions_for(&[comment_id])
+            .await?
+            .remove(&comment_id)
+            .unwrap_or_default())
+    }
+
+    async fn count_reactions_for(
+        &self,
+        comment_ids: &[uuid::Uuid],
+    ) -> Result<HashMap<uuid::Uuid, HashMap<String, u64>>> {
+        let _timer = metrics::db_timer("comment.count_reactions");
+        if comment_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let rows: Vec<(String, String, i64)> = reaction_counts_query(comment_ids)
+            .into_tuple()
+            .all(&*self.db)
+            .await
+            .context("Failed to count reactions")?;
+
+        let mut counts: HashMap<uuid::Uuid, HashMap<String, u64>> = HashMap::new();
+        for (comment_id, reaction_type, count) in rows {
+            let Ok(comment_id) = uuid::Uuid::parse_str(&comment_id) else {
+                continue;
+            };
+            counts
+                .entry(comment_id)
+                .or_default()
+                .insert(reaction_type, count.max(0) as u64);
+        }
+        Ok(counts)
+    }
 }
 
 impl CommentRepositoryImpl {
@@ -171,6 +432,10 @@ impl CommentRepositoryImpl {
             github_username: model.github_username,
             github_avatar_url: model.github_avatar_url,
             content: model.content,
+            status: CommentStatus::parse(&model.status).unwrap_or_default(),
+            parent_id: model
+                .parent_comment_id
+                .and_then(|id| uuid::Uuid::parse_str(&id).ok()),
             created_at: model
                 .created_at
                 .parse()
@@ -186,6 +451,58 @@ impl CommentRepositoryImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[test]
+    fn test_comment_counts_are_grouped_per_post_of_author() {
+        let author_id = uuid::Uuid::nil();
+        let sql = comments_by_post_author_query(author_id)
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert_eq!(
+            sql,
+            format!(
+                r#"SELECT "comment"."post_id", COUNT("comment"."id") AS "count" FROM "comment" INNER JOIN "post" ON "comment"."post_id" = "post"."id" WHERE "post"."user_id" = '{}' GROUP BY "comment"."post_id""#,
+                author_id
+            )
+        );
+    }
+
+    #[test]
+    fn test_reactions_are_counted_per_comment_and_type_in_one_query() {
+        let first = uuid::Uuid::nil();
+        let second = uuid::Uuid::max();
+        let sql = reaction_counts_query(&[first, second])
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert_eq!(
+            sql,
+            format!(
+                r#"SELECT "comment_reaction"."comment_id", "comment_reaction"."reaction_type", COUNT("comment_reaction"."user_id") AS "count" FROM "comment_reaction" WHERE "comment_reaction"."comment_id" IN ('{}', '{}') GROUP BY "comment_reaction"."comment_id", "comment_reaction"."reaction_type""#,
+                first, second
+            )
+        );
+    }
+
+    #[test]
+    fn test_search_matches_content_or_username_newest_first() {
+        let sql = comment_search_query("spam")
+            .find_also_related(PostEntity)
+            .limit(20)
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(sql.contains(
+            r#"WHERE "comment"."content" LIKE '%spam%' OR "comment"."github_username" LIKE '%spam%'"#
+        ));
+        assert!(sql.contains(r#"LEFT JOIN "post""#));
+        assert!(sql.contains(r#""post"."title""#));
+        assert!(
+            sql.contains(r#"ORDER BY "comment"."created_at" DESC, "comment"."id" DESC LIMIT 20"#)
+        );
+    }
 
     #[tokio::test]
     async fn test_comment_repository_structure() {
@@ -0,0 +1,129 @@
+//! SMTP Email Client Implementation
+//!
+//! This module provides the SMTP client used to send notification emails,
+//! such as alerting post authors about new comments, and account emails for
+//! address verification and password resets.
+//!
+//! Connections use STARTTLS by default, or implicit TLS when the configured
+//! port is 465.
+
+use config::SmtpConfig;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// Port conventionally used for SMTP over implicit TLS
+const SMTPS_PORT: u16 = 465;
+
+pub struct EmailClient {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailClient {
+    /// Build a client from the `[smtp]` configuration section
+    pub fn new(config: &SmtpConfig) -> Result<Self, String> {
+        let builder = if config.port == SMTPS_PORT {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+        }
+        .map_err(|e| format!("Invalid SMTP host {}: {}", config.host, e))?
+        .port(config.port);
+
+        let builder = match (&config.username, &config.password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username.clone(), password.clone()))
+            }
+            _ => builder,
+        };
+
+        Self::with_transport(builder.build(), &config.from_address)
+    }
+
+    /// Build a client that talks plain SMTP without TLS or authentication
+    ///
+    /// Only suitable for trusted local relays and test servers.
+    pub fn unencrypted(host: &str, port: u16, from_address: &str) -> Result<Self, String> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+            .port(port)
+            .build();
+
+        Self::with_transport(transport, from_address)
+    }
+
+    fn with_transport(
+        transport: AsyncSmtpTransport<Tokio1Executor>,
+        from_address: &str,
+    ) -> Result<Self, String> {
+        let from = from_address
+            .parse()
+            .map_err(|e| format!("Invalid from address {}: {}", from_address, e))?;
+
+        Ok(Self { transport, from })
+    }
+
+    /// Send a plain-text email
+    pub async fn send(&self, to: &str, subject: &str, body: String) -> Result<(), String> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|e| format!("Invalid recipient address {}: {}", to, e))?;
+
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to.clone())
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body)
+            .map_err(|e| format!("Failed to build email: {}", e))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| format!("SMTP error: {}", e))?;
+
+        tracing::info!("Email sent to {}", to.email);
+        Ok(())
+    }
+
+    /// Send the link that confirms a newly set email address
+    pub async fn send_verification(&self, to: &str, link: &str) -> Result<(), String> {
+        let body = format!(
+            "Please confirm your email address by opening this link:\n\n{}\n\n\
+             If you did not request this, you can ignore this email.",
+            link
+        );
+        self.send(to, "Confirm your email address", body).await
+    }
+
+    /// Send a password reset link
+    pub async fn send_password_reset(&self, to: &str, link: &str) -> Result<(), String> {
+        let body = format!(
+            "A password reset was requested for your account. \
+             Choose a new password here:\n\n{}\n\n\
+             The link is only valid for a short time. If you did not request this, \
+             you can ignore this email.",
+            link
+        );
+        self.send(to, "Reset your password", body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_invalid_from_address() {
+        let result = EmailClient::unencrypted("localhost", 2525, "not an address");
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_rejects_invalid_recipient() {
+        let client = EmailClient::unencrypted("localhost", 2525, "blog@example.com").unwrap();
+        let result = client.send("nobody", "Subject", "Body".to_string()).await;
+        assert!(result.unwrap_err().contains("Invalid recipient"));
+    }
+}
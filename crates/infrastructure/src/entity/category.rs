@@ -9,6 +9,7 @@ pub struct Model {
     pub name: String,
     pub slug: String,
     pub parent_id: Option<String>,
+    pub post_count: i64,
     pub created_at: String,
 }
 
@@ -10,6 +10,8 @@ pub struct Model {
     pub github_username: Option<String>,
     pub github_avatar_url: Option<String>,
     pub content: String,
+    pub status: String,
+    pub parent_comment_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "comment_reaction")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub comment_id: String,
+    #[sea_orm(primary_key)]
+    pub user_id: String,
+    #[sea_orm(primary_key)]
+    pub reaction_type: String,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::comment::Entity",
+        from = "Column::CommentId",
+        to = "super::comment::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Comment,
+}
+
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,16 @@
+use sea_orm::entity::prelude::*;
+
+/// Visit count per calendar day (UTC)
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "daily_stats")]
+pub struct Model {
+    /// Date formatted as `YYYY-MM-DD`
+    #[sea_orm(primary_key)]
+    pub date: String,
+    pub visits: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
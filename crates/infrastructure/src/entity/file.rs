@@ -11,6 +11,8 @@ pub struct Model {
     pub content_type: String,
     pub size_bytes: i64,
     pub url: String,
+    pub sha256_hash: Option<String>,
+    pub thumbnail_url: Option<String>,
     pub created_at: String,
 }
 
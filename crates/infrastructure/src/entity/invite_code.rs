@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "invite_code")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub code: String,
+    pub created_by: Option<String>,
+    pub used_by: Option<String>,
+    pub expires_at: String,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
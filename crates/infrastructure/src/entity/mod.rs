@@ -2,27 +2,41 @@
 //!
 //! This module contains Sea-ORM entity definitions for the database tables.
 
+pub mod api_key;
 pub mod category;
 pub mod comment;
+pub mod comment_reaction;
+pub mod daily_stats;
 pub mod file;
+pub mod invite_code;
 pub mod post;
+pub mod post_like;
 pub mod post_stats;
 pub mod post_tag;
+pub mod post_view_log;
 pub mod session;
 pub mod stats;
 pub mod tag;
 pub mod user;
+pub mod user_audit_log;
 
 // Prelude module for convenient imports
 pub mod prelude;
 
+pub use api_key::Entity as ApiKeyEntity;
 pub use category::Entity as CategoryEntity;
 pub use comment::Entity as CommentEntity;
+pub use comment_reaction::Entity as CommentReactionEntity;
+pub use daily_stats::Entity as DailyStatsEntity;
 pub use file::Entity as FileEntity;
+pub use invite_code::Entity as InviteCodeEntity;
 pub use post::Entity as PostEntity;
+pub use post_like::Entity as PostLikeEntity;
 pub use post_stats::Entity as PostStatsEntity;
 pub use post_tag::Entity as PostTagEntity;
+pub use post_view_log::Entity as PostViewLogEntity;
 pub use session::Entity as SessionEntity;
 pub use stats::Entity as VisitStatsEntity;
 pub use tag::Entity as TagEntity;
 pub use user::Entity as UserEntity;
+pub use user_audit_log::Entity as UserAuditLogEntity;
@@ -46,6 +46,24 @@ pub struct Model {
 
     /// Last IndexNow submission error message
     pub indexnow_last_error: Option<String>,
+
+    /// ISO 8601 datetime string when the post was soft-deleted
+    pub deleted_at: Option<String>,
+
+    /// ISO 8601 datetime string when the post was pinned to the top of listings
+    pub pinned_at: Option<String>,
+
+    /// Plain-text preview shown in listings
+    pub summary: Option<String>,
+
+    /// Whether the summary is generated from the content
+    pub use_auto_summary: bool,
+
+    /// ISO 8601 datetime string when the post should be published automatically
+    pub scheduled_publish_at: Option<String>,
+
+    /// URL of the hero image shown in listings and social previews
+    pub feature_image_url: Option<String>,
 }
 
 /// Relations for Post entity
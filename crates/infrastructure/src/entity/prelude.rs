@@ -4,8 +4,12 @@
 //! Use `use crate::entity::prelude::*;` to import all entity-related types.
 
 // Re-export all entities
+pub use super::api_key::Entity as ApiKeyEntity;
 pub use super::comment::Entity as CommentEntity;
+pub use super::comment_reaction::Entity as CommentReactionEntity;
+pub use super::daily_stats::Entity as DailyStatsEntity;
 pub use super::file::Entity as FileEntity;
+pub use super::invite_code::Entity as InviteCodeEntity;
 pub use super::post::Entity as PostEntity;
 pub use super::post_stats::Entity as PostStatsEntity;
 pub use super::session::Entity as SessionEntity;
@@ -6,7 +6,12 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: String,
     pub user_id: String,
+    pub refresh_token: Option<String>,
+    pub revoked: bool,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
     pub expires_at: String,
+    pub refresh_at: Option<String>,
     pub created_at: String,
 }
 
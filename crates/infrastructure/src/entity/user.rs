@@ -9,6 +9,16 @@ pub struct Model {
     pub password_hash: String,
     pub permissions: i64,
     pub created_at: String,
+    pub totp_secret: Option<String>,
+    pub totp_enabled: i64,
+    pub totp_last_step: Option<i64>,
+    pub email: Option<String>,
+    pub email_verified: i64,
+    pub email_verification_token: Option<String>,
+    pub password_reset_token: Option<String>,
+    pub password_reset_expires_at: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,159 @@
+//! Infrastructure Errors
+//!
+//! Repository implementations work with [`InfrastructureError`] internally
+//! and convert to [`domain::Error`] at the trait boundary, so the kind of
+//! database failure decides the domain error instead of everything becoming
+//! `Error::Internal`.
+//!
+//! | Database failure | Domain error |
+//! |------------------|--------------|
+//! | `DbErr::RecordNotFound` | `Error::NotFound` |
+//! | Unique constraint violation | `Error::Validation` ("already exists") |
+//! | Anything else | `Error::Internal` |
+
+use sea_orm::{DbErr, SqlErr};
+use thiserror::Error;
+
+/// Error raised by repository implementations
+#[derive(Debug, Error)]
+pub enum InfrastructureError {
+    /// Database failure without a more specific meaning
+    #[error("{context}: {source}")]
+    DbError {
+        /// What the repository was doing, e.g. "Failed to list posts"
+        context: String,
+        #[source]
+        source: DbErr,
+    },
+    /// The row the operation needs does not exist
+    #[error("{0}")]
+    NotFound(String),
+    /// A unique or other constraint rejected the write
+    #[error("{0}")]
+    Constraint(String),
+    /// A stored value could not be converted to or from its domain type
+    #[error("{0}")]
+    Serialize(String),
+}
+
+impl InfrastructureError {
+    /// Classify a database error, keeping what the repository was doing
+    pub fn db(context: impl Into<String>, err: DbErr) -> Self {
+        let context = context.into();
+        match err {
+            DbErr::RecordNotFound(msg) => Self::NotFound(format!("{}: {}", context, msg)),
+            err if is_unique_violation(&err) => {
+                Self::Constraint(format!("{}: already exists", context))
+            }
+            err => Self::DbError {
+                context,
+                source: err,
+            },
+        }
+    }
+}
+
+impl From<DbErr> for InfrastructureError {
+    fn from(err: DbErr) -> Self {
+        Self::db("Database error", err)
+    }
+}
+
+impl From<InfrastructureError> for domain::Error {
+    fn from(err: InfrastructureError) -> Self {
+        match err {
+            InfrastructureError::NotFound(msg) => domain::Error::NotFound(msg),
+            InfrastructureError::Constraint(msg) => domain::Error::Validation(msg),
+            err @ (InfrastructureError::DbError { .. } | InfrastructureError::Serialize(_)) => {
+                domain::Error::Internal(err.to_string())
+            }
+        }
+    }
+}
+
+/// Check for a unique constraint violation on Postgres or SQLite
+///
+/// Drivers report it as a structured error; errors that only carry the
+/// database message are recognized by its wording.
+fn is_unique_violation(err: &DbErr) -> bool {
+    if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) {
+        return true;
+    }
+    let message = err.to_string();
+    message.contains("UNIQUE constraint failed")
+        || message.contains("duplicate key value violates unique constraint")
+}
+
+/// Attach context to database results, classifying the error
+pub trait DbResultExt<T> {
+    fn context(self, context: impl Into<String>) -> Result<T, InfrastructureError>;
+}
+
+impl<T> DbResultExt<T> for Result<T, DbErr> {
+    fn context(self, context: impl Into<String>) -> Result<T, InfrastructureError> {
+        self.map_err(|err| InfrastructureError::db(context, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::RuntimeErr;
+
+    fn domain_error(err: DbErr) -> domain::Error {
+        InfrastructureError::db("Failed to create tag", err).into()
+    }
+
+    #[test]
+    fn test_record_not_found_maps_to_not_found() {
+        let err = domain_error(DbErr::RecordNotFound("tag".to_string()));
+
+        assert!(matches!(err, domain::Error::NotFound(msg) if msg.contains("tag")));
+    }
+
+    #[test]
+    fn test_sqlite_unique_violation_maps_to_validation() {
+        let err = domain_error(DbErr::Exec(RuntimeErr::Internal(
+            "UNIQUE constraint failed: tag.slug".to_string(),
+        )));
+
+        match err {
+            domain::Error::Validation(msg) => assert!(msg.contains("already exists")),
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_postgres_unique_violation_maps_to_validation() {
+        let err = domain_error(DbErr::Query(RuntimeErr::Internal(
+            r#"duplicate key value violates unique constraint "tag_slug_key""#.to_string(),
+        )));
+
+        assert!(matches!(err, domain::Error::Validation(_)));
+    }
+
+    #[test]
+    fn test_other_database_errors_map_to_internal_with_context() {
+        let err = domain_error(DbErr::Conn(RuntimeErr::Internal(
+            "connection refused".to_string(),
+        )));
+
+        match err {
+            domain::Error::Internal(msg) => {
+                assert_eq!(
+                    msg,
+                    "Failed to create tag: Connection Error: connection refused"
+                )
+            }
+            other => panic!("Expected internal error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serialize_error_maps_to_internal() {
+        let err: domain::Error =
+            InfrastructureError::Serialize("Invalid post ID: bad".to_string()).into();
+
+        assert!(matches!(err, domain::Error::Internal(msg) if msg == "Invalid post ID: bad"));
+    }
+}
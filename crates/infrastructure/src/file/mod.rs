@@ -10,10 +10,14 @@
 
 use crate::entity::file;
 use crate::entity::prelude::*;
+use crate::error::{DbResultExt, InfrastructureError};
+use crate::metrics;
 use async_trait::async_trait;
 use domain::{Error, File, Result};
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
 };
 use std::sync::Arc;
 
@@ -41,12 +45,46 @@ impl FileRepositoryImpl {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
         Self { db }
     }
+
+    /// Build the condition for a content type filter (see `File::matches_content_type`)
+    fn content_type_condition(content_type_filter: Option<&str>) -> Condition {
+        match content_type_filter {
+            Some(filter) if filter.ends_with('/') => {
+                Condition::all().add(file::Column::ContentType.starts_with(filter))
+            }
+            Some(filter) => Condition::all().add(file::Column::ContentType.eq(filter)),
+            None => Condition::all(),
+        }
+    }
+
+    /// Convert a database model into a domain file
+    fn model_to_file(m: file::Model) -> Result<File> {
+        Ok(File {
+            id: uuid::Uuid::parse_str(&m.id).map_err(|e| {
+                InfrastructureError::Serialize(format!("Invalid file ID in database: {}", e))
+            })?,
+            user_id: uuid::Uuid::parse_str(&m.user_id).map_err(|e| {
+                InfrastructureError::Serialize(format!("Invalid user ID in database: {}", e))
+            })?,
+            filename: m.filename,
+            original_filename: m.original_filename,
+            content_type: m.content_type,
+            size_bytes: m.size_bytes as u64,
+            url: m.url,
+            sha256_hash: m.sha256_hash,
+            thumbnail_url: m.thumbnail_url,
+            created_at: m.created_at.parse().map_err(|e| {
+                InfrastructureError::Serialize(format!("Invalid created_at in database: {}", e))
+            })?,
+        })
+    }
 }
 
 #[async_trait]
 impl domain::FileRepository for FileRepositoryImpl {
     /// Create a new file record
     async fn create_file(&self, file: File) -> Result<File> {
+        let _timer = metrics::db_timer("file.create_file");
         let active_model = file::ActiveModel {
             id: Set(file.id.to_string()),
             user_id: Set(file.user_id.to_string()),
@@ -55,23 +93,26 @@ impl domain::FileRepository for FileRepositoryImpl {
             content_type: Set(file.content_type.clone()),
             size_bytes: Set(file.size_bytes as i64),
             url: Set(file.url.clone()),
+            sha256_hash: Set(file.sha256_hash.clone()),
+            thumbnail_url: Set(file.thumbnail_url.clone()),
             created_at: Set(file.created_at.to_rfc3339()),
         };
 
         active_model
             .insert(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create file: {}", e)))?;
+            .context("Failed to create file")?;
 
         Ok(file)
     }
 
     /// Get a file by ID
     async fn get_file(&self, id: uuid::Uuid) -> Result<Option<File>> {
+        let _timer = metrics::db_timer("file.get_file");
         let model = FileEntity::find_by_id(id.to_string())
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get file: {}", e)))?;
+            .context("Failed to get file")?;
 
         Ok(model.map(|m| File {
             id: uuid::Uuid::parse_str(&m.id).unwrap_or_else(|_| uuid::Uuid::new_v4()),
@@ -81,50 +122,34 @@ impl domain::FileRepository for FileRepositoryImpl {
             content_type: m.content_type,
             size_bytes: m.size_bytes as u64,
             url: m.url,
+            sha256_hash: m.sha256_hash,
+            thumbnail_url: m.thumbnail_url,
             created_at: m.created_at.parse().unwrap_or_else(|_| chrono::Utc::now()),
         }))
     }
 
     /// List files by user ID
     async fn list_files_by_user(&self, user_id: uuid::Uuid, limit: u64) -> Result<Vec<File>> {
+        let _timer = metrics::db_timer("file.list_files_by_user");
         let models = FileEntity::find()
             .filter(file::Column::UserId.eq(user_id.to_string()))
             .limit(limit)
             .all(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to list files: {}", e)))?;
+            .context("Failed to list files")?;
 
-        models
-            .into_iter()
-            .map(|m| {
-                Ok(File {
-                    id: uuid::Uuid::parse_str(&m.id).map_err(|e| {
-                        Error::Internal(format!("Invalid file ID in database: {}", e))
-                    })?,
-                    user_id: uuid::Uuid::parse_str(&m.user_id).map_err(|e| {
-                        Error::Internal(format!("Invalid user ID in database: {}", e))
-                    })?,
-                    filename: m.filename,
-                    original_filename: m.original_filename,
-                    content_type: m.content_type,
-                    size_bytes: m.size_bytes as u64,
-                    url: m.url,
-                    created_at: m.created_at.parse().map_err(|e| {
-                        Error::Internal(format!("Invalid created_at in database: {}", e))
-                    })?,
-                })
-            })
-            .collect()
+        models.into_iter().map(Self::model_to_file).collect()
     }
 
     /// Delete a file by ID
     async fn delete_file(&self, id: uuid::Uuid, user_id: uuid::Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("file.delete_file");
         // Verify ownership first
         // Check ownership first
         let model = FileEntity::find_by_id(id.to_string())
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find file: {}", e)))?
+            .context("Failed to find file")?
             .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
 
         if model.user_id != user_id.to_string() {
@@ -136,10 +161,142 @@ impl domain::FileRepository for FileRepositoryImpl {
         FileEntity::delete_by_id(id.to_string())
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to delete file: {}", e)))?;
+            .context("Failed to delete file")?;
 
         Ok(())
     }
+
+    /// Reassign a file record to another user
+    async fn update_file_owner(
+        &self,
+        file_id: uuid::Uuid,
+        new_user_id: uuid::Uuid,
+    ) -> Result<File> {
+        let _timer = metrics::db_timer("file.update_file_owner");
+        let model = FileEntity::find_by_id(file_id.to_string())
+            .one(&*self.db)
+            .await
+            .context("Failed to find file")?
+            .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
+
+        let mut active_model: file::ActiveModel = model.into();
+        active_model.user_id = Set(new_user_id.to_string());
+        let updated = active_model
+            .update(&*self.db)
+            .await
+            .context("Failed to update file owner")?;
+
+        Self::model_to_file(updated)
+    }
+
+    /// Find the oldest file record with the given content hash
+    async fn get_file_by_filename(&self, filename: &str) -> Result<Option<File>> {
+        let _timer = metrics::db_timer("file.get_file_by_filename");
+        let model = FileEntity::find()
+            .filter(file::Column::Filename.eq(filename))
+            .order_by_asc(file::Column::CreatedAt)
+            .one(&*self.db)
+            .await
+            .context("Failed to find file by filename")?;
+
+        model.map(Self::model_to_file).transpose()
+    }
+
+    async fn find_by_hash(&self, hash: &str) -> Result<Option<File>> {
+        let _timer = metrics::db_timer("file.find_by_hash");
+        let model = FileEntity::find()
+            .filter(file::Column::Sha256Hash.eq(hash))
+            .order_by_asc(file::Column::CreatedAt)
+            .one(&*self.db)
+            .await
+            .context("Failed to find file by hash")?;
+
+        model.map(Self::model_to_file).transpose()
+    }
+
+    /// Find a user's file record with the given content hash
+    async fn find_by_hash_for_user(&self, hash: &str, user_id: uuid::Uuid) -> Result<Option<File>> {
+        let _timer = metrics::db_timer("file.find_by_hash_for_user");
+        let model = FileEntity::find()
+            .filter(file::Column::Sha256Hash.eq(hash))
+            .filter(file::Column::UserId.eq(user_id.to_string()))
+            .one(&*self.db)
+            .await
+            .context("Failed to find file by hash")?;
+
+        model.map(Self::model_to_file).transpose()
+    }
+
+    /// Count file records sharing the given content hash
+    async fn count_by_hash(&self, hash: &str) -> Result<u64> {
+        let _timer = metrics::db_timer("file.count_by_hash");
+        Ok(FileEntity::find()
+            .filter(file::Column::Sha256Hash.eq(hash))
+            .count(&*self.db)
+            .await
+            .context("Failed to count files by hash")?)
+    }
+
+    /// List files of all users, newest first, with the total matching count
+    async fn list_all_files(
+        &self,
+        limit: u64,
+        offset: u64,
+        content_type_filter: Option<&str>,
+    ) -> Result<(Vec<File>, u64)> {
+        let _timer = metrics::db_timer("file.list_all_files");
+        let condition = Self::content_type_condition(content_type_filter);
+
+        let total = FileEntity::find()
+            .filter(condition.clone())
+            .count(&*self.db)
+            .await
+            .context("Failed to count files")?;
+
+        let models = FileEntity::find()
+            .filter(condition)
+            .order_by_desc(file::Column::CreatedAt)
+            .offset(offset)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .context("Failed to list files")?;
+
+        let files = models
+            .into_iter()
+            .map(Self::model_to_file)
+            .collect::<Result<Vec<_>>>()?;
+        Ok((files, total))
+    }
+
+    /// Sum the sizes of all files matching the content type filter
+    async fn total_file_size(&self, content_type_filter: Option<&str>) -> Result<u64> {
+        let _timer = metrics::db_timer("file.total_file_size");
+        let total: Option<i64> = FileEntity::find()
+            .select_only()
+            .column_as(Expr::cust("COALESCE(SUM(size_bytes), 0)::BIGINT"), "total")
+            .filter(Self::content_type_condition(content_type_filter))
+            .into_tuple()
+            .one(&*self.db)
+            .await
+            .context("Failed to sum file sizes")?;
+
+        Ok(total.unwrap_or(0).max(0) as u64)
+    }
+
+    async fn total_storage_used_by_user(&self, user_id: uuid::Uuid) -> Result<u64> {
+        let _timer = metrics::db_timer("file.total_storage_used_by_user");
+        let total: Option<i64> = FileEntity::find()
+            .select_only()
+            .column_as(Expr::cust("COALESCE(SUM(size_bytes), 0)::BIGINT"), "total")
+            .filter(file::Column::UserId.eq(user_id.to_string()))
+            .into_tuple()
+            .one(&*self.db)
+            .await
+            .context("Failed to sum storage used by user")?;
+
+        Ok(total.unwrap_or(0).max(0) as u64)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,36 @@
+//! Health Repository Implementation
+//!
+//! Answers database pings for the health check endpoint.
+
+use crate::error::DbResultExt;
+use crate::metrics;
+use async_trait::async_trait;
+use domain::{HealthRepository, Result};
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+use std::sync::Arc;
+
+pub struct HealthRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl HealthRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl HealthRepository for HealthRepositoryImpl {
+    async fn ping(&self) -> Result<()> {
+        let _timer = metrics::db_timer("health.ping");
+        self.db
+            .execute(Statement::from_string(
+                self.db.get_database_backend(),
+                "SELECT 1",
+            ))
+            .await
+            .context("Database ping failed")?;
+
+        Ok(())
+    }
+}
@@ -2,12 +2,58 @@
 //!
 //! This module provides the HTTP client for sending IndexNow notifications
 //! to search engines.
+//!
+//! Network errors and HTTP 429/5xx responses are transient: [`IndexNowClient::retry_notify`]
+//! retries them with exponential backoff and jitter as set by [`RetryConfig`].
 
 use domain::IndexNowRequest;
+use rand::Rng;
+use std::time::Duration;
+
+/// Backoff settings for retrying IndexNow notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u8,
+    /// Delay before the first retry, in milliseconds
+    pub initial_delay_ms: u64,
+    /// Upper bound for any single delay, in milliseconds
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 500,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay to wait after the failed attempt number `attempt` (zero-based)
+    ///
+    /// `min(initial * 2^attempt + rand(0..=base/4), max_delay)`
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let base = self
+            .initial_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt));
+        let jitter = rand::thread_rng().gen_range(0..=base / 4);
+        Duration::from_millis(base.saturating_add(jitter).min(self.max_delay_ms))
+    }
+}
+
+/// Outcome of a single failed notification attempt
+struct AttemptError {
+    message: String,
+    retryable: bool,
+}
 
 pub struct IndexNowClient {
     client: reqwest::Client,
     endpoint: String,
+    retry: RetryConfig,
 }
 
 impl IndexNowClient {
@@ -15,18 +61,63 @@ impl IndexNowClient {
         Self {
             client: reqwest::Client::new(),
             endpoint,
+            retry: RetryConfig::default(),
         }
     }
 
+    /// Replace the default backoff settings used by [`Self::retry_notify`]
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Send one notification without retrying
     pub async fn notify(&self, request: IndexNowRequest) -> Result<(), String> {
+        self.attempt(&request).await.map_err(|e| e.message)
+    }
+
+    /// Send a notification, retrying transient failures with backoff
+    ///
+    /// Returns the error of the last attempt once all attempts are used up,
+    /// or immediately for a non-transient response such as 400 or 403.
+    pub async fn retry_notify(&self, request: IndexNowRequest) -> Result<(), String> {
+        let max_attempts = u32::from(self.retry.max_attempts.max(1));
+        let mut attempt = 0;
+        loop {
+            let err = match self.attempt(&request).await {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+
+            attempt += 1;
+            if !err.retryable || attempt >= max_attempts {
+                return Err(err.message);
+            }
+
+            let delay = self.retry.delay(attempt - 1);
+            tracing::warn!(
+                "IndexNow attempt {}/{} failed, retrying in {:?}: {}",
+                attempt,
+                max_attempts,
+                delay,
+                err.message
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn attempt(&self, request: &IndexNowRequest) -> Result<(), AttemptError> {
         let response = self
             .client
             .post(&self.endpoint)
             .header("Content-Type", "application/json; charset=utf-8")
-            .json(&request)
+            .json(request)
             .send()
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| AttemptError {
+                message: format!("Network error: {}", e),
+                retryable: true,
+            })?;
 
         let status = response.status();
 
@@ -41,7 +132,108 @@ impl IndexNowClient {
 
             tracing::error!("IndexNow notification failed: {} - {}", status, error_text);
 
-            Err(format!("IndexNow API error: {} - {}", status, error_text))
+            Err(AttemptError {
+                message: format!("IndexNow API error: {} - {}", status, error_text),
+                retryable: status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || status.is_server_error(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Instant;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sample_request() -> IndexNowRequest {
+        IndexNowRequest {
+            host: "example.com".to_string(),
+            key: "key".to_string(),
+            key_location: None,
+            url_list: vec!["https://example.com/post/1".to_string()],
+        }
+    }
+
+    const RETRY: RetryConfig = RetryConfig {
+        max_attempts: 3,
+        initial_delay_ms: 1_000,
+        max_delay_ms: 60_000,
+    };
+
+    #[test]
+    fn test_delay_grows_exponentially_and_is_capped() {
+        for attempt in 0..4 {
+            let base = 1_000 * 2u64.pow(attempt);
+            let delay = RETRY.delay(attempt).as_millis() as u64;
+            assert!((base..=base + base / 4).contains(&delay));
         }
+
+        let capped = RetryConfig {
+            max_delay_ms: 1_500,
+            ..RETRY
+        };
+        assert_eq!(capped.delay(5), Duration::from_millis(1_500));
+    }
+
+    #[tokio::test]
+    async fn test_retry_notify_retries_server_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = IndexNowClient::new(server.uri()).with_retry(RETRY);
+
+        tokio::time::pause();
+        let started = Instant::now();
+        client.retry_notify(sample_request()).await.unwrap();
+        let waited = started.elapsed();
+
+        // The paused clock may also jump ahead while waiting on the socket,
+        // so only the lower bound set by the backoff is reliable
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        assert!(waited >= Duration::from_millis(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_retry_notify_returns_last_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("busy"))
+            .mount(&server)
+            .await;
+
+        let client = IndexNowClient::new(server.uri()).with_retry(RETRY);
+
+        tokio::time::pause();
+        let err = client.retry_notify(sample_request()).await.unwrap_err();
+
+        assert!(err.contains("503"));
+        assert!(err.contains("busy"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_notify_does_not_retry_client_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let client = IndexNowClient::new(server.uri()).with_retry(RETRY);
+        let err = client.retry_notify(sample_request()).await.unwrap_err();
+
+        assert!(err.contains("403"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
     }
 }
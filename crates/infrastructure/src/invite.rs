@@ -0,0 +1,108 @@
+//! Invite Code Repository Implementation
+//!
+//! This module provides the concrete implementation of InviteRepository
+//! using SeaORM.
+
+use crate::entity::invite_code;
+use crate::entity::prelude::*;
+use crate::error::DbResultExt;
+use crate::metrics;
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::{InviteCode, Result};
+use sea_orm::prelude::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::sync::Arc;
+
+/// Concrete implementation of InviteRepository
+pub struct InviteRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl Clone for InviteRepositoryImpl {
+    fn clone(&self) -> Self {
+        Self {
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+impl InviteRepositoryImpl {
+    /// Create a new invite code repository
+    ///
+    /// # Arguments
+    /// * `db` - Database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl domain::InviteRepository for InviteRepositoryImpl {
+    async fn create_invite(&self, invite: InviteCode) -> Result<InviteCode> {
+        let _timer = metrics::db_timer("invite.create_invite");
+        let active_model = invite_code::ActiveModel {
+            code: Set(invite.code.clone()),
+            created_by: Set(invite.created_by.map(|id| id.to_string())),
+            used_by: Set(invite.used_by.map(|id| id.to_string())),
+            expires_at: Set(invite.expires_at.to_rfc3339()),
+            created_at: Set(invite.created_at.to_rfc3339()),
+        };
+
+        active_model
+            .insert(&*self.db)
+            .await
+            .context("Failed to create invite code")?;
+
+        Ok(invite)
+    }
+
+    async fn get_invite(&self, code: &str) -> Result<Option<InviteCode>> {
+        let _timer = metrics::db_timer("invite.get_invite");
+        let model = InviteCodeEntity::find_by_id(code.to_string())
+            .one(&*self.db)
+            .await
+            .context("Failed to get invite code")?;
+
+        Ok(model.map(model_to_invite))
+    }
+
+    async fn list_invites(&self) -> Result<Vec<InviteCode>> {
+        let _timer = metrics::db_timer("invite.list_invites");
+        let models = InviteCodeEntity::find()
+            .order_by_desc(invite_code::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .context("Failed to list invite codes")?;
+
+        Ok(models.into_iter().map(model_to_invite).collect())
+    }
+
+    async fn mark_invite_used(&self, code: &str, user_id: uuid::Uuid) -> Result<bool> {
+        let _timer = metrics::db_timer("invite.mark_invite_used");
+        let result = InviteCodeEntity::update_many()
+            .col_expr(
+                invite_code::Column::UsedBy,
+                Expr::value(user_id.to_string()),
+            )
+            .filter(invite_code::Column::Code.eq(code))
+            .filter(invite_code::Column::UsedBy.is_null())
+            .exec(&*self.db)
+            .await
+            .context("Failed to use invite code")?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
+
+fn model_to_invite(m: invite_code::Model) -> InviteCode {
+    InviteCode {
+        code: m.code,
+        created_by: m.created_by.and_then(|id| uuid::Uuid::parse_str(&id).ok()),
+        used_by: m.used_by.and_then(|id| uuid::Uuid::parse_str(&id).ok()),
+        expires_at: m.expires_at.parse().unwrap_or_else(|_| Utc::now()),
+        created_at: m.created_at.parse().unwrap_or_else(|_| Utc::now()),
+    }
+}
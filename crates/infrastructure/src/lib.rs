@@ -16,32 +16,47 @@
 pub mod entity;
 
 pub mod config;
+pub mod error;
 
 // Repository implementations
+pub mod api_key;
 pub mod category;
 pub mod comment;
+pub mod email;
 pub mod file;
+pub mod health;
 pub mod indexnow;
+pub mod invite;
+pub mod like;
+pub mod metrics;
 pub mod post;
 pub mod session;
 pub mod stats;
 pub mod tag;
 pub mod user;
+pub mod webhook;
 
 // Database migrations
 pub mod migrations;
 
 // Re-exports for convenience
+pub use api_key::*;
 pub use category::*;
 pub use comment::*;
 pub use config::*;
+pub use email::*;
+pub use error::InfrastructureError;
 pub use file::*;
+pub use health::*;
 pub use indexnow::*;
+pub use invite::*;
+pub use like::*;
 pub use post::*;
 pub use session::*;
 pub use stats::*;
 pub use tag::*;
 pub use user::*;
+pub use webhook::*;
 
 use sea_orm::{Database, DatabaseConnection, DbErr};
 use std::sync::Arc;
@@ -63,4 +78,4 @@ pub async fn establish_connection(database_url: &str) -> Result<Arc<DatabaseConn
 pub use migrations::Migrator;
 
 /// Re-export MigratorTrait for running migrations
-pub use sea_orm_migration::MigratorTrait;
+pub use sea_orm_migration::{MigrationStatus, MigratorTrait};
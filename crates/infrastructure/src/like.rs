@@ -0,0 +1,66 @@
+//! Post Like Repository Implementation
+//!
+//! Likes are keyed by post and a salted hash of the reader's IP address, so
+//! the primary key alone deduplicates repeated likes.
+
+use crate::error::InfrastructureError;
+use crate::metrics;
+use async_trait::async_trait;
+use domain::{LikeRepository, Result};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::entity::post_like;
+
+pub struct LikeRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl Clone for LikeRepositoryImpl {
+    fn clone(&self) -> Self {
+        Self {
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+impl LikeRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl LikeRepository for LikeRepositoryImpl {
+    async fn add_like(&self, post_id: Uuid, ip_hash: &str) -> Result<bool> {
+        let _timer = metrics::db_timer("like.add_like");
+        let like = post_like::ActiveModel {
+            post_id: Set(post_id.to_string()),
+            ip_hash: Set(ip_hash.to_string()),
+            created_at: Set(chrono::Utc::now().to_rfc3339()),
+        };
+
+        let inserted = post_like::Entity::insert(like)
+            .on_conflict(
+                OnConflict::columns([post_like::Column::PostId, post_like::Column::IpHash])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(self.db.as_ref())
+            .await
+            .map_err(InfrastructureError::from)?;
+
+        Ok(inserted > 0)
+    }
+
+    async fn count_likes(&self, post_id: Uuid) -> Result<u64> {
+        let _timer = metrics::db_timer("like.count_likes");
+        Ok(post_like::Entity::find()
+            .filter(post_like::Column::PostId.eq(post_id.to_string()))
+            .count(self.db.as_ref())
+            .await
+            .map_err(InfrastructureError::from)?)
+    }
+}
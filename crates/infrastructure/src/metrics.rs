@@ -0,0 +1,109 @@
+//! Prometheus Metrics
+//!
+//! Process-wide metrics registered in the `prometheus` default registry.
+//! HTTP metrics are recorded by the API's metrics middleware, database
+//! timings by the repository implementations in this crate, and the active
+//! session count is refreshed whenever `/metrics` is scraped.
+//!
+//! Metrics are created on first use, so a process that never touches one
+//! (e.g. the CLI) never registers it.
+
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, Encoder, HistogramTimer,
+    HistogramVec, IntCounterVec, IntGauge, TextEncoder,
+};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Requests served, by method, matched route and status code
+static HTTP_REQUESTS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "http_requests_total",
+        "Total number of HTTP requests",
+        &["method", "path", "status"]
+    )
+    .expect("http_requests_total is registered once")
+});
+
+/// Request latency, by method and matched route
+static HTTP_REQUEST_DURATION_SECONDS: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "http_request_duration_seconds",
+        "HTTP request latency in seconds",
+        &["method", "path"]
+    )
+    .expect("http_request_duration_seconds is registered once")
+});
+
+/// Repository call latency, by operation (e.g. `post.get_post`)
+static DB_QUERY_DURATION_SECONDS: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "db_query_duration_seconds",
+        "Database query latency in seconds",
+        &["operation"]
+    )
+    .expect("db_query_duration_seconds is registered once")
+});
+
+/// Sessions that are neither revoked nor expired
+static ACTIVE_SESSIONS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("active_sessions_gauge", "Number of active sessions")
+        .expect("active_sessions_gauge is registered once")
+});
+
+/// Record a served HTTP request
+///
+/// # Arguments
+/// * `method` - HTTP method, e.g. "GET"
+/// * `path` - Matched route template (e.g. "/api/posts/{id}"), never the raw
+///   URI, so that label values stay bounded
+/// * `status` - Response status code
+/// * `elapsed` - Time taken to produce the response
+pub fn record_http_request(method: &str, path: &str, status: u16, elapsed: Duration) {
+    HTTP_REQUESTS_TOTAL
+        .with_label_values(&[method, path, &status.to_string()])
+        .inc();
+    HTTP_REQUEST_DURATION_SECONDS
+        .with_label_values(&[method, path])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Start timing a database operation; the time is recorded when the timer drops
+pub fn db_timer(operation: &str) -> HistogramTimer {
+    DB_QUERY_DURATION_SECONDS
+        .with_label_values(&[operation])
+        .start_timer()
+}
+
+/// Set the number of active sessions
+pub fn set_active_sessions(count: u64) {
+    ACTIVE_SESSIONS.set(i64::try_from(count).unwrap_or(i64::MAX));
+}
+
+/// Render every registered metric in the Prometheus text exposition format
+pub fn gather() -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&prometheus::gather(), &mut buffer) {
+        tracing::error!("Failed to encode metrics: {}", e);
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_metrics_are_exposed() {
+        record_http_request("GET", "/api/posts/{id}", 200, Duration::from_millis(12));
+        drop(db_timer("post.get_post"));
+        set_active_sessions(3);
+
+        let text = gather();
+        assert!(text
+            .contains(r#"http_requests_total{method="GET",path="/api/posts/{id}",status="200"}"#));
+        assert!(text.contains("http_request_duration_seconds_bucket"));
+        assert!(text.contains(r#"db_query_duration_seconds_count{operation="post.get_post"} 1"#));
+        assert!(text.contains("active_sessions_gauge 3"));
+    }
+}
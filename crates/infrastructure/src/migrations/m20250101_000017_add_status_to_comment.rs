@@ -0,0 +1,70 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddStatusToComment;
+
+impl MigrationName for AddStatusToComment {
+    fn name(&self) -> &str {
+        "m20250101_000017_add_status_to_comment"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddStatusToComment {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Existing comments were already public, so backfill them as approved
+        let add_status = "ALTER TABLE comment ADD COLUMN status TEXT NOT NULL DEFAULT 'approved'";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_status.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let set_default = "ALTER TABLE comment ALTER COLUMN status SET DEFAULT 'pending'";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            set_default.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_check = "ALTER TABLE comment ADD CONSTRAINT chk_comment_status CHECK (status IN ('pending', 'approved', 'rejected'))";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_check.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_index = "CREATE INDEX idx_comment_status ON comment(status)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_index.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_index = "DROP INDEX IF EXISTS idx_comment_status";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_index.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_status = "ALTER TABLE comment DROP COLUMN IF EXISTS status";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_status.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
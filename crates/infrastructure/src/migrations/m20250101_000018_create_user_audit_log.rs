@@ -0,0 +1,54 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreateUserAuditLog;
+
+impl MigrationName for CreateUserAuditLog {
+    fn name(&self) -> &str {
+        "m20250101_000018_create_user_audit_log"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateUserAuditLog {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let create_table = r#"
+            CREATE TABLE user_audit_log (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                action TEXT NOT NULL,
+                detail TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (user_id) REFERENCES "user"(id) ON DELETE CASCADE
+            )
+        "#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let idx_user_id = "CREATE INDEX idx_user_audit_log_user_id ON user_audit_log(user_id)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            idx_user_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = "DROP TABLE user_audit_log";
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                manager.get_database_backend(),
+                sql.to_owned(),
+            ))
+            .await
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,54 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddSha256HashToFile;
+
+impl MigrationName for AddSha256HashToFile {
+    fn name(&self) -> &str {
+        "m20250101_000019_add_sha256_hash_to_file"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddSha256HashToFile {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Existing files have no hash and are never deduplicated against
+        let add_hash = "ALTER TABLE file ADD COLUMN sha256_hash TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_hash.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_index = "CREATE INDEX idx_file_sha256_hash ON file(sha256_hash)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_index.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_index = "DROP INDEX IF EXISTS idx_file_sha256_hash";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_index.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_hash = "ALTER TABLE file DROP COLUMN IF EXISTS sha256_hash";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_hash.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
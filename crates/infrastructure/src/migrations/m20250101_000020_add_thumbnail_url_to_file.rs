@@ -0,0 +1,37 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddThumbnailUrlToFile;
+
+impl MigrationName for AddThumbnailUrlToFile {
+    fn name(&self) -> &str {
+        "m20250101_000020_add_thumbnail_url_to_file"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddThumbnailUrlToFile {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_thumbnail_url = "ALTER TABLE file ADD COLUMN thumbnail_url TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_thumbnail_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_thumbnail_url = "ALTER TABLE file DROP COLUMN IF EXISTS thumbnail_url";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_thumbnail_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
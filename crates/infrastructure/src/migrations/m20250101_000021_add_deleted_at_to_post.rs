@@ -0,0 +1,53 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddDeletedAtToPost;
+
+impl MigrationName for AddDeletedAtToPost {
+    fn name(&self) -> &str {
+        "m20250101_000021_add_deleted_at_to_post"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddDeletedAtToPost {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_deleted_at = "ALTER TABLE post ADD COLUMN deleted_at TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_deleted_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_idx_post_deleted_at = "CREATE INDEX idx_post_deleted_at ON post(deleted_at)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_idx_post_deleted_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_idx_post_deleted_at = "DROP INDEX IF EXISTS idx_post_deleted_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_idx_post_deleted_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_deleted_at = "ALTER TABLE post DROP COLUMN IF EXISTS deleted_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_deleted_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
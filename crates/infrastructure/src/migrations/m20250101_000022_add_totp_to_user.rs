@@ -0,0 +1,70 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddTotpToUser;
+
+impl MigrationName for AddTotpToUser {
+    fn name(&self) -> &str {
+        "m20250101_000022_add_totp_to_user"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddTotpToUser {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_totp_secret = r#"ALTER TABLE "user" ADD COLUMN totp_secret TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_totp_secret.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_totp_enabled =
+            r#"ALTER TABLE "user" ADD COLUMN totp_enabled BIGINT NOT NULL DEFAULT 0"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_totp_enabled.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_totp_last_step = r#"ALTER TABLE "user" ADD COLUMN totp_last_step BIGINT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_totp_last_step.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_totp_last_step = r#"ALTER TABLE "user" DROP COLUMN IF EXISTS totp_last_step"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_totp_last_step.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_totp_enabled = r#"ALTER TABLE "user" DROP COLUMN IF EXISTS totp_enabled"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_totp_enabled.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_totp_secret = r#"ALTER TABLE "user" DROP COLUMN IF EXISTS totp_secret"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_totp_secret.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
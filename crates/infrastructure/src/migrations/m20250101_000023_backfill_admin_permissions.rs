@@ -0,0 +1,41 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct BackfillAdminPermissions;
+
+impl MigrationName for BackfillAdminPermissions {
+    fn name(&self) -> &str {
+        "m20250101_000023_backfill_admin_permissions"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for BackfillAdminPermissions {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Grant category, comment and file management to existing admins
+        let stmt =
+            r#"UPDATE "user" SET permissions = permissions | 224 WHERE permissions & 16 <> 0"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            stmt.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Only revoke from the admins that up() granted to
+        let stmt =
+            r#"UPDATE "user" SET permissions = permissions & ~224 WHERE permissions & 16 <> 0"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            stmt.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
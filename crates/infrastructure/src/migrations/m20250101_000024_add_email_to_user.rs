@@ -0,0 +1,37 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddEmailToUser;
+
+impl MigrationName for AddEmailToUser {
+    fn name(&self) -> &str {
+        "m20250101_000024_add_email_to_user"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddEmailToUser {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_email = r#"ALTER TABLE "user" ADD COLUMN email TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_email.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_email = r#"ALTER TABLE "user" DROP COLUMN email"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_email.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,70 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddRefreshTokenToSession;
+
+impl MigrationName for AddRefreshTokenToSession {
+    fn name(&self) -> &str {
+        "m20250101_000025_add_refresh_token_to_session"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddRefreshTokenToSession {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_refresh_token = "ALTER TABLE session ADD COLUMN refresh_token TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_refresh_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_revoked = "ALTER TABLE session ADD COLUMN revoked BOOLEAN NOT NULL DEFAULT FALSE";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_revoked.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_idx_session_refresh_token =
+            "CREATE UNIQUE INDEX idx_session_refresh_token ON session(refresh_token)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_idx_session_refresh_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_idx_session_refresh_token = "DROP INDEX idx_session_refresh_token";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_idx_session_refresh_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_revoked = "ALTER TABLE session DROP COLUMN revoked";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_revoked.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_refresh_token = "ALTER TABLE session DROP COLUMN refresh_token";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_refresh_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
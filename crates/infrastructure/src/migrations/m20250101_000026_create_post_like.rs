@@ -0,0 +1,45 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreatePostLike;
+
+impl MigrationName for CreatePostLike {
+    fn name(&self) -> &str {
+        "m20250101_000026_create_post_like"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreatePostLike {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let create_table = r#"
+            CREATE TABLE post_like (
+                post_id TEXT NOT NULL,
+                ip_hash TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (post_id, ip_hash),
+                FOREIGN KEY (post_id) REFERENCES post(id) ON DELETE CASCADE
+            )
+        "#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_table = "DROP TABLE post_like";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
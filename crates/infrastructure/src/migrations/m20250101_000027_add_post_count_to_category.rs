@@ -0,0 +1,46 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddPostCountToCategory;
+
+impl MigrationName for AddPostCountToCategory {
+    fn name(&self) -> &str {
+        "m20250101_000027_add_post_count_to_category"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddPostCountToCategory {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_post_count = "ALTER TABLE category ADD COLUMN post_count BIGINT NOT NULL DEFAULT 0";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_post_count.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Count the posts that were categorised before the column existed
+        let backfill_post_count = "UPDATE category SET post_count = (SELECT COUNT(*) FROM post WHERE post.category_id = category.id AND post.deleted_at IS NULL)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            backfill_post_count.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_post_count = "ALTER TABLE category DROP COLUMN post_count";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_post_count.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,90 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreateArchivedPost;
+
+impl MigrationName for CreateArchivedPost {
+    fn name(&self) -> &str {
+        "m20250101_000029_create_archived_post"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateArchivedPost {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Same columns as the live tables; foreign keys are not copied, so archived
+        // rows outlive cascading deletes. Columns later added to `post`, `post_tag`,
+        // `comment` or `post_like` must be added here too, in the same order.
+        let create_archived_post = "CREATE TABLE archived_post (LIKE post INCLUDING ALL)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_archived_post.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_archived_post_tag =
+            "CREATE TABLE archived_post_tag (LIKE post_tag INCLUDING ALL)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_archived_post_tag.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_archived_comment = "CREATE TABLE archived_comment (LIKE comment INCLUDING ALL)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_archived_comment.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_archived_post_like =
+            "CREATE TABLE archived_post_like (LIKE post_like INCLUDING ALL)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_archived_post_like.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_archived_post_like = "DROP TABLE archived_post_like";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_post_like.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_archived_comment = "DROP TABLE archived_comment";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_comment.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_archived_post_tag = "DROP TABLE archived_post_tag";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_post_tag.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_archived_post = "DROP TABLE archived_post";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_post.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
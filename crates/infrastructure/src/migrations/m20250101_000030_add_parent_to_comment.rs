@@ -0,0 +1,73 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddParentToComment;
+
+impl MigrationName for AddParentToComment {
+    fn name(&self) -> &str {
+        "m20250101_000030_add_parent_to_comment"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddParentToComment {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_parent_comment_id = "ALTER TABLE comment ADD COLUMN parent_comment_id TEXT REFERENCES comment(id) ON DELETE CASCADE";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_parent_comment_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Keep the archive copy column-compatible with the live table
+        let add_archived_parent_comment_id =
+            "ALTER TABLE archived_comment ADD COLUMN parent_comment_id TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_archived_parent_comment_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_idx_comment_parent_comment_id =
+            "CREATE INDEX idx_comment_parent_comment_id ON comment(parent_comment_id)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_idx_comment_parent_comment_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_idx_comment_parent_comment_id = "DROP INDEX idx_comment_parent_comment_id";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_idx_comment_parent_comment_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_archived_parent_comment_id =
+            "ALTER TABLE archived_comment DROP COLUMN parent_comment_id";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_parent_comment_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_comment_parent_comment_id = "ALTER TABLE comment DROP COLUMN parent_comment_id";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_comment_parent_comment_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
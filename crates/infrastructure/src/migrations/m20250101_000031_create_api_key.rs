@@ -0,0 +1,55 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreateApiKey;
+
+impl MigrationName for CreateApiKey {
+    fn name(&self) -> &str {
+        "m20250101_000031_create_api_key"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateApiKey {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let create_table = r#"
+            CREATE TABLE api_key (
+                key_hash TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                last_used_at TEXT,
+                scopes TEXT NOT NULL,
+                FOREIGN KEY (user_id) REFERENCES "user"(id) ON DELETE CASCADE
+            )
+        "#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let idx_user_id = "CREATE INDEX idx_api_key_user_id ON api_key(user_id)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            idx_user_id.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_table = "DROP TABLE api_key";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
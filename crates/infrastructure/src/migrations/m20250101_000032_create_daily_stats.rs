@@ -0,0 +1,42 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreateDailyStats;
+
+impl MigrationName for CreateDailyStats {
+    fn name(&self) -> &str {
+        "m20250101_000032_create_daily_stats"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateDailyStats {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let create_table = r#"
+            CREATE TABLE daily_stats (
+                date TEXT PRIMARY KEY,
+                visits INTEGER NOT NULL DEFAULT 0
+            )
+        "#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_table = "DROP TABLE daily_stats";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
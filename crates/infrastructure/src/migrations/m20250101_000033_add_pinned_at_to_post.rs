@@ -0,0 +1,54 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddPinnedAtToPost;
+
+impl MigrationName for AddPinnedAtToPost {
+    fn name(&self) -> &str {
+        "m20250101_000033_add_pinned_at_to_post"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddPinnedAtToPost {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_pinned_at = "ALTER TABLE post ADD COLUMN pinned_at TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_pinned_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Keep the archive copy column-compatible with the live table
+        let add_archived_pinned_at = "ALTER TABLE archived_post ADD COLUMN pinned_at TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_archived_pinned_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_archived_pinned_at = "ALTER TABLE archived_post DROP COLUMN pinned_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_pinned_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_post_pinned_at = "ALTER TABLE post DROP COLUMN pinned_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_post_pinned_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,89 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddSummaryToPost;
+
+impl MigrationName for AddSummaryToPost {
+    fn name(&self) -> &str {
+        "m20250101_000034_add_summary_to_post"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddSummaryToPost {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_summary = "ALTER TABLE post ADD COLUMN summary TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_use_auto_summary =
+            "ALTER TABLE post ADD COLUMN use_auto_summary BOOLEAN NOT NULL DEFAULT TRUE";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_use_auto_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Keep the archive copy column-compatible with the live table
+        let add_archived_summary = "ALTER TABLE archived_post ADD COLUMN summary TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_archived_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_archived_use_auto_summary =
+            "ALTER TABLE archived_post ADD COLUMN use_auto_summary BOOLEAN NOT NULL DEFAULT TRUE";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_archived_use_auto_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_archived_use_auto_summary =
+            "ALTER TABLE archived_post DROP COLUMN use_auto_summary";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_use_auto_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_archived_summary = "ALTER TABLE archived_post DROP COLUMN summary";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_post_use_auto_summary = "ALTER TABLE post DROP COLUMN use_auto_summary";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_post_use_auto_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_post_summary = "ALTER TABLE post DROP COLUMN summary";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_post_summary.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
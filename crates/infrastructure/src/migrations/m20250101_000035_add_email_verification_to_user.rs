@@ -0,0 +1,91 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddEmailVerificationToUser;
+
+impl MigrationName for AddEmailVerificationToUser {
+    fn name(&self) -> &str {
+        "m20250101_000035_add_email_verification_to_user"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddEmailVerificationToUser {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_email_verified =
+            r#"ALTER TABLE "user" ADD COLUMN email_verified BIGINT NOT NULL DEFAULT 0"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_email_verified.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_email_verification_token =
+            r#"ALTER TABLE "user" ADD COLUMN email_verification_token TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_email_verification_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_password_reset_token = r#"ALTER TABLE "user" ADD COLUMN password_reset_token TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_password_reset_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_password_reset_expires_at =
+            r#"ALTER TABLE "user" ADD COLUMN password_reset_expires_at TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_password_reset_expires_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_password_reset_expires_at =
+            r#"ALTER TABLE "user" DROP COLUMN IF EXISTS password_reset_expires_at"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_password_reset_expires_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_password_reset_token =
+            r#"ALTER TABLE "user" DROP COLUMN IF EXISTS password_reset_token"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_password_reset_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_email_verification_token =
+            r#"ALTER TABLE "user" DROP COLUMN IF EXISTS email_verification_token"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_email_verification_token.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_email_verified = r#"ALTER TABLE "user" DROP COLUMN IF EXISTS email_verified"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_email_verified.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,53 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddClientInfoToSession;
+
+impl MigrationName for AddClientInfoToSession {
+    fn name(&self) -> &str {
+        "m20250101_000036_add_client_info_to_session"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddClientInfoToSession {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_user_agent = "ALTER TABLE session ADD COLUMN user_agent TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_user_agent.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_ip_address = "ALTER TABLE session ADD COLUMN ip_address TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_ip_address.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_ip_address = "ALTER TABLE session DROP COLUMN ip_address";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_ip_address.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_user_agent = "ALTER TABLE session DROP COLUMN user_agent";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_user_agent.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
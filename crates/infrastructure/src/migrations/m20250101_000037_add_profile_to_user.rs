@@ -0,0 +1,53 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddProfileToUser;
+
+impl MigrationName for AddProfileToUser {
+    fn name(&self) -> &str {
+        "m20250101_000037_add_profile_to_user"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddProfileToUser {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_bio = r#"ALTER TABLE "user" ADD COLUMN bio TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_bio.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let add_avatar_url = r#"ALTER TABLE "user" ADD COLUMN avatar_url TEXT"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_avatar_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_avatar_url = r#"ALTER TABLE "user" DROP COLUMN IF EXISTS avatar_url"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_avatar_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_bio = r#"ALTER TABLE "user" DROP COLUMN IF EXISTS bio"#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_bio.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
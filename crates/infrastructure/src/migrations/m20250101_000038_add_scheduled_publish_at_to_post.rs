@@ -0,0 +1,73 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddScheduledPublishAtToPost;
+
+impl MigrationName for AddScheduledPublishAtToPost {
+    fn name(&self) -> &str {
+        "m20250101_000038_add_scheduled_publish_at_to_post"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddScheduledPublishAtToPost {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_scheduled_publish_at = "ALTER TABLE post ADD COLUMN scheduled_publish_at TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_scheduled_publish_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Keep the archive copy column-compatible with the live table
+        let add_archived_scheduled_publish_at =
+            "ALTER TABLE archived_post ADD COLUMN scheduled_publish_at TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_archived_scheduled_publish_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let create_idx_post_scheduled_publish_at = "CREATE INDEX idx_post_scheduled_publish_at ON post (scheduled_publish_at) WHERE scheduled_publish_at IS NOT NULL";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_idx_post_scheduled_publish_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_idx_post_scheduled_publish_at =
+            "DROP INDEX IF EXISTS idx_post_scheduled_publish_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_idx_post_scheduled_publish_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_archived_scheduled_publish_at =
+            "ALTER TABLE archived_post DROP COLUMN scheduled_publish_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_scheduled_publish_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_post_scheduled_publish_at = "ALTER TABLE post DROP COLUMN scheduled_publish_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_post_scheduled_publish_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,47 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreateInviteCode;
+
+impl MigrationName for CreateInviteCode {
+    fn name(&self) -> &str {
+        "m20250101_000039_create_invite_code"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateInviteCode {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let create_table = r#"
+            CREATE TABLE invite_code (
+                code TEXT PRIMARY KEY,
+                created_by TEXT,
+                used_by TEXT,
+                expires_at TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (created_by) REFERENCES "user"(id) ON DELETE SET NULL,
+                FOREIGN KEY (used_by) REFERENCES "user"(id) ON DELETE SET NULL
+            )
+        "#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_table = "DROP TABLE invite_code";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddFeatureImageUrlToPost;
+
+impl MigrationName for AddFeatureImageUrlToPost {
+    fn name(&self) -> &str {
+        "m20250101_000040_add_feature_image_url_to_post"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddFeatureImageUrlToPost {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_feature_image_url = "ALTER TABLE post ADD COLUMN feature_image_url TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_feature_image_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Keep the archive copy column-compatible with the live table
+        let add_archived_feature_image_url =
+            "ALTER TABLE archived_post ADD COLUMN feature_image_url TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_archived_feature_image_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_archived_feature_image_url =
+            "ALTER TABLE archived_post DROP COLUMN feature_image_url";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_feature_image_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_post_feature_image_url = "ALTER TABLE post DROP COLUMN feature_image_url";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_post_feature_image_url.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,65 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct CreateCommentReaction;
+
+impl MigrationName for CreateCommentReaction {
+    fn name(&self) -> &str {
+        "m20250101_000041_create_comment_reaction"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateCommentReaction {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let create_table = r#"
+            CREATE TABLE comment_reaction (
+                comment_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                reaction_type TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (comment_id, user_id, reaction_type),
+                FOREIGN KEY (comment_id) REFERENCES comment(id) ON DELETE CASCADE,
+                FOREIGN KEY (user_id) REFERENCES "user"(id) ON DELETE CASCADE
+            )
+        "#;
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        // Reactions move with their comments when a post is archived
+        let create_archived_table =
+            "CREATE TABLE archived_comment_reaction (LIKE comment_reaction INCLUDING ALL)";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            create_archived_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_archived_table = "DROP TABLE archived_comment_reaction";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_archived_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())?;
+
+        let drop_table = "DROP TABLE comment_reaction";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_table.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,37 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+pub struct AddRefreshAtToSession;
+
+impl MigrationName for AddRefreshAtToSession {
+    fn name(&self) -> &str {
+        "m20250101_000042_add_refresh_at_to_session"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddRefreshAtToSession {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let add_refresh_at = "ALTER TABLE session ADD COLUMN refresh_at TEXT";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            add_refresh_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let drop_refresh_at = "ALTER TABLE session DROP COLUMN refresh_at";
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            drop_refresh_at.to_owned(),
+        ))
+        .await
+        .map(|_| ())
+    }
+}
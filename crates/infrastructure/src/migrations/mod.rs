@@ -16,6 +16,32 @@ mod m20250101_000013_create_tag;
 mod m20250101_000014_create_post_tag;
 mod m20250101_000015_add_category_to_post;
 mod m20250101_000016_add_indexnow_to_post;
+mod m20250101_000017_add_status_to_comment;
+mod m20250101_000018_create_user_audit_log;
+mod m20250101_000019_add_sha256_hash_to_file;
+mod m20250101_000020_add_thumbnail_url_to_file;
+mod m20250101_000021_add_deleted_at_to_post;
+mod m20250101_000022_add_totp_to_user;
+mod m20250101_000023_backfill_admin_permissions;
+mod m20250101_000024_add_email_to_user;
+mod m20250101_000025_add_refresh_token_to_session;
+mod m20250101_000026_create_post_like;
+mod m20250101_000027_add_post_count_to_category;
+mod m20250101_000028_create_post_view_log;
+mod m20250101_000029_create_archived_post;
+mod m20250101_000030_add_parent_to_comment;
+mod m20250101_000031_create_api_key;
+mod m20250101_000032_create_daily_stats;
+mod m20250101_000033_add_pinned_at_to_post;
+mod m20250101_000034_add_summary_to_post;
+mod m20250101_000035_add_email_verification_to_user;
+mod m20250101_000036_add_client_info_to_session;
+mod m20250101_000037_add_profile_to_user;
+mod m20250101_000038_add_scheduled_publish_at_to_post;
+mod m20250101_000039_create_invite_code;
+mod m20250101_000040_add_feature_image_url_to_post;
+mod m20250101_000041_create_comment_reaction;
+mod m20250101_000042_add_refresh_at_to_session;
 
 use sea_orm_migration::prelude::*;
 
@@ -32,6 +58,32 @@ pub use m20250101_000013_create_tag::CreateTag;
 pub use m20250101_000014_create_post_tag::CreatePostTag;
 pub use m20250101_000015_add_category_to_post::AddCategoryToPost;
 pub use m20250101_000016_add_indexnow_to_post::AddIndexNowToPost;
+pub use m20250101_000017_add_status_to_comment::AddStatusToComment;
+pub use m20250101_000018_create_user_audit_log::CreateUserAuditLog;
+pub use m20250101_000019_add_sha256_hash_to_file::AddSha256HashToFile;
+pub use m20250101_000020_add_thumbnail_url_to_file::AddThumbnailUrlToFile;
+pub use m20250101_000021_add_deleted_at_to_post::AddDeletedAtToPost;
+pub use m20250101_000022_add_totp_to_user::AddTotpToUser;
+pub use m20250101_000023_backfill_admin_permissions::BackfillAdminPermissions;
+pub use m20250101_000024_add_email_to_user::AddEmailToUser;
+pub use m20250101_000025_add_refresh_token_to_session::AddRefreshTokenToSession;
+pub use m20250101_000026_create_post_like::CreatePostLike;
+pub use m20250101_000027_add_post_count_to_category::AddPostCountToCategory;
+pub use m20250101_000028_create_post_view_log::CreatePostViewLog;
+pub use m20250101_000029_create_archived_post::CreateArchivedPost;
+pub use m20250101_000030_add_parent_to_comment::AddParentToComment;
+pub use m20250101_000031_create_api_key::CreateApiKey;
+pub use m20250101_000032_create_daily_stats::CreateDailyStats;
+pub use m20250101_000033_add_pinned_at_to_post::AddPinnedAtToPost;
+pub use m20250101_000034_add_summary_to_post::AddSummaryToPost;
+pub use m20250101_000035_add_email_verification_to_user::AddEmailVerificationToUser;
+pub use m20250101_000036_add_client_info_to_session::AddClientInfoToSession;
+pub use m20250101_000037_add_profile_to_user::AddProfileToUser;
+pub use m20250101_000038_add_scheduled_publish_at_to_post::AddScheduledPublishAtToPost;
+pub use m20250101_000039_create_invite_code::CreateInviteCode;
+pub use m20250101_000040_add_feature_image_url_to_post::AddFeatureImageUrlToPost;
+pub use m20250101_000041_create_comment_reaction::CreateCommentReaction;
+pub use m20250101_000042_add_refresh_at_to_session::AddRefreshAtToSession;
 
 pub struct Migrator;
 
@@ -52,6 +104,32 @@ impl MigratorTrait for Migrator {
             Box::new(CreatePostTag),
             Box::new(AddCategoryToPost),
             Box::new(AddIndexNowToPost),
+            Box::new(AddStatusToComment),
+            Box::new(CreateUserAuditLog),
+            Box::new(AddSha256HashToFile),
+            Box::new(AddThumbnailUrlToFile),
+            Box::new(AddDeletedAtToPost),
+            Box::new(AddTotpToUser),
+            Box::new(BackfillAdminPermissions),
+            Box::new(AddEmailToUser),
+            Box::new(AddRefreshTokenToSession),
+            Box::new(CreatePostLike),
+            Box::new(AddPostCountToCategory),
+            Box::new(CreatePostViewLog),
+            Box::new(CreateArchivedPost),
+            Box::new(AddParentToComment),
+            Box::new(CreateApiKey),
+            Box::new(CreateDailyStats),
+            Box::new(AddPinnedAtToPost),
+            Box::new(AddSummaryToPost),
+            Box::new(AddEmailVerificationToUser),
+            Box::new(AddClientInfoToSession),
+            Box::new(AddProfileToUser),
+            Box::new(AddScheduledPublishAtToPost),
+            Box::new(CreateInviteCode),
+            Box::new(AddFeatureImageUrlToPost),
+            Box::new(CreateCommentReaction),
+            Box::new(AddRefreshAtToSession),
         ]
     }
 }
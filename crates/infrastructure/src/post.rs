@@ -1,8 +1,17 @@
+use crate::error::{DbResultExt, InfrastructureError};
+use crate::metrics;
 use async_trait::async_trait;
-use domain::{Error, Post, PostRepository, Result, SearchPostsResponse};
+use domain::{
+    ArchiveMonth, Error, ErrorCode, Post, PostRepository, PostSearchRequest, PostWithAuthor,
+    PostWithTags, Result, SearchPostsResponse, SortDir, SortField, WordCountStats,
+    MAX_CATEGORY_DEPTH,
+};
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::{Func, NullOrdering, OnConflict, Query};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, EntityTrait, FromQueryResult, Insert, Iterable, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select, Set, Statement, TransactionTrait,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -39,6 +48,12 @@ fn post_to_entity(post: &Post) -> crate::entity::post::Model {
         indexnow_submitted_at: post.indexnow_submitted_at.map(|d| d.to_rfc3339()),
         indexnow_last_status: post.indexnow_last_status.clone(),
         indexnow_last_error: post.indexnow_last_error.clone(),
+        deleted_at: post.deleted_at.map(|d| d.to_rfc3339()),
+        pinned_at: post.pinned_at.map(|d| d.to_rfc3339()),
+        summary: post.summary.clone(),
+        use_auto_summary: post.use_auto_summary,
+        scheduled_publish_at: post.scheduled_publish_at.map(|d| d.to_rfc3339()),
+        feature_image_url: post.feature_image_url.clone(),
     }
 }
 
@@ -56,7 +71,173 @@ fn entity_to_active_model(entity: crate::entity::post::Model) -> crate::entity::
         indexnow_submitted_at: Set(entity.indexnow_submitted_at),
         indexnow_last_status: Set(entity.indexnow_last_status),
         indexnow_last_error: Set(entity.indexnow_last_error),
+        deleted_at: Set(entity.deleted_at),
+        pinned_at: Set(entity.pinned_at),
+        summary: Set(entity.summary),
+        use_auto_summary: Set(entity.use_auto_summary),
+        scheduled_publish_at: Set(entity.scheduled_publish_at),
+        feature_image_url: Set(entity.feature_image_url),
+    }
+}
+
+/// Insert `post`, overwriting every column of an existing row with its ID
+fn post_upsert_query(post: &Post) -> Insert<crate::entity::post::ActiveModel> {
+    use crate::entity::post;
+
+    let on_conflict = OnConflict::column(post::Column::Id)
+        .update_columns(post::Column::iter().filter(|column| !matches!(column, post::Column::Id)))
+        .to_owned();
+
+    post::Entity::insert(entity_to_active_model(post_to_entity(post))).on_conflict(on_conflict)
+}
+
+/// Published posts with pinned ones first (most recently pinned on top),
+/// then newest first
+fn published_posts_pinned_first() -> Select<crate::entity::post::Entity> {
+    crate::entity::post::Entity::find()
+        .filter(crate::entity::post::Column::PublishedAt.is_not_null())
+        .filter(crate::entity::post::Column::DeletedAt.is_null())
+        .order_by_with_nulls(
+            crate::entity::post::Column::PinnedAt,
+            Order::Desc,
+            NullOrdering::Last,
+        )
+        .order_by_desc(crate::entity::post::Column::PublishedAt)
+}
+
+/// Published posts in any of the given categories, newest first
+fn published_in_categories(category_ids: &[String]) -> Select<crate::entity::post::Entity> {
+    crate::entity::post::Entity::find()
+        .filter(crate::entity::post::Column::CategoryId.is_in(category_ids.iter().cloned()))
+        .filter(crate::entity::post::Column::PublishedAt.is_not_null())
+        .filter(crate::entity::post::Column::DeletedAt.is_null())
+        .order_by_desc(crate::entity::post::Column::PublishedAt)
+}
+
+/// Published posts with `published_at` in `[start, end)`, newest first
+///
+/// Timestamps are stored as RFC 3339 text in UTC, so they compare in order.
+fn published_between_query(
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> Select<crate::entity::post::Entity> {
+    crate::entity::post::Entity::find()
+        .filter(crate::entity::post::Column::PublishedAt.gte(start.to_rfc3339()))
+        .filter(crate::entity::post::Column::PublishedAt.lt(end.to_rfc3339()))
+        .filter(crate::entity::post::Column::DeletedAt.is_null())
+        .order_by_desc(crate::entity::post::Column::PublishedAt)
+}
+
+/// The live published post next to `current_id` in publication order
+///
+/// Looks forward with `next`, backward otherwise. Ties on `published_at`
+/// are broken by ID so every post has a single neighbour on each side.
+fn adjacent_published_query(
+    published_at: chrono::DateTime<chrono::Utc>,
+    current_id: Uuid,
+    next: bool,
+) -> Select<crate::entity::post::Entity> {
+    use crate::entity::post;
+
+    let published_at = published_at.to_rfc3339();
+    let current_id = current_id.to_string();
+    let (beyond, tie_break, order) = if next {
+        (
+            post::Column::PublishedAt.gt(published_at.clone()),
+            post::Column::Id.gt(current_id),
+            Order::Asc,
+        )
+    } else {
+        (
+            post::Column::PublishedAt.lt(published_at.clone()),
+            post::Column::Id.lt(current_id),
+            Order::Desc,
+        )
+    };
+
+    post::Entity::find()
+        .filter(post::Column::DeletedAt.is_null())
+        .filter(
+            Condition::any().add(beyond).add(
+                Condition::all()
+                    .add(post::Column::PublishedAt.eq(published_at))
+                    .add(tie_break),
+            ),
+        )
+        .order_by(post::Column::PublishedAt, order.clone())
+        .order_by(post::Column::Id, order)
+}
+
+/// Published posts matching every filter in `request`, in the requested order
+///
+/// Paging is left to the caller so the same query can be counted.
+fn post_search_query(request: &PostSearchRequest) -> Select<crate::entity::post::Entity> {
+    use crate::entity::{post, post_tag};
+
+    let mut condition = Condition::all()
+        .add(post::Column::PublishedAt.is_not_null())
+        .add(post::Column::DeletedAt.is_null());
+
+    if let Some(text) = request.title_contains.as_deref().map(str::trim) {
+        if !text.is_empty() {
+            condition = condition.add(post::Column::Title.like(format!("%{text}%")));
+        }
+    }
+    if let Some(text) = request.content_contains.as_deref().map(str::trim) {
+        if !text.is_empty() {
+            condition = condition.add(post::Column::Content.like(format!("%{text}%")));
+        }
+    }
+    if let Some(author_id) = request.author_id {
+        condition = condition.add(post::Column::UserId.eq(author_id.to_string()));
+    }
+    if let Some(category_id) = request.category_id {
+        condition = condition.add(post::Column::CategoryId.eq(category_id.to_string()));
     }
+    if !request.tag_ids.is_empty() {
+        // Posts carrying every requested tag
+        let mut tag_ids: Vec<String> = request.tag_ids.iter().map(Uuid::to_string).collect();
+        tag_ids.sort();
+        tag_ids.dedup();
+        let tag_count = tag_ids.len() as i64;
+        condition = condition.add(
+            post::Column::Id.in_subquery(
+                Query::select()
+                    .column(post_tag::Column::PostId)
+                    .from(post_tag::Entity)
+                    .and_where(post_tag::Column::TagId.is_in(tag_ids))
+                    .group_by_col(post_tag::Column::PostId)
+                    .and_having(
+                        Expr::expr(Func::count_distinct(Expr::col(post_tag::Column::TagId)))
+                            .eq(tag_count),
+                    )
+                    .to_owned(),
+            ),
+        );
+    }
+    if let Some(after) = request.published_after {
+        condition = condition.add(post::Column::PublishedAt.gte(after.to_rfc3339()));
+    }
+    if let Some(before) = request.published_before {
+        condition = condition.add(post::Column::PublishedAt.lt(before.to_rfc3339()));
+    }
+
+    let column = match request.sort_by {
+        SortField::PublishedAt => post::Column::PublishedAt,
+        SortField::CreatedAt => post::Column::CreatedAt,
+        SortField::Views => post::Column::Views,
+        SortField::Title => post::Column::Title,
+    };
+    let order = match request.sort_dir {
+        SortDir::Asc => Order::Asc,
+        SortDir::Desc => Order::Desc,
+    };
+
+    post::Entity::find()
+        .filter(condition)
+        .order_by(column, order)
+        // Keep pages stable when the sort column has ties
+        .order_by_asc(post::Column::Id)
 }
 
 fn parse_datetime_option(
@@ -66,29 +247,308 @@ fn parse_datetime_option(
         None => Ok(None),
         Some(s) => chrono::DateTime::parse_from_rfc3339(s)
             .map(|dt| Some(dt.with_timezone(&chrono::Utc)))
-            .map_err(|e| Error::Internal(format!("Invalid datetime: {}", e))),
+            .map_err(|e| InfrastructureError::Serialize(format!("Invalid datetime: {}", e)).into()),
+    }
+}
+
+/// Add `delta` to a category's cached post count, clamping at zero
+async fn adjust_category_post_count(
+    db: &impl ConnectionTrait,
+    category_id: &str,
+    delta: i64,
+) -> Result<()> {
+    use crate::entity::category;
+
+    let count = Expr::col(category::Column::PostCount).add(delta);
+    category::Entity::update_many()
+        .col_expr(
+            category::Column::PostCount,
+            Func::greatest([count, Expr::value(0)]).into(),
+        )
+        .filter(category::Column::Id.eq(category_id))
+        .exec(db)
+        .await
+        .context("Failed to update category post count")?;
+
+    Ok(())
+}
+
+/// Word count totals of live posts, optionally only published ones
+///
+/// A post's words are approximated as its spaces plus one, which is cheap
+/// enough to run over every post without loading the content.
+fn word_count_stats_sql(published_only: bool) -> String {
+    let published = if published_only {
+        " AND published_at IS NOT NULL"
+    } else {
+        ""
+    };
+    format!(
+        "WITH counts AS ( \
+         SELECT id, length(content) - length(replace(content, ' ', '')) + 1 AS word_count \
+         FROM post WHERE deleted_at IS NULL{published}) \
+         SELECT COALESCE(SUM(word_count), 0)::BIGINT AS total_words, \
+         COUNT(*) AS post_count, \
+         (SELECT id FROM counts ORDER BY word_count DESC, id LIMIT 1) AS longest_post_id, \
+         (SELECT id FROM counts ORDER BY word_count ASC, id LIMIT 1) AS shortest_post_id \
+         FROM counts"
+    )
+}
+
+/// Row returned by [`word_count_stats_sql`]
+#[derive(Debug, FromQueryResult)]
+struct WordCountRow {
+    total_words: i64,
+    post_count: i64,
+    longest_post_id: Option<String>,
+    shortest_post_id: Option<String>,
+}
+
+impl WordCountRow {
+    fn into_stats(self) -> Result<WordCountStats> {
+        let parse_id = |id: Option<String>| {
+            id.map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| InfrastructureError::Serialize(format!("Invalid post ID: {}", e)))
+        };
+        let total_words = self.total_words.max(0) as u64;
+        let average_words_per_post = if self.post_count > 0 {
+            total_words as f64 / self.post_count as f64
+        } else {
+            0.0
+        };
+
+        Ok(WordCountStats {
+            total_words,
+            average_words_per_post,
+            longest_post_id: parse_id(self.longest_post_id)?,
+            shortest_post_id: parse_id(self.shortest_post_id)?,
+        })
+    }
+}
+
+/// Count published posts per month from the year and month of the RFC 3339 text
+const ARCHIVE_MONTHS_SQL: &str = "SELECT CAST(substr(published_at, 1, 4) AS INTEGER) AS year, \
+     CAST(substr(published_at, 6, 2) AS INTEGER) AS month, \
+     COUNT(*) AS count \
+     FROM post WHERE published_at IS NOT NULL AND deleted_at IS NULL \
+     GROUP BY 1, 2 ORDER BY 1 DESC, 2 DESC";
+
+/// Row returned by [`ARCHIVE_MONTHS_SQL`]
+#[derive(Debug, FromQueryResult)]
+struct ArchiveMonthRow {
+    year: i32,
+    month: i32,
+    count: i64,
+}
+
+impl From<ArchiveMonthRow> for ArchiveMonth {
+    fn from(row: ArchiveMonthRow) -> Self {
+        Self {
+            year: row.year,
+            month: row.month.max(0) as u32,
+            count: u32::try_from(row.count).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// Published posts with their author, in the order of [`published_posts_pinned_first`]
+///
+/// A LEFT JOIN keeps posts whose author row is gone.
+const PUBLISHED_POSTS_WITH_AUTHORS_SQL: &str = r#"SELECT post.*, "user".username AS author_username, "user".avatar_url AS author_avatar_url
+     FROM post LEFT JOIN "user" ON "user".id = post.user_id
+     WHERE post.published_at IS NOT NULL AND post.deleted_at IS NULL
+     ORDER BY post.pinned_at DESC NULLS LAST, post.published_at DESC
+     LIMIT $1 OFFSET $2"#;
+
+/// Page of published posts with their author and tags, in the order of
+/// [`published_posts_pinned_first`]
+///
+/// The page is cut in a subquery before the joins, so `LIMIT` counts posts
+/// rather than rows. Each post then yields one row per tag, or one row with
+/// empty tag columns; ordering by `post.id` keeps a post's rows together.
+const PUBLISHED_POSTS_WITH_TAGS_SQL: &str = r#"SELECT post.*, "user".username AS author_username, "user".avatar_url AS author_avatar_url,
+            tag.id AS tag_id, tag.name AS tag_name, tag.slug AS tag_slug, tag.created_at AS tag_created_at
+     FROM (SELECT * FROM post
+           WHERE published_at IS NOT NULL AND deleted_at IS NULL
+           ORDER BY pinned_at DESC NULLS LAST, published_at DESC
+           LIMIT $1 OFFSET $2) AS post
+     LEFT JOIN "user" ON "user".id = post.user_id
+     LEFT JOIN post_tag ON post_tag.post_id = post.id
+     LEFT JOIN tag ON tag.id = post_tag.tag_id
+     ORDER BY post.pinned_at DESC NULLS LAST, post.published_at DESC, post.id, tag.name"#;
+
+/// Published posts of a category and all of its descendants, newest first
+///
+/// `depth` bounds the recursion in case the `parent_id` chain has a cycle.
+const POSTS_IN_CATEGORY_TREE_SQL: &str = r#"WITH RECURSIVE descendants AS (
+         SELECT c.id, 0 AS depth FROM category c WHERE c.id = $1
+         UNION ALL
+         SELECT c.id, d.depth + 1 FROM category c
+         JOIN descendants d ON c.parent_id = d.id
+         WHERE d.depth < $2
+     )
+     SELECT * FROM post
+     WHERE category_id IN (SELECT id FROM descendants)
+     AND published_at IS NOT NULL AND deleted_at IS NULL
+     ORDER BY created_at DESC
+     LIMIT $3"#;
+
+/// IDs of category `$1` and all of its descendants
+///
+/// `depth` bounds the recursion in case the `parent_id` chain has a cycle.
+const CATEGORY_TREE_IDS_SQL: &str = r#"WITH RECURSIVE descendants AS (
+         SELECT c.id, 0 AS depth FROM category c WHERE c.id = $1
+         UNION ALL
+         SELECT c.id, d.depth + 1 FROM category c
+         JOIN descendants d ON c.parent_id = d.id
+         WHERE d.depth < $2
+     )
+     SELECT DISTINCT id FROM descendants"#;
+
+/// Row returned by [`CATEGORY_TREE_IDS_SQL`]
+#[derive(Debug, FromQueryResult)]
+struct CategoryIdRow {
+    id: String,
+}
+
+/// Author columns returned by [`PUBLISHED_POSTS_WITH_AUTHORS_SQL`]
+#[derive(Debug, FromQueryResult)]
+struct AuthorColumns {
+    author_username: Option<String>,
+    author_avatar_url: Option<String>,
+}
+
+impl AuthorColumns {
+    fn with_post(self, post: Post) -> PostWithAuthor {
+        PostWithAuthor {
+            post,
+            author_username: self
+                .author_username
+                .unwrap_or_else(|| domain::post::DELETED_AUTHOR_USERNAME.to_string()),
+            author_avatar_url: self.author_avatar_url,
+        }
+    }
+}
+
+/// Tag columns returned by [`PUBLISHED_POSTS_WITH_TAGS_SQL`], empty for
+/// posts without tags
+#[derive(Debug, FromQueryResult)]
+struct TagColumns {
+    tag_id: Option<String>,
+    tag_name: Option<String>,
+    tag_slug: Option<String>,
+    tag_created_at: Option<String>,
+}
+
+impl TagColumns {
+    fn into_tag(self) -> Result<Option<domain::Tag>> {
+        let (Some(id), Some(name), Some(slug), Some(created_at)) = (
+            self.tag_id,
+            self.tag_name,
+            self.tag_slug,
+            self.tag_created_at,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(Some(domain::Tag {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| InfrastructureError::Serialize(format!("Invalid tag id: {}", e)))?,
+            name,
+            slug,
+            created_at: chrono::DateTime::parse_from_rfc3339(&created_at)
+                .map_err(|e| {
+                    InfrastructureError::Serialize(format!("Invalid tag created_at: {}", e))
+                })?
+                .with_timezone(&chrono::Utc),
+        }))
+    }
+}
+
+/// Fold joined post/tag rows into one entry per post, keeping row order
+///
+/// Rows of the same post must be adjacent.
+fn group_post_tags(rows: Vec<(PostWithAuthor, Option<domain::Tag>)>) -> Vec<PostWithTags> {
+    let mut posts: Vec<PostWithTags> = Vec::new();
+    for (post, tag) in rows {
+        match posts.last_mut() {
+            Some(last) if last.post.post.id == post.post.id => last.tags.extend(tag),
+            _ => posts.push(PostWithTags {
+                post,
+                tags: tag.into_iter().collect(),
+            }),
+        }
     }
+    posts
+}
+
+/// Copy statements run when archiving a post, after the post row itself
+///
+/// Stats and view logs are not kept; they are removed with the live post.
+const ARCHIVE_CHILD_ROWS: [&str; 4] = [
+    "INSERT INTO archived_post_tag SELECT * FROM post_tag WHERE post_id = $1",
+    "INSERT INTO archived_comment SELECT * FROM comment WHERE post_id = $1",
+    "INSERT INTO archived_comment_reaction SELECT * FROM comment_reaction WHERE comment_id IN (SELECT id FROM comment WHERE post_id = $1)",
+    "INSERT INTO archived_post_like SELECT * FROM post_like WHERE post_id = $1",
+];
+
+/// Copy statements run when restoring an archived post, after the post row
+///
+/// Rows pointing at tags or users deleted in the meantime are dropped, along
+/// with replies to dropped comments and reactions on them.
+const UNARCHIVE_CHILD_ROWS: [&str; 4] = [
+    "INSERT INTO post_tag SELECT * FROM archived_post_tag WHERE post_id = $1 AND tag_id IN (SELECT id FROM tag)",
+    r#"INSERT INTO comment SELECT * FROM archived_comment WHERE post_id = $1
+       AND (user_id IS NULL OR user_id IN (SELECT id FROM "user"))
+       AND (parent_comment_id IS NULL OR parent_comment_id IN (
+           SELECT id FROM archived_comment WHERE post_id = $1
+           AND (user_id IS NULL OR user_id IN (SELECT id FROM "user"))))"#,
+    r#"INSERT INTO comment_reaction SELECT * FROM archived_comment_reaction
+       WHERE comment_id IN (SELECT id FROM comment WHERE post_id = $1)
+       AND user_id IN (SELECT id FROM "user")"#,
+    "INSERT INTO post_like SELECT * FROM archived_post_like WHERE post_id = $1",
+];
+
+/// Cleanup statements run once an archived post has been restored
+const DELETE_ARCHIVED_ROWS: [&str; 5] = [
+    "DELETE FROM archived_post_tag WHERE post_id = $1",
+    "DELETE FROM archived_comment_reaction WHERE comment_id IN (SELECT id FROM archived_comment WHERE post_id = $1)",
+    "DELETE FROM archived_comment WHERE post_id = $1",
+    "DELETE FROM archived_post_like WHERE post_id = $1",
+    "DELETE FROM archived_post WHERE id = $1",
+];
+
+/// Execute a statement bound to a post ID, returning the affected row count
+async fn execute_for_post(txn: &DatabaseTransaction, sql: &str, id: Uuid) -> Result<u64> {
+    let statement =
+        Statement::from_sql_and_values(txn.get_database_backend(), sql, [id.to_string().into()]);
+    let result = txn
+        .execute(statement)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to move post {}: {}", id, e)))?;
+    Ok(result.rows_affected())
 }
 
 fn model_to_post(model: crate::entity::post::Model) -> Result<Post> {
     let id = uuid::Uuid::parse_str(&model.id)
-        .map_err(|e| Error::Internal(format!("Invalid post id: {}", e)))?;
+        .map_err(|e| InfrastructureError::Serialize(format!("Invalid post id: {}", e)))?;
 
     let user_id = uuid::Uuid::parse_str(&model.user_id)
-        .map_err(|e| Error::Internal(format!("Invalid user_id: {}", e)))?;
+        .map_err(|e| InfrastructureError::Serialize(format!("Invalid user_id: {}", e)))?;
 
     let category_id = model
         .category_id
         .map(|id_str| {
             uuid::Uuid::parse_str(&id_str)
-                .map_err(|e| Error::Internal(format!("Invalid category_id: {}", e)))
+                .map_err(|e| InfrastructureError::Serialize(format!("Invalid category_id: {}", e)))
         })
         .transpose()?;
 
     let published_at = parse_datetime_option(&model.published_at)?;
 
     let created_at = chrono::DateTime::parse_from_rfc3339(&model.created_at)
-        .map_err(|e| Error::Internal(format!("Invalid created_at: {}", e)))?
+        .map_err(|e| InfrastructureError::Serialize(format!("Invalid created_at: {}", e)))?
         .with_timezone(&chrono::Utc);
 
     Ok(Post {
@@ -104,12 +564,21 @@ fn model_to_post(model: crate::entity::post::Model) -> Result<Post> {
         indexnow_submitted_at: parse_datetime_option(&model.indexnow_submitted_at)?,
         indexnow_last_status: model.indexnow_last_status,
         indexnow_last_error: model.indexnow_last_error,
+        deleted_at: parse_datetime_option(&model.deleted_at)?,
+        pinned_at: parse_datetime_option(&model.pinned_at)?,
+        summary: model.summary,
+        use_auto_summary: model.use_auto_summary,
+        scheduled_publish_at: parse_datetime_option(&model.scheduled_publish_at)?,
+        feature_image_url: model.feature_image_url,
+        word_count: 0,
+        reading_time_minutes: 0,
     })
 }
 
 #[async_trait]
 impl PostRepository for PostRepositoryImpl {
     async fn create_post(&self, user_id: Uuid, title: String, content: String) -> Result<Post> {
+        let _timer = metrics::db_timer("post.create_post");
         let post = Post::new(user_id, title, content);
         let entity = post_to_entity(&post);
         let active_model = entity_to_active_model(entity);
@@ -117,125 +586,747 @@ impl PostRepository for PostRepositoryImpl {
         active_model
             .insert(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create post: {}", e)))?;
+            .context("Failed to create post")?;
 
         Ok(post)
     }
 
+    async fn batch_upsert_posts(&self, posts: Vec<Post>) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.batch_upsert_posts");
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // Dropping the transaction on an early return rolls back the batch
+        for post in &posts {
+            post_upsert_query(post)
+                .exec_without_returning(&txn)
+                .await
+                .context("Failed to upsert post")?;
+        }
+
+        txn.commit().await.context("Failed to commit transaction")?;
+        Ok(posts)
+    }
+
     async fn get_post(&self, id: Uuid) -> Result<Post> {
+        let _timer = metrics::db_timer("post.get_post");
         let model = crate::entity::post::Entity::find_by_id(id.to_string())
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find post: {}", e)))?
-            .ok_or_else(|| Error::NotFound(format!("Post with id {} not found", id)))?;
+            .context("Failed to find post")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::PostNotFound,
+                    format!("Post with id {} not found", id),
+                )
+            })?;
 
         model_to_post(model)
     }
 
     async fn update_post(&self, post: Post) -> Result<Post> {
+        let _timer = metrics::db_timer("post.update_post");
         let entity = post_to_entity(&post);
         let active_model = entity_to_active_model(entity);
 
         active_model
             .update(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to update post: {}", e)))?;
+            .context("Failed to update post")?;
 
         Ok(post)
     }
 
-    async fn list_published_posts(&self, limit: u64) -> Result<Vec<Post>> {
-        let models = crate::entity::post::Entity::find()
-            .filter(crate::entity::post::Column::PublishedAt.is_not_null())
-            .order_by_desc(crate::entity::post::Column::PublishedAt)
+    async fn list_published_posts(&self, limit: u64, offset: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.list_published_posts");
+        let models = published_posts_pinned_first()
+            .offset(offset)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to list published posts")?;
+
+        models.into_iter().map(model_to_post).collect()
+    }
+
+    async fn list_published_posts_with_authors(
+        &self,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<PostWithAuthor>> {
+        let _timer = metrics::db_timer("post.list_published_posts_with_authors");
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                self.db.get_database_backend(),
+                PUBLISHED_POSTS_WITH_AUTHORS_SQL,
+                [(limit as i64).into(), (offset as i64).into()],
+            ))
+            .await
+            .context("Failed to list published posts with authors")?;
+
+        rows.iter()
+            .map(|row| {
+                let model = crate::entity::post::Model::from_query_result(row, "")
+                    .context("Invalid post row")?;
+                let author =
+                    AuthorColumns::from_query_result(row, "").context("Invalid author columns")?;
+                Ok(author.with_post(model_to_post(model)?))
+            })
+            .collect()
+    }
+
+    async fn list_posts_with_tags(&self, limit: u64, offset: u64) -> Result<Vec<PostWithTags>> {
+        let _timer = metrics::db_timer("post.list_posts_with_tags");
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                self.db.get_database_backend(),
+                PUBLISHED_POSTS_WITH_TAGS_SQL,
+                [(limit as i64).into(), (offset as i64).into()],
+            ))
+            .await
+            .context("Failed to list published posts with tags")?;
+
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let model = crate::entity::post::Model::from_query_result(row, "")
+                    .context("Invalid post row")?;
+                let author =
+                    AuthorColumns::from_query_result(row, "").context("Invalid author columns")?;
+                let tag = TagColumns::from_query_result(row, "")
+                    .context("Invalid tag columns")?
+                    .into_tag()?;
+                Ok((author.with_post(model_to_post(model)?), tag))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(group_post_tags(rows))
+    }
+
+    async fn count_published(&self) -> Result<u64> {
+        let _timer = metrics::db_timer("post.count_published");
+        Ok(published_posts_pinned_first()
+            .count(self.db.as_ref())
+            .await
+            .context("Failed to count published posts")?)
+    }
+
+    async fn list_published_between(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+        limit: u64,
+    ) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.list_published_between");
+        let models = published_between_query(start, end)
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to list published posts: {}", e)))?;
+            .context("Failed to list posts by publish date")?;
 
         models.into_iter().map(model_to_post).collect()
     }
 
+    async fn count_published_by_month(&self) -> Result<Vec<ArchiveMonth>> {
+        let _timer = metrics::db_timer("post.count_published_by_month");
+        let rows = ArchiveMonthRow::find_by_statement(Statement::from_string(
+            self.db.get_database_backend(),
+            ARCHIVE_MONTHS_SQL,
+        ))
+        .all(self.db.as_ref())
+        .await
+        .context("Failed to count posts by month")?;
+
+        Ok(rows.into_iter().map(ArchiveMonth::from).collect())
+    }
+
+    async fn list_published_posts_by_views(&self, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.list_published_posts_by_views");
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                self.db.get_database_backend(),
+                "SELECT post.* FROM post \
+                 LEFT JOIN post_stats ON post_stats.post_id = post.id \
+                 WHERE post.published_at IS NOT NULL AND post.deleted_at IS NULL \
+                 ORDER BY COALESCE(post_stats.views, 0) DESC, post.published_at DESC \
+                 LIMIT $1",
+                [(limit as i64).into()],
+            ))
+            .await
+            .context("Failed to list posts by views")?;
+
+        rows.iter()
+            .map(|row| {
+                let model = crate::entity::post::Model::from_query_result(row, "")
+                    .context("Invalid post row")?;
+                model_to_post(model)
+            })
+            .collect()
+    }
+
+    async fn bulk_update_publication(
+        &self,
+        ids: Vec<Uuid>,
+        published_at: Option<chrono::DateTime<chrono::Utc>>,
+        scheduled_publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<u64> {
+        let _timer = metrics::db_timer("post.bulk_update_publication");
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = crate::entity::post::Entity::update_many()
+            .col_expr(
+                crate::entity::post::Column::PublishedAt,
+                Expr::value(published_at.map(|at| at.to_rfc3339())),
+            )
+            .col_expr(
+                crate::entity::post::Column::ScheduledPublishAt,
+                Expr::value(scheduled_publish_at.map(|at| at.to_rfc3339())),
+            )
+            .filter(crate::entity::post::Column::Id.is_in(ids.iter().map(|id| id.to_string())))
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
+            .exec(self.db.as_ref())
+            .await
+            .context("Failed to update posts")?;
+
+        Ok(result.rows_affected)
+    }
+
     async fn delete_post(&self, id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.delete_post");
+        let result = crate::entity::post::Entity::update_many()
+            .col_expr(
+                crate::entity::post::Column::DeletedAt,
+                Expr::value(chrono::Utc::now().to_rfc3339()),
+            )
+            .filter(crate::entity::post::Column::Id.eq(id.to_string()))
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
+            .exec(self.db.as_ref())
+            .await
+            .context("Failed to delete post")?;
+
+        if result.rows_affected == 0 {
+            return Err(Error::coded(
+                ErrorCode::PostNotFound,
+                format!("Post with id {} not found", id),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn hard_delete_post(&self, id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.hard_delete_post");
         crate::entity::post::Entity::delete_by_id(id.to_string())
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to delete post: {}", e)))?;
+            .context("Failed to delete post")?;
         Ok(())
     }
 
+    async fn restore_post(&self, id: Uuid) -> Result<Post> {
+        let _timer = metrics::db_timer("post.restore_post");
+        let model = crate::entity::post::Entity::find_by_id(id.to_string())
+            .filter(crate::entity::post::Column::DeletedAt.is_not_null())
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find post")?
+            .ok_or_else(|| Error::NotFound(format!("Deleted post with id {} not found", id)))?;
+
+        let mut active: crate::entity::post::ActiveModel = model.into();
+        active.deleted_at = Set(None);
+
+        let model = active
+            .update(self.db.as_ref())
+            .await
+            .context("Failed to restore post")?;
+
+        model_to_post(model)
+    }
+
+    async fn purge_deleted_posts(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64> {
+        let _timer = metrics::db_timer("post.purge_deleted_posts");
+        let result = crate::entity::post::Entity::delete_many()
+            .filter(crate::entity::post::Column::DeletedAt.is_not_null())
+            .filter(crate::entity::post::Column::DeletedAt.lt(before.to_rfc3339()))
+            .exec(self.db.as_ref())
+            .await
+            .context("Failed to purge deleted posts")?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn archive_post(&self, id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.archive_post");
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let copied = execute_for_post(
+            &txn,
+            "INSERT INTO archived_post SELECT * FROM post WHERE id = $1 AND deleted_at IS NULL",
+            id,
+        )
+        .await?;
+        if copied == 0 {
+            return Err(Error::coded(
+                ErrorCode::PostNotFound,
+                format!("Post with id {} not found", id),
+            ));
+        }
+        for sql in ARCHIVE_CHILD_ROWS {
+            execute_for_post(&txn, sql, id).await?;
+        }
+        // Child rows left in the live tables go with the post via ON DELETE CASCADE
+        execute_for_post(&txn, "DELETE FROM post WHERE id = $1", id).await?;
+
+        Ok(txn.commit().await.context("Failed to commit transaction")?)
+    }
+
+    async fn unarchive_post(&self, id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.unarchive_post");
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // The category may have been deleted while the post was archived
+        execute_for_post(
+            &txn,
+            "UPDATE archived_post SET category_id = NULL WHERE id = $1 AND category_id NOT IN (SELECT id FROM category)",
+            id,
+        )
+        .await?;
+        let copied = execute_for_post(
+            &txn,
+            "INSERT INTO post SELECT * FROM archived_post WHERE id = $1",
+            id,
+        )
+        .await?;
+        if copied == 0 {
+            return Err(Error::NotFound(format!(
+                "Archived post with id {} not found",
+                id
+            )));
+        }
+        for sql in UNARCHIVE_CHILD_ROWS.into_iter().chain(DELETE_ARCHIVED_ROWS) {
+            execute_for_post(&txn, sql, id).await?;
+        }
+
+        Ok(txn.commit().await.context("Failed to commit transaction")?)
+    }
+
+    async fn get_archived_post(&self, id: Uuid) -> Result<Post> {
+        let _timer = metrics::db_timer("post.get_archived_post");
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            "SELECT * FROM archived_post WHERE id = $1",
+            [id.to_string().into()],
+        );
+        let model = crate::entity::post::Entity::find()
+            .from_raw_sql(statement)
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find archived post")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::PostNotFound,
+                    format!("Post with id {} not found", id),
+                )
+            })?;
+
+        model_to_post(model)
+    }
+
+    async fn list_archived_posts(&self, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.list_archived_posts");
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            "SELECT * FROM archived_post ORDER BY published_at DESC NULLS LAST LIMIT $1",
+            [(limit as i64).into()],
+        );
+        let models = crate::entity::post::Entity::find()
+            .from_raw_sql(statement)
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to list archived posts")?;
+
+        models.into_iter().map(model_to_post).collect()
+    }
+
+    async fn get_posts_sharing_tags(
+        &self,
+        tag_ids: Vec<Uuid>,
+        exclude_post_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<(Post, u32)>> {
+        let _timer = metrics::db_timer("post.get_posts_sharing_tags");
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders: Vec<String> = (0..tag_ids.len()).map(|i| format!("${}", i + 3)).collect();
+        let sql = format!(
+            "SELECT post.*, shared.shared_tags FROM post \
+             JOIN (SELECT post_id, COUNT(*) AS shared_tags FROM post_tag \
+                   WHERE tag_id IN ({}) AND post_id <> $1 GROUP BY post_id) shared \
+             ON shared.post_id = post.id \
+             WHERE post.published_at IS NOT NULL AND post.deleted_at IS NULL \
+             ORDER BY shared.shared_tags DESC, post.published_at DESC \
+             LIMIT $2",
+            placeholders.join(", ")
+        );
+        let mut values: Vec<sea_orm::Value> =
+            vec![exclude_post_id.to_string().into(), (limit as i64).into()];
+        values.extend(tag_ids.iter().map(|id| id.to_string().into()));
+
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                self.db.get_database_backend(),
+                sql,
+                values,
+            ))
+            .await
+            .context("Failed to find related posts")?;
+
+        rows.iter()
+            .map(|row| {
+                let model = crate::entity::post::Model::from_query_result(row, "")
+                    .context("Invalid post row")?;
+                let shared: i64 = row.try_get("", "shared_tags").context("Invalid post row")?;
+                Ok((model_to_post(model)?, shared as u32))
+            })
+            .collect()
+    }
+
+    async fn get_next_published_post(
+        &self,
+        after_published_at: chrono::DateTime<chrono::Utc>,
+        current_id: Uuid,
+    ) -> Result<Option<Post>> {
+        let _timer = metrics::db_timer("post.get_next_published_post");
+        adjacent_published_query(after_published_at, current_id, true)
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to get next post")?
+            .map(model_to_post)
+            .transpose()
+    }
+
+    async fn get_prev_published_post(
+        &self,
+        before_published_at: chrono::DateTime<chrono::Utc>,
+        current_id: Uuid,
+    ) -> Result<Option<Post>> {
+        let _timer = metrics::db_timer("post.get_prev_published_post");
+        adjacent_published_query(before_published_at, current_id, false)
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to get previous post")?
+            .map(model_to_post)
+            .transpose()
+    }
+
+    async fn list_published_post_ids_before(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Uuid>> {
+        let _timer = metrics::db_timer("post.list_published_post_ids_before");
+        let ids: Vec<String> = crate::entity::post::Entity::find()
+            .select_only()
+            .column(crate::entity::post::Column::Id)
+            .filter(crate::entity::post::Column::PublishedAt.is_not_null())
+            .filter(crate::entity::post::Column::PublishedAt.lt(before.to_rfc3339()))
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
+            .filter(crate::entity::post::Column::PinnedAt.is_null())
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to list posts to archive")?;
+
+        ids.iter()
+            .map(|id| {
+                Uuid::parse_str(id).map_err(|e| {
+                    InfrastructureError::Serialize(format!("Invalid post ID: {}", e)).into()
+                })
+            })
+            .collect()
+    }
+
+    async fn list_due_scheduled_post_ids(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Uuid>> {
+        let _timer = metrics::db_timer("post.list_due_scheduled_post_ids");
+        let ids: Vec<String> = crate::entity::post::Entity::find()
+            .select_only()
+            .column(crate::entity::post::Column::Id)
+            .filter(crate::entity::post::Column::ScheduledPublishAt.is_not_null())
+            .filter(crate::entity::post::Column::ScheduledPublishAt.lte(now.to_rfc3339()))
+            .filter(crate::entity::post::Column::PublishedAt.is_null())
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to list scheduled posts")?;
+
+        ids.iter()
+            .map(|id| {
+                Uuid::parse_str(id).map_err(|e| {
+                    InfrastructureError::Serialize(format!("Invalid post ID: {}", e)).into()
+                })
+            })
+            .collect()
+    }
+
     async fn get_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.get_posts_by_user");
         let models = crate::entity::post::Entity::find()
             .filter(crate::entity::post::Column::UserId.eq(user_id.to_string()))
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .order_by_desc(crate::entity::post::Column::CreatedAt)
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to list posts by user: {}", e)))?;
+            .context("Failed to list posts by user")?;
 
         models.into_iter().map(model_to_post).collect()
     }
 
+    async fn count_posts_by_user(&self, user_id: Uuid) -> Result<u64> {
+        let _timer = metrics::db_timer("post.count_posts_by_user");
+        Ok(crate::entity::post::Entity::find()
+            .filter(crate::entity::post::Column::UserId.eq(user_id.to_string()))
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
+            .count(self.db.as_ref())
+            .await
+            .context("Failed to count posts by user")?)
+    }
+
     async fn list_published_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.list_published_posts_by_user");
         let models = crate::entity::post::Entity::find()
             .filter(crate::entity::post::Column::UserId.eq(user_id.to_string()))
             .filter(crate::entity::post::Column::PublishedAt.is_not_null())
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .order_by_desc(crate::entity::post::Column::PublishedAt)
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| {
-                Error::Internal(format!("Failed to list published posts by user: {}", e))
-            })?;
+            .context("Failed to list published posts by user")?;
 
         models.into_iter().map(model_to_post).collect()
     }
 
+    async fn list_posts_with_view_counts(
+        &self,
+        user_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<(Post, u64)>> {
+        let _timer = metrics::db_timer("post.list_posts_with_view_counts");
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                self.db.get_database_backend(),
+                "SELECT post.*, COALESCE(post_stats.views, 0) AS stats_views FROM post \
+                 LEFT JOIN post_stats ON post_stats.post_id = post.id \
+                 WHERE post.user_id = $1 AND post.deleted_at IS NULL \
+                 ORDER BY post.created_at DESC \
+                 LIMIT $2",
+                [user_id.to_string().into(), (limit as i64).into()],
+            ))
+            .await
+            .context("Failed to list posts with views")?;
+
+        rows.iter()
+            .map(|row| {
+                let model = crate::entity::post::Model::from_query_result(row, "")
+                    .context("Invalid post row")?;
+                let views: i64 = row
+                    .try_get("", "stats_views")
+                    .context("Invalid view count")?;
+                Ok((model_to_post(model)?, views.max(0) as u64))
+            })
+            .collect()
+    }
+
+    async fn aggregate_word_counts(&self, published_only: bool) -> Result<WordCountStats> {
+        let _timer = metrics::db_timer("post.aggregate_word_counts");
+        let row = WordCountRow::find_by_statement(Statement::from_string(
+            self.db.get_database_backend(),
+            word_count_stats_sql(published_only),
+        ))
+        .one(self.db.as_ref())
+        .await
+        .context("Failed to count words")?;
+
+        match row {
+            Some(row) => row.into_stats(),
+            None => Ok(WordCountStats::default()),
+        }
+    }
+
     async fn list_all_posts(&self, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.list_all_posts");
         let models = crate::entity::post::Entity::find()
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .order_by_desc(crate::entity::post::Column::CreatedAt)
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to list all posts: {}", e)))?;
+            .context("Failed to list all posts")?;
 
         models.into_iter().map(model_to_post).collect()
     }
 
     async fn update_post_category(&self, post_id: Uuid, category_id: Option<Uuid>) -> Result<()> {
-        use sea_orm::ActiveModelTrait;
-
+        let _timer = metrics::db_timer("post.update_post_category");
         let post = crate::entity::post::Entity::find_by_id(post_id.to_string())
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find post: {}", e)))?
+            .context("Failed to find post")?
             .ok_or_else(|| Error::NotFound("Post not found".to_string()))?;
 
-        let mut active: crate::entity::post::ActiveModel = post.into();
-        active.category_id = Set(category_id.map(|id| id.to_string()));
+        let old_category = post.category_id.clone();
+        let new_category = category_id.map(|id| id.to_string());
+        if old_category == new_category {
+            return Ok(());
+        }
 
-        active
-            .update(self.db.as_ref())
+        // The post and both category counts are written atomically
+        let txn = self
+            .db
+            .begin()
             .await
-            .map_err(|e| Error::Internal(format!("Failed to update post: {}", e)))?;
+            .context("Failed to start transaction")?;
+
+        let mut active: crate::entity::post::ActiveModel = post.into();
+        active.category_id = Set(new_category.clone());
+        active.update(&txn).await.context("Failed to update post")?;
+
+        if let Some(old_category) = old_category {
+            adjust_category_post_count(&txn, &old_category, -1).await?;
+        }
+        if let Some(new_category) = new_category {
+            adjust_category_post_count(&txn, &new_category, 1).await?;
+        }
+
+        txn.commit().await.context("Failed to commit transaction")?;
 
         Ok(())
     }
 
+    async fn increment_category_post_count(&self, category_id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.increment_category_post_count");
+        adjust_category_post_count(self.db.as_ref(), &category_id.to_string(), 1).await
+    }
+
+    async fn decrement_category_post_count(&self, category_id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.decrement_category_post_count");
+        adjust_category_post_count(self.db.as_ref(), &category_id.to_string(), -1).await
+    }
+
     async fn get_posts_by_category(&self, category_id: Uuid, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.get_posts_by_category");
         let models = crate::entity::post::Entity::find()
             .filter(crate::entity::post::Column::CategoryId.eq(category_id.to_string()))
             .filter(crate::entity::post::Column::PublishedAt.is_not_null())
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .order_by_desc(crate::entity::post::Column::CreatedAt)
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get posts by category: {}", e)))?;
+            .context("Failed to get posts by category")?;
 
         models.into_iter().map(model_to_post).collect()
     }
 
+    async fn get_posts_by_category_recursive(
+        &self,
+        category_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.get_posts_by_category_recursive");
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            POSTS_IN_CATEGORY_TREE_SQL,
+            [
+                category_id.to_string().into(),
+                (MAX_CATEGORY_DEPTH as i32).into(),
+                (limit as i64).into(),
+            ],
+        );
+        let models = crate::entity::post::Entity::find()
+            .from_raw_sql(statement)
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to get posts by category tree")?;
+
+        models.into_iter().map(model_to_post).collect()
+    }
+
+    async fn list_published_by_category_paginated(
+        &self,
+        category_id: Uuid,
+        recursive: bool,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<Post>, u64)> {
+        let _timer = metrics::db_timer("post.list_published_by_category_paginated");
+        let category_ids: Vec<String> = if recursive {
+            CategoryIdRow::find_by_statement(Statement::from_sql_and_values(
+                self.db.get_database_backend(),
+                CATEGORY_TREE_IDS_SQL,
+                [
+                    category_id.to_string().into(),
+                    (MAX_CATEGORY_DEPTH as i32).into(),
+                ],
+            ))
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to list category tree")?
+            .into_iter()
+            .map(|row| row.id)
+            .collect()
+        } else {
+            vec![category_id.to_string()]
+        };
+
+        let total = published_in_categories(&category_ids)
+            .count(self.db.as_ref())
+            .await
+            .context("Failed to count posts by category")?;
+
+        let models = published_in_categories(&category_ids)
+            .offset(offset)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to list posts by category")?;
+
+        Ok((
+            models
+                .into_iter()
+                .map(model_to_post)
+                .collect::<Result<_>>()?,
+            total,
+        ))
+    }
+
     async fn add_tag_to_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.add_tag_to_post");
         use sea_orm::ActiveModelTrait;
 
         let post_tag = crate::entity::post_tag::ActiveModel {
@@ -244,28 +1335,64 @@ impl PostRepository for PostRepositoryImpl {
         };
 
         post_tag.insert(self.db.as_ref()).await.map_err(|e| {
-            if e.to_string().contains("UNIQUE") {
-                Error::Validation("Tag already added to post".to_string())
-            } else {
-                Error::Internal(format!("Failed to add tag to post: {}", e))
+            match InfrastructureError::db("Failed to add tag to post", e) {
+                InfrastructureError::Constraint(_) => {
+                    Error::Validation("Tag already added to post".to_string())
+                }
+                other => other.into(),
             }
         })?;
 
         Ok(())
     }
 
+    async fn replace_post_tags(&self, post_id: Uuid, tag_ids: Vec<Uuid>) -> Result<()> {
+        let _timer = metrics::db_timer("post.replace_post_tags");
+        // Existing tags are only removed if every new tag is inserted
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        crate::entity::post_tag::Entity::delete_many()
+            .filter(crate::entity::post_tag::Column::PostId.eq(post_id.to_string()))
+            .exec(&txn)
+            .await
+            .context("Failed to clear post tags")?;
+
+        if !tag_ids.is_empty() {
+            let rows = tag_ids
+                .into_iter()
+                .map(|tag_id| crate::entity::post_tag::ActiveModel {
+                    post_id: Set(post_id.to_string()),
+                    tag_id: Set(tag_id.to_string()),
+                });
+            crate::entity::post_tag::Entity::insert_many(rows)
+                .exec(&txn)
+                .await
+                .context("Failed to add post tags")?;
+        }
+
+        txn.commit().await.context("Failed to commit transaction")?;
+
+        Ok(())
+    }
+
     async fn remove_tag_from_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("post.remove_tag_from_post");
         crate::entity::post_tag::Entity::delete_many()
             .filter(crate::entity::post_tag::Column::PostId.eq(post_id.to_string()))
             .filter(crate::entity::post_tag::Column::TagId.eq(tag_id.to_string()))
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to remove tag from post: {}", e)))?;
+            .context("Failed to remove tag from post")?;
 
         Ok(())
     }
 
     async fn get_post_tags(&self, post_id: Uuid) -> Result<Vec<domain::Tag>> {
+        let _timer = metrics::db_timer("post.get_post_tags");
         use sea_orm::EntityTrait;
 
         let post_tags = crate::entity::post_tag::Entity::find()
@@ -273,7 +1400,7 @@ impl PostRepository for PostRepositoryImpl {
             .find_also_related(crate::entity::tag::Entity)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get post tags: {}", e)))?;
+            .context("Failed to get post tags")?;
 
         let tags = post_tags
             .into_iter()
@@ -292,15 +1419,17 @@ impl PostRepository for PostRepositoryImpl {
     }
 
     async fn get_posts_by_tag(&self, tag_id: Uuid, limit: u64) -> Result<Vec<Post>> {
+        let _timer = metrics::db_timer("post.get_posts_by_tag");
         use sea_orm::{EntityTrait, QuerySelect};
 
         let post_tags = crate::entity::post_tag::Entity::find()
             .filter(crate::entity::post_tag::Column::TagId.eq(tag_id.to_string()))
             .find_also_related(crate::entity::post::Entity)
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get posts by tag: {}", e)))?;
+            .context("Failed to get posts by tag")?;
 
         let posts = post_tags
             .into_iter()
@@ -317,23 +1446,26 @@ impl PostRepository for PostRepositoryImpl {
         limit: u64,
         offset: u64,
     ) -> Result<SearchPostsResponse> {
+        let _timer = metrics::db_timer("post.search_posts");
         let search_pattern = format!("%{query}%");
 
         // Build condition: (title LIKE ? OR content LIKE ?) AND published_at IS NOT NULL
+        // AND deleted_at IS NULL
         let condition = Condition::all()
             .add(
                 Condition::any()
                     .add(crate::entity::post::Column::Title.like(&search_pattern))
                     .add(crate::entity::post::Column::Content.like(&search_pattern)),
             )
-            .add(crate::entity::post::Column::PublishedAt.is_not_null());
+            .add(crate::entity::post::Column::PublishedAt.is_not_null())
+            .add(crate::entity::post::Column::DeletedAt.is_null());
 
         // Get total count
         let total = crate::entity::post::Entity::find()
             .filter(condition.clone())
             .count(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to count search results: {}", e)))?;
+            .context("Failed to count search results")?;
 
         // Get paginated results
         let models = crate::entity::post::Entity::find()
@@ -343,7 +1475,7 @@ impl PostRepository for PostRepositoryImpl {
             .offset(offset)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to search posts: {}", e)))?;
+            .context("Failed to search posts")?;
 
         let posts = models
             .into_iter()
@@ -356,4 +1488,406 @@ impl PostRepository for PostRepositoryImpl {
             query: query.to_string(),
         })
     }
+
+    async fn search_posts_advanced(
+        &self,
+        request: &PostSearchRequest,
+    ) -> Result<SearchPostsResponse> {
+        let _timer = metrics::db_timer("post.search_posts_advanced");
+        let query = post_search_query(request);
+
+        let total = query
+            .clone()
+            .count(self.db.as_ref())
+            .await
+            .context("Failed to count search results")?;
+
+        let models = query
+            .limit(request.limit)
+            .offset(request.offset)
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to search posts")?;
+
+        let posts = models
+            .into_iter()
+            .map(model_to_post)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SearchPostsResponse {
+            posts,
+            total,
+            query: request
+                .title_contains
+                .clone()
+                .or_else(|| request.content_contains.clone())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    /// Connect to the Postgres database named by `TEST_DATABASE_URL`
+    ///
+    /// Tests that need a real database are skipped when it is not set.
+    async fn test_database() -> Option<Arc<DatabaseConnection>> {
+        use crate::{Migrator, MigratorTrait};
+
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let db = crate::establish_connection(&url).await.unwrap();
+        Migrator::up(&*db, None).await.unwrap();
+        Some(db)
+    }
+
+    #[tokio::test]
+    async fn test_archive_round_trip_keeps_comment_reactions() {
+        use crate::{CommentRepositoryImpl, UserRepositoryImpl};
+        use domain::{Comment, CommentRepository, UserRepository};
+
+        let Some(db) = test_database().await else {
+            return;
+        };
+        let users = UserRepositoryImpl::new(db.clone());
+        let posts = PostRepositoryImpl::new(db.clone());
+        let comments = CommentRepositoryImpl::new(db);
+
+        let username = format!("archive-{}", Uuid::new_v4().simple());
+        let user = users
+            .create_user(username, "password123".to_string(), 0)
+            .await
+            .unwrap();
+        let post = posts
+            .create_post(user.id, "Archived".to_string(), "content".to_string())
+            .await
+            .unwrap();
+        let comment = comments
+            .create_comment(Comment::from_user(post.id, user.id, "Nice".to_string()))
+            .await
+            .unwrap();
+        assert!(comments
+            .add_reaction(comment.id, user.id, "like")
+            .await
+            .unwrap());
+
+        posts.archive_post(post.id).await.unwrap();
+        assert!(comments
+            .count_reactions(comment.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        posts.unarchive_post(post.id).await.unwrap();
+        let reactions = comments.count_reactions(comment.id).await.unwrap();
+        assert_eq!(reactions.get("like"), Some(&1));
+
+        posts.hard_delete_post(post.id).await.unwrap();
+        users.delete_user(user.id).await.unwrap();
+    }
+
+    #[test]
+    fn test_pinned_posts_are_listed_before_newer_posts() {
+        let sql = published_posts_pinned_first()
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(sql.ends_with(
+            r#"ORDER BY "post"."pinned_at" DESC NULLS LAST, "post"."published_at" DESC"#
+        ));
+    }
+
+    #[test]
+    fn test_posts_with_authors_left_join_users_in_listing_order() {
+        let sql = PUBLISHED_POSTS_WITH_AUTHORS_SQL;
+
+        assert!(sql.contains(r#"FROM post LEFT JOIN "user" ON "user".id = post.user_id"#));
+        assert!(sql.contains(r#""user".username AS author_username"#));
+        assert!(sql.contains(r#""user".avatar_url AS author_avatar_url"#));
+        assert!(sql.contains("ORDER BY post.pinned_at DESC NULLS LAST, post.published_at DESC"));
+        assert!(sql.ends_with("LIMIT $1 OFFSET $2"));
+    }
+
+    #[test]
+    fn test_posts_in_category_tree_follow_children_to_bounded_depth() {
+        let sql = POSTS_IN_CATEGORY_TREE_SQL;
+
+        assert!(sql.starts_with("WITH RECURSIVE descendants AS ("));
+        assert!(sql.contains("JOIN descendants d ON c.parent_id = d.id"));
+        assert!(sql.contains("WHERE d.depth < $2"));
+        assert!(sql.contains("WHERE category_id IN (SELECT id FROM descendants)"));
+        assert!(sql.contains("published_at IS NOT NULL AND deleted_at IS NULL"));
+        assert!(sql.ends_with("LIMIT $3"));
+    }
+
+    #[test]
+    fn test_category_tree_ids_follow_children_to_bounded_depth() {
+        let sql = CATEGORY_TREE_IDS_SQL;
+
+        assert!(sql.contains("SELECT c.id, 0 AS depth FROM category c WHERE c.id = $1"));
+        assert!(sql.contains("JOIN descendants d ON c.parent_id = d.id"));
+        assert!(sql.contains("WHERE d.depth < $2"));
+        assert!(sql.ends_with("SELECT DISTINCT id FROM descendants"));
+    }
+
+    #[test]
+    fn test_author_columns_fill_in_deleted_author() {
+        let post = Post::new(Uuid::new_v4(), "Title".to_string(), "Content".to_string());
+
+        let joined = AuthorColumns {
+            author_username: Some("alice".to_string()),
+            author_avatar_url: Some("https://example.com/a.png".to_string()),
+        }
+        .with_post(post.clone());
+        assert_eq!(joined.author_username, "alice");
+        assert_eq!(
+            joined.author_avatar_url.as_deref(),
+            Some("https://example.com/a.png")
+        );
+
+        let orphaned = AuthorColumns {
+            author_username: None,
+            author_avatar_url: None,
+        }
+        .with_post(post.clone());
+        assert_eq!(orphaned.post, post);
+        assert_eq!(orphaned.author_username, "[deleted]");
+        assert_eq!(orphaned.author_avatar_url, None);
+    }
+
+    #[test]
+    fn test_posts_with_tags_are_paged_before_joining_tags() {
+        let sql = PUBLISHED_POSTS_WITH_TAGS_SQL;
+
+        assert!(sql.contains("LIMIT $1 OFFSET $2) AS post"));
+        assert!(sql.contains("LEFT JOIN post_tag ON post_tag.post_id = post.id"));
+        assert!(sql.contains("LEFT JOIN tag ON tag.id = post_tag.tag_id"));
+        assert!(sql.ends_with("post.id, tag.name"));
+    }
+
+    #[test]
+    fn test_group_post_tags_folds_rows_per_post() {
+        let author = |title: &str| {
+            AuthorColumns {
+                author_username: Some("alice".to_string()),
+                author_avatar_url: None,
+            }
+            .with_post(Post::new(
+                Uuid::new_v4(),
+                title.to_string(),
+                "Content".to_string(),
+            ))
+        };
+        let tag = |name: &str| Some(domain::Tag::new(name.to_string(), name.to_string()));
+        let (first, untagged, last) = (author("First"), author("Untagged"), author("Last"));
+
+        let posts = group_post_tags(vec![
+            (first.clone(), tag("async")),
+            (first.clone(), tag("rust")),
+            (untagged.clone(), None),
+            (last.clone(), tag("web")),
+        ]);
+
+        let titles: Vec<&str> = posts.iter().map(|p| p.post.post.title.as_str()).collect();
+        assert_eq!(titles, ["First", "Untagged", "Last"]);
+        let tags: Vec<Vec<&str>> = posts
+            .iter()
+            .map(|p| p.tags.iter().map(|t| t.name.as_str()).collect())
+            .collect();
+        assert_eq!(tags, [vec!["async", "rust"], vec![], vec!["web"]]);
+    }
+
+    #[test]
+    fn test_word_count_stats_filters_published_posts() {
+        let all = word_count_stats_sql(false);
+        let published = word_count_stats_sql(true);
+
+        assert!(all.contains("FROM post WHERE deleted_at IS NULL)"));
+        assert!(!all.contains("published_at"));
+        assert!(published.contains("WHERE deleted_at IS NULL AND published_at IS NOT NULL)"));
+    }
+
+    #[test]
+    fn test_word_count_row_averages_over_posts() {
+        let longest = Uuid::new_v4();
+        let shortest = Uuid::new_v4();
+        let row = WordCountRow {
+            total_words: 1_000,
+            post_count: 4,
+            longest_post_id: Some(longest.to_string()),
+            shortest_post_id: Some(shortest.to_string()),
+        };
+
+        let stats = row.into_stats().unwrap();
+        assert_eq!(stats.total_words, 1_000);
+        assert_eq!(stats.average_words_per_post, 250.0);
+        assert_eq!(stats.longest_post_id, Some(longest));
+        assert_eq!(stats.shortest_post_id, Some(shortest));
+
+        let empty = WordCountRow {
+            total_words: 0,
+            post_count: 0,
+            longest_post_id: None,
+            shortest_post_id: None,
+        };
+        assert_eq!(empty.into_stats().unwrap(), WordCountStats::default());
+    }
+
+    #[test]
+    fn test_published_between_is_half_open() {
+        let start = "2024-12-01T00:00:00Z".parse().unwrap();
+        let end = "2025-01-01T00:00:00Z".parse().unwrap();
+        let sql = published_between_query(start, end)
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(sql.contains(r#""post"."published_at" >= '2024-12-01T00:00:00+00:00'"#));
+        assert!(sql.contains(r#""post"."published_at" < '2025-01-01T00:00:00+00:00'"#));
+        assert!(sql.contains(r#""post"."deleted_at" IS NULL"#));
+        assert!(sql.ends_with(r#"ORDER BY "post"."published_at" DESC"#));
+    }
+
+    #[test]
+    fn test_adjacent_posts_break_publication_ties_by_id() {
+        let at = "2025-01-01T00:00:00Z".parse().unwrap();
+        let id = Uuid::nil();
+        let next = adjacent_published_query(at, id, true)
+            .build(DbBackend::Postgres)
+            .to_string();
+        let prev = adjacent_published_query(at, id, false)
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(next.contains(
+            r#"("post"."published_at" > '2025-01-01T00:00:00+00:00' OR ("post"."published_at" = '2025-01-01T00:00:00+00:00' AND "post"."id" > '00000000-0000-0000-0000-000000000000'))"#
+        ));
+        assert!(next.ends_with(r#"ORDER BY "post"."published_at" ASC, "post"."id" ASC"#));
+        assert!(prev.contains(r#""post"."published_at" < '2025-01-01T00:00:00+00:00' OR"#));
+        assert!(prev.ends_with(r#"ORDER BY "post"."published_at" DESC, "post"."id" DESC"#));
+    }
+
+    #[test]
+    fn test_post_upsert_overwrites_existing_row_by_id() {
+        let post = Post::new(Uuid::nil(), "Title".to_string(), "Content".to_string());
+        let sql = post_upsert_query(&post)
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(sql.starts_with(r#"INSERT INTO "post""#));
+        assert!(
+            sql.contains(r#"ON CONFLICT ("id") DO UPDATE SET "user_id" = "excluded"."user_id""#)
+        );
+        assert!(sql.contains(r#""scheduled_publish_at" = "excluded"."scheduled_publish_at""#));
+        assert!(!sql.contains(r#""id" = "excluded"."id""#));
+    }
+
+    #[test]
+    fn test_archive_month_row_conversion() {
+        let row = ArchiveMonthRow {
+            year: 2024,
+            month: 12,
+            count: 7,
+        };
+
+        assert_eq!(
+            ArchiveMonth::from(row),
+            ArchiveMonth {
+                year: 2024,
+                month: 12,
+                count: 7
+            }
+        );
+    }
+
+    fn search_sql(request: &PostSearchRequest) -> String {
+        post_search_query(request)
+            .build(DbBackend::Postgres)
+            .to_string()
+    }
+
+    #[test]
+    fn test_search_applies_each_filter_alone() {
+        let id = Uuid::new_v4();
+        let at = chrono::DateTime::parse_from_rfc3339("2025-01-01T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let cases = [
+            (
+                PostSearchRequest::builder().title_contains("rust").build(),
+                r#""post"."title" LIKE '%rust%'"#.to_string(),
+            ),
+            (
+                PostSearchRequest::builder()
+                    .content_contains("axum")
+                    .build(),
+                r#""post"."content" LIKE '%axum%'"#.to_string(),
+            ),
+            (
+                PostSearchRequest::builder().author_id(id).build(),
+                format!(r#""post"."user_id" = '{}'"#, id),
+            ),
+            (
+                PostSearchRequest::builder().category_id(id).build(),
+                format!(r#""post"."category_id" = '{}'"#, id),
+            ),
+            (
+                PostSearchRequest::builder().tag_ids(vec![id, id]).build(),
+                format!(
+                    r#""post"."id" IN (SELECT "post_id" FROM "post_tag" WHERE "post_tag"."tag_id" IN ('{}') GROUP BY "post_id" HAVING COUNT(DISTINCT "tag_id") = 1)"#,
+                    id
+                ),
+            ),
+            (
+                PostSearchRequest::builder().published_after(at).build(),
+                r#""post"."published_at" >= '2025-01-01T00:00:00+00:00'"#.to_string(),
+            ),
+            (
+                PostSearchRequest::builder().published_before(at).build(),
+                r#""post"."published_at" < '2025-01-01T00:00:00+00:00'"#.to_string(),
+            ),
+        ];
+
+        let unfiltered = search_sql(&PostSearchRequest::default());
+        assert!(unfiltered.contains(r#""post"."published_at" IS NOT NULL"#));
+        assert!(unfiltered.contains(r#""post"."deleted_at" IS NULL"#));
+        for (request, fragment) in &cases {
+            assert!(!unfiltered.contains(fragment.as_str()));
+            let sql = search_sql(request);
+            assert!(
+                sql.contains(fragment.as_str()),
+                "{} missing from {}",
+                fragment,
+                sql
+            );
+            let others = cases
+                .iter()
+                .filter(|(_, other)| other != fragment)
+                .any(|(_, other)| sql.contains(other.as_str()));
+            assert!(!others, "unexpected extra filter in {}", sql);
+        }
+    }
+
+    #[test]
+    fn test_search_combines_filters_and_sorts() {
+        let author = Uuid::new_v4();
+        let sql = search_sql(
+            &PostSearchRequest::builder()
+                .title_contains(" rust ")
+                .author_id(author)
+                .sort_by(SortField::Views)
+                .sort_dir(SortDir::Asc)
+                .build(),
+        );
+
+        assert!(sql.contains(&format!(
+            r#""post"."title" LIKE '%rust%' AND "post"."user_id" = '{}'"#,
+            author
+        )));
+        assert!(sql.ends_with(r#"ORDER BY "post"."views" ASC, "post"."id" ASC"#));
+
+        let default_order = search_sql(&PostSearchRequest::builder().title_contains("x").build());
+        assert!(default_order.ends_with(r#"ORDER BY "post"."published_at" DESC, "post"."id" ASC"#));
+    }
 }
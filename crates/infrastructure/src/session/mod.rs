@@ -10,10 +10,16 @@
 
 use crate::entity::prelude::*;
 use crate::entity::session;
+use crate::error::DbResultExt;
+use crate::metrics;
 use async_trait::async_trait;
-use chrono::Utc;
-use domain::{Error, Result, Session};
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use chrono::{DateTime, Utc};
+use domain::{Result, Session};
+use sea_orm::prelude::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set,
+};
 use std::sync::Arc;
 
 /// Concrete implementation of SessionRepository
@@ -45,75 +51,196 @@ impl SessionRepositoryImpl {
 #[async_trait]
 impl domain::SessionRepository for SessionRepositoryImpl {
     /// Create a new session
-    async fn create_session(&self, user_id: uuid::Uuid, remember_me: bool) -> Result<Session> {
-        let session = if remember_me {
+    async fn create_session(
+        &self,
+        user_id: uuid::Uuid,
+        remember_me: bool,
+        user_agent: &str,
+        ip_address: &str,
+    ) -> Result<Session> {
+        let _timer = metrics::db_timer("session.create_session");
+        let mut session = if remember_me {
             Session::with_remember(user_id)
         } else {
             Session::new(user_id)
         };
+        session.user_agent = Some(user_agent.to_string());
+        session.ip_address = Some(ip_address.to_string());
 
         let active_model = session::ActiveModel {
             id: Set(session.id.clone()),
             user_id: Set(session.user_id.to_string()),
+            refresh_token: Set(Some(session.refresh_token.clone())),
+            revoked: Set(session.revoked),
+            user_agent: Set(session.user_agent.clone()),
+            ip_address: Set(session.ip_address.clone()),
             expires_at: Set(session.expires_at.to_rfc3339()),
+            refresh_at: Set(Some(session.refresh_at.to_rfc3339())),
             created_at: Set(session.created_at.to_rfc3339()),
         };
 
         active_model
             .insert(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create session: {}", e)))?;
+            .context("Failed to create session")?;
 
         Ok(session)
     }
 
     /// Get a session by token
     async fn get_session(&self, token: &str) -> Result<Option<Session>> {
+        let _timer = metrics::db_timer("session.get_session");
         let model = SessionEntity::find_by_id(token.to_string())
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get session: {}", e)))?;
-
-        Ok(model.map(|m| Session {
-            id: m.id,
-            user_id: uuid::Uuid::parse_str(&m.user_id).unwrap_or_else(|_| uuid::Uuid::new_v4()),
-            expires_at: m.expires_at.parse().unwrap_or_else(|_| chrono::Utc::now()),
-            created_at: m.created_at.parse().unwrap_or_else(|_| chrono::Utc::now()),
-        }))
+            .context("Failed to get session")?;
+
+        Ok(model.map(model_to_session))
+    }
+
+    /// Get a session by its refresh token
+    async fn get_session_by_refresh_token(&self, refresh_token: &str) -> Result<Option<Session>> {
+        let _timer = metrics::db_timer("session.get_session_by_refresh_token");
+        let model = SessionEntity::find()
+            .filter(session::Column::RefreshToken.eq(refresh_token))
+            .one(&*self.db)
+            .await
+            .context("Failed to get session")?;
+
+        Ok(model.map(model_to_session))
+    }
+
+    /// Mark the session owning a refresh token as revoked
+    async fn revoke_refresh_token(&self, refresh_token: &str) -> Result<()> {
+        let _timer = metrics::db_timer("session.revoke_refresh_token");
+        SessionEntity::update_many()
+            .col_expr(session::Column::Revoked, Expr::value(true))
+            .filter(session::Column::RefreshToken.eq(refresh_token))
+            .exec(&*self.db)
+            .await
+            .context("Failed to revoke refresh token")?;
+
+        Ok(())
+    }
+
+    /// Move a session's expiry and refresh point forward
+    async fn extend_session(
+        &self,
+        token: &str,
+        new_expires_at: DateTime<Utc>,
+        new_refresh_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let _timer = metrics::db_timer("session.extend_session");
+        SessionEntity::update_many()
+            .col_expr(
+                session::Column::ExpiresAt,
+                Expr::value(new_expires_at.to_rfc3339()),
+            )
+            .col_expr(
+                session::Column::RefreshAt,
+                Expr::value(new_refresh_at.to_rfc3339()),
+            )
+            .filter(session::Column::Id.eq(token))
+            .exec(&*self.db)
+            .await
+            .context("Failed to extend session")?;
+
+        Ok(())
     }
 
     /// Delete a session by token
     async fn delete_session(&self, token: &str) -> Result<()> {
+        let _timer = metrics::db_timer("session.delete_session");
         SessionEntity::delete_by_id(token.to_string())
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to delete session: {}", e)))?;
+            .context("Failed to delete session")?;
 
         Ok(())
     }
 
-    /// Delete all sessions for a user
-    async fn delete_user_sessions(&self, user_id: uuid::Uuid) -> Result<()> {
-        SessionEntity::delete_many()
+    /// List all sessions of a user, newest first
+    async fn list_user_sessions(&self, user_id: uuid::Uuid) -> Result<Vec<Session>> {
+        let _timer = metrics::db_timer("session.list_user_sessions");
+        let models = SessionEntity::find()
             .filter(session::Column::UserId.eq(user_id.to_string()))
+            .order_by_desc(session::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .context("Failed to list user sessions")?;
+
+        Ok(models.into_iter().map(model_to_session).collect())
+    }
+
+    /// Delete all sessions for a user, optionally keeping one
+    async fn delete_user_sessions(
+        &self,
+        user_id: uuid::Uuid,
+        except_session_id: Option<&str>,
+    ) -> Result<u64> {
+        let _timer = metrics::db_timer("session.delete_user_sessions");
+        let mut query =
+            SessionEntity::delete_many().filter(session::Column::UserId.eq(user_id.to_string()));
+        if let Some(session_id) = except_session_id {
+            query = query.filter(session::Column::Id.ne(session_id));
+        }
+        let result = query
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to delete user sessions: {}", e)))?;
+            .context("Failed to delete user sessions")?;
 
-        Ok(())
+        Ok(result.rows_affected)
     }
 
     /// Clean up expired sessions
     async fn cleanup_expired_sessions(&self) -> Result<u64> {
+        let _timer = metrics::db_timer("session.cleanup_expired_sessions");
         let now = Utc::now().to_rfc3339();
         let result = SessionEntity::delete_many()
             .filter(session::Column::ExpiresAt.lt(now))
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to cleanup expired sessions: {}", e)))?;
+            .context("Failed to cleanup expired sessions")?;
 
         Ok(result.rows_affected)
     }
+
+    /// Count sessions that are neither revoked nor expired
+    async fn count_active_sessions(&self) -> Result<u64> {
+        let _timer = metrics::db_timer("session.count_active_sessions");
+        Ok(SessionEntity::find()
+            .filter(session::Column::Revoked.eq(false))
+            .filter(session::Column::ExpiresAt.gt(Utc::now().to_rfc3339()))
+            .count(&*self.db)
+            .await
+            .context("Failed to count active sessions")?)
+    }
+}
+
+/// Convert a database model to a domain session
+///
+/// Sessions created before refresh tokens existed get an empty token,
+/// which never matches a refresh request. Sessions created before
+/// `refresh_at` existed refresh half way between creation and expiry.
+fn model_to_session(m: session::Model) -> Session {
+    let expires_at: DateTime<Utc> = m.expires_at.parse().unwrap_or_else(|_| Utc::now());
+    let created_at: DateTime<Utc> = m.created_at.parse().unwrap_or_else(|_| Utc::now());
+    let refresh_at = m
+        .refresh_at
+        .and_then(|refresh_at| refresh_at.parse().ok())
+        .unwrap_or_else(|| created_at + (expires_at - created_at) / 2);
+
+    Session {
+        id: m.id,
+        user_id: uuid::Uuid::parse_str(&m.user_id).unwrap_or_else(|_| uuid::Uuid::new_v4()),
+        refresh_token: m.refresh_token.unwrap_or_default(),
+        revoked: m.revoked,
+        user_agent: m.user_agent,
+        ip_address: m.ip_address,
+        expires_at,
+        refresh_at,
+        created_at,
+    }
 }
 
 #[cfg(test)]
@@ -9,11 +9,20 @@
 //! - No special cases
 
 use crate::entity::prelude::*;
-use crate::entity::{post_stats, stats};
+use crate::entity::{daily_stats, post_stats, post_view_log, stats};
+use crate::error::{DbResultExt, InfrastructureError};
+use crate::metrics;
 use async_trait::async_trait;
-use domain::{Error, PostStats, Result, StatsRepository, StatsResponse, VisitStats};
+use chrono::{Duration, NaiveDate, Utc};
+use domain::{
+    DailyStats, DailyTopPost, Error, PostStats, Result, StatsRepository, StatsResponse, VisitStats,
+};
 use sea_orm::prelude::Expr;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, Statement, TransactionTrait,
+};
 use std::sync::Arc;
 
 /// Concrete implementation of StatsRepository
@@ -46,28 +55,29 @@ impl StatsRepositoryImpl {
 impl StatsRepository for StatsRepositoryImpl {
     /// Get global visitor statistics
     async fn get_visit_stats(&self) -> Result<VisitStats> {
+        let _timer = metrics::db_timer("stats.get_visit_stats");
         let model = VisitStatsEntity::find_by_id(1)
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get visit stats: {}", e)))?
+            .context("Failed to get visit stats")?
             .ok_or_else(|| Error::Internal("Visit stats not found. Run migrations.".to_string()))?;
 
         Ok(VisitStats {
             total_visits: model.total_visits as u64,
             today_visits: model.today_visits as u64,
-            last_updated: model
-                .last_updated
-                .parse()
-                .map_err(|e| Error::Internal(format!("Invalid last_updated in database: {}", e)))?,
+            last_updated: model.last_updated.parse().map_err(|e| {
+                InfrastructureError::Serialize(format!("Invalid last_updated in database: {}", e))
+            })?,
         })
     }
 
     /// Increment visitor count
     async fn increment_visit(&self, is_today: bool) -> Result<()> {
+        let _timer = metrics::db_timer("stats.increment_visit");
         let stats = VisitStatsEntity::find_by_id(1)
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find visit stats: {}", e)))?
+            .context("Failed to find visit stats")?
             .ok_or_else(|| Error::Internal("Visit stats not found. Run migrations.".to_string()))?;
 
         let mut active_model: stats::ActiveModel = stats.into();
@@ -83,17 +93,18 @@ impl StatsRepository for StatsRepositoryImpl {
         active_model
             .update(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to increment visit stats: {}", e)))?;
+            .context("Failed to increment visit stats")?;
 
         Ok(())
     }
 
     /// Reset today's visit count
     async fn reset_today_visits(&self) -> Result<()> {
+        let _timer = metrics::db_timer("stats.reset_today_visits");
         let stats = VisitStatsEntity::find_by_id(1)
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find visit stats: {}", e)))?
+            .context("Failed to find visit stats")?
             .ok_or_else(|| Error::Internal("Visit stats not found. Run migrations.".to_string()))?;
 
         let mut active_model: stats::ActiveModel = stats.into();
@@ -103,26 +114,31 @@ impl StatsRepository for StatsRepositoryImpl {
         active_model
             .update(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to reset today's visits: {}", e)))?;
+            .context("Failed to reset today's visits")?;
 
         Ok(())
     }
 
     /// Get or create post statistics
     async fn get_or_create_post_stats(&self, post_id: uuid::Uuid) -> Result<PostStats> {
+        let _timer = metrics::db_timer("stats.get_or_create_post_stats");
         // Try to get existing stats
         if let Some(model) = PostStatsEntity::find()
             .filter(post_stats::Column::PostId.eq(post_id.to_string()))
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to get post stats: {}", e)))?
+            .context("Failed to get post stats")?
         {
             return Ok(PostStats {
-                post_id: uuid::Uuid::parse_str(&model.post_id)
-                    .map_err(|e| Error::Internal(format!("Invalid post_id in database: {}", e)))?,
+                post_id: uuid::Uuid::parse_str(&model.post_id).map_err(|e| {
+                    InfrastructureError::Serialize(format!("Invalid post_id in database: {}", e))
+                })?,
                 views: model.views as u64,
                 last_viewed_at: model.last_viewed_at.parse().map_err(|e| {
-                    Error::Internal(format!("Invalid last_viewed_at in database: {}", e))
+                    InfrastructureError::Serialize(format!(
+                        "Invalid last_viewed_at in database: {}",
+                        e
+                    ))
                 })?,
             });
         }
@@ -139,13 +155,14 @@ impl StatsRepository for StatsRepositoryImpl {
         active_model
             .insert(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to create post stats: {}", e)))?;
+            .context("Failed to create post stats")?;
 
         Ok(new_stats)
     }
 
     /// Increment post view count
     async fn increment_post_view(&self, post_id: uuid::Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("stats.increment_post_view");
         // Ensure stats record exists
         let _stats = self.get_or_create_post_stats(post_id).await?;
 
@@ -158,14 +175,14 @@ impl StatsRepository for StatsRepositoryImpl {
             )
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to increment post view: {}", e)))?;
+            .context("Failed to increment post view")?;
 
         // Update last_viewed_at
         let model = PostStatsEntity::find()
             .filter(post_stats::Column::PostId.eq(post_id.to_string()))
             .one(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find post stats: {}", e)))?
+            .context("Failed to find post stats")?
             .ok_or_else(|| Error::Internal("Post stats not found after increment".to_string()))?;
 
         let mut active_model: post_stats::ActiveModel = model.into();
@@ -174,7 +191,7 @@ impl StatsRepository for StatsRepositoryImpl {
         active_model
             .update(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to update post stats: {}", e)))?;
+            .context("Failed to update post stats")?;
 
         // Also increment views in post table for consistency
         use crate::entity::post;
@@ -183,26 +200,118 @@ impl StatsRepository for StatsRepositoryImpl {
             .col_expr(post::Column::Views, Expr::col(post::Column::Views).add(1))
             .exec(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to increment post views: {}", e)))?;
+            .context("Failed to increment post views")?;
 
         Ok(())
     }
 
+    /// Zero a post's views in post_stats and on the post row
+    async fn reset_post_stats(&self, post_id: uuid::Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("stats.reset_post_stats");
+        use crate::entity::post;
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        PostStatsEntity::update_many()
+            .filter(post_stats::Column::PostId.eq(post_id.to_string()))
+            .col_expr(post_stats::Column::Views, Expr::value(0))
+            .col_expr(
+                post_stats::Column::LastViewedAt,
+                Expr::value(Utc::now().to_rfc3339()),
+            )
+            .exec(&txn)
+            .await
+            .context("Failed to reset post stats")?;
+        PostEntity::update_many()
+            .filter(post::Column::Id.eq(post_id.to_string()))
+            .col_expr(post::Column::Views, Expr::value(0))
+            .exec(&txn)
+            .await
+            .context("Failed to reset post views")?;
+
+        Ok(txn.commit().await.context("Failed to commit transaction")?)
+    }
+
+    /// Zero the views of every post, one statement per table
+    async fn reset_all_post_stats(&self) -> Result<u64> {
+        let _timer = metrics::db_timer("stats.reset_all_post_stats");
+        use crate::entity::post;
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let result = PostStatsEntity::update_many()
+            .col_expr(post_stats::Column::Views, Expr::value(0))
+            .exec(&txn)
+            .await
+            .context("Failed to reset post stats")?;
+        PostEntity::update_many()
+            .col_expr(post::Column::Views, Expr::value(0))
+            .exec(&txn)
+            .await
+            .context("Failed to reset post views")?;
+
+        txn.commit().await.context("Failed to commit transaction")?;
+        Ok(result.rows_affected)
+    }
+
+    /// Log a visitor's view, refreshing it only if older than `since`
+    async fn log_post_view(
+        &self,
+        post_id: uuid::Uuid,
+        visitor_hash: &str,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool> {
+        let _timer = metrics::db_timer("stats.log_post_view");
+        let view = post_view_log::ActiveModel {
+            post_id: Set(post_id.to_string()),
+            visitor_hash: Set(visitor_hash.to_string()),
+            viewed_at: Set(chrono::Utc::now().to_rfc3339()),
+        };
+
+        // A recent existing row blocks the update, so no rows are affected
+        let affected = post_view_log::Entity::insert(view)
+            .on_conflict(
+                OnConflict::columns([
+                    post_view_log::Column::PostId,
+                    post_view_log::Column::VisitorHash,
+                ])
+                .update_column(post_view_log::Column::ViewedAt)
+                .action_and_where(
+                    Expr::col((post_view_log::Entity, post_view_log::Column::ViewedAt))
+                        .lt(since.to_rfc3339()),
+                )
+                .to_owned(),
+            )
+            .exec_without_returning(&*self.db)
+            .await
+            .context("Failed to log post view")?;
+
+        Ok(affected > 0)
+    }
+
     /// Get total statistics (admin only)
     async fn get_total_stats(&self) -> Result<StatsResponse> {
+        let _timer = metrics::db_timer("stats.get_total_stats");
         let visit_stats = self.get_visit_stats().await?;
 
         // Get total posts count
         let total_posts = PostEntity::find()
+            .filter(crate::entity::post::Column::DeletedAt.is_null())
             .count(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to count posts: {}", e)))?;
+            .context("Failed to count posts")?;
 
         // Get total comments count
         let total_comments = CommentEntity::find()
             .count(&*self.db)
             .await
-            .map_err(|e| Error::Internal(format!("Failed to count comments: {}", e)))?;
+            .context("Failed to count comments")?;
 
         Ok(StatsResponse {
             total_visits: visit_stats.total_visits,
@@ -211,6 +320,169 @@ impl StatsRepository for StatsRepositoryImpl {
             total_comments,
         })
     }
+
+    /// Get per-day visit counts, oldest first
+    async fn get_daily_stats(&self, days: u32) -> Result<Vec<DailyStats>> {
+        let _timer = metrics::db_timer("stats.get_daily_stats");
+        let first_day = Utc::now().date_naive() - Duration::days(i64::from(days) - 1);
+
+        // `YYYY-MM-DD` strings sort chronologically
+        let models = DailyStatsEntity::find()
+            .filter(daily_stats::Column::Date.gte(first_day.format("%Y-%m-%d").to_string()))
+            .order_by_asc(daily_stats::Column::Date)
+            .all(&*self.db)
+            .await
+            .context("Failed to get daily stats")?;
+
+        Ok(models
+            .into_iter()
+            .filter_map(|m| {
+                Some(DailyStats {
+                    date: NaiveDate::parse_from_str(&m.date, "%Y-%m-%d").ok()?,
+                    visits: m.visits.max(0) as u64,
+                })
+            })
+            .collect())
+    }
+
+    /// Get per-day visit counts in an inclusive date range, oldest first
+    async fn get_daily_stats_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyStats>> {
+        let _timer = metrics::db_timer("stats.get_daily_stats_between");
+        let models = DailyStatsEntity::find()
+            .filter(daily_stats::Column::Date.gte(start.format("%Y-%m-%d").to_string()))
+            .filter(daily_stats::Column::Date.lte(end.format("%Y-%m-%d").to_string()))
+            .order_by_asc(daily_stats::Column::Date)
+            .all(&*self.db)
+            .await
+            .context("Failed to get daily stats")?;
+
+        Ok(models
+            .into_iter()
+            .filter_map(|m| {
+                Some(DailyStats {
+                    date: NaiveDate::parse_from_str(&m.date, "%Y-%m-%d").ok()?,
+                    visits: m.visits.max(0) as u64,
+                })
+            })
+            .collect())
+    }
+
+    /// Rank each day's logged post views and keep the first post per day
+    async fn get_daily_top_posts(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyTopPost>> {
+        let _timer = metrics::db_timer("stats.get_daily_top_posts");
+        // viewed_at is RFC 3339 text, so its first ten characters are the UTC date
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            "SELECT day, post_id, title, views FROM ( \
+               SELECT substr(post_view_log.viewed_at, 1, 10) AS day, \
+                      post_view_log.post_id, post.title, COUNT(*) AS views, \
+                      ROW_NUMBER() OVER ( \
+                        PARTITION BY substr(post_view_log.viewed_at, 1, 10) \
+                        ORDER BY COUNT(*) DESC, post_view_log.post_id \
+                      ) AS day_rank \
+               FROM post_view_log \
+               JOIN post ON post.id = post_view_log.post_id \
+               WHERE post_view_log.viewed_at >= $1 AND post_view_log.viewed_at < $2 \
+                 AND post.deleted_at IS NULL \
+               GROUP BY 1, post_view_log.post_id, post.title \
+             ) ranked WHERE day_rank = 1 ORDER BY day",
+            [
+                start.format("%Y-%m-%d").to_string().into(),
+                (end + Duration::days(1))
+                    .format("%Y-%m-%d")
+                    .to_string()
+                    .into(),
+            ],
+        );
+
+        let rows = self
+            .db
+            .query_all(statement)
+            .await
+            .context("Failed to get daily top posts")?;
+
+        rows.iter()
+            .map(|row| {
+                let invalid = |e| Error::Internal(format!("Invalid daily top post row: {}", e));
+                let day: String = row.try_get("", "day").map_err(invalid)?;
+                let post_id: String = row.try_get("", "post_id").map_err(invalid)?;
+                let title: String = row.try_get("", "title").map_err(invalid)?;
+                let views: i64 = row.try_get("", "views").map_err(invalid)?;
+                Ok(DailyTopPost {
+                    date: NaiveDate::parse_from_str(&day, "%Y-%m-%d").map_err(|e| {
+                        InfrastructureError::Serialize(format!("Invalid view date: {}", e))
+                    })?,
+                    post_id: uuid::Uuid::parse_str(&post_id).map_err(|e| {
+                        InfrastructureError::Serialize(format!(
+                            "Invalid post_id in database: {}",
+                            e
+                        ))
+                    })?,
+                    title,
+                    views: views.max(0) as u64,
+                })
+            })
+            .collect()
+    }
+
+    /// Upsert the day's row, incrementing its visit count
+    async fn record_daily_visit(&self, date: &str) -> Result<()> {
+        let _timer = metrics::db_timer("stats.record_daily_visit");
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            "INSERT INTO daily_stats (date, visits) VALUES ($1, 1) \
+             ON CONFLICT(date) DO UPDATE SET visits = daily_stats.visits + 1",
+            [date.into()],
+        );
+
+        self.db
+            .execute(statement)
+            .await
+            .context("Failed to record daily visit")?;
+
+        Ok(())
+    }
+
+    /// Most viewed posts, skipping drafts and deleted posts
+    async fn get_top_posts(&self, limit: u64) -> Result<Vec<(uuid::Uuid, u64)>> {
+        let _timer = metrics::db_timer("stats.get_top_posts");
+        let statement = Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            "SELECT post_stats.post_id, post_stats.views FROM post_stats \
+             JOIN post ON post.id = post_stats.post_id \
+             WHERE post.published_at IS NOT NULL AND post.deleted_at IS NULL \
+             ORDER BY post_stats.views DESC, post.published_at DESC \
+             LIMIT $1",
+            [(limit as i64).into()],
+        );
+
+        let rows = self
+            .db
+            .query_all(statement)
+            .await
+            .context("Failed to get top posts")?;
+
+        rows.iter()
+            .map(|row| {
+                let post_id: String = row
+                    .try_get("", "post_id")
+                    .context("Invalid post stats row")?;
+                let views: i64 = row.try_get("", "views").context("Invalid post stats row")?;
+                let post_id = uuid::Uuid::parse_str(&post_id).map_err(|e| {
+                    InfrastructureError::Serialize(format!("Invalid post_id in database: {}", e))
+                })?;
+                Ok((post_id, views.max(0) as u64))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
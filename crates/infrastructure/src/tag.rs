@@ -1,10 +1,57 @@
+use crate::error::{DbResultExt, InfrastructureError};
+use crate::metrics;
 use async_trait::async_trait;
-use domain::{Result, Tag, TagRepository};
+use domain::{Result, Tag, TagCloudEntry, TagRepository};
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::OnConflict;
 use sea_orm::*;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::entity::tag;
+use crate::entity::{post_tag, tag};
+
+/// Tags with the number of live published posts carrying them
+///
+/// The inner join on `post` drops tags that only appear on drafts or
+/// deleted posts.
+const TAG_CLOUD_SQL: &str = r#"
+SELECT t.id, t.name, t.slug, t.created_at, COUNT(pt.post_id) AS post_count
+FROM tag t
+JOIN post_tag pt ON pt.tag_id = t.id
+JOIN post p ON p.id = pt.post_id
+WHERE p.published_at IS NOT NULL AND p.deleted_at IS NULL
+GROUP BY t.id, t.name, t.slug, t.created_at
+ORDER BY post_count DESC, t.name ASC
+"#;
+
+/// Row returned by [`TAG_CLOUD_SQL`]
+#[derive(Debug, FromQueryResult)]
+struct TagCloudRow {
+    id: String,
+    name: String,
+    slug: String,
+    created_at: String,
+    post_count: i64,
+}
+
+/// Insert a tag, turning a slug collision into a no-op update
+///
+/// `DO UPDATE` rather than `DO NOTHING` so that `RETURNING` yields the
+/// existing row, including one a concurrent transaction just committed.
+fn tag_upsert_query(name: &str, slug: &str) -> Insert<tag::ActiveModel> {
+    let tag = tag::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        name: Set(name.to_string()),
+        slug: Set(slug.to_string()),
+        created_at: Set(chrono::Utc::now().to_rfc3339()),
+    };
+
+    tag::Entity::insert(tag).on_conflict(
+        OnConflict::column(tag::Column::Slug)
+            .update_column(tag::Column::Slug)
+            .to_owned(),
+    )
+}
 
 pub struct TagRepositoryImpl {
     db: Arc<DatabaseConnection>,
@@ -38,6 +85,7 @@ impl TagRepositoryImpl {
 #[async_trait]
 impl TagRepository for TagRepositoryImpl {
     async fn create_tag(&self, name: String, slug: String) -> Result<Tag> {
+        let _timer = metrics::db_timer("tag.create_tag");
         let tag = tag::ActiveModel {
             id: Set(Uuid::new_v4().to_string()),
             name: Set(name),
@@ -45,52 +93,166 @@ impl TagRepository for TagRepositoryImpl {
             created_at: Set(chrono::Utc::now().to_rfc3339()),
         };
 
-        let result = tag.insert(self.db.as_ref()).await.map_err(|e| match e {
-            DbErr::Custom(err) if err.contains("UNIQUE") => {
-                domain::Error::Validation("Tag already exists".to_string())
-            }
-            _ => domain::Error::Internal(e.to_string()),
-        })?;
+        let result =
+            tag.insert(self.db.as_ref()).await.map_err(|e| {
+                match InfrastructureError::db("Failed to create tag", e) {
+                    InfrastructureError::Constraint(_) => {
+                        domain::Error::Validation("Tag already exists".to_string())
+                    }
+                    other => other.into(),
+                }
+            })?;
 
         Ok(Self::entity_to_domain(result))
     }
 
     async fn get_tag(&self, id: Uuid) -> Result<Option<Tag>> {
+        let _timer = metrics::db_timer("tag.get_tag");
         let result = tag::Entity::find_by_id(id.to_string())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(result.map(Self::entity_to_domain))
     }
 
     async fn get_tag_by_slug(&self, slug: &str) -> Result<Option<Tag>> {
+        let _timer = metrics::db_timer("tag.get_tag_by_slug");
         let result = tag::Entity::find()
             .filter(tag::Column::Slug.eq(slug))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(result.map(Self::entity_to_domain))
     }
 
+    async fn upsert_tag(&self, name: &str, slug: &str) -> Result<Tag> {
+        let _timer = metrics::db_timer("tag.upsert_tag");
+        let result = tag_upsert_query(name, slug)
+            .exec_with_returning(self.db.as_ref())
+            .await
+            .map_err(|e| match e.sql_err() {
+                // The slug is free but another tag already has the name
+                Some(SqlErr::UniqueConstraintViolation(_)) => domain::Error::Conflict(format!(
+                    "A tag named '{}' already exists with a different slug",
+                    name
+                )),
+                _ => domain::Error::Internal(e.to_string()),
+            })?;
+
+        Ok(Self::entity_to_domain(result))
+    }
+
     async fn list_tags(&self) -> Result<Vec<Tag>> {
+        let _timer = metrics::db_timer("tag.list_tags");
         let result = tag::Entity::find()
             .all(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(result.into_iter().map(Self::entity_to_domain).collect())
     }
 
+    async fn update_tag(&self, id: Uuid, name: String, slug: String) -> Result<Tag> {
+        let _timer = metrics::db_timer("tag.update_tag");
+        let mut tag: tag::ActiveModel = tag::Entity::find_by_id(id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find tag")?
+            .ok_or_else(|| domain::Error::NotFound("Tag not found".to_string()))?
+            .into();
+
+        tag.name = Set(name);
+        tag.slug = Set(slug);
+
+        let result =
+            tag.update(self.db.as_ref()).await.map_err(|e| {
+                match InfrastructureError::db("Failed to update tag", e) {
+                    InfrastructureError::Constraint(_) => {
+                        domain::Error::Conflict("Tag already exists".to_string())
+                    }
+                    other => other.into(),
+                }
+            })?;
+
+        Ok(Self::entity_to_domain(result))
+    }
+
     async fn delete_tag(&self, id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("tag.delete_tag");
         tag::Entity::delete_by_id(id.to_string())
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| domain::Error::Internal(e.to_string()))?;
+            .map_err(InfrastructureError::from)?;
 
         Ok(())
     }
+
+    async fn merge_tags(&self, source_id: Uuid, target_id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("tag.merge_tags");
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        // Posts tagged with both would end up with a duplicate primary key
+        let tagged_with_target: Vec<String> = post_tag::Entity::find()
+            .select_only()
+            .column(post_tag::Column::PostId)
+            .filter(post_tag::Column::TagId.eq(target_id.to_string()))
+            .into_tuple()
+            .all(&txn)
+            .await
+            .map_err(InfrastructureError::from)?;
+
+        post_tag::Entity::delete_many()
+            .filter(post_tag::Column::TagId.eq(source_id.to_string()))
+            .filter(post_tag::Column::PostId.is_in(tagged_with_target))
+            .exec(&txn)
+            .await
+            .map_err(InfrastructureError::from)?;
+
+        post_tag::Entity::update_many()
+            .col_expr(post_tag::Column::TagId, Expr::value(target_id.to_string()))
+            .filter(post_tag::Column::TagId.eq(source_id.to_string()))
+            .exec(&txn)
+            .await
+            .map_err(InfrastructureError::from)?;
+
+        tag::Entity::delete_by_id(source_id.to_string())
+            .exec(&txn)
+            .await
+            .map_err(InfrastructureError::from)?;
+
+        Ok(txn.commit().await.context("Failed to commit transaction")?)
+    }
+
+    async fn get_tag_cloud(&self) -> Result<Vec<TagCloudEntry>> {
+        let _timer = metrics::db_timer("tag.get_tag_cloud");
+        let rows = TagCloudRow::find_by_statement(Statement::from_string(
+            self.db.get_database_backend(),
+            TAG_CLOUD_SQL,
+        ))
+        .all(self.db.as_ref())
+        .await
+        .context("Failed to load tag cloud")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TagCloudEntry {
+                tag: Self::entity_to_domain(tag::Model {
+                    id: row.id,
+                    name: row.name,
+                    slug: row.slug,
+                    created_at: row.created_at,
+                }),
+                post_count: row.post_count.max(0) as u64,
+                normalized_weight: 0.0,
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -122,6 +284,17 @@ mod tests {
         assert_eq!(domain.slug, entity.slug);
     }
 
+    #[test]
+    fn test_tag_upsert_query_returns_row_on_slug_conflict() {
+        let sql = tag_upsert_query("Rust", "rust")
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(sql.starts_with(r#"INSERT INTO "tag""#));
+        assert!(sql.contains(r#"'Rust', 'rust'"#));
+        assert!(sql.ends_with(r#"ON CONFLICT ("slug") DO UPDATE SET "slug" = "excluded"."slug""#));
+    }
+
     #[tokio::test]
     async fn test_tag_unique_id() {
         // Verify each tag gets a unique ID
@@ -141,6 +314,19 @@ mod tests {
         assert!(tag.created_at <= after);
     }
 
+    #[test]
+    fn test_tag_cloud_counts_only_published_posts() {
+        let sql = TAG_CLOUD_SQL
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        assert!(sql.contains("JOIN post p ON p.id = pt.post_id"));
+        assert!(sql.contains("WHERE p.published_at IS NOT NULL AND p.deleted_at IS NULL"));
+        assert!(!sql.contains("LEFT JOIN"));
+        assert!(sql.contains("ORDER BY post_count DESC"));
+    }
+
     #[test]
     fn test_tag_repository_impl_clone() {
         // This test verifies that TagRepositoryImpl implements Clone
@@ -1,16 +1,42 @@
+use crate::error::{DbResultExt, InfrastructureError};
+use crate::metrics;
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
 use async_trait::async_trait;
-use domain::{Error, Result, User, UserRepository};
+use domain::{Error, ErrorCode, Result, User, UserChanges, UserRepository};
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::LikeExpr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select, Set, TransactionTrait,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Users whose username contains `query`, alphabetically
+///
+/// `%` and `_` in the query match literally.
+fn user_search_query(query: &str) -> Select<crate::entity::user::Entity> {
+    let pattern = LikeExpr::new(format!("%{}%", escape_like(query))).escape('\\');
+    crate::entity::user::Entity::find()
+        .filter(crate::entity::user::Column::Username.like(pattern))
+        .order_by_asc(crate::entity::user::Column::Username)
+}
+
+/// Escape the `LIKE` wildcards and the `\` escape character itself
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 pub struct UserRepositoryImpl {
     db: Arc<DatabaseConnection>,
 }
@@ -36,29 +62,41 @@ impl UserRepositoryImpl {
             .map(|hash| hash.to_string())
             .map_err(|e| Error::Internal(format!("Failed to hash password: {}", e)))
     }
+}
 
-    fn verify_password(&self, password: &str, hash: &str) -> Result<bool> {
-        let parsed_hash = PasswordHash::new(hash)
-            .map_err(|e| Error::Internal(format!("Invalid password hash: {}", e)))?;
-        let argon2 = Argon2::default();
-        argon2
-            .verify_password(password.as_bytes(), &parsed_hash)
-            .map_err(|_| Error::Internal("Password verification failed".to_string()))?;
-        Ok(true)
+/// Check a password against a stored hash
+///
+/// A wrong password is `Ok(false)`; only a malformed hash or a hasher
+/// failure is an error.
+fn verify_password(password: &str, hash: &str) -> Result<bool> {
+    let parsed_hash = PasswordHash::new(hash)
+        .map_err(|e| Error::Internal(format!("Invalid password hash: {}", e)))?;
+    match Argon2::default().verify_password(password.as_bytes(), &parsed_hash) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(Error::Internal(format!(
+            "Password verification failed: {}",
+            e
+        ))),
     }
 }
 
 fn parse_datetime(dt_str: &str) -> Result<chrono::DateTime<chrono::Utc>> {
     chrono::DateTime::parse_from_rfc3339(dt_str)
         .map(|dt| dt.with_timezone(&chrono::Utc))
-        .map_err(|e| Error::Internal(format!("Invalid datetime: {}", e)))
+        .map_err(|e| InfrastructureError::Serialize(format!("Invalid datetime: {}", e)).into())
 }
 
 fn model_to_user(model: crate::entity::user::Model) -> Result<User> {
     let id = uuid::Uuid::parse_str(&model.id)
-        .map_err(|e| Error::Internal(format!("Invalid user id: {}", e)))?;
+        .map_err(|e| InfrastructureError::Serialize(format!("Invalid user id: {}", e)))?;
 
     let created_at = parse_datetime(&model.created_at)?;
+    let password_reset_expires_at = model
+        .password_reset_expires_at
+        .as_deref()
+        .map(parse_datetime)
+        .transpose()?;
 
     Ok(User {
         id,
@@ -66,6 +104,16 @@ fn model_to_user(model: crate::entity::user::Model) -> Result<User> {
         password_hash: model.password_hash,
         permissions: model.permissions as u64,
         created_at,
+        totp_secret: model.totp_secret,
+        totp_enabled: model.totp_enabled != 0,
+        totp_last_step: model.totp_last_step.map(|step| step as u64),
+        email: model.email,
+        email_verified: model.email_verified != 0,
+        email_verification_token: model.email_verification_token,
+        password_reset_token: model.password_reset_token,
+        password_reset_expires_at,
+        bio: model.bio,
+        avatar_url: model.avatar_url,
     })
 }
 
@@ -77,37 +125,42 @@ impl UserRepository for UserRepositoryImpl {
         password: String,
         permissions: u64,
     ) -> Result<User> {
+        let _timer = metrics::db_timer("user.create_user");
         let password_hash = self.hash_password(&password)?;
         let user_id = Uuid::new_v4();
         let created_at = chrono::Utc::now();
 
-        crate::entity::user::ActiveModel {
+        let model = crate::entity::user::ActiveModel {
             id: Set(user_id.to_string()),
-            username: Set(username.clone()),
-            password_hash: Set(password_hash.clone()),
+            username: Set(username),
+            password_hash: Set(password_hash),
             permissions: Set(permissions as i64),
             created_at: Set(created_at.to_rfc3339()),
+            totp_secret: Set(None),
+            totp_enabled: Set(0),
+            totp_last_step: Set(None),
+            email: Set(None),
+            email_verified: Set(0),
+            email_verification_token: Set(None),
+            password_reset_token: Set(None),
+            password_reset_expires_at: Set(None),
+            bio: Set(None),
+            avatar_url: Set(None),
         }
         .insert(self.db.as_ref())
         .await
-        .map_err(|e| Error::Internal(format!("Failed to create user: {}", e)))?;
+        .context("Failed to create user")?;
 
-        // Return User object directly since we have all the data
-        Ok(User {
-            id: user_id,
-            username,
-            password_hash,
-            permissions,
-            created_at,
-        })
+        model_to_user(model)
     }
 
     async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
+        let _timer = metrics::db_timer("user.find_by_username");
         let model = crate::entity::user::Entity::find()
             .filter(crate::entity::user::Column::Username.eq(username))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find user: {}", e)))?;
+            .context("Failed to find user")?;
 
         match model {
             Some(model) => Ok(Some(model_to_user(model)?)),
@@ -116,11 +169,12 @@ impl UserRepository for UserRepositoryImpl {
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>> {
+        let _timer = metrics::db_timer("user.find_by_id");
         let model = crate::entity::user::Entity::find()
             .filter(crate::entity::user::Column::Id.eq(id.to_string()))
             .one(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find user: {}", e)))?;
+            .context("Failed to find user")?;
 
         match model {
             Some(model) => Ok(Some(model_to_user(model)?)),
@@ -129,11 +183,12 @@ impl UserRepository for UserRepositoryImpl {
     }
 
     async fn verify_credentials(&self, username: &str, password: &str) -> Result<Option<User>> {
+        let _timer = metrics::db_timer("user.verify_credentials");
         let user = self.find_by_username(username).await?;
 
         match user {
             Some(user) => {
-                let valid = self.verify_password(password, &user.password_hash)?;
+                let valid = verify_password(password, &user.password_hash)?;
                 if valid {
                     Ok(Some(user))
                 } else {
@@ -145,78 +200,454 @@ impl UserRepository for UserRepositoryImpl {
     }
 
     async fn update_permissions(&self, user_id: Uuid, permissions: u64) -> Result<User> {
+        let _timer = metrics::db_timer("user.update_permissions");
         let model = crate::entity::user::Entity::find_by_id(user_id.to_string())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find user: {}", e)))?
-            .ok_or_else(|| Error::NotFound(format!("User with id {} not found", user_id)))?;
+            .context("Failed to find user")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::UserNotFound,
+                    format!("User with id {} not found", user_id),
+                )
+            })?;
 
-        let active_model = crate::entity::user::ActiveModel {
-            id: Set(model.id),
-            username: Set(model.username),
-            password_hash: Set(model.password_hash),
-            permissions: Set(permissions as i64),
-            created_at: Set(model.created_at),
-        };
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        active_model.permissions = Set(permissions as i64);
 
         let updated_model = active_model
             .update(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to update user: {}", e)))?;
+            .context("Failed to update user")?;
+
+        model_to_user(updated_model)
+    }
+
+    async fn update_username(&self, user_id: Uuid, new_username: String) -> Result<User> {
+        let _timer = metrics::db_timer("user.update_username");
+        let model = crate::entity::user::Entity::find_by_id(user_id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find user")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::UserNotFound,
+                    format!("User with id {} not found", user_id),
+                )
+            })?;
+
+        let old_username = model.username.clone();
+
+        // Rename and audit entry are written atomically
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        active_model.username = Set(new_username.clone());
+
+        let updated_model = active_model
+            .update(&txn)
+            .await
+            .context("Failed to update username")?;
+
+        crate::entity::user_audit_log::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            user_id: Set(user_id.to_string()),
+            action: Set("username_changed".to_string()),
+            detail: Set(Some(format!("{} -> {}", old_username, new_username))),
+            created_at: Set(chrono::Utc::now().to_rfc3339()),
+        }
+        .insert(&txn)
+        .await
+        .context("Failed to write audit log")?;
+
+        txn.commit().await.context("Failed to commit transaction")?;
 
         model_to_user(updated_model)
     }
 
+    async fn add_audit_log(
+        &self,
+        user_id: Uuid,
+        action: &str,
+        detail: Option<String>,
+    ) -> Result<()> {
+        let _timer = metrics::db_timer("user.add_audit_log");
+        crate::entity::user_audit_log::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            user_id: Set(user_id.to_string()),
+            action: Set(action.to_string()),
+            detail: Set(detail),
+            created_at: Set(chrono::Utc::now().to_rfc3339()),
+        }
+        .insert(self.db.as_ref())
+        .await
+        .context("Failed to write audit log")?;
+
+        Ok(())
+    }
+
     async fn list_users(&self, limit: u64) -> Result<Vec<User>> {
+        let _timer = metrics::db_timer("user.list_users");
         let models = crate::entity::user::Entity::find()
             .order_by_asc(crate::entity::user::Column::CreatedAt)
             .limit(limit)
             .all(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to list users: {}", e)))?;
+            .context("Failed to list users")?;
 
         models.into_iter().map(model_to_user).collect()
     }
 
+    async fn search_users(&self, query: &str, limit: u64, offset: u64) -> Result<(Vec<User>, u64)> {
+        let _timer = metrics::db_timer("user.search_users");
+        let total = user_search_query(query)
+            .count(self.db.as_ref())
+            .await
+            .context("Failed to count users")?;
+
+        let models = user_search_query(query)
+            .offset(offset)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await
+            .context("Failed to search users")?;
+
+        Ok((
+            models
+                .into_iter()
+                .map(model_to_user)
+                .collect::<Result<_>>()?,
+            total,
+        ))
+    }
+
     async fn update_password(&self, user_id: Uuid, new_password: String) -> Result<()> {
+        let _timer = metrics::db_timer("user.update_password");
         // Load user and check existence in one query
         let model = crate::entity::user::Entity::find_by_id(user_id.to_string())
             .one(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to find user: {}", e)))?
-            .ok_or_else(|| Error::NotFound(format!("User with id {} not found", user_id)))?;
+            .context("Failed to find user")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::UserNotFound,
+                    format!("User with id {} not found", user_id),
+                )
+            })?;
 
         // Hash new password and update
         let password_hash = self.hash_password(&new_password)?;
 
-        crate::entity::user::ActiveModel {
-            id: Set(model.id),
-            username: Set(model.username),
-            password_hash: Set(password_hash),
-            permissions: Set(model.permissions),
-            created_at: Set(model.created_at),
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        active_model.password_hash = Set(password_hash);
+
+        active_model
+            .update(self.db.as_ref())
+            .await
+            .context("Failed to update password")?;
+
+        Ok(())
+    }
+
+    async fn update_totp(
+        &self,
+        user_id: Uuid,
+        secret: Option<String>,
+        enabled: bool,
+    ) -> Result<()> {
+        let _timer = metrics::db_timer("user.update_totp");
+        let model = crate::entity::user::Entity::find_by_id(user_id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find user")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::UserNotFound,
+                    format!("User with id {} not found", user_id),
+                )
+            })?;
+
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        active_model.totp_secret = Set(secret);
+        active_model.totp_enabled = Set(enabled as i64);
+
+        active_model
+            .update(self.db.as_ref())
+            .await
+            .context("Failed to update TOTP settings")?;
+
+        Ok(())
+    }
+
+    async fn update_totp_last_step(&self, user_id: Uuid, step: u64) -> Result<bool> {
+        let _timer = metrics::db_timer("user.update_totp_last_step");
+        let result = crate::entity::user::Entity::update_many()
+            .col_expr(
+                crate::entity::user::Column::TotpLastStep,
+                Expr::value(step as i64),
+            )
+            .filter(crate::entity::user::Column::Id.eq(user_id.to_string()))
+            .filter(
+                Condition::any()
+                    .add(crate::entity::user::Column::TotpLastStep.is_null())
+                    .add(crate::entity::user::Column::TotpLastStep.lt(step as i64)),
+            )
+            .exec(self.db.as_ref())
+            .await
+            .context("Failed to record TOTP step")?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>> {
+        let _timer = metrics::db_timer("user.find_by_email");
+        let model = crate::entity::user::Entity::find()
+            .filter(crate::entity::user::Column::Email.eq(email))
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find user")?;
+
+        model.map(model_to_user).transpose()
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: Uuid,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+    ) -> Result<User> {
+        let _timer = metrics::db_timer("user.update_profile");
+        let model = crate::entity::user::Entity::find_by_id(user_id.to_string())
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find user")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::UserNotFound,
+                    format!("User with id {} not found", user_id),
+                )
+            })?;
+
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        active_model.bio = Set(bio);
+        active_model.avatar_url = Set(avatar_url);
+
+        let updated_model = active_model
+            .update(self.db.as_ref())
+            .await
+            .context("Failed to update profile")?;
+
+        model_to_user(updated_model)
+    }
+
+    async fn update_user(&self, user_id: Uuid, changes: UserChanges) -> Result<User> {
+        let _timer = metrics::db_timer("user.update_user");
+        let txn = self
+            .db
+            .begin()
+            .await
+            .context("Failed to start transaction")?;
+
+        let model = crate::entity::user::Entity::find_by_id(user_id.to_string())
+            .one(&txn)
+            .await
+            .context("Failed to find user")?
+            .ok_or_else(|| {
+                Error::coded(
+                    ErrorCode::UserNotFound,
+                    format!("User with id {} not found", user_id),
+                )
+            })?;
+        let old_username = model.username.clone();
+
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        if let Some(username) = &changes.username {
+            active_model.username = Set(username.clone());
+        }
+        if let Some(permissions) = changes.permissions {
+            active_model.permissions = Set(permissions as i64);
+        }
+        if let Some(bio) = changes.bio {
+            active_model.bio = Set(bio);
+        }
+        if let Some(avatar_url) = changes.avatar_url {
+            active_model.avatar_url = Set(avatar_url);
         }
-        .update(self.db.as_ref())
-        .await
-        .map_err(|e| Error::Internal(format!("Failed to update password: {}", e)))?;
 
+        let updated_model = active_model.update(&txn).await.map_err(|e| {
+            match InfrastructureError::db("Failed to update user", e) {
+                InfrastructureError::Constraint(_) => {
+                    Error::coded(ErrorCode::UsernameExists, "Username already exists")
+                        .with_field("username")
+                }
+                other => other.into(),
+            }
+        })?;
+
+        if let Some(username) = changes.username.filter(|name| *name != old_username) {
+            crate::entity::user_audit_log::ActiveModel {
+                id: Set(Uuid::new_v4().to_string()),
+                user_id: Set(user_id.to_string()),
+                action: Set("username_changed".to_string()),
+                detail: Set(Some(format!("{} -> {}", old_username, username))),
+                created_at: Set(chrono::Utc::now().to_rfc3339()),
+            }
+            .insert(&txn)
+            .await
+            .context("Failed to write audit log")?;
+        }
+
+        txn.commit().await.context("Failed to commit transaction")?;
+
+        model_to_user(updated_model)
+    }
+
+    async fn update_email(
+        &self,
+        user_id: Uuid,
+        email: String,
+        verification_token: String,
+    ) -> Result<()> {
+        let _timer = metrics::db_timer("user.update_email");
+        let result = crate::entity::user::Entity::update_many()
+            .col_expr(crate::entity::user::Column::Email, Expr::value(email))
+            .col_expr(
+                crate::entity::user::Column::EmailVerified,
+                Expr::value(0i64),
+            )
+            .col_expr(
+                crate::entity::user::Column::EmailVerificationToken,
+                Expr::value(verification_token),
+            )
+            .filter(crate::entity::user::Column::Id.eq(user_id.to_string()))
+            .exec(self.db.as_ref())
+            .await
+            .context("Failed to update email")?;
+
+        if result.rows_affected == 0 {
+            return Err(Error::coded(
+                ErrorCode::UserNotFound,
+                format!("User with id {} not found", user_id),
+            ));
+        }
         Ok(())
     }
 
+    async fn verify_email(&self, token: &str) -> Result<Option<User>> {
+        let _timer = metrics::db_timer("user.verify_email");
+        let Some(model) = crate::entity::user::Entity::find()
+            .filter(crate::entity::user::Column::EmailVerificationToken.eq(token))
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find user")?
+        else {
+            return Ok(None);
+        };
+
+        let mut active_model: crate::entity::user::ActiveModel = model.into();
+        active_model.email_verified = Set(1);
+        active_model.email_verification_token = Set(None);
+
+        let updated_model = active_model
+            .update(self.db.as_ref())
+            .await
+            .context("Failed to verify email")?;
+
+        model_to_user(updated_model).map(Some)
+    }
+
+    async fn set_password_reset_token(
+        &self,
+        user_id: Uuid,
+        token: Option<String>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<()> {
+        let _timer = metrics::db_timer("user.set_password_reset_token");
+        crate::entity::user::Entity::update_many()
+            .col_expr(
+                crate::entity::user::Column::PasswordResetToken,
+                Expr::value(token),
+            )
+            .col_expr(
+                crate::entity::user::Column::PasswordResetExpiresAt,
+                Expr::value(expires_at.map(|at| at.to_rfc3339())),
+            )
+            .filter(crate::entity::user::Column::Id.eq(user_id.to_string()))
+            .exec(self.db.as_ref())
+            .await
+            .context("Failed to store reset token")?;
+
+        Ok(())
+    }
+
+    async fn find_by_password_reset_token(&self, token: &str) -> Result<Option<User>> {
+        let _timer = metrics::db_timer("user.find_by_password_reset_token");
+        let model = crate::entity::user::Entity::find()
+            .filter(crate::entity::user::Column::PasswordResetToken.eq(token))
+            .one(self.db.as_ref())
+            .await
+            .context("Failed to find user")?;
+
+        model.map(model_to_user).transpose()
+    }
+
     async fn delete_user(&self, user_id: Uuid) -> Result<()> {
+        let _timer = metrics::db_timer("user.delete_user");
         // Delete user and check if it existed (cascade will delete related records)
         let result = crate::entity::user::Entity::delete_by_id(user_id.to_string())
             .exec(self.db.as_ref())
             .await
-            .map_err(|e| Error::Internal(format!("Failed to delete user: {}", e)))?;
+            .context("Failed to delete user")?;
 
         if result.rows_affected == 0 {
-            return Err(Error::NotFound(format!(
-                "User with id {} not found",
-                user_id
-            )));
+            return Err(Error::coded(
+                ErrorCode::UserNotFound,
+                format!("User with id {} not found", user_id),
+            ));
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[test]
+    fn test_user_search_matches_wildcards_literally() {
+        let sql = user_search_query(r"a_b%c\d")
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(
+            sql.contains(r#""user"."username" LIKE E'%a\\_b\\%c\\\\d%' ESCAPE E'\\'"#),
+            "{}",
+            sql
+        );
+    }
+
+    fn hash(password: &str) -> String {
+        Argon2::default()
+            .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_verify_password_mismatch_is_false() {
+        let stored = hash("correct horse");
+
+        assert!(verify_password("correct horse", &stored).unwrap());
+        assert!(!verify_password("wrong password", &stored).unwrap());
+        assert!(matches!(
+            verify_password("correct horse", "not a hash"),
+            Err(Error::Internal(_))
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+//! Webhook HTTP Client Implementation
+//!
+//! This module provides the HTTP client for delivering signed webhook
+//! notifications when posts are published or unpublished.
+//!
+//! The request body is signed with HMAC-SHA256 using the configured secret
+//! and sent in the `X-Hub-Signature-256` header (`sha256=<hex digest>`),
+//! matching the GitHub webhook convention.
+
+use domain::WebhookPayload;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying the HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+
+/// Time allowed for a webhook endpoint to answer
+pub const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+pub struct WebhookClient {
+    client: reqwest::Client,
+    url: String,
+    secret: Option<String>,
+}
+
+impl WebhookClient {
+    pub fn new(url: String, secret: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            url,
+            secret,
+        }
+    }
+
+    /// Compute the `sha256=<hex>` signature for a request body
+    pub fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    pub async fn send(&self, payload: &WebhookPayload) -> Result<(), String> {
+        let body = serde_json::to_vec(payload)
+            .map_err(|e| format!("Failed to serialize webhook payload: {}", e))?;
+
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Content-Type", "application/json");
+
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, Self::sign(secret, &body));
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Network error: {}", e))?;
+
+        let status = response.status();
+
+        if status.is_success() {
+            tracing::info!("Webhook delivered: {}", status);
+            Ok(())
+        } else {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unable to read error response".to_string());
+
+            tracing::error!("Webhook delivery failed: {} - {}", status, error_text);
+
+            Err(format!("Webhook error: {} - {}", status, error_text))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use domain::WebhookEvent;
+    use uuid::Uuid;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sample_payload(event: WebhookEvent) -> WebhookPayload {
+        WebhookPayload {
+            event,
+            post_id: Uuid::new_v4(),
+            slug: "hello-world".to_string(),
+            title: "Hello World".to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_posts_signed_payload() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("Content-Type", "application/json"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client =
+            WebhookClient::new(format!("{}/hook", server.uri()), Some("s3cret".to_string()));
+        let payload = sample_payload(WebhookEvent::PostPublished);
+
+        client.send(&payload).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let request = &requests[0];
+
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["event"], "post.published");
+        assert_eq!(body["post_id"], payload.post_id.to_string());
+        assert_eq!(body["slug"], "hello-world");
+        assert_eq!(body["title"], "Hello World");
+        assert!(body["timestamp"].is_string());
+
+        let signature = request.headers.get(SIGNATURE_HEADER).unwrap();
+        assert_eq!(
+            signature.to_str().unwrap(),
+            WebhookClient::sign("s3cret", &request.body)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_without_secret_omits_signature() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let client = WebhookClient::new(server.uri(), None);
+        client
+            .send(&sample_payload(WebhookEvent::PostUnpublished))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get(SIGNATURE_HEADER).is_none());
+
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["event"], "post.unpublished");
+    }
+
+    #[tokio::test]
+    async fn test_send_reports_error_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+
+        let client = WebhookClient::new(server.uri(), Some("s3cret".to_string()));
+        let result = client
+            .send(&sample_payload(WebhookEvent::PostPublished))
+            .await;
+
+        let err = result.unwrap_err();
+        assert!(err.contains("500"));
+        assert!(err.contains("boom"));
+    }
+
+    #[test]
+    fn test_sign_matches_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            WebhookClient::sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -1,20 +1,47 @@
-use crate::CategoryRepository;
-use domain::{Category, CreateCategory, Error, Result, UpdateCategory};
+use crate::internal_error;
+use crate::{CategoryRepository, PermissionService};
+use domain::{
+    Category, CategoryNode, CreateCategory, Error, Result, UpdateCategory, MAX_CATEGORY_DEPTH,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Deepest nesting accepted for new and moved categories when
+/// `site.max_category_depth` is not set
+pub const DEFAULT_MAX_CATEGORY_DEPTH: u32 = 5;
+
 /// Service for category business logic
 #[derive(Clone)]
 pub struct CategoryService {
     repo: Arc<dyn CategoryRepository>,
+    permissions: Arc<PermissionService>,
+    max_depth: u32,
 }
 
 impl CategoryService {
-    pub fn new(repo: Arc<dyn CategoryRepository>) -> Self {
-        Self { repo }
+    pub fn new(repo: Arc<dyn CategoryRepository>, permissions: Arc<PermissionService>) -> Self {
+        Self {
+            repo,
+            permissions,
+            max_depth: DEFAULT_MAX_CATEGORY_DEPTH,
+        }
     }
 
-    pub async fn create(&self, input: CreateCategory) -> Result<Category> {
+    /// Set the deepest nesting allowed when creating or moving categories
+    ///
+    /// Values are clamped to 1..=`MAX_CATEGORY_DEPTH`, beyond which the
+    /// hierarchy can no longer be read back.
+    pub fn with_max_depth(mut self, max_depth: Option<u32>) -> Self {
+        self.max_depth = max_depth
+            .unwrap_or(DEFAULT_MAX_CATEGORY_DEPTH)
+            .clamp(1, MAX_CATEGORY_DEPTH as u32);
+        self
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn create(&self, user_id: Uuid, input: CreateCategory) -> Result<Category> {
+        self.require_manage(user_id).await?;
         self.validate_slug(&input.slug)?;
         self.validate_name(&input.name)?;
 
@@ -23,13 +50,18 @@ impl CategoryService {
                 .get_category(parent_id)
                 .await?
                 .ok_or_else(|| Error::Validation("Parent category not found".to_string()))?;
+            self.check_depth(parent_id, 1).await?;
         }
 
-        self.repo
+        let category = self
+            .repo
             .create_category(input.name, input.slug, input.parent_id)
-            .await
+            .await?;
+        tracing::info!("Category {} created by user {}", category.id, user_id);
+        Ok(category)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(category_id = %id))]
     pub async fn get(&self, id: Uuid) -> Result<Category> {
         self.repo
             .get_category(id)
@@ -37,6 +69,7 @@ impl CategoryService {
             .ok_or_else(|| Error::NotFound("Category not found".to_string()))
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_by_slug(&self, slug: &str) -> Result<Category> {
         self.repo
             .get_category_by_slug(slug)
@@ -44,11 +77,15 @@ impl CategoryService {
             .ok_or_else(|| Error::NotFound("Category not found".to_string()))
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list(&self) -> Result<Vec<Category>> {
         self.repo.list_categories().await
     }
 
-    pub async fn update(&self, id: Uuid, input: UpdateCategory) -> Result<Category> {
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id, category_id = %id))]
+    pub async fn update(&self, user_id: Uuid, id: Uuid, input: UpdateCategory) -> Result<Category> {
+        self.require_manage(user_id).await?;
+
         if let Some(parent_id) = input.parent_id {
             if parent_id == id {
                 return Err(Error::Validation(
@@ -60,27 +97,122 @@ impl CategoryService {
                 .get_category(parent_id)
                 .await?
                 .ok_or_else(|| Error::Validation("Parent category not found".to_string()))?;
+
+            let categories = self.repo.list_categories().await?;
+            let subtree = Subtree::of(&categories, id);
+            if subtree.ids.contains(&parent_id) {
+                return Err(Error::Validation(
+                    "Category cannot be moved under its own descendant".to_string(),
+                ));
+            }
+            self.check_depth(parent_id, subtree.height).await?;
         }
 
         if let Some(ref name) = input.name {
             self.validate_name(name)?;
         }
 
-        self.repo
+        let category = self
+            .repo
             .update_category(id, input.name, input.parent_id)
-            .await
+            .await?;
+        tracing::info!("Category {} updated by user {}", id, user_id);
+        Ok(category)
     }
 
-    pub async fn delete(&self, id: Uuid) -> Result<()> {
-        self.repo.delete_category(id).await
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id, category_id = %id))]
+    pub async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        self.require_manage(user_id).await?;
+        self.repo.delete_category(id).await?;
+        tracing::info!("Category {} deleted by user {}", id, user_id);
+        Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_children(&self, parent_id: Option<Uuid>) -> Result<Vec<Category>> {
         self.repo.get_children(parent_id).await
     }
 
+    /// Get the chain of categories from the root down to the given category
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Category>)` - Root first, the requested category last
+    /// * `Err(Error::NotFound)` - Category does not exist
+    /// * `Err(Error::Internal)` - The hierarchy is deeper than
+    ///   `MAX_CATEGORY_DEPTH` levels or its `parent_id` chain has a cycle
+    #[tracing::instrument(level = "debug", skip_all, fields(category_id = %id))]
+    pub async fn get_breadcrumb(&self, id: Uuid) -> Result<Vec<Category>> {
+        let mut chain = self.repo.get_category_with_ancestors(id).await?;
+        if chain.is_empty() {
+            return Err(Error::NotFound("Category not found".to_string()));
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        if chain.len() > MAX_CATEGORY_DEPTH || !chain.iter().all(|c| seen.insert(c.id)) {
+            return Err(internal_error(format!(
+                "Category {} has a cyclic or too deep parent chain",
+                id
+            )));
+        }
+
+        chain.reverse();
+        Ok(chain)
+    }
+
+    /// Get every category arranged as a tree of root categories
+    ///
+    /// All categories are loaded in one query and nested in memory, keeping
+    /// the repository order among siblings.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CategoryNode>)` - Root categories with their descendants
+    /// * `Err(Error::Internal)` - The hierarchy is deeper than
+    ///   `MAX_CATEGORY_DEPTH` levels or its `parent_id` chain has a cycle
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_tree(&self) -> Result<Vec<CategoryNode>> {
+        let categories = self.repo.list_categories().await?;
+        let total = categories.len();
+
+        let mut by_parent: HashMap<Option<Uuid>, Vec<Category>> = HashMap::new();
+        for category in categories {
+            by_parent
+                .entry(category.parent_id)
+                .or_default()
+                .push(category);
+        }
+
+        let roots = attach_children(&mut by_parent, None, 1)?;
+
+        // Categories in a cycle are never reached from a root
+        if count_nodes(&roots) != total {
+            return Err(internal_error(
+                "Category hierarchy has a cyclic parent chain",
+            ));
+        }
+
+        Ok(roots)
+    }
+
     // Private validation methods
 
+    /// Fail if `levels` levels of categories below `parent_id` would nest
+    /// deeper than the configured maximum
+    async fn check_depth(&self, parent_id: Uuid, levels: u32) -> Result<()> {
+        let parent_depth = self.repo.get_category_depth(parent_id).await?;
+        if parent_depth + levels > self.max_depth {
+            return Err(Error::Validation(format!(
+                "Category depth exceeds maximum of {}",
+                self.max_depth
+            )));
+        }
+        Ok(())
+    }
+
+    async fn require_manage(&self, user_id: Uuid) -> Result<()> {
+        let allowed = self.permissions.can_manage_categories(user_id).await?;
+        PermissionService::require(allowed, "manage categories")
+    }
+
     fn validate_slug(&self, slug: &str) -> Result<()> {
         if slug.trim().is_empty() {
             return Err(Error::Validation("Slug cannot be empty".to_string()));
@@ -113,25 +245,95 @@ impl CategoryService {
     }
 }
 
+/// Build the nodes for the children of `parent_id`, `depth` levels down
+fn attach_children(
+    by_parent: &mut HashMap<Option<Uuid>, Vec<Category>>,
+    parent_id: Option<Uuid>,
+    depth: usize,
+) -> Result<Vec<CategoryNode>> {
+    let Some(children) = by_parent.remove(&parent_id) else {
+        return Ok(Vec::new());
+    };
+    if depth > MAX_CATEGORY_DEPTH {
+        return Err(internal_error(format!(
+            "Category hierarchy is deeper than {} levels",
+            MAX_CATEGORY_DEPTH
+        )));
+    }
+
+    children
+        .into_iter()
+        .map(|category| {
+            let children = attach_children(by_parent, Some(category.id), depth + 1)?;
+            Ok(CategoryNode { category, children })
+        })
+        .collect()
+}
+
+/// A category together with all of its descendants
+struct Subtree {
+    ids: HashSet<Uuid>,
+    /// Levels in the subtree, the category itself being 1
+    height: u32,
+}
+
+impl Subtree {
+    /// Walk down from `root`, stopping past `MAX_CATEGORY_DEPTH` levels so
+    /// a cycle cannot make it loop
+    fn of(categories: &[Category], root: Uuid) -> Self {
+        let mut subtree = Self {
+            ids: HashSet::from([root]),
+            height: 1,
+        };
+        let mut level = vec![root];
+        while subtree.height as usize <= MAX_CATEGORY_DEPTH {
+            level = categories
+                .iter()
+                .filter(|c| c.parent_id.is_some_and(|parent| level.contains(&parent)))
+                .filter(|c| subtree.ids.insert(c.id))
+                .map(|c| c.id)
+                .collect();
+            if level.is_empty() {
+                break;
+            }
+            subtree.height += 1;
+        }
+        subtree
+    }
+}
+
+fn count_nodes(nodes: &[CategoryNode]) -> usize {
+    nodes
+        .iter()
+        .map(|node| 1 + count_nodes(&node.children))
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use domain::Error;
-    use mockall::{mock, predicate::*};
-
-    mock! {
-        CategoryRepo {}
-
-        #[async_trait::async_trait]
-        impl CategoryRepository for CategoryRepo {
-            async fn create_category(&self, name: String, slug: String, parent_id: Option<Uuid>) -> Result<Category>;
-            async fn get_category(&self, id: Uuid) -> Result<Option<Category>>;
-            async fn get_category_by_slug(&self, slug: &str) -> Result<Option<Category>>;
-            async fn list_categories(&self) -> Result<Vec<Category>>;
-            async fn update_category(&self, id: Uuid, name: Option<String>, parent_id: Option<Uuid>) -> Result<Category>;
-            async fn delete_category(&self, id: Uuid) -> Result<()>;
-            async fn get_children(&self, parent_id: Option<Uuid>) -> Result<Vec<Category>>;
-        }
+    use crate::test_support::MockCategoryRepo;
+    use crate::test_support::MockUserRepo;
+    use domain::{Error, ErrorCode, User, ADMIN_PERMISSIONS, DEFAULT_USER_PERMISSIONS};
+    use mockall::predicate::*;
+
+    const ADMIN_ID: Uuid = Uuid::from_u128(1);
+    const USER_ID: Uuid = Uuid::from_u128(2);
+
+    fn service_with(repo: MockCategoryRepo) -> CategoryService {
+        let users = MockUserRepo::with_users(vec![
+            User::new(ADMIN_ID, "admin".into(), "hash".into(), ADMIN_PERMISSIONS),
+            User::new(
+                USER_ID,
+                "user".into(),
+                "hash".into(),
+                DEFAULT_USER_PERMISSIONS,
+            ),
+        ]);
+        CategoryService::new(
+            Arc::new(repo),
+            Arc::new(PermissionService::new(Arc::new(users))),
+        )
     }
 
     fn create_test_category(id: Uuid, name: &str, slug: &str) -> Category {
@@ -140,14 +342,14 @@ mod tests {
             name: name.to_string(),
             slug: slug.to_string(),
             parent_id: None,
+            post_count: 0,
             created_at: chrono::Utc::now(),
         }
     }
 
     #[tokio::test]
     async fn test_create_category_validates_empty_slug() {
-        let mock_repo = Arc::new(MockCategoryRepo::new());
-        let service = CategoryService::new(mock_repo);
+        let service = service_with(MockCategoryRepo::new());
 
         let input = CreateCategory {
             name: "Test".to_string(),
@@ -155,7 +357,7 @@ mod tests {
             parent_id: None,
         };
 
-        let result = service.create(input).await;
+        let result = service.create(ADMIN_ID, input).await;
 
         assert!(result.is_err());
         match result {
@@ -166,8 +368,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_category_validates_invalid_slug() {
-        let mock_repo = Arc::new(MockCategoryRepo::new());
-        let service = CategoryService::new(mock_repo);
+        let service = service_with(MockCategoryRepo::new());
 
         let input = CreateCategory {
             name: "Test".to_string(),
@@ -175,7 +376,7 @@ mod tests {
             parent_id: None,
         };
 
-        let result = service.create(input).await;
+        let result = service.create(ADMIN_ID, input).await;
 
         assert!(result.is_err());
         match result {
@@ -188,8 +389,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_category_validates_empty_name() {
-        let mock_repo = Arc::new(MockCategoryRepo::new());
-        let service = CategoryService::new(mock_repo);
+        let service = service_with(MockCategoryRepo::new());
 
         let input = CreateCategory {
             name: "".to_string(),
@@ -197,7 +397,7 @@ mod tests {
             parent_id: None,
         };
 
-        let result = service.create(input).await;
+        let result = service.create(ADMIN_ID, input).await;
 
         assert!(result.is_err());
         match result {
@@ -208,8 +408,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_category_validates_long_name() {
-        let mock_repo = Arc::new(MockCategoryRepo::new());
-        let service = CategoryService::new(mock_repo);
+        let service = service_with(MockCategoryRepo::new());
 
         let input = CreateCategory {
             name: "a".repeat(101),
@@ -217,7 +416,7 @@ mod tests {
             parent_id: None,
         };
 
-        let result = service.create(input).await;
+        let result = service.create(ADMIN_ID, input).await;
 
         assert!(result.is_err());
         match result {
@@ -237,7 +436,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None));
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let input = CreateCategory {
             name: "Test".to_string(),
@@ -245,7 +444,7 @@ mod tests {
             parent_id: Some(parent_id),
         };
 
-        let result = service.create(input).await;
+        let result = service.create(ADMIN_ID, input).await;
 
         assert!(result.is_err());
         match result {
@@ -268,11 +467,12 @@ mod tests {
                     name,
                     slug,
                     parent_id: None,
+                    post_count: 0,
                     created_at: chrono::Utc::now(),
                 })
             });
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let input = CreateCategory {
             name: "Test".to_string(),
@@ -280,7 +480,7 @@ mod tests {
             parent_id: None,
         };
 
-        let result = service.create(input).await;
+        let result = service.create(ADMIN_ID, input).await;
 
         assert!(result.is_ok());
         let category = result.unwrap();
@@ -299,7 +499,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None));
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let result = service.get(category_id).await;
 
@@ -320,7 +520,7 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(create_test_category(category_id, "Test", "test"))));
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let result = service.get(category_id).await;
 
@@ -335,14 +535,14 @@ mod tests {
         let mock_repo = MockCategoryRepo::new();
         let category_id = Uuid::new_v4();
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let input = UpdateCategory {
             name: Some("Updated".to_string()),
             parent_id: Some(category_id),
         };
 
-        let result = service.update(category_id, input).await;
+        let result = service.update(ADMIN_ID, category_id, input).await;
 
         assert!(result.is_err());
         match result {
@@ -362,7 +562,7 @@ mod tests {
             .times(1)
             .returning(move || Ok(vec![category1.clone(), category2.clone()]));
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let result = service.list().await;
 
@@ -382,9 +582,9 @@ mod tests {
             .times(1)
             .returning(|_| Ok(()));
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
-        let result = service.delete(category_id).await;
+        let result = service.delete(ADMIN_ID, category_id).await;
 
         assert!(result.is_ok());
     }
@@ -401,7 +601,7 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(vec![child.clone()]));
 
-        let service = CategoryService::new(Arc::new(mock_repo));
+        let service = service_with(mock_repo);
 
         let result = service.get_children(Some(parent_id)).await;
 
@@ -410,4 +610,318 @@ mod tests {
         assert_eq!(children.len(), 1);
         assert_eq!(children[0].name, "Child");
     }
+
+    #[tokio::test]
+    async fn test_create_category_requires_category_manage() {
+        let service = service_with(MockCategoryRepo::new());
+
+        let input = CreateCategory {
+            name: "Test".to_string(),
+            slug: "test".to_string(),
+            parent_id: None,
+        };
+
+        let result = service.create(USER_ID, input).await;
+
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected permission error for regular user"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_category_requires_category_manage() {
+        let service = service_with(MockCategoryRepo::new());
+
+        let result = service.delete(USER_ID, Uuid::new_v4()).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_breadcrumb_returns_root_first() {
+        let mut mock_repo = MockCategoryRepo::new();
+        let root = create_test_category(Uuid::new_v4(), "Programming", "programming");
+        let mut middle = create_test_category(Uuid::new_v4(), "Rust", "rust");
+        middle.parent_id = Some(root.id);
+        let mut leaf = create_test_category(Uuid::new_v4(), "Async", "async");
+        leaf.parent_id = Some(middle.id);
+        let leaf_id = leaf.id;
+
+        let ancestors = vec![leaf.clone(), middle.clone(), root.clone()];
+        mock_repo
+            .expect_get_category_with_ancestors()
+            .with(eq(leaf_id))
+            .times(1)
+            .returning(move |_| Ok(ancestors.clone()));
+        mock_repo
+            .expect_get_category_with_ancestors()
+            .returning(|_| Ok(vec![]));
+
+        let service = service_with(mock_repo);
+
+        let breadcrumb = service.get_breadcrumb(leaf_id).await.unwrap();
+        let slugs: Vec<&str> = breadcrumb.iter().map(|c| c.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["programming", "rust", "async"]);
+
+        let missing = service.get_breadcrumb(Uuid::new_v4()).await;
+        assert!(matches!(missing, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_breadcrumb_detects_cycles() {
+        let mut mock_repo = MockCategoryRepo::new();
+        let mut a = create_test_category(Uuid::new_v4(), "A", "a");
+        let mut b = create_test_category(Uuid::new_v4(), "B", "b");
+        a.parent_id = Some(b.id);
+        b.parent_id = Some(a.id);
+        let a_id = a.id;
+
+        // The repository stops after MAX_CATEGORY_DEPTH + 1 rows
+        let chain: Vec<Category> = [a, b]
+            .into_iter()
+            .cycle()
+            .take(MAX_CATEGORY_DEPTH + 1)
+            .collect();
+        mock_repo
+            .expect_get_category_with_ancestors()
+            .returning(move |_| Ok(chain.clone()));
+
+        let service = service_with(mock_repo);
+
+        let result = service.get_breadcrumb(a_id).await;
+        assert!(matches!(result, Err(Error::Internal(_))));
+    }
+
+    fn child_of(parent: &Category, name: &str, post_count: u32) -> Category {
+        let mut category = create_test_category(Uuid::new_v4(), name, &name.to_lowercase());
+        category.parent_id = Some(parent.id);
+        category.post_count = post_count;
+        category
+    }
+
+    #[tokio::test]
+    async fn test_get_tree_nests_three_levels() {
+        let programming = create_test_category(Uuid::new_v4(), "Programming", "programming");
+        let rust = child_of(&programming, "Rust", 4);
+        let go = child_of(&programming, "Go", 1);
+        let tokio = child_of(&rust, "Tokio", 2);
+        let life = create_test_category(Uuid::new_v4(), "Life", "life");
+
+        // Children may be listed before their parents
+        let categories = vec![
+            tokio.clone(),
+            programming.clone(),
+            rust.clone(),
+            life.clone(),
+            go.clone(),
+        ];
+        let mut mock_repo = MockCategoryRepo::new();
+        mock_repo
+            .expect_list_categories()
+            .times(1)
+            .returning(move || Ok(categories.clone()));
+        let service = service_with(mock_repo);
+
+        let tree = service.get_tree().await.unwrap();
+
+        let expected = vec![
+            CategoryNode {
+                category: programming,
+                children: vec![
+                    CategoryNode {
+                        category: rust,
+                        children: vec![CategoryNode {
+                            category: tokio,
+                            children: vec![],
+                        }],
+                    },
+                    CategoryNode {
+                        category: go,
+                        children: vec![],
+                    },
+                ],
+            },
+            CategoryNode {
+                category: life,
+                children: vec![],
+            },
+        ];
+        assert_eq!(tree, expected);
+
+        let json = serde_json::to_value(&tree).unwrap();
+        assert_eq!(json[0]["slug"], "programming");
+        assert_eq!(json[0]["children"][0]["post_count"], 4);
+        assert_eq!(json[0]["children"][0]["children"][0]["name"], "Tokio");
+    }
+
+    #[tokio::test]
+    async fn test_get_tree_rejects_cycles_and_deep_chains() {
+        let mut a = create_test_category(Uuid::new_v4(), "A", "a");
+        let mut b = create_test_category(Uuid::new_v4(), "B", "b");
+        a.parent_id = Some(b.id);
+        b.parent_id = Some(a.id);
+        let root = create_test_category(Uuid::new_v4(), "Root", "root");
+        let cyclic = vec![root.clone(), a, b];
+
+        let mut chain = vec![root];
+        for level in 1..=MAX_CATEGORY_DEPTH {
+            let child = child_of(chain.last().unwrap(), &format!("L{}", level), 0);
+            chain.push(child);
+        }
+
+        for categories in [cyclic, chain] {
+            let mut mock_repo = MockCategoryRepo::new();
+            mock_repo
+                .expect_list_categories()
+                .returning(move || Ok(categories.clone()));
+            let service = service_with(mock_repo);
+
+            let result = service.get_tree().await;
+            assert!(matches!(result, Err(Error::Internal(_))));
+        }
+    }
+
+    /// Chain of `len` categories, each the child of the one before
+    fn category_chain(len: usize) -> Vec<Category> {
+        let mut chain: Vec<Category> = Vec::new();
+        for level in 0..len {
+            let mut category = create_test_category(
+                Uuid::new_v4(),
+                &format!("Level {}", level + 1),
+                &format!("level-{}", level + 1),
+            );
+            category.parent_id = chain.last().map(|parent| parent.id);
+            chain.push(category);
+        }
+        chain
+    }
+
+    /// Repository over `categories` answering lookups, depths, creates and updates
+    fn tree_repo(categories: Vec<Category>) -> MockCategoryRepo {
+        let mut mock_repo = MockCategoryRepo::new();
+        let lookup = categories.clone();
+        mock_repo
+            .expect_get_category()
+            .returning(move |id| Ok(lookup.iter().find(|c| c.id == id).cloned()));
+        let chains = categories.clone();
+        mock_repo.expect_get_category_depth().returning(move |id| {
+            let mut depth = 0;
+            let mut current = Some(id);
+            while let Some(category) = current.and_then(|id| chains.iter().find(|c| c.id == id)) {
+                depth += 1;
+                current = category.parent_id;
+            }
+            Ok(depth)
+        });
+        let all = categories.clone();
+        mock_repo
+            .expect_list_categories()
+            .returning(move || Ok(all.clone()));
+        mock_repo
+            .expect_create_category()
+            .returning(|name, slug, parent_id| {
+                Ok(Category {
+                    parent_id,
+                    ..create_test_category(Uuid::new_v4(), &name, &slug)
+                })
+            });
+        let existing = categories;
+        mock_repo
+            .expect_update_category()
+            .returning(move |id, _, parent_id| {
+                let category = existing.iter().find(|c| c.id == id).unwrap();
+                Ok(Category {
+                    parent_id,
+                    ..category.clone()
+                })
+            });
+        mock_repo
+    }
+
+    fn new_child(parent_id: Uuid) -> CreateCategory {
+        CreateCategory {
+            name: "Child".to_string(),
+            slug: "child".to_string(),
+            parent_id: Some(parent_id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_category_rejects_sixth_level() {
+        let chain = category_chain(5);
+        let service = service_with(tree_repo(chain.clone()));
+
+        let err = service
+            .create(ADMIN_ID, new_child(chain[4].id))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::Validation(ref msg) if msg == "Category depth exceeds maximum of 5")
+        );
+
+        let created = service
+            .create(ADMIN_ID, new_child(chain[3].id))
+            .await
+            .unwrap();
+        assert_eq!(created.parent_id, Some(chain[3].id));
+    }
+
+    #[tokio::test]
+    async fn test_max_depth_follows_configuration() {
+        let chain = category_chain(5);
+        let service = service_with(tree_repo(chain.clone())).with_max_depth(Some(6));
+
+        assert!(service
+            .create(ADMIN_ID, new_child(chain[4].id))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_moving_deep_subtree_too_low() {
+        // Two three-level chains; the root of one is moved under the other
+        let target = category_chain(3);
+        let moved = category_chain(3);
+        let service = service_with(tree_repo([target.clone(), moved.clone()].concat()));
+        let move_under = |parent_id| UpdateCategory {
+            name: None,
+            parent_id: Some(parent_id),
+        };
+
+        let err = service
+            .update(ADMIN_ID, moved[0].id, move_under(target[2].id))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(ref msg) if msg.contains("maximum of 5")));
+
+        let updated = service
+            .update(ADMIN_ID, moved[0].id, move_under(target[1].id))
+            .await
+            .unwrap();
+        assert_eq!(updated.parent_id, Some(target[1].id));
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_moving_under_own_descendant() {
+        let chain = category_chain(3);
+        let service = service_with(tree_repo(chain.clone()));
+
+        let input = UpdateCategory {
+            name: None,
+            parent_id: Some(chain[2].id),
+        };
+        let err = service
+            .update(ADMIN_ID, chain[0].id, input)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, Error::Validation(ref msg) if msg.contains("own descendant")));
+    }
 }
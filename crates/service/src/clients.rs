@@ -0,0 +1,134 @@
+//! Outbound Clients
+//!
+//! The IndexNow, webhook and SMTP clients are built from the configuration.
+//! [`SharedClients`] holds the current set: [`crate::ConfigService`] rebuilds
+//! it whenever the configuration is reloaded or updated, and services read it
+//! on each use, the same way they read [`crate::SharedConfig`].
+
+use domain::{Config, Error, Result};
+use infrastructure::{EmailClient, IndexNowClient, WebhookClient};
+use std::sync::{Arc, RwLock};
+
+/// Live set of outbound clients shared by every service that sends requests
+pub type SharedClients = Arc<RwLock<Clients>>;
+
+/// Outbound clients for the integrations enabled in the configuration
+#[derive(Clone, Default)]
+pub struct Clients {
+    /// IndexNow client and the API key submitted with each request
+    pub indexnow: Option<(Arc<IndexNowClient>, String)>,
+    pub webhook: Option<Arc<WebhookClient>>,
+    pub email: Option<Arc<EmailClient>>,
+}
+
+impl Clients {
+    /// Build the clients enabled in `config`
+    ///
+    /// # Returns
+    /// * `Ok(Clients)` - Clients for every enabled integration
+    /// * `Err(Error::Validation)` - SMTP is enabled with unusable settings
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let indexnow = if !config.indexnow.enabled {
+            tracing::info!("IndexNow is disabled");
+            None
+        } else if config.indexnow.api_key.is_empty() {
+            tracing::warn!(
+                "IndexNow is enabled but API key is empty. IndexNow notifications will be disabled."
+            );
+            None
+        } else {
+            tracing::info!(
+                "IndexNow enabled with endpoint: {}",
+                config.indexnow.endpoint
+            );
+            Some((
+                Arc::new(IndexNowClient::new(config.indexnow.endpoint.clone())),
+                config.indexnow.api_key.clone(),
+            ))
+        };
+
+        let webhook = match config.webhook.url.as_deref() {
+            Some(url) if !url.is_empty() => {
+                tracing::info!("Webhook notifications enabled: {}", url);
+                if config.webhook.secret.is_none() {
+                    tracing::warn!("Webhook secret is not set. Payloads will be sent unsigned.");
+                }
+                Some(Arc::new(WebhookClient::new(
+                    url.to_string(),
+                    config.webhook.secret.clone(),
+                )))
+            }
+            _ => None,
+        };
+
+        let email = if config.smtp.enabled {
+            tracing::info!(
+                "Email notifications enabled via {}:{}",
+                config.smtp.host,
+                config.smtp.port
+            );
+            let smtp = config::SmtpConfig {
+                enabled: config.smtp.enabled,
+                host: config.smtp.host.clone(),
+                port: config.smtp.port,
+                username: config.smtp.username.clone(),
+                password: config.smtp.password.clone(),
+                from_address: config.smtp.from_address.clone(),
+            };
+            let client = EmailClient::new(&smtp)
+                .map_err(|e| Error::Validation(format!("Invalid SMTP settings: {}", e)))?;
+            Some(Arc::new(client))
+        } else {
+            tracing::info!("Email notifications are disabled");
+            None
+        };
+
+        Ok(Self {
+            indexnow,
+            webhook,
+            email,
+        })
+    }
+
+    /// Wrap the clients in a handle that can be shared and rebuilt
+    pub fn shared(self) -> SharedClients {
+        Arc::new(RwLock::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_config;
+
+    #[test]
+    fn test_clients_follow_config() {
+        let mut config = test_config();
+        config.indexnow.enabled = true;
+        config.indexnow.api_key = String::new();
+        config.webhook.url = None;
+        config.smtp.enabled = false;
+
+        let clients = Clients::from_config(&config).unwrap();
+        assert!(clients.indexnow.is_none());
+        assert!(clients.webhook.is_none());
+        assert!(clients.email.is_none());
+
+        config.indexnow.api_key = "key".to_string();
+        config.webhook.url = Some("https://hooks.example.com/blog".to_string());
+        config.smtp.enabled = true;
+        config.smtp.host = "smtp.example.com".to_string();
+        config.smtp.from_address = "blog@example.com".to_string();
+
+        let clients = Clients::from_config(&config).unwrap();
+        assert_eq!(clients.indexnow.unwrap().1, "key");
+        assert!(clients.webhook.is_some());
+        assert!(clients.email.is_some());
+
+        config.smtp.from_address = "not an address".to_string();
+        assert!(matches!(
+            Clients::from_config(&config),
+            Err(Error::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+//! Disqus XML export parsing
+//!
+//! A Disqus export lists every `<thread>` (one per commented page) followed
+//! by every `<post>` (one per comment), which refers back to its thread and
+//! optional parent comment by `dsq:id`. Element prefixes are ignored, so both
+//! `<thread>` and `<disqus:thread>` are understood.
+
+use domain::{Error, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+/// A commented page of the exported site
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct DisqusThread {
+    /// Disqus identifier chosen by the site, often the page slug
+    pub identifier: String,
+    pub link: String,
+}
+
+impl DisqusThread {
+    /// Slugs the thread may be known by: the last path segment of its link,
+    /// then its identifier
+    pub fn slug_candidates(&self) -> Vec<&str> {
+        let link_slug = self
+            .link
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+
+        [link_slug, self.identifier.trim()]
+            .into_iter()
+            .filter(|slug| !slug.is_empty())
+            .collect()
+    }
+}
+
+/// A single exported comment
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct DisqusPost {
+    pub dsq_id: String,
+    pub thread_id: String,
+    pub parent_id: Option<String>,
+    /// Comment body as HTML
+    pub message: String,
+    pub created_at: String,
+    pub author_name: String,
+    pub author_avatar_url: Option<String>,
+    pub is_deleted: bool,
+    pub is_spam: bool,
+}
+
+/// Threads by `dsq:id` and comments in document order
+#[derive(Debug, Default)]
+pub(crate) struct DisqusExport {
+    pub threads: HashMap<String, DisqusThread>,
+    pub posts: Vec<DisqusPost>,
+}
+
+/// Parse a Disqus XML export
+///
+/// Fails with `Error::Validation` when the document is not well-formed XML.
+pub(crate) fn parse_export(xml: &str) -> Result<DisqusExport> {
+    let invalid = |e: quick_xml::Error| Error::Validation(format!("Invalid Disqus export: {}", e));
+
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut export = DisqusExport::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut thread: Option<(String, DisqusThread)> = None;
+    let mut post: Option<DisqusPost> = None;
+
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(element) => {
+                let name = local_name(&element);
+                match (path.len(), name.as_str()) {
+                    (1, "thread") => thread = Some((dsq_id(&element), DisqusThread::default())),
+                    (1, "post") => {
+                        post = Some(DisqusPost {
+                            dsq_id: dsq_id(&element),
+                            ..DisqusPost::default()
+                        })
+                    }
+                    _ => {}
+                }
+                if let Some(post) = post.as_mut().filter(|_| path.len() == 2) {
+                    post_reference(post, &name, &element);
+                }
+                path.push(name);
+            }
+            Event::Empty(element) => {
+                if let Some(post) = post.as_mut().filter(|_| path.len() == 2) {
+                    post_reference(post, &local_name(&element), &element);
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_err(invalid)?;
+                add_text(&path, &text, thread.as_mut(), post.as_mut());
+            }
+            Event::CData(data) => {
+                let text = String::from_utf8_lossy(&data);
+                add_text(&path, &text, thread.as_mut(), post.as_mut());
+            }
+            Event::End(_) => {
+                path.pop();
+                if path.len() == 1 {
+                    if let Some((id, thread)) = thread.take() {
+                        export.threads.insert(id, thread);
+                    }
+                    if let Some(post) = post.take() {
+                        export.posts.push(post);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(export)
+}
+
+/// Record the thread or parent a comment refers to
+fn post_reference(post: &mut DisqusPost, name: &str, element: &BytesStart) {
+    match name {
+        "thread" => post.thread_id = dsq_id(element),
+        "parent" => post.parent_id = Some(dsq_id(element)).filter(|id| !id.is_empty()),
+        _ => {}
+    }
+}
+
+/// Store element text in the field of the thread or comment it belongs to
+fn add_text(
+    path: &[String],
+    text: &str,
+    thread: Option<&mut (String, DisqusThread)>,
+    post: Option<&mut DisqusPost>,
+) {
+    let path: Vec<&str> = path.iter().skip(1).map(String::as_str).collect();
+
+    if let Some((_, thread)) = thread {
+        match path.as_slice() {
+            ["thread", "id"] => thread.identifier.push_str(text),
+            ["thread", "link"] => thread.link.push_str(text),
+            _ => {}
+        }
+    } else if let Some(post) = post {
+        match path.as_slice() {
+            ["post", "message"] => post.message.push_str(text),
+            ["post", "createdAt"] => post.created_at.push_str(text),
+            ["post", "isDeleted"] => post.is_deleted = text.trim() == "true",
+            ["post", "isSpam"] => post.is_spam = text.trim() == "true",
+            ["post", "author", "name"] => post.author_name.push_str(text),
+            ["post", "author", "avatar", ..] => {
+                post.author_avatar_url
+                    .get_or_insert_with(|| text.trim().to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Element name without its namespace prefix
+fn local_name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.local_name().as_ref()).into_owned()
+}
+
+/// Value of the element's `dsq:id` attribute, or an empty string
+fn dsq_id(element: &BytesStart) -> String {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == b"id")
+        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+        .unwrap_or_default()
+}
+
+/// Plain text of a Disqus HTML message
+///
+/// Paragraphs and line breaks become newlines, other tags are dropped and
+/// the common HTML entities decoded.
+pub(crate) fn html_to_text(html: &str) -> String {
+    let html = html
+        .replace("</p>", "\n\n")
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = include_str!("../../fixtures/disqus/export.xml");
+
+    #[test]
+    fn test_parse_export_reads_threads_and_posts() {
+        let export = parse_export(EXPORT).unwrap();
+
+        assert_eq!(export.threads.len(), 3);
+        assert_eq!(
+            export.threads["1001"].slug_candidates(),
+            ["hello-world", "hello-world-id"]
+        );
+
+        let first = &export.posts[0];
+        assert_eq!(first.dsq_id, "5001");
+        assert_eq!(first.thread_id, "1001");
+        assert_eq!(first.parent_id, None);
+        assert_eq!(first.author_name, "Jane Doe");
+        assert_eq!(
+            first.author_avatar_url.as_deref(),
+            Some("https://disqus.com/api/users/avatars/jane.jpg")
+        );
+        assert_eq!(first.created_at, "2019-03-01T10:00:00Z");
+        assert!(!first.is_deleted && !first.is_spam);
+
+        let reply = &export.posts[1];
+        assert_eq!(reply.parent_id.as_deref(), Some("5001"));
+        assert_eq!(reply.author_avatar_url, None);
+    }
+
+    #[test]
+    fn test_parse_export_rejects_malformed_xml() {
+        assert!(matches!(
+            parse_export("<disqus><thread></disqus>"),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text(
+                "<p>Great post &amp; thanks!</p><p>Line<br>two <a href=\"x\">link</a></p>"
+            ),
+            "Great post & thanks!\n\nLine\ntwo link"
+        );
+    }
+}
@@ -1,13 +1,48 @@
-use domain::{Config, ConfigRepository, Result, UpdateConfigRequest};
-use std::sync::Arc;
+use crate::{Clients, SharedClients};
+use domain::{
+    Config, ConfigRepository, Result, UpdateConfigRequest, UpdateSiteConfig, USER_MANAGE,
+};
+use std::sync::{Arc, RwLock};
+
+/// Live configuration shared by every service that reads config values
+///
+/// Services read through this handle on each use, so a reload is picked up
+/// without restarting the server.
+pub type SharedConfig = Arc<RwLock<Config>>;
 
 pub struct ConfigService {
     repo: Arc<dyn ConfigRepository>,
+    config: SharedConfig,
+    clients: Option<SharedClients>,
 }
 
 impl ConfigService {
-    pub fn new(repo: Arc<dyn ConfigRepository>) -> Self {
-        Self { repo }
+    pub fn new(repo: Arc<dyn ConfigRepository>, config: SharedConfig) -> Self {
+        Self {
+            repo,
+            config,
+            clients: None,
+        }
+    }
+
+    /// Attach the outbound clients rebuilt whenever the configuration changes
+    pub fn with_clients(mut self, clients: SharedClients) -> Self {
+        self.clients = Some(clients);
+        self
+    }
+
+    /// Handle to the live configuration
+    pub fn shared(&self) -> SharedConfig {
+        self.config.clone()
+    }
+
+    /// Re-read the configuration file from disk and apply it to all services
+    pub async fn reload(&self) -> Result<Config> {
+        let config = self.repo.get_config().await?;
+        let clients = self.build_clients(&config)?;
+        self.apply(&config, clients);
+        tracing::info!("Configuration reloaded");
+        Ok(config)
     }
 
     /// Get the current configuration
@@ -32,12 +67,27 @@ impl ConfigService {
             if let Some(port) = server.port {
                 config.server.port = port;
             }
+            if let Some(allowed_origins) = server.allowed_origins {
+                config.server.allowed_origins = allowed_origins;
+            }
+            if let Some(cors_max_age_seconds) = server.cors_max_age_seconds {
+                config.server.cors_max_age_seconds = cors_max_age_seconds;
+            }
+            if let Some(metrics_token) = server.metrics_token {
+                config.server.metrics_token = Some(metrics_token).filter(|t| !t.is_empty());
+            }
+            if let Some(compression_level) = server.compression_level {
+                config.server.compression_level = Some(compression_level);
+            }
         }
 
         if let Some(auth) = request.auth {
             if let Some(jwt_secret) = auth.jwt_secret {
                 config.auth.jwt_secret = jwt_secret;
             }
+            if let Some(reserved_usernames) = auth.reserved_usernames {
+                config.auth.reserved_usernames = reserved_usernames;
+            }
         }
 
         if let Some(storage) = request.storage {
@@ -47,6 +97,21 @@ impl ConfigService {
             if let Some(cache_dir) = storage.cache_dir {
                 config.storage.cache_dir = cache_dir;
             }
+            if let Some(max_image_dimension) = storage.max_image_dimension {
+                config.storage.max_image_dimension = Some(max_image_dimension);
+            }
+            if let Some(max_file_size_mb) = storage.max_file_size_mb {
+                config.storage.max_file_size_mb = Some(max_file_size_mb);
+            }
+            if let Some(max_storage_per_user_mb) = storage.max_storage_per_user_mb {
+                config.storage.max_storage_per_user_mb = Some(max_storage_per_user_mb);
+            }
+            if let Some(mirror_external_images) = storage.mirror_external_images {
+                config.storage.mirror_external_images = mirror_external_images;
+            }
+            if let Some(allowed_extensions) = storage.allowed_extensions {
+                config.storage.allowed_extensions = allowed_extensions;
+            }
         }
 
         if let Some(github) = request.github {
@@ -62,6 +127,12 @@ impl ConfigService {
             if let Some(allow_registration) = site.allow_registration {
                 config.site.allow_registration = allow_registration;
             }
+            if let Some(invite_only) = site.invite_only {
+                config.site.invite_only = invite_only;
+            }
+            if let Some(archive_after_days) = site.archive_after_days {
+                config.site.archive_after_days = Some(archive_after_days).filter(|d| *d > 0);
+            }
         }
 
         if let Some(indexnow) = request.indexnow {
@@ -76,7 +147,203 @@ impl ConfigService {
             }
         }
 
+        if let Some(webhook) = request.webhook {
+            if let Some(url) = webhook.url {
+                config.webhook.url = Some(url).filter(|u| !u.is_empty());
+            }
+            if let Some(secret) = webhook.secret {
+                config.webhook.secret = Some(secret).filter(|s| !s.is_empty());
+            }
+        }
+
+        if let Some(comments) = request.comments {
+            if let Some(require_moderation) = comments.require_moderation {
+                config.comments.require_moderation = require_moderation;
+            }
+        }
+
+        if let Some(smtp) = request.smtp {
+            if let Some(enabled) = smtp.enabled {
+                config.smtp.enabled = enabled;
+            }
+            if let Some(host) = smtp.host {
+                config.smtp.host = host;
+            }
+            if let Some(port) = smtp.port {
+                config.smtp.port = port;
+            }
+            if let Some(username) = smtp.username {
+                config.smtp.username = Some(username).filter(|u| !u.is_empty());
+            }
+            if let Some(password) = smtp.password {
+                config.smtp.password = Some(password).filter(|p| !p.is_empty());
+            }
+            if let Some(from_address) = smtp.from_address {
+                config.smtp.from_address = from_address;
+            }
+        }
+
+        let clients = self.build_clients(&config)?;
         self.repo.save_config(&config).await?;
+        self.apply(&config, clients);
         Ok(config)
     }
+
+    /// Build the outbound clients for `config` before anything is applied,
+    /// so invalid settings leave the running configuration untouched
+    fn build_clients(&self, config: &Config) -> Result<Option<Clients>> {
+        self.clients
+            .as_ref()
+            .map(|_| Clients::from_config(config))
+            .transpose()
+    }
+
+    fn apply(&self, config: &Config, clients: Option<Clients>) {
+        *self.config.write().unwrap() = config.clone();
+        if let (Some(shared), Some(clients)) = (&self.clients, clients) {
+            *shared.write().unwrap() = clients;
+        }
+    }
+
+    /// Update site settings and persist them to the configuration file
+    ///
+    /// Requires USER_MANAGE. Only the fields set in `update` change; the new
+    /// values are written to disk and applied to the live configuration.
+    pub async fn update_site_config(
+        &self,
+        update: UpdateSiteConfig,
+        requester_permissions: u64,
+    ) -> Result<Config> {
+        domain::check_permission(requester_permissions, USER_MANAGE)?;
+
+        self.update(UpdateConfigRequest {
+            site: Some(update),
+            ..Default::default()
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{shared_config, test_config, MockUserRepo};
+    use crate::UserService;
+    use domain::{Error, ErrorCode};
+    use infrastructure::ConfigRepositoryImpl;
+    use std::path::Path;
+
+    fn temp_config_path() -> &'static Path {
+        let path = std::env::temp_dir().join(format!("peng-blog-{}.toml", uuid::Uuid::new_v4()));
+        Box::leak(path.into_boxed_path())
+    }
+
+    async fn write_config(path: &'static Path, allow_registration: bool) {
+        let mut config = test_config();
+        config.site.allow_registration = allow_registration;
+        ConfigRepositoryImpl::with_path(path)
+            .save_config(&config)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reload_applies_new_config_to_services() {
+        let path = temp_config_path();
+        write_config(path, false).await;
+
+        let repo = Arc::new(ConfigRepositoryImpl::with_path(path));
+        let shared = shared_config(repo.get_config().await.unwrap());
+        let config_service = ConfigService::new(repo, shared.clone());
+        let user_service = UserService::new(Arc::new(MockUserRepo::with_users(vec![])), shared);
+
+        let result = user_service
+            .register("alice".to_string(), "password123".to_string(), None)
+            .await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        write_config(path, true).await;
+        let reloaded = config_service.reload().await.unwrap();
+        assert!(reloaded.site.allow_registration);
+
+        let result = user_service
+            .register("alice".to_string(), "password123".to_string(), None)
+            .await;
+        assert!(result.is_ok());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_reload_rebuilds_clients() {
+        let path = temp_config_path();
+        write_config(path, false).await;
+
+        let repo = Arc::new(ConfigRepositoryImpl::with_path(path));
+        let config = repo.get_config().await.unwrap();
+        let clients = Clients::from_config(&config).unwrap().shared();
+        let service = ConfigService::new(repo.clone(), shared_config(config.clone()))
+            .with_clients(clients.clone());
+        assert!(clients.read().unwrap().webhook.is_none());
+
+        let mut config = config;
+        config.webhook.url = Some("https://hooks.example.com/blog".to_string());
+        repo.save_config(&config).await.unwrap();
+        service.reload().await.unwrap();
+        assert!(clients.read().unwrap().webhook.is_some());
+
+        config.smtp.enabled = true;
+        config.smtp.host = "smtp.example.com".to_string();
+        config.smtp.from_address = "not an address".to_string();
+        repo.save_config(&config).await.unwrap();
+        assert!(matches!(service.reload().await, Err(Error::Validation(_))));
+        assert!(!service.shared().read().unwrap().smtp.enabled);
+        assert!(clients.read().unwrap().email.is_none());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_update_site_config_writes_toml_file() {
+        let path = temp_config_path();
+        write_config(path, false).await;
+
+        let repo = Arc::new(ConfigRepositoryImpl::with_path(path));
+        let shared = shared_config(repo.get_config().await.unwrap());
+        let service = ConfigService::new(repo, shared.clone());
+        let update = UpdateSiteConfig {
+            allow_registration: Some(true),
+            archive_after_days: Some(30),
+            ..Default::default()
+        };
+
+        let result = service
+            .update_site_config(update.clone(), domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+        let unchanged = std::fs::read_to_string(path).unwrap();
+        assert!(unchanged.contains("allow_registration = false"));
+
+        let config = service
+            .update_site_config(update, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert!(config.site.allow_registration);
+        assert!(shared.read().unwrap().site.allow_registration);
+
+        let saved = std::fs::read_to_string(path).unwrap();
+        assert!(saved.contains("[site]"));
+        assert!(saved.contains("allow_registration = true"));
+        assert!(saved.contains("archive_after_days = 30"));
+        assert!(saved.contains("invite_only = false"));
+        assert!(!path.with_extension("toml.tmp").exists());
+
+        let _ = std::fs::remove_file(path);
+    }
 }
@@ -7,10 +7,47 @@
 //! - Simple CRUD operations on files
 //! - No special cases - all files follow the same rules
 //! - File system operations are abstracted through repository
+//! - Identical content is stored once and shared by reference
 
-use domain::FileRepository;
-use domain::{Error, File, FileResponse, Result, UploadFile};
+use crate::internal_error;
+use crate::PermissionService;
+use domain::{
+    Error, ErrorCode, File, FileListing, FileResponse, FileServeResponse, Result, StorageUsage,
+    UploadFile, DEFAULT_ALLOWED_EXTENSIONS, USER_MANAGE,
+};
+use domain::{FileRepository, UserRepository};
+use image::{GenericImageView, ImageFormat};
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, SeekFrom};
 use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Default bounding box for generated thumbnails, in pixels
+pub const DEFAULT_MAX_IMAGE_DIMENSION: u32 = 400;
+
+/// Default upload size limit, in megabytes
+pub const DEFAULT_MAX_FILE_SIZE_MB: u64 = 10;
+
+/// Maximum page size when listing all files
+pub const MAX_ADMIN_FILE_LIMIT: u64 = 200;
+
+/// Content types accepted for upload
+///
+/// `application/octet-stream` is narrowed down by file extension instead,
+/// since browsers often send it for types they do not know.
+const ALLOWED_CONTENT_TYPES: [&str; 8] = [
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+    "text/plain",
+    "text/markdown",
+    "application/octet-stream",
+];
+
+/// Content types that get a thumbnail generated on upload
+const THUMBNAIL_CONTENT_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
 
 /// File service for managing user uploads
 ///
@@ -24,8 +61,14 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct FileService {
     file_repo: Arc<dyn FileRepository>,
+    permissions: Arc<PermissionService>,
+    user_repo: Option<Arc<dyn UserRepository>>,
     upload_dir: String,
     base_url: String,
+    max_image_dimension: u32,
+    max_file_size_mb: u64,
+    max_storage_per_user_mb: Option<u64>,
+    allowed_extensions: Vec<String>,
 }
 
 impl FileService {
@@ -33,16 +76,69 @@ impl FileService {
     ///
     /// # Arguments
     /// * `file_repo` - The file repository implementation (wrapped in Arc)
+    /// * `permissions` - Permission service used to authorize managing other users' files
     /// * `upload_dir` - Directory where files are stored (absolute path)
     /// * `base_url` - Base URL for file access (e.g., "http://example.com")
-    pub fn new(file_repo: Arc<dyn FileRepository>, upload_dir: String, base_url: String) -> Self {
+    pub fn new(
+        file_repo: Arc<dyn FileRepository>,
+        permissions: Arc<PermissionService>,
+        upload_dir: String,
+        base_url: String,
+    ) -> Self {
         Self {
             file_repo,
+            permissions,
+            user_repo: None,
             upload_dir,
             base_url,
+            max_image_dimension: DEFAULT_MAX_IMAGE_DIMENSION,
+            max_file_size_mb: DEFAULT_MAX_FILE_SIZE_MB,
+            max_storage_per_user_mb: None,
+            allowed_extensions: DEFAULT_ALLOWED_EXTENSIONS
+                .iter()
+                .map(|extension| extension.to_string())
+                .collect(),
         }
     }
 
+    /// Attach the user repository used to check new owners when moving files
+    pub fn with_user_repository(mut self, user_repo: Arc<dyn UserRepository>) -> Self {
+        self.user_repo = Some(user_repo);
+        self
+    }
+
+    /// Set the file extensions accepted for upload
+    ///
+    /// Extensions are compared case-insensitively; a leading dot is ignored.
+    pub fn with_allowed_extensions(mut self, allowed_extensions: Vec<String>) -> Self {
+        self.allowed_extensions = allowed_extensions
+            .iter()
+            .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+            .filter(|extension| !extension.is_empty())
+            .collect();
+        self
+    }
+
+    /// Set the largest accepted upload, in megabytes
+    pub fn with_max_file_size_mb(mut self, max_file_size_mb: Option<u64>) -> Self {
+        self.max_file_size_mb = max_file_size_mb.unwrap_or(DEFAULT_MAX_FILE_SIZE_MB);
+        self
+    }
+
+    /// Set the total upload storage each non-admin user may use, in megabytes
+    ///
+    /// `None` leaves storage unlimited.
+    pub fn with_max_storage_per_user_mb(mut self, max_storage_per_user_mb: Option<u64>) -> Self {
+        self.max_storage_per_user_mb = max_storage_per_user_mb;
+        self
+    }
+
+    /// Set the bounding box used for image thumbnails
+    pub fn with_max_image_dimension(mut self, max_image_dimension: Option<u32>) -> Self {
+        self.max_image_dimension = max_image_dimension.unwrap_or(DEFAULT_MAX_IMAGE_DIMENSION);
+        self
+    }
+
     /// Upload a new file
     ///
     /// # Arguments
@@ -51,94 +147,113 @@ impl FileService {
     ///
     /// # Returns
     /// * `Ok(FileResponse)` - The uploaded file information
-    /// * `Err(Error::Validation)` - File size exceeds limit or invalid type
+    /// * `Err(Error::Validation)` - File size exceeds limit, invalid type, or
+    ///   the upload would take the user over their storage quota
     /// * `Err(Error::Internal)` - File system or database error
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
     pub async fn upload_file(
         &self,
         user_id: uuid::Uuid,
         upload: UploadFile,
     ) -> Result<FileResponse> {
-        // Validate file size (max 10MB)
-        const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
-        if upload.data.len() as u64 > MAX_FILE_SIZE {
-            return Err(Error::Validation(
-                "File size exceeds 10MB limit".to_string(),
-            ));
+        // Validate file size
+        if upload.data.len() as u64 > self.max_file_size_mb * 1024 * 1024 {
+            return Err(Error::Validation(format!(
+                "File size exceeds {}MB limit",
+                self.max_file_size_mb
+            )));
         }
 
         // Validate content type - allow octet-stream and detect from extension
         // Browsers often send octet-stream for unknown file types
-        let allowed_types = [
-            "image/jpeg",
-            "image/png",
-            "image/gif",
-            "image/webp",
-            "application/pdf",
-            "text/plain",
-            "text/markdown",
-            "application/octet-stream", // Will be validated from extension
-        ];
-        if !allowed_types.contains(&upload.content_type.as_str()) {
+        if !ALLOWED_CONTENT_TYPES.contains(&upload.content_type.as_str()) {
             return Err(Error::Validation(format!(
                 "Invalid content type: {}. Allowed types: {}",
                 upload.content_type,
-                allowed_types.join(", ")
+                ALLOWED_CONTENT_TYPES.join(", ")
             )));
         }
 
-        // If octet-stream, validate based on file extension
-        if upload.content_type == "application/octet-stream" {
-            let allowed_extensions = ["jpg", "jpeg", "png", "gif", "webp", "pdf", "txt", "md"];
-            let extension = std::path::Path::new(&upload.filename)
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-
-            if !allowed_extensions.contains(&extension.as_str()) {
-                return Err(Error::Validation(format!(
-                    "Invalid file type for octet-stream. Allowed extensions: {}",
-                    allowed_extensions.join(", ")
-                )));
+        // The declared type comes from the client, so check it against the
+        // actual bytes. Text has no magic bytes and is taken as declared.
+        if let Some(detected) = detect_content_type(&upload.data) {
+            if detected != upload.content_type {
+                tracing::warn!(
+                    "Upload {} declared as {} but detected as {}",
+                    upload.filename,
+                    upload.content_type,
+                    detected
+                );
+                let declared_generic = upload.content_type == "application/octet-stream";
+                if !declared_generic || !ALLOWED_CONTENT_TYPES.contains(&detected.as_str()) {
+                    return Err(Error::Validation(format!(
+                        "File content is {} but was declared as {}",
+                        detected, upload.content_type
+                    )));
+                }
             }
-
-            // For octet-stream files, infer content type from extension
-            // This will be stored in the database for proper MIME type handling
         }
 
-        // Generate unique filename
-        let filename = self.generate_filename(&upload.filename);
-        let file_path = format!("{}/{}", self.upload_dir.trim_end_matches('/'), filename);
+        // The extension decides how the file is served back, so it must be
+        // allowed whatever the content type
+        self.validate_extension(&upload.filename)?;
+
+        // Deduplicate by content hash before touching the disk
+        let hash = hex::encode(Sha256::digest(&upload.data));
+
+        if let Some(existing) = self.file_repo.find_by_hash_for_user(&hash, user_id).await? {
+            return Ok(Self::to_response(existing));
+        }
 
         // Get file size before moving data
         let size_bytes = upload.data.len() as u64;
 
-        // Write file to disk
-        tokio::fs::write(&file_path, upload.data)
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to write file: {}", e)))?;
+        if let Some(quota) = self.quota_bytes(user_id).await? {
+            let used = self.file_repo.total_storage_used_by_user(user_id).await?;
+            if used.saturating_add(size_bytes) > quota {
+                return Err(Error::Validation("Storage quota exceeded".to_string()));
+            }
+        }
+
+        // Another user already uploaded identical content: share the stored file
+        let (filename, thumbnail_url) = match self.file_repo.find_by_hash(&hash).await? {
+            Some(existing) => (existing.filename, existing.thumbnail_url),
+            None => {
+                let filename = self.generate_filename(&upload.filename);
+
+                let thumbnail_url =
+                    if THUMBNAIL_CONTENT_TYPES.contains(&upload.content_type.as_str()) {
+                        self.store_thumbnail(&filename, upload.data.clone()).await
+                    } else {
+                        None
+                    };
+
+                // Write file to disk
+                tokio::fs::write(self.file_path(&filename), upload.data)
+                    .await
+                    .map_err(|e| internal_error(format!("Failed to write file: {}", e)))?;
+
+                (filename, thumbnail_url)
+            }
+        };
 
         // Create file record in database
         let url = File::generate_url(&self.base_url, &filename);
-        let file = File::new(
+        let mut file = File::new(
             user_id,
-            filename.clone(),
+            filename,
             upload.filename,
             upload.content_type,
             size_bytes,
             url,
-        );
+        )
+        .with_hash(hash);
+        file.thumbnail_url = thumbnail_url;
 
         let saved_file = self.file_repo.create_file(file).await?;
+        tracing::info!("File {} uploaded by user {}", saved_file.id, user_id);
 
-        Ok(FileResponse {
-            id: saved_file.id,
-            url: saved_file.url,
-            filename: saved_file.filename,
-            original_filename: saved_file.original_filename,
-            content_type: saved_file.content_type,
-            size_bytes: saved_file.size_bytes,
-        })
+        Ok(Self::to_response(saved_file))
     }
 
     /// Get file information by ID
@@ -150,17 +265,125 @@ impl FileService {
     /// * `Ok(Some(FileResponse))` - File information if found
     /// * `Ok(None)` - File not found
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all, fields(file_id = %id))]
     pub async fn get_file(&self, id: uuid::Uuid) -> Result<Option<FileResponse>> {
-        self.file_repo.get_file(id).await.map(|opt| {
-            opt.map(|file| FileResponse {
-                id: file.id,
-                url: file.url,
-                filename: file.filename,
-                original_filename: file.original_filename,
+        self.file_repo
+            .get_file(id)
+            .await
+            .map(|opt| opt.map(Self::to_response))
+    }
+
+    /// Get file metadata by the name the file is stored under
+    ///
+    /// # Returns
+    /// * `Ok(FileResponse)` - The oldest record stored under `filename`
+    /// * `Err(Error::NotFound)` - No file with that name
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_file_by_filename(&self, filename: &str) -> Result<FileResponse> {
+        self.file_repo
+            .get_file_by_filename(filename)
+            .await?
+            .map(Self::to_response)
+            .ok_or_else(|| Error::NotFound("File not found".to_string()))
+    }
+
+    /// Get file metadata from a download URL, e.g. one found in post content
+    ///
+    /// # Returns
+    /// * `Ok(FileResponse)` - The file the URL points to
+    /// * `Err(Error::NotFound)` - Not a download URL, or no such file
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_file_by_url(&self, url: &str) -> Result<FileResponse> {
+        let filename = File::filename_from_url(&self.base_url, url)
+            .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
+        self.get_file_by_filename(filename).await
+    }
+
+    /// Read a file for download, honoring a `Range: bytes=...` header
+    ///
+    /// Only single ranges are supported (`N-M`, `N-` and suffix `-N`).
+    /// Malformed or multi-range headers are ignored and the whole file is
+    /// sent, as RFC 9110 allows; ranges starting past the end yield 416.
+    /// Partial responses read only the requested bytes from disk.
+    ///
+    /// # Returns
+    /// * `Ok(FileServeResponse)` - The bytes to send and the response status
+    /// * `Err(Error::NotFound)` - File record or stored content not found
+    #[tracing::instrument(level = "debug", skip_all, fields(file_id = %id))]
+    pub async fn serve_file(
+        &self,
+        id: uuid::Uuid,
+        range_header: Option<&str>,
+    ) -> Result<FileServeResponse> {
+        let file = self
+            .file_repo
+            .get_file(id)
+            .await?
+            .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
+
+        let mut handle = tokio::fs::File::open(self.file_path(&file.filename))
+            .await
+            .map_err(|_| Error::NotFound("File not found".to_string()))?;
+        let size = handle
+            .metadata()
+            .await
+            .map_err(|e| internal_error(format!("Failed to read file metadata: {}", e)))?
+            .len();
+        let read_error = |e: std::io::Error| internal_error(format!("Failed to read file: {}", e));
+
+        match range_header.and_then(|header| parse_range(header, size)) {
+            None => {
+                let mut data = Vec::with_capacity(size as usize);
+                handle.read_to_end(&mut data).await.map_err(read_error)?;
+                Ok(FileServeResponse {
+                    data,
+                    content_type: file.content_type,
+                    content_range: None,
+                    status: 200,
+                })
+            }
+            Some(None) => Ok(FileServeResponse {
+                data: Vec::new(),
                 content_type: file.content_type,
-                size_bytes: file.size_bytes,
-            })
-        })
+                content_range: Some(format!("bytes */{}", size)),
+                status: 416,
+            }),
+            Some(Some((start, end))) => {
+                let length = end - start + 1;
+                let mut data = Vec::with_capacity(length as usize);
+                handle
+                    .seek(SeekFrom::Start(start))
+                    .await
+                    .map_err(read_error)?;
+                handle
+                    .take(length)
+                    .read_to_end(&mut data)
+                    .await
+                    .map_err(read_error)?;
+                Ok(FileServeResponse {
+                    data,
+                    content_type: file.content_type,
+                    content_range: Some(format!("bytes {}-{}/{}", start, end, size)),
+                    status: 206,
+                })
+            }
+        }
+    }
+
+    /// Report how much of their storage quota a user has used
+    ///
+    /// Admins have no quota, so `quota_bytes` is `None` for them.
+    ///
+    /// # Returns
+    /// * `Ok(StorageUsage)` - Bytes used, the quota and the share in use
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn get_storage_usage(&self, user_id: uuid::Uuid) -> Result<StorageUsage> {
+        let used_bytes = self.file_repo.total_storage_used_by_user(user_id).await?;
+        Ok(StorageUsage::new(
+            used_bytes,
+            self.quota_bytes(user_id).await?,
+        ))
     }
 
     /// List files uploaded by a user
@@ -172,58 +395,248 @@ impl FileService {
     /// # Returns
     /// * `Ok(Vec<FileResponse>)` - List of files
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
     pub async fn list_files(&self, user_id: uuid::Uuid, limit: u64) -> Result<Vec<FileResponse>> {
         let files = self.file_repo.list_files_by_user(user_id, limit).await?;
-        Ok(files
-            .into_iter()
-            .map(|file| FileResponse {
-                id: file.id,
-                url: file.url,
-                filename: file.filename,
-                original_filename: file.original_filename,
-                content_type: file.content_type,
-                size_bytes: file.size_bytes,
-            })
-            .collect())
+        Ok(files.into_iter().map(Self::to_response).collect())
+    }
+
+    /// List files uploaded by all users (admin only)
+    ///
+    /// # Arguments
+    /// * `requester_permissions` - Permissions of the requesting user
+    /// * `limit` - Page size, clamped to 1..=MAX_ADMIN_FILE_LIMIT
+    /// * `offset` - Number of files to skip
+    /// * `content_type_filter` - Exact content type, or a prefix ending in `/`
+    ///   such as `image/`
+    ///
+    /// # Returns
+    /// * `Ok(FileListing)` - The page, with the total count and size of all
+    ///   matching files
+    /// * `Err(Error::Validation)` - Requester lacks USER_MANAGE
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_all_files(
+        &self,
+        requester_permissions: u64,
+        limit: u64,
+        offset: u64,
+        content_type_filter: Option<&str>,
+    ) -> Result<FileListing> {
+        domain::check_permission(requester_permissions, USER_MANAGE)?;
+
+        let content_type_filter = content_type_filter
+            .map(str::trim)
+            .filter(|filter| !filter.is_empty());
+        let limit = limit.clamp(1, MAX_ADMIN_FILE_LIMIT);
+
+        let (files, total) = self
+            .file_repo
+            .list_all_files(limit, offset, content_type_filter)
+            .await?;
+        let total_size_bytes = self.file_repo.total_file_size(content_type_filter).await?;
+
+        Ok(FileListing {
+            files,
+            total,
+            total_size_bytes,
+        })
+    }
+
+    /// Reassign a file to another user (admin only)
+    ///
+    /// Only the file record changes owner; the stored file stays where it is.
+    ///
+    /// # Arguments
+    /// * `file_id` - The file to move
+    /// * `new_owner_id` - The user who will own the file
+    /// * `requester_id` - The admin moving the file
+    /// * `requester_permissions` - Permission bits of the requester
+    ///
+    /// # Returns
+    /// * `Ok(File)` - The file record with its new owner
+    /// * `Err(Error::NotFound)` - File not found
+    /// * `Err(Error::CodedError)` - `UserNotFound` when the new owner does not
+    ///   exist, `PermissionDenied` when the requester lacks USER_MANAGE
+    #[tracing::instrument(level = "debug", skip_all, fields(%file_id, %new_owner_id, %requester_id))]
+    pub async fn move_file(
+        &self,
+        file_id: uuid::Uuid,
+        new_owner_id: uuid::Uuid,
+        requester_id: uuid::Uuid,
+        requester_permissions: u64,
+    ) -> Result<File> {
+        domain::check_permission(requester_permissions, USER_MANAGE)?;
+        let user_repo = self
+            .user_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("User repository not configured"))?;
+
+        let file = self
+            .file_repo
+            .get_file(file_id)
+            .await?
+            .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
+        if user_repo.find_by_id(new_owner_id).await?.is_none() {
+            return Err(Error::coded(
+                ErrorCode::UserNotFound,
+                format!("User with id {} not found", new_owner_id),
+            ));
+        }
+
+        let moved = self
+            .file_repo
+            .update_file_owner(file_id, new_owner_id)
+            .await?;
+        tracing::info!(
+            "File {} moved from user {} to user {} by {}",
+            file_id,
+            file.user_id,
+            new_owner_id,
+            requester_id
+        );
+
+        Ok(moved)
     }
 
     /// Delete a file
     ///
+    /// Owners can always delete their files; users with FILE_MANAGE can
+    /// delete files belonging to anyone.
+    ///
     /// # Arguments
     /// * `id` - The file ID
-    /// * `user_id` - The ID of the user (for ownership verification)
+    /// * `user_id` - The ID of the requesting user
     ///
     /// # Returns
     /// * `Ok(())` - File deleted
-    /// * `Err(Error::NotFound)` - File not found or not owned by user
+    /// * `Err(Error::NotFound)` - File not found
+    /// * `Err(Error::Validation)` - User neither owns the file nor has FILE_MANAGE
     /// * `Err(Error)` - File system or database error
+    #[tracing::instrument(level = "debug", skip_all, fields(file_id = %id, %user_id))]
     pub async fn delete_file(&self, id: uuid::Uuid, user_id: uuid::Uuid) -> Result<()> {
         // Check ownership first
         let file_opt = self.file_repo.get_file(id).await?;
         let file = file_opt.ok_or_else(|| Error::NotFound("File not found".to_string()))?;
 
-        if !file.is_owned_by(user_id) {
+        if !file.is_owned_by(user_id) && !self.permissions.can_manage_files(user_id).await? {
+            tracing::warn!("User {} denied deleting file {}", user_id, id);
             return Err(Error::Validation(
                 "You can only delete your own files".to_string(),
             ));
         }
 
         // Delete from database
-        self.file_repo.delete_file(id, user_id).await?;
+        self.file_repo.delete_file(id, file.user_id).await?;
+        tracing::info!("File {} deleted by user {}", id, user_id);
+
+        // Deduplicated content stays on disk while other records still reference it
+        if let Some(hash) = &file.sha256_hash {
+            if self.file_repo.count_by_hash(hash).await? > 0 {
+                return Ok(());
+            }
+        }
 
         // Delete from filesystem (best effort)
-        let file_path = format!(
-            "{}/{}",
-            self.upload_dir.trim_end_matches('/'),
-            file.filename
-        );
-        let _ = tokio::fs::remove_file(file_path).await;
+        let _ = tokio::fs::remove_file(self.file_path(&file.filename)).await;
+        if file.thumbnail_url.is_some() {
+            let _ =
+                tokio::fs::remove_file(self.file_path(&Self::thumbnail_filename(&file.filename)))
+                    .await;
+        }
 
         Ok(())
     }
 }
 
 impl FileService {
+    /// Storage quota of a user in bytes, `None` when unlimited or an admin
+    async fn quota_bytes(&self, user_id: uuid::Uuid) -> Result<Option<u64>> {
+        match self.max_storage_per_user_mb {
+            Some(_) if self.permissions.is_admin(user_id).await? => Ok(None),
+            Some(megabytes) => Ok(Some(megabytes.saturating_mul(1024 * 1024))),
+            None => Ok(None),
+        }
+    }
+
+    /// Check that a file name ends in one of the allowed extensions
+    fn validate_extension(&self, filename: &str) -> Result<()> {
+        let extension = std::path::Path::new(filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+
+        match extension {
+            Some(extension) if self.allowed_extensions.contains(&extension) => Ok(()),
+            Some(extension) => Err(Error::Validation(format!(
+                "File extension .{} is not allowed. Allowed extensions: {}",
+                extension,
+                self.allowed_extensions.join(", ")
+            ))),
+            None => Err(Error::Validation(format!(
+                "File name has no extension. Allowed extensions: {}",
+                self.allowed_extensions.join(", ")
+            ))),
+        }
+    }
+
+    /// Absolute path of a stored file inside the upload directory
+    fn file_path(&self, filename: &str) -> String {
+        format!("{}/{}", self.upload_dir.trim_end_matches('/'), filename)
+    }
+
+    /// Convert a file record into its API response
+    fn to_response(file: File) -> FileResponse {
+        FileResponse {
+            id: file.id,
+            url: file.url,
+            filename: file.filename,
+            original_filename: file.original_filename,
+            content_type: file.content_type,
+            size_bytes: file.size_bytes,
+            thumbnail_url: file.thumbnail_url,
+        }
+    }
+
+    /// Thumbnail filename for a stored file, e.g. `{uuid}_thumb.webp`
+    fn thumbnail_filename(filename: &str) -> String {
+        let stem = std::path::Path::new(filename)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(filename);
+        format!("{}_thumb.webp", stem)
+    }
+
+    /// Generate and store a thumbnail next to the original file
+    ///
+    /// Thumbnails are a convenience: undecodable images are logged and
+    /// uploaded without one instead of failing the upload.
+    ///
+    /// # Returns
+    /// The thumbnail URL, or `None` if no thumbnail could be generated
+    async fn store_thumbnail(&self, filename: &str, data: Vec<u8>) -> Option<String> {
+        let max_dimension = self.max_image_dimension;
+        let encoded = tokio::task::spawn_blocking(move || make_thumbnail(&data, max_dimension))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result);
+
+        let encoded = match encoded {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                tracing::warn!("Failed to generate thumbnail for {}: {}", filename, e);
+                return None;
+            }
+        };
+
+        let thumbnail_filename = Self::thumbnail_filename(filename);
+        if let Err(e) = tokio::fs::write(self.file_path(&thumbnail_filename), encoded).await {
+            tracing::warn!("Failed to write thumbnail {}: {}", thumbnail_filename, e);
+            return None;
+        }
+
+        Some(File::generate_url(&self.base_url, &thumbnail_filename))
+    }
+
     /// Generate a unique filename
     ///
     /// # Arguments
@@ -246,50 +659,387 @@ impl FileService {
     }
 }
 
+/// Decode an image and re-encode it as WebP fitting within `max_dimension`
+///
+/// Images already within the bounding box keep their original size.
+fn make_thumbnail(data: &[u8], max_dimension: u32) -> std::result::Result<Vec<u8>, String> {
+    let image = image::load_from_memory(data).map_err(|e| e.to_string())?;
+
+    let (width, height) = image.dimensions();
+    let image = if width > max_dimension || height > max_dimension {
+        image.thumbnail(max_dimension, max_dimension)
+    } else {
+        image
+    };
+
+    // The WebP encoder only accepts 8-bit RGB(A) buffers
+    let mut encoded = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgba8(image.to_rgba8())
+        .write_to(&mut encoded, ImageFormat::WebP)
+        .map_err(|e| e.to_string())?;
+
+    Ok(encoded.into_inner())
+}
+
+/// Detect the MIME type of file content from its magic bytes
+///
+/// Returns `None` for content without a recognizable signature, such as
+/// plain text.
+pub fn detect_content_type(data: &[u8]) -> Option<String> {
+    infer::get(data).map(|kind| kind.mime_type().to_string())
+}
+
+/// Resolve a `Range` header against a file of `size` bytes
+///
+/// # Returns
+/// * `None` - Header ignored; send the whole file
+/// * `Some(None)` - Range not satisfiable
+/// * `Some(Some((start, end)))` - Inclusive byte range to send
+fn parse_range(header: &str, size: u64) -> Option<Option<(u64, u64)>> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.trim().split_once('-')?;
+
+    let range = if start.is_empty() {
+        let suffix: u64 = end.parse().ok()?;
+        (suffix > 0 && size > 0).then(|| (size.saturating_sub(suffix), size - 1))
+    } else {
+        let start: u64 = start.parse().ok()?;
+        let end = match end {
+            "" => u64::MAX,
+            end => end.parse().ok()?,
+        };
+        if end < start {
+            return None;
+        }
+        (start < size).then(|| (start, end.min(size - 1)))
+    };
+    Some(range)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_trait::async_trait;
-    use domain::FileRepository;
-    use domain::{File, Result};
+    use crate::test_support::{MockFileRepo, MockUserRepo};
+    use domain::{ErrorCode, File};
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::RwLock;
 
-    // Mock repository for testing
-    struct MockFileRepo {
-        files: Arc<RwLock<HashMap<uuid::Uuid, File>>>,
+    fn no_permissions() -> Arc<PermissionService> {
+        Arc::new(PermissionService::new(Arc::new(MockUserRepo::with_users(
+            vec![],
+        ))))
     }
 
-    #[async_trait]
-    impl FileRepository for MockFileRepo {
-        async fn create_file(&self, file: File) -> Result<File> {
-            let mut files = self.files.write().await;
-            files.insert(file.id, file.clone());
-            Ok(file)
-        }
+    /// Service over a repository holding files with the given content types and sizes
+    fn service_with_files(files: &[(&str, u64)]) -> FileService {
+        let base = chrono::Utc::now();
+        let files = files
+            .iter()
+            .enumerate()
+            .map(|(i, (content_type, size))| {
+                let mut file = File::new(
+                    uuid::Uuid::new_v4(),
+                    format!("f{}", i),
+                    format!("f{}", i),
+                    content_type.to_string(),
+                    *size,
+                    String::new(),
+                );
+                file.created_at = base - chrono::Duration::seconds(i as i64);
+                (file.id, file)
+            })
+            .collect();
+        FileService::new(
+            Arc::new(MockFileRepo {
+                files: Arc::new(RwLock::new(files)),
+            }),
+            no_permissions(),
+            "/tmp".to_string(),
+            "http://example.com".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_file_by_filename() {
+        let service = service_with_files(&[("image/png", 10), ("image/jpeg", 20)]);
 
-        async fn get_file(&self, id: uuid::Uuid) -> Result<Option<File>> {
-            let files = self.files.read().await;
-            Ok(files.get(&id).cloned())
+        let file = service.get_file_by_filename("f1").await.unwrap();
+        assert_eq!(file.filename, "f1");
+        assert_eq!(file.content_type, "image/jpeg");
+
+        let missing = service.get_file_by_filename("nope.png").await;
+        assert!(matches!(missing, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_file_by_url_strips_base_url() {
+        let service = service_with_files(&[("image/png", 10)]);
+
+        for url in [
+            "http://example.com/api/files/f0/download",
+            "/api/files/f0/download",
+        ] {
+            let file = service.get_file_by_url(url).await.unwrap();
+            assert_eq!(file.filename, "f0", "{}", url);
         }
 
-        async fn list_files_by_user(&self, user_id: uuid::Uuid, _limit: u64) -> Result<Vec<File>> {
-            let files = self.files.read().await;
-            Ok(files
-                .values()
-                .filter(|f| f.user_id == user_id)
-                .cloned()
-                .collect())
+        for url in [
+            "http://other.example/uploads/f0",
+            "http://example.com/api/files/f0",
+            "http://example.com/api/files/missing.png/download",
+        ] {
+            let result = service.get_file_by_url(url).await;
+            assert!(matches!(result, Err(Error::NotFound(_))), "{}", url);
         }
+    }
+
+    #[tokio::test]
+    async fn test_list_all_files_filters_by_content_type() {
+        let service = service_with_files(&[
+            ("image/jpeg", 100),
+            ("image/png", 20),
+            ("image/jpeg", 5),
+            ("application/pdf", 1000),
+        ]);
+        let admin = domain::ADMIN_PERMISSIONS;
+
+        let exact = service
+            .list_all_files(admin, 50, 0, Some("image/jpeg"))
+            .await
+            .unwrap();
+        assert_eq!(exact.total, 2);
+        assert_eq!(exact.total_size_bytes, 105);
+
+        let prefix = service
+            .list_all_files(admin, 50, 0, Some("image/"))
+            .await
+            .unwrap();
+        assert_eq!(prefix.total, 3);
+        assert_eq!(prefix.total_size_bytes, 125);
+
+        // "image" without the slash is an exact match, not a prefix
+        let partial = service
+            .list_all_files(admin, 50, 0, Some("image"))
+            .await
+            .unwrap();
+        assert_eq!(partial.total, 0);
+
+        let all = service
+            .list_all_files(admin, 50, 0, Some(" "))
+            .await
+            .unwrap();
+        assert_eq!(all.total, 4);
+        assert_eq!(all.total_size_bytes, 1125);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_files_pagination() {
+        let service = service_with_files(&[("text/plain", 1); 5]);
+        let admin = domain::ADMIN_PERMISSIONS;
+
+        let page = service.list_all_files(admin, 2, 4, None).await.unwrap();
+        assert_eq!(page.files.len(), 1);
+        assert_eq!(page.files[0].filename, "f4");
+        assert_eq!(page.total, 5);
+        assert_eq!(page.total_size_bytes, 5);
+
+        let past_end = service.list_all_files(admin, 2, 10, None).await.unwrap();
+        assert!(past_end.files.is_empty());
+        assert_eq!(past_end.total, 5);
+
+        // A zero limit still returns one file rather than nothing
+        let zero = service.list_all_files(admin, 0, 0, None).await.unwrap();
+        assert_eq!(zero.files.len(), 1);
+        assert_eq!(zero.files[0].filename, "f0");
+    }
+
+    #[tokio::test]
+    async fn test_list_all_files_requires_user_manage() {
+        let service = service_with_files(&[("text/plain", 1)]);
+
+        let result = service
+            .list_all_files(domain::DEFAULT_USER_PERMISSIONS, 50, 0, None)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+    }
+
+    /// Service over a single file, with `new_owner` as the only known user
+    fn move_service(new_owner: &domain::User) -> (FileService, File) {
+        let file = File::new(
+            uuid::Uuid::new_v4(),
+            "stored.png".to_string(),
+            "photo.png".to_string(),
+            "image/png".to_string(),
+            10,
+            String::new(),
+        );
+        let service = FileService::new(
+            Arc::new(MockFileRepo {
+                files: Arc::new(RwLock::new(HashMap::from([(file.id, file.clone())]))),
+            }),
+            no_permissions(),
+            "/tmp".to_string(),
+            "http://example.com".to_string(),
+        )
+        .with_user_repository(Arc::new(MockUserRepo::with_users(vec![new_owner.clone()])));
+        (service, file)
+    }
+
+    fn new_owner() -> domain::User {
+        domain::User::new(
+            uuid::Uuid::new_v4(),
+            "bob".to_string(),
+            "hash".to_string(),
+            domain::DEFAULT_USER_PERMISSIONS,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_admin_moves_file_to_another_user() {
+        let owner = new_owner();
+        let (service, file) = move_service(&owner);
 
-        async fn delete_file(&self, id: uuid::Uuid, _user_id: uuid::Uuid) -> Result<()> {
-            let mut files = self.files.write().await;
-            files.remove(&id);
-            Ok(())
+        let moved = service
+            .move_file(file.id, owner.id, uuid::Uuid::new_v4(), domain::USER_MANAGE)
+            .await
+            .unwrap();
+
+        assert_eq!(moved.user_id, owner.id);
+        assert_eq!(moved.filename, file.filename);
+    }
+
+    #[tokio::test]
+    async fn test_move_file_requires_user_manage() {
+        let owner = new_owner();
+        let (service, file) = move_service(&owner);
+
+        let err = service
+            .move_file(
+                file.id,
+                owner.id,
+                uuid::Uuid::new_v4(),
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), ErrorCode::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_move_file_to_unknown_user_is_not_found() {
+        let (service, file) = move_service(&new_owner());
+
+        let err = service
+            .move_file(
+                file.id,
+                uuid::Uuid::new_v4(),
+                uuid::Uuid::new_v4(),
+                domain::USER_MANAGE,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), ErrorCode::UserNotFound);
+        assert!(err.is_not_found());
+    }
+
+    fn temp_upload_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("peng-blog-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn text_upload(filename: &str, data: &[u8]) -> UploadFile {
+        UploadFile {
+            filename: filename.to_string(),
+            content_type: "text/plain".to_string(),
+            data: data.to_vec(),
         }
     }
 
+    #[tokio::test]
+    async fn test_upload_same_content_same_user_returns_existing() {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = FileService::new(
+            repo.clone(),
+            no_permissions(),
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        );
+        let user_id = uuid::Uuid::new_v4();
+
+        let first = service
+            .upload_file(user_id, text_upload("a.txt", b"same content"))
+            .await
+            .unwrap();
+        let second = service
+            .upload_file(user_id, text_upload("b.txt", b"same content"))
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.original_filename, "a.txt");
+        assert_eq!(repo.files.read().await.len(), 1);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_upload_same_content_other_user_shares_stored_file() {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = FileService::new(
+            repo.clone(),
+            no_permissions(),
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        );
+        let alice = uuid::Uuid::new_v4();
+        let bob = uuid::Uuid::new_v4();
+
+        let first = service
+            .upload_file(alice, text_upload("a.txt", b"shared content"))
+            .await
+            .unwrap();
+        let second = service
+            .upload_file(bob, text_upload("b.txt", b"shared content"))
+            .await
+            .unwrap();
+
+        assert_ne!(first.id, second.id);
+        assert_eq!(first.filename, second.filename);
+        assert_eq!(second.original_filename, "b.txt");
+        assert_eq!(repo.files.read().await.len(), 2);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        // The stored file survives while another record still references it
+        service.delete_file(first.id, alice).await.unwrap();
+        assert!(dir.join(&second.filename).exists());
+
+        service.delete_file(second.id, bob).await.unwrap();
+        assert!(!dir.join(&second.filename).exists());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
     #[tokio::test]
     async fn test_upload_file() {
         let repo = Arc::new(MockFileRepo {
@@ -297,6 +1047,7 @@ mod tests {
         });
         let _service = FileService::new(
             repo,
+            no_permissions(),
             "/tmp/uploads".to_string(),
             "http://example.com".to_string(),
         );
@@ -315,6 +1066,213 @@ mod tests {
         // assert!(result.is_ok());
     }
 
+    fn png_upload(width: u32, height: u32) -> UploadFile {
+        let mut data = Cursor::new(Vec::new());
+        image::DynamicImage::new_rgb8(width, height)
+            .write_to(&mut data, ImageFormat::Png)
+            .unwrap();
+        UploadFile {
+            filename: "image.png".to_string(),
+            content_type: "image/png".to_string(),
+            data: data.into_inner(),
+        }
+    }
+
+    async fn thumbnail_dimensions(max_dimension: Option<u32>, upload: UploadFile) -> (u32, u32) {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = FileService::new(
+            repo,
+            no_permissions(),
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        )
+        .with_max_image_dimension(max_dimension);
+
+        let response = service
+            .upload_file(uuid::Uuid::new_v4(), upload)
+            .await
+            .unwrap();
+        let thumbnail_url = response
+            .thumbnail_url
+            .expect("thumbnail should be generated");
+        let thumbnail_filename = FileService::thumbnail_filename(&response.filename);
+        assert!(thumbnail_url.contains(&thumbnail_filename));
+
+        let thumbnail = image::open(dir.join(&thumbnail_filename)).unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+        thumbnail.dimensions()
+    }
+
+    #[tokio::test]
+    async fn test_upload_large_image_generates_capped_thumbnail() {
+        let dimensions = thumbnail_dimensions(None, png_upload(1000, 500)).await;
+        assert_eq!(dimensions, (400, 200));
+
+        let dimensions = thumbnail_dimensions(Some(100), png_upload(300, 600)).await;
+        assert_eq!(dimensions, (50, 100));
+    }
+
+    #[tokio::test]
+    async fn test_upload_small_image_keeps_thumbnail_size() {
+        let dimensions = thumbnail_dimensions(None, png_upload(120, 80)).await;
+        assert_eq!(dimensions, (120, 80));
+    }
+
+    #[tokio::test]
+    async fn test_upload_non_image_has_no_thumbnail() {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = FileService::new(
+            repo,
+            no_permissions(),
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        );
+
+        let response = service
+            .upload_file(uuid::Uuid::new_v4(), text_upload("a.txt", b"plain text"))
+            .await
+            .unwrap();
+        assert!(response.thumbnail_url.is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    async fn upload_as(filename: &str, content_type: &str, data: Vec<u8>) -> Result<FileResponse> {
+        upload_with(|service| service, filename, content_type, data).await
+    }
+
+    async fn upload_with(
+        configure: impl FnOnce(FileService) -> FileService,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<FileResponse> {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = configure(FileService::new(
+            repo,
+            no_permissions(),
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        ));
+
+        let upload = UploadFile {
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            data,
+        };
+        let result = service.upload_file(uuid::Uuid::new_v4(), upload).await;
+        let _ = std::fs::remove_dir_all(dir);
+        result
+    }
+
+    fn jpeg_bytes() -> Vec<u8> {
+        let mut data = Cursor::new(Vec::new());
+        image::DynamicImage::new_rgb8(8, 8)
+            .write_to(&mut data, ImageFormat::Jpeg)
+            .unwrap();
+        data.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_jpeg_declared_as_png() {
+        assert_eq!(
+            detect_content_type(&jpeg_bytes()).as_deref(),
+            Some("image/jpeg")
+        );
+
+        let result = upload_as("photo.png", "image/png", jpeg_bytes()).await;
+
+        match result {
+            Err(Error::Validation(msg)) => assert!(msg.contains("image/jpeg")),
+            _ => panic!("Expected validation error for mismatched content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_accepts_text_without_magic_bytes() {
+        assert_eq!(detect_content_type(b"just some notes"), None);
+
+        let result = upload_as("notes.txt", "text/plain", b"just some notes".to_vec()).await;
+
+        assert_eq!(result.unwrap().content_type, "text/plain");
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_executable_declared_as_image() {
+        let mut elf = b"\x7fELF\x02\x01\x01\x00".to_vec();
+        elf.resize(64, 0);
+
+        let result = upload_as("cat.jpg", "image/jpeg", elf.clone()).await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        let result = upload_as("cat.jpg", "application/octet-stream", elf).await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_octet_stream_with_allowed_content_passes() {
+        let result = upload_as("photo.jpg", "application/octet-stream", jpeg_bytes()).await;
+
+        assert!(result.is_ok());
+    }
+
+    fn validation_message(result: Result<FileResponse>) -> String {
+        match result {
+            Err(Error::Validation(msg)) => msg,
+            _ => panic!("Expected validation error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_file_over_size_limit() {
+        let data = vec![b'a'; 1024 * 1024 + 1];
+        let result = upload_with(
+            |service| service.with_max_file_size_mb(Some(1)),
+            "big.txt",
+            "text/plain",
+            data,
+        )
+        .await;
+
+        assert!(validation_message(result).contains("exceeds 1MB"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_content_type_not_allowed() {
+        let result = upload_as("page.txt", "text/html", b"<p>hi</p>".to_vec()).await;
+
+        assert!(validation_message(result).contains("Invalid content type: text/html"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_extension_not_allowed() {
+        let result = upload_as("shell.php", "text/plain", b"<?php echo 1;".to_vec()).await;
+        assert!(validation_message(result).contains("extension .php is not allowed"));
+
+        let result = upload_as("README", "text/plain", b"read me".to_vec()).await;
+        assert!(validation_message(result).contains("has no extension"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_follows_configured_extensions() {
+        let csv_only = |service: FileService| service.with_allowed_extensions(vec![".CSV".into()]);
+
+        let result = upload_with(csv_only, "Data.csv", "text/plain", b"a,b\n1,2".to_vec()).await;
+        assert!(result.is_ok());
+
+        let result = upload_with(csv_only, "notes.txt", "text/plain", b"notes".to_vec()).await;
+        assert!(validation_message(result).contains("Allowed extensions: csv"));
+    }
+
     #[tokio::test]
     async fn test_generate_filename() {
         let repo = Arc::new(MockFileRepo {
@@ -322,6 +1280,7 @@ mod tests {
         });
         let service = FileService::new(
             repo,
+            no_permissions(),
             "/tmp/uploads".to_string(),
             "http://example.com".to_string(),
         );
@@ -332,4 +1291,172 @@ mod tests {
         let filename = service.generate_filename("test");
         assert!(!filename.contains("."));
     }
+
+    #[tokio::test]
+    async fn test_delete_other_users_file_requires_file_manage() {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let manager = domain::User::new(
+            uuid::Uuid::new_v4(),
+            "manager".to_string(),
+            "hash".to_string(),
+            domain::FILE_MANAGE,
+        );
+        let permissions = Arc::new(PermissionService::new(Arc::new(MockUserRepo::with_users(
+            vec![manager.clone()],
+        ))));
+        let service = FileService::new(
+            repo.clone(),
+            permissions,
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        );
+
+        let uploaded = service
+            .upload_file(uuid::Uuid::new_v4(), text_upload("a.txt", b"owned"))
+            .await
+            .unwrap();
+
+        let result = service.delete_file(uploaded.id, uuid::Uuid::new_v4()).await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        service.delete_file(uploaded.id, manager.id).await.unwrap();
+        assert!(repo.files.read().await.is_empty());
+        assert!(!dir.join(&uploaded.filename).exists());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    /// Service with a 1 MB quota over a repository holding no files
+    fn quota_service(dir: &std::path::Path, users: Vec<domain::User>) -> FileService {
+        let permissions = Arc::new(PermissionService::new(Arc::new(MockUserRepo::with_users(
+            users,
+        ))));
+        FileService::new(
+            Arc::new(MockFileRepo {
+                files: Arc::new(RwLock::new(HashMap::new())),
+            }),
+            permissions,
+            dir.to_string_lossy().to_string(),
+            "http://example.com".to_string(),
+        )
+        .with_max_storage_per_user_mb(Some(1))
+    }
+
+    const MIB: usize = 1024 * 1024;
+
+    #[tokio::test]
+    async fn test_upload_rejected_once_quota_is_reached() {
+        let dir = temp_upload_dir();
+        let user = domain::User::new(
+            uuid::Uuid::new_v4(),
+            "alice".to_string(),
+            "hash".to_string(),
+            domain::DEFAULT_USER_PERMISSIONS,
+        );
+        let service = quota_service(&dir, vec![user.clone()]);
+
+        service
+            .upload_file(user.id, text_upload("a.txt", &vec![b'a'; MIB / 2]))
+            .await
+            .unwrap();
+        // Filling the quota exactly is still allowed
+        service
+            .upload_file(user.id, text_upload("b.txt", &vec![b'b'; MIB / 2]))
+            .await
+            .unwrap();
+
+        let result = service
+            .upload_file(user.id, text_upload("c.txt", b"c"))
+            .await;
+        assert!(matches!(result, Err(Error::Validation(msg)) if msg == "Storage quota exceeded"));
+
+        let usage = service.get_storage_usage(user.id).await.unwrap();
+        assert_eq!(usage, StorageUsage::new(MIB as u64, Some(MIB as u64)));
+        assert_eq!(usage.percent_used, 100.0);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_admin_bypasses_storage_quota() {
+        let dir = temp_upload_dir();
+        let admin = domain::User::new(
+            uuid::Uuid::new_v4(),
+            "admin".to_string(),
+            "hash".to_string(),
+            domain::ADMIN_PERMISSIONS,
+        );
+        let service = quota_service(&dir, vec![admin.clone()]);
+
+        service
+            .upload_file(admin.id, text_upload("a.txt", &vec![b'a'; MIB + 1]))
+            .await
+            .unwrap();
+
+        let usage = service.get_storage_usage(admin.id).await.unwrap();
+        assert_eq!(usage.used_bytes, MIB as u64 + 1);
+        assert_eq!(usage.quota_bytes, None);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_serve_file_supports_byte_ranges() {
+        let dir = temp_upload_dir();
+        let repo = Arc::new(MockFileRepo {
+            files: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = FileService::new(
+            repo,
+            no_permissions(),
+            dir.to_string_lossy().to_string(),
+            "http://localhost".to_string(),
+        );
+        let content: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let uploaded = service
+            .upload_file(uuid::Uuid::new_v4(), text_upload("data.txt", &content))
+            .await
+            .unwrap();
+
+        let full = service.serve_file(uploaded.id, None).await.unwrap();
+        assert_eq!(full.status, 200);
+        assert_eq!(full.data, content);
+        assert_eq!(full.content_range, None);
+
+        let middle = service
+            .serve_file(uploaded.id, Some("bytes=100-199"))
+            .await
+            .unwrap();
+        assert_eq!(middle.status, 206);
+        assert_eq!(middle.data, &content[100..200]);
+        assert_eq!(middle.content_range.as_deref(), Some("bytes 100-199/1000"));
+
+        let suffix = service
+            .serve_file(uploaded.id, Some("bytes=-500"))
+            .await
+            .unwrap();
+        assert_eq!(suffix.status, 206);
+        assert_eq!(suffix.data, &content[500..]);
+        assert_eq!(suffix.content_range.as_deref(), Some("bytes 500-999/1000"));
+
+        let invalid = service
+            .serve_file(uploaded.id, Some("bytes=5000-"))
+            .await
+            .unwrap();
+        assert_eq!(invalid.status, 416);
+        assert!(invalid.data.is_empty());
+        assert_eq!(invalid.content_range.as_deref(), Some("bytes */1000"));
+
+        // Malformed headers are ignored rather than rejected
+        let ignored = service
+            .serve_file(uploaded.id, Some("lines=1-2"))
+            .await
+            .unwrap();
+        assert_eq!(ignored.status, 200);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
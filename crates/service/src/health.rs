@@ -0,0 +1,82 @@
+//! Health Service
+//!
+//! Checks whether the backing services of the blog are reachable, for use
+//! by load balancer and monitoring probes.
+
+use domain::HealthRepository;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Longest a database ping may take before it counts as failed
+pub const DB_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Health service for service status probes
+#[derive(Clone)]
+pub struct HealthService {
+    repo: Arc<dyn HealthRepository>,
+}
+
+impl HealthService {
+    /// Create a new health service
+    pub fn new(repo: Arc<dyn HealthRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Check that the database answers within [`DB_PING_TIMEOUT`]
+    pub async fn database_ok(&self) -> bool {
+        match tokio::time::timeout(DB_PING_TIMEOUT, self.repo.ping()).await {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                tracing::warn!("Health check database ping failed: {}", e);
+                false
+            }
+            Err(_) => {
+                tracing::warn!("Health check database ping timed out");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use domain::{Error, Result};
+
+    enum MockHealthRepo {
+        Up,
+        Down,
+        Hanging,
+    }
+
+    #[async_trait]
+    impl HealthRepository for MockHealthRepo {
+        async fn ping(&self) -> Result<()> {
+            match self {
+                Self::Up => Ok(()),
+                Self::Down => Err(Error::Internal("connection refused".to_string())),
+                Self::Hanging => std::future::pending().await,
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_database_ok_reports_failures_and_timeouts() {
+        assert!(
+            HealthService::new(Arc::new(MockHealthRepo::Up))
+                .database_ok()
+                .await
+        );
+        assert!(
+            !HealthService::new(Arc::new(MockHealthRepo::Down))
+                .database_ok()
+                .await
+        );
+        assert!(
+            !HealthService::new(Arc::new(MockHealthRepo::Hanging))
+                .database_ok()
+                .await
+        );
+    }
+}
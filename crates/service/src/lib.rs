@@ -17,23 +17,37 @@
 //! - Domain (types) is implemented by Infrastructure (data access)
 
 pub mod category;
+pub mod clients;
 pub mod comment;
 pub mod config;
 pub mod file;
+pub mod health;
+pub mod notification;
+mod outbound;
+pub mod permission;
 pub mod post;
 pub mod rss;
 pub mod session;
+pub mod sitemap;
 pub mod stats;
 pub mod tag;
 pub mod user;
 
+#[cfg(test)]
+mod test_support;
+
 pub use category::CategoryService;
+pub use clients::{Clients, SharedClients};
 pub use comment::CommentService;
-pub use config::ConfigService;
+pub use config::{ConfigService, SharedConfig};
 pub use file::FileService;
+pub use health::HealthService;
+pub use notification::NotificationService;
+pub use permission::PermissionService;
 pub use post::PostService;
 pub use rss::{RssService, RssServiceImpl};
 pub use session::SessionService;
+pub use sitemap::SitemapService;
 pub use stats::StatsService;
 pub use tag::TagService;
 pub use user::UserService;
@@ -42,3 +56,13 @@ pub use domain::{
     CategoryRepository, CommentRepository, ConfigRepository, Error, FileRepository, PostRepository,
     Result, SessionRepository, StatsRepository, TagRepository, UserRepository,
 };
+
+/// Log an internal failure and turn it into [`Error::Internal`]
+///
+/// Internal errors reach clients as a generic 500, so the message is only
+/// useful if it ends up in the logs.
+pub(crate) fn internal_error(message: impl Into<String>) -> Error {
+    let message = message.into();
+    tracing::error!("{}", message);
+    Error::Internal(message)
+}
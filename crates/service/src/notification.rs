@@ -0,0 +1,215 @@
+//! Notification Service
+//!
+//! This module sends email notifications to users, currently to tell post
+//! authors that someone commented on one of their posts.
+
+use crate::SharedClients;
+use domain::{CommentRepository, Error, PostRepository, Result, UserRepository};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Service for sending user-facing email notifications
+pub struct NotificationService {
+    post_repo: Arc<dyn PostRepository>,
+    comment_repo: Arc<dyn CommentRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    clients: SharedClients,
+    base_url: String,
+}
+
+impl NotificationService {
+    /// Create a new notification service
+    ///
+    /// # Arguments
+    /// * `post_repo` - Post repository used to look up the commented post
+    /// * `comment_repo` - Comment repository used to load the new comment
+    /// * `user_repo` - User repository used to find the author's email
+    /// * `clients` - Outbound clients whose SMTP client is used for delivery
+    /// * `base_url` - Public site URL used to link back to the post
+    pub fn new(
+        post_repo: Arc<dyn PostRepository>,
+        comment_repo: Arc<dyn CommentRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        clients: SharedClients,
+        base_url: String,
+    ) -> Self {
+        Self {
+            post_repo,
+            comment_repo,
+            user_repo,
+            clients,
+            base_url,
+        }
+    }
+
+    /// Email the post author about a new comment
+    ///
+    /// Nothing is sent when email is disabled, the author has no email
+    /// address or wrote the comment themselves.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Notification sent or intentionally skipped
+    /// * `Err(Error::NotFound)` - Post or comment not found
+    /// * `Err(Error::Internal)` - SMTP delivery failed
+    pub async fn notify_new_comment(&self, post_id: Uuid, comment_id: Uuid) -> Result<()> {
+        let Some(email_client) = self.clients.read().unwrap().email.clone() else {
+            tracing::debug!("Email is disabled, skipping comment notification");
+            return Ok(());
+        };
+        let comment = self
+            .comment_repo
+            .get_comment(comment_id)
+            .await?
+            .ok_or_else(|| Error::NotFound("Comment not found".to_string()))?;
+        let post = self.post_repo.get_post(post_id).await?;
+
+        let Some(author) = self.user_repo.find_by_id(post.user_id).await? else {
+            return Ok(());
+        };
+        let Some(email) = author.email.as_deref() else {
+            tracing::debug!("Author {} has no email, skipping notification", author.id);
+            return Ok(());
+        };
+        if comment.user_id == Some(author.id) {
+            return Ok(());
+        }
+
+        let commenter = match (&comment.github_username, comment.user_id) {
+            (Some(github_username), _) => github_username.clone(),
+            (None, Some(user_id)) => self
+                .user_repo
+                .find_by_id(user_id)
+                .await?
+                .map(|user| user.username)
+                .unwrap_or_else(|| "Someone".to_string()),
+            (None, None) => "Someone".to_string(),
+        };
+
+        let subject = format!("New comment on \"{}\"", post.title);
+        let body = format!(
+            "{} commented on your post \"{}\":\n\n{}\n\nView the discussion: {}/post/{}\n",
+            commenter, post.title, comment.content, self.base_url, post.id
+        );
+
+        email_client
+            .send(email, &subject, body)
+            .await
+            .map_err(Error::Internal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockCommentRepo, MockPostRepo, MockSmtpServer, MockUserRepo};
+    use domain::{Comment, Post, User};
+
+    struct Fixture {
+        service: NotificationService,
+        smtp: MockSmtpServer,
+        author: User,
+        reader: User,
+        post: Post,
+        comments: Arc<MockCommentRepo>,
+    }
+
+    async fn fixture(author_email: Option<&str>) -> Fixture {
+        let mut author = User::new(Uuid::new_v4(), "author".into(), "hash".into(), 0);
+        author.email = author_email.map(str::to_string);
+        let reader = User::new(Uuid::new_v4(), "reader".into(), "hash".into(), 0);
+
+        let post = Post::new(author.id, "Hello World".into(), "content".into());
+        let returned = post.clone();
+        let mut post_repo = MockPostRepo::new();
+        post_repo
+            .expect_get_post()
+            .returning(move |_| Ok(returned.clone()));
+
+        let smtp = MockSmtpServer::start().await;
+        let email_client =
+            infrastructure::EmailClient::unencrypted("127.0.0.1", smtp.port, "blog@example.com")
+                .unwrap();
+        let comments = Arc::new(MockCommentRepo::new());
+
+        let service = NotificationService::new(
+            Arc::new(post_repo),
+            comments.clone(),
+            Arc::new(MockUserRepo::with_users(vec![
+                author.clone(),
+                reader.clone(),
+            ])),
+            crate::Clients {
+                email: Some(Arc::new(email_client)),
+                ..Default::default()
+            }
+            .shared(),
+            "http://example.com".to_string(),
+        );
+
+        Fixture {
+            service,
+            smtp,
+            author,
+            reader,
+            post,
+            comments,
+        }
+    }
+
+    async fn add_comment(fixture: &Fixture, user_id: Uuid) -> Comment {
+        let comment = Comment::from_user(fixture.post.id, user_id, "Great post!".into());
+        fixture
+            .comments
+            .create_comment(comment.clone())
+            .await
+            .unwrap();
+        comment
+    }
+
+    #[tokio::test]
+    async fn test_notify_new_comment_emails_author() {
+        let fixture = fixture(Some("author@example.com")).await;
+        let comment = add_comment(&fixture, fixture.reader.id).await;
+
+        fixture
+            .service
+            .notify_new_comment(fixture.post.id, comment.id)
+            .await
+            .unwrap();
+
+        let messages = fixture.smtp.wait_for_messages(1).await;
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("To: author@example.com"));
+        assert!(messages[0].contains("Subject: New comment on \"Hello World\""));
+        assert!(messages[0].contains("reader commented on your post"));
+        assert!(messages[0].contains("Great post!"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_skips_author_without_email() {
+        let fixture = fixture(None).await;
+        let comment = add_comment(&fixture, fixture.reader.id).await;
+
+        fixture
+            .service
+            .notify_new_comment(fixture.post.id, comment.id)
+            .await
+            .unwrap();
+
+        assert!(fixture.smtp.wait_for_messages(0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_notify_skips_authors_own_comment() {
+        let fixture = fixture(Some("author@example.com")).await;
+        let comment = add_comment(&fixture, fixture.author.id).await;
+
+        fixture
+            .service
+            .notify_new_comment(fixture.post.id, comment.id)
+            .await
+            .unwrap();
+
+        assert!(fixture.smtp.wait_for_messages(0).await.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! # Outbound HTTP - Requests to User-Supplied URLs
+//!
+//! Post authors control the URLs the server fetches for feature images and
+//! link checks, so those requests must not reach the server's own network.
+//! Clients built here:
+//! - resolve hostnames themselves and drop loopback, private, link-local and
+//!   other non-public addresses before connecting
+//! - refuse URLs and redirect targets that name such an address directly
+//! - follow at most [`MAX_REDIRECTS`] redirects, re-checking each hop
+//!
+//! Tests that talk to a local mock server build clients with
+//! `allow_private` set.
+
+use domain::{Error, Result};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{redirect, Client, Response, Url};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::internal_error;
+
+/// Redirects followed before a request is given up
+pub const MAX_REDIRECTS: usize = 5;
+
+/// Build a client for user-supplied URLs
+pub(crate) fn client(timeout: Duration, allow_private: bool) -> Result<Client> {
+    let mut builder =
+        Client::builder()
+            .timeout(timeout)
+            .no_proxy()
+            .redirect(redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if let Err(reason) = check_url(attempt.url(), allow_private) {
+                    attempt.error(reason)
+                } else {
+                    attempt.follow()
+                }
+            }));
+    if !allow_private {
+        builder = builder.dns_resolver(Arc::new(PublicResolver));
+    }
+    builder
+        .build()
+        .map_err(|e| internal_error(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Parse a user-supplied URL and check it with [`check_url`]
+pub(crate) fn parse_url(url: &str, allow_private: bool) -> Result<Url> {
+    let parsed =
+        Url::parse(url).map_err(|e| Error::Validation(format!("Invalid URL {}: {}", url, e)))?;
+    check_url(&parsed, allow_private)
+        .map_err(|reason| Error::Validation(format!("URL {} is not allowed: {}", url, reason)))?;
+    Ok(parsed)
+}
+
+/// Check that a URL is http(s) and does not name a non-public address
+///
+/// Hostnames are checked when they are resolved, see [`PublicResolver`].
+pub(crate) fn check_url(url: &Url, allow_private: bool) -> std::result::Result<(), String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("unsupported URL scheme {}", url.scheme()));
+    }
+    let host = url.host_str().ok_or("URL has no host")?;
+    let Ok(ip) = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    else {
+        return Ok(());
+    };
+    if allow_private || is_public_ip(ip) {
+        Ok(())
+    } else {
+        Err(format!("{} is not a public address", ip))
+    }
+}
+
+/// Read a response body, failing once it grows past `max_bytes`
+pub(crate) async fn read_limited(mut response: Response, max_bytes: u64) -> Result<Vec<u8>> {
+    let too_large = || {
+        Error::Validation(format!(
+            "Response is larger than the {} byte limit",
+            max_bytes
+        ))
+    };
+    if response.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| Error::Validation(format!("Failed to read response: {}", e)))?
+    {
+        if (body.len() + chunk.len()) as u64 > max_bytes {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Whether an address is reachable on the public internet
+pub(crate) fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8 and 100.64.0.0/10 (carrier-grade NAT)
+        || a == 0
+        || (a == 100 && (b & 0xc0) == 64))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local())
+}
+
+/// DNS resolver that only returns public addresses
+///
+/// Checking at connect time, rather than before the request, also covers
+/// hostnames that resolve differently on a second lookup.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_public_addresses_are_rejected() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{} is not public", ip);
+        }
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{} is public", ip);
+        }
+    }
+
+    #[test]
+    fn test_check_url_rejects_private_literals_and_other_schemes() {
+        let check = |url: &str, allow_private| check_url(&Url::parse(url).unwrap(), allow_private);
+
+        assert!(check("http://127.0.0.1:8080/", false).is_err());
+        assert!(check("https://[::1]/", false).is_err());
+        assert!(check("file:///etc/passwd", false).is_err());
+        assert!(check("https://example.com/", false).is_ok());
+        assert!(check("http://127.0.0.1:8080/", true).is_ok());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::UserRepository;
+use domain::{
+    Error, ErrorCode, Result, CATEGORY_MANAGE, COMMENT_MODERATE, FILE_MANAGE, USER_MANAGE,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Service for resolving a user's granular permissions
+///
+/// Permissions are looked up from the user record on every call so that
+/// changes made by an administrator take effect immediately.
+#[derive(Clone)]
+pub struct PermissionService {
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl PermissionService {
+    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { user_repo }
+    }
+
+    pub async fn can_manage_categories(&self, user_id: Uuid) -> Result<bool> {
+        self.has_permission(user_id, CATEGORY_MANAGE).await
+    }
+
+    pub async fn can_moderate_comments(&self, user_id: Uuid) -> Result<bool> {
+        self.has_permission(user_id, COMMENT_MODERATE).await
+    }
+
+    pub async fn can_manage_files(&self, user_id: Uuid) -> Result<bool> {
+        self.has_permission(user_id, FILE_MANAGE).await
+    }
+
+    pub async fn is_admin(&self, user_id: Uuid) -> Result<bool> {
+        self.has_permission(user_id, USER_MANAGE).await
+    }
+
+    /// Fail with a permission error unless `allowed` is true
+    pub(crate) fn require(allowed: bool, action: &str) -> Result<()> {
+        if allowed {
+            Ok(())
+        } else {
+            tracing::warn!("Permission denied: not allowed to {}", action);
+            Err(Error::coded(
+                ErrorCode::PermissionDenied,
+                format!("Permission denied: you are not allowed to {}", action),
+            ))
+        }
+    }
+
+    async fn has_permission(&self, user_id: Uuid, permission: u64) -> Result<bool> {
+        Ok(self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .is_some_and(|user| user.has_permission(permission)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockUserRepo;
+    use domain::{User, ADMIN_PERMISSIONS, DEFAULT_USER_PERMISSIONS};
+
+    fn service_with(users: Vec<User>) -> PermissionService {
+        PermissionService::new(Arc::new(MockUserRepo::with_users(users)))
+    }
+
+    #[tokio::test]
+    async fn test_admin_has_all_granular_permissions() {
+        let admin = User::new(
+            Uuid::new_v4(),
+            "admin".into(),
+            "hash".into(),
+            ADMIN_PERMISSIONS,
+        );
+        let service = service_with(vec![admin.clone()]);
+
+        assert!(service.can_manage_categories(admin.id).await.unwrap());
+        assert!(service.can_moderate_comments(admin.id).await.unwrap());
+        assert!(service.can_manage_files(admin.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_regular_user_lacks_granular_permissions() {
+        let user = User::new(
+            Uuid::new_v4(),
+            "user".into(),
+            "hash".into(),
+            DEFAULT_USER_PERMISSIONS,
+        );
+        let service = service_with(vec![user.clone()]);
+
+        assert!(!service.can_manage_categories(user.id).await.unwrap());
+        assert!(!service.can_moderate_comments(user.id).await.unwrap());
+        assert!(!service.can_manage_files(user.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_single_flag_grants_only_that_permission() {
+        let moderator = User::new(
+            Uuid::new_v4(),
+            "moderator".into(),
+            "hash".into(),
+            DEFAULT_USER_PERMISSIONS | COMMENT_MODERATE,
+        );
+        let service = service_with(vec![moderator.clone()]);
+
+        assert!(service.can_moderate_comments(moderator.id).await.unwrap());
+        assert!(!service.can_manage_categories(moderator.id).await.unwrap());
+        assert!(!service.can_manage_files(moderator.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_user_has_no_permissions() {
+        let service = service_with(vec![]);
+
+        assert!(!service.can_manage_files(Uuid::new_v4()).await.unwrap());
+    }
+}
@@ -3,12 +3,25 @@
 //! This service implements business logic for post operations.
 //! It coordinates repository calls and enforces business rules.
 
-use domain::PostRepository;
+use crate::file::DEFAULT_MAX_FILE_SIZE_MB;
+use crate::internal_error;
+use crate::outbound;
+use crate::{FileService, SharedClients, SharedConfig, TagService};
 use domain::{
-    Error, IndexNowRequest, Post, Result, SearchPostsRequest, SearchPostsResponse, POST_DELETE,
-    POST_PUBLISH, POST_UPDATE,
+    AdjacentPosts, ArchiveMonth, BulkResult, Error, ErrorCode, ExportPost, FeatureImageSource,
+    FrontMatter, HtmlAllowList, IndexNowRequest, LikeResponse, LinkCheckResult, NewScheduledPost,
+    OgData, Post, PostLengthLimits, PostSearchRequest, PostWithAuthor, PostWithStats, PostWithTags,
+    Result, SearchPostsRequest, SearchPostsResponse, TocEntry, UploadFile, WebhookEvent,
+    WebhookPayload, WordCountStats, POST_CREATE, POST_DELETE, POST_PUBLISH, POST_UPDATE,
+    USER_MANAGE,
 };
-use infrastructure::IndexNowClient;
+use domain::{
+    CategoryRepository, CommentRepository, LikeRepository, PostRepository, TagRepository,
+    UserRepository,
+};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -19,51 +32,404 @@ use uuid::Uuid;
 /// Default limit for listing posts
 const DEFAULT_LIST_LIMIT: u64 = 20;
 
+/// Maximum page size for an advanced search
+pub const MAX_SEARCH_LIMIT: u64 = 100;
+
+/// Maximum post title length
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// Upper bound on posts included in an export
+const MAX_EXPORT_POSTS: u64 = 10_000;
+
+/// Maximum number of posts in one bulk publish or unpublish
+pub const MAX_BULK_POSTS: usize = 100;
+
+/// Timeout for checking and mirroring an external feature image
+const FEATURE_IMAGE_TIMEOUT_SECS: u64 = 10;
+
+/// Maximum number of external links checked in one call
+pub const MAX_LINK_CHECKS: usize = 20;
+
+/// Timeout for each external link check
+const LINK_CHECK_TIMEOUT_SECS: u64 = 5;
+
+/// External links checked at the same time
+const LINK_CHECK_CONCURRENCY: usize = 5;
+
+/// Maximum number of headings in a table of contents
+pub const MAX_TOC_ENTRIES: usize = 50;
+
+/// Days a soft-deleted post stays restorable before it is purged
+pub const SOFT_DELETE_RETENTION_DAYS: i64 = 30;
+
+/// Tags removed with their content from rendered posts, even when allow-listed
+const ALWAYS_DENIED_TAGS: &[&str] = &["script", "style"];
+
 /// Service for post business logic
 ///
 /// This service encapsulates all business rules for post operations.
 /// It uses dependency injection for repositories, making it testable.
 pub struct PostService {
     repo: Arc<dyn PostRepository>,
-    indexnow_client: Option<Arc<IndexNowClient>>,
     base_url: String,
-    indexnow_key: Option<String>,
+    clients: Option<SharedClients>,
+    like_repo: Option<Arc<dyn LikeRepository>>,
+    like_salt: String,
+    tag_repo: Option<Arc<dyn TagRepository>>,
+    category_repo: Option<Arc<dyn CategoryRepository>>,
+    file_service: Option<FileService>,
+    config: Option<SharedConfig>,
+    comment_repo: Option<Arc<dyn CommentRepository>>,
+    user_repo: Option<Arc<dyn UserRepository>>,
+    allow_private_hosts: bool,
 }
 
 impl PostService {
-    /// Create a new PostService with given repository and optional IndexNow client
-    pub fn new(
-        repo: Arc<dyn PostRepository>,
-        indexnow_client: Option<Arc<IndexNowClient>>,
-        base_url: String,
-        indexnow_key: Option<String>,
-    ) -> Self {
+    /// Create a new PostService with given repository and public site URL
+    pub fn new(repo: Arc<dyn PostRepository>, base_url: String) -> Self {
         Self {
             repo,
-            indexnow_client,
             base_url,
-            indexnow_key,
+            clients: None,
+            like_repo: None,
+            like_salt: String::new(),
+            tag_repo: None,
+            category_repo: None,
+            file_service: None,
+            config: None,
+            comment_repo: None,
+            user_repo: None,
+            allow_private_hosts: false,
         }
     }
 
+    /// Let outbound requests reach local mock servers
+    #[cfg(test)]
+    fn with_private_hosts_allowed(mut self) -> Self {
+        self.allow_private_hosts = true;
+        self
+    }
+
+    /// Attach the IndexNow and webhook clients notified on publish/unpublish events
+    ///
+    /// The clients are looked up on each notification, so a config reload
+    /// that rebuilds them takes effect immediately.
+    pub fn with_clients(mut self, clients: SharedClients) -> Self {
+        self.clients = Some(clients);
+        self
+    }
+
+    /// Attach the live configuration
+    ///
+    /// Content length limits (`site.max_post_chars`), the rendered HTML
+    /// allow-list and `storage.mirror_external_images` are read on each
+    /// call, so a reload takes effect immediately. Without it the defaults
+    /// apply and external feature images are not mirrored.
+    pub fn with_config(mut self, config: SharedConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Attach the like repository and the server secret used to salt IP hashes
+    pub fn with_likes(mut self, like_repo: Arc<dyn LikeRepository>, salt: String) -> Self {
+        self.like_repo = Some(like_repo);
+        self.like_salt = salt;
+        self
+    }
+
+    /// Attach the tag repository used to validate bulk tag assignments
+    pub fn with_tag_repository(mut self, tag_repo: Arc<dyn TagRepository>) -> Self {
+        self.tag_repo = Some(tag_repo);
+        self
+    }
+
+    /// Attach the category repository used to resolve imported categories
+    pub fn with_category_repository(mut self, category_repo: Arc<dyn CategoryRepository>) -> Self {
+        self.category_repo = Some(category_repo);
+        self
+    }
+
+    /// Attach the file service used to resolve and mirror feature images
+    pub fn with_feature_images(mut self, file_service: FileService) -> Self {
+        self.file_service = Some(file_service);
+        self
+    }
+
+    /// Attach the comment repository used for dashboard comment counts
+    pub fn with_comment_repository(mut self, comment_repo: Arc<dyn CommentRepository>) -> Self {
+        self.comment_repo = Some(comment_repo);
+        self
+    }
+
+    /// Attach the user repository used to name authors in sharing previews
+    pub fn with_user_repository(mut self, user_repo: Arc<dyn UserRepository>) -> Self {
+        self.user_repo = Some(user_repo);
+        self
+    }
+
+    /// Content length limits from the live `site` config
+    fn length_limits(&self) -> PostLengthLimits {
+        self.config
+            .as_ref()
+            .map(|config| config.read().unwrap().site.post_length_limits())
+            .unwrap_or_default()
+    }
+
+    /// HTML kept in rendered posts, from the live `site` config
+    fn html_allow_list(&self) -> HtmlAllowList {
+        self.config
+            .as_ref()
+            .map(|config| config.read().unwrap().site.html_allow_list())
+            .unwrap_or_default()
+    }
+
     /// Create a new post with validation
-    pub async fn create(&self, user_id: Uuid, title: String, content: String) -> Result<Post> {
+    ///
+    /// Without a `summary`, one is generated from the first paragraph of the
+    /// content and kept up to date as the content changes. The content
+    /// length limit depends on the author's `permissions`.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        title: String,
+        content: String,
+        summary: Option<String>,
+        permissions: u64,
+    ) -> Result<Post> {
         self.validate_title(&title)?;
-        self.validate_content(&content)?;
-        self.repo.create_post(user_id, title, content).await
+        self.validate_content(&content, permissions)?;
+        if let Some(summary) = &summary {
+            self.validate_summary(summary)?;
+        }
+
+        let mut post = self.repo.create_post(user_id, title, content).await?;
+        if summary.is_some() {
+            post.set_summary(summary);
+            post = self.repo.update_post(post).await?;
+        }
+        tracing::info!("Post {} created by user {}", post.id, user_id);
+        Ok(post)
     }
 
     /// Get a post by ID
+    ///
+    /// Falls back to the archive when the post is not among the live posts.
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id))]
     pub async fn get(&self, id: Uuid) -> Result<Post> {
-        self.repo.get_post(id).await
+        let mut post = match self.repo.get_post(id).await {
+            Err(e) if e.is_not_found() => self.repo.get_archived_post(id).await?,
+            result => result?,
+        };
+        post.compute_reading_stats();
+        Ok(post)
+    }
+
+    /// Copy a post into a new draft owned by `new_author_id`
+    ///
+    /// The copy keeps the content, category and tags, and its title is
+    /// prefixed with "Copy of". Only published posts may be copied by
+    /// anyone; drafts can only be copied by their owner or an admin.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %new_author_id))]
+    pub async fn duplicate(
+        &self,
+        post_id: Uuid,
+        new_author_id: Uuid,
+        permissions: u64,
+    ) -> Result<Post> {
+        domain::check_permission(permissions, POST_CREATE)?;
+
+        let source = self.repo.get_post(post_id).await?;
+        if !source.is_published() {
+            domain::check_ownership_or_admin(
+                source.user_id,
+                new_author_id,
+                permissions,
+                POST_DELETE,
+            )?;
+        }
+
+        let mut title = format!("Copy of {}", source.title);
+        while title.len() > MAX_TITLE_LENGTH {
+            title.pop();
+        }
+        let mut copy = self
+            .repo
+            .create_post(new_author_id, title, source.content)
+            .await?;
+
+        if !source.use_auto_summary {
+            copy.set_summary(source.summary);
+            copy = self.repo.update_post(copy).await?;
+        }
+        if source.category_id.is_some() {
+            self.repo
+                .update_post_category(copy.id, source.category_id)
+                .await?;
+        }
+        for tag in self.repo.get_post_tags(post_id).await? {
+            self.repo.add_tag_to_post(copy.id, tag.id).await?;
+        }
+        tracing::info!(
+            "Post {} duplicated as {} by user {}",
+            post_id,
+            copy.id,
+            new_author_id
+        );
+
+        let mut copy = self.repo.get_post(copy.id).await?;
+        copy.compute_reading_stats();
+        Ok(copy)
+    }
+
+    /// Import a Markdown document with YAML front-matter as a new post
+    ///
+    /// The front-matter supplies the title, summary, category, tags and
+    /// publication date; the rest of the document becomes the content.
+    /// Categories and tags are referenced by slug and created when missing.
+    /// A `published_at` date publishes the post and needs `POST_PUBLISH`.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn import(&self, user_id: Uuid, markdown: &str, permissions: u64) -> Result<Post> {
+        domain::check_permission(permissions, POST_CREATE)?;
+
+        let (front_matter, body) = FrontMatter::parse(markdown)?;
+        let title = front_matter
+            .title
+            .ok_or_else(|| Error::Validation("Front-matter must contain a title".to_string()))?;
+        for slug in front_matter.category.iter().chain(&front_matter.tags) {
+            self.validate_slug(slug)?;
+        }
+        if front_matter.published_at.is_some() {
+            domain::check_permission(permissions, POST_PUBLISH)?;
+        }
+        if !front_matter.tags.is_empty() && self.tag_repo.is_none() {
+            return Err(internal_error("Tag repository not configured"));
+        }
+        if front_matter.category.is_some() && self.category_repo.is_none() {
+            return Err(internal_error("Category repository not configured"));
+        }
+
+        let post = self
+            .create(
+                user_id,
+                title,
+                body.to_string(),
+                front_matter.summary,
+                permissions,
+            )
+            .await?;
+
+        if let Some(slug) = front_matter.category {
+            let category = self.category_for_slug(slug).await?;
+            self.set_category(post.id, Some(category.id), user_id, permissions)
+                .await?;
+        }
+
+        if let Some(tag_repo) = &self.tag_repo {
+            let tags = TagService::new(Arc::clone(tag_repo));
+            for slug in front_matter.tags {
+                let tag = tags.get_or_create(&slug).await?;
+                self.add_tag(post.id, tag.id, user_id, permissions).await?;
+            }
+        }
+
+        let mut post = self.repo.get_post(post.id).await?;
+        if let Some(published_at) = front_matter.published_at {
+            post.published_at = Some(published_at);
+            post = self.repo.update_post(post).await?;
+        }
+        tracing::info!("Post {} imported by user {}", post.id, user_id);
+
+        post.compute_reading_stats();
+        Ok(post)
+    }
+
+    /// Category with `slug`, created with the slug as its name when missing
+    async fn category_for_slug(&self, slug: String) -> Result<domain::Category> {
+        let category_repo = self
+            .category_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Category repository not configured"))?;
+
+        match category_repo.get_category_by_slug(&slug).await? {
+            Some(category) => Ok(category),
+            None => {
+                category_repo
+                    .create_category(slug.clone(), slug, None)
+                    .await
+            }
+        }
+    }
+
+    /// Render every post, drafts included, as a Markdown file (admin only)
+    ///
+    /// Each file carries the post metadata as front-matter in the format
+    /// [`PostService::import`] reads. Files are named `{slug}.md`, or
+    /// `{id}.md` when another post already took the slug.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn export(&self, user_id: Uuid, permissions: u64) -> Result<Vec<ExportPost>> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        let posts = self.repo.list_all_posts(MAX_EXPORT_POSTS).await?;
+        let mut category_slugs: HashMap<Uuid, Option<String>> = HashMap::new();
+        let mut exported: Vec<ExportPost> = Vec::with_capacity(posts.len());
+
+        for post in posts {
+            let category = match (post.category_id, &self.category_repo) {
+                (Some(category_id), Some(category_repo)) => {
+                    match category_slugs.get(&category_id) {
+                        Some(slug) => slug.clone(),
+                        None => {
+                            let slug = category_repo
+                                .get_category(category_id)
+                                .await?
+                                .map(|category| category.slug);
+                            category_slugs.insert(category_id, slug.clone());
+                            slug
+                        }
+                    }
+                }
+                _ => None,
+            };
+            let tags = self.repo.get_post_tags(post.id).await?;
+
+            let slug = post.slug();
+            let filename = if exported
+                .iter()
+                .any(|f| f.filename == format!("{}.md", slug))
+            {
+                format!("{}.md", post.id)
+            } else {
+                format!("{}.md", slug)
+            };
+            let front_matter = FrontMatter {
+                title: Some(post.title),
+                slug: Some(slug),
+                tags: tags.into_iter().map(|tag| tag.slug).collect(),
+                category,
+                published_at: post.published_at,
+                created_at: Some(post.created_at),
+                summary: post.summary.filter(|_| !post.use_auto_summary),
+            };
+
+            exported.push(ExportPost {
+                filename,
+                content: format!("{}{}", front_matter.render(), post.content),
+            });
+        }
+        tracing::info!("{} posts exported by user {}", exported.len(), user_id);
+
+        Ok(exported)
     }
 
     /// Update an existing post with ownership and permission checks
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %updater_id))]
     pub async fn update(
         &self,
         id: Uuid,
         title: Option<String>,
         content: Option<String>,
+        summary: Option<String>,
         updater_id: Uuid,
         updater_permissions: u64,
     ) -> Result<Post> {
@@ -89,11 +455,21 @@ impl PostService {
         }
 
         if let Some(content) = content {
-            self.validate_content(&content)?;
+            self.validate_content(&content, updater_permissions)?;
             post.content = content;
         }
 
+        // A blank summary switches back to the generated one
+        match summary {
+            Some(summary) => {
+                self.validate_summary(&summary)?;
+                post.set_summary(Some(summary));
+            }
+            None => post.refresh_auto_summary(),
+        }
+
         let updated_post = self.repo.update_post(post).await?;
+        tracing::info!("Post {} updated by user {}", id, updater_id);
 
         // Notify IndexNow if post is published and content changed
         if was_published && content_changed {
@@ -104,6 +480,7 @@ impl PostService {
     }
 
     /// Publish a post with permission and ownership checks
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
     pub async fn publish(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<Post> {
         domain::check_permission(permissions, POST_PUBLISH)?;
 
@@ -113,6 +490,9 @@ impl PostService {
 
         post.publish();
         let updated_post = self.repo.update_post(post).await?;
+        tracing::info!("Post {} published by user {}", id, user_id);
+
+        self.notify_webhook(WebhookEvent::PostPublished, &updated_post);
 
         // Notify IndexNow if configured
         let _ = self.notify_indexnow(updated_post.id).await;
@@ -121,6 +501,7 @@ impl PostService {
     }
 
     /// Unpublish a post with permission and ownership checks
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
     pub async fn unpublish(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<Post> {
         domain::check_permission(permissions, POST_PUBLISH)?;
 
@@ -129,353 +510,4348 @@ impl PostService {
         domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
 
         post.unpublish();
-        self.repo.update_post(post).await
+        let updated_post = self.repo.update_post(post).await?;
+        tracing::info!("Post {} unpublished by user {}", id, user_id);
+
+        self.notify_webhook(WebhookEvent::PostUnpublished, &updated_post);
+
+        Ok(updated_post)
     }
 
-    /// Delete a post with permission and ownership checks
-    pub async fn delete(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<()> {
-        domain::check_permission(permissions, POST_DELETE)?;
+    /// Publish several posts in one update, or schedule them for later
+    ///
+    /// Each post is checked for ownership separately; posts that fail the
+    /// check or do not exist are reported in `failed` without stopping the
+    /// others. The remaining posts are updated together in a single
+    /// statement. With `schedule_at` the posts are scheduled as in
+    /// [`Self::schedule`] and already-published posts are reported as
+    /// failures. Webhooks and IndexNow are only notified for posts that go
+    /// live immediately.
+    ///
+    /// # Returns
+    /// * `Ok(BulkResult)` - Which posts were published and which failed
+    /// * `Err(Error::Validation)` - Missing POST_PUBLISH, too many posts, or
+    ///   `schedule_at` is not in the future
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn bulk_publish(
+        &self,
+        post_ids: Vec<Uuid>,
+        schedule_at: Option<chrono::DateTime<chrono::Utc>>,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<BulkResult> {
+        let now = chrono::Utc::now();
+        if let Some(at) = schedule_at {
+            Self::validate_schedule_time(at, now)?;
+            let (result, _) = self
+                .bulk_set_publication(post_ids, None, Some(at), user_id, permissions)
+                .await?;
+            return Ok(result);
+        }
 
-        let post = self.repo.get_post(id).await?;
+        let (result, posts) = self
+            .bulk_set_publication(post_ids, Some(now), None, user_id, permissions)
+            .await?;
 
-        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+        for post in &posts {
+            self.notify_webhook(WebhookEvent::PostPublished, post);
+            let _ = self.notify_indexnow(post.id).await;
+        }
 
-        self.repo.delete_post(id).await
+        Ok(result)
     }
 
-    /// List published posts
-    pub async fn list_published(&self, limit: Option<u64>) -> Result<Vec<Post>> {
-        self.repo
-            .list_published_posts(limit.unwrap_or(DEFAULT_LIST_LIMIT))
-            .await
-    }
+    /// Unpublish several posts in one update
+    ///
+    /// Failures are collected per post as in [`Self::bulk_publish`]. Any
+    /// pending schedule on the posts is cleared as well.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn bulk_unpublish(
+        &self,
+        post_ids: Vec<Uuid>,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<BulkResult> {
+        let (result, posts) = self
+            .bulk_set_publication(post_ids, None, None, user_id, permissions)
+            .await?;
 
-    /// Get posts by user
-    pub async fn list_by_user(&self, user_id: Uuid, limit: Option<u64>) -> Result<Vec<Post>> {
-        self.repo
-            .get_posts_by_user(user_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
-            .await
-    }
+        for post in &posts {
+            self.notify_webhook(WebhookEvent::PostUnpublished, post);
+        }
 
-    /// List all posts (including unpublished) - admin only
-    pub async fn list_all(&self, limit: Option<u64>) -> Result<Vec<Post>> {
-        self.repo
-            .list_all_posts(limit.unwrap_or(DEFAULT_LIST_LIMIT))
-            .await
+        Ok(result)
     }
 
-    /// List published posts by a specific user
-    pub async fn list_published_by_user(
+    /// Check each post, then set the publication fields on the allowed ones at once
+    ///
+    /// Returns the outcome and the updated posts.
+    async fn bulk_set_publication(
         &self,
+        post_ids: Vec<Uuid>,
+        published_at: Option<chrono::DateTime<chrono::Utc>>,
+        scheduled_publish_at: Option<chrono::DateTime<chrono::Utc>>,
         user_id: Uuid,
-        limit: Option<u64>,
-    ) -> Result<Vec<Post>> {
+        permissions: u64,
+    ) -> Result<(BulkResult, Vec<Post>)> {
+        domain::check_permission(permissions, POST_PUBLISH)?;
+        if post_ids.len() > MAX_BULK_POSTS {
+            return Err(Error::Validation(format!(
+                "At most {} posts can be changed at once",
+                MAX_BULK_POSTS
+            )));
+        }
+
+        let mut result = BulkResult::default();
+        let mut posts = Vec::new();
+        for id in post_ids {
+            if posts.iter().any(|p: &Post| p.id == id) {
+                continue;
+            }
+            let checked = match self.repo.get_post(id).await {
+                Ok(post) => domain::check_ownership_or_admin(
+                    post.user_id,
+                    user_id,
+                    permissions,
+                    POST_DELETE,
+                )
+                .and_then(|_| {
+                    if scheduled_publish_at.is_some() && post.is_published() {
+                        Err(Error::Validation("Post is already published".to_string()))
+                    } else {
+                        Ok(post)
+                    }
+                }),
+                Err(e) => Err(e),
+            };
+            match checked {
+                Ok(mut post) => {
+                    post.published_at = published_at;
+                    post.scheduled_publish_at = scheduled_publish_at;
+                    posts.push(post);
+                }
+                Err(e) => result.failed.push((id, e.to_string())),
+            }
+        }
+
+        let ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
         self.repo
-            .list_published_posts_by_user(user_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
-            .await
+            .bulk_update_publication(ids.clone(), published_at, scheduled_publish_at)
+            .await?;
+        result.succeeded = ids;
+
+        Ok((result, posts))
     }
 
-    /// Notify IndexNow for a single post and update status
-    pub async fn notify_indexnow(&self, post_id: Uuid) -> Result<Post> {
-        let Some(client) = &self.indexnow_client else {
-            tracing::debug!("IndexNow client not configured");
-            return self.repo.get_post(post_id).await;
-        };
+    /// Schedule an unpublished post to go live at `publish_at`
+    ///
+    /// The post stays unpublished until the background scheduler picks it
+    /// up via [`Self::publish_due_scheduled`]. Scheduling again replaces the
+    /// previous time.
+    ///
+    /// # Returns
+    /// * `Ok(Post)` - The post with `scheduled_publish_at` set
+    /// * `Err(Error::Validation)` - Missing POST_PUBLISH, not the owner,
+    ///   `publish_at` is not in the future, or the post is already published
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn schedule(
+        &self,
+        post_id: Uuid,
+        publish_at: chrono::DateTime<chrono::Utc>,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<Post> {
+        domain::check_permission(permissions, POST_PUBLISH)?;
+        Self::validate_schedule_time(publish_at, chrono::Utc::now())?;
 
-        let Some(key) = &self.indexnow_key else {
-            tracing::debug!("IndexNow key not configured");
-            return self.repo.get_post(post_id).await;
-        };
+        let mut post = self.repo.get_post(post_id).await?;
+
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        if post.is_published() {
+            return Err(Error::Validation("Post is already published".to_string()));
+        }
+
+        post.scheduled_publish_at = Some(publish_at);
+        self.repo.update_post(post).await
+    }
+
+    /// Cancel a pending schedule, leaving the post unpublished
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn cancel_schedule(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<Post> {
+        domain::check_permission(permissions, POST_PUBLISH)?;
 
-        // Get current post
         let mut post = self.repo.get_post(post_id).await?;
 
-        // Prepare IndexNow request
-        let url = format!("{}/post/{}", self.base_url, post.id);
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
 
-        let base_url_clean = self
-            .base_url
-            .trim_start_matches("http://")
-            .trim_start_matches("https://");
-        let host = base_url_clean.split('/').next().unwrap_or("localhost");
+        post.scheduled_publish_at = None;
+        self.repo.update_post(post).await
+    }
 
-        let key_location = format!("{}/{}.txt", self.base_url, key);
+    /// Create several posts that go live at their own times (admin only)
+    ///
+    /// Meant for importing a backlog of prepared posts. Every entry is
+    /// checked before anything is written and the posts are stored in one
+    /// transaction, so either the whole batch is created or none of it.
+    /// Categories and tags are referenced by slug and created when missing.
+    /// The posts stay unpublished until the background scheduler picks them
+    /// up via [`Self::publish_due_scheduled`].
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Post>)` - The created posts, in request order
+    /// * `Err(Error::Validation)` - Missing USER_MANAGE, an empty or too large
+    ///   batch, or an invalid entry; the message names the entry
+    #[tracing::instrument(level = "debug", skip_all, fields(%importer_id, count = posts.len()))]
+    pub async fn schedule_batch(
+        &self,
+        posts: Vec<NewScheduledPost>,
+        importer_id: Uuid,
+        permissions: u64,
+    ) -> Result<Vec<Post>> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+        if posts.is_empty() {
+            return Err(Error::Validation("No posts to schedule".to_string()));
+        }
+        if posts.len() > MAX_BULK_POSTS {
+            return Err(Error::Validation(format!(
+                "At most {} posts can be scheduled at once",
+                MAX_BULK_POSTS
+            )));
+        }
 
-        let request = IndexNowRequest {
-            host: host.to_string(),
-            key: key.clone(),
-            key_location: Some(key_location),
-            url_list: vec![url],
+        let now = chrono::Utc::now();
+        for (index, entry) in posts.iter().enumerate() {
+            self.validate_scheduled_post(entry, now, permissions)
+                .map_err(|e| match e {
+                    Error::Validation(msg) => {
+                        Error::Validation(format!("Post {}: {}", index + 1, msg))
+                    }
+                    Error::CodedError {
+                        code,
+                        message,
+                        field,
+                    } => Error::CodedError {
+                        code,
+                        message: format!("Post {}: {}", index + 1, message),
+                        field,
+                    },
+                    e => e,
+                })?;
+        }
+        let tags = match &self.tag_repo {
+            Some(tag_repo) => Some(TagService::new(Arc::clone(tag_repo))),
+            None if posts.iter().any(|p| !p.tags.is_empty()) => {
+                return Err(internal_error("Tag repository not configured"))
+            }
+            None => None,
         };
+        if self.category_repo.is_none() && posts.iter().any(|p| p.category.is_some()) {
+            return Err(internal_error("Category repository not configured"));
+        }
 
-        // Update status to pending
-        post.indexnow_submitted = true;
-        post.indexnow_submitted_at = Some(chrono::Utc::now());
-        post.indexnow_last_status = Some("pending".to_string());
-        post.indexnow_last_error = None;
-        post = self.repo.update_post(post).await?;
-
-        // Send notification
-        match client.notify(request).await {
-            Ok(()) => {
-                post.indexnow_last_status = Some("success".to_string());
-                post.indexnow_last_error = None;
-                tracing::info!("IndexNow notification successful for post {}", post_id);
+        let mut batch = Vec::with_capacity(posts.len());
+        let mut batch_tags = Vec::with_capacity(posts.len());
+        for entry in posts {
+            let mut post = Post::new(importer_id, entry.title, entry.content);
+            post.scheduled_publish_at = Some(entry.scheduled_publish_at);
+            if let Some(slug) = entry.category {
+                post.category_id = Some(self.category_for_slug(slug).await?.id);
             }
-            Err(e) => {
-                post.indexnow_last_status = Some("failed".to_string());
-                post.indexnow_last_error = Some(e.clone());
-                tracing::error!("IndexNow notification failed for post {}: {}", post_id, e);
+            let mut tag_ids = Vec::with_capacity(entry.tags.len());
+            if let Some(tags) = &tags {
+                for slug in &entry.tags {
+                    tag_ids.push(tags.get_or_create(slug).await?.id);
+                }
             }
+            batch.push(post);
+            batch_tags.push(tag_ids);
         }
 
-        // Update final status
-        self.repo.update_post(post).await
+        let mut created = self.repo.batch_upsert_posts(batch).await?;
+        for (post, tag_ids) in created.iter().zip(batch_tags) {
+            if !tag_ids.is_empty() {
+                self.repo.replace_post_tags(post.id, tag_ids).await?;
+            }
+        }
+        tracing::info!(
+            "{} scheduled posts imported by user {}",
+            created.len(),
+            importer_id
+        );
+
+        created.iter_mut().for_each(Post::compute_reading_stats);
+        Ok(created)
     }
 
-    /// Set category for a post with permission and ownership checks
-    pub async fn set_category(
+    /// Check one entry of [`Self::schedule_batch`] without touching storage
+    fn validate_scheduled_post(
+        &self,
+        entry: &NewScheduledPost,
+        now: chrono::DateTime<chrono::Utc>,
+        permissions: u64,
+    ) -> Result<()> {
+        self.validate_title(&entry.title)?;
+        self.validate_content(&entry.content, permissions)?;
+        for slug in entry.category.iter().chain(&entry.tags) {
+            self.validate_slug(slug)?;
+        }
+        Self::validate_schedule_time(entry.scheduled_publish_at, now)
+    }
+
+    /// Set a post's feature image from an uploaded file or an external URL
+    ///
+    /// Uploaded files must be images. External URLs must use HTTPS and answer
+    /// a HEAD request with a success status and, when given, an image content
+    /// type. With `storage.mirror_external_images` enabled the image is
+    /// downloaded and stored as an upload of the requester, and the local URL
+    /// is saved instead.
+    ///
+    /// # Returns
+    /// * `Ok(Post)` - The updated post
+    /// * `Err(Error::NotFound)` - Post or file not found
+    /// * `Err(Error::Validation)` - Permission denied, or the image is unusable
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn set_feature_image(
         &self,
         post_id: Uuid,
-        category_id: Option<Uuid>,
+        source: FeatureImageSource,
         user_id: Uuid,
         permissions: u64,
-    ) -> Result<()> {
+    ) -> Result<Post> {
         domain::check_permission(permissions, POST_UPDATE)?;
 
-        let post = self.repo.get_post(post_id).await?;
+        let mut post = self.repo.get_post(post_id).await?;
+
         domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
 
-        self.repo.update_post_category(post_id, category_id).await
+        let url = match source {
+            FeatureImageSource::File(file_id) => {
+                let file = self
+                    .file_service()?
+                    .get_file(file_id)
+                    .await?
+                    .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
+                if !file.content_type.starts_with("image/") {
+                    return Err(Error::Validation(
+                        "Feature image must be an image file".to_string(),
+                    ));
+                }
+                file.url
+            }
+            FeatureImageSource::Url(url) => {
+                Self::validate_feature_image_url(&url)?;
+                self.external_feature_image(&url, user_id).await?
+            }
+        };
+
+        post.feature_image_url = Some(url);
+        self.repo.update_post(post).await
     }
 
-    /// Add tag to post with permission and ownership checks
-    pub async fn add_tag(
+    /// Remove a post's feature image
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn clear_feature_image(
         &self,
         post_id: Uuid,
-        tag_id: Uuid,
         user_id: Uuid,
         permissions: u64,
-    ) -> Result<()> {
+    ) -> Result<Post> {
         domain::check_permission(permissions, POST_UPDATE)?;
 
-        let post = self.repo.get_post(post_id).await?;
+        let mut post = self.repo.get_post(post_id).await?;
+
         domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
 
-        self.repo.add_tag_to_post(post_id, tag_id).await
+        post.feature_image_url = None;
+        self.repo.update_post(post).await
     }
 
-    /// Remove tag from post with permission and ownership checks
-    pub async fn remove_tag(
+    /// Check the external links in a post's content for broken URLs
+    ///
+    /// The first [`MAX_LINK_CHECKS`] distinct https:// URLs, from Markdown
+    /// links and bare URLs alike, get a HEAD request each. Results keep the
+    /// order the links appear in.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn validate_links(
         &self,
         post_id: Uuid,
-        tag_id: Uuid,
         user_id: Uuid,
         permissions: u64,
-    ) -> Result<()> {
+    ) -> Result<Vec<LinkCheckResult>> {
         domain::check_permission(permissions, POST_UPDATE)?;
 
         let post = self.repo.get_post(post_id).await?;
-        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
 
-        self.repo.remove_tag_from_post(post_id, tag_id).await
-    }
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
 
-    /// Get tags for a post
-    pub async fn get_tags(&self, post_id: Uuid) -> Result<Vec<domain::Tag>> {
-        self.repo.get_post_tags(post_id).await
+        check_links(external_links(&post.content), self.allow_private_hosts).await
     }
 
-    /// List published posts by category
-    pub async fn list_by_category(
+    /// Check that a feature image URL is an absolute HTTPS URL with a host
+    fn validate_feature_image_url(url: &str) -> Result<()> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|_| Error::Validation("Feature image URL is not a valid URL".to_string()))?;
+        if parsed.scheme() != "https" || parsed.host_str().is_none() {
+            return Err(Error::Validation(
+                "Feature image URL must be an https:// URL".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check an external image with a HEAD request, mirroring it if enabled
+    ///
+    /// Requests never reach non-public addresses (see [`crate::outbound`]),
+    /// and a mirrored download may not exceed the upload size limit.
+    /// Returns the URL to store on the post.
+    async fn external_feature_image(&self, url: &str, user_id: Uuid) -> Result<String> {
+        let parsed = outbound::parse_url(url, self.allow_private_hosts)?;
+        let client = outbound::client(
+            std::time::Duration::from_secs(FEATURE_IMAGE_TIMEOUT_SECS),
+            self.allow_private_hosts,
+        )?;
+
+        let head =
+            client.head(parsed.clone()).send().await.map_err(|e| {
+                Error::Validation(format!("Feature image URL is not reachable: {}", e))
+            })?;
+        if !head.status().is_success() {
+            return Err(Error::Validation(format!(
+                "Feature image URL returned status {}",
+                head.status()
+            )));
+        }
+        let content_type = media_type(head.headers());
+        if content_type
+            .as_deref()
+            .is_some_and(|content_type| !content_type.starts_with("image/"))
+        {
+            return Err(Error::Validation(
+                "Feature image URL does not point to an image".to_string(),
+            ));
+        }
+
+        let Some(storage) = self
+            .config
+            .as_ref()
+            .map(|config| config.read().unwrap().storage.clone())
+            .filter(|storage| storage.mirror_external_images)
+        else {
+            return Ok(url.to_string());
+        };
+        let max_bytes = storage
+            .max_file_size_mb
+            .unwrap_or(DEFAULT_MAX_FILE_SIZE_MB)
+            .saturating_mul(1024 * 1024);
+
+        let response = client
+            .get(parsed)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::Validation(format!("Failed to download feature image: {}", e)))?;
+        let content_type = media_type(response.headers())
+            .or(content_type)
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let mut filename = response
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("feature-image")
+            .to_string();
+        // Uploads are checked by extension, which image CDN URLs often lack
+        if !filename.contains('.') {
+            if let Some(subtype) = content_type.strip_prefix("image/") {
+                let extension = if subtype == "jpeg" { "jpg" } else { subtype };
+                filename = format!("{}.{}", filename, extension);
+            }
+        }
+        let data = outbound::read_limited(response, max_bytes).await?;
+
+        let file = self
+            .file_service()?
+            .upload_file(
+                user_id,
+                UploadFile {
+                    filename,
+                    content_type,
+                    data,
+                },
+            )
+            .await?;
+        Ok(file.url)
+    }
+
+    /// Publish every post whose schedule has come due by `now`
+    ///
+    /// Called periodically by the background scheduler. Each post is
+    /// published on behalf of its author; a failure is logged and the
+    /// remaining posts are still published.
+    ///
+    /// # Returns
+    /// * `Ok(count)` - Number of posts published
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn publish_due_scheduled(&self, now: chrono::DateTime<chrono::Utc>) -> Result<u64> {
+        let ids = self.repo.list_due_scheduled_post_ids(now).await?;
+
+        let mut published = 0;
+        for id in ids {
+            let result = match self.repo.get_post(id).await {
+                Ok(post) => self.publish(id, post.user_id, POST_PUBLISH).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(_) => published += 1,
+                Err(e) => tracing::warn!("Failed to publish scheduled post {}: {}", id, e),
+            }
+        }
+
+        Ok(published)
+    }
+
+    fn validate_schedule_time(
+        publish_at: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        if publish_at <= now {
+            return Err(Error::Validation(
+                "Scheduled time must be in the future".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Delete a post with permission and ownership checks
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
+    pub async fn delete(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<()> {
+        domain::check_permission(permissions, POST_DELETE)?;
+
+        let post = self.repo.get_post(id).await?;
+
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        self.repo.delete_post(id).await?;
+        tracing::info!("Post {} deleted by user {}", id, user_id);
+
+        // Soft-deleted posts no longer count towards their category
+        if let Some(category_id) = post.category_id {
+            self.repo.decrement_category_post_count(category_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore a soft-deleted post - admin only
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
+    pub async fn restore(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<Post> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        let post = self.repo.restore_post(id).await?;
+        tracing::info!("Post {} restored by user {}", id, user_id);
+
+        if let Some(category_id) = post.category_id {
+            self.repo.increment_category_post_count(category_id).await?;
+        }
+
+        Ok(post)
+    }
+
+    /// Pin a post to the top of listings - admin only
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
+    pub async fn pin(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<Post> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        let mut post = self.repo.get_post(id).await?;
+        post.pinned_at = Some(chrono::Utc::now());
+        let post = self.repo.update_post(post).await?;
+        tracing::info!("Post {} pinned by user {}", id, user_id);
+
+        Ok(post)
+    }
+
+    /// Remove a post from the top of listings - admin only
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
+    pub async fn unpin(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<Post> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        let mut post = self.repo.get_post(id).await?;
+        post.pinned_at = None;
+        let post = self.repo.update_post(post).await?;
+        tracing::info!("Post {} unpinned by user {}", id, user_id);
+
+        Ok(post)
+    }
+
+    /// Permanently remove posts soft-deleted more than `retention_days` ago
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn purge_deleted(&self, retention_days: i64) -> Result<u64> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days);
+        self.repo.purge_deleted_posts(cutoff).await
+    }
+
+    /// Move a post to the archive with permission and ownership checks
+    ///
+    /// Archived posts disappear from listings but can still be fetched by ID.
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
+    pub async fn archive(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<()> {
+        domain::check_permission(permissions, POST_DELETE)?;
+
+        let post = self.repo.get_post(id).await?;
+
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        self.move_to_archive(&post).await?;
+        tracing::info!("Post {} archived by user {}", id, user_id);
+        Ok(())
+    }
+
+    /// Move an archived post back to the live posts
+    #[tracing::instrument(level = "debug", skip_all, fields(post_id = %id, %user_id))]
+    pub async fn unarchive(&self, id: Uuid, user_id: Uuid, permissions: u64) -> Result<Post> {
+        domain::check_permission(permissions, POST_DELETE)?;
+
+        let post = self.repo.get_archived_post(id).await?;
+
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        self.repo.unarchive_post(id).await?;
+        let post = self.repo.get_post(id).await?;
+
+        if let Some(category_id) = post.category_id {
+            self.repo.increment_category_post_count(category_id).await?;
+        }
+
+        Ok(post)
+    }
+
+    /// Archive every post published more than `days` ago
+    ///
+    /// Posts are moved one at a time, so a failure leaves earlier moves in place.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn archive_older_than(&self, days: u32) -> Result<u64> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+        let ids = self.repo.list_published_post_ids_before(cutoff).await?;
+
+        let mut archived = 0;
+        for id in ids {
+            let post = self.repo.get_post(id).await?;
+            self.move_to_archive(&post).await?;
+            archived += 1;
+        }
+
+        Ok(archived)
+    }
+
+    /// List archived posts
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_archived(&self, limit: Option<u64>) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .list_archived_posts(limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// List published posts, skipping the first `offset` of them
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_published(&self, limit: Option<u64>, offset: u64) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .list_published_posts(limit.unwrap_or(DEFAULT_LIST_LIMIT), offset)
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// List published posts with their author's username and avatar
+    ///
+    /// Authors are joined in by the repository, so listings need no extra
+    /// user lookup per post.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_published_with_authors(
         &self,
-        category_id: Uuid,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> Result<Vec<PostWithAuthor>> {
+        let mut posts = self
+            .repo
+            .list_published_posts_with_authors(limit.unwrap_or(DEFAULT_LIST_LIMIT), offset)
+            .await?;
+        posts
+            .iter_mut()
+            .for_each(|entry| entry.post.compute_reading_stats());
+        Ok(posts)
+    }
+
+    /// List published posts with their author and tags
+    ///
+    /// The repository fetches the whole page in a single query, so the
+    /// number of database round trips does not grow with the page size as
+    /// it would with a [`Self::get_tags`] call per post.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_published_with_tags(
+        &self,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> Result<Vec<PostWithTags>> {
+        let mut posts = self
+            .repo
+            .list_posts_with_tags(limit.unwrap_or(DEFAULT_LIST_LIMIT), offset)
+            .await?;
+        posts
+            .iter_mut()
+            .for_each(|entry| entry.post.post.compute_reading_stats());
+        Ok(posts)
+    }
+
+    /// Count published posts, for paginating [`Self::list_published`]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn count_published(&self) -> Result<u64> {
+        self.repo.count_published().await
+    }
+
+    /// List posts published in a calendar month (UTC), newest first
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_for_month(
+        &self,
+        year: i32,
+        month: u32,
         limit: Option<u64>,
     ) -> Result<Vec<Post>> {
-        self.repo
-            .get_posts_by_category(category_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
-            .await
+        let (start, end) = month_bounds(year, month)?;
+        let posts = self
+            .repo
+            .list_published_between(start, end, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// Months that have published posts, newest first, with their post counts
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_archive_months(&self) -> Result<Vec<ArchiveMonth>> {
+        self.repo.count_published_by_month().await
+    }
+
+    /// List published posts with the most viewed first
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_published_by_views(&self, limit: Option<u64>) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .list_published_posts_by_views(limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// Get posts by user
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn list_by_user(&self, user_id: Uuid, limit: Option<u64>) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .get_posts_by_user(user_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// A user's posts with view and comment counts, newest first
+    ///
+    /// Drafts are included, so only the user themselves or an admin may
+    /// call this. Views and comment counts are fetched concurrently.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id, %requester_id))]
+    pub async fn list_user_posts_with_stats(
+        &self,
+        user_id: Uuid,
+        requester_id: Uuid,
+        requester_permissions: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<PostWithStats>> {
+        domain::check_ownership_or_admin(
+            user_id,
+            requester_id,
+            requester_permissions,
+            USER_MANAGE,
+        )?;
+        let comment_repo = self.comment_repo()?;
+
+        let (posts, comment_counts) = tokio::join!(
+            self.repo
+                .list_posts_with_view_counts(user_id, limit.unwrap_or(DEFAULT_LIST_LIMIT)),
+            comment_repo.count_comments_by_post_author(user_id),
+        );
+        let comment_counts = comment_counts?;
+
+        Ok(posts?
+            .into_iter()
+            .map(|(mut post, views)| {
+                post.compute_reading_stats();
+                PostWithStats {
+                    comment_count: comment_counts.get(&post.id).copied().unwrap_or(0),
+                    post,
+                    views,
+                }
+            })
+            .collect())
+    }
+
+    /// Blog-wide word counts for the admin dashboard (admin only)
+    ///
+    /// Deleted posts never count; drafts count unless `published_only` is set.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_word_count_stats(
+        &self,
+        published_only: bool,
+        requester_permissions: u64,
+    ) -> Result<WordCountStats> {
+        domain::check_permission(requester_permissions, USER_MANAGE)?;
+        self.repo.aggregate_word_counts(published_only).await
+    }
+
+    /// List all posts (including unpublished) - admin only
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_all(&self, limit: Option<u64>) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .list_all_posts(limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// List published posts by a specific user
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn list_published_by_user(
+        &self,
+        user_id: Uuid,
+        limit: Option<u64>,
+    ) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .list_published_posts_by_user(user_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// Notify IndexNow for a single post and update status
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn notify_indexnow(&self, post_id: Uuid) -> Result<Post> {
+        let indexnow = self
+            .clients
+            .as_ref()
+            .and_then(|clients| clients.read().unwrap().indexnow.clone());
+        let Some((client, key)) = indexnow else {
+            tracing::debug!("IndexNow client not configured");
+            return self.repo.get_post(post_id).await;
+        };
+
+        // Get current post
+        let mut post = self.repo.get_post(post_id).await?;
+
+        // Prepare IndexNow request
+        let url = format!("{}/post/{}", self.base_url, post.id);
+
+        let base_url_clean = self
+            .base_url
+            .trim_start_matches("http://")
+            .trim_start_matches("https://");
+        let host = base_url_clean.split('/').next().unwrap_or("localhost");
+
+        let key_location = format!("{}/{}.txt", self.base_url, key);
+
+        let request = IndexNowRequest {
+            host: host.to_string(),
+            key: key.clone(),
+            key_location: Some(key_location),
+            url_list: vec![url],
+        };
+
+        // Update status to pending
+        post.indexnow_submitted = true;
+        post.indexnow_submitted_at = Some(chrono::Utc::now());
+        post.indexnow_last_status = Some("pending".to_string());
+        post.indexnow_last_error = None;
+        post = self.repo.update_post(post).await?;
+
+        // Send notification, retrying transient failures
+        match client.retry_notify(request).await {
+            Ok(()) => {
+                post.indexnow_last_status = Some("success".to_string());
+                post.indexnow_last_error = None;
+                tracing::info!("IndexNow notification successful for post {}", post_id);
+            }
+            Err(e) => {
+                post.indexnow_last_status = Some("failed".to_string());
+                post.indexnow_last_error = Some(e.clone());
+                tracing::error!("IndexNow notification failed for post {}: {}", post_id, e);
+            }
+        }
+
+        // Update final status
+        self.repo.update_post(post).await
+    }
+
+    /// Deliver a webhook event for a post in the background
+    ///
+    /// Delivery never delays the request, and failures are only logged: the
+    /// post change has already been persisted at this point.
+    fn notify_webhook(&self, event: WebhookEvent, post: &Post) {
+        let webhook = self
+            .clients
+            .as_ref()
+            .and_then(|clients| clients.read().unwrap().webhook.clone());
+        let Some(client) = webhook else {
+            return;
+        };
+
+        let (post_id, payload) = (post.id, WebhookPayload::for_post(event, post));
+        tokio::spawn(async move {
+            if let Err(e) = client.send(&payload).await {
+                tracing::error!("Webhook notification failed for post {}: {}", post_id, e);
+            }
+        });
+    }
+
+    /// Set category for a post with permission and ownership checks
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn set_category(
+        &self,
+        post_id: Uuid,
+        category_id: Option<Uuid>,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<()> {
+        domain::check_permission(permissions, POST_UPDATE)?;
+
+        let post = self.repo.get_post(post_id).await?;
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        // The repository moves the post between category counts atomically
+        self.repo.update_post_category(post_id, category_id).await
+    }
+
+    /// Add tag to post with permission and ownership checks
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %tag_id, %user_id))]
+    pub async fn add_tag(
+        &self,
+        post_id: Uuid,
+        tag_id: Uuid,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<()> {
+        domain::check_permission(permissions, POST_UPDATE)?;
+
+        let post = self.repo.get_post(post_id).await?;
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        self.repo.add_tag_to_post(post_id, tag_id).await
+    }
+
+    /// Replace all tags of a post with permission and ownership checks
+    ///
+    /// Every tag must exist; otherwise nothing is changed and the invalid
+    /// IDs are reported. Duplicate IDs are ignored.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %user_id))]
+    pub async fn set_tags(
+        &self,
+        post_id: Uuid,
+        tag_ids: Vec<Uuid>,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<Vec<domain::Tag>> {
+        domain::check_permission(permissions, POST_UPDATE)?;
+
+        let post = self.repo.get_post(post_id).await?;
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        let tag_repo = self
+            .tag_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Tag repository not configured"))?;
+
+        let mut unique_ids = Vec::with_capacity(tag_ids.len());
+        for tag_id in tag_ids {
+            if !unique_ids.contains(&tag_id) {
+                unique_ids.push(tag_id);
+            }
+        }
+
+        let mut invalid = Vec::new();
+        for tag_id in &unique_ids {
+            if tag_repo.get_tag(*tag_id).await?.is_none() {
+                invalid.push(tag_id.to_string());
+            }
+        }
+        if !invalid.is_empty() {
+            return Err(Error::Validation(format!(
+                "Invalid tag IDs: {}",
+                invalid.join(", ")
+            )));
+        }
+
+        self.repo.replace_post_tags(post_id, unique_ids).await?;
+        self.repo.get_post_tags(post_id).await
+    }
+
+    /// Remove tag from post with permission and ownership checks
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id, %tag_id, %user_id))]
+    pub async fn remove_tag(
+        &self,
+        post_id: Uuid,
+        tag_id: Uuid,
+        user_id: Uuid,
+        permissions: u64,
+    ) -> Result<()> {
+        domain::check_permission(permissions, POST_UPDATE)?;
+
+        let post = self.repo.get_post(post_id).await?;
+        domain::check_ownership_or_admin(post.user_id, user_id, permissions, POST_DELETE)?;
+
+        self.repo.remove_tag_from_post(post_id, tag_id).await
+    }
+
+    /// Get tags for a post
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn get_tags(&self, post_id: Uuid) -> Result<Vec<domain::Tag>> {
+        self.repo.get_post_tags(post_id).await
+    }
+
+    /// List published posts by category
+    ///
+    /// With `recursive` the posts of all descendant categories are included.
+    #[tracing::instrument(level = "debug", skip_all, fields(%category_id, recursive))]
+    pub async fn list_by_category(
+        &self,
+        category_id: Uuid,
+        limit: Option<u64>,
+        recursive: bool,
+    ) -> Result<Vec<Post>> {
+        let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT);
+        let posts = if recursive {
+            self.repo
+                .get_posts_by_category_recursive(category_id, limit)
+                .await?
+        } else {
+            self.repo.get_posts_by_category(category_id, limit).await?
+        };
+        Ok(with_reading_stats(posts))
+    }
+
+    /// List published posts by tag
+    #[tracing::instrument(level = "debug", skip_all, fields(%tag_id))]
+    pub async fn list_by_tag(&self, tag_id: Uuid, limit: Option<u64>) -> Result<Vec<Post>> {
+        let posts = self
+            .repo
+            .get_posts_by_tag(tag_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await?;
+        Ok(with_reading_stats(posts))
+    }
+
+    /// OpenGraph preview data of a published post
+    ///
+    /// The image is the feature image, falling back to the first image in
+    /// the content; site-relative URLs are made absolute. Drafts are
+    /// reported as not found.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn get_og_data(&self, post_id: Uuid) -> Result<OgData> {
+        let user_repo = self
+            .user_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("User repository not configured"))?;
+
+        let post = self.get(post_id).await?;
+        if !post.is_published() {
+            return Err(Error::coded(ErrorCode::PostNotFound, "Post not found"));
+        }
+
+        let author = user_repo
+            .find_by_id(post.user_id)
+            .await?
+            .map(|user| user.username)
+            .unwrap_or_else(|| domain::post::DELETED_AUTHOR_USERNAME.to_string());
+        let tags = self
+            .repo
+            .get_post_tags(post_id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        let image_url = post
+            .feature_image_url
+            .as_deref()
+            .or_else(|| domain::post::first_image_url(&post.content))
+            .map(|src| self.absolute_url(src));
+
+        Ok(OgData {
+            description: domain::post::plain_text(&post.content)
+                .chars()
+                .take(domain::post::OG_DESCRIPTION_MAX_CHARS)
+                .collect(),
+            image_url,
+            url: format!("{}/post/{}", self.base_url, post.id),
+            author,
+            published_at: post.published_at,
+            tags,
+            title: post.title,
+        })
+    }
+
+    /// Resolve a site-relative URL against the base URL
+    fn absolute_url(&self, src: &str) -> String {
+        if src.starts_with("http://") || src.starts_with("https://") {
+            src.to_string()
+        } else {
+            format!(
+                "{}/{}",
+                self.base_url.trim_end_matches('/'),
+                src.trim_start_matches('/')
+            )
+        }
+    }
+
+    /// Published posts sharing the most tags with the given post
+    ///
+    /// Ties are broken by publication date, newest first. Posts without tags
+    /// have no related posts.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn get_related(&self, post_id: Uuid, limit: u64) -> Result<Vec<Post>> {
+        let tag_ids: Vec<Uuid> = self
+            .repo
+            .get_post_tags(post_id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect();
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut related = self
+            .repo
+            .get_posts_sharing_tags(tag_ids, post_id, limit)
+            .await?;
+        related.sort_by(|(a, a_shared), (b, b_shared)| {
+            b_shared
+                .cmp(a_shared)
+                .then_with(|| b.published_at.cmp(&a.published_at))
+        });
+        related.truncate(limit as usize);
+
+        Ok(with_reading_stats(
+            related.into_iter().map(|(post, _)| post).collect(),
+        ))
+    }
+
+    /// Published posts just before and after a published post
+    ///
+    /// Both neighbours are looked up concurrently.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn get_adjacent(&self, post_id: Uuid) -> Result<AdjacentPosts> {
+        let published_at = self.published_at(post_id).await?;
+        let (prev, next) = tokio::join!(
+            self.repo.get_prev_published_post(published_at, post_id),
+            self.repo.get_next_published_post(published_at, post_id),
+        );
+
+        Ok(AdjacentPosts {
+            prev: prev?,
+            next: next?,
+        })
+    }
+
+    /// Published post following a published post, if any
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn get_next(&self, post_id: Uuid) -> Result<Option<Post>> {
+        let published_at = self.published_at(post_id).await?;
+        self.repo
+            .get_next_published_post(published_at, post_id)
+            .await
+    }
+
+    /// Published post preceding a published post, if any
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn get_prev(&self, post_id: Uuid) -> Result<Option<Post>> {
+        let published_at = self.published_at(post_id).await?;
+        self.repo
+            .get_prev_published_post(published_at, post_id)
+            .await
+    }
+
+    /// Publication date of a post, treating drafts as missing
+    async fn published_at(&self, post_id: Uuid) -> Result<chrono::DateTime<chrono::Utc>> {
+        self.get(post_id)
+            .await?
+            .published_at
+            .ok_or_else(|| Error::coded(ErrorCode::PostNotFound, "Post not found"))
+    }
+
+    /// Table of contents built from the headings of a published post
+    ///
+    /// Returns at most [`MAX_TOC_ENTRIES`] headings in document order.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn extract_toc(&self, post_id: Uuid) -> Result<Vec<TocEntry>> {
+        let post = self.get(post_id).await?;
+        if !post.is_published() {
+            return Err(Error::coded(ErrorCode::PostNotFound, "Post not found"));
+        }
+
+        Ok(table_of_contents(&post.content))
+    }
+
+    /// Render post content from Markdown to sanitized HTML
+    ///
+    /// Raw HTML in the content keeps the sanitizer's safe defaults plus the
+    /// configured allow-list, so owners can embed e.g. YouTube iframes.
+    pub fn render_content(&self, markdown: &str) -> String {
+        render_html(markdown, &self.html_allow_list())
+    }
+
+    /// Like a published post on behalf of an IP address
+    ///
+    /// Each IP can like a post once; repeated likes are ignored. The IP is
+    /// stored only as a salted SHA-256 hash.
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn like(&self, post_id: Uuid, ip_address: &str) -> Result<LikeResponse> {
+        let like_repo = self.like_repo()?;
+
+        let post = self.repo.get_post(post_id).await?;
+        if !post.is_published() {
+            return Err(Error::coded(ErrorCode::PostNotFound, "Post not found"));
+        }
+
+        let liked = like_repo
+            .add_like(post_id, &self.hash_ip(ip_address))
+            .await?;
+        let likes = like_repo.count_likes(post_id).await?;
+
+        Ok(LikeResponse { liked, likes })
+    }
+
+    /// Count the likes of a post
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn count_likes(&self, post_id: Uuid) -> Result<u64> {
+        self.like_repo()?.count_likes(post_id).await
+    }
+
+    /// Search posts by query
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn search(&self, request: SearchPostsRequest) -> Result<SearchPostsResponse> {
+        let query = request.query.trim();
+        if query.is_empty() {
+            return Err(Error::Validation(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+        let limit = request.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+        let offset = request.offset.unwrap_or(0);
+        let mut response = self.repo.search_posts(query, limit, offset).await?;
+        response.posts = with_reading_stats(response.posts);
+        Ok(response)
+    }
+
+    /// Search published posts by several fields at once
+    ///
+    /// The limit defaults to [`DEFAULT_LIST_LIMIT`] and is capped at
+    /// [`MAX_SEARCH_LIMIT`].
+    ///
+    /// # Returns
+    /// * `Ok(SearchPostsResponse)` - The matching page and the total count
+    /// * `Err(Error::Validation)` - No filter is set, or the date range is empty
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn search_advanced(
+        &self,
+        mut request: PostSearchRequest,
+    ) -> Result<SearchPostsResponse> {
+        if !request.has_filter() {
+            return Err(Error::Validation(
+                "At least one search filter is required".to_string(),
+            ));
+        }
+        if let (Some(after), Some(before)) = (request.published_after, request.published_before) {
+            if after >= before {
+                return Err(Error::Validation(
+                    "published_after must be earlier than published_before".to_string(),
+                ));
+            }
+        }
+
+        request.limit = Some(
+            request
+                .limit
+                .unwrap_or(DEFAULT_LIST_LIMIT)
+                .min(MAX_SEARCH_LIMIT),
+        );
+        request.offset = Some(request.offset.unwrap_or(0));
+
+        let mut response = self.repo.search_posts_advanced(&request).await?;
+        response.posts = with_reading_stats(response.posts);
+        Ok(response)
+    }
+}
+
+/// Compute reading stats for every post in a list
+fn with_reading_stats(mut posts: Vec<Post>) -> Vec<Post> {
+    posts.iter_mut().for_each(Post::compute_reading_stats);
+    posts
+}
+
+/// Start of `month` and start of the following month, in UTC
+fn month_bounds(
+    year: i32,
+    month: u32,
+) -> Result<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
+    let invalid = || Error::Validation(format!("Invalid month: {}-{:02}", year, month));
+    let first = chrono::NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(invalid)?;
+    let next = if month == 12 {
+        chrono::NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        chrono::NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .ok_or_else(invalid)?;
+
+    Ok((
+        first.and_time(chrono::NaiveTime::MIN).and_utc(),
+        next.and_time(chrono::NaiveTime::MIN).and_utc(),
+    ))
+}
+
+/// Render Markdown to HTML and sanitize it with ammonia
+///
+/// Tags and attributes of `allow_list` are kept on top of ammonia's safe
+/// defaults. [`ALWAYS_DENIED_TAGS`] and `on*` event handler attributes are
+/// dropped whatever the allow-list says.
+fn render_html(markdown: &str, allow_list: &HtmlAllowList) -> String {
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, Parser::new(markdown));
+
+    let mut builder = ammonia::Builder::default();
+    builder.add_tags(allow_list.tags.iter().map(String::as_str).filter(|tag| {
+        !ALWAYS_DENIED_TAGS
+            .iter()
+            .any(|denied| tag.eq_ignore_ascii_case(denied))
+    }));
+    for (tag, attributes) in &allow_list.attributes {
+        builder.add_tag_attributes(
+            tag.as_str(),
+            attributes
+                .iter()
+                .map(String::as_str)
+                .filter(|attribute| !is_event_handler(attribute)),
+        );
+    }
+
+    builder.clean(&html).to_string()
+}
+
+/// Check if an attribute is an inline event handler such as `onclick`
+fn is_event_handler(attribute: &str) -> bool {
+    attribute
+        .get(..2)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("on"))
+}
+
+/// Headings of a Markdown document with unique anchors
+///
+/// Anchors are the lowercased heading text with every run of other
+/// characters replaced by `-`; repeats get `-2`, `-3`, ... appended.
+fn table_of_contents(markdown: &str) -> Vec<TocEntry> {
+    let mut entries = Vec::new();
+    let mut used = HashSet::new();
+    let mut heading: Option<(u8, String)> = None;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some((level as u8, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, buf)) = heading.as_mut() {
+                    buf.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((level, text)) = heading.take() else {
+                    continue;
+                };
+                let text = text.trim().to_string();
+                if text.is_empty() {
+                    continue;
+                }
+
+                let base = heading_anchor(&text);
+                let mut anchor = base.clone();
+                let mut n = 2;
+                while !used.insert(anchor.clone()) {
+                    anchor = format!("{}-{}", base, n);
+                    n += 1;
+                }
+
+                entries.push(TocEntry {
+                    level,
+                    text,
+                    anchor,
+                });
+                if entries.len() == MAX_TOC_ENTRIES {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// Distinct https:// URLs linked from a Markdown document, in order
+///
+/// Covers `[text](url)` links, autolinks and bare URLs in text; code is
+/// skipped. At most [`MAX_LINK_CHECKS`] URLs are returned.
+fn external_links(markdown: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut seen = HashSet::new();
+    let mut in_code_block = false;
+
+    let mut push = |url: &str| {
+        let is_https = reqwest::Url::parse(url)
+            .is_ok_and(|parsed| parsed.scheme() == "https" && parsed.host_str().is_some());
+        if is_https && seen.insert(url.to_string()) {
+            links.push(url.to_string());
+        }
+    };
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Link { dest_url, .. }) => push(&dest_url),
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(text) if !in_code_block => {
+                for (start, _) in text.match_indices("https://") {
+                    let url = text[start..]
+                        .split(|c: char| c.is_whitespace() || "<>()[]\"'".contains(c))
+                        .next()
+                        .unwrap_or_default()
+                        .trim_end_matches(['.', ',', ';', ':', '!', '?']);
+                    push(url);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    links.truncate(MAX_LINK_CHECKS);
+    links
+}
+
+/// Send a HEAD request to each URL, [`LINK_CHECK_CONCURRENCY`] at a time
+///
+/// Redirects are followed under the [`crate::outbound`] policy, so the
+/// status is that of the final response. URLs that point at a non-public
+/// address report status 0 without being requested.
+async fn check_links(urls: Vec<String>, allow_private: bool) -> Result<Vec<LinkCheckResult>> {
+    let client = outbound::client(
+        std::time::Duration::from_secs(LINK_CHECK_TIMEOUT_SECS),
+        allow_private,
+    )?;
+    let semaphore = tokio::sync::Semaphore::new(LINK_CHECK_CONCURRENCY);
+
+    let checks = urls.into_iter().map(|url| {
+        let client = &client;
+        let semaphore = &semaphore;
+        async move {
+            let parsed = match outbound::parse_url(&url, allow_private) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    tracing::debug!("Link {} is not checked: {}", url, e);
+                    return LinkCheckResult { url, status: 0 };
+                }
+            };
+            let _permit = semaphore.acquire().await;
+            let status = match client.head(parsed).send().await {
+                Ok(response) => response.status().as_u16(),
+                Err(e) => {
+                    tracing::debug!("Link {} is not reachable: {}", url, e);
+                    0
+                }
+            };
+            LinkCheckResult { url, status }
+        }
+    });
+
+    Ok(futures::future::join_all(checks).await)
+}
+
+/// Slug of a heading's text, `section` when nothing alphanumeric is left
+fn heading_anchor(text: &str) -> String {
+    let mut anchor = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            anchor.extend(c.to_lowercase());
+        } else if !anchor.is_empty() && !anchor.ends_with('-') {
+            anchor.push('-');
+        }
+    }
+    let anchor = anchor.trim_end_matches('-');
+
+    if anchor.is_empty() {
+        "section".to_string()
+    } else {
+        anchor.to_string()
+    }
+}
+
+// ============================================================================
+// Private Validation Helpers
+// ============================================================================
+
+impl PostService {
+    async fn move_to_archive(&self, post: &Post) -> Result<()> {
+        self.repo.archive_post(post.id).await?;
+
+        // Archived posts no longer count towards their category
+        if let Some(category_id) = post.category_id {
+            self.repo.decrement_category_post_count(category_id).await?;
+        }
+
+        Ok(())
+    }
+
+    fn like_repo(&self) -> Result<&Arc<dyn LikeRepository>> {
+        self.like_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Likes are not configured"))
+    }
+
+    fn comment_repo(&self) -> Result<&Arc<dyn CommentRepository>> {
+        self.comment_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Comment counts are not configured"))
+    }
+
+    fn file_service(&self) -> Result<&FileService> {
+        self.file_service
+            .as_ref()
+            .ok_or_else(|| internal_error("Feature images are not configured"))
+    }
+
+    /// Salted SHA-256 of an IP address, so likes never store the raw IP
+    fn hash_ip(&self, ip_address: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.like_salt.as_bytes());
+        hasher.update(ip_address.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    fn validate_title(&self, title: &str) -> Result<()> {
+        if title.trim().is_empty() {
+            return Err(Error::invalid_field("title", "Title cannot be empty"));
+        }
+        if title.len() > MAX_TITLE_LENGTH {
+            return Err(Error::invalid_field(
+                "title",
+                format!("Title too long (max {} characters)", MAX_TITLE_LENGTH),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_summary(&self, summary: &str) -> Result<()> {
+        if summary.chars().count() > domain::post::SUMMARY_MAX_CHARS {
+            return Err(Error::invalid_field(
+                "summary",
+                format!(
+                    "Summary too long (max {} characters)",
+                    domain::post::SUMMARY_MAX_CHARS
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_content(&self, content: &str, permissions: u64) -> Result<()> {
+        if content.trim().is_empty() {
+            return Err(Error::invalid_field("content", "Content cannot be empty"));
+        }
+        let max_chars = self.length_limits().max_chars(permissions);
+        if content.chars().count() > max_chars {
+            return Err(Error::invalid_field(
+                "content",
+                format!("Content too long (max {} characters)", max_chars),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_slug(&self, slug: &str) -> Result<()> {
+        if slug.is_empty()
+            || !slug
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::invalid_field(
+                "slug",
+                format!("Invalid slug: {}", slug),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+/// Media type of a response without parameters such as `charset`
+fn media_type(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)?
+        .to_str()
+        .ok()?
+        .split(';')
+        .next()
+        .map(|media_type| media_type.trim().to_ascii_lowercase())
+        .filter(|media_type| !media_type.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{shared_config, test_config, MockPostRepo};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_create_post_validates_empty_title() {
+        let mock_repo = Arc::new(MockPostRepo::new());
+        let service = PostService::new(mock_repo, "http://localhost".to_string());
+
+        let user_id = Uuid::new_v4();
+
+        let result = service
+            .create(
+                user_id,
+                "".to_string(),
+                "content".to_string(),
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+
+        assert!(result.is_err());
+        match result {
+            Err(e) => {
+                assert_eq!(e.field(), Some("title"));
+                assert!(e.message().contains("cannot be empty"));
+            }
+            _ => panic!("Expected validation error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_post_validates_title_too_long() {
+        let mock_repo = Arc::new(MockPostRepo::new());
+        let service = PostService::new(mock_repo, "http://localhost".to_string());
+
+        let user_id = Uuid::new_v4();
+        let long_title = "a".repeat(201);
+
+        let result = service
+            .create(
+                user_id,
+                long_title,
+                "content".to_string(),
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+
+        assert!(result.is_err());
+        match result {
+            Err(e) => {
+                assert_eq!(e.field(), Some("title"));
+                assert!(e.message().contains("too long"));
+            }
+            _ => panic!("Expected validation error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_post_validates_long_content() {
+        let mock_repo = Arc::new(MockPostRepo::new());
+        let service = PostService::new(mock_repo, "http://localhost".to_string());
+
+        let _long_content = "a".repeat(10001);
+        let user_id = Uuid::new_v4();
+
+        let result = service
+            .create(
+                user_id,
+                "title".to_string(),
+                "".to_string(),
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+
+        assert!(result.is_err());
+        match result {
+            Err(e) => {
+                assert_eq!(e.field(), Some("content"));
+                assert!(e.message().contains("cannot be empty"));
+            }
+            _ => panic!("Expected validation error"),
+        }
+    }
+
+    fn length_limited_service() -> PostService {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_create_post()
+            .returning(|user_id, title, content| Ok(Post::new(user_id, title, content)));
+        let mut config = test_config();
+        config.site.max_post_chars = Some(10);
+        config.site.max_post_chars_admin = Some(20);
+        PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_config(shared_config(config))
+    }
+
+    #[tokio::test]
+    async fn test_create_post_enforces_content_length_limit() {
+        let service = length_limited_service();
+        let create = |content: String| {
+            service.create(
+                Uuid::new_v4(),
+                "title".to_string(),
+                content,
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+        };
+
+        assert!(create("a".repeat(10)).await.is_ok());
+        match create("a".repeat(11)).await {
+            Err(e) => {
+                assert_eq!(e.field(), Some("content"));
+                assert!(e.message().contains("max 10 characters"));
+            }
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_content_length_limit_follows_config_reload() {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_create_post()
+            .returning(|user_id, title, content| Ok(Post::new(user_id, title, content)));
+        let mut config = test_config();
+        config.site.max_post_chars = Some(10);
+        let config = shared_config(config);
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_config(config.clone());
+        let create = |content: String| {
+            service.create(
+                Uuid::new_v4(),
+                "title".to_string(),
+                content,
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+        };
+
+        assert!(create("a".repeat(11)).await.is_err());
+        config.write().unwrap().site.max_post_chars = Some(20);
+        assert!(create("a".repeat(11)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_admin_content_length_limit_is_separate() {
+        let service = length_limited_service();
+        let create = |content: String| {
+            service.create(
+                Uuid::new_v4(),
+                "title".to_string(),
+                content,
+                None,
+                domain::ADMIN_PERMISSIONS,
+            )
+        };
+
+        assert!(create("a".repeat(20)).await.is_ok());
+        assert!(matches!(
+            create("a".repeat(21)).await,
+            Err(e) if e.field() == Some("content")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_requires_permission() {
+        let mock_repo = Arc::new(MockPostRepo::new());
+        let service = PostService::new(mock_repo, "http://localhost".to_string());
+
+        let post_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let no_permissions = 0;
+
+        let result = service
+            .update(
+                post_id,
+                None,
+                Some("new content".to_string()),
+                None,
+                user_id,
+                no_permissions,
+            )
+            .await;
+
+        assert!(result.is_err());
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected permission error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_post_requires_permission() {
+        let mock_repo = Arc::new(MockPostRepo::new());
+        let service = PostService::new(mock_repo, "http://localhost".to_string());
+
+        let post_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let no_permissions = 0;
+
+        let result = service.delete(post_id, user_id, no_permissions).await;
+
+        assert!(result.is_err());
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected permission error"),
+        }
+    }
+
+    fn draft_post(user_id: Uuid) -> Post {
+        Post::new(user_id, "Hello World".to_string(), "content".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_soft_deleted_post_hidden_until_restored() {
+        use std::sync::Mutex;
+
+        let owner = Uuid::new_v4();
+        let store = Arc::new(Mutex::new(vec![draft_post(owner)]));
+        let post_id = store.lock().unwrap()[0].id;
+
+        let mut mock_repo = MockPostRepo::new();
+        let posts = store.clone();
+        mock_repo.expect_get_post().returning(move |id| {
+            posts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.id == id && p.deleted_at.is_none())
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        });
+        let posts = store.clone();
+        mock_repo.expect_delete_post().returning(move |id| {
+            for post in posts.lock().unwrap().iter_mut().filter(|p| p.id == id) {
+                post.deleted_at = Some(chrono::Utc::now());
+            }
+            Ok(())
+        });
+        let posts = store.clone();
+        mock_repo.expect_list_all_posts().returning(move |_| {
+            Ok(posts
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|p| p.deleted_at.is_none())
+                .cloned()
+                .collect())
+        });
+        let posts = store.clone();
+        mock_repo.expect_restore_post().returning(move |id| {
+            let mut posts = posts.lock().unwrap();
+            let post = posts
+                .iter_mut()
+                .find(|p| p.id == id && p.deleted_at.is_some())
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))?;
+            post.deleted_at = None;
+            Ok(post.clone())
+        });
+        mock_repo
+            .expect_get_archived_post()
+            .returning(|_| Err(Error::NotFound("Post not found".to_string())));
+        mock_repo.expect_hard_delete_post().never();
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        service
+            .delete(post_id, owner, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert!(service.list_all(None).await.unwrap().is_empty());
+        assert!(service.get(post_id).await.is_err());
+
+        let restored = service
+            .restore(post_id, Uuid::new_v4(), domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(service.list_all(None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pin_and_unpin_post() {
+        let post = draft_post(Uuid::new_v4());
+        let post_id = post.id;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        mock_repo.expect_update_post().returning(Ok);
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let result = service
+            .pin(post_id, Uuid::new_v4(), domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+
+        let pinned = service
+            .pin(post_id, Uuid::new_v4(), domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert!(pinned.is_pinned());
+
+        let unpinned = service
+            .unpin(post_id, Uuid::new_v4(), domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert!(!unpinned.is_pinned());
+    }
+
+    #[tokio::test]
+    async fn test_restore_post_requires_admin() {
+        let mock_repo = Arc::new(MockPostRepo::new());
+        let service = PostService::new(mock_repo, "http://localhost".to_string());
+
+        let result = service
+            .restore(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                domain::DEFAULT_USER_PERMISSIONS | POST_DELETE,
+            )
+            .await;
+
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected permission error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archived_post_moves_out_of_listings() {
+        use std::sync::Mutex;
+
+        let owner = Uuid::new_v4();
+        let category_id = Uuid::new_v4();
+        let mut post = draft_post(owner);
+        post.category_id = Some(category_id);
+        let post_id = post.id;
+
+        let live = Arc::new(Mutex::new(vec![post]));
+        let archive: Arc<Mutex<Vec<Post>>> = Arc::new(Mutex::new(Vec::new()));
+        let count = Arc::new(Mutex::new(1i64));
+
+        let find = |store: &Arc<Mutex<Vec<Post>>>, id: Uuid| {
+            store
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.id == id)
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        };
+
+        let mut mock_repo = MockPostRepo::new();
+        let posts = live.clone();
+        mock_repo
+            .expect_get_post()
+            .returning(move |id| find(&posts, id));
+        let posts = archive.clone();
+        mock_repo
+            .expect_get_archived_post()
+            .returning(move |id| find(&posts, id));
+        let posts = live.clone();
+        mock_repo
+            .expect_list_all_posts()
+            .returning(move |_| Ok(posts.lock().unwrap().clone()));
+        let (from, to) = (live.clone(), archive.clone());
+        mock_repo.expect_archive_post().returning(move |id| {
+            let mut from = from.lock().unwrap();
+            let index = from.iter().position(|p| p.id == id).unwrap();
+            to.lock().unwrap().push(from.remove(index));
+            Ok(())
+        });
+        let (from, to) = (archive.clone(), live.clone());
+        mock_repo.expect_unarchive_post().returning(move |id| {
+            let mut from = from.lock().unwrap();
+            let index = from.iter().position(|p| p.id == id).unwrap();
+            to.lock().unwrap().push(from.remove(index));
+            Ok(())
+        });
+        let counter = count.clone();
+        mock_repo
+            .expect_decrement_category_post_count()
+            .returning(move |_| {
+                *counter.lock().unwrap() -= 1;
+                Ok(())
+            });
+        let counter = count.clone();
+        mock_repo
+            .expect_increment_category_post_count()
+            .returning(move |_| {
+                *counter.lock().unwrap() += 1;
+                Ok(())
+            });
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let denied = service
+            .archive(post_id, owner, domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(
+            denied,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+
+        service
+            .archive(post_id, owner, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert!(live.lock().unwrap().is_empty());
+        assert!(service.list_all(None).await.unwrap().is_empty());
+        assert_eq!(service.get(post_id).await.unwrap().id, post_id);
+        assert_eq!(*count.lock().unwrap(), 0);
+
+        let restored = service
+            .unarchive(post_id, owner, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert_eq!(restored.id, post_id);
+        assert!(archive.lock().unwrap().is_empty());
+        assert_eq!(service.list_all(None).await.unwrap().len(), 1);
+        assert_eq!(*count.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_archive_keeps_post_and_count() {
+        let mut post = Post::new(Uuid::new_v4(), "Old".to_string(), "content".to_string());
+        post.category_id = Some(Uuid::new_v4());
+        let mut mock_repo = MockPostRepo::new();
+        let id = post.id;
+        mock_repo
+            .expect_list_published_post_ids_before()
+            .returning(move |_| Ok(vec![id]));
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        mock_repo
+            .expect_archive_post()
+            .returning(|_| Err(Error::Internal("Failed to move post".to_string())));
+        mock_repo.expect_decrement_category_post_count().never();
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        assert!(service.archive_older_than(365).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_related_posts_sorted_by_shared_tags() {
+        let tags: Vec<domain::Tag> = (0..3)
+            .map(|i| domain::Tag::new(format!("Tag {}", i), format!("tag-{}", i)))
+            .collect();
+        let tag_ids: Vec<Uuid> = tags.iter().map(|t| t.id).collect();
+        let post_id = Uuid::new_v4();
+
+        let published = |title: &str, days_ago: i64| {
+            let mut post = Post::new(Uuid::new_v4(), title.to_string(), String::new());
+            post.published_at = Some(chrono::Utc::now() - chrono::Duration::days(days_ago));
+            post
+        };
+        let one_old = published("One shared, older", 5);
+        let one_new = published("One shared, newer", 1);
+        let three = published("Three shared", 10);
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post_tags()
+            .returning(move |_| Ok(tags.clone()));
+        let candidates = vec![
+            (one_old.clone(), 1),
+            (three.clone(), 3),
+            (one_new.clone(), 1),
+        ];
+        mock_repo
+            .expect_get_posts_sharing_tags()
+            .withf(move |ids, exclude, _| *ids == tag_ids && *exclude == post_id)
+            .returning(move |_, _, _| Ok(candidates.clone()));
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        // Posts sharing no tags are never returned by the repository
+        let related = service.get_related(post_id, 5).await.unwrap();
+        let titles: Vec<&str> = related.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Three shared", "One shared, newer", "One shared, older"]
+        );
+
+        let top = service.get_related(post_id, 1).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].id, three.id);
+    }
+
+    #[tokio::test]
+    async fn test_untagged_post_has_no_related_posts() {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo.expect_get_post_tags().returning(|_| Ok(vec![]));
+        mock_repo.expect_get_posts_sharing_tags().never();
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        assert!(service
+            .get_related(Uuid::new_v4(), 5)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_publish_fires_signed_webhook() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let user_id = Uuid::new_v4();
+        let post = draft_post(user_id);
+        let post_id = post.id;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        mock_repo.expect_update_post().returning(Ok);
+
+        let clients = crate::Clients {
+            webhook: Some(Arc::new(infrastructure::WebhookClient::new(
+                server.uri(),
+                Some("s3cret".to_string()),
+            ))),
+            ..Default::default()
+        };
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_clients(clients.shared());
+
+        let published = service
+            .publish(post_id, user_id, POST_PUBLISH)
+            .await
+            .unwrap();
+        assert!(published.is_published());
+
+        let requests = delivered_requests(&server, 1).await;
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["event"], "post.published");
+        assert_eq!(body["post_id"], post_id.to_string());
+        assert_eq!(body["slug"], "hello-world");
+        assert_eq!(
+            requests[0]
+                .headers
+                .get(infrastructure::webhook::SIGNATURE_HEADER)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            infrastructure::WebhookClient::sign("s3cret", &requests[0].body)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unpublish_ignores_webhook_failure() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let user_id = Uuid::new_v4();
+        let mut post = draft_post(user_id);
+        post.publish();
+        let post_id = post.id;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        mock_repo.expect_update_post().returning(Ok);
+
+        let clients = crate::Clients {
+            webhook: Some(Arc::new(infrastructure::WebhookClient::new(
+                server.uri(),
+                None,
+            ))),
+            ..Default::default()
+        };
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_clients(clients.shared());
+
+        let result = service.unpublish(post_id, user_id, POST_PUBLISH).await;
+        assert!(!result.unwrap().is_published());
+        delivered_requests(&server, 1).await;
+    }
+
+    /// Requests received by the mock server once at least `count` arrived
+    ///
+    /// Webhooks are delivered in the background, after the call returns.
+    async fn delivered_requests(
+        server: &wiremock::MockServer,
+        count: usize,
+    ) -> Vec<wiremock::Request> {
+        for _ in 0..200 {
+            let requests = server.received_requests().await.unwrap();
+            if requests.len() >= count {
+                return requests;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("Expected {} webhook deliveries", count);
+    }
+
+    /// In-memory like store that records the hashes it receives
+    #[derive(Default)]
+    struct MockLikeRepo {
+        likes: std::sync::Mutex<std::collections::HashSet<(Uuid, String)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LikeRepository for MockLikeRepo {
+        async fn add_like(&self, post_id: Uuid, ip_hash: &str) -> Result<bool> {
+            Ok(self
+                .likes
+                .lock()
+                .unwrap()
+                .insert((post_id, ip_hash.to_string())))
+        }
+
+        async fn count_likes(&self, post_id: Uuid) -> Result<u64> {
+            let likes = self.likes.lock().unwrap();
+            Ok(likes.iter().filter(|(id, _)| *id == post_id).count() as u64)
+        }
+    }
+
+    fn service_with_likes(post: Post, likes: Arc<MockLikeRepo>) -> PostService {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+
+        PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_likes(likes, "server-secret".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_like_is_idempotent_per_ip() {
+        let mut post = draft_post(Uuid::new_v4());
+        post.publish();
+        let post_id = post.id;
+        let likes = Arc::new(MockLikeRepo::default());
+        let service = service_with_likes(post, likes.clone());
+
+        let first = service.like(post_id, "203.0.113.7").await.unwrap();
+        assert!(first.liked);
+        assert_eq!(first.likes, 1);
+
+        let second = service.like(post_id, "203.0.113.7").await.unwrap();
+        assert!(!second.liked);
+        assert_eq!(second.likes, 1);
+
+        let other = service.like(post_id, "198.51.100.1").await.unwrap();
+        assert!(other.liked);
+        assert_eq!(other.likes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_like_never_stores_plaintext_ip() {
+        let mut post = draft_post(Uuid::new_v4());
+        post.publish();
+        let post_id = post.id;
+        let likes = Arc::new(MockLikeRepo::default());
+        let service = service_with_likes(post, likes.clone());
+
+        service.like(post_id, "203.0.113.7").await.unwrap();
+
+        let stored = likes.likes.lock().unwrap();
+        let (_, hash) = stored.iter().next().unwrap();
+        assert!(!hash.contains("203.0.113.7"));
+        assert_eq!(hash.len(), 64);
+        // The salt must be mixed in, so the hash differs from a bare SHA-256
+        assert_ne!(*hash, hex::encode(Sha256::digest(b"203.0.113.7")));
+    }
+
+    #[tokio::test]
+    async fn test_like_rejects_draft_post() {
+        let post = draft_post(Uuid::new_v4());
+        let post_id = post.id;
+        let service = service_with_likes(post, Arc::new(MockLikeRepo::default()));
+
+        let result = service.like(post_id, "203.0.113.7").await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PostNotFound,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_category_post_counts_stay_consistent() {
+        use std::collections::HashMap;
+        use std::sync::Mutex;
+
+        let owner = Uuid::new_v4();
+        let first = draft_post(owner);
+        let second = draft_post(owner);
+        let (first_id, second_id) = (first.id, second.id);
+        let (rust, go) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let store = Arc::new(Mutex::new(vec![first, second]));
+        let counts: Arc<Mutex<HashMap<Uuid, u32>>> = Arc::new(Mutex::new(HashMap::new()));
+        let mut mock_repo = MockPostRepo::new();
+
+        let posts = store.clone();
+        mock_repo.expect_get_post().returning(move |id| {
+            posts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.id == id && p.deleted_at.is_none())
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        });
+        let (posts, category_counts) = (store.clone(), counts.clone());
+        mock_repo
+            .expect_update_post_category()
+            .returning(move |id, category_id| {
+                let mut posts = posts.lock().unwrap();
+                let post = posts.iter_mut().find(|p| p.id == id).unwrap();
+                let mut counts = category_counts.lock().unwrap();
+                if let Some(old) = post.category_id {
+                    *counts.entry(old).or_default() -= 1;
+                }
+                if let Some(new) = category_id {
+                    *counts.entry(new).or_default() += 1;
+                }
+                post.category_id = category_id;
+                Ok(())
+            });
+        let category_counts = counts.clone();
+        mock_repo
+            .expect_increment_category_post_count()
+            .returning(move |id| {
+                *category_counts.lock().unwrap().entry(id).or_default() += 1;
+                Ok(())
+            });
+        let category_counts = counts.clone();
+        mock_repo
+            .expect_decrement_category_post_count()
+            .returning(move |id| {
+                let mut counts = category_counts.lock().unwrap();
+                let count = counts.entry(id).or_default();
+                *count = count.saturating_sub(1);
+                Ok(())
+            });
+        let posts = store.clone();
+        mock_repo.expect_delete_post().returning(move |id| {
+            for post in posts.lock().unwrap().iter_mut().filter(|p| p.id == id) {
+                post.deleted_at = Some(chrono::Utc::now());
+            }
+            Ok(())
+        });
+        let posts = store.clone();
+        mock_repo.expect_restore_post().returning(move |id| {
+            let mut posts = posts.lock().unwrap();
+            let post = posts.iter_mut().find(|p| p.id == id).unwrap();
+            post.deleted_at = None;
+            Ok(post.clone())
+        });
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+        let admin = domain::ADMIN_PERMISSIONS;
+        let count = |id: Uuid| counts.lock().unwrap().get(&id).copied().unwrap_or(0);
+
+        service
+            .set_category(first_id, Some(rust), owner, admin)
+            .await
+            .unwrap();
+        service
+            .set_category(second_id, Some(rust), owner, admin)
+            .await
+            .unwrap();
+        assert_eq!((count(rust), count(go)), (2, 0));
+
+        service
+            .set_category(second_id, Some(go), owner, admin)
+            .await
+            .unwrap();
+        assert_eq!((count(rust), count(go)), (1, 1));
+
+        service.delete(first_id, owner, admin).await.unwrap();
+        assert_eq!((count(rust), count(go)), (0, 1));
+
+        service.restore(first_id, owner, admin).await.unwrap();
+        assert_eq!((count(rust), count(go)), (1, 1));
+
+        service
+            .set_category(second_id, None, owner, admin)
+            .await
+            .unwrap();
+        assert_eq!((count(rust), count(go)), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_set_tags_with_invalid_id_changes_nothing() {
+        use crate::test_support::MockTagRepo;
+        use domain::Tag;
+        use std::sync::Mutex;
+
+        let owner = Uuid::new_v4();
+        let post = draft_post(owner);
+        let post_id = post.id;
+        let existing = Tag::new("Rust".to_string(), "rust".to_string());
+        let replacement = Tag::new("Go".to_string(), "go".to_string());
+        let missing = Uuid::new_v4();
+
+        let assigned = Arc::new(Mutex::new(vec![existing.clone()]));
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        let (tags, known) = (
+            assigned.clone(),
+            vec![existing.clone(), replacement.clone()],
+        );
+        mock_repo
+            .expect_replace_post_tags()
+            .returning(move |_, tag_ids| {
+                *tags.lock().unwrap() = known
+                    .iter()
+                    .filter(|t| tag_ids.contains(&t.id))
+                    .cloned()
+                    .collect();
+                Ok(())
+            });
+        let tags = assigned.clone();
+        mock_repo
+            .expect_get_post_tags()
+            .returning(move |_| Ok(tags.lock().unwrap().clone()));
+
+        let mut tag_repo = MockTagRepo::new();
+        let known = [existing.clone(), replacement.clone()];
+        tag_repo
+            .expect_get_tag()
+            .returning(move |id| Ok(known.iter().find(|t| t.id == id).cloned()));
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_tag_repository(Arc::new(tag_repo));
+        let admin = domain::ADMIN_PERMISSIONS;
+
+        let result = service
+            .set_tags(post_id, vec![replacement.id, missing], owner, admin)
+            .await;
+        match result {
+            Err(Error::Validation(msg)) => {
+                assert!(msg.contains(&missing.to_string()));
+                assert!(!msg.contains(&replacement.id.to_string()));
+            }
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+        assert_eq!(*assigned.lock().unwrap(), vec![existing.clone()]);
+
+        let tags = service
+            .set_tags(post_id, vec![replacement.id, replacement.id], owner, admin)
+            .await
+            .unwrap();
+        assert_eq!(tags, vec![replacement]);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_creates_independent_draft() {
+        use domain::Tag;
+        use std::sync::Mutex;
+
+        let owner = Uuid::new_v4();
+        let copier = Uuid::new_v4();
+        let mut original = draft_post(owner);
+        original.published_at = Some(chrono::Utc::now());
+        original.category_id = Some(Uuid::new_v4());
+        let original_id = original.id;
+        let tags = vec![
+            Tag::new("Rust".to_string(), "rust".to_string()),
+            Tag::new("Go".to_string(), "go".to_string()),
+        ];
+
+        let store = Arc::new(Mutex::new(vec![original.clone()]));
+        let assigned: Arc<Mutex<Vec<(Uuid, Uuid)>>> = Arc::new(Mutex::new(
+            tags.iter().map(|t| (original_id, t.id)).collect(),
+        ));
+        let mut mock_repo = MockPostRepo::new();
+
+        let posts = store.clone();
+        mock_repo.expect_get_post().returning(move |id| {
+            posts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.id == id)
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        });
+        let posts = store.clone();
+        mock_repo
+            .expect_create_post()
+            .returning(move |user_id, title, content| {
+                let post = Post::new(user_id, title, content);
+                posts.lock().unwrap().push(post.clone());
+                Ok(post)
+            });
+        let posts = store.clone();
+        mock_repo.expect_update_post().returning(move |post| {
+            let mut posts = posts.lock().unwrap();
+            let stored = posts.iter_mut().find(|p| p.id == post.id).unwrap();
+            *stored = post.clone();
+            Ok(post)
+        });
+        let posts = store.clone();
+        mock_repo
+            .expect_update_post_category()
+            .returning(move |id, category_id| {
+                let mut posts = posts.lock().unwrap();
+                posts.iter_mut().find(|p| p.id == id).unwrap().category_id = category_id;
+                Ok(())
+            });
+        let pairs = assigned.clone();
+        mock_repo
+            .expect_add_tag_to_post()
+            .returning(move |post_id, tag_id| {
+                pairs.lock().unwrap().push((post_id, tag_id));
+                Ok(())
+            });
+        let (pairs, all_tags) = (assigned.clone(), tags.clone());
+        mock_repo.expect_get_post_tags().returning(move |post_id| {
+            let pairs = pairs.lock().unwrap();
+            Ok(all_tags
+                .iter()
+                .filter(|t| pairs.contains(&(post_id, t.id)))
+                .cloned()
+                .collect())
+        });
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let copy = service
+            .duplicate(original_id, copier, domain::DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap();
+        assert_ne!(copy.id, original_id);
+        assert_eq!(copy.user_id, copier);
+        assert!(!copy.is_published());
+        assert_eq!(copy.title, "Copy of Hello World");
+        assert_eq!(copy.content, original.content);
+        assert_eq!(copy.category_id, original.category_id);
+        assert_eq!(service.get_tags(copy.id).await.unwrap(), tags);
+
+        service
+            .update(
+                copy.id,
+                Some("Edited".to_string()),
+                None,
+                None,
+                copier,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+        let unchanged = service.get(original_id).await.unwrap();
+        assert_eq!(unchanged.title, original.title);
+        assert_eq!(unchanged.published_at, original.published_at);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_hides_other_users_drafts() {
+        let draft = draft_post(Uuid::new_v4());
+        let draft_id = draft.id;
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(draft.clone()));
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let result = service
+            .duplicate(draft_id, Uuid::new_v4(), domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(result.is_err());
+    }
+
+    /// Post service over an in-memory post store supporting create, get and update
+    fn service_with_post_store() -> PostService {
+        PostService::new(
+            Arc::new(post_store_repo(Arc::default())),
+            "http://localhost".to_string(),
+        )
+    }
+
+    /// Mock repository keeping created posts in `store`
+    fn post_store_repo(store: Arc<std::sync::Mutex<Vec<Post>>>) -> MockPostRepo {
+        let mut mock_repo = MockPostRepo::new();
+        let posts = store.clone();
+        mock_repo
+            .expect_create_post()
+            .returning(move |user_id, title, content| {
+                let post = Post::new(user_id, title, content);
+                posts.lock().unwrap().push(post.clone());
+                Ok(post)
+            });
+        let posts = store.clone();
+        mock_repo.expect_get_post().returning(move |id| {
+            posts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.id == id)
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        });
+        let posts = store;
+        mock_repo.expect_update_post().returning(move |post| {
+            let mut posts = posts.lock().unwrap();
+            let stored = posts.iter_mut().find(|p| p.id == post.id).unwrap();
+            *stored = post.clone();
+            Ok(post)
+        });
+
+        mock_repo
+    }
+
+    /// [`post_store_repo`] that also applies bulk publication updates
+    fn bulk_store_repo(store: Arc<std::sync::Mutex<Vec<Post>>>) -> MockPostRepo {
+        let mut mock_repo = post_store_repo(store.clone());
+        mock_repo.expect_bulk_update_publication().returning(
+            move |ids, published_at, scheduled_publish_at| {
+                let mut posts = store.lock().unwrap();
+                for post in posts.iter_mut().filter(|p| ids.contains(&p.id)) {
+                    post.published_at = published_at;
+                    post.scheduled_publish_at = scheduled_publish_at;
+                }
+                Ok(ids.len() as u64)
+            },
+        );
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_bulk_publish_reports_failures_per_post() {
+        let owner = Uuid::new_v4();
+        let mine = draft_post(owner);
+        let theirs = draft_post(Uuid::new_v4());
+        let missing = Uuid::new_v4();
+        let store = Arc::new(std::sync::Mutex::new(vec![mine.clone(), theirs.clone()]));
+        let service = PostService::new(
+            Arc::new(bulk_store_repo(store.clone())),
+            "http://localhost".to_string(),
+        );
+
+        let result = service
+            .bulk_publish(
+                vec![mine.id, theirs.id, missing, mine.id],
+                None,
+                owner,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.succeeded, vec![mine.id]);
+        let failed: Vec<Uuid> = result.failed.iter().map(|(id, _)| *id).collect();
+        assert_eq!(failed, vec![theirs.id, missing]);
+        let posts = store.lock().unwrap();
+        assert!(posts
+            .iter()
+            .find(|p| p.id == mine.id)
+            .unwrap()
+            .is_published());
+        assert!(!posts
+            .iter()
+            .find(|p| p.id == theirs.id)
+            .unwrap()
+            .is_published());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_publish_schedules_and_unpublish_clears() {
+        let owner = Uuid::new_v4();
+        let post = draft_post(owner);
+        let store = Arc::new(std::sync::Mutex::new(vec![post.clone()]));
+        let service = PostService::new(
+            Arc::new(bulk_store_repo(store.clone())),
+            "http://localhost".to_string(),
+        );
+        let perms = domain::DEFAULT_USER_PERMISSIONS;
+
+        let past = chrono::Utc::now() - chrono::Duration::hours(1);
+        let err = service
+            .bulk_publish(vec![post.id], Some(past), owner, perms)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+
+        let at = chrono::Utc::now() + chrono::Duration::days(1);
+        service
+            .bulk_publish(vec![post.id], Some(at), owner, perms)
+            .await
+            .unwrap();
+        let scheduled = store.lock().unwrap()[0].clone();
+        assert_eq!(scheduled.scheduled_publish_at, Some(at));
+        assert!(!scheduled.is_published());
+
+        let result = service
+            .bulk_unpublish(vec![post.id], owner, perms)
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded, vec![post.id]);
+        let unpublished = store.lock().unwrap()[0].clone();
+        assert!(unpublished.published_at.is_none());
+        assert!(unpublished.scheduled_publish_at.is_none());
+    }
+
+    /// [`post_store_repo`] that reports due schedules from `store`
+    fn schedule_store_repo(store: Arc<std::sync::Mutex<Vec<Post>>>) -> MockPostRepo {
+        let mut mock_repo = post_store_repo(store.clone());
+        mock_repo
+            .expect_list_due_scheduled_post_ids()
+            .returning(move |now| {
+                Ok(store
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|p| {
+                        !p.is_published() && p.scheduled_publish_at.is_some_and(|at| at <= now)
+                    })
+                    .map(|p| p.id)
+                    .collect())
+            });
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_schedule_rejects_past_time_and_published_posts() {
+        let owner = Uuid::new_v4();
+        let draft = draft_post(owner);
+        let mut live = draft_post(owner);
+        live.publish();
+        let store = Arc::new(std::sync::Mutex::new(vec![draft.clone(), live.clone()]));
+        let service = PostService::new(
+            Arc::new(schedule_store_repo(store.clone())),
+            "http://localhost".to_string(),
+        );
+        let perms = domain::DEFAULT_USER_PERMISSIONS;
+
+        let past = chrono::Utc::now() - chrono::Duration::minutes(1);
+        let err = service
+            .schedule(draft.id, past, owner, perms)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+
+        let future = chrono::Utc::now() + chrono::Duration::hours(1);
+        let err = service
+            .schedule(live.id, future, owner, perms)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+
+        let err = service
+            .schedule(draft.id, future, Uuid::new_v4(), perms)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            }
+        ));
+
+        let scheduled = service
+            .schedule(draft.id, future, owner, perms)
+            .await
+            .unwrap();
+        assert_eq!(scheduled.scheduled_publish_at, Some(future));
+        assert!(scheduled.is_scheduled());
+
+        let cancelled = service
+            .cancel_schedule(draft.id, owner, perms)
+            .await
+            .unwrap();
+        assert!(cancelled.scheduled_publish_at.is_none());
+        assert!(!cancelled.is_published());
+    }
+
+    fn scheduled_entry(title: &str, hours_ahead: i64) -> NewScheduledPost {
+        NewScheduledPost {
+            title: title.to_string(),
+            content: "Prepared content".to_string(),
+            tags: Vec::new(),
+            category: None,
+            scheduled_publish_at: chrono::Utc::now() + chrono::Duration::hours(hours_ahead),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schedule_batch_creates_all_posts_in_one_batch() {
+        use crate::test_support::MockTagRepo;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo.expect_batch_upsert_posts().times(1).returning(Ok);
+        mock_repo
+            .expect_replace_post_tags()
+            .times(1)
+            .returning(|_, tag_ids| {
+                assert_eq!(tag_ids.len(), 1);
+                Ok(())
+            });
+        let mut tag_repo = MockTagRepo::new();
+        tag_repo
+            .expect_upsert_tag()
+            .returning(|name, slug| Ok(domain::Tag::new(name.to_string(), slug.to_string())));
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_tag_repository(Arc::new(tag_repo));
+        let importer = Uuid::new_v4();
+
+        let mut tagged = scheduled_entry("Second", 48);
+        tagged.tags = vec!["rust".to_string()];
+        let first = scheduled_entry("First", 24);
+        let posts = service
+            .schedule_batch(
+                vec![first.clone(), tagged],
+                importer,
+                domain::ADMIN_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].title, "First");
+        assert_eq!(
+            posts[0].scheduled_publish_at,
+            Some(first.scheduled_publish_at)
+        );
+        assert!(posts
+            .iter()
+            .all(|p| p.user_id == importer && p.is_scheduled()));
+        assert!(posts.iter().all(|p| !p.is_published()));
+    }
+
+    #[tokio::test]
+    async fn test_schedule_batch_rejects_whole_batch_on_invalid_entry() {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo.expect_batch_upsert_posts().never();
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+        let perms = domain::ADMIN_PERMISSIONS;
+
+        let err = service
+            .schedule_batch(
+                vec![scheduled_entry("Fine", 1), scheduled_entry(" ", 1)],
+                Uuid::new_v4(),
+                perms,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.field(), Some("title"));
+        assert!(err.message().starts_with("Post 2: Title"));
+
+        let err = service
+            .schedule_batch(vec![scheduled_entry("Late", -1)], Uuid::new_v4(), perms)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(msg) if msg.contains("must be in the future")));
+
+        let err = service
+            .schedule_batch(
+                vec![scheduled_entry("Fine", 1)],
+                Uuid::new_v4(),
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_batch_failed_insert_leaves_nothing_behind() {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_batch_upsert_posts()
+            .times(1)
+            .returning(|_| Err(Error::Internal("Failed to upsert post".to_string())));
+        mock_repo.expect_replace_post_tags().never();
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let result = service
+            .schedule_batch(
+                vec![scheduled_entry("First", 1), scheduled_entry("Second", 2)],
+                Uuid::new_v4(),
+                domain::ADMIN_PERMISSIONS,
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Internal(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_advanced_requires_a_filter() {
+        let service = PostService::new(
+            Arc::new(MockPostRepo::new()),
+            "http://localhost".to_string(),
+        );
+
+        let err = service
+            .search_advanced(
+                PostSearchRequest::builder()
+                    .sort_by(domain::SortField::Views)
+                    .build(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+
+        let now = chrono::Utc::now();
+        let err = service
+            .search_advanced(
+                PostSearchRequest::builder()
+                    .published_after(now)
+                    .published_before(now)
+                    .build(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_search_advanced_passes_filters_and_caps_limit() {
+        let author = Uuid::new_v4();
+        let tag = Uuid::new_v4();
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_search_posts_advanced()
+            .withf(move |request| {
+                request.author_id == Some(author)
+                    && request.tag_ids == vec![tag]
+                    && request.title_contains.as_deref() == Some("rust")
+                    && request.limit == Some(MAX_SEARCH_LIMIT)
+                    && request.offset == Some(0)
+            })
+            .times(1)
+            .returning(move |_| {
+                Ok(SearchPostsResponse {
+                    posts: vec![Post::new(author, "Rust".to_string(), "one two".to_string())],
+                    total: 1,
+                    query: "rust".to_string(),
+                })
+            });
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let response = service
+            .search_advanced(
+                PostSearchRequest::builder()
+                    .title_contains("rust")
+                    .author_id(author)
+                    .tag_ids(vec![tag])
+                    .limit(1_000)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 1);
+        assert_eq!(response.posts[0].word_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_scheduled_fires_only_once_due() {
+        let owner = Uuid::new_v4();
+        let post = draft_post(owner);
+        let store = Arc::new(std::sync::Mutex::new(vec![post.clone()]));
+        let service = PostService::new(
+            Arc::new(schedule_store_repo(store.clone())),
+            "http://localhost".to_string(),
+        );
+
+        let at = chrono::Utc::now() + chrono::Duration::hours(1);
+        service
+            .schedule(post.id, at, owner, domain::DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap();
+
+        let early = service
+            .publish_due_scheduled(at - chrono::Duration::minutes(1))
+            .await
+            .unwrap();
+        assert_eq!(early, 0);
+        assert!(!store.lock().unwrap()[0].is_published());
+
+        let due = service.publish_due_scheduled(at).await.unwrap();
+        assert_eq!(due, 1);
+        let published = store.lock().unwrap()[0].clone();
+        assert!(published.is_published());
+        assert!(published.scheduled_publish_at.is_none());
+
+        let again = service
+            .publish_due_scheduled(at + chrono::Duration::minutes(1))
+            .await
+            .unwrap();
+        assert_eq!(again, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_generates_summary_from_first_paragraph() {
+        let service = service_with_post_store();
+        let author = Uuid::new_v4();
+
+        let post = service
+            .create(
+                author,
+                "Hello".to_string(),
+                "# Intro\n\nThe **first** paragraph\nspans two lines.\n\nSecond one.".to_string(),
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+
+        assert!(post.use_auto_summary);
+        assert_eq!(
+            post.summary.as_deref(),
+            Some("The first paragraph spans two lines.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manual_summary_survives_content_changes() {
+        let service = service_with_post_store();
+        let author = Uuid::new_v4();
+        let permissions = domain::DEFAULT_USER_PERMISSIONS;
+
+        let post = service
+            .create(
+                author,
+                "Hello".to_string(),
+                "Original text.".to_string(),
+                Some("Hand-written summary".to_string()),
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+        assert!(!post.use_auto_summary);
+        assert_eq!(post.summary.as_deref(), Some("Hand-written summary"));
+
+        let post = service
+            .update(
+                post.id,
+                None,
+                Some("Rewritten text.".to_string()),
+                None,
+                author,
+                permissions,
+            )
+            .await
+            .unwrap();
+        assert_eq!(post.summary.as_deref(), Some("Hand-written summary"));
+
+        // A blank summary goes back to the generated one
+        let post = service
+            .update(
+                post.id,
+                None,
+                None,
+                Some(String::new()),
+                author,
+                permissions,
+            )
+            .await
+            .unwrap();
+        assert!(post.use_auto_summary);
+        assert_eq!(post.summary.as_deref(), Some("Rewritten text."));
+    }
+
+    #[tokio::test]
+    async fn test_auto_summary_regenerates_on_content_change() {
+        let service = service_with_post_store();
+        let author = Uuid::new_v4();
+
+        let post = service
+            .create(
+                author,
+                "Hello".to_string(),
+                "Original text.".to_string(),
+                None,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+
+        let post = service
+            .update(
+                post.id,
+                None,
+                Some("Rewritten text.".to_string()),
+                None,
+                author,
+                domain::DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+        assert_eq!(post.summary.as_deref(), Some("Rewritten text."));
+        assert_eq!(service.get(post.id).await.unwrap().summary, post.summary);
+    }
+
+    const HELLO_WORLD_MD: &str = include_str!("../fixtures/import/hello-world.md");
+    const MISSING_TITLE_MD: &str = include_str!("../fixtures/import/missing-title.md");
+
+    #[tokio::test]
+    async fn test_import_creates_post_with_front_matter() {
+        use crate::test_support::{MockCategoryRepo, MockTagRepo};
+        use std::sync::Mutex;
+
+        let store: Arc<Mutex<Vec<Post>>> = Arc::default();
+        let mut post_repo = post_store_repo(store.clone());
+        let posts = store.clone();
+        post_repo
+            .expect_update_post_category()
+            .returning(move |post_id, category_id| {
+                let mut posts = posts.lock().unwrap();
+                posts
+                    .iter_mut()
+                    .find(|p| p.id == post_id)
+                    .unwrap()
+                    .category_id = category_id;
+                Ok(())
+            });
+        let post_tags: Arc<Mutex<Vec<Uuid>>> = Arc::default();
+        let added = post_tags.clone();
+        post_repo
+            .expect_add_tag_to_post()
+            .returning(move |_, tag_id| {
+                added.lock().unwrap().push(tag_id);
+                Ok(())
+            });
+
+        let existing = domain::Tag::new("Rust".to_string(), "rust".to_string());
+        let mut tag_repo = MockTagRepo::new();
+        let found = existing.clone();
+        tag_repo
+            .expect_upsert_tag()
+            .times(2)
+            .returning(move |name, slug| {
+                Ok(if slug == "rust" {
+                    found.clone()
+                } else {
+                    domain::Tag::new(name.to_string(), slug.to_string())
+                })
+            });
+
+        let mut category_repo = MockCategoryRepo::new();
+        category_repo
+            .expect_get_category_by_slug()
+            .returning(|_| Ok(None));
+        category_repo
+            .expect_create_category()
+            .times(1)
+            .returning(|name, slug, parent_id| {
+                Ok(domain::Category {
+                    id: Uuid::new_v4(),
+                    name,
+                    slug,
+                    parent_id,
+                    post_count: 0,
+                    created_at: chrono::Utc::now(),
+                })
+            });
+
+        let service = PostService::new(Arc::new(post_repo), "http://localhost".to_string())
+            .with_tag_repository(Arc::new(tag_repo))
+            .with_category_repository(Arc::new(category_repo));
+        let author = Uuid::new_v4();
+
+        let post = service
+            .import(author, HELLO_WORLD_MD, domain::DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(post.title, "Hello, World");
+        assert!(post.content.starts_with("# Hello\n\nThis post was written"));
+        assert_eq!(
+            post.summary.as_deref(),
+            Some("Saying hello from an imported post.")
+        );
+        assert!(!post.use_auto_summary);
+        assert!(post.category_id.is_some());
+        assert_eq!(
+            post.published_at.unwrap().to_rfc3339(),
+            "2024-03-01T09:30:00+00:00"
+        );
+        let post_tags = post_tags.lock().unwrap();
+        assert_eq!(post_tags.len(), 2);
+        assert_eq!(post_tags[0], existing.id);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_invalid_documents() {
+        let service = service_with_post_store();
+        let author = Uuid::new_v4();
+
+        let result = service
+            .import(author, MISSING_TITLE_MD, domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(result, Err(Error::Validation(msg)) if msg.contains("title")));
+
+        let result = service.import(author, HELLO_WORLD_MD, POST_CREATE).await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_renders_front_matter_per_post() {
+        let author = Uuid::new_v4();
+        let mut published = Post::new(author, "Same Title".to_string(), "First".to_string());
+        published.published_at = Some(chrono::Utc::now());
+        let draft = Post::new(author, "Same Title".to_string(), "Second".to_string());
+        let posts = vec![published.clone(), draft.clone()];
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_list_all_posts()
+            .returning(move |_| Ok(posts.clone()));
+        mock_repo.expect_get_post_tags().returning(|_| {
+            Ok(vec![domain::Tag::new(
+                "Rust".to_string(),
+                "rust".to_string(),
+            )])
+        });
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let denied = service
+            .export(author, domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(denied.is_err());
+
+        let files = service
+            .export(author, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].filename, "same-title.md");
+        assert_eq!(files[1].filename, format!("{}.md", draft.id));
+
+        let (front_matter, body) = FrontMatter::parse(&files[0].content).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Same Title"));
+        assert_eq!(front_matter.slug.as_deref(), Some("same-title"));
+        assert_eq!(front_matter.tags, vec!["rust"]);
+        assert_eq!(front_matter.published_at, published.published_at);
+        assert_eq!(front_matter.created_at, Some(published.created_at));
+        assert_eq!(body, "First");
+
+        let (front_matter, _) = FrontMatter::parse(&files[1].content).unwrap();
+        assert!(front_matter.published_at.is_none());
+    }
+
+    /// Post service whose feature images resolve through an in-memory file store
+    fn feature_image_service(
+        post: Post,
+        files: Arc<crate::test_support::MockFileRepo>,
+        mirror_external_images: bool,
+    ) -> (PostService, std::path::PathBuf) {
+        use crate::test_support::MockUserRepo;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        mock_repo.expect_update_post().returning(Ok);
+
+        let upload_dir = std::env::temp_dir().join(format!("peng-blog-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&upload_dir).unwrap();
+        let file_service = FileService::new(
+            files,
+            Arc::new(crate::PermissionService::new(Arc::new(
+                MockUserRepo::with_users(vec![]),
+            ))),
+            upload_dir.to_string_lossy().into_owned(),
+            "http://blog.test".to_string(),
+        );
+        let mut config = test_config();
+        config.storage.mirror_external_images = mirror_external_images;
+
+        let service = PostService::new(Arc::new(mock_repo), "http://blog.test".to_string())
+            .with_feature_images(file_service)
+            .with_config(shared_config(config))
+            .with_private_hosts_allowed();
+        (service, upload_dir)
     }
 
-    /// List published posts by tag
-    pub async fn list_by_tag(&self, tag_id: Uuid, limit: Option<u64>) -> Result<Vec<Post>> {
-        self.repo
-            .get_posts_by_tag(tag_id, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+    #[tokio::test]
+    async fn test_feature_image_from_uploaded_file() {
+        let owner = Uuid::new_v4();
+        let post = draft_post(owner);
+        let files = Arc::new(crate::test_support::MockFileRepo::default());
+        let image = domain::File::new(
+            owner,
+            "stored.png".to_string(),
+            "hero.png".to_string(),
+            "image/png".to_string(),
+            10,
+            "http://blog.test/api/files/stored.png".to_string(),
+        );
+        let document = domain::File::new(
+            owner,
+            "stored.pdf".to_string(),
+            "notes.pdf".to_string(),
+            "application/pdf".to_string(),
+            10,
+            "http://blog.test/api/files/stored.pdf".to_string(),
+        );
+        let (image_id, document_id) = (image.id, document.id);
+        files
+            .files
+            .write()
+            .await
+            .extend([(image.id, image), (document.id, document)]);
+        let (service, upload_dir) = feature_image_service(post.clone(), files, false);
+
+        let updated = service
+            .set_feature_image(
+                post.id,
+                FeatureImageSource::File(image_id),
+                owner,
+                POST_UPDATE,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            updated.feature_image_url.as_deref(),
+            Some("http://blog.test/api/files/stored.png")
+        );
+
+        for (source, expected) in [
+            (FeatureImageSource::File(document_id), "image file"),
+            (
+                FeatureImageSource::Url("http://example.com/a.png".to_string()),
+                "https",
+            ),
+            (
+                FeatureImageSource::Url("not a url".to_string()),
+                "valid URL",
+            ),
+        ] {
+            match service
+                .set_feature_image(post.id, source, owner, POST_UPDATE)
+                .await
+            {
+                Err(Error::Validation(msg)) => assert!(msg.contains(expected), "{}", msg),
+                other => panic!("Expected validation error, got {:?}", other),
+            }
+        }
+        assert!(matches!(
+            service
+                .set_feature_image(
+                    post.id,
+                    FeatureImageSource::File(Uuid::new_v4()),
+                    owner,
+                    POST_UPDATE
+                )
+                .await,
+            Err(Error::NotFound(_))
+        ));
+        assert!(matches!(
+            service
+                .set_feature_image(
+                    post.id,
+                    FeatureImageSource::File(image_id),
+                    Uuid::new_v4(),
+                    POST_UPDATE
+                )
+                .await,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+
+        let cleared = service
+            .clear_feature_image(post.id, owner, POST_UPDATE)
             .await
+            .unwrap();
+        assert!(cleared.feature_image_url.is_none());
+
+        let _ = std::fs::remove_dir_all(upload_dir);
     }
 
-    /// Search posts by query
-    pub async fn search(&self, request: SearchPostsRequest) -> Result<SearchPostsResponse> {
-        let query = request.query.trim();
-        if query.is_empty() {
-            return Err(Error::Validation(
-                "Search query cannot be empty".to_string(),
-            ));
+    #[tokio::test]
+    async fn test_external_feature_image_is_checked_and_mirrored() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::new_rgb8(4, 4)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let png = png.into_inner();
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/images/hero.png"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-type", "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/images/hero.png"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "image/png")
+                    .set_body_bytes(png.clone()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/page.html"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html; charset=utf-8"),
+            )
+            .mount(&server)
+            .await;
+        let image_url = format!("{}/images/hero.png", server.uri());
+        let owner = Uuid::new_v4();
+
+        // Without mirroring the checked URL is kept as is
+        let files = Arc::new(crate::test_support::MockFileRepo::default());
+        let (service, upload_dir) = feature_image_service(draft_post(owner), files.clone(), false);
+        assert_eq!(
+            service
+                .external_feature_image(&image_url, owner)
+                .await
+                .unwrap(),
+            image_url
+        );
+        for (url, expected) in [
+            (
+                format!("{}/page.html", server.uri()),
+                "not point to an image",
+            ),
+            (format!("{}/missing.png", server.uri()), "404"),
+        ] {
+            match service.external_feature_image(&url, owner).await {
+                Err(Error::Validation(msg)) => assert!(msg.contains(expected), "{}", msg),
+                other => panic!("Expected validation error, got {:?}", other),
+            }
         }
-        let limit = request.limit.unwrap_or(DEFAULT_LIST_LIMIT);
-        let offset = request.offset.unwrap_or(0);
-        self.repo.search_posts(query, limit, offset).await
+        assert!(files.files.read().await.is_empty());
+        let _ = std::fs::remove_dir_all(upload_dir);
+
+        // With mirroring the image is stored as an upload and served locally
+        let (service, upload_dir) = feature_image_service(draft_post(owner), files.clone(), true);
+        let mirrored = service
+            .external_feature_image(&image_url, owner)
+            .await
+            .unwrap();
+        assert!(mirrored.starts_with("http://blog.test/"));
+        assert_ne!(mirrored, image_url);
+
+        let stored = files.files.read().await;
+        let file = stored.values().next().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(file.url, mirrored);
+        assert_eq!(file.user_id, owner);
+        assert_eq!(file.original_filename, "hero.png");
+        assert_eq!(file.content_type, "image/png");
+        assert_eq!(std::fs::read(upload_dir.join(&file.filename)).unwrap(), png);
+
+        let _ = std::fs::remove_dir_all(upload_dir);
     }
-}
 
-// ============================================================================
-// Private Validation Helpers
-// ============================================================================
+    #[tokio::test]
+    async fn test_external_feature_image_rejects_private_hosts() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-impl PostService {
-    fn validate_title(&self, title: &str) -> Result<()> {
-        if title.trim().is_empty() {
-            return Err(Error::Validation("Title cannot be empty".to_string()));
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200).insert_header("content-type", "image/png"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let owner = Uuid::new_v4();
+        let files = Arc::new(crate::test_support::MockFileRepo::default());
+        let (mut service, upload_dir) = feature_image_service(draft_post(owner), files, true);
+        service.allow_private_hosts = false;
+
+        for url in [
+            format!("{}/hero.png", server.uri()),
+            format!("http://localhost:{}/hero.png", server.address().port()),
+            "http://169.254.169.254/latest/meta-data".to_string(),
+            "https://10.0.0.1/hero.png".to_string(),
+        ] {
+            assert!(
+                matches!(
+                    service.external_feature_image(&url, owner).await,
+                    Err(Error::Validation(_))
+                ),
+                "{} should be rejected",
+                url
+            );
         }
-        if title.len() > 200 {
-            return Err(Error::Validation(
-                "Title too long (max 200 characters)".to_string(),
-            ));
+        let _ = std::fs::remove_dir_all(upload_dir);
+    }
+
+    #[tokio::test]
+    async fn test_mirrored_feature_image_is_capped_at_upload_limit() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-type", "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "image/png")
+                    .set_body_bytes(vec![0u8; 2 * 1024 * 1024]),
+            )
+            .mount(&server)
+            .await;
+        let owner = Uuid::new_v4();
+        let files = Arc::new(crate::test_support::MockFileRepo::default());
+        let (service, upload_dir) = feature_image_service(draft_post(owner), files.clone(), true);
+        service
+            .config
+            .as_ref()
+            .unwrap()
+            .write()
+            .unwrap()
+            .storage
+            .max_file_size_mb = Some(1);
+
+        let url = format!("{}/huge.png", server.uri());
+        match service.external_feature_image(&url, owner).await {
+            Err(Error::Validation(msg)) => assert!(msg.contains("larger than"), "{}", msg),
+            other => panic!("Expected size limit error, got {:?}", other),
         }
-        Ok(())
+        assert!(files.files.read().await.is_empty());
+        let _ = std::fs::remove_dir_all(upload_dir);
     }
 
-    fn validate_content(&self, content: &str) -> Result<()> {
-        if content.trim().is_empty() {
-            return Err(Error::Validation("Content cannot be empty".to_string()));
+    const TOC_GUIDE_MD: &str = include_str!("../fixtures/toc/guide.md");
+
+    fn toc_entry(level: u8, text: &str, anchor: &str) -> TocEntry {
+        TocEntry {
+            level,
+            text: text.to_string(),
+            anchor: anchor.to_string(),
         }
-        Ok(())
     }
-}
 
-// ============================================================================
-// Tests
-// ============================================================================
+    #[test]
+    fn test_table_of_contents_from_markdown_fixture() {
+        assert_eq!(
+            table_of_contents(TOC_GUIDE_MD),
+            vec![
+                toc_entry(1, "Getting Started", "getting-started"),
+                toc_entry(2, "Install peng-blog", "install-peng-blog"),
+                toc_entry(3, "Configuration & Setup", "configuration-setup"),
+                toc_entry(4, "Environment Variables", "environment-variables"),
+                toc_entry(2, "Usage", "usage"),
+                toc_entry(3, "Configuration & Setup", "configuration-setup-2"),
+                toc_entry(2, "Setext Heading", "setext-heading"),
+            ]
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use async_trait::async_trait;
-    use domain::Tag;
-    use mockall::mock;
-    use std::sync::Arc;
+    #[test]
+    fn test_table_of_contents_deduplicates_anchors() {
+        let markdown = "# Intro\n\n# Intro\n\n# Intro-2\n\n# Intro\n\n# ???";
+        let anchors: Vec<String> = table_of_contents(markdown)
+            .into_iter()
+            .map(|entry| entry.anchor)
+            .collect();
+
+        assert_eq!(
+            anchors,
+            vec!["intro", "intro-2", "intro-2-2", "intro-3", "section"]
+        );
+    }
 
-    // Mock repository for testing
-    mock! {
-        PostRepo {}
+    #[test]
+    fn test_table_of_contents_is_capped() {
+        let markdown: String = (0..MAX_TOC_ENTRIES + 10)
+            .map(|i| format!("## Part {}\n\n", i))
+            .collect();
 
-        #[async_trait]
-        impl PostRepository for PostRepo {
-            async fn create_post(&self, user_id: Uuid, title: String, content: String) -> Result<Post>;
-            async fn get_post(&self, id: Uuid) -> Result<Post>;
-            async fn update_post(&self, post: Post) -> Result<Post>;
-            async fn list_published_posts(&self, limit: u64) -> Result<Vec<Post>>;
-            async fn delete_post(&self, id: Uuid) -> Result<()>;
-            async fn get_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>>;
-            async fn list_published_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>>;
-            async fn list_all_posts(&self, limit: u64) -> Result<Vec<Post>>;
-            async fn update_post_category(&self, post_id: Uuid, category_id: Option<Uuid>) -> Result<()>;
-            async fn get_posts_by_category(&self, category_id: Uuid, limit: u64) -> Result<Vec<Post>>;
-            async fn add_tag_to_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()>;
-            async fn remove_tag_from_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()>;
-            async fn get_post_tags(&self, post_id: Uuid) -> Result<Vec<Tag>>;
-            async fn get_posts_by_tag(&self, tag_id: Uuid, limit: u64) -> Result<Vec<Post>>;
-            async fn search_posts(&self, query: &str, limit: u64, offset: u64) -> Result<SearchPostsResponse>;
-        }
+        let toc = table_of_contents(&markdown);
+        assert_eq!(toc.len(), MAX_TOC_ENTRIES);
+        assert_eq!(toc[0].anchor, "part-0");
+    }
+
+    #[test]
+    fn test_external_links_finds_distinct_https_urls() {
+        let markdown = "See [the docs](https://docs.rs/axum) and https://example.com/a.\n\n\
+            Old [mirror](http://example.org) and <https://example.com/b>, \
+            again https://docs.rs/axum\n\n\
+            ```\ncurl https://example.com/in-code\n```\n";
+
+        assert_eq!(
+            external_links(markdown),
+            vec![
+                "https://docs.rs/axum",
+                "https://example.com/a",
+                "https://example.com/b",
+            ]
+        );
+
+        let many: String = (0..MAX_LINK_CHECKS + 5)
+            .map(|i| format!("https://example.com/{}\n\n", i))
+            .collect();
+        let links = external_links(&many);
+        assert_eq!(links.len(), MAX_LINK_CHECKS);
+        assert_eq!(links[0], "https://example.com/0");
     }
 
     #[tokio::test]
-    async fn test_create_post_validates_empty_title() {
-        let mock_repo = Arc::new(MockPostRepo::new());
-        let service = PostService::new(mock_repo, None, "http://localhost".to_string(), None);
+    async fn test_check_links_reports_status_codes() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-        let user_id = Uuid::new_v4();
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/ok"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/gone"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        // Nothing listens on port 1, so the request fails to connect
+        let unreachable = "http://127.0.0.1:1/".to_string();
 
-        let result = service
-            .create(user_id, "".to_string(), "content".to_string())
+        let results = check_links(
+            vec![
+                format!("{}/ok", server.uri()),
+                format!("{}/gone", server.uri()),
+                unreachable.clone(),
+            ],
+            true,
+        )
+        .await
+        .unwrap();
+
+        let statuses: Vec<u16> = results.iter().map(|r| r.status).collect();
+        assert_eq!(statuses, vec![200, 404, 0]);
+        assert_eq!(results[2].url, unreachable);
+    }
+
+    #[tokio::test]
+    async fn test_check_links_skips_private_addresses() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
             .await;
+        let urls = vec![
+            format!("{}/admin", server.uri()),
+            format!("http://localhost:{}/admin", server.address().port()),
+            "http://192.168.1.1/".to_string(),
+            "https://10.0.0.1/status".to_string(),
+            "http://169.254.169.254/latest/meta-data".to_string(),
+        ];
 
-        assert!(result.is_err());
-        match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("cannot be empty")),
-            _ => panic!("Expected validation error"),
-        }
+        let results = check_links(urls.clone(), false).await.unwrap();
+
+        let checked: Vec<(&str, u16)> =
+            results.iter().map(|r| (r.url.as_str(), r.status)).collect();
+        let expected: Vec<(&str, u16)> = urls.iter().map(|url| (url.as_str(), 0)).collect();
+        assert_eq!(checked, expected);
     }
 
     #[tokio::test]
-    async fn test_create_post_validates_title_too_long() {
-        let mock_repo = Arc::new(MockPostRepo::new());
-        let service = PostService::new(mock_repo, None, "http://localhost".to_string(), None);
+    async fn test_validate_links_requires_ownership() {
+        let owner = Uuid::new_v4();
+        let mut post = draft_post(owner);
+        post.content = "No links here".to_string();
+        let post_id = post.id;
 
-        let user_id = Uuid::new_v4();
-        let long_title = "a".repeat(201);
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
 
-        let result = service
-            .create(user_id, long_title, "content".to_string())
-            .await;
+        let err = service
+            .validate_links(post_id, Uuid::new_v4(), POST_UPDATE)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::PermissionDenied);
 
-        assert!(result.is_err());
-        match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("too long")),
-            _ => panic!("Expected validation error"),
+        let results = service
+            .validate_links(post_id, owner, POST_UPDATE)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_extract_toc_hides_drafts() {
+        let draft = Post::new(Uuid::new_v4(), "Draft".to_string(), "# Secret".to_string());
+        let mut published = draft.clone();
+        published.id = Uuid::new_v4();
+        published.publish();
+        let posts = [draft.clone(), published.clone()];
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo.expect_get_post().returning(move |id| {
+            posts
+                .iter()
+                .find(|p| p.id == id)
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        });
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let toc = service.extract_toc(published.id).await.unwrap();
+        assert_eq!(toc, vec![toc_entry(1, "Secret", "secret")]);
+
+        let err = service.extract_toc(draft.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CodedError {
+                code: ErrorCode::PostNotFound,
+                ..
+            }
+        ));
+    }
+
+    /// Service over published posts, oldest first, one day apart
+    fn adjacent_service(posts: Vec<Post>) -> PostService {
+        let mut mock_repo = MockPostRepo::new();
+        let by_id = posts.clone();
+        mock_repo.expect_get_post().returning(move |id| {
+            by_id
+                .iter()
+                .find(|p| p.id == id)
+                .cloned()
+                .ok_or_else(|| Error::NotFound("Post not found".to_string()))
+        });
+        let later = posts.clone();
+        mock_repo
+            .expect_get_next_published_post()
+            .returning(move |after, _| {
+                Ok(later
+                    .iter()
+                    .find(|p| p.published_at.is_some_and(|at| at > after))
+                    .cloned())
+            });
+        mock_repo
+            .expect_get_prev_published_post()
+            .returning(move |before, _| {
+                Ok(posts
+                    .iter()
+                    .rev()
+                    .find(|p| p.published_at.is_some_and(|at| at < before))
+                    .cloned())
+            });
+        PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+    }
+
+    fn published_series(count: i64) -> Vec<Post> {
+        let start = chrono::Utc::now() - chrono::Duration::days(count);
+        (0..count)
+            .map(|i| {
+                let mut post = Post::new(Uuid::new_v4(), format!("Part {}", i), String::new());
+                post.published_at = Some(start + chrono::Duration::days(i));
+                post
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_get_adjacent_at_series_ends() {
+        let posts = published_series(3);
+        let service = adjacent_service(posts.clone());
+
+        let first = service.get_adjacent(posts[0].id).await.unwrap();
+        assert_eq!(first.prev, None);
+        assert_eq!(first.next.map(|p| p.id), Some(posts[1].id));
+
+        let middle = service.get_adjacent(posts[1].id).await.unwrap();
+        assert_eq!(middle.prev.map(|p| p.id), Some(posts[0].id));
+        assert_eq!(middle.next.map(|p| p.id), Some(posts[2].id));
+
+        let last = service.get_adjacent(posts[2].id).await.unwrap();
+        assert_eq!(last.prev.map(|p| p.id), Some(posts[1].id));
+        assert_eq!(last.next, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_next_and_prev() {
+        let posts = published_series(2);
+        let service = adjacent_service(posts.clone());
+
+        assert_eq!(
+            service.get_next(posts[0].id).await.unwrap().map(|p| p.id),
+            Some(posts[1].id)
+        );
+        assert_eq!(service.get_prev(posts[0].id).await.unwrap(), None);
+        assert_eq!(service.get_next(posts[1].id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_adjacent_hides_drafts() {
+        let draft = Post::new(Uuid::new_v4(), "Draft".to_string(), String::new());
+        let service = adjacent_service(vec![draft.clone()]);
+
+        let err = service.get_adjacent(draft.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CodedError {
+                code: ErrorCode::PostNotFound,
+                ..
+            }
+        ));
+    }
+
+    const RENDER_EMBEDS_MD: &str = include_str!("../fixtures/render/embeds.md");
+
+    fn render_service(html_allow_list: HtmlAllowList) -> PostService {
+        let mut config = test_config();
+        config.site.allowed_html_tags = html_allow_list.tags;
+        config.site.allowed_html_attributes = html_allow_list.attributes;
+        PostService::new(
+            Arc::new(MockPostRepo::new()),
+            "http://localhost".to_string(),
+        )
+        .with_config(shared_config(config))
+    }
+
+    #[test]
+    fn test_render_content_keeps_allowed_html() {
+        let html = render_service(HtmlAllowList::default()).render_content(RENDER_EMBEDS_MD);
+
+        assert!(html.contains("<h1>Watch the talk</h1>"));
+        assert!(html.contains(
+            r#"<iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ" width="560" height="315" allowfullscreen="">"#
+        ));
+        assert!(html.contains("<div><span>Thanks for watching!</span></div>"));
+        assert!(html.contains(r#"href="https://example.com/slides""#));
+    }
+
+    #[test]
+    fn test_render_content_strips_scripts_and_event_handlers() {
+        let html = render_service(HtmlAllowList::default()).render_content(RENDER_EMBEDS_MD);
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("onload"));
+    }
+
+    #[test]
+    fn test_render_content_enforces_deny_list_over_config() {
+        let allow_list = HtmlAllowList {
+            tags: vec!["script".to_string(), "iframe".to_string()],
+            attributes: HashMap::from([(
+                "iframe".to_string(),
+                vec!["src".to_string(), "onload".to_string()],
+            )]),
+        };
+
+        let html = render_service(allow_list).render_content(RENDER_EMBEDS_MD);
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("onload"));
+        assert!(html.contains(r#"<iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ">"#));
+    }
+
+    fn og_service(post: Post, tags: Vec<domain::Tag>) -> PostService {
+        use crate::test_support::MockUserRepo;
+
+        let author = domain::User::new(
+            post.user_id,
+            "alice".to_string(),
+            "hash".to_string(),
+            domain::DEFAULT_USER_PERMISSIONS,
+        );
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_get_post()
+            .returning(move |_| Ok(post.clone()));
+        mock_repo
+            .expect_get_post_tags()
+            .returning(move |_| Ok(tags.clone()));
+        PostService::new(Arc::new(mock_repo), "https://blog.example.com".to_string())
+            .with_user_repository(Arc::new(MockUserRepo::with_users(vec![author])))
+    }
+
+    fn published(content: &str) -> Post {
+        let mut post = Post::new(Uuid::new_v4(), "Hello".to_string(), content.to_string());
+        post.publish();
+        post
+    }
+
+    #[tokio::test]
+    async fn test_og_data_extracts_markdown_image_and_tags() {
+        let post =
+            published("# Intro\n\nSome **bold** text\n\n![cover](/uploads/cover.png \"Cover\")");
+        let tags = vec![
+            domain::Tag::new("Rust".to_string(), "rust".to_string()),
+            domain::Tag::new("Web".to_string(), "web".to_string()),
+        ];
+        let service = og_service(post.clone(), tags);
+
+        let og = service.get_og_data(post.id).await.unwrap();
+
+        assert_eq!(og.title, "Hello");
+        assert_eq!(og.description, "Intro Some bold text");
+        assert_eq!(
+            og.image_url.as_deref(),
+            Some("https://blog.example.com/uploads/cover.png")
+        );
+        assert_eq!(og.url, format!("https://blog.example.com/post/{}", post.id));
+        assert_eq!(og.author, "alice");
+        assert_eq!(og.published_at, post.published_at);
+        assert_eq!(og.tags, ["Rust", "Web"]);
+    }
+
+    #[tokio::test]
+    async fn test_og_data_without_image() {
+        let post = published(&"word ".repeat(100));
+        let service = og_service(post.clone(), Vec::new());
+
+        let og = service.get_og_data(post.id).await.unwrap();
+
+        assert_eq!(og.image_url, None);
+        assert_eq!(
+            og.description.chars().count(),
+            domain::post::OG_DESCRIPTION_MAX_CHARS
+        );
+        assert!(og.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_og_data_prefers_feature_image_and_hides_drafts() {
+        let mut post = published("![inline](https://cdn.example.com/inline.png)");
+        post.feature_image_url = Some("https://cdn.example.com/feature.png".to_string());
+        let service = og_service(post.clone(), Vec::new());
+        let og = service.get_og_data(post.id).await.unwrap();
+        assert_eq!(
+            og.image_url.as_deref(),
+            Some("https://cdn.example.com/feature.png")
+        );
+
+        post.unpublish();
+        let service = og_service(post.clone(), Vec::new());
+        assert!(matches!(
+            service.get_og_data(post.id).await,
+            Err(Error::CodedError {
+                code: ErrorCode::PostNotFound,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_user_posts_with_stats_combines_views_and_comments() {
+        use crate::test_support::MockCommentRepo;
+
+        let author = Uuid::new_v4();
+        let unviewed = draft_post(author);
+        let popular = draft_post(author);
+        let unviewed_id = unviewed.id;
+        let popular_id = popular.id;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_list_posts_with_view_counts()
+            .withf(move |user_id, limit| *user_id == author && *limit == DEFAULT_LIST_LIMIT)
+            .returning(move |_, _| Ok(vec![(unviewed.clone(), 0), (popular.clone(), 7)]));
+
+        let comment_repo = Arc::new(MockCommentRepo::new());
+        for content in ["first", "second"] {
+            comment_repo
+                .create_comment(domain::Comment::from_user(
+                    popular_id,
+                    Uuid::new_v4(),
+                    content.to_string(),
+                ))
+                .await
+                .unwrap();
         }
+
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string())
+            .with_comment_repository(comment_repo);
+
+        let posts = service
+            .list_user_posts_with_stats(author, author, 0, None)
+            .await
+            .unwrap();
+        let counts: Vec<_> = posts
+            .iter()
+            .map(|p| (p.post.id, p.views, p.comment_count))
+            .collect();
+        assert_eq!(counts, vec![(unviewed_id, 0, 0), (popular_id, 7, 2)]);
+
+        let err = service
+            .list_user_posts_with_stats(author, Uuid::new_v4(), 0, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            }
+        ));
     }
 
     #[tokio::test]
-    async fn test_create_post_validates_long_content() {
-        let mock_repo = Arc::new(MockPostRepo::new());
-        let service = PostService::new(mock_repo, None, "http://localhost".to_string(), None);
+    async fn test_word_count_stats_requires_user_manage() {
+        let longest = Uuid::new_v4();
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_aggregate_word_counts()
+            .with(mockall::predicate::eq(true))
+            .times(1)
+            .returning(move |_| {
+                Ok(WordCountStats {
+                    total_words: 1_200,
+                    average_words_per_post: 400.0,
+                    longest_post_id: Some(longest),
+                    shortest_post_id: Some(Uuid::new_v4()),
+                })
+            });
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
 
-        let _long_content = "a".repeat(10001);
-        let user_id = Uuid::new_v4();
+        let err = service
+            .get_word_count_stats(true, domain::DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            }
+        ));
 
-        let result = service
-            .create(user_id, "title".to_string(), "".to_string())
-            .await;
+        let stats = service
+            .get_word_count_stats(true, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert_eq!(stats.total_words, 1_200);
+        assert_eq!(stats.longest_post_id, Some(longest));
+    }
 
-        assert!(result.is_err());
-        match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("cannot be empty")),
-            _ => panic!("Expected validation error"),
+    #[test]
+    fn test_month_bounds_roll_over_into_next_year() {
+        let (start, end) = month_bounds(2024, 12).unwrap();
+        assert_eq!(start.to_rfc3339(), "2024-12-01T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+
+        let (start, end) = month_bounds(2025, 1).unwrap();
+        assert_eq!(start.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2025-02-01T00:00:00+00:00");
+
+        let (_, end) = month_bounds(2024, 2).unwrap();
+        assert_eq!(end.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_month_bounds_reject_invalid_month() {
+        for month in [0, 13] {
+            assert!(matches!(
+                month_bounds(2024, month),
+                Err(Error::Validation(_))
+            ));
         }
     }
 
     #[tokio::test]
-    async fn test_update_requires_permission() {
-        let mock_repo = Arc::new(MockPostRepo::new());
-        let service = PostService::new(mock_repo, None, "http://localhost".to_string(), None);
+    async fn test_list_for_month_queries_month_range() {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_list_published_between()
+            .withf(|start, end, limit| {
+                start.to_rfc3339() == "2024-12-01T00:00:00+00:00"
+                    && end.to_rfc3339() == "2025-01-01T00:00:00+00:00"
+                    && *limit == DEFAULT_LIST_LIMIT
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
 
-        let post_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-        let no_permissions = 0;
+        assert!(service
+            .list_for_month(2024, 12, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 
-        let result = service
-            .update(
-                post_id,
-                None,
-                Some("new content".to_string()),
-                user_id,
-                no_permissions,
-            )
-            .await;
+    #[tokio::test]
+    async fn test_list_published_pages_with_offset() {
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_list_published_posts_with_authors()
+            .withf(|limit, offset| *limit == 10 && *offset == 20)
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+        mock_repo
+            .expect_list_published_posts()
+            .withf(|limit, offset| *limit == DEFAULT_LIST_LIMIT && *offset == 5)
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
 
-        assert!(result.is_err());
-        match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("Permission denied")),
-            _ => panic!("Expected permission error"),
+        service
+            .list_published_with_authors(Some(10), 20)
+            .await
+            .unwrap();
+        service.list_published(None, 5).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_published_with_tags_uses_one_repository_call() {
+        let posts: Vec<PostWithTags> = (0..3)
+            .map(|i| {
+                let mut post = Post::new(
+                    Uuid::new_v4(),
+                    format!("Post {}", i),
+                    "one two three".to_string(),
+                );
+                post.publish();
+                PostWithTags {
+                    post: PostWithAuthor {
+                        post,
+                        author_username: "alice".to_string(),
+                        author_avatar_url: None,
+                    },
+                    tags: vec![domain::Tag::new(format!("tag-{}", i), format!("tag-{}", i))],
+                }
+            })
+            .collect();
+
+        // No get_post_tags expectation: a per-post tags query would panic
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_list_posts_with_tags()
+            .withf(|limit, offset| *limit == DEFAULT_LIST_LIMIT && *offset == 0)
+            .times(1)
+            .returning(move |_, _| Ok(posts.clone()));
+        let service = PostService::new(Arc::new(mock_repo), "http://localhost".to_string());
+
+        let listed = service.list_published_with_tags(None, 0).await.unwrap();
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[2].tags[0].name, "tag-2");
+        assert_eq!(listed[0].post.post.word_count, 3);
+    }
+
+    /// Category tree root > child > grandchild with one published post on each level
+    fn category_tree_posts() -> (Vec<Uuid>, Vec<Post>) {
+        let levels: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let posts = levels
+            .iter()
+            .enumerate()
+            .map(|(depth, category_id)| {
+                let mut post = Post::new(
+                    Uuid::new_v4(),
+                    format!("Level {}", depth),
+                    "Content".to_string(),
+                );
+                post.category_id = Some(*category_id);
+                post.publish();
+                post
+            })
+            .collect();
+        (levels, posts)
+    }
+
+    /// Repository serving the category tree, with posts of the level and below
+    /// for recursive queries
+    fn category_tree_repo(levels: &[Uuid], posts: &[Post]) -> MockPostRepo {
+        let mut mock_repo = MockPostRepo::new();
+        let direct = posts.to_vec();
+        mock_repo
+            .expect_get_posts_by_category()
+            .returning(move |category_id, _| {
+                Ok(direct
+                    .iter()
+                    .filter(|p| p.category_id == Some(category_id))
+                    .cloned()
+                    .collect())
+            });
+        let (levels, tree) = (levels.to_vec(), posts.to_vec());
+        mock_repo
+            .expect_get_posts_by_category_recursive()
+            .returning(move |category_id, _| {
+                let depth = levels.iter().position(|id| *id == category_id).unwrap();
+                Ok(tree
+                    .iter()
+                    .filter(|p| levels[depth..].contains(&p.category_id.unwrap()))
+                    .cloned()
+                    .collect())
+            });
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_list_by_category_recursive_includes_all_levels() {
+        let (levels, posts) = category_tree_posts();
+        let service = PostService::new(
+            Arc::new(category_tree_repo(&levels, &posts)),
+            "http://localhost".to_string(),
+        );
+
+        let listed = service
+            .list_by_category(levels[0], None, true)
+            .await
+            .unwrap();
+        let titles: Vec<&str> = listed.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["Level 0", "Level 1", "Level 2"]);
+
+        let listed = service
+            .list_by_category(levels[1], None, true)
+            .await
+            .unwrap();
+        let titles: Vec<&str> = listed.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["Level 1", "Level 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_by_category_without_recursive_only_lists_that_category() {
+        let (levels, posts) = category_tree_posts();
+        let service = PostService::new(
+            Arc::new(category_tree_repo(&levels, &posts)),
+            "http://localhost".to_string(),
+        );
+
+        let listed = service
+            .list_by_category(levels[0], None, false)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].title, "Level 0");
+    }
+
+    /// Records the name and fields of every span opened under it
+    #[derive(Clone, Default)]
+    struct SpanRecorder(Arc<std::sync::Mutex<Vec<(String, String)>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanRecorder {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            struct Fields(String);
+            impl tracing::field::Visit for Fields {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    self.0.push_str(&format!("{}={:?} ", field.name(), value));
+                }
+            }
+
+            let mut fields = Fields(String::new());
+            attrs.record(&mut fields);
+            self.0
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name().to_string(), fields.0));
         }
     }
 
     #[tokio::test]
-    async fn test_delete_post_requires_permission() {
-        let mock_repo = Arc::new(MockPostRepo::new());
-        let service = PostService::new(mock_repo, None, "http://localhost".to_string(), None);
+    async fn test_service_call_emits_span_with_post_id() {
+        use tracing::instrument::WithSubscriber;
+        use tracing_subscriber::layer::SubscriberExt;
 
+        let recorder = SpanRecorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        let service = PostService::new(
+            Arc::new(MockPostRepo::new()),
+            "http://localhost".to_string(),
+        );
         let post_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
-        let no_permissions = 0;
 
-        let result = service.delete(post_id, user_id, no_permissions).await;
+        // Denied before the repository is touched
+        let result = service
+            .publish(post_id, user_id, 0)
+            .with_subscriber(subscriber)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
 
-        assert!(result.is_err());
-        match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("Permission denied")),
-            _ => panic!("Expected permission error"),
-        }
+        let spans = recorder.0.lock().unwrap();
+        let (name, fields) = &spans[0];
+        assert_eq!(name, "publish");
+        assert!(fields.contains(&format!("post_id={}", post_id)));
+        assert!(fields.contains(&format!("user_id={}", user_id)));
     }
 }
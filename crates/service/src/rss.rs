@@ -4,7 +4,7 @@
 //! It provides an interface for generating RSS feeds from published posts.
 
 use async_trait::async_trait;
-use domain::{Post, PostRepository, Result};
+use domain::{Category, Post, PostRepository, Result};
 use rss::{ChannelBuilder, Guid, ItemBuilder};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -30,6 +30,16 @@ pub trait RssService: Send + Sync {
     /// Returns the RSS feed as an XML string
     async fn generate_rss_with_limit(&self, limit: usize) -> Result<String>;
 
+    /// Generate RSS feed of the published posts in a category
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - Category whose posts are included
+    /// * `recursive` - Also include posts of its descendant categories
+    ///
+    /// Returns the RSS feed as an XML string
+    async fn generate_category_rss(&self, category: &Category, recursive: bool) -> Result<String>;
+
     /// Refresh the RSS feed cache
     ///
     /// This should be called whenever posts are created, updated, or deleted
@@ -74,11 +84,17 @@ impl RssServiceImpl {
     }
 
     /// Generate RSS channel from posts
-    fn build_channel(&self, posts: Vec<Post>) -> Result<String> {
+    fn build_channel(
+        &self,
+        title: &str,
+        link: &str,
+        description: &str,
+        posts: Vec<Post>,
+    ) -> Result<String> {
         let mut channel = ChannelBuilder::default()
-            .title(&self.feed_title)
-            .link(&self.base_url)
-            .description(&self.feed_description)
+            .title(title)
+            .link(link)
+            .description(description)
             .build();
 
         for post in posts {
@@ -115,10 +131,15 @@ impl RssService for RssServiceImpl {
 
         let posts = self
             .post_repo
-            .list_published_posts(DEFAULT_FEED_LIMIT as u64)
+            .list_published_posts(DEFAULT_FEED_LIMIT as u64, 0)
             .await?;
 
-        let feed = self.build_channel(posts)?;
+        let feed = self.build_channel(
+            &self.feed_title,
+            &self.base_url,
+            &self.feed_description,
+            posts,
+        )?;
 
         {
             let mut cache = self.cache.write().await;
@@ -130,9 +151,36 @@ impl RssService for RssServiceImpl {
 
     /// Generate RSS feed with a limit on the number of posts
     async fn generate_rss_with_limit(&self, limit: usize) -> Result<String> {
-        let posts = self.post_repo.list_published_posts(limit as u64).await?;
+        let posts = self.post_repo.list_published_posts(limit as u64, 0).await?;
 
-        self.build_channel(posts)
+        self.build_channel(
+            &self.feed_title,
+            &self.base_url,
+            &self.feed_description,
+            posts,
+        )
+    }
+
+    /// Generate RSS feed of the published posts in a category
+    ///
+    /// Category feeds are not cached here; clients cache them over HTTP.
+    async fn generate_category_rss(&self, category: &Category, recursive: bool) -> Result<String> {
+        let (posts, _) = self
+            .post_repo
+            .list_published_by_category_paginated(
+                category.id,
+                recursive,
+                DEFAULT_FEED_LIMIT as u64,
+                0,
+            )
+            .await?;
+
+        self.build_channel(
+            &format!("{} - {}", self.feed_title, category.name),
+            &format!("{}/category/{}", self.base_url, category.slug),
+            &format!("Latest posts in {}", category.name),
+            posts,
+        )
     }
 
     /// Refresh the RSS feed cache
@@ -147,3 +195,82 @@ impl RssService for RssServiceImpl {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockPostRepo;
+    use uuid::Uuid;
+
+    fn published_in(category: &Category, title: &str) -> Post {
+        let mut post = Post::new(Uuid::new_v4(), title.to_string(), "content".to_string());
+        post.category_id = Some(category.id);
+        post.publish();
+        post
+    }
+
+    /// Service over a parent category with one post and a child category
+    /// with one post
+    fn category_feed_service(parent: &Category, child: &Category) -> RssServiceImpl {
+        let parent_post = published_in(parent, "Parent post");
+        let child_post = published_in(child, "Child post");
+        let parent_id = parent.id;
+
+        let mut mock_repo = MockPostRepo::new();
+        mock_repo
+            .expect_list_published_by_category_paginated()
+            .returning(move |category_id, recursive, _, _| {
+                let posts: Vec<Post> = [&parent_post, &child_post]
+                    .into_iter()
+                    .filter(|post| {
+                        post.category_id == Some(category_id)
+                            || (recursive && category_id == parent_id)
+                    })
+                    .cloned()
+                    .collect();
+                let total = posts.len() as u64;
+                Ok((posts, total))
+            });
+
+        RssServiceImpl::new(
+            Arc::new(mock_repo),
+            "https://blog.example.com".to_string(),
+            Some("Peng Blog".to_string()),
+            None,
+        )
+    }
+
+    fn item_titles(channel: &rss::Channel) -> Vec<&str> {
+        channel
+            .items()
+            .iter()
+            .filter_map(|item| item.title())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_category_feed_has_category_title_link_and_posts() {
+        let parent = Category::new("Rust".to_string(), "rust".to_string(), None);
+        let child = Category::new("Async".to_string(), "async".to_string(), Some(parent.id));
+        let service = category_feed_service(&parent, &child);
+
+        let xml = service.generate_category_rss(&parent, false).await.unwrap();
+        let channel = rss::Channel::read_from(xml.as_bytes()).unwrap();
+
+        assert_eq!(channel.title(), "Peng Blog - Rust");
+        assert_eq!(channel.link(), "https://blog.example.com/category/rust");
+        assert_eq!(item_titles(&channel), vec!["Parent post"]);
+    }
+
+    #[tokio::test]
+    async fn test_recursive_category_feed_includes_child_posts() {
+        let parent = Category::new("Rust".to_string(), "rust".to_string(), None);
+        let child = Category::new("Async".to_string(), "async".to_string(), Some(parent.id));
+        let service = category_feed_service(&parent, &child);
+
+        let xml = service.generate_category_rss(&parent, true).await.unwrap();
+        let channel = rss::Channel::read_from(xml.as_bytes()).unwrap();
+
+        assert_eq!(item_titles(&channel), vec!["Parent post", "Child post"]);
+    }
+}
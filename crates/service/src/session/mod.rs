@@ -8,10 +8,14 @@
 //! - Automatic expiration handling
 //! - No special cases - all sessions follow the same rules
 
+use chrono::Utc;
 use domain::SessionRepository;
-use domain::{Result, Session};
+use domain::{Error, Result, Session, USER_MANAGE};
 use std::sync::Arc;
 
+/// Longest `User-Agent` stored with a session; longer values are truncated
+pub const MAX_USER_AGENT_LENGTH: usize = 512;
+
 /// Session service for managing user sessions
 ///
 /// This service handles all session-related business logic including:
@@ -34,24 +38,81 @@ impl SessionService {
 
     /// Create a new session for a user
     ///
+    /// Every session stores a freshly generated refresh token UUID that can
+    /// later be exchanged for a new access token via [`Self::refresh_session`].
+    ///
     /// # Arguments
     /// * `user_id` - The ID of the user
     /// * `remember_me` - If true, session expires in 30 days; otherwise 24 hours
+    /// * `user_agent` - The client's `User-Agent` header, shown in the session list
+    /// * `ip_address` - The client's IP address
     ///
     /// # Returns
     /// * `Ok(Session)` - The created session
     /// * `Err(Error)` - Database error
-    pub async fn create_session(&self, user_id: uuid::Uuid, remember_me: bool) -> Result<Session> {
-        let _session = if remember_me {
-            Session::with_remember(user_id)
-        } else {
-            Session::new(user_id)
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn create_session(
+        &self,
+        user_id: uuid::Uuid,
+        remember_me: bool,
+        user_agent: &str,
+        ip_address: &str,
+    ) -> Result<Session> {
+        let user_agent = match user_agent.char_indices().nth(MAX_USER_AGENT_LENGTH) {
+            Some((end, _)) => &user_agent[..end],
+            None => user_agent,
         };
-        self.session_repo.create_session(user_id, remember_me).await
+        let session = self
+            .session_repo
+            .create_session(user_id, remember_me, user_agent, ip_address)
+            .await?;
+        tracing::info!("Session created for user {}", user_id);
+        Ok(session)
+    }
+
+    /// List a user's active sessions, newest first
+    ///
+    /// Revoked and expired sessions are left out.
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Session>)` - The user's active sessions
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn list_user_sessions(&self, user_id: uuid::Uuid) -> Result<Vec<Session>> {
+        let mut sessions = self.session_repo.list_user_sessions(user_id).await?;
+        sessions.retain(Session::is_active);
+        Ok(sessions)
+    }
+
+    /// Revoke one of a user's sessions by ID
+    ///
+    /// # Arguments
+    /// * `user_id` - The ID of the user revoking the session
+    /// * `session_id` - The ID of the session to revoke
+    ///
+    /// # Returns
+    /// * `Ok(())` - Session revoked
+    /// * `Err(Error::NotFound)` - No session with this ID belongs to the user
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn revoke_user_session(&self, user_id: uuid::Uuid, session_id: &str) -> Result<()> {
+        match self.session_repo.get_session(session_id).await? {
+            Some(session) if session.user_id == user_id => {
+                self.session_repo.delete_session(session_id).await
+            }
+            _ => Err(Error::NotFound("Session not found".to_string())),
+        }
     }
 
     /// Validate a session token
     ///
+    /// A session used after its `refresh_at` is extended by its original
+    /// lifetime (24 hours, or 30 days with "remember me"), so active users
+    /// stay signed in.
+    ///
     /// # Arguments
     /// * `token` - The session token to validate
     ///
@@ -59,21 +120,65 @@ impl SessionService {
     /// * `Ok(Some(Session))` - Valid session
     /// * `Ok(None)` - Session not found or expired
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn validate_session(&self, token: &str) -> Result<Option<Session>> {
         let session = self.session_repo.get_session(token).await?;
 
-        if let Some(session) = session {
+        if let Some(mut session) = session {
+            if session.revoked {
+                return Ok(None);
+            }
             if session.is_expired() {
                 // Delete expired session on access
                 let _ = self.session_repo.delete_session(token).await;
                 return Ok(None);
             }
+
+            let now = Utc::now();
+            if session.needs_refresh(now) {
+                session.extend(now);
+                self.session_repo
+                    .extend_session(token, session.expires_at, session.refresh_at)
+                    .await?;
+                tracing::debug!("Session extended until {}", session.expires_at);
+            }
             return Ok(Some(session));
         }
 
         Ok(None)
     }
 
+    /// Look up the session behind a refresh token
+    ///
+    /// # Arguments
+    /// * `refresh_token` - The refresh token from the JWT `refresh_token` claim
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - The session is active and may issue a new access token
+    /// * `Err(Error::Unauthorized)` - Token unknown, revoked or expired
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn refresh_session(&self, refresh_token: &str) -> Result<Session> {
+        self.session_repo
+            .get_session_by_refresh_token(refresh_token)
+            .await?
+            .filter(Session::is_active)
+            .ok_or_else(|| Error::Unauthorized("Invalid or expired refresh token".to_string()))
+    }
+
+    /// Revoke a refresh token so it can no longer be used
+    ///
+    /// # Arguments
+    /// * `token_id` - The refresh token to revoke
+    ///
+    /// # Returns
+    /// * `Ok(())` - Token revoked (or did not exist)
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn revoke_refresh_token(&self, token_id: &str) -> Result<()> {
+        self.session_repo.revoke_refresh_token(token_id).await
+    }
+
     /// Destroy a session
     ///
     /// # Arguments
@@ -82,20 +187,55 @@ impl SessionService {
     /// # Returns
     /// * `Ok(())` - Session destroyed
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn destroy_session(&self, token: &str) -> Result<()> {
         self.session_repo.delete_session(token).await
     }
 
-    /// Destroy all sessions for a user
+    /// Destroy all sessions of a user, logging them out everywhere
+    ///
+    /// Users may revoke their own sessions; revoking anyone else's needs
+    /// `USER_MANAGE`. When users revoke their own sessions, the one they
+    /// are calling from is kept so they stay signed in.
     ///
     /// # Arguments
-    /// * `user_id` - The ID of the user
+    /// * `target_user_id` - The ID of the user whose sessions are destroyed
+    /// * `requester_id` - The ID of the user making the request
+    /// * `requester_permissions` - Permissions of the requester
+    /// * `current_session_id` - The session the request was made with, if any
     ///
     /// # Returns
-    /// * `Ok(())` - All sessions destroyed
+    /// * `Ok(count)` - Number of sessions destroyed
+    /// * `Err(Error::Validation)` - Requester is neither the user nor an admin
     /// * `Err(Error)` - Database error
-    pub async fn destroy_user_sessions(&self, user_id: uuid::Uuid) -> Result<()> {
-        self.session_repo.delete_user_sessions(user_id).await
+    #[tracing::instrument(level = "debug", skip_all, fields(%target_user_id, %requester_id))]
+    pub async fn destroy_all_for_user(
+        &self,
+        target_user_id: uuid::Uuid,
+        requester_id: uuid::Uuid,
+        requester_permissions: u64,
+        current_session_id: Option<&str>,
+    ) -> Result<u64> {
+        domain::check_ownership_or_admin(
+            target_user_id,
+            requester_id,
+            requester_permissions,
+            USER_MANAGE,
+        )?;
+
+        let keep = current_session_id.filter(|_| requester_id == target_user_id);
+        let revoked = self
+            .session_repo
+            .delete_user_sessions(target_user_id, keep)
+            .await?;
+        tracing::info!(
+            "Revoked {} sessions of user {} by user {}",
+            revoked,
+            target_user_id,
+            requester_id
+        );
+
+        Ok(revoked)
     }
 
     /// Clean up all expired sessions
@@ -106,64 +246,28 @@ impl SessionService {
     /// # Returns
     /// * `Ok(count)` - Number of sessions cleaned up
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn cleanup_expired(&self) -> Result<u64> {
         self.session_repo.cleanup_expired_sessions().await
     }
+
+    /// Count sessions that are neither revoked nor expired
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn count_active(&self) -> Result<u64> {
+        self.session_repo.count_active_sessions().await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_trait::async_trait;
-    use domain::SessionRepository;
-    use domain::{Result, Session};
+    use crate::test_support::MockSessionRepo;
+    use chrono::Duration;
+    use domain::{ErrorCode, Session};
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::RwLock;
 
-    // Mock repository for testing
-    struct MockSessionRepo {
-        sessions: Arc<RwLock<HashMap<String, Session>>>,
-    }
-
-    #[async_trait]
-    impl SessionRepository for MockSessionRepo {
-        async fn create_session(&self, user_id: uuid::Uuid, remember_me: bool) -> Result<Session> {
-            let session = if remember_me {
-                Session::with_remember(user_id)
-            } else {
-                Session::new(user_id)
-            };
-            let mut sessions = self.sessions.write().await;
-            sessions.insert(session.id.clone(), session.clone());
-            Ok(session)
-        }
-
-        async fn get_session(&self, token: &str) -> Result<Option<Session>> {
-            let sessions = self.sessions.read().await;
-            Ok(sessions.get(token).cloned())
-        }
-
-        async fn delete_session(&self, token: &str) -> Result<()> {
-            let mut sessions = self.sessions.write().await;
-            sessions.remove(token);
-            Ok(())
-        }
-
-        async fn delete_user_sessions(&self, user_id: uuid::Uuid) -> Result<()> {
-            let mut sessions = self.sessions.write().await;
-            sessions.retain(|_, session| session.user_id != user_id);
-            Ok(())
-        }
-
-        async fn cleanup_expired_sessions(&self) -> Result<u64> {
-            let mut sessions = self.sessions.write().await;
-            let before = sessions.len();
-            sessions.retain(|_, session| !session.is_expired());
-            Ok((before - sessions.len()) as u64)
-        }
-    }
-
     #[tokio::test]
     async fn test_create_session() {
         let repo = Arc::new(MockSessionRepo {
@@ -172,7 +276,10 @@ mod tests {
         let service = SessionService::new(repo);
 
         let user_id = uuid::Uuid::new_v4();
-        let session = service.create_session(user_id, false).await.unwrap();
+        let session = service
+            .create_session(user_id, false, "test-agent", "127.0.0.1")
+            .await
+            .unwrap();
 
         assert_eq!(session.user_id, user_id);
         assert!(!session.id.is_empty());
@@ -187,7 +294,10 @@ mod tests {
         let service = SessionService::new(repo);
 
         let user_id = uuid::Uuid::new_v4();
-        let session = service.create_session(user_id, false).await.unwrap();
+        let session = service
+            .create_session(user_id, false, "test-agent", "127.0.0.1")
+            .await
+            .unwrap();
 
         // Valid session
         let validated = service.validate_session(&session.id).await.unwrap();
@@ -199,6 +309,101 @@ mod tests {
         assert!(invalid.is_none());
     }
 
+    /// Store a session that was created `age` ago with the given lifetime
+    async fn insert_aged_session(
+        sessions: &RwLock<HashMap<String, Session>>,
+        lifetime: Duration,
+        age: Duration,
+    ) -> Session {
+        let mut session = Session::new(uuid::Uuid::new_v4());
+        session.created_at = Utc::now() - age;
+        session.expires_at = session.created_at + lifetime;
+        session.refresh_at = session.created_at + lifetime / 2;
+        sessions
+            .write()
+            .await
+            .insert(session.id.clone(), session.clone());
+        session
+    }
+
+    fn assert_expires_in(session: &Session, lifetime: Duration) {
+        let remaining = session.expires_at - Utc::now();
+        assert!(remaining > lifetime - Duration::minutes(1) && remaining <= lifetime);
+    }
+
+    #[tokio::test]
+    async fn test_validate_session_extends_past_refresh_at() {
+        let sessions = Arc::new(RwLock::new(HashMap::new()));
+        let service = SessionService::new(Arc::new(MockSessionRepo {
+            sessions: sessions.clone(),
+        }));
+        let session =
+            insert_aged_session(&sessions, Duration::hours(24), Duration::hours(13)).await;
+
+        let validated = service
+            .validate_session(&session.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_expires_in(&validated, Duration::hours(24));
+        let stored = sessions.read().await[&session.id].clone();
+        assert_eq!(stored.expires_at, validated.expires_at);
+        assert_eq!(stored.refresh_at, validated.refresh_at);
+        assert_eq!(stored.lifetime(), Duration::hours(24));
+    }
+
+    #[tokio::test]
+    async fn test_validate_session_before_refresh_at_keeps_expiry() {
+        let sessions = Arc::new(RwLock::new(HashMap::new()));
+        let service = SessionService::new(Arc::new(MockSessionRepo {
+            sessions: sessions.clone(),
+        }));
+        let session = insert_aged_session(&sessions, Duration::hours(24), Duration::hours(1)).await;
+
+        let validated = service
+            .validate_session(&session.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(validated.expires_at, session.expires_at);
+    }
+
+    #[tokio::test]
+    async fn test_validate_session_extends_remember_me_by_30_days() {
+        let sessions = Arc::new(RwLock::new(HashMap::new()));
+        let service = SessionService::new(Arc::new(MockSessionRepo {
+            sessions: sessions.clone(),
+        }));
+        let session = insert_aged_session(&sessions, Duration::days(30), Duration::days(20)).await;
+
+        let validated = service
+            .validate_session(&session.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_expires_in(&validated, Duration::days(30));
+    }
+
+    #[tokio::test]
+    async fn test_validate_session_past_expiry_is_invalid() {
+        let sessions = Arc::new(RwLock::new(HashMap::new()));
+        let service = SessionService::new(Arc::new(MockSessionRepo {
+            sessions: sessions.clone(),
+        }));
+        let session =
+            insert_aged_session(&sessions, Duration::hours(24), Duration::hours(25)).await;
+
+        assert!(service
+            .validate_session(&session.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(sessions.read().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_destroy_session() {
         let repo = Arc::new(MockSessionRepo {
@@ -207,11 +412,217 @@ mod tests {
         let service = SessionService::new(repo);
 
         let user_id = uuid::Uuid::new_v4();
-        let session = service.create_session(user_id, false).await.unwrap();
+        let session = service
+            .create_session(user_id, false, "test-agent", "127.0.0.1")
+            .await
+            .unwrap();
 
         service.destroy_session(&session.id).await.unwrap();
 
         let validated = service.validate_session(&session.id).await.unwrap();
         assert!(validated.is_none());
     }
+
+    #[tokio::test]
+    async fn test_refresh_session() {
+        let repo = Arc::new(MockSessionRepo {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = SessionService::new(repo);
+
+        let user_id = uuid::Uuid::new_v4();
+        let session = service
+            .create_session(user_id, true, "test-agent", "127.0.0.1")
+            .await
+            .unwrap();
+        assert_ne!(session.refresh_token, session.id);
+
+        let refreshed = service
+            .refresh_session(&session.refresh_token)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.user_id, user_id);
+
+        let unknown = service.refresh_session("unknown").await;
+        assert!(matches!(unknown, Err(Error::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_refresh_token_is_rejected() {
+        let repo = Arc::new(MockSessionRepo {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = SessionService::new(repo);
+
+        let session = service
+            .create_session(uuid::Uuid::new_v4(), true, "test-agent", "127.0.0.1")
+            .await
+            .unwrap();
+        service
+            .revoke_refresh_token(&session.refresh_token)
+            .await
+            .unwrap();
+
+        let result = service.refresh_session(&session.refresh_token).await;
+        assert!(matches!(result, Err(Error::Unauthorized(_))));
+        assert!(service
+            .validate_session(&session.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sessions_record_client_and_can_be_revoked() {
+        let repo = Arc::new(MockSessionRepo {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = SessionService::new(repo);
+
+        let user_id = uuid::Uuid::new_v4();
+        let laptop = service
+            .create_session(user_id, true, "Mozilla/5.0 (X11; Linux)", "203.0.113.7")
+            .await
+            .unwrap();
+        let long_agent = "a".repeat(MAX_USER_AGENT_LENGTH + 10);
+        service
+            .create_session(user_id, false, &long_agent, "198.51.100.2")
+            .await
+            .unwrap();
+        service
+            .create_session(uuid::Uuid::new_v4(), false, "curl/8.0", "192.0.2.1")
+            .await
+            .unwrap();
+
+        let sessions = service.list_user_sessions(user_id).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        let stored = sessions.iter().find(|s| s.id == laptop.id).unwrap();
+        assert_eq!(
+            stored.user_agent.as_deref(),
+            Some("Mozilla/5.0 (X11; Linux)")
+        );
+        assert_eq!(stored.ip_address.as_deref(), Some("203.0.113.7"));
+        let phone = sessions.iter().find(|s| s.id != laptop.id).unwrap();
+        assert_eq!(
+            phone.user_agent.as_ref().map(String::len),
+            Some(MAX_USER_AGENT_LENGTH)
+        );
+        assert_eq!(phone.ip_address.as_deref(), Some("198.51.100.2"));
+
+        let other_user = service.revoke_user_session(uuid::Uuid::new_v4(), &laptop.id);
+        assert!(matches!(other_user.await, Err(Error::NotFound(_))));
+
+        service
+            .revoke_user_session(user_id, &laptop.id)
+            .await
+            .unwrap();
+        let sessions = service.list_user_sessions(user_id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_ne!(sessions[0].id, laptop.id);
+    }
+
+    async fn service_with_sessions(
+        user_id: uuid::Uuid,
+        count: usize,
+    ) -> (SessionService, Vec<Session>) {
+        let repo = Arc::new(MockSessionRepo {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        });
+        let service = SessionService::new(repo);
+        let mut sessions = Vec::new();
+        for _ in 0..count {
+            sessions.push(
+                service
+                    .create_session(user_id, false, "test-agent", "127.0.0.1")
+                    .await
+                    .unwrap(),
+            );
+        }
+        (service, sessions)
+    }
+
+    #[tokio::test]
+    async fn test_admin_revokes_all_sessions_of_another_user() {
+        let user_id = uuid::Uuid::new_v4();
+        let (service, sessions) = service_with_sessions(user_id, 3).await;
+        let bystander = service
+            .create_session(uuid::Uuid::new_v4(), false, "test-agent", "127.0.0.1")
+            .await
+            .unwrap();
+
+        let revoked = service
+            .destroy_all_for_user(
+                user_id,
+                uuid::Uuid::new_v4(),
+                domain::ADMIN_PERMISSIONS,
+                Some(&sessions[0].id),
+            )
+            .await
+            .unwrap();
+
+        // The admin's own session belongs to someone else, so nothing is kept
+        assert_eq!(revoked, 3);
+        assert!(service
+            .list_user_sessions(user_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(service
+            .validate_session(&bystander.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_user_revokes_own_sessions_except_current() {
+        let user_id = uuid::Uuid::new_v4();
+        let (service, sessions) = service_with_sessions(user_id, 3).await;
+        let current = &sessions[1].id;
+
+        let revoked = service
+            .destroy_all_for_user(
+                user_id,
+                user_id,
+                domain::DEFAULT_USER_PERMISSIONS,
+                Some(current),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(revoked, 2);
+        let remaining = service.list_user_sessions(user_id).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(&remaining[0].id, current);
+
+        let without_current = service
+            .destroy_all_for_user(user_id, user_id, domain::DEFAULT_USER_PERMISSIONS, None)
+            .await
+            .unwrap();
+        assert_eq!(without_current, 1);
+    }
+
+    #[tokio::test]
+    async fn test_user_cannot_revoke_sessions_of_another_user() {
+        let user_id = uuid::Uuid::new_v4();
+        let (service, _) = service_with_sessions(user_id, 2).await;
+
+        let result = service
+            .destroy_all_for_user(
+                user_id,
+                uuid::Uuid::new_v4(),
+                domain::DEFAULT_USER_PERMISSIONS,
+                None,
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+        assert_eq!(service.list_user_sessions(user_id).await.unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,287 @@
+//! # Sitemap Service - XML Sitemap Generation
+//!
+//! This service builds sitemaps following the sitemaps.org protocol so that
+//! search engines can discover published posts, categories and tags.
+//!
+//! URLs are split across child sitemaps of at most [`SITEMAP_URL_LIMIT`]
+//! entries each, which are referenced from a single sitemap index.
+
+use chrono::{DateTime, Utc};
+use domain::{CategoryRepository, Error, PostRepository, Result, TagRepository};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Maximum number of URLs in a single child sitemap
+pub const SITEMAP_URL_LIMIT: usize = 1000;
+
+/// Upper bound on posts fetched when building sitemaps
+const MAX_SITEMAP_POSTS: u64 = 50_000;
+
+const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// A single `<url>` entry of a sitemap
+struct SitemapEntry {
+    loc: String,
+    lastmod: Option<DateTime<Utc>>,
+    changefreq: &'static str,
+}
+
+/// Service for sitemap business logic
+#[derive(Clone)]
+pub struct SitemapService {
+    post_repo: Arc<dyn PostRepository>,
+    category_repo: Arc<dyn CategoryRepository>,
+    tag_repo: Arc<dyn TagRepository>,
+    base_url: String,
+}
+
+impl SitemapService {
+    /// Create a new sitemap service
+    ///
+    /// # Arguments
+    ///
+    /// * `post_repo` - Post repository for fetching published posts
+    /// * `category_repo` - Category repository for category pages
+    /// * `tag_repo` - Tag repository for tag pages
+    /// * `base_url` - Base URL of the blog (e.g., "http://localhost:3000")
+    pub fn new(
+        post_repo: Arc<dyn PostRepository>,
+        category_repo: Arc<dyn CategoryRepository>,
+        tag_repo: Arc<dyn TagRepository>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            post_repo,
+            category_repo,
+            tag_repo,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Generate the sitemap index referencing every child sitemap
+    pub async fn generate_index(&self) -> Result<String> {
+        let pages = Self::page_count(self.entries().await?.len());
+
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = write!(xml, r#"<sitemapindex xmlns="{}">"#, SITEMAP_NAMESPACE);
+        for page in 0..pages {
+            let _ = write!(
+                xml,
+                "<sitemap><loc>{}</loc></sitemap>",
+                escape_xml(&self.page_url(page))
+            );
+        }
+        xml.push_str("</sitemapindex>");
+
+        Ok(xml)
+    }
+
+    /// Generate the child sitemap with the given zero-based index
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The sitemap XML
+    /// * `Err(Error::NotFound)` - No sitemap exists at this index
+    pub async fn generate_page(&self, page: usize) -> Result<String> {
+        let entries = self.entries().await?;
+        if page >= Self::page_count(entries.len()) {
+            return Err(Error::NotFound("Sitemap not found".to_string()));
+        }
+
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = write!(xml, r#"<urlset xmlns="{}">"#, SITEMAP_NAMESPACE);
+        for entry in entries
+            .iter()
+            .skip(page * SITEMAP_URL_LIMIT)
+            .take(SITEMAP_URL_LIMIT)
+        {
+            let _ = write!(xml, "<url><loc>{}</loc>", escape_xml(&entry.loc));
+            if let Some(lastmod) = entry.lastmod {
+                let _ = write!(xml, "<lastmod>{}</lastmod>", lastmod.format("%Y-%m-%d"));
+            }
+            let _ = write!(xml, "<changefreq>{}</changefreq></url>", entry.changefreq);
+        }
+        xml.push_str("</urlset>");
+
+        Ok(xml)
+    }
+
+    /// Public URL of a child sitemap
+    fn page_url(&self, page: usize) -> String {
+        format!("{}/api/sitemap-{}.xml", self.base_url, page)
+    }
+
+    /// Number of child sitemaps needed; there is always at least one
+    fn page_count(entries: usize) -> usize {
+        entries.div_ceil(SITEMAP_URL_LIMIT).max(1)
+    }
+
+    /// Collect every URL that belongs in the sitemap, posts first
+    async fn entries(&self) -> Result<Vec<SitemapEntry>> {
+        let posts = self
+            .post_repo
+            .list_published_posts(MAX_SITEMAP_POSTS, 0)
+            .await?;
+        let categories = self.category_repo.list_categories().await?;
+        let tags = self.tag_repo.list_tags().await?;
+
+        let mut entries = Vec::with_capacity(posts.len() + categories.len() + tags.len());
+        entries.extend(posts.into_iter().map(|post| SitemapEntry {
+            loc: format!("{}/post/{}", self.base_url, post.id),
+            lastmod: post.published_at,
+            changefreq: "weekly",
+        }));
+        entries.extend(categories.into_iter().map(|category| SitemapEntry {
+            loc: format!("{}/categories/{}", self.base_url, category.slug),
+            lastmod: None,
+            changefreq: "daily",
+        }));
+        entries.extend(tags.into_iter().map(|tag| SitemapEntry {
+            loc: format!("{}/tags/{}", self.base_url, tag.slug),
+            lastmod: None,
+            changefreq: "daily",
+        }));
+
+        Ok(entries)
+    }
+}
+
+/// Escape the five XML special characters
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockCategoryRepo, MockPostRepo, MockTagRepo};
+    use domain::{Category, Post, Tag};
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+    use uuid::Uuid;
+
+    fn published_posts(count: usize) -> Vec<Post> {
+        (0..count)
+            .map(|i| {
+                let mut post = Post::new(Uuid::new_v4(), format!("Post {}", i), String::new());
+                post.published_at = Some(Utc::now());
+                post
+            })
+            .collect()
+    }
+
+    fn service_with(posts: Vec<Post>, categories: Vec<Category>, tags: Vec<Tag>) -> SitemapService {
+        let mut post_repo = MockPostRepo::new();
+        post_repo
+            .expect_list_published_posts()
+            .returning(move |_, _| Ok(posts.clone()));
+        let mut category_repo = MockCategoryRepo::new();
+        category_repo
+            .expect_list_categories()
+            .returning(move || Ok(categories.clone()));
+        let mut tag_repo = MockTagRepo::new();
+        tag_repo
+            .expect_list_tags()
+            .returning(move || Ok(tags.clone()));
+
+        SitemapService::new(
+            Arc::new(post_repo),
+            Arc::new(category_repo),
+            Arc::new(tag_repo),
+            "http://example.com/".to_string(),
+        )
+    }
+
+    /// Text content of every `<loc>` element in the document
+    fn locations(xml: &str) -> Vec<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut locations = Vec::new();
+        let mut in_loc = false;
+
+        loop {
+            match reader.read_event().expect("sitemap should be valid XML") {
+                Event::Start(e) if e.name().as_ref() == b"loc" => in_loc = true,
+                Event::End(e) if e.name().as_ref() == b"loc" => in_loc = false,
+                Event::Text(text) if in_loc => {
+                    locations.push(text.unescape().unwrap().into_owned());
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        locations
+    }
+
+    #[tokio::test]
+    async fn test_sitemap_lists_posts_categories_and_tags() {
+        let posts = published_posts(3);
+        let category = Category {
+            id: Uuid::new_v4(),
+            name: "Rust".to_string(),
+            slug: "rust".to_string(),
+            parent_id: None,
+            post_count: 0,
+            created_at: Utc::now(),
+        };
+        let tag = Tag::new("Async & Await".to_string(), "async&await".to_string());
+        let service = service_with(posts.clone(), vec![category], vec![tag]);
+
+        let index = service.generate_index().await.unwrap();
+        assert_eq!(
+            locations(&index),
+            vec!["http://example.com/api/sitemap-0.xml".to_string()]
+        );
+
+        let sitemap = service.generate_page(0).await.unwrap();
+        let urls = locations(&sitemap);
+        assert_eq!(urls.len(), 5);
+        for post in &posts {
+            assert!(urls.contains(&format!("http://example.com/post/{}", post.id)));
+        }
+        assert!(urls.contains(&"http://example.com/categories/rust".to_string()));
+        assert!(urls.contains(&"http://example.com/tags/async&await".to_string()));
+        assert!(sitemap.contains("<lastmod>"));
+        assert!(sitemap.contains("<changefreq>weekly</changefreq>"));
+    }
+
+    #[tokio::test]
+    async fn test_sitemap_splits_at_url_limit() {
+        let posts = published_posts(SITEMAP_URL_LIMIT + 1);
+        let service = service_with(posts.clone(), vec![], vec![]);
+
+        let index = service.generate_index().await.unwrap();
+        assert_eq!(locations(&index).len(), 2);
+
+        let first = locations(&service.generate_page(0).await.unwrap());
+        let second = locations(&service.generate_page(1).await.unwrap());
+        assert_eq!(first.len(), SITEMAP_URL_LIMIT);
+        assert_eq!(second.len(), 1);
+
+        let all: Vec<String> = first.into_iter().chain(second).collect();
+        for post in &posts {
+            assert!(all.contains(&format!("http://example.com/post/{}", post.id)));
+        }
+
+        let missing = service.generate_page(2).await;
+        assert!(matches!(missing, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_empty_sitemap_is_still_served() {
+        let service = service_with(vec![], vec![], vec![]);
+
+        assert_eq!(locations(&service.generate_index().await.unwrap()).len(), 1);
+        assert!(locations(&service.generate_page(0).await.unwrap()).is_empty());
+    }
+}
@@ -8,12 +8,28 @@
 //! - No special cases - all stats follow the same rules
 //! - Periodic cleanup for daily resets
 
-use chrono::Utc;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use domain::stats::{RecordViewRequest, StatsResponse};
 use domain::StatsRepository;
-use domain::{Result, VisitStats};
+use domain::{DailyStats, Error, Result, VisitStats, USER_MANAGE};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
+/// Window in which repeated views of a post by one visitor count once
+const VIEW_DEDUP_WINDOW_HOURS: i64 = 24;
+
+/// Longest window accepted by [`StatsService::get_daily_stats`]
+pub const MAX_DAILY_STATS_DAYS: u32 = 365;
+
+/// Longest date range accepted by [`StatsService::export_stats_csv`], in days
+pub const MAX_STATS_EXPORT_DAYS: i64 = 365;
+
+/// Header row of the stats CSV export
+const STATS_CSV_HEADER: &str = "date,visits,top_post_id,top_post_title,top_post_views";
+
+/// Largest `limit` accepted by [`StatsService::get_top_posts`]
+pub const MAX_TOP_POSTS: u64 = 50;
+
 /// Stats service for tracking visitors and post views
 ///
 /// This service handles all statistics-related business logic including:
@@ -39,8 +55,10 @@ impl StatsService {
 
     /// Record a page view
     ///
-    /// This increments the global visit count and optionally increments
-    /// the view count for a specific post.
+    /// This increments the global visit count and today's row in the daily
+    /// series, and optionally increments the view count for a specific post.
+    /// When the request carries a visitor fingerprint, a post view is only
+    /// counted once per visitor every 24 hours.
     ///
     /// # Arguments
     /// * `request` - The view recording request
@@ -48,27 +66,62 @@ impl StatsService {
     /// # Returns
     /// * `Ok(())` - View recorded
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn record_view(&self, request: RecordViewRequest) -> Result<()> {
-        // Determine if it's today
-        let _now = Utc::now();
+        self.record_view_at(request, Utc::now()).await
+    }
+
+    async fn record_view_at(&self, request: RecordViewRequest, now: DateTime<Utc>) -> Result<()> {
         let is_today = true; // Simplification - always today
 
         // Increment global visit count
         self.stats_repo.increment_visit(is_today).await?;
+        self.stats_repo
+            .record_daily_visit(&now.format("%Y-%m-%d").to_string())
+            .await?;
 
         // If it's a post view, increment post view count
         if let Some(post_id) = request.post_id {
-            self.stats_repo.increment_post_view(post_id).await?;
+            let is_new_view = match request.visitor_fingerprint.as_deref() {
+                Some(visitor_id) => {
+                    let since = Utc::now() - Duration::hours(VIEW_DEDUP_WINDOW_HOURS);
+                    self.stats_repo
+                        .log_post_view(post_id, visitor_id, since)
+                        .await?
+                }
+                None => true,
+            };
+
+            if is_new_view {
+                self.stats_repo.increment_post_view(post_id).await?;
+            }
         }
 
         Ok(())
     }
 
+    /// Derive an anonymous visitor fingerprint from request metadata
+    ///
+    /// # Arguments
+    /// * `ip_address` - The client IP address
+    /// * `user_agent` - The client's `User-Agent` header, if any
+    ///
+    /// # Returns
+    /// A hex-encoded SHA-256 hash; the raw IP is never stored.
+    pub fn visitor_fingerprint(ip_address: &str, user_agent: Option<&str>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(ip_address.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(user_agent.unwrap_or_default().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
     /// Get global visitor statistics
     ///
     /// # Returns
     /// * `Ok(VisitStats)` - Global statistics
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_visit_stats(&self) -> Result<VisitStats> {
         self.stats_repo.get_visit_stats().await
     }
@@ -81,6 +134,7 @@ impl StatsService {
     /// # Returns
     /// * `Ok(PostStats)` - Post statistics
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
     pub async fn get_post_stats(&self, post_id: uuid::Uuid) -> Result<domain::PostStats> {
         self.stats_repo.get_or_create_post_stats(post_id).await
     }
@@ -93,10 +147,42 @@ impl StatsService {
     /// # Returns
     /// * `Ok(())` - Reset completed
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn reset_today_visits(&self) -> Result<()> {
         self.stats_repo.reset_today_visits().await
     }
 
+    /// Reset a post's view count to zero (admin only)
+    ///
+    /// Used to correct counts inflated by crawlers or testing. Other posts
+    /// keep their counts.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Views reset
+    /// * `Err(Error::CodedError)` - Requester lacks USER_MANAGE
+    #[tracing::instrument(level = "debug", skip_all, fields(%post_id))]
+    pub async fn reset_post_stats(&self, post_id: uuid::Uuid, permissions: u64) -> Result<()> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        self.stats_repo.reset_post_stats(post_id).await?;
+        tracing::info!("View count of post {} reset", post_id);
+        Ok(())
+    }
+
+    /// Reset the view counts of all posts to zero (admin only)
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Number of posts whose stats were reset
+    /// * `Err(Error::CodedError)` - Requester lacks USER_MANAGE
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn reset_all_post_stats(&self, permissions: u64) -> Result<u64> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        let reset = self.stats_repo.reset_all_post_stats().await?;
+        tracing::info!("View counts of {} posts reset", reset);
+        Ok(reset)
+    }
+
     /// Get total statistics (admin only)
     ///
     /// This provides aggregated statistics including total visits, today's visits,
@@ -105,10 +191,105 @@ impl StatsService {
     /// # Returns
     /// * `Ok(StatsResponse)` - Total statistics
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_total_stats(&self) -> Result<StatsResponse> {
         self.stats_repo.get_total_stats().await
     }
 
+    /// Get visit counts for each of the last `days` days, oldest first
+    ///
+    /// Days without visits are included with a count of zero so the series
+    /// can be charted directly.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<DailyStats>)` - One entry per day, ending today
+    /// * `Err(Error::Validation)` - `days` is zero or above the maximum
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_daily_stats(&self, days: u32) -> Result<Vec<DailyStats>> {
+        if days == 0 || days > MAX_DAILY_STATS_DAYS {
+            return Err(Error::Validation(format!(
+                "days must be between 1 and {}",
+                MAX_DAILY_STATS_DAYS
+            )));
+        }
+
+        let recorded = self.stats_repo.get_daily_stats(days).await?;
+        Ok(fill_daily_series(recorded, days, Utc::now().date_naive()))
+    }
+
+    /// Get the most viewed published posts, highest view count first
+    ///
+    /// # Returns
+    /// * `Ok(Vec<PostStats>)` - Stats of at most `limit` posts
+    /// * `Err(Error::Validation)` - `limit` is zero or above the maximum
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_top_posts(&self, limit: u64) -> Result<Vec<domain::PostStats>> {
+        if limit == 0 || limit > MAX_TOP_POSTS {
+            return Err(Error::Validation(format!(
+                "limit must be between 1 and {}",
+                MAX_TOP_POSTS
+            )));
+        }
+
+        let mut top_posts = Vec::new();
+        for (post_id, views) in self.stats_repo.get_top_posts(limit).await? {
+            let mut stats = self.stats_repo.get_or_create_post_stats(post_id).await?;
+            stats.views = views;
+            top_posts.push(stats);
+        }
+        Ok(top_posts)
+    }
+
+    /// Export daily traffic from `start` to `end` inclusive as CSV
+    ///
+    /// Each day gets one row with its visit count and the post most viewed
+    /// that day; days without a logged post view leave the post columns empty.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - CSV text with a header row, oldest day first
+    /// * `Err(Error::Validation)` - `start` is after `end`, or the range is
+    ///   longer than [`MAX_STATS_EXPORT_DAYS`]
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn export_stats_csv(&self, start: NaiveDate, end: NaiveDate) -> Result<String> {
+        if start > end {
+            return Err(Error::Validation("start must not be after end".to_string()));
+        }
+        if (end - start).num_days() + 1 > MAX_STATS_EXPORT_DAYS {
+            return Err(Error::Validation(format!(
+                "Export range cannot exceed {} days",
+                MAX_STATS_EXPORT_DAYS
+            )));
+        }
+
+        let daily = self.stats_repo.get_daily_stats_between(start, end).await?;
+        let top_posts = self.stats_repo.get_daily_top_posts(start, end).await?;
+
+        let mut csv = String::from(STATS_CSV_HEADER);
+        csv.push('\n');
+        for date in start.iter_days().take_while(|date| *date <= end) {
+            let visits = daily
+                .iter()
+                .find(|stats| stats.date == date)
+                .map_or(0, |stats| stats.visits);
+            let top = top_posts.iter().find(|top| top.date == date);
+
+            let row = [
+                date.format("%Y-%m-%d").to_string(),
+                visits.to_string(),
+                top.map(|top| top.post_id.to_string()).unwrap_or_default(),
+                top.map(|top| csv_field(&top.title)).unwrap_or_default(),
+                top.map(|top| top.views.to_string()).unwrap_or_default(),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+
+        Ok(csv)
+    }
+
     /// Check if daily reset is needed
     ///
     /// This helper checks if the last_updated date in visit_stats is different
@@ -118,6 +299,7 @@ impl StatsService {
     /// * `Ok(true)` - Reset needed (last updated was not today)
     /// * `Ok(false)` - Reset not needed
     /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn should_reset_today_visits(&self) -> Result<bool> {
         let stats = self.stats_repo.get_visit_stats().await?;
         let today = Utc::now().date_naive();
@@ -127,26 +309,62 @@ impl StatsService {
     }
 }
 
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One entry per day ending at `today`, with zero visits for missing days
+fn fill_daily_series(recorded: Vec<DailyStats>, days: u32, today: NaiveDate) -> Vec<DailyStats> {
+    let first_day = today - Duration::days(i64::from(days) - 1);
+    first_day
+        .iter_days()
+        .take(days as usize)
+        .map(|date| {
+            recorded
+                .iter()
+                .find(|stats| stats.date == date)
+                .cloned()
+                .unwrap_or_else(|| DailyStats::new(date))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use async_trait::async_trait;
     use domain::StatsRepository;
-    use domain::{PostStats, Result, StatsResponse, VisitStats};
+    use domain::{DailyTopPost, PostStats, Result, StatsResponse, VisitStats};
     use std::sync::Arc;
     use tokio::sync::RwLock;
 
     // Mock repository for testing
     struct MockStatsRepo {
         visit_stats: Arc<RwLock<VisitStats>>,
+        daily: Arc<RwLock<std::collections::BTreeMap<String, u64>>>,
         post_stats: Arc<RwLock<std::collections::HashMap<uuid::Uuid, PostStats>>>,
+        view_log: ViewLog,
+        published: Arc<RwLock<std::collections::HashSet<uuid::Uuid>>>,
+        daily_top: Arc<RwLock<Vec<DailyTopPost>>>,
     }
 
+    type ViewLog =
+        Arc<RwLock<std::collections::HashMap<(uuid::Uuid, String), chrono::DateTime<Utc>>>>;
+
     impl MockStatsRepo {
         fn new() -> Self {
             Self {
                 visit_stats: Arc::new(RwLock::new(VisitStats::new())),
+                daily: Arc::new(RwLock::new(std::collections::BTreeMap::new())),
                 post_stats: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                view_log: Arc::new(RwLock::new(std::collections::HashMap::new())),
+                published: Arc::new(RwLock::new(std::collections::HashSet::new())),
+                daily_top: Arc::new(RwLock::new(Vec::new())),
             }
         }
     }
@@ -190,6 +408,37 @@ mod tests {
             Ok(())
         }
 
+        async fn reset_post_stats(&self, post_id: uuid::Uuid) -> Result<()> {
+            if let Some(stat) = self.post_stats.write().await.get_mut(&post_id) {
+                stat.views = 0;
+                stat.last_viewed_at = Utc::now();
+            }
+            Ok(())
+        }
+
+        async fn reset_all_post_stats(&self) -> Result<u64> {
+            let mut stats = self.post_stats.write().await;
+            for stat in stats.values_mut() {
+                stat.views = 0;
+            }
+            Ok(stats.len() as u64)
+        }
+
+        async fn log_post_view(
+            &self,
+            post_id: uuid::Uuid,
+            visitor_hash: &str,
+            since: chrono::DateTime<Utc>,
+        ) -> Result<bool> {
+            let mut log = self.view_log.write().await;
+            let key = (post_id, visitor_hash.to_string());
+            if log.get(&key).is_some_and(|viewed_at| *viewed_at >= since) {
+                return Ok(false);
+            }
+            log.insert(key, Utc::now());
+            Ok(true)
+        }
+
         async fn get_total_stats(&self) -> Result<StatsResponse> {
             let visit_stats = self.visit_stats.read().await;
             Ok(StatsResponse {
@@ -199,6 +448,73 @@ mod tests {
                 total_comments: 0,
             })
         }
+
+        async fn get_daily_stats(&self, days: u32) -> Result<Vec<DailyStats>> {
+            let first_day = Utc::now().date_naive() - Duration::days(i64::from(days) - 1);
+            let daily = self.daily.read().await;
+            Ok(daily
+                .iter()
+                .map(|(date, visits)| DailyStats {
+                    date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+                    visits: *visits,
+                })
+                .filter(|stats| stats.date >= first_day)
+                .collect())
+        }
+
+        async fn get_daily_stats_between(
+            &self,
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<Vec<DailyStats>> {
+            let daily = self.daily.read().await;
+            Ok(daily
+                .iter()
+                .map(|(date, visits)| DailyStats {
+                    date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+                    visits: *visits,
+                })
+                .filter(|stats| stats.date >= start && stats.date <= end)
+                .collect())
+        }
+
+        async fn get_daily_top_posts(
+            &self,
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<Vec<DailyTopPost>> {
+            let daily_top = self.daily_top.read().await;
+            Ok(daily_top
+                .iter()
+                .filter(|top| top.date >= start && top.date <= end)
+                .cloned()
+                .collect())
+        }
+
+        async fn record_daily_visit(&self, date: &str) -> Result<()> {
+            *self
+                .daily
+                .write()
+                .await
+                .entry(date.to_string())
+                .or_default() += 1;
+            Ok(())
+        }
+
+        async fn get_top_posts(&self, limit: u64) -> Result<Vec<(uuid::Uuid, u64)>> {
+            let published = self.published.read().await;
+            let mut top: Vec<(uuid::Uuid, u64)> = self
+                .post_stats
+                .read()
+                .await
+                .values()
+                .filter(|stats| published.contains(&stats.post_id))
+                .map(|stats| (stats.post_id, stats.views))
+                .collect();
+            top.sort_by_key(|(_, views)| std::cmp::Reverse(*views));
+            top.truncate(limit as usize);
+            Ok(top)
+        }
     }
 
     #[tokio::test]
@@ -206,7 +522,10 @@ mod tests {
         let repo = Arc::new(MockStatsRepo::new());
         let service = StatsService::new(repo);
 
-        let request = RecordViewRequest { post_id: None };
+        let request = RecordViewRequest {
+            post_id: None,
+            visitor_fingerprint: None,
+        };
 
         service.record_view(request).await.unwrap();
 
@@ -223,6 +542,7 @@ mod tests {
         let post_id = uuid::Uuid::new_v4();
         let request = RecordViewRequest {
             post_id: Some(post_id),
+            visitor_fingerprint: None,
         };
 
         service.record_view(request).await.unwrap();
@@ -237,7 +557,10 @@ mod tests {
         let service = StatsService::new(repo);
 
         // Record some visits
-        let request = RecordViewRequest { post_id: None };
+        let request = RecordViewRequest {
+            post_id: None,
+            visitor_fingerprint: None,
+        };
         service.record_view(request.clone()).await.unwrap();
         service.record_view(request.clone()).await.unwrap();
 
@@ -254,11 +577,268 @@ mod tests {
         let repo = Arc::new(MockStatsRepo::new());
         let service = StatsService::new(repo);
 
-        let request = RecordViewRequest { post_id: None };
+        let request = RecordViewRequest {
+            post_id: None,
+            visitor_fingerprint: None,
+        };
         service.record_view(request).await.unwrap();
 
         let stats = service.get_total_stats().await.unwrap();
         assert_eq!(stats.total_visits, 1);
         assert_eq!(stats.today_visits, 1);
     }
+
+    #[tokio::test]
+    async fn test_duplicate_post_views_within_a_day_count_once() {
+        let repo = Arc::new(MockStatsRepo::new());
+        let service = StatsService::new(repo.clone());
+
+        let post_id = uuid::Uuid::new_v4();
+        let fingerprint = StatsService::visitor_fingerprint("203.0.113.7", Some("Firefox"));
+        let request = RecordViewRequest {
+            post_id: Some(post_id),
+            visitor_fingerprint: Some(fingerprint.clone()),
+        };
+
+        service.record_view(request.clone()).await.unwrap();
+        service.record_view(request.clone()).await.unwrap();
+        assert_eq!(service.get_post_stats(post_id).await.unwrap().views, 1);
+
+        // Another browser on the same IP is a different visitor
+        let other = RecordViewRequest {
+            post_id: Some(post_id),
+            visitor_fingerprint: Some(StatsService::visitor_fingerprint(
+                "203.0.113.7",
+                Some("Chrome"),
+            )),
+        };
+        service.record_view(other).await.unwrap();
+        assert_eq!(service.get_post_stats(post_id).await.unwrap().views, 2);
+
+        // Once the previous view is older than a day it counts again
+        repo.view_log
+            .write()
+            .await
+            .insert((post_id, fingerprint), Utc::now() - Duration::hours(25));
+        service.record_view(request).await.unwrap();
+        assert_eq!(service.get_post_stats(post_id).await.unwrap().views, 3);
+
+        // Global visits are still counted for every request
+        assert_eq!(service.get_visit_stats().await.unwrap().total_visits, 4);
+    }
+
+    #[tokio::test]
+    async fn test_daily_stats_are_kept_per_calendar_day() {
+        let repo = Arc::new(MockStatsRepo::new());
+        let service = StatsService::new(repo.clone());
+        let request = RecordViewRequest {
+            post_id: None,
+            visitor_fingerprint: None,
+        };
+
+        let now = Utc::now();
+        let yesterday = now - Duration::days(1);
+        service.record_view_at(request.clone(), now).await.unwrap();
+        service.record_view_at(request.clone(), now).await.unwrap();
+        service
+            .record_view_at(request.clone(), yesterday)
+            .await
+            .unwrap();
+        // Outside the 30-day window
+        service
+            .record_view_at(request, now - Duration::days(30))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.daily.read().await.len(), 3);
+
+        let series = service.get_daily_stats(30).await.unwrap();
+        assert_eq!(series.len(), 30);
+        assert_eq!(series[0].date, (now - Duration::days(29)).date_naive());
+        assert_eq!(series[29].date, now.date_naive());
+        assert_eq!(series[29].visits, 2);
+        assert_eq!(series[28].date, yesterday.date_naive());
+        assert_eq!(series[28].visits, 1);
+        assert_eq!(series.iter().map(|s| s.visits).sum::<u64>(), 3);
+
+        let longer = service.get_daily_stats(31).await.unwrap();
+        assert_eq!(longer[0].visits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_daily_stats_window_is_validated() {
+        let service = StatsService::new(Arc::new(MockStatsRepo::new()));
+
+        assert!(matches!(
+            service.get_daily_stats(0).await,
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            service.get_daily_stats(MAX_DAILY_STATS_DAYS + 1).await,
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_top_posts_sorted_by_views_without_drafts() {
+        let repo = Arc::new(MockStatsRepo::new());
+        let service = StatsService::new(repo.clone());
+
+        let popular = uuid::Uuid::new_v4();
+        let quiet = uuid::Uuid::new_v4();
+        let draft = uuid::Uuid::new_v4();
+        repo.published.write().await.extend([popular, quiet]);
+        for (post_id, views) in [(quiet, 1), (draft, 10), (popular, 3)] {
+            for _ in 0..views {
+                repo.increment_post_view(post_id).await.unwrap();
+            }
+        }
+
+        let top = service.get_top_posts(10).await.unwrap();
+        let ranked: Vec<_> = top.iter().map(|s| (s.post_id, s.views)).collect();
+        assert_eq!(ranked, vec![(popular, 3), (quiet, 1)]);
+
+        assert_eq!(service.get_top_posts(1).await.unwrap().len(), 1);
+        assert!(matches!(
+            service.get_top_posts(0).await,
+            Err(Error::Validation(_))
+        ));
+    }
+
+    /// Repository with two posts viewed three and five times
+    async fn viewed_posts_repo() -> (Arc<MockStatsRepo>, uuid::Uuid, uuid::Uuid) {
+        let repo = Arc::new(MockStatsRepo::new());
+        let (inflated, other) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        for (post_id, views) in [(inflated, 3), (other, 5)] {
+            for _ in 0..views {
+                repo.increment_post_view(post_id).await.unwrap();
+            }
+        }
+        (repo, inflated, other)
+    }
+
+    #[tokio::test]
+    async fn test_reset_post_stats_zeroes_only_that_post() {
+        let (repo, inflated, other) = viewed_posts_repo().await;
+        let service = StatsService::new(repo);
+
+        service
+            .reset_post_stats(inflated, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(service.get_post_stats(inflated).await.unwrap().views, 0);
+        assert_eq!(service.get_post_stats(other).await.unwrap().views, 5);
+    }
+
+    #[tokio::test]
+    async fn test_reset_all_post_stats() {
+        let (repo, inflated, other) = viewed_posts_repo().await;
+        let service = StatsService::new(repo);
+
+        let reset = service
+            .reset_all_post_stats(domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(reset, 2);
+        assert_eq!(service.get_post_stats(inflated).await.unwrap().views, 0);
+        assert_eq!(service.get_post_stats(other).await.unwrap().views, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reset_post_stats_requires_user_manage() {
+        let (repo, inflated, _) = viewed_posts_repo().await;
+        let service = StatsService::new(repo);
+
+        let err = service
+            .reset_post_stats(inflated, domain::DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), domain::ErrorCode::PermissionDenied);
+        let err = service
+            .reset_all_post_stats(domain::DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), domain::ErrorCode::PermissionDenied);
+
+        assert_eq!(service.get_post_stats(inflated).await.unwrap().views, 3);
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_stats_csv_fills_every_day_and_escapes_titles() {
+        let repo = Arc::new(MockStatsRepo::new());
+        let service = StatsService::new(repo.clone());
+        for day in ["2025-01-01", "2025-01-01", "2025-01-03", "2025-01-09"] {
+            repo.record_daily_visit(day).await.unwrap();
+        }
+        let quoted = uuid::Uuid::new_v4();
+        let plain = uuid::Uuid::new_v4();
+        repo.daily_top.write().await.extend([
+            DailyTopPost {
+                date: date("2025-01-01"),
+                post_id: quoted,
+                title: "Rust, \"fast\" and safe".to_string(),
+                views: 7,
+            },
+            DailyTopPost {
+                date: date("2025-01-03"),
+                post_id: plain,
+                title: "Hello".to_string(),
+                views: 2,
+            },
+        ]);
+
+        let csv = service
+            .export_stats_csv(date("2025-01-01"), date("2025-01-03"))
+            .await
+            .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "date,visits,top_post_id,top_post_title,top_post_views",
+                format!("2025-01-01,2,{},\"Rust, \"\"fast\"\" and safe\",7", quoted).as_str(),
+                "2025-01-02,0,,,",
+                format!("2025-01-03,1,{},Hello,2", plain).as_str(),
+            ]
+        );
+        assert!(csv.ends_with('\n'));
+
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[tokio::test]
+    async fn test_export_stats_csv_validates_range() {
+        let service = StatsService::new(Arc::new(MockStatsRepo::new()));
+
+        assert!(matches!(
+            service
+                .export_stats_csv(date("2025-02-01"), date("2025-01-31"))
+                .await,
+            Err(Error::Validation(msg)) if msg.contains("after")
+        ));
+        assert!(matches!(
+            service
+                .export_stats_csv(date("2025-01-01"), date("2026-01-01"))
+                .await,
+            Err(Error::Validation(msg)) if msg.contains("365")
+        ));
+
+        let full_year = service
+            .export_stats_csv(date("2025-01-01"), date("2025-12-31"))
+            .await
+            .unwrap();
+        assert_eq!(full_year.lines().count(), 366);
+        let single_day = service
+            .export_stats_csv(date("2025-01-01"), date("2025-01-01"))
+            .await
+            .unwrap();
+        assert_eq!(single_day.lines().count(), 2);
+    }
 }
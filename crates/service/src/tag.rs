@@ -1,8 +1,28 @@
 use crate::TagRepository;
-use domain::{CreateTag, Result, Tag};
+use domain::{CreateTag, Result, Tag, TagCloudEntry, USER_MANAGE};
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Number of most frequent content words matched against tags
+const SUGGESTION_TOP_WORDS: usize = 30;
+
+/// Maximum number of tag suggestions returned
+pub const MAX_TAG_SUGGESTIONS: u64 = 20;
+
+/// Common English words that never make useful tags
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "because",
+    "been", "before", "being", "but", "by", "can", "could", "did", "do", "does", "each", "for",
+    "from", "get", "had", "has", "have", "he", "her", "here", "his", "how", "i", "if", "in",
+    "into", "is", "it", "its", "just", "let", "like", "may", "me", "more", "most", "my", "new",
+    "no", "not", "now", "of", "on", "one", "only", "or", "other", "our", "out", "over", "she",
+    "so", "some", "such", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "those", "to", "too", "up", "us", "use", "used", "using", "very", "was", "we", "were",
+    "what", "when", "where", "which", "while", "who", "why", "will", "with", "would", "you",
+    "your",
+];
+
 /// Service for tag business logic
 #[derive(Clone)]
 pub struct TagService {
@@ -14,13 +34,17 @@ impl TagService {
         Self { repo }
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn create(&self, input: CreateTag) -> Result<Tag> {
         self.validate_slug(&input.slug)?;
         self.validate_name(&input.name)?;
 
-        self.repo.create_tag(input.name, input.slug).await
+        let tag = self.repo.create_tag(input.name, input.slug).await?;
+        tracing::info!("Tag {} created", tag.id);
+        Ok(tag)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(tag_id = %id))]
     pub async fn get(&self, id: Uuid) -> Result<Tag> {
         self.repo
             .get_tag(id)
@@ -28,6 +52,7 @@ impl TagService {
             .ok_or_else(|| domain::Error::NotFound("Tag not found".to_string()))
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_by_slug(&self, slug: &str) -> Result<Tag> {
         self.repo
             .get_tag_by_slug(slug)
@@ -35,13 +60,191 @@ impl TagService {
             .ok_or_else(|| domain::Error::NotFound("Tag not found".to_string()))
     }
 
+    /// Find the tag for a name, creating it when missing
+    ///
+    /// The slug is derived from the name. Lookup and creation happen in one
+    /// repository call, so concurrent calls for the same name never create
+    /// duplicates.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_or_create(&self, name: &str) -> Result<Tag> {
+        let name = name.trim();
+        self.validate_name(name)?;
+        let slug = slugify(name);
+        self.validate_slug(&slug)?;
+
+        self.repo.upsert_tag(name, &slug).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list(&self) -> Result<Vec<Tag>> {
         self.repo.list_tags().await
     }
 
+    /// Tags used by published posts, most used first, weighted for a tag cloud
+    ///
+    /// `normalized_weight` is `post_count / max_post_count`, so the most used
+    /// tag has weight 1.0.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_cloud(&self) -> Result<Vec<TagCloudEntry>> {
+        let mut entries = self.repo.get_tag_cloud().await?;
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.post_count));
+
+        let max_count = entries.first().map_or(0, |entry| entry.post_count);
+        for entry in &mut entries {
+            entry.normalized_weight = if max_count == 0 {
+                0.0
+            } else {
+                entry.post_count as f32 / max_count as f32
+            };
+        }
+
+        Ok(entries)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(tag_id = %id))]
     pub async fn delete(&self, id: Uuid) -> Result<()> {
-        self.repo.delete_tag(id).await
+        self.repo.delete_tag(id).await?;
+        tracing::info!("Tag {} deleted", id);
+        Ok(())
+    }
+
+    /// Rename a tag - admin only
+    ///
+    /// The slug is derived from the new name, so links using the old slug
+    /// stop resolving. Fails with `Error::Conflict` when another tag already
+    /// has that slug.
+    #[tracing::instrument(level = "debug", skip_all, fields(tag_id = %id))]
+    pub async fn rename(&self, id: Uuid, new_name: &str, permissions: u64) -> Result<Tag> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        let name = new_name.trim();
+        self.validate_name(name)?;
+        let slug = slugify(name);
+        self.validate_slug(&slug)?;
+
+        self.get(id).await?;
+        if let Some(existing) = self.repo.get_tag_by_slug(&slug).await? {
+            if existing.id != id {
+                return Err(domain::Error::Conflict(format!(
+                    "A tag with slug '{}' already exists",
+                    slug
+                )));
+            }
+        }
+
+        let tag = self.repo.update_tag(id, name.to_string(), slug).await?;
+        tracing::info!("Tag {} renamed to '{}'", id, tag.name);
+        Ok(tag)
+    }
+
+    /// Merge a duplicate tag into another - admin only
+    ///
+    /// Every post tagged with `source_id` is tagged with `target_id` instead,
+    /// and the source tag is deleted.
+    #[tracing::instrument(level = "debug", skip_all, fields(%source_id, %target_id))]
+    pub async fn merge(&self, source_id: Uuid, target_id: Uuid, permissions: u64) -> Result<()> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+
+        if source_id == target_id {
+            return Err(domain::Error::Validation(
+                "Cannot merge a tag into itself".to_string(),
+            ));
+        }
+        self.get(source_id).await?;
+        self.get(target_id).await?;
+
+        self.repo.merge_tags(source_id, target_id).await?;
+        tracing::info!("Merged tag {} into {}", source_id, target_id);
+
+        Ok(())
+    }
+
+    /// Suggest existing tags for post content
+    ///
+    /// The most frequent words of the content, ignoring Markdown syntax and
+    /// stop-words, are matched against tag names and slugs. Matching tags
+    /// are returned most frequent first.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn suggest_tags_for_content(&self, content: &str, limit: u64) -> Result<Vec<Tag>> {
+        let limit = limit.clamp(1, MAX_TAG_SUGGESTIONS) as usize;
+        let frequencies = top_words(content, SUGGESTION_TOP_WORDS);
+        if frequencies.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut scored: Vec<(u32, Tag)> = self
+            .repo
+            .list_tags()
+            .await?
+            .into_iter()
+            .filter_map(|tag| {
+                let name = frequencies.get(&tag.name.to_lowercase());
+                let slug = frequencies.get(&tag.slug.to_lowercase());
+                name.max(slug).map(|count| (*count, tag))
+            })
+            .collect();
+
+        scored.sort_by(|(a, a_tag), (b, b_tag)| b.cmp(a).then_with(|| a_tag.name.cmp(&b_tag.name)));
+        Ok(scored.into_iter().take(limit).map(|(_, tag)| tag).collect())
+    }
+}
+
+/// Lowercase a tag name, joining words with single hyphens
+fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Count of each of the `top` most frequent non-stop-words in Markdown content
+fn top_words(content: &str, top: usize) -> HashMap<String, u32> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for word in strip_markdown(content)
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|word| word.trim_matches('-').to_lowercase())
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+    {
+        *counts.entry(word).or_default() += 1;
     }
+
+    let mut words: Vec<(String, u32)> = counts.into_iter().collect();
+    words.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    words.into_iter().take(top).collect()
+}
+
+/// Drop Markdown link targets, HTML tags and code fence lines, keeping the text
+fn strip_markdown(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            continue;
+        }
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                ']' if chars.peek() == Some(&'(') => {
+                    chars.by_ref().find(|c| *c == ')');
+                    text.push(' ');
+                }
+                '<' => {
+                    chars.by_ref().find(|c| *c == '>');
+                    text.push(' ');
+                }
+                _ => text.push(c),
+            }
+        }
+        text.push('\n');
+    }
+    text
 }
 
 impl TagService {
@@ -84,21 +287,9 @@ impl TagService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use domain::Error;
-    use mockall::{mock, predicate::*};
-
-    mock! {
-        TagRepo {}
-
-        #[async_trait::async_trait]
-        impl TagRepository for TagRepo {
-            async fn create_tag(&self, name: String, slug: String) -> Result<Tag>;
-            async fn get_tag(&self, id: Uuid) -> Result<Option<Tag>>;
-            async fn get_tag_by_slug(&self, slug: &str) -> Result<Option<Tag>>;
-            async fn list_tags(&self) -> Result<Vec<Tag>>;
-            async fn delete_tag(&self, id: Uuid) -> Result<()>;
-        }
-    }
+    use crate::test_support::MockTagRepo;
+    use domain::{Error, ErrorCode};
+    use mockall::predicate::*;
 
     fn create_test_tag(id: Uuid, name: &str, slug: &str) -> Tag {
         Tag {
@@ -263,6 +454,63 @@ mod tests {
         assert_eq!(tag.name, "Rust");
     }
 
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Rust"), "rust");
+        assert_eq!(slugify("Web  Development!"), "web-development");
+        assert_eq!(slugify(" -- C++ -- "), "c");
+        assert_eq!(slugify("snake_case"), "snake_case");
+        assert_eq!(slugify("Données"), "données");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_get_or_create_stores_one_tag() {
+        use std::sync::Mutex;
+
+        let store: Arc<Mutex<Vec<Tag>>> = Arc::default();
+        let rows = store.clone();
+        let mut mock_repo = MockTagRepo::new();
+        mock_repo.expect_upsert_tag().returning(move |name, slug| {
+            let mut rows = rows.lock().unwrap();
+            if let Some(tag) = rows.iter().find(|tag| tag.slug == slug) {
+                return Ok(tag.clone());
+            }
+            let tag = Tag::new(name.to_string(), slug.to_string());
+            rows.push(tag.clone());
+            Ok(tag)
+        });
+        let service = TagService::new(Arc::new(mock_repo));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let service = service.clone();
+                tokio::spawn(async move { service.get_or_create("Async Rust").await })
+            })
+            .collect();
+        let mut ids = Vec::new();
+        for handle in handles {
+            ids.push(handle.await.unwrap().unwrap().id);
+        }
+
+        let rows = store.lock().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].slug, "async-rust");
+        assert!(ids.iter().all(|id| *id == rows[0].id));
+    }
+
+    #[tokio::test]
+    async fn test_get_or_create_rejects_name_without_slug() {
+        let service = TagService::new(Arc::new(MockTagRepo::new()));
+
+        let result = service.get_or_create("???").await;
+
+        match result {
+            Err(Error::Validation(msg)) => assert!(msg.contains("Slug cannot be empty")),
+            _ => panic!("Expected validation error for name without slug"),
+        }
+    }
+
     #[tokio::test]
     async fn test_get_tag_by_slug_not_found() {
         let mut mock_repo = MockTagRepo::new();
@@ -304,6 +552,43 @@ mod tests {
         assert_eq!(tag.slug, "rust");
     }
 
+    #[tokio::test]
+    async fn test_get_cloud_normalizes_weights() {
+        let mut mock_repo = MockTagRepo::new();
+        mock_repo.expect_get_tag_cloud().times(1).returning(|| {
+            Ok([("go", 1), ("rust", 4), ("python", 2)]
+                .into_iter()
+                .map(|(slug, count)| TagCloudEntry {
+                    tag: create_test_tag(Uuid::new_v4(), slug, slug),
+                    post_count: count,
+                    normalized_weight: 0.0,
+                })
+                .collect())
+        });
+
+        let service = TagService::new(Arc::new(mock_repo));
+        let cloud = service.get_cloud().await.unwrap();
+
+        let weights: Vec<(&str, u64, f32)> = cloud
+            .iter()
+            .map(|e| (e.tag.slug.as_str(), e.post_count, e.normalized_weight))
+            .collect();
+        assert_eq!(
+            weights,
+            vec![("rust", 4, 1.0), ("python", 2, 0.5), ("go", 1, 0.25)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_cloud_empty() {
+        let mut mock_repo = MockTagRepo::new();
+        mock_repo.expect_get_tag_cloud().returning(|| Ok(vec![]));
+
+        let service = TagService::new(Arc::new(mock_repo));
+
+        assert!(service.get_cloud().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_list_tags() {
         let mut mock_repo = MockTagRepo::new();
@@ -369,4 +654,232 @@ mod tests {
         let result = service.create(input).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_merge_leaves_single_reference_to_target() {
+        use std::sync::Mutex;
+
+        let source = create_test_tag(Uuid::new_v4(), "Rustlang", "rustlang");
+        let target = create_test_tag(Uuid::new_v4(), "Rust", "rust");
+        let both = Uuid::new_v4();
+        let source_only = Uuid::new_v4();
+        let links = Arc::new(Mutex::new(vec![
+            (both, source.id),
+            (both, target.id),
+            (source_only, source.id),
+        ]));
+
+        let mut mock_repo = MockTagRepo::new();
+        let tags = [source.clone(), target.clone()];
+        mock_repo
+            .expect_get_tag()
+            .returning(move |id| Ok(tags.iter().find(|t| t.id == id).cloned()));
+        let store = links.clone();
+        mock_repo
+            .expect_merge_tags()
+            .times(1)
+            .returning(move |source_id, target_id| {
+                let mut links = store.lock().unwrap();
+                let tagged: Vec<Uuid> = links
+                    .iter()
+                    .filter(|(_, tag)| *tag == target_id)
+                    .map(|(post, _)| *post)
+                    .collect();
+                links.retain(|(post, tag)| !(*tag == source_id && tagged.contains(post)));
+                for link in links.iter_mut().filter(|(_, tag)| *tag == source_id) {
+                    link.1 = target_id;
+                }
+                Ok(())
+            });
+
+        let service = TagService::new(Arc::new(mock_repo));
+        service
+            .merge(source.id, target.id, domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        let links = links.lock().unwrap();
+        for post in [both, source_only] {
+            let refs: Vec<_> = links.iter().filter(|(p, _)| *p == post).collect();
+            assert_eq!(refs, vec![&(post, target.id)]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_validates_input() {
+        let tag = create_test_tag(Uuid::new_v4(), "Rust", "rust");
+        let mut mock_repo = MockTagRepo::new();
+        let existing = tag.clone();
+        mock_repo
+            .expect_get_tag()
+            .returning(move |id| Ok(Some(existing.clone()).filter(|t| t.id == id)));
+        mock_repo.expect_merge_tags().never();
+        let service = TagService::new(Arc::new(mock_repo));
+
+        let result = service
+            .merge(tag.id, Uuid::new_v4(), domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+
+        let result = service
+            .merge(tag.id, tag.id, domain::ADMIN_PERMISSIONS)
+            .await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        let result = service
+            .merge(tag.id, Uuid::new_v4(), domain::ADMIN_PERMISSIONS)
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    fn rename_service(tags: Vec<Tag>) -> TagService {
+        let mut mock_repo = MockTagRepo::new();
+        let by_id = tags.clone();
+        mock_repo
+            .expect_get_tag()
+            .returning(move |id| Ok(by_id.iter().find(|t| t.id == id).cloned()));
+        mock_repo
+            .expect_get_tag_by_slug()
+            .returning(move |slug| Ok(tags.iter().find(|t| t.slug == slug).cloned()));
+        mock_repo
+            .expect_update_tag()
+            .returning(|id, name, slug| Ok(create_test_tag(id, &name, &slug)));
+        TagService::new(Arc::new(mock_repo))
+    }
+
+    #[tokio::test]
+    async fn test_rename_updates_name_and_slug() {
+        let tag = create_test_tag(Uuid::new_v4(), "Rustlang", "rustlang");
+        let service = rename_service(vec![tag.clone()]);
+
+        let renamed = service
+            .rename(tag.id, "  Rust Async  ", domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(renamed.id, tag.id);
+        assert_eq!(renamed.name, "Rust Async");
+        assert_eq!(renamed.slug, "rust-async");
+    }
+
+    #[tokio::test]
+    async fn test_rename_keeping_own_slug_is_allowed() {
+        let tag = create_test_tag(Uuid::new_v4(), "rust", "rust");
+        let service = rename_service(vec![tag.clone()]);
+
+        let renamed = service
+            .rename(tag.id, "Rust", domain::ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(renamed.name, "Rust");
+        assert_eq!(renamed.slug, "rust");
+    }
+
+    #[tokio::test]
+    async fn test_rename_rejects_slug_of_another_tag() {
+        let tag = create_test_tag(Uuid::new_v4(), "Rustlang", "rustlang");
+        let other = create_test_tag(Uuid::new_v4(), "Rust", "rust");
+        let service = rename_service(vec![tag.clone(), other]);
+
+        let result = service
+            .rename(tag.id, "Rust", domain::ADMIN_PERMISSIONS)
+            .await;
+
+        assert!(matches!(result, Err(Error::Conflict(msg)) if msg.contains("rust")));
+    }
+
+    #[tokio::test]
+    async fn test_rename_validates_input() {
+        let tag = create_test_tag(Uuid::new_v4(), "Rust", "rust");
+        let service = rename_service(vec![tag.clone()]);
+
+        let result = service
+            .rename(tag.id, "Tokio", domain::DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+
+        let result = service
+            .rename(tag.id, "   ", domain::ADMIN_PERMISSIONS)
+            .await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        let result = service
+            .rename(tag.id, &"a".repeat(51), domain::ADMIN_PERMISSIONS)
+            .await;
+        assert!(matches!(result, Err(Error::Validation(_))));
+
+        let result = service
+            .rename(Uuid::new_v4(), "Tokio", domain::ADMIN_PERMISSIONS)
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    const FIXTURE: &str = r#"# Getting started with Rust
+
+Rust makes **async** code safe. This post shows how to write an async
+web server in [Rust](https://www.rust-lang.org/learn) with Tokio.
+
+```rust
+async fn main() {}
+```
+
+- Rust ownership and Rust lifetimes
+- Tokio runtime and async tasks
+"#;
+
+    fn service_with_tags(tags: Vec<Tag>) -> TagService {
+        let mut mock_repo = MockTagRepo::new();
+        mock_repo
+            .expect_list_tags()
+            .returning(move || Ok(tags.clone()));
+        TagService::new(Arc::new(mock_repo))
+    }
+
+    #[tokio::test]
+    async fn test_suggest_tags_ranks_present_tags_by_frequency() {
+        let service = service_with_tags(vec![
+            create_test_tag(Uuid::new_v4(), "Python", "python"),
+            create_test_tag(Uuid::new_v4(), "Tokio", "tokio"),
+            create_test_tag(Uuid::new_v4(), "Rust", "rust"),
+            create_test_tag(Uuid::new_v4(), "Async", "async"),
+            create_test_tag(Uuid::new_v4(), "Learn", "learn"),
+        ]);
+
+        let suggestions = service.suggest_tags_for_content(FIXTURE, 10).await.unwrap();
+        let names: Vec<&str> = suggestions.iter().map(|t| t.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Rust", "Async", "Tokio"]);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_tags_ignores_stop_words_and_respects_limit() {
+        let service = service_with_tags(vec![
+            create_test_tag(Uuid::new_v4(), "The", "the"),
+            create_test_tag(Uuid::new_v4(), "Rust", "rust"),
+            create_test_tag(Uuid::new_v4(), "Async", "async"),
+        ]);
+
+        let suggestions = service.suggest_tags_for_content(FIXTURE, 1).await.unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].name, "Rust");
+
+        let none = service
+            .suggest_tags_for_content("the and of", 5)
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+    }
 }
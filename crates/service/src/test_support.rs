@@ -0,0 +1,916 @@
+//! Test doubles shared across service tests
+
+use crate::SharedConfig;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::{
+    ApiKey, ApiKeyRepository, ArchiveMonth, Category, CategoryRepository, Comment,
+    CommentRepository, CommentStatus, Config, Error, File, FileRepository, InviteCode,
+    InviteRepository, Post, PostRepository, PostSearchRequest, Result, SearchPostsResponse,
+    Session, SessionRepository, Tag, TagRepository, User, UserRepository,
+};
+use mockall::mock;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Minimal configuration for services that read live settings
+pub(crate) fn test_config() -> Config {
+    serde_json::from_value(serde_json::json!({
+        "database": { "url": "sqlite::memory:" },
+        "server": { "host": "127.0.0.1", "port": 3000 },
+        "auth": { "jwt_secret": "test-secret" },
+        "storage": { "upload_dir": "uploads", "cache_dir": "cache" },
+        "github": { "client_id": "test_client_id", "client_secret": "test_secret" },
+        "site": { "allow_registration": true },
+        "indexnow": { "enabled": false, "api_key": "", "endpoint": "" },
+    }))
+    .expect("test config should deserialize")
+}
+
+/// Wrap a configuration in the shared handle services expect
+pub(crate) fn shared_config(config: Config) -> SharedConfig {
+    Arc::new(std::sync::RwLock::new(config))
+}
+
+/// In-memory user repository shared by service tests
+pub(crate) struct MockUserRepo {
+    pub(crate) users: Arc<RwLock<HashMap<uuid::Uuid, User>>>,
+}
+
+#[async_trait]
+impl UserRepository for MockUserRepo {
+    async fn create_user(
+        &self,
+        _username: String,
+        _password: String,
+        _permissions: u64,
+    ) -> Result<User> {
+        Ok(User::new(
+            uuid::Uuid::new_v4(),
+            "test".to_string(),
+            "hash".to_string(),
+            0,
+        ))
+    }
+
+    async fn find_by_username(&self, _username: &str) -> Result<Option<User>> {
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<User>> {
+        let users = self.users.read().await;
+        Ok(users.get(&id).cloned())
+    }
+
+    async fn verify_credentials(&self, _username: &str, _password: &str) -> Result<Option<User>> {
+        Ok(None)
+    }
+
+    async fn update_permissions(&self, _user_id: uuid::Uuid, _permissions: u64) -> Result<User> {
+        Ok(User::new(
+            uuid::Uuid::new_v4(),
+            "test".to_string(),
+            "hash".to_string(),
+            0,
+        ))
+    }
+
+    async fn list_users(&self, _limit: u64) -> Result<Vec<User>> {
+        Ok(Vec::new())
+    }
+
+    async fn search_users(&self, query: &str, limit: u64, offset: u64) -> Result<(Vec<User>, u64)> {
+        let users = self.users.read().await;
+        let mut matches: Vec<User> = users
+            .values()
+            .filter(|user| user.username.contains(query))
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| a.username.cmp(&b.username));
+        let total = matches.len() as u64;
+        let page = matches
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn update_password(&self, _user_id: uuid::Uuid, _new_password: String) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_username(&self, _user_id: uuid::Uuid, _new_username: String) -> Result<User> {
+        Ok(User::new(
+            uuid::Uuid::new_v4(),
+            "test".to_string(),
+            "hash".to_string(),
+            0,
+        ))
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: uuid::Uuid,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+    ) -> Result<User> {
+        let mut user = self
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| domain::Error::NotFound("User not found".to_string()))?;
+        user.bio = bio;
+        user.avatar_url = avatar_url;
+        Ok(user)
+    }
+
+    async fn update_user(&self, user_id: uuid::Uuid, changes: domain::UserChanges) -> Result<User> {
+        let mut users = self.users.write().await;
+        let user = users
+            .get_mut(&user_id)
+            .ok_or_else(|| domain::Error::NotFound("User not found".to_string()))?;
+        if let Some(username) = changes.username {
+            user.username = username;
+        }
+        if let Some(permissions) = changes.permissions {
+            user.permissions = permissions;
+        }
+        if let Some(bio) = changes.bio {
+            user.bio = bio;
+        }
+        if let Some(avatar_url) = changes.avatar_url {
+            user.avatar_url = avatar_url;
+        }
+        Ok(user.clone())
+    }
+
+    async fn add_audit_log(
+        &self,
+        _user_id: uuid::Uuid,
+        _action: &str,
+        _detail: Option<String>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_totp(
+        &self,
+        _user_id: uuid::Uuid,
+        _secret: Option<String>,
+        _enabled: bool,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_totp_last_step(&self, _user_id: uuid::Uuid, _step: u64) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>> {
+        let users = self.users.read().await;
+        Ok(users
+            .values()
+            .find(|user| user.email.as_deref() == Some(email))
+            .cloned())
+    }
+
+    async fn update_email(
+        &self,
+        _user_id: uuid::Uuid,
+        _email: String,
+        _verification_token: String,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn verify_email(&self, _token: &str) -> Result<Option<User>> {
+        Ok(None)
+    }
+
+    async fn set_password_reset_token(
+        &self,
+        _user_id: uuid::Uuid,
+        _token: Option<String>,
+        _expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn find_by_password_reset_token(&self, _token: &str) -> Result<Option<User>> {
+        Ok(None)
+    }
+
+    async fn delete_user(&self, _user_id: uuid::Uuid) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl MockUserRepo {
+    /// Repository pre-populated with the given users
+    pub(crate) fn with_users(users: Vec<User>) -> Self {
+        Self {
+            users: Arc::new(RwLock::new(
+                users.into_iter().map(|user| (user.id, user)).collect(),
+            )),
+        }
+    }
+}
+
+mock! {
+    /// mockall-based post repository shared by service tests
+    pub(crate) PostRepo {}
+
+    #[async_trait]
+    impl PostRepository for PostRepo {
+        async fn create_post(&self, user_id: Uuid, title: String, content: String) -> Result<Post>;
+        async fn batch_upsert_posts(&self, posts: Vec<Post>) -> Result<Vec<Post>>;
+        async fn get_post(&self, id: Uuid) -> Result<Post>;
+        async fn update_post(&self, post: Post) -> Result<Post>;
+        async fn list_published_posts(&self, limit: u64, offset: u64) -> Result<Vec<Post>>;
+        async fn list_published_posts_with_authors(&self, limit: u64, offset: u64) -> Result<Vec<domain::PostWithAuthor>>;
+        async fn list_posts_with_tags(&self, limit: u64, offset: u64) -> Result<Vec<domain::PostWithTags>>;
+        async fn count_published(&self) -> Result<u64>;
+        async fn list_published_posts_by_views(&self, limit: u64) -> Result<Vec<Post>>;
+        async fn list_published_between(&self, start: DateTime<Utc>, end: DateTime<Utc>, limit: u64) -> Result<Vec<Post>>;
+        async fn count_published_by_month(&self) -> Result<Vec<ArchiveMonth>>;
+        async fn bulk_update_publication(&self, ids: Vec<Uuid>, published_at: Option<DateTime<Utc>>, scheduled_publish_at: Option<DateTime<Utc>>) -> Result<u64>;
+        async fn delete_post(&self, id: Uuid) -> Result<()>;
+        async fn hard_delete_post(&self, id: Uuid) -> Result<()>;
+        async fn restore_post(&self, id: Uuid) -> Result<Post>;
+        async fn purge_deleted_posts(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64>;
+        async fn archive_post(&self, id: Uuid) -> Result<()>;
+        async fn unarchive_post(&self, id: Uuid) -> Result<()>;
+        async fn get_archived_post(&self, id: Uuid) -> Result<Post>;
+        async fn list_archived_posts(&self, limit: u64) -> Result<Vec<Post>>;
+        async fn get_posts_sharing_tags(&self, tag_ids: Vec<Uuid>, exclude_post_id: Uuid, limit: u64) -> Result<Vec<(Post, u32)>>;
+        async fn get_next_published_post(&self, after_published_at: chrono::DateTime<chrono::Utc>, current_id: Uuid) -> Result<Option<Post>>;
+        async fn get_prev_published_post(&self, before_published_at: chrono::DateTime<chrono::Utc>, current_id: Uuid) -> Result<Option<Post>>;
+        async fn list_published_post_ids_before(&self, before: chrono::DateTime<chrono::Utc>) -> Result<Vec<Uuid>>;
+        async fn list_due_scheduled_post_ids(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<Uuid>>;
+        async fn get_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>>;
+        async fn count_posts_by_user(&self, user_id: Uuid) -> Result<u64>;
+        async fn list_published_posts_by_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<Post>>;
+        async fn list_posts_with_view_counts(&self, user_id: Uuid, limit: u64) -> Result<Vec<(Post, u64)>>;
+        async fn aggregate_word_counts(&self, published_only: bool) -> Result<domain::WordCountStats>;
+        async fn list_all_posts(&self, limit: u64) -> Result<Vec<Post>>;
+        async fn update_post_category(&self, post_id: Uuid, category_id: Option<Uuid>) -> Result<()>;
+        async fn increment_category_post_count(&self, category_id: Uuid) -> Result<()>;
+        async fn decrement_category_post_count(&self, category_id: Uuid) -> Result<()>;
+        async fn get_posts_by_category(&self, category_id: Uuid, limit: u64) -> Result<Vec<Post>>;
+        async fn get_posts_by_category_recursive(
+            &self,
+            category_id: Uuid,
+            limit: u64,
+        ) -> Result<Vec<Post>>;
+        async fn list_published_by_category_paginated(
+            &self,
+            category_id: Uuid,
+            recursive: bool,
+            limit: u64,
+            offset: u64,
+        ) -> Result<(Vec<Post>, u64)>;
+        async fn add_tag_to_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()>;
+        async fn replace_post_tags(&self, post_id: Uuid, tag_ids: Vec<Uuid>) -> Result<()>;
+        async fn remove_tag_from_post(&self, post_id: Uuid, tag_id: Uuid) -> Result<()>;
+        async fn get_post_tags(&self, post_id: Uuid) -> Result<Vec<Tag>>;
+        async fn get_posts_by_tag(&self, tag_id: Uuid, limit: u64) -> Result<Vec<Post>>;
+        async fn search_posts(&self, query: &str, limit: u64, offset: u64) -> Result<SearchPostsResponse>;
+        async fn search_posts_advanced(&self, request: &PostSearchRequest) -> Result<SearchPostsResponse>;
+    }
+}
+
+mock! {
+    /// mockall-based category repository shared by service tests
+    pub(crate) CategoryRepo {}
+
+    #[async_trait::async_trait]
+    impl CategoryRepository for CategoryRepo {
+        async fn create_category(&self, name: String, slug: String, parent_id: Option<Uuid>) -> Result<Category>;
+        async fn get_category(&self, id: Uuid) -> Result<Option<Category>>;
+        async fn get_category_by_slug(&self, slug: &str) -> Result<Option<Category>>;
+        async fn list_categories(&self) -> Result<Vec<Category>>;
+        async fn update_category(&self, id: Uuid, name: Option<String>, parent_id: Option<Uuid>) -> Result<Category>;
+        async fn delete_category(&self, id: Uuid) -> Result<()>;
+        async fn get_children(&self, parent_id: Option<Uuid>) -> Result<Vec<Category>>;
+        async fn get_category_with_ancestors(&self, id: Uuid) -> Result<Vec<Category>>;
+        async fn get_category_depth(&self, id: Uuid) -> Result<u32>;
+    }
+}
+
+mock! {
+    /// mockall-based tag repository shared by service tests
+    pub(crate) TagRepo {}
+
+    #[async_trait::async_trait]
+    impl TagRepository for TagRepo {
+        async fn create_tag(&self, name: String, slug: String) -> Result<Tag>;
+        async fn get_tag(&self, id: Uuid) -> Result<Option<Tag>>;
+        async fn get_tag_by_slug(&self, slug: &str) -> Result<Option<Tag>>;
+        async fn upsert_tag(&self, name: &str, slug: &str) -> Result<Tag>;
+        async fn list_tags(&self) -> Result<Vec<Tag>>;
+        async fn update_tag(&self, id: Uuid, name: String, slug: String) -> Result<Tag>;
+        async fn delete_tag(&self, id: Uuid) -> Result<()>;
+        async fn merge_tags(&self, source_id: Uuid, target_id: Uuid) -> Result<()>;
+        async fn get_tag_cloud(&self) -> Result<Vec<domain::TagCloudEntry>>;
+    }
+}
+
+/// In-memory comment repository shared by service tests
+pub(crate) struct MockCommentRepo {
+    pub(crate) comments: Arc<RwLock<HashMap<uuid::Uuid, Comment>>>,
+    reactions: RwLock<HashSet<(uuid::Uuid, uuid::Uuid, String)>>,
+    /// Number of reaction count queries run
+    pub(crate) reaction_queries: AtomicUsize,
+}
+
+#[async_trait]
+impl CommentRepository for MockCommentRepo {
+    async fn create_comment(&self, comment: Comment) -> Result<Comment> {
+        let mut comments = self.comments.write().await;
+        comments.insert(comment.id, comment.clone());
+        Ok(comment)
+    }
+
+    async fn get_comment(&self, id: uuid::Uuid) -> Result<Option<Comment>> {
+        let comments = self.comments.read().await;
+        Ok(comments.get(&id).cloned())
+    }
+
+    async fn list_post_comments(
+        &self,
+        post_id: uuid::Uuid,
+        status: Option<CommentStatus>,
+        limit: u64,
+    ) -> Result<Vec<Comment>> {
+        let comments = self.comments.read().await;
+        let mut matching: Vec<Comment> = comments
+            .values()
+            .filter(|c| c.post_id == post_id)
+            .filter(|c| status.is_none_or(|s| c.status == s))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|c| c.created_at);
+        matching.truncate(limit as usize);
+        Ok(matching)
+    }
+
+    async fn list_comments_by_status(
+        &self,
+        status: CommentStatus,
+        limit: u64,
+    ) -> Result<Vec<Comment>> {
+        let comments = self.comments.read().await;
+        Ok(comments
+            .values()
+            .filter(|c| c.status == status)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_comments_by_user(
+        &self,
+        user_id: uuid::Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<Comment>, u64)> {
+        let comments = self.comments.read().await;
+        let mut matching: Vec<Comment> = comments
+            .values()
+            .filter(|c| c.user_id == Some(user_id))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        let total = matching.len() as u64;
+        Ok((
+            matching
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect(),
+            total,
+        ))
+    }
+
+    async fn search_comments(
+        &self,
+        query: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<(Comment, Option<String>)>, u64)> {
+        let comments = self.comments.read().await;
+        let mut matching: Vec<Comment> = comments
+            .values()
+            .filter(|c| {
+                c.content.contains(query)
+                    || c.github_username
+                        .as_deref()
+                        .is_some_and(|u| u.contains(query))
+            })
+            .cloned()
+            .collect();
+        matching.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        let total = matching.len() as u64;
+        let page = matching
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|c| {
+                let title = format!("Post {}", c.post_id);
+                (c, Some(title))
+            })
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn update_comment(&self, comment: Comment) -> Result<Comment> {
+        let mut comments = self.comments.write().await;
+        comments.insert(comment.id, comment.clone());
+        Ok(comment)
+    }
+
+    async fn delete_comment(
+        &self,
+        id: uuid::Uuid,
+        _user_id: Option<uuid::Uuid>,
+        _is_github_user: bool,
+    ) -> Result<()> {
+        let mut comments = self.comments.write().await;
+        comments.remove(&id);
+        // Mirrors the ON DELETE CASCADE on parent_comment_id
+        comments.retain(|_, c| c.parent_id != Some(id));
+        Ok(())
+    }
+
+    async fn get_post_comment_count(&self, _post_id: uuid::Uuid) -> Result<u64> {
+        Ok(0)
+    }
+
+    /// The mock knows no post authors, so every stored comment is counted
+    async fn count_comments_by_post_author(
+        &self,
+        _author_id: uuid::Uuid,
+    ) -> Result<HashMap<uuid::Uuid, u64>> {
+        let comments = self.comments.read().await;
+        let mut counts = HashMap::new();
+        for comment in comments.values() {
+            *counts.entry(comment.post_id).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    async fn count_comments_by_user(&self, user_id: uuid::Uuid) -> Result<u64> {
+        let comments = self.comments.read().await;
+        Ok(comments
+            .values()
+            .filter(|c| c.user_id == Some(user_id))
+            .count() as u64)
+    }
+
+    async fn add_reaction(
+        &self,
+        comment_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        reaction_type: &str,
+    ) -> Result<bool> {
+        let mut reactions = self.reactions.write().await;
+        Ok(reactions.insert((comment_id, user_id, reaction_type.to_string())))
+    }
+
+    async fn remove_reaction(
+        &self,
+        comment_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        reaction_type: &str,
+    ) -> Result<bool> {
+        let mut reactions = self.reactions.write().await;
+        Ok(reactions.remove(&(comment_id, user_id, reaction_type.to_string())))
+    }
+
+    async fn count_reactions(&self, comment_id: uuid::Uuid) -> Result<HashMap<String, u64>> {
+        self.reaction_queries.fetch_add(1, Ordering::Relaxed);
+        let reactions = self.reactions.read().await;
+        let mut counts = HashMap::new();
+        for (_, _, reaction_type) in reactions.iter().filter(|r| r.0 == comment_id) {
+            *counts.entry(reaction_type.clone()).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    async fn count_reactions_for(
+        &self,
+        comment_ids: &[uuid::Uuid],
+    ) -> Result<HashMap<uuid::Uuid, HashMap<String, u64>>> {
+        self.reaction_queries.fetch_add(1, Ordering::Relaxed);
+        let reactions = self.reactions.read().await;
+        let mut counts: HashMap<uuid::Uuid, HashMap<String, u64>> = HashMap::new();
+        for (comment_id, _, reaction_type) in
+            reactions.iter().filter(|r| comment_ids.contains(&r.0))
+        {
+            *counts
+                .entry(*comment_id)
+                .or_default()
+                .entry(reaction_type.clone())
+                .or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+}
+
+impl MockCommentRepo {
+    /// Empty repository
+    pub(crate) fn new() -> Self {
+        Self {
+            comments: Arc::new(RwLock::new(HashMap::new())),
+            reactions: RwLock::new(HashSet::new()),
+            reaction_queries: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// In-memory API key repository keyed by key hash
+#[derive(Default)]
+pub(crate) struct MockApiKeyRepo {
+    keys: RwLock<HashMap<String, ApiKey>>,
+}
+
+#[async_trait]
+impl ApiKeyRepository for MockApiKeyRepo {
+    async fn create_api_key(&self, api_key: ApiKey) -> Result<ApiKey> {
+        let mut keys = self.keys.write().await;
+        keys.insert(api_key.key_hash.clone(), api_key.clone());
+        Ok(api_key)
+    }
+
+    async fn get_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        Ok(self.keys.read().await.get(key_hash).cloned())
+    }
+
+    async fn list_user_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>> {
+        let keys = self.keys.read().await;
+        Ok(keys
+            .values()
+            .filter(|k| k.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn touch_api_key(&self, key_hash: &str, used_at: DateTime<Utc>) -> Result<()> {
+        if let Some(key) = self.keys.write().await.get_mut(key_hash) {
+            key.last_used_at = Some(used_at);
+        }
+        Ok(())
+    }
+
+    async fn delete_api_key(&self, user_id: Uuid, key_hash: &str) -> Result<bool> {
+        let mut keys = self.keys.write().await;
+        if keys.get(key_hash).is_some_and(|k| k.user_id == user_id) {
+            keys.remove(key_hash);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
+
+/// In-memory file store
+#[derive(Default)]
+pub(crate) struct MockFileRepo {
+    pub(crate) files: Arc<RwLock<HashMap<Uuid, File>>>,
+}
+
+#[async_trait]
+impl FileRepository for MockFileRepo {
+    async fn create_file(&self, file: File) -> Result<File> {
+        let mut files = self.files.write().await;
+        files.insert(file.id, file.clone());
+        Ok(file)
+    }
+
+    async fn get_file(&self, id: Uuid) -> Result<Option<File>> {
+        let files = self.files.read().await;
+        Ok(files.get(&id).cloned())
+    }
+
+    async fn list_files_by_user(&self, user_id: Uuid, _limit: u64) -> Result<Vec<File>> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .filter(|f| f.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_file(&self, id: Uuid, user_id: Uuid) -> Result<()> {
+        let mut files = self.files.write().await;
+        match files.get(&id) {
+            Some(file) if file.user_id != user_id => Err(Error::Validation(
+                "You can only delete your own files".to_string(),
+            )),
+            _ => {
+                files.remove(&id);
+                Ok(())
+            }
+        }
+    }
+
+    async fn get_file_by_filename(&self, filename: &str) -> Result<Option<File>> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .filter(|f| f.filename == filename)
+            .min_by_key(|f| f.created_at)
+            .cloned())
+    }
+
+    async fn find_by_hash(&self, hash: &str) -> Result<Option<File>> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .filter(|f| f.sha256_hash.as_deref() == Some(hash))
+            .min_by_key(|f| f.created_at)
+            .cloned())
+    }
+
+    async fn find_by_hash_for_user(&self, hash: &str, user_id: Uuid) -> Result<Option<File>> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .find(|f| f.sha256_hash.as_deref() == Some(hash) && f.user_id == user_id)
+            .cloned())
+    }
+
+    async fn count_by_hash(&self, hash: &str) -> Result<u64> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .filter(|f| f.sha256_hash.as_deref() == Some(hash))
+            .count() as u64)
+    }
+
+    async fn list_all_files(
+        &self,
+        limit: u64,
+        offset: u64,
+        content_type_filter: Option<&str>,
+    ) -> Result<(Vec<File>, u64)> {
+        let files = self.files.read().await;
+        let mut matching: Vec<File> = files
+            .values()
+            .filter(|f| content_type_filter.is_none_or(|filter| f.matches_content_type(filter)))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|f| std::cmp::Reverse(f.created_at));
+        let total = matching.len() as u64;
+        Ok((
+            matching
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect(),
+            total,
+        ))
+    }
+
+    async fn total_file_size(&self, content_type_filter: Option<&str>) -> Result<u64> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .filter(|f| content_type_filter.is_none_or(|filter| f.matches_content_type(filter)))
+            .map(|f| f.size_bytes)
+            .sum())
+    }
+
+    async fn update_file_owner(&self, file_id: Uuid, new_user_id: Uuid) -> Result<File> {
+        let mut files = self.files.write().await;
+        let file = files
+            .get_mut(&file_id)
+            .ok_or_else(|| Error::NotFound("File not found".to_string()))?;
+        file.user_id = new_user_id;
+        Ok(file.clone())
+    }
+
+    async fn total_storage_used_by_user(&self, user_id: uuid::Uuid) -> Result<u64> {
+        let files = self.files.read().await;
+        Ok(files
+            .values()
+            .filter(|f| f.user_id == user_id)
+            .map(|f| f.size_bytes)
+            .sum())
+    }
+}
+
+/// In-memory invite code store
+#[derive(Default)]
+pub(crate) struct MockInviteRepo {
+    invites: RwLock<HashMap<String, InviteCode>>,
+}
+
+#[async_trait]
+impl InviteRepository for MockInviteRepo {
+    async fn create_invite(&self, invite: InviteCode) -> Result<InviteCode> {
+        let mut invites = self.invites.write().await;
+        invites.insert(invite.code.clone(), invite.clone());
+        Ok(invite)
+    }
+
+    async fn get_invite(&self, code: &str) -> Result<Option<InviteCode>> {
+        Ok(self.invites.read().await.get(code).cloned())
+    }
+
+    async fn list_invites(&self) -> Result<Vec<InviteCode>> {
+        let mut invites: Vec<InviteCode> = self.invites.read().await.values().cloned().collect();
+        invites.sort_by_key(|i| std::cmp::Reverse(i.created_at));
+        Ok(invites)
+    }
+
+    async fn mark_invite_used(&self, code: &str, user_id: Uuid) -> Result<bool> {
+        match self.invites.write().await.get_mut(code) {
+            Some(invite) if invite.used_by.is_none() => {
+                invite.used_by = Some(user_id);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// In-memory session repository shared by service tests
+pub(crate) struct MockSessionRepo {
+    pub(crate) sessions: Arc<RwLock<HashMap<String, Session>>>,
+}
+
+#[async_trait]
+impl SessionRepository for MockSessionRepo {
+    async fn create_session(
+        &self,
+        user_id: uuid::Uuid,
+        remember_me: bool,
+        user_agent: &str,
+        ip_address: &str,
+    ) -> Result<Session> {
+        let mut session = if remember_me {
+            Session::with_remember(user_id)
+        } else {
+            Session::new(user_id)
+        };
+        session.user_agent = Some(user_agent.to_string());
+        session.ip_address = Some(ip_address.to_string());
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(session.id.clone(), session.clone());
+        Ok(session)
+    }
+
+    async fn get_session(&self, token: &str) -> Result<Option<Session>> {
+        let sessions = self.sessions.read().await;
+        Ok(sessions.get(token).cloned())
+    }
+
+    async fn get_session_by_refresh_token(&self, refresh_token: &str) -> Result<Option<Session>> {
+        let sessions = self.sessions.read().await;
+        Ok(sessions
+            .values()
+            .find(|session| session.refresh_token == refresh_token)
+            .cloned())
+    }
+
+    async fn revoke_refresh_token(&self, refresh_token: &str) -> Result<()> {
+        let mut sessions = self.sessions.write().await;
+        for session in sessions.values_mut() {
+            if session.refresh_token == refresh_token {
+                session.revoked = true;
+            }
+        }
+        Ok(())
+    }
+
+    async fn extend_session(
+        &self,
+        token: &str,
+        new_expires_at: DateTime<Utc>,
+        new_refresh_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut sessions = self.sessions.write().await;
+        if let Some(session) = sessions.get_mut(token) {
+            session.expires_at = new_expires_at;
+            session.refresh_at = new_refresh_at;
+        }
+        Ok(())
+    }
+
+    async fn delete_session(&self, token: &str) -> Result<()> {
+        let mut sessions = self.sessions.write().await;
+        sessions.remove(token);
+        Ok(())
+    }
+
+    async fn list_user_sessions(&self, user_id: uuid::Uuid) -> Result<Vec<Session>> {
+        let sessions = self.sessions.read().await;
+        let mut sessions: Vec<Session> = sessions
+            .values()
+            .filter(|session| session.user_id == user_id)
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.created_at));
+        Ok(sessions)
+    }
+
+    async fn delete_user_sessions(
+        &self,
+        user_id: uuid::Uuid,
+        except_session_id: Option<&str>,
+    ) -> Result<u64> {
+        let mut sessions = self.sessions.write().await;
+        let before = sessions.len();
+        sessions.retain(|id, session| {
+            session.user_id != user_id || Some(id.as_str()) == except_session_id
+        });
+        Ok((before - sessions.len()) as u64)
+    }
+
+    async fn cleanup_expired_sessions(&self) -> Result<u64> {
+        let mut sessions = self.sessions.write().await;
+        let before = sessions.len();
+        sessions.retain(|_, session| !session.is_expired());
+        Ok((before - sessions.len()) as u64)
+    }
+
+    async fn count_active_sessions(&self) -> Result<u64> {
+        let sessions = self.sessions.read().await;
+        Ok(sessions.values().filter(|s| s.is_active()).count() as u64)
+    }
+}
+
+/// Minimal SMTP server that accepts every message and records its DATA
+pub(crate) struct MockSmtpServer {
+    pub(crate) port: u16,
+    messages: Arc<RwLock<Vec<String>>>,
+}
+
+impl MockSmtpServer {
+    /// Start listening on an ephemeral localhost port
+    pub(crate) async fn start() -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let messages = Arc::new(RwLock::new(Vec::new()));
+
+        let recorded = messages.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(Self::handle(stream, recorded.clone()));
+            }
+        });
+
+        Self { port, messages }
+    }
+
+    /// Wait until `count` messages have arrived, giving up after a few seconds
+    pub(crate) async fn wait_for_messages(&self, count: usize) -> Vec<String> {
+        for _ in 0..100 {
+            let messages = self.messages.read().await;
+            if messages.len() >= count {
+                return messages.clone();
+            }
+            drop(messages);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        self.messages.read().await.clone()
+    }
+
+    async fn handle(stream: tokio::net::TcpStream, messages: Arc<RwLock<Vec<String>>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let _ = writer.write_all(b"220 localhost ESMTP mock\r\n").await;
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            let command = line.to_ascii_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 localhost\r\n"
+            } else if command.starts_with("DATA") {
+                let _ = writer
+                    .write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n")
+                    .await;
+                let mut data = String::new();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if line == "." {
+                        break;
+                    }
+                    data.push_str(&line);
+                    data.push('\n');
+                }
+                messages.write().await.push(data);
+                b"250 OK\r\n"
+            } else if command.starts_with("QUIT") {
+                let _ = writer.write_all(b"221 Bye\r\n").await;
+                break;
+            } else {
+                b"250 OK\r\n"
+            };
+            let _ = writer.write_all(reply).await;
+        }
+    }
+}
@@ -3,9 +3,26 @@
 //! This service implements business logic for user operations.
 //! It coordinates repository calls and enforces business rules.
 
-use domain::UserRepository;
-use domain::{Error, Result, User, DEFAULT_USER_PERMISSIONS, USER_MANAGE};
-use std::sync::Arc;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use chrono::{DateTime, Duration, Utc};
+use domain::{ApiKey, ApiKeyRepository, UserRepository, API_KEY_PREFIX, API_KEY_SCOPES};
+use domain::{
+    BatchCreateResult, Error, ErrorCode, InviteCode, InviteRepository, LoginResult, NewUserEntry,
+    PaginatedUsers, Result, UpdateUser, User, UserChanges, UserInfo, DEFAULT_USER_PERMISSIONS,
+    INVITE_CODE_LENGTH, SCOPE_READ_POSTS, SUPER_ADMIN, USER_MANAGE,
+};
+use domain::{CommentRepository, PostRepository, SessionRepository, UserProfile};
+use infrastructure::EmailClient;
+use rand::distributions::{Alphanumeric, DistString};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::internal_error;
+use crate::{SharedClients, SharedConfig};
+use totp_rs::{Algorithm, Secret, TOTP};
 use uuid::Uuid;
 
 // ============================================================================
@@ -18,39 +35,172 @@ const DEFAULT_LIST_LIMIT: u64 = 50;
 /// Limit used when checking for admin count
 const ADMIN_COUNT_CHECK_LIMIT: u64 = 1000;
 
+/// Issuer shown in authenticator apps
+const TOTP_ISSUER: &str = "PengBlog";
+
+/// Prefix marking a TOTP secret stored encrypted rather than as plain base32
+const TOTP_SECRET_PREFIX: &str = "enc:v1:";
+
+/// Length of the AES-GCM nonce stored in front of an encrypted TOTP secret
+const TOTP_NONCE_LENGTH: usize = 12;
+
+/// TOTP time step in seconds
+const TOTP_STEP_SECONDS: u64 = 30;
+
+/// Lifetime of the partial token issued when a TOTP code is still required
+const PARTIAL_TOKEN_TTL_MINUTES: i64 = 5;
+
+/// Wrong TOTP codes allowed per partial token before it is discarded
+const MAX_OTP_ATTEMPTS: u32 = 5;
+
+/// Random bytes in an API key, before hex encoding
+const API_KEY_BYTES: usize = 32;
+
+/// Maximum length of an API key label
+const MAX_API_KEY_LABEL_LENGTH: usize = 100;
+
+/// Lifetime of a password reset link
+const PASSWORD_RESET_TTL_MINUTES: i64 = 30;
+
+/// Maximum length of an email address (RFC 5321 path limit)
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Maximum length of a profile bio, in characters
+const MAX_BIO_LENGTH: usize = 500;
+
+/// Maximum length of an avatar URL
+const MAX_AVATAR_URL_LENGTH: usize = 2048;
+
+/// Longest lifetime an invite code can be given
+pub const MAX_INVITE_EXPIRY_HOURS: u64 = 24 * 365;
+
+/// Longest accepted user search query, in characters
+const MAX_USER_SEARCH_QUERY_LENGTH: usize = 50;
+
+/// Maximum page size for a user search
+pub const MAX_USER_SEARCH_LIMIT: u64 = 100;
+
+/// Maximum number of accounts created by one bulk import
+pub const MAX_BATCH_USERS: usize = 1000;
+
+/// A password-verified login waiting for its TOTP code
+struct PendingOtp {
+    user_id: Uuid,
+    expires_at: DateTime<Utc>,
+    attempts: u32,
+}
+
 /// Service for user business logic
 ///
 /// This service encapsulates all business rules for user operations.
 /// It uses dependency injection for repositories, making it testable.
 pub struct UserService {
     repo: Arc<dyn UserRepository>,
-    allow_registration: bool,
+    config: SharedConfig,
+    totp_cipher: Aes256Gcm,
+    pending_otp: Mutex<HashMap<String, PendingOtp>>,
+    api_key_repo: Option<Arc<dyn ApiKeyRepository>>,
+    invite_repo: Option<Arc<dyn InviteRepository>>,
+    session_repo: Option<Arc<dyn SessionRepository>>,
+    clients: Option<SharedClients>,
+    base_url: String,
+    post_repo: Option<Arc<dyn PostRepository>>,
+    comment_repo: Option<Arc<dyn CommentRepository>>,
 }
 
 impl UserService {
     /// Create a new UserService with given repository and config
-    pub fn new(repo: Arc<dyn UserRepository>, allow_registration: bool) -> Self {
+    pub fn new(repo: Arc<dyn UserRepository>, config: SharedConfig) -> Self {
+        let totp_cipher = totp_cipher(&config.read().unwrap().auth.jwt_secret);
         Self {
             repo,
-            allow_registration,
+            config,
+            totp_cipher,
+            pending_otp: Mutex::new(HashMap::new()),
+            api_key_repo: None,
+            invite_repo: None,
+            session_repo: None,
+            clients: None,
+            base_url: String::new(),
+            post_repo: None,
+            comment_repo: None,
         }
     }
 
+    /// Attach the repository used to store API keys
+    pub fn with_api_keys(mut self, api_key_repo: Arc<dyn ApiKeyRepository>) -> Self {
+        self.api_key_repo = Some(api_key_repo);
+        self
+    }
+
+    /// Attach the repository used to store registration invite codes
+    pub fn with_invites(mut self, invite_repo: Arc<dyn InviteRepository>) -> Self {
+        self.invite_repo = Some(invite_repo);
+        self
+    }
+
+    /// Attach the repository of login sessions ended by a password reset
+    pub fn with_sessions(mut self, session_repo: Arc<dyn SessionRepository>) -> Self {
+        self.session_repo = Some(session_repo);
+        self
+    }
+
+    /// Attach the clients whose SMTP client sends verification and password
+    /// reset emails
+    ///
+    /// `base_url` is the public site URL that emailed links point to.
+    pub fn with_email(mut self, clients: SharedClients, base_url: String) -> Self {
+        self.clients = Some(clients);
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Attach the repositories used to count a user's posts and comments
+    pub fn with_activity_counts(
+        mut self,
+        post_repo: Arc<dyn PostRepository>,
+        comment_repo: Arc<dyn CommentRepository>,
+    ) -> Self {
+        self.post_repo = Some(post_repo);
+        self.comment_repo = Some(comment_repo);
+        self
+    }
+
     /// Register a new user with validation
     ///
     /// This method validates username and password, checks if username is unique,
-    /// and assigns appropriate permissions.
-    pub async fn register(&self, username: String, password: String) -> Result<User> {
-        if !self.allow_registration {
+    /// and assigns appropriate permissions. While the site is invite-only an
+    /// unused, unexpired `invite_code` is required and is marked as used by
+    /// the new account; otherwise the code is ignored.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn register(
+        &self,
+        username: String,
+        password: String,
+        invite_code: Option<&str>,
+    ) -> Result<User> {
+        let site = self.config.read().unwrap().site.clone();
+        let invite = if site.allow_registration {
+            None
+        } else if site.is_invite_only() {
+            let code = invite_code
+                .map(str::trim)
+                .filter(|code| !code.is_empty())
+                .ok_or_else(|| Error::Validation("An invite code is required".to_string()))?;
+            Some(self.usable_invite(code, Utc::now()).await?)
+        } else {
             return Err(Error::Validation("Registration is disabled".to_string()));
-        }
+        };
 
-        self.validate_username(&username)?;
+        self.validate_new_username(&username)?;
         self.validate_password(&password)?;
 
         // Check if username already exists
         if self.repo.find_by_username(&username).await?.is_some() {
-            return Err(Error::Validation("Username already exists".to_string()));
+            return Err(
+                Error::coded(ErrorCode::UsernameExists, "Username already exists")
+                    .with_field("username"),
+            );
         }
 
         // Check if this is first user (make them admin)
@@ -62,41 +212,335 @@ impl UserService {
             DEFAULT_USER_PERMISSIONS
         };
 
-        self.repo.create_user(username, password, permissions).await
+        let user = self
+            .repo
+            .create_user(username, password, permissions)
+            .await?;
+
+        if let Some(invite) = invite {
+            // Another registration may have claimed the code in the meantime
+            if !self
+                .invite_repo()?
+                .mark_invite_used(&invite.code, user.id)
+                .await?
+            {
+                self.repo.delete_user(user.id).await?;
+                return Err(Error::Validation(
+                    "Invite code has already been used".to_string(),
+                ));
+            }
+        }
+
+        tracing::info!("User {} registered", user.id);
+        Ok(user)
     }
 
-    /// Authenticate user with username and password
+    /// Create accounts in bulk, e.g. from a CSV file (admin only)
+    ///
+    /// Entries are created one by one; an entry with an invalid or taken
+    /// username or a weak password is recorded in `failed` and the rest are
+    /// still created. Like `peng-blog user create`, reserved usernames are
+    /// accepted.
     ///
-    /// Returns the user if credentials are valid, None otherwise.
-    pub async fn login(&self, username: String, password: String) -> Result<User> {
-        self.validate_username(&username)?;
+    /// # Returns
+    /// * `Ok(BatchCreateResult)` - Created users and the failed usernames with reasons
+    /// * `Err(Error::Validation)` - Empty batch or more than [`MAX_BATCH_USERS`] entries
+    /// * `Err(Error::CodedError)` - Requester lacks USER_MANAGE
+    #[tracing::instrument(level = "debug", skip_all, fields(count = users.len()))]
+    pub async fn batch_create(
+        &self,
+        users: Vec<NewUserEntry>,
+        requester_permissions: u64,
+    ) -> Result<BatchCreateResult> {
+        domain::check_permission(requester_permissions, USER_MANAGE)?;
+        if users.is_empty() {
+            return Err(Error::Validation("No users to create".to_string()));
+        }
+        if users.len() > MAX_BATCH_USERS {
+            return Err(Error::Validation(format!(
+                "Too many users: at most {} can be created at once",
+                MAX_BATCH_USERS
+            )));
+        }
+
+        let mut result = BatchCreateResult::default();
+        for entry in users {
+            let username = entry.username.trim().to_string();
+            match self
+                .create_entry(&username, entry.password, entry.admin)
+                .await
+            {
+                Ok(user) => result.created.push(user),
+                Err(e) => {
+                    tracing::warn!("Bulk import skipped user {}: {}", username, e.message());
+                    result.failed.push((username, e.message().to_string()));
+                }
+            }
+        }
+
+        tracing::info!(
+            "Bulk import created {} users, {} failed",
+            result.created.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    }
+
+    /// Validate and create a single account of a bulk import
+    async fn create_entry(&self, username: &str, password: String, admin: bool) -> Result<User> {
+        self.validate_username(username)?;
         self.validate_password(&password)?;
+        if self.repo.find_by_username(username).await?.is_some() {
+            return Err(
+                Error::coded(ErrorCode::UsernameExists, "Username already exists")
+                    .with_field("username"),
+            );
+        }
 
+        let permissions = if admin {
+            domain::ADMIN_PERMISSIONS
+        } else {
+            DEFAULT_USER_PERMISSIONS
+        };
         self.repo
+            .create_user(username.to_string(), password, permissions)
+            .await
+    }
+
+    /// Create a single-use invite code valid for `expires_in_hours`
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The 16-character alphanumeric code
+    /// * `Err(Error::Validation)` - Missing USER_MANAGE, or the lifetime is
+    ///   zero or longer than [`MAX_INVITE_EXPIRY_HOURS`]
+    #[tracing::instrument(level = "debug", skip_all, fields(%admin_id))]
+    pub async fn create_invite(
+        &self,
+        admin_id: Uuid,
+        permissions: u64,
+        expires_in_hours: u64,
+    ) -> Result<String> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+        if expires_in_hours == 0 || expires_in_hours > MAX_INVITE_EXPIRY_HOURS {
+            return Err(Error::Validation(format!(
+                "Invite expiry must be between 1 and {} hours",
+                MAX_INVITE_EXPIRY_HOURS
+            )));
+        }
+
+        let now = Utc::now();
+        let invite = InviteCode {
+            code: Alphanumeric.sample_string(&mut rand::thread_rng(), INVITE_CODE_LENGTH),
+            created_by: Some(admin_id),
+            used_by: None,
+            expires_at: now + Duration::hours(expires_in_hours as i64),
+            created_at: now,
+        };
+
+        let invite = self.invite_repo()?.create_invite(invite).await?;
+        Ok(invite.code)
+    }
+
+    /// List every invite code, newest first (requires USER_MANAGE)
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_invites(&self, permissions: u64) -> Result<Vec<InviteCode>> {
+        domain::check_permission(permissions, USER_MANAGE)?;
+        self.invite_repo()?.list_invites().await
+    }
+
+    /// Look up an invite code that can still be used at `now`
+    async fn usable_invite(&self, code: &str, now: DateTime<Utc>) -> Result<InviteCode> {
+        let invite = self
+            .invite_repo()?
+            .get_invite(code)
+            .await?
+            .ok_or_else(|| Error::Validation("Invalid invite code".to_string()))?;
+
+        if invite.is_used() {
+            return Err(Error::Validation(
+                "Invite code has already been used".to_string(),
+            ));
+        }
+        if invite.is_expired(now) {
+            return Err(Error::Validation("Invite code has expired".to_string()));
+        }
+
+        Ok(invite)
+    }
+
+    /// Authenticate user with username or email address and password
+    ///
+    /// Input containing `@` is looked up as an email address, ignoring case.
+    /// An unknown user or email fails with the same `Unauthorized` "Invalid
+    /// credentials" error as a wrong password, so logins cannot be used to
+    /// probe for accounts.
+    ///
+    /// Users with TOTP enabled get a short-lived partial token instead,
+    /// which must be exchanged via `complete_totp_login`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn login(&self, username_or_email: String, password: String) -> Result<LoginResult> {
+        let invalid_credentials = || Error::Unauthorized("Invalid credentials".to_string());
+        self.validate_password(&password)?;
+
+        let username = if username_or_email.contains('@') {
+            let email = username_or_email.trim().to_lowercase();
+            self.repo
+                .find_by_email(&email)
+                .await?
+                .ok_or_else(invalid_credentials)?
+                .username
+        } else {
+            self.validate_username(&username_or_email)?;
+            username_or_email
+        };
+
+        let user = self
+            .repo
             .verify_credentials(&username, &password)
             .await?
-            .ok_or_else(|| Error::NotFound("Invalid credentials".to_string()))
+            .ok_or_else(invalid_credentials)?;
+
+        if user.totp_enabled {
+            return Ok(LoginResult::RequiresOtp(self.issue_partial_token(user.id)));
+        }
+
+        Ok(LoginResult::Authenticated(Box::new(user)))
+    }
+
+    /// Exchange a partial token and TOTP code for the authenticated user
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn complete_totp_login(&self, partial_token: &str, code: &str) -> Result<User> {
+        let user_id = {
+            let mut pending = self.pending_otp.lock().unwrap();
+            match pending.get(partial_token) {
+                Some(entry) if entry.expires_at > Utc::now() => entry.user_id,
+                Some(_) => {
+                    pending.remove(partial_token);
+                    return Err(Error::Unauthorized("Login token has expired".to_string()));
+                }
+                None => return Err(Error::Unauthorized("Invalid login token".to_string())),
+            }
+        };
+
+        if !self.verify_totp(user_id, code).await? {
+            let mut pending = self.pending_otp.lock().unwrap();
+            if let Some(entry) = pending.get_mut(partial_token) {
+                entry.attempts += 1;
+                if entry.attempts >= MAX_OTP_ATTEMPTS {
+                    pending.remove(partial_token);
+                }
+            }
+            return Err(Error::Unauthorized("Invalid TOTP code".to_string()));
+        }
+
+        self.pending_otp.lock().unwrap().remove(partial_token);
+        self.get(user_id).await
+    }
+
+    /// Start TOTP setup for a user
+    ///
+    /// Generates a new secret, stores it encrypted, and returns the
+    /// `otpauth://` provisioning URI. TOTP is not enforced until the user
+    /// confirms a code with `confirm_totp`. Fails with `Conflict` when TOTP is
+    /// already enabled, so a stolen session cannot replace the second factor.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn enable_totp(&self, user_id: Uuid) -> Result<String> {
+        let user = self.get(user_id).await?;
+        if user.totp_enabled {
+            return Err(Error::Conflict(
+                "Two-factor authentication is already enabled".to_string(),
+            ));
+        }
+
+        let secret = match Secret::generate_secret().to_encoded() {
+            Secret::Encoded(secret) => secret,
+            Secret::Raw(_) => unreachable!("to_encoded always returns an encoded secret"),
+        };
+        let totp = Self::build_totp(&secret, &user.username)?;
+
+        let sealed = self.seal_totp_secret(&secret)?;
+        self.repo.update_totp(user_id, Some(sealed), false).await?;
+
+        Ok(totp.get_url())
+    }
+
+    /// Confirm TOTP setup with a code from the authenticator app
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn confirm_totp(&self, user_id: Uuid, code: &str) -> Result<()> {
+        if !self.verify_totp(user_id, code).await? {
+            return Err(Error::Validation("Invalid TOTP code".to_string()));
+        }
+
+        let user = self.get(user_id).await?;
+        self.repo.update_totp(user_id, user.totp_secret, true).await
+    }
+
+    /// Check a TOTP code for a user
+    ///
+    /// Accepts the current time step ±1. A step that was already used is
+    /// rejected so that an intercepted code cannot be replayed.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn verify_totp(&self, user_id: Uuid, code: &str) -> Result<bool> {
+        self.verify_totp_at(user_id, code, Utc::now().timestamp() as u64)
+            .await
     }
 
     /// Get user by ID
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %id))]
     pub async fn get(&self, id: Uuid) -> Result<User> {
-        self.repo
-            .find_by_id(id)
-            .await?
-            .ok_or_else(|| Error::NotFound(format!("User with id {} not found", id)))
+        self.repo.find_by_id(id).await?.ok_or_else(|| {
+            Error::coded(
+                ErrorCode::UserNotFound,
+                format!("User with id {} not found", id),
+            )
+        })
+    }
+
+    /// The user's own profile with post and comment counts
+    ///
+    /// Both counts are queried concurrently.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn get_profile(&self, user_id: Uuid) -> Result<UserProfile> {
+        let (post_repo, comment_repo) = (self.post_repo()?, self.comment_repo()?);
+        let user = self.get(user_id).await?;
+
+        let (post_count, comment_count) = tokio::try_join!(
+            post_repo.count_posts_by_user(user_id),
+            comment_repo.count_comments_by_user(user_id),
+        )?;
+
+        Ok(UserProfile {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            bio: user.bio,
+            avatar_url: user.avatar_url,
+            permissions: user.permissions,
+            permission_names: domain::permission_names(user.permissions)
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            created_at: user.created_at,
+            post_count,
+            comment_count,
+        })
     }
 
     /// Get user by username
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_by_username(&self, username: &str) -> Result<User> {
-        self.repo
-            .find_by_username(username)
-            .await?
-            .ok_or_else(|| Error::NotFound(format!("User '{}' not found", username)))
+        self.repo.find_by_username(username).await?.ok_or_else(|| {
+            Error::coded(
+                ErrorCode::UserNotFound,
+                format!("User '{}' not found", username),
+            )
+        })
     }
 
     /// Update user permissions (admin only)
     ///
     /// Only users with USER_MANAGE permission can call this.
+    #[tracing::instrument(level = "debug", skip_all, fields(%requester_id, %target_user_id))]
     pub async fn update_permissions(
         &self,
         requester_id: Uuid,
@@ -104,35 +548,69 @@ impl UserService {
         target_user_id: Uuid,
         new_permissions: u64,
     ) -> Result<User> {
-        // Check if requester has admin permission
+        Self::check_can_manage_users(requester_permissions)?;
+
+        let target_user = self
+            .repo
+            .find_by_id(target_user_id)
+            .await?
+            .ok_or_else(|| Error::coded(ErrorCode::UserNotFound, "Target user not found"))?;
+        self.check_permissions_change(requester_id, &target_user, new_permissions)
+            .await?;
+
+        let user = self
+            .repo
+            .update_permissions(target_user_id, new_permissions)
+            .await?;
+        tracing::info!(
+            "Permissions of user {} set to {} by user {}",
+            target_user_id,
+            new_permissions,
+            requester_id
+        );
+        Ok(user)
+    }
+
+    /// Check that the requester may manage users at all
+    fn check_can_manage_users(requester_permissions: u64) -> Result<()> {
         if (requester_permissions & USER_MANAGE) == 0 {
             return Err(Error::Validation(
                 "Insufficient permissions to manage users".to_string(),
             ));
         }
+        Ok(())
+    }
 
-        // Get requester and target user
-        let _requester = self
+    /// Check that a requester may set `new_permissions` on `target`
+    ///
+    /// Refuses to remove admin rights from the requester themselves or from
+    /// the last remaining admin. Only a SUPER_ADMIN may grant or revoke
+    /// SUPER_ADMIN.
+    async fn check_permissions_change(
+        &self,
+        requester_id: Uuid,
+        target: &User,
+        new_permissions: u64,
+    ) -> Result<()> {
+        let requester = self
             .repo
             .find_by_id(requester_id)
             .await?
-            .ok_or_else(|| Error::NotFound("Requester not found".to_string()))?;
+            .ok_or_else(|| Error::coded(ErrorCode::UserNotFound, "Requester not found"))?;
 
-        let target_user = self
-            .repo
-            .find_by_id(target_user_id)
-            .await?
-            .ok_or_else(|| Error::NotFound("Target user not found".to_string()))?;
+        if (target.permissions ^ new_permissions) & SUPER_ADMIN != 0 {
+            domain::check_permission(requester.permissions, SUPER_ADMIN)?;
+        }
 
         // Prevent users from removing their own admin privileges
-        if requester_id == target_user_id && (new_permissions & USER_MANAGE) == 0 {
+        if requester_id == target.id && (new_permissions & USER_MANAGE) == 0 {
             return Err(Error::Validation(
                 "Cannot remove your own admin privileges".to_string(),
             ));
         }
 
         // Prevent removing permissions from the last admin
-        if target_user.has_permission(USER_MANAGE) && (new_permissions & USER_MANAGE) == 0 {
+        if target.has_permission(USER_MANAGE) && (new_permissions & USER_MANAGE) == 0 {
             let admin_count = self
                 .repo
                 .list_users(ADMIN_COUNT_CHECK_LIMIT)
@@ -147,15 +625,239 @@ impl UserService {
                 ));
             }
         }
+        Ok(())
+    }
+
+    /// Update a user's username, permissions and profile in one write
+    ///
+    /// Every field is validated before anything is stored, so a rejected
+    /// field leaves the user untouched. Account fields need ownership or
+    /// USER_MANAGE; permissions follow the rules of `update_permissions`.
+    ///
+    /// # Returns
+    /// * `Ok(User)` - The updated user
+    /// * `Err(Error::NotFound)` - User does not exist
+    /// * `Err(Error::Validation)` - Permission denied or invalid field
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id, %requester_id))]
+    pub async fn update_user(
+        &self,
+        user_id: Uuid,
+        update: UpdateUser,
+        requester_id: Uuid,
+        requester_permissions: u64,
+    ) -> Result<User> {
+        if update.is_empty() {
+            return Err(Error::Validation("No valid fields to update".to_string()));
+        }
+        if update.permissions.is_some() {
+            Self::check_can_manage_users(requester_permissions)?;
+        }
+        if update.changes_account() {
+            domain::check_ownership_or_admin(
+                user_id,
+                requester_id,
+                requester_permissions,
+                USER_MANAGE,
+            )?;
+        }
+        if let Some(username) = &update.username {
+            self.validate_new_username(username)?;
+        }
+        let (bio, avatar_url) = Self::normalize_profile(update.bio, update.avatar_url)?;
+
+        let user = self.get(user_id).await?;
+        if let Some(permissions) = update.permissions {
+            self.check_permissions_change(requester_id, &user, permissions)
+                .await?;
+        }
+
+        let username = match update.username {
+            Some(username) if username != user.username => {
+                if self.repo.find_by_username(&username).await?.is_some() {
+                    return Err(
+                        Error::coded(ErrorCode::UsernameExists, "Username already exists")
+                            .with_field("username"),
+                    );
+                }
+                Some(username)
+            }
+            _ => None,
+        };
+        let non_empty = |value: String| (!value.is_empty()).then_some(value);
+        let changes = UserChanges {
+            username,
+            permissions: update.permissions,
+            bio: bio.map(non_empty),
+            avatar_url: avatar_url.map(non_empty),
+        };
+
+        let updated = self.repo.update_user(user_id, changes).await?;
+        tracing::info!("User {} updated by user {}", user_id, requester_id);
+        Ok(updated)
+    }
+
+    /// Change a user's username (self or admin)
+    ///
+    /// The new username must pass the registration rules and not be taken
+    /// by another user. The change is recorded in the user audit log.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id, %requester_id))]
+    pub async fn update_username(
+        &self,
+        user_id: Uuid,
+        new_username: String,
+        requester_id: Uuid,
+        requester_permissions: u64,
+    ) -> Result<User> {
+        domain::check_ownership_or_admin(
+            user_id,
+            requester_id,
+            requester_permissions,
+            USER_MANAGE,
+        )?;
+
+        self.validate_new_username(&new_username)?;
+
+        let user = self.get(user_id).await?;
+        if user.username == new_username {
+            return Ok(user);
+        }
+
+        if self.repo.find_by_username(&new_username).await?.is_some() {
+            return Err(
+                Error::coded(ErrorCode::UsernameExists, "Username already exists")
+                    .with_field("username"),
+            );
+        }
+
+        self.repo.update_username(user_id, new_username).await
+    }
+
+    /// Update a user's bio and avatar URL (self or admin)
+    ///
+    /// A field left as `None` keeps its current value; an empty string
+    /// clears it. The bio may be at most 500 characters and the avatar must
+    /// be an HTTPS URL.
+    ///
+    /// # Returns
+    /// * `Ok(User)` - The updated user
+    /// * `Err(Error::NotFound)` - User does not exist
+    /// * `Err(Error::Validation)` - Permission denied or invalid field
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id, %requester_id))]
+    pub async fn update_profile(
+        &self,
+        user_id: Uuid,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+        requester_id: Uuid,
+        requester_permissions: u64,
+    ) -> Result<User> {
+        domain::check_ownership_or_admin(
+            user_id,
+            requester_id,
+            requester_permissions,
+            USER_MANAGE,
+        )?;
+
+        let (bio, avatar_url) = Self::normalize_profile(bio, avatar_url)?;
+
+        let user = self.get(user_id).await?;
+        let bio = match bio {
+            Some(bio) if bio.is_empty() => None,
+            Some(bio) => Some(bio),
+            None => user.bio,
+        };
+        let avatar_url = match avatar_url {
+            Some(url) if url.is_empty() => None,
+            Some(url) => Some(url),
+            None => user.avatar_url,
+        };
+
+        self.repo.update_profile(user_id, bio, avatar_url).await
+    }
+
+    /// Trim and validate a bio and avatar URL; empty strings are kept
+    fn normalize_profile(
+        bio: Option<String>,
+        avatar_url: Option<String>,
+    ) -> Result<(Option<String>, Option<String>)> {
+        let bio = bio.map(|bio| bio.trim().to_string());
+        if let Some(bio) = &bio {
+            if bio.chars().count() > MAX_BIO_LENGTH {
+                return Err(Error::Validation(format!(
+                    "Bio must be at most {} characters",
+                    MAX_BIO_LENGTH
+                )));
+            }
+        }
+
+        let avatar_url = avatar_url.map(|url| url.trim().to_string());
+        if let Some(url) = avatar_url.as_deref().filter(|url| !url.is_empty()) {
+            Self::validate_avatar_url(url)?;
+        }
+        Ok((bio, avatar_url))
+    }
+
+    /// Check that an avatar URL is an absolute HTTPS URL with a host
+    fn validate_avatar_url(url: &str) -> Result<()> {
+        if url.len() > MAX_AVATAR_URL_LENGTH {
+            return Err(Error::Validation(format!(
+                "Avatar URL must be at most {} characters",
+                MAX_AVATAR_URL_LENGTH
+            )));
+        }
+
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|_| Error::Validation("Avatar URL is not a valid URL".to_string()))?;
+        if parsed.scheme() != "https" || parsed.host_str().is_none() {
+            return Err(Error::Validation(
+                "Avatar URL must be an https:// URL".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Look up the user an admin wants to impersonate
+    ///
+    /// Requires USER_MANAGE; impersonating another admin also requires
+    /// SUPER_ADMIN. The impersonation is recorded in the target's audit log
+    /// with the admin's ID.
+    ///
+    /// # Returns
+    /// * `Ok(User)` - The user to issue an impersonation token for
+    /// * `Err(Error::NotFound)` - Target user does not exist
+    /// * `Err(Error::Validation)` - Permission denied or self-impersonation
+    #[tracing::instrument(level = "debug", skip_all, fields(%target_id, %admin_id))]
+    pub async fn impersonate(
+        &self,
+        target_id: Uuid,
+        admin_id: Uuid,
+        admin_permissions: u64,
+    ) -> Result<User> {
+        domain::check_permission(admin_permissions, USER_MANAGE)?;
+        if target_id == admin_id {
+            return Err(Error::Validation("Cannot impersonate yourself".to_string()));
+        }
+
+        let target = self.get(target_id).await?;
+        if target.permissions & USER_MANAGE != 0 {
+            domain::check_permission(admin_permissions, SUPER_ADMIN)?;
+        }
 
         self.repo
-            .update_permissions(target_user_id, new_permissions)
-            .await
+            .add_audit_log(
+                target_id,
+                "impersonated",
+                Some(format!("by admin {}", admin_id)),
+            )
+            .await?;
+
+        Ok(target)
     }
 
     /// List all users (admin only)
     ///
     /// Only users with USER_MANAGE permission can call this.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list(&self, requester_permissions: u64, limit: Option<u64>) -> Result<Vec<User>> {
         // Check if requester has admin permission
         if (requester_permissions & USER_MANAGE) == 0 {
@@ -169,7 +871,53 @@ impl UserService {
             .await
     }
 
+    /// Search users by username (admin only)
+    ///
+    /// # Arguments
+    /// * `query` - Text to look for; matched as a substring of the username
+    /// * `requester_permissions` - Permissions of the caller
+    /// * `limit` - Page size, clamped to [`MAX_USER_SEARCH_LIMIT`]
+    /// * `offset` - Number of matches to skip
+    ///
+    /// # Returns
+    /// * `Ok(PaginatedUsers)` - Matches by username, empty when nobody matches
+    /// * `Err(Error::Validation)` - Caller lacks USER_MANAGE, or the query is
+    ///   blank or longer than 50 characters
+    /// * `Err(Error)` - Database error
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn search(
+        &self,
+        query: &str,
+        requester_permissions: u64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<PaginatedUsers> {
+        domain::check_permission(requester_permissions, USER_MANAGE)?;
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(Error::Validation(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+        if query.chars().count() > MAX_USER_SEARCH_QUERY_LENGTH {
+            return Err(Error::Validation(format!(
+                "Search query cannot exceed {} characters",
+                MAX_USER_SEARCH_QUERY_LENGTH
+            )));
+        }
+
+        let limit = limit.clamp(1, MAX_USER_SEARCH_LIMIT);
+        let (users, total) = self.repo.search_users(query, limit, offset).await?;
+
+        Ok(PaginatedUsers {
+            items: users.iter().map(UserInfo::from).collect(),
+            total,
+        })
+    }
+
     /// Check if user exists by username
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn exists(&self, username: &str) -> bool {
         match self.repo.find_by_username(username).await {
             Ok(user_opt) => user_opt.is_some(),
@@ -178,6 +926,7 @@ impl UserService {
     }
 
     /// Check if user exists by ID
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %id))]
     pub async fn exists_by_id(&self, id: Uuid) -> bool {
         match self.repo.find_by_id(id).await {
             Ok(user_opt) => user_opt.is_some(),
@@ -189,6 +938,7 @@ impl UserService {
     ///
     /// Users can delete their own account. Admins can delete any account
     /// except the last admin account.
+    #[tracing::instrument(level = "debug", skip_all, fields(%target_user_id, %requester_id))]
     pub async fn delete(
         &self,
         target_user_id: Uuid,
@@ -200,6 +950,11 @@ impl UserService {
         let is_admin = (requester_permissions & USER_MANAGE) != 0;
 
         if !is_self && !is_admin {
+            tracing::warn!(
+                "User {} denied deleting user {}",
+                requester_id,
+                target_user_id
+            );
             return Err(Error::Unauthorized(
                 "You can only delete your own account".to_string(),
             ));
@@ -210,7 +965,7 @@ impl UserService {
             .repo
             .find_by_id(target_user_id)
             .await?
-            .ok_or_else(|| Error::NotFound("User not found".to_string()))?;
+            .ok_or_else(|| Error::coded(ErrorCode::UserNotFound, "User not found"))?;
 
         // If deleting an admin, check that this is not the last admin
         let is_target_admin = (target_user.permissions & USER_MANAGE) != 0;
@@ -230,44 +985,523 @@ impl UserService {
         }
 
         // Delete the user
-        self.repo.delete_user(target_user_id).await
+        self.repo.delete_user(target_user_id).await?;
+        tracing::info!("User {} deleted by user {}", target_user_id, requester_id);
+        Ok(())
     }
 }
 
 // ============================================================================
-// Private Validation Helpers
+// API Keys
 // ============================================================================
 
 impl UserService {
-    fn validate_username(&self, username: &str) -> Result<()> {
-        if username.trim().is_empty() {
-            return Err(Error::Validation("Username cannot be empty".to_string()));
-        }
-        if username.len() < 3 {
-            return Err(Error::Validation(
-                "Username must be at least 3 characters".to_string(),
-            ));
+    /// Issue a new API key for a user
+    ///
+    /// Keys without scopes are read-only. The plaintext key is returned once
+    /// and only its SHA-256 hash is stored.
+    ///
+    /// # Returns
+    /// * `Ok((String, ApiKey))` - The plaintext key and its stored metadata
+    /// * `Err(Error::Validation)` - Empty or overlong label, or unknown scope
+    /// * `Err(Error::NotFound)` - User not found
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn create_api_key(
+        &self,
+        user_id: Uuid,
+        label: String,
+        scopes: Vec<String>,
+    ) -> Result<(String, ApiKey)> {
+        let label = label.trim().to_string();
+        if label.is_empty() || label.len() > MAX_API_KEY_LABEL_LENGTH {
+            return Err(Error::Validation(format!(
+                "Label must be between 1 and {} characters",
+                MAX_API_KEY_LABEL_LENGTH
+            )));
         }
-        if username.len() > 30 {
-            return Err(Error::Validation(
-                "Username too long (max 30 characters)".to_string(),
-            ));
+        if let Some(unknown) = scopes
+            .iter()
+            .find(|s| !API_KEY_SCOPES.contains(&s.as_str()))
+        {
+            return Err(Error::Validation(format!("Unknown scope: {}", unknown)));
         }
+        let mut scopes = if scopes.is_empty() {
+            vec![SCOPE_READ_POSTS.to_string()]
+        } else {
+            scopes
+        };
+        scopes.sort();
+        scopes.dedup();
+
+        self.get(user_id).await?;
+
+        let mut bytes = [0u8; API_KEY_BYTES];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let key = format!("{}{}", API_KEY_PREFIX, hex::encode(bytes));
+
+        let api_key = self
+            .api_key_repo()?
+            .create_api_key(ApiKey {
+                key_hash: hash_api_key(&key),
+                user_id,
+                label,
+                scopes,
+                created_at: Utc::now(),
+                last_used_at: None,
+            })
+            .await?;
+
+        Ok((key, api_key))
+    }
 
-        // Check for invalid characters (alphanumeric and underscore only)
-        if !username.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return Err(Error::Validation(
-                "Username can only contain letters, numbers, and underscores".to_string(),
-            ));
-        }
+    /// List a user's API keys without their plaintext
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn list_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>> {
+        self.api_key_repo()?.list_user_api_keys(user_id).await
+    }
 
-        Ok(())
+    /// Revoke one of a user's API keys
+    ///
+    /// # Returns
+    /// * `Ok(())` - Key deleted
+    /// * `Err(Error::NotFound)` - The user has no key with this hash
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn revoke_api_key(&self, user_id: Uuid, key_hash: &str) -> Result<()> {
+        if self
+            .api_key_repo()?
+            .delete_api_key(user_id, key_hash)
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(Error::NotFound("API key not found".to_string()))
+        }
     }
 
-    fn validate_password(&self, password: &str) -> Result<()> {
-        if password.len() < 8 {
-            return Err(Error::Validation(
-                "Password must be at least 8 characters".to_string(),
+    /// Replace an API key with a new one carrying the same label and scopes
+    ///
+    /// The old key stops working immediately.
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn rotate_api_key(&self, user_id: Uuid, key_hash: &str) -> Result<(String, ApiKey)> {
+        let old = self
+            .api_key_repo()?
+            .get_api_key(key_hash)
+            .await?
+            .filter(|key| key.user_id == user_id)
+            .ok_or_else(|| Error::NotFound("API key not found".to_string()))?;
+
+        let rotated = self.create_api_key(user_id, old.label, old.scopes).await?;
+        self.revoke_api_key(user_id, key_hash).await?;
+        Ok(rotated)
+    }
+
+    /// Resolve a plaintext API key to its owner and record the use
+    ///
+    /// # Returns
+    /// * `Ok((User, ApiKey))` - The key's owner and metadata
+    /// * `Err(Error::Unauthorized)` - Unknown key or deleted owner
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn authenticate_api_key(&self, key: &str) -> Result<(User, ApiKey)> {
+        let repo = self.api_key_repo()?;
+        let api_key = repo
+            .get_api_key(&hash_api_key(key))
+            .await?
+            .ok_or_else(|| Error::Unauthorized("Invalid API key".to_string()))?;
+        let user = self
+            .repo
+            .find_by_id(api_key.user_id)
+            .await?
+            .ok_or_else(|| Error::Unauthorized("Invalid API key".to_string()))?;
+
+        repo.touch_api_key(&api_key.key_hash, Utc::now()).await?;
+        Ok((user, api_key))
+    }
+
+    /// Set a new email address and send it a verification link
+    ///
+    /// The address stays unverified until the link is opened, and is not
+    /// used for password resets before that.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Address stored and verification email sent
+    /// * `Err(Error::Validation)` - Malformed address
+    /// * `Err(Error::Conflict)` - Address belongs to another user
+    /// * `Err(Error::Internal)` - Email is not configured or delivery failed
+    #[tracing::instrument(level = "debug", skip_all, fields(%user_id))]
+    pub async fn update_email(&self, user_id: Uuid, new_email: String) -> Result<()> {
+        let email = new_email.trim().to_lowercase();
+        self.validate_email(&email)?;
+        let email_client = self.email_client()?;
+
+        if let Some(owner) = self.repo.find_by_email(&email).await? {
+            if owner.id != user_id {
+                return Err(Error::Conflict("Email is already in use".to_string()));
+            }
+        }
+
+        let token = Uuid::new_v4().to_string();
+        self.repo
+            .update_email(user_id, email.clone(), token.clone())
+            .await?;
+
+        let link = format!("{}/api/auth/verify-email?token={}", self.base_url, token);
+        email_client
+            .send_verification(&email, &link)
+            .await
+            .map_err(Error::Internal)
+    }
+
+    /// Confirm the email address a verification token was sent to
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn verify_email(&self, token: &str) -> Result<User> {
+        let user = self
+            .repo
+            .verify_email(token)
+            .await?
+            .ok_or_else(|| Error::Validation("Invalid verification token".to_string()))?;
+
+        tracing::info!("Email verified for user {}", user.id);
+        Ok(user)
+    }
+
+    /// Email a password reset link valid for [`PASSWORD_RESET_TTL_MINUTES`]
+    ///
+    /// Unknown and unverified addresses succeed silently, so the endpoint
+    /// cannot be used to discover which addresses have accounts.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn request_password_reset(&self, email: &str) -> Result<()> {
+        let email_client = self.email_client()?;
+        let email = email.trim().to_lowercase();
+
+        let Some(user) = self.repo.find_by_email(&email).await? else {
+            return Ok(());
+        };
+        if !user.email_verified {
+            tracing::debug!("Ignoring reset request for unverified email of {}", user.id);
+            return Ok(());
+        }
+
+        let token = Uuid::new_v4().to_string();
+        let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES);
+        self.repo
+            .set_password_reset_token(user.id, Some(token.clone()), Some(expires_at))
+            .await?;
+
+        let link = format!("{}/reset-password?token={}", self.base_url, token);
+        email_client
+            .send_password_reset(&email, &link)
+            .await
+            .map_err(Error::Internal)
+    }
+
+    /// Set a new password using a token from a reset email
+    ///
+    /// The token can only be used once. Every session of the user is ended,
+    /// along with its refresh token, so whoever held the old password loses
+    /// access.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn reset_password(&self, token: &str, new_password: String) -> Result<()> {
+        self.validate_password(&new_password)?;
+
+        let invalid = || Error::Validation("Invalid or expired reset token".to_string());
+        let user = self
+            .repo
+            .find_by_password_reset_token(token)
+            .await?
+            .ok_or_else(invalid)?;
+
+        self.repo
+            .set_password_reset_token(user.id, None, None)
+            .await?;
+        if user
+            .password_reset_expires_at
+            .is_none_or(|expires_at| expires_at < Utc::now())
+        {
+            return Err(invalid());
+        }
+
+        self.repo.update_password(user.id, new_password).await?;
+        let ended = self
+            .session_repo()?
+            .delete_user_sessions(user.id, None)
+            .await?;
+        tracing::info!(
+            "Password reset for user {}, {} sessions ended",
+            user.id,
+            ended
+        );
+        Ok(())
+    }
+
+    fn email_client(&self) -> Result<Arc<EmailClient>> {
+        self.clients
+            .as_ref()
+            .and_then(|clients| clients.read().unwrap().email.clone())
+            .ok_or_else(|| internal_error("Email is not configured"))
+    }
+
+    fn api_key_repo(&self) -> Result<&Arc<dyn ApiKeyRepository>> {
+        self.api_key_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("API keys are not configured"))
+    }
+
+    fn invite_repo(&self) -> Result<&Arc<dyn InviteRepository>> {
+        self.invite_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Invite codes are not configured"))
+    }
+
+    fn session_repo(&self) -> Result<&Arc<dyn SessionRepository>> {
+        self.session_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Sessions are not configured"))
+    }
+
+    fn post_repo(&self) -> Result<&Arc<dyn PostRepository>> {
+        self.post_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Post counts are not configured"))
+    }
+
+    fn comment_repo(&self) -> Result<&Arc<dyn CommentRepository>> {
+        self.comment_repo
+            .as_ref()
+            .ok_or_else(|| internal_error("Comment counts are not configured"))
+    }
+}
+
+/// Parse a bulk import CSV with a `username,password,admin` header row
+///
+/// `admin` is `true` or `false` and may be left empty for a regular user.
+/// Fails with `Error::Validation` naming the line of the first malformed row.
+pub fn parse_users_csv(data: &str) -> Result<Vec<NewUserEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    reader
+        .deserialize::<CsvUserRow>()
+        .map(|row| {
+            let row = row.map_err(|e| {
+                let line = e.position().map_or(0, |p| p.line());
+                Error::Validation(format!("Invalid users CSV on line {}: {}", line, e))
+            })?;
+            Ok(NewUserEntry {
+                username: row.username,
+                password: row.password,
+                admin: row.admin.unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+/// Row of a bulk import CSV; an empty `admin` column reads as `None`
+#[derive(Debug, serde::Deserialize)]
+struct CsvUserRow {
+    username: String,
+    password: String,
+    admin: Option<bool>,
+}
+
+/// Hex-encoded SHA-256 of a plaintext API key
+fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+// ============================================================================
+// Private TOTP Helpers
+// ============================================================================
+
+impl UserService {
+    fn build_totp(secret: &str, username: &str) -> Result<TOTP> {
+        let secret = Secret::Encoded(secret.to_string())
+            .to_bytes()
+            .map_err(|e| internal_error(format!("Invalid TOTP secret: {:?}", e)))?;
+
+        TOTP::new(
+            Algorithm::SHA1,
+            6,
+            1,
+            TOTP_STEP_SECONDS,
+            secret,
+            Some(TOTP_ISSUER.to_string()),
+            username.to_string(),
+        )
+        .map_err(|e| internal_error(format!("Failed to build TOTP: {}", e)))
+    }
+
+    /// Encrypt a base32 TOTP secret for storage
+    fn seal_totp_secret(&self, secret: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .totp_cipher
+            .encrypt(&nonce, secret.as_bytes())
+            .map_err(|e| internal_error(format!("Failed to encrypt TOTP secret: {}", e)))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", TOTP_SECRET_PREFIX, hex::encode(sealed)))
+    }
+
+    /// Decrypt a stored TOTP secret
+    ///
+    /// Secrets saved before encryption was introduced have no prefix and
+    /// are returned as they are.
+    fn open_totp_secret(&self, stored: &str) -> Result<String> {
+        let Some(encoded) = stored.strip_prefix(TOTP_SECRET_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+
+        let sealed = hex::decode(encoded)
+            .map_err(|e| internal_error(format!("Invalid TOTP secret encoding: {}", e)))?;
+        if sealed.len() <= TOTP_NONCE_LENGTH {
+            return Err(internal_error("Stored TOTP secret is truncated"));
+        }
+        let (nonce, ciphertext) = sealed.split_at(TOTP_NONCE_LENGTH);
+        let secret = self
+            .totp_cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|e| internal_error(format!("Failed to decrypt TOTP secret: {}", e)))?;
+        String::from_utf8(secret).map_err(|e| internal_error(format!("Invalid TOTP secret: {}", e)))
+    }
+
+    async fn verify_totp_at(&self, user_id: Uuid, code: &str, now: u64) -> Result<bool> {
+        let user = self.get(user_id).await?;
+        let stored = user.totp_secret.as_deref().ok_or_else(|| {
+            Error::Validation("Two-factor authentication is not set up".to_string())
+        })?;
+        let secret = self.open_totp_secret(stored)?;
+        let totp = Self::build_totp(&secret, &user.username)?;
+
+        let current_step = now / TOTP_STEP_SECONDS;
+        let matched_step = [
+            current_step.saturating_sub(1),
+            current_step,
+            current_step + 1,
+        ]
+        .into_iter()
+        .find(|step| totp.generate(step * TOTP_STEP_SECONDS) == code.trim());
+
+        let Some(step) = matched_step else {
+            return Ok(false);
+        };
+
+        if user.totp_last_step.is_some_and(|last| step <= last) {
+            return Ok(false);
+        }
+
+        // A concurrent login may have recorded this step since the read above
+        self.repo.update_totp_last_step(user_id, step).await
+    }
+
+    fn issue_partial_token(&self, user_id: Uuid) -> String {
+        let token = Uuid::new_v4().simple().to_string();
+        let now = Utc::now();
+
+        let mut pending = self.pending_otp.lock().unwrap();
+        pending.retain(|_, entry| entry.expires_at > now);
+        pending.insert(
+            token.clone(),
+            PendingOtp {
+                user_id,
+                expires_at: now + Duration::minutes(PARTIAL_TOKEN_TTL_MINUTES),
+                attempts: 0,
+            },
+        );
+
+        token
+    }
+}
+
+// ============================================================================
+// Private Validation Helpers
+// ============================================================================
+
+impl UserService {
+    fn validate_username(&self, username: &str) -> Result<()> {
+        if username.trim().is_empty() {
+            return Err(Error::invalid_field("username", "Username cannot be empty"));
+        }
+        if username.len() < 3 {
+            return Err(Error::invalid_field(
+                "username",
+                "Username must be at least 3 characters",
+            ));
+        }
+        if username.len() > 30 {
+            return Err(Error::invalid_field(
+                "username",
+                "Username too long (max 30 characters)",
+            ));
+        }
+
+        // Check for invalid characters (alphanumeric and underscore only)
+        if !username.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(Error::invalid_field(
+                "username",
+                "Username can only contain letters, numbers, and underscores",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate a username that is about to be taken by an account
+    ///
+    /// On top of [`Self::validate_username`], rejects the configured reserved
+    /// names and anything that parses as a UUID, which would be confused with
+    /// ID-based routes. Login skips this so existing accounts keep working.
+    fn validate_new_username(&self, username: &str) -> Result<()> {
+        let normalized = username.trim().to_lowercase();
+        let reserved = self
+            .config
+            .read()
+            .unwrap()
+            .auth
+            .reserved_usernames
+            .iter()
+            .any(|name| name.trim().to_lowercase() == normalized);
+        if reserved {
+            return Err(Error::invalid_field("username", "Username is reserved"));
+        }
+        if Uuid::parse_str(&normalized).is_ok() {
+            return Err(Error::invalid_field(
+                "username",
+                "Username cannot be a UUID",
+            ));
+        }
+
+        self.validate_username(username)
+    }
+
+    /// Basic RFC 5322 shape check: one `@`, a dotted domain, no whitespace
+    fn validate_email(&self, email: &str) -> Result<()> {
+        let invalid = || Error::invalid_field("email", "Invalid email address");
+        if email.len() > MAX_EMAIL_LENGTH || email.chars().any(char::is_whitespace) {
+            return Err(invalid());
+        }
+
+        let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+        let valid_domain = domain.contains('.')
+            && domain
+                .split('.')
+                .all(|label| !label.is_empty() && !label.starts_with('-') && !label.ends_with('-'))
+            && domain
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '.' || c == '-');
+        if local.is_empty() || local.len() > 64 || local.contains('@') || !valid_domain {
+            return Err(invalid());
+        }
+
+        Ok(())
+    }
+
+    fn validate_password(&self, password: &str) -> Result<()> {
+        if password.len() < 8 {
+            return Err(Error::invalid_field(
+                "password",
+                "Password must be at least 8 characters",
             ));
         }
 
@@ -276,8 +1510,9 @@ impl UserService {
         let has_number = password.chars().any(|c| c.is_numeric());
 
         if !has_letter || !has_number {
-            return Err(Error::Validation(
-                "Password must contain at least one letter and one number".to_string(),
+            return Err(Error::invalid_field(
+                "password",
+                "Password must contain at least one letter and one number",
             ));
         }
 
@@ -285,6 +1520,20 @@ impl UserService {
     }
 }
 
+/// Cipher for TOTP secrets, keyed from the JWT secret the server started with
+///
+/// The key is fixed for the life of the service, like the JWT signing key,
+/// so changing `auth.jwt_secret` through the config API does not lock users
+/// out. Rotating it with a restart makes stored TOTP secrets unreadable, so
+/// users must set up TOTP again afterwards.
+fn totp_cipher(jwt_secret: &str) -> Aes256Gcm {
+    let key = Sha256::new()
+        .chain_update(b"peng-blog totp secret")
+        .chain_update(jwt_secret.as_bytes())
+        .finalize();
+    Aes256Gcm::new(&key)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -292,6 +1541,9 @@ impl UserService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{
+        shared_config, test_config, MockApiKeyRepo, MockInviteRepo, MockSessionRepo, MockSmtpServer,
+    };
     use async_trait::async_trait;
     use domain::{ADMIN_PERMISSIONS, DEFAULT_USER_PERMISSIONS};
     use mockall::mock;
@@ -307,14 +1559,26 @@ mod tests {
             async fn verify_credentials(&self, username: &str, password: &str) -> Result<Option<User>>;
             async fn update_permissions(&self, user_id: Uuid, permissions: u64) -> Result<User>;
             async fn update_password(&self, user_id: Uuid, new_password: String) -> Result<()>;
+            async fn update_username(&self, user_id: Uuid, new_username: String) -> Result<User>;
+            async fn update_profile(&self, user_id: Uuid, bio: Option<String>, avatar_url: Option<String>) -> Result<User>;
+            async fn update_user(&self, user_id: Uuid, changes: domain::UserChanges) -> Result<User>;
+            async fn add_audit_log(&self, user_id: Uuid, action: &str, detail: Option<String>) -> Result<()>;
+            async fn update_totp(&self, user_id: Uuid, secret: Option<String>, enabled: bool) -> Result<()>;
+            async fn update_totp_last_step(&self, user_id: Uuid, step: u64) -> Result<bool>;
+            async fn find_by_email(&self, email: &str) -> Result<Option<User>>;
+            async fn update_email(&self, user_id: Uuid, email: String, verification_token: String) -> Result<()>;
+            async fn verify_email(&self, token: &str) -> Result<Option<User>>;
+            async fn set_password_reset_token(&self, user_id: Uuid, token: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<()>;
+            async fn find_by_password_reset_token(&self, token: &str) -> Result<Option<User>>;
             async fn list_users(&self, limit: u64) -> Result<Vec<User>>;
+            async fn search_users(&self, query: &str, limit: u64, offset: u64) -> Result<(Vec<User>, u64)>;
             async fn delete_user(&self, user_id: Uuid) -> Result<()>;
         }
     }
 
     fn setup_service() -> UserService {
         let mock_repo = Arc::new(MockUserRepo::new());
-        UserService::new(mock_repo, true)
+        UserService::new(mock_repo, shared_config(test_config()))
     }
 
     #[tokio::test]
@@ -322,12 +1586,15 @@ mod tests {
         let service = setup_service();
 
         let result = service
-            .register("".to_string(), "password123".to_string())
+            .register("".to_string(), "password123".to_string(), None)
             .await;
 
         assert!(result.is_err());
         match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("cannot be empty")),
+            Err(e) => {
+                assert_eq!(e.field(), Some("username"));
+                assert!(e.message().contains("cannot be empty"));
+            }
             _ => panic!("Expected validation error"),
         }
     }
@@ -337,12 +1604,15 @@ mod tests {
         let service = setup_service();
 
         let result = service
-            .register("ab".to_string(), "password123".to_string())
+            .register("ab".to_string(), "password123".to_string(), None)
             .await;
 
         assert!(result.is_err());
         match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("at least 3 characters")),
+            Err(e) => {
+                assert_eq!(e.field(), Some("username"));
+                assert!(e.message().contains("at least 3 characters"));
+            }
             _ => panic!("Expected validation error"),
         }
     }
@@ -353,12 +1623,15 @@ mod tests {
 
         let long_username = "a".repeat(31);
         let result = service
-            .register(long_username, "password123".to_string())
+            .register(long_username, "password123".to_string(), None)
             .await;
 
         assert!(result.is_err());
         match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("too long")),
+            Err(e) => {
+                assert_eq!(e.field(), Some("username"));
+                assert!(e.message().contains("too long"));
+            }
             _ => panic!("Expected validation error"),
         }
     }
@@ -368,12 +1641,15 @@ mod tests {
         let service = setup_service();
 
         let result = service
-            .register("username".to_string(), "short".to_string())
+            .register("username".to_string(), "short".to_string(), None)
             .await;
 
         assert!(result.is_err());
         match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("at least 8 characters")),
+            Err(e) => {
+                assert_eq!(e.field(), Some("password"));
+                assert!(e.message().contains("at least 8 characters"));
+            }
             _ => panic!("Expected validation error"),
         }
     }
@@ -384,27 +1660,95 @@ mod tests {
 
         // Password without number
         let result = service
-            .register("username".to_string(), "password".to_string())
+            .register("username".to_string(), "password".to_string(), None)
             .await;
 
         assert!(result.is_err());
         match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("letter and one number")),
+            Err(e) => {
+                assert_eq!(e.field(), Some("password"));
+                assert!(e.message().contains("letter and one number"));
+            }
             _ => panic!("Expected validation error"),
         }
 
         // Password without letter
         let result = service
-            .register("username".to_string(), "12345678".to_string())
+            .register("username".to_string(), "12345678".to_string(), None)
             .await;
 
         assert!(result.is_err());
         match result {
-            Err(Error::Validation(msg)) => assert!(msg.contains("letter and one number")),
+            Err(e) => {
+                assert_eq!(e.field(), Some("password"));
+                assert!(e.message().contains("letter and one number"));
+            }
             _ => panic!("Expected validation error"),
         }
     }
 
+    #[tokio::test]
+    async fn test_register_rejects_reserved_usernames() {
+        let service = setup_service();
+
+        for username in ["admin", "Root", " api ", "SUPPORT", "help", "www", "mail"] {
+            match service
+                .register(username.to_string(), "password123".to_string(), None)
+                .await
+            {
+                Err(e) => assert_eq!(e.message(), "Username is reserved"),
+                other => panic!(
+                    "Expected reserved error for {:?}, got {:?}",
+                    username, other
+                ),
+            }
+        }
+
+        let id = Uuid::new_v4();
+        for username in [id.to_string(), id.simple().to_string().to_uppercase()] {
+            match service
+                .register(username.clone(), "password123".to_string(), None)
+                .await
+            {
+                Err(e) => assert!(e.message().contains("UUID")),
+                other => panic!("Expected UUID error for {:?}, got {:?}", username, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reserved_usernames_follow_config() {
+        let mut repo = MockUserRepo::new();
+        repo.expect_find_by_username().returning(|_| Ok(None));
+        repo.expect_list_users().returning(|_| Ok(vec![]));
+        repo.expect_create_user()
+            .returning(|username, _, permissions| {
+                Ok(User::new(
+                    Uuid::new_v4(),
+                    username,
+                    "hash".to_string(),
+                    permissions,
+                ))
+            });
+        let mut config = test_config();
+        config.auth.reserved_usernames = vec!["Staff".to_string()];
+        let service = UserService::new(Arc::new(repo), shared_config(config));
+
+        assert!(matches!(
+            service
+                .register("staff".to_string(), "password123".to_string(), None)
+                .await,
+            Err(e) if e.field() == Some("username") && e.message() == "Username is reserved"
+        ));
+        for username in ["admin", "administrator", "helpdesk"] {
+            let user = service
+                .register(username.to_string(), "password123".to_string(), None)
+                .await
+                .unwrap();
+            assert_eq!(user.username, username);
+        }
+    }
+
     #[tokio::test]
     async fn test_update_permissions_requires_admin() {
         let service = setup_service();
@@ -442,7 +1786,7 @@ mod tests {
                 )))
             });
 
-        let service = UserService::new(Arc::new(mock_repo), true);
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
 
         let no_admin = DEFAULT_USER_PERMISSIONS;
 
@@ -457,6 +1801,57 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_only_super_admin_can_grant_super_admin() {
+        let mut mock_repo = MockUserRepo::new();
+        let admin_id = Uuid::new_v4();
+        let super_admin_id = Uuid::new_v4();
+
+        mock_repo.expect_find_by_id().returning(move |id| {
+            let mut user = existing_user(id, "admin");
+            user.permissions = if id == super_admin_id {
+                ADMIN_PERMISSIONS | SUPER_ADMIN
+            } else {
+                ADMIN_PERMISSIONS
+            };
+            Ok(Some(user))
+        });
+        mock_repo
+            .expect_update_permissions()
+            .times(1)
+            .returning(|id, permissions| {
+                let mut user = existing_user(id, "admin");
+                user.permissions = permissions;
+                Ok(user)
+            });
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service
+            .update_permissions(
+                admin_id,
+                ADMIN_PERMISSIONS,
+                admin_id,
+                ADMIN_PERMISSIONS | SUPER_ADMIN,
+            )
+            .await;
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected granting SUPER_ADMIN to be denied"),
+        }
+
+        let granted = service
+            .update_permissions(
+                super_admin_id,
+                ADMIN_PERMISSIONS | SUPER_ADMIN,
+                admin_id,
+                ADMIN_PERMISSIONS | SUPER_ADMIN,
+            )
+            .await
+            .unwrap();
+        assert!(granted.has_permission(SUPER_ADMIN));
+    }
+
     #[tokio::test]
     async fn test_list_requires_admin() {
         let service = setup_service();
@@ -471,4 +1866,1217 @@ mod tests {
             _ => panic!("Expected permission error"),
         }
     }
+
+    fn existing_user(id: Uuid, username: &str) -> User {
+        User::new(
+            id,
+            username.to_string(),
+            "hash".to_string(),
+            DEFAULT_USER_PERMISSIONS,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_validates_fields() {
+        let mut mock_repo = MockUserRepo::new();
+        let user_id = Uuid::new_v4();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing_user(user_id, "alice"))));
+        mock_repo.expect_update_profile().never();
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let too_long = "x".repeat(MAX_BIO_LENGTH + 1);
+        let result = service
+            .update_profile(
+                user_id,
+                Some(too_long),
+                None,
+                user_id,
+                DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+        assert!(matches!(result, Err(Error::Validation(msg)) if msg.contains("Bio")));
+
+        for url in [
+            "http://example.com/a.png",
+            "not a url",
+            "javascript:alert(1)",
+            "ftp://example.com/a.png",
+        ] {
+            let result = service
+                .update_profile(
+                    user_id,
+                    None,
+                    Some(url.to_string()),
+                    user_id,
+                    DEFAULT_USER_PERMISSIONS,
+                )
+                .await;
+            assert!(
+                matches!(result, Err(Error::Validation(ref msg)) if msg.contains("Avatar URL")),
+                "{} should be rejected",
+                url
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_self_or_admin() {
+        let mut mock_repo = MockUserRepo::new();
+        let user_id = Uuid::new_v4();
+        mock_repo.expect_find_by_id().returning(move |_| {
+            let mut user = existing_user(user_id, "alice");
+            user.bio = Some("old bio".to_string());
+            Ok(Some(user))
+        });
+        mock_repo
+            .expect_update_profile()
+            .times(2)
+            .returning(move |_, bio, avatar_url| {
+                let mut user = existing_user(user_id, "alice");
+                user.bio = bio;
+                user.avatar_url = avatar_url;
+                Ok(user)
+            });
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+        let avatar = "https://cdn.example.com/alice.png".to_string();
+
+        let user = service
+            .update_profile(
+                user_id,
+                None,
+                Some(avatar.clone()),
+                user_id,
+                DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.bio.as_deref(), Some("old bio"));
+        assert_eq!(user.avatar_url, Some(avatar));
+
+        let result = service
+            .update_profile(
+                user_id,
+                Some("hi".to_string()),
+                None,
+                Uuid::new_v4(),
+                DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+
+        let user = service
+            .update_profile(
+                user_id,
+                Some(String::new()),
+                None,
+                Uuid::new_v4(),
+                ADMIN_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+        assert!(user.bio.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_username_rejects_duplicate() {
+        let mut mock_repo = MockUserRepo::new();
+        let user_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing_user(user_id, "alice"))));
+        mock_repo
+            .expect_find_by_username()
+            .with(mockall::predicate::eq("bob"))
+            .returning(|_| Ok(Some(existing_user(Uuid::new_v4(), "bob"))));
+        mock_repo.expect_update_username().never();
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service
+            .update_username(
+                user_id,
+                "bob".to_string(),
+                user_id,
+                DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::UsernameExists),
+            _ => panic!("Expected duplicate username error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_username_by_self() {
+        let mut mock_repo = MockUserRepo::new();
+        let user_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing_user(user_id, "alice"))));
+        mock_repo.expect_find_by_username().returning(|_| Ok(None));
+        mock_repo
+            .expect_update_username()
+            .times(1)
+            .returning(move |id, name| Ok(existing_user(id, &name)));
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let updated = service
+            .update_username(
+                user_id,
+                "alice_2".to_string(),
+                user_id,
+                DEFAULT_USER_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.username, "alice_2");
+    }
+
+    #[tokio::test]
+    async fn test_update_username_by_admin() {
+        let mut mock_repo = MockUserRepo::new();
+        let user_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing_user(user_id, "alice"))));
+        mock_repo.expect_find_by_username().returning(|_| Ok(None));
+        mock_repo
+            .expect_update_username()
+            .times(1)
+            .returning(move |id, name| Ok(existing_user(id, &name)));
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let updated = service
+            .update_username(user_id, "renamed".to_string(), admin_id, ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(updated.id, user_id);
+        assert_eq!(updated.username, "renamed");
+    }
+
+    #[tokio::test]
+    async fn test_update_username_rejects_other_non_admin() {
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo.expect_update_username().never();
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service
+            .update_username(
+                Uuid::new_v4(),
+                "renamed".to_string(),
+                Uuid::new_v4(),
+                DEFAULT_USER_PERMISSIONS,
+            )
+            .await;
+
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected permission error"),
+        }
+    }
+
+    const TEST_TOTP_SECRET: &str = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP";
+
+    fn totp_user(id: Uuid, last_step: Option<u64>) -> User {
+        let mut user = existing_user(id, "alice");
+        user.totp_secret = Some(TEST_TOTP_SECRET.to_string());
+        user.totp_enabled = true;
+        user.totp_last_step = last_step;
+        user
+    }
+
+    fn code_at(time: u64) -> String {
+        UserService::build_totp(TEST_TOTP_SECRET, "alice")
+            .unwrap()
+            .generate(time)
+    }
+
+    #[tokio::test]
+    async fn test_update_user_rejects_all_changes_on_duplicate_username() {
+        let mut mock_repo = MockUserRepo::new();
+        let admin_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(existing_user(id, "alice"))));
+        mock_repo
+            .expect_find_by_username()
+            .returning(|name| Ok(Some(existing_user(Uuid::new_v4(), name))));
+        mock_repo.expect_update_user().never();
+        mock_repo.expect_update_permissions().never();
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service
+            .update_user(
+                user_id,
+                UpdateUser {
+                    username: Some("bob".to_string()),
+                    permissions: Some(ADMIN_PERMISSIONS),
+                    ..Default::default()
+                },
+                admin_id,
+                ADMIN_PERMISSIONS,
+            )
+            .await;
+
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::UsernameExists),
+            _ => panic!("Expected duplicate username error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_user_applies_changes_in_one_write() {
+        let mut mock_repo = MockUserRepo::new();
+        let admin_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        mock_repo.expect_find_by_id().returning(move |id| {
+            let mut user = existing_user(id, "alice");
+            user.bio = Some("old bio".to_string());
+            Ok(Some(user))
+        });
+        mock_repo.expect_find_by_username().returning(|_| Ok(None));
+        mock_repo
+            .expect_update_user()
+            .times(1)
+            .withf(|_, changes| {
+                *changes
+                    == UserChanges {
+                        username: Some("renamed".to_string()),
+                        permissions: Some(ADMIN_PERMISSIONS),
+                        bio: Some(None),
+                        avatar_url: None,
+                    }
+            })
+            .returning(|id, changes| {
+                let mut user = existing_user(id, &changes.username.unwrap());
+                user.permissions = changes.permissions.unwrap();
+                Ok(user)
+            });
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let updated = service
+            .update_user(
+                user_id,
+                UpdateUser {
+                    username: Some("renamed".to_string()),
+                    permissions: Some(ADMIN_PERMISSIONS),
+                    bio: Some(String::new()),
+                    avatar_url: None,
+                },
+                admin_id,
+                ADMIN_PERMISSIONS,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.username, "renamed");
+        assert_eq!(updated.permissions, ADMIN_PERMISSIONS);
+    }
+    #[tokio::test]
+    async fn test_verify_totp_accepts_current_and_adjacent_steps() {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(totp_user(id, None))));
+        mock_repo
+            .expect_update_totp_last_step()
+            .times(2)
+            .returning(|_, _| Ok(true));
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let now = 1_700_000_010;
+        assert!(service
+            .verify_totp_at(user_id, &code_at(now), now)
+            .await
+            .unwrap());
+        assert!(service
+            .verify_totp_at(user_id, &code_at(now - TOTP_STEP_SECONDS), now)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_enable_totp_refuses_when_already_enabled() {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(totp_user(id, None))));
+        mock_repo.expect_update_totp().never();
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service.enable_totp(user_id).await;
+        assert!(matches!(result, Err(Error::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_enable_totp_stores_encrypted_secret() {
+        let user_id = Uuid::new_v4();
+        let stored = Arc::new(Mutex::new(None));
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(existing_user(id, "alice"))));
+        let captured = Arc::clone(&stored);
+        mock_repo
+            .expect_update_totp()
+            .times(1)
+            .returning(move |_, secret, enabled| {
+                assert!(!enabled);
+                *captured.lock().unwrap() = secret;
+                Ok(())
+            });
+        let config = shared_config(test_config());
+        let service = UserService::new(Arc::new(mock_repo), config.clone());
+
+        let uri = service.enable_totp(user_id).await.unwrap();
+        let stored = stored.lock().unwrap().clone().unwrap();
+        config.write().unwrap().auth.jwt_secret = "rotated-secret".to_string();
+        let secret = service.open_totp_secret(&stored).unwrap();
+
+        assert!(stored.starts_with(TOTP_SECRET_PREFIX));
+        assert!(!stored.contains(&secret));
+        assert!(uri.contains(&format!("secret={}", secret)));
+    }
+
+    #[tokio::test]
+    async fn test_verify_totp_rejects_expired_code() {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(totp_user(id, None))));
+        mock_repo.expect_update_totp_last_step().never();
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let now = 1_700_000_010;
+        let expired = code_at(now - 3 * TOTP_STEP_SECONDS);
+        assert!(!service
+            .verify_totp_at(user_id, &expired, now)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_totp_rejects_replayed_code() {
+        let user_id = Uuid::new_v4();
+        let now = 1_700_000_010;
+        let used_step = now / TOTP_STEP_SECONDS;
+
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(totp_user(id, Some(used_step)))));
+        mock_repo.expect_update_totp_last_step().never();
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        assert!(!service
+            .verify_totp_at(user_id, &code_at(now), now)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_totp_rejects_code_claimed_concurrently() {
+        let user_id = Uuid::new_v4();
+        let now = 1_700_000_010;
+
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(totp_user(id, None))));
+        // Another login recorded the step between the read and the update
+        mock_repo
+            .expect_update_totp_last_step()
+            .times(1)
+            .returning(|_, _| Ok(false));
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        assert!(!service
+            .verify_totp_at(user_id, &code_at(now), now)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_login_with_totp_requires_otp_and_partial_token_expires() {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_verify_credentials()
+            .returning(move |_, _| Ok(Some(totp_user(user_id, None))));
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service
+            .login("alice".to_string(), "password123".to_string())
+            .await
+            .unwrap();
+        let LoginResult::RequiresOtp(partial_token) = result else {
+            panic!("Expected TOTP challenge");
+        };
+
+        service
+            .pending_otp
+            .lock()
+            .unwrap()
+            .get_mut(&partial_token)
+            .unwrap()
+            .expires_at = Utc::now() - Duration::seconds(1);
+
+        match service.complete_totp_login(&partial_token, "000000").await {
+            Err(Error::Unauthorized(msg)) => assert!(msg.contains("expired")),
+            _ => panic!("Expected expired token error"),
+        }
+    }
+
+    /// Service whose only account is "alice" with `alice@example.com`
+    fn login_service() -> UserService {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo.expect_find_by_email().returning(move |email| {
+            Ok((email == "alice@example.com").then(|| existing_user(user_id, "alice")))
+        });
+        mock_repo
+            .expect_verify_credentials()
+            .returning(move |username, password| {
+                Ok((username == "alice" && password == "password123")
+                    .then(|| existing_user(user_id, "alice")))
+            });
+        UserService::new(Arc::new(mock_repo), shared_config(test_config()))
+    }
+
+    async fn login_username(service: &UserService, username_or_email: &str) -> Result<String> {
+        match service
+            .login(username_or_email.to_string(), "password123".to_string())
+            .await?
+        {
+            LoginResult::Authenticated(user) => Ok(user.username),
+            LoginResult::RequiresOtp(_) => panic!("Expected authenticated user"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_with_username_or_email() {
+        let service = login_service();
+
+        assert_eq!(login_username(&service, "alice").await.unwrap(), "alice");
+        assert_eq!(
+            login_username(&service, "alice@example.com").await.unwrap(),
+            "alice"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_email_ignores_case() {
+        let service = login_service();
+
+        assert_eq!(
+            login_username(&service, " Alice@Example.COM ")
+                .await
+                .unwrap(),
+            "alice"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_unknown_email_is_invalid_credentials() {
+        let service = login_service();
+
+        match login_username(&service, "bob@example.com").await {
+            Err(Error::Unauthorized(msg)) => assert_eq!(msg, "Invalid credentials"),
+            other => panic!("Expected invalid credentials, got {:?}", other),
+        }
+    }
+
+    fn invite_only_service(invites: Arc<MockInviteRepo>) -> UserService {
+        let mut repo = MockUserRepo::new();
+        repo.expect_find_by_username().returning(|_| Ok(None));
+        repo.expect_list_users()
+            .returning(|_| Ok(vec![existing_user(Uuid::new_v4(), "admin")]));
+        repo.expect_create_user()
+            .returning(|username, _, permissions| {
+                Ok(User::new(
+                    Uuid::new_v4(),
+                    username,
+                    "hash".to_string(),
+                    permissions,
+                ))
+            });
+
+        let mut config = test_config();
+        config.site.allow_registration = false;
+        config.site.invite_only = true;
+        UserService::new(Arc::new(repo), shared_config(config)).with_invites(invites)
+    }
+
+    #[tokio::test]
+    async fn test_invite_code_is_required_and_single_use() {
+        let invites = Arc::new(MockInviteRepo::default());
+        let service = invite_only_service(invites.clone());
+        let admin_id = Uuid::new_v4();
+
+        assert!(matches!(
+            service
+                .create_invite(admin_id, DEFAULT_USER_PERMISSIONS, 24)
+                .await,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+        let code = service
+            .create_invite(admin_id, ADMIN_PERMISSIONS, 24)
+            .await
+            .unwrap();
+        assert_eq!(code.len(), INVITE_CODE_LENGTH);
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        for (invite_code, expected) in [(None, "required"), (Some("nope"), "Invalid")] {
+            match service
+                .register("alice".to_string(), "password123".to_string(), invite_code)
+                .await
+            {
+                Err(Error::Validation(msg)) => assert!(msg.contains(expected)),
+                other => panic!("Expected validation error, got {:?}", other),
+            }
+        }
+
+        let user = service
+            .register("alice".to_string(), "password123".to_string(), Some(&code))
+            .await
+            .unwrap();
+        let stored = invites.get_invite(&code).await.unwrap().unwrap();
+        assert_eq!(stored.used_by, Some(user.id));
+        assert_eq!(stored.created_by, Some(admin_id));
+
+        match service
+            .register("bob".to_string(), "password123".to_string(), Some(&code))
+            .await
+        {
+            Err(Error::Validation(msg)) => assert!(msg.contains("already been used")),
+            other => panic!("Expected used code error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_invite_code_is_rejected() {
+        let invites = Arc::new(MockInviteRepo::default());
+        let now = Utc::now();
+        invites
+            .create_invite(InviteCode {
+                code: "expiredinvite123".to_string(),
+                created_by: None,
+                used_by: None,
+                expires_at: now - Duration::hours(1),
+                created_at: now - Duration::hours(25),
+            })
+            .await
+            .unwrap();
+        let service = invite_only_service(invites.clone());
+
+        match service
+            .register(
+                "alice".to_string(),
+                "password123".to_string(),
+                Some("expiredinvite123"),
+            )
+            .await
+        {
+            Err(Error::Validation(msg)) => assert!(msg.contains("expired")),
+            other => panic!("Expected expired code error, got {:?}", other),
+        }
+        assert!(!invites
+            .get_invite("expiredinvite123")
+            .await
+            .unwrap()
+            .unwrap()
+            .is_used());
+
+        assert!(matches!(
+            service
+                .create_invite(Uuid::new_v4(), ADMIN_PERMISSIONS, 0)
+                .await,
+            Err(Error::Validation(_))
+        ));
+    }
+
+    fn api_key_service(user: User) -> UserService {
+        let mut repo = MockUserRepo::new();
+        repo.expect_find_by_id()
+            .returning(move |id| Ok((id == user.id).then(|| user.clone())));
+        UserService::new(Arc::new(repo), shared_config(test_config()))
+            .with_api_keys(Arc::new(MockApiKeyRepo::default()))
+    }
+
+    #[tokio::test]
+    async fn test_api_key_rotation_invalidates_old_key() {
+        let user = existing_user(Uuid::new_v4(), "alice");
+        let service = api_key_service(user.clone());
+
+        let (key, created) = service
+            .create_api_key(user.id, "feed reader".to_string(), vec![])
+            .await
+            .unwrap();
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert_ne!(created.key_hash, key);
+        assert_eq!(created.scopes, vec![SCOPE_READ_POSTS.to_string()]);
+
+        let (owner, used) = service.authenticate_api_key(&key).await.unwrap();
+        assert_eq!(owner.id, user.id);
+        assert!(used.last_used_at.is_none());
+        let listed = service.list_api_keys(user.id).await.unwrap();
+        assert!(listed[0].last_used_at.is_some());
+
+        let (new_key, rotated) = service
+            .rotate_api_key(user.id, &created.key_hash)
+            .await
+            .unwrap();
+        assert_ne!(new_key, key);
+        assert_eq!(rotated.label, "feed reader");
+
+        let old = service.authenticate_api_key(&key).await;
+        assert!(matches!(old, Err(Error::Unauthorized(_))));
+        assert!(service.authenticate_api_key(&new_key).await.is_ok());
+        assert_eq!(service.list_api_keys(user.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_scopes_limit_permissions() {
+        let mut user = existing_user(Uuid::new_v4(), "alice");
+        user.permissions = ADMIN_PERMISSIONS;
+        let service = api_key_service(user.clone());
+
+        let unknown = service
+            .create_api_key(user.id, "bad".to_string(), vec!["admin".to_string()])
+            .await;
+        assert!(matches!(unknown, Err(Error::Validation(_))));
+
+        let (key, _) = service
+            .create_api_key(
+                user.id,
+                "read".to_string(),
+                vec![SCOPE_READ_POSTS.to_string()],
+            )
+            .await
+            .unwrap();
+        let (owner, api_key) = service.authenticate_api_key(&key).await.unwrap();
+        assert!(!api_key.can_write());
+        assert_eq!(
+            api_key.effective_permissions(owner.permissions) & domain::POST_CREATE,
+            0
+        );
+
+        let (key, _) = service
+            .create_api_key(
+                user.id,
+                "write".to_string(),
+                vec![domain::SCOPE_WRITE_POSTS.to_string()],
+            )
+            .await
+            .unwrap();
+        let (owner, api_key) = service.authenticate_api_key(&key).await.unwrap();
+        assert_ne!(
+            api_key.effective_permissions(owner.permissions) & domain::POST_CREATE,
+            0
+        );
+        assert_eq!(
+            api_key.effective_permissions(owner.permissions) & USER_MANAGE,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revoke_api_key_of_other_user_fails() {
+        let user = existing_user(Uuid::new_v4(), "alice");
+        let service = api_key_service(user.clone());
+        let (_, created) = service
+            .create_api_key(user.id, "feed".to_string(), vec![])
+            .await
+            .unwrap();
+
+        let result = service
+            .revoke_api_key(Uuid::new_v4(), &created.key_hash)
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        service
+            .revoke_api_key(user.id, &created.key_hash)
+            .await
+            .unwrap();
+    }
+
+    async fn email_service(mock_repo: MockUserRepo) -> (UserService, MockSmtpServer) {
+        let smtp = MockSmtpServer::start().await;
+        let email_client =
+            EmailClient::unencrypted("127.0.0.1", smtp.port, "blog@example.com").unwrap();
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()))
+            .with_email(
+                crate::Clients {
+                    email: Some(Arc::new(email_client)),
+                    ..Default::default()
+                }
+                .shared(),
+                "http://blog.example.com/".to_string(),
+            );
+        (service, smtp)
+    }
+
+    #[tokio::test]
+    async fn test_update_email_sends_verification_link() {
+        let user_id = Uuid::new_v4();
+        let token = Arc::new(Mutex::new(String::new()));
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo.expect_find_by_email().returning(|_| Ok(None));
+        let stored = token.clone();
+        mock_repo
+            .expect_update_email()
+            .withf(move |id, email, _| *id == user_id && email == "reader@example.com")
+            .times(1)
+            .returning(move |_, _, verification_token| {
+                *stored.lock().unwrap() = verification_token;
+                Ok(())
+            });
+        let (service, smtp) = email_service(mock_repo).await;
+
+        for invalid in [
+            "not-an-email",
+            "reader@localhost",
+            "a b@example.com",
+            "@example.com",
+        ] {
+            let result = service.update_email(user_id, invalid.to_string()).await;
+            assert!(
+                matches!(result, Err(e) if e.field() == Some("email")),
+                "{}",
+                invalid
+            );
+        }
+
+        service
+            .update_email(user_id, " Reader@Example.com ".to_string())
+            .await
+            .unwrap();
+
+        let messages = smtp.wait_for_messages(1).await;
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("To: reader@example.com"));
+        // The body is quoted-printable encoded
+        let body = messages[0].replace("=\n", "").replace("=3D", "=");
+        let link = format!(
+            "http://blog.example.com/api/auth/verify-email?token={}",
+            token.lock().unwrap()
+        );
+        assert!(body.contains(&link));
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_only_for_verified_email() {
+        let mut unverified = existing_user(Uuid::new_v4(), "alice");
+        unverified.email = Some("alice@example.com".to_string());
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_| Ok(Some(unverified.clone())));
+        mock_repo.expect_set_password_reset_token().never();
+        let (service, smtp) = email_service(mock_repo).await;
+
+        service
+            .request_password_reset("alice@example.com")
+            .await
+            .unwrap();
+        assert!(smtp.wait_for_messages(0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_honours_expiry() {
+        let mut user = existing_user(Uuid::new_v4(), "alice");
+        user.password_reset_token = Some("valid".to_string());
+        user.password_reset_expires_at = Some(Utc::now() + Duration::minutes(5));
+        let mut expired = existing_user(Uuid::new_v4(), "bob");
+        expired.password_reset_token = Some("expired".to_string());
+        expired.password_reset_expires_at = Some(Utc::now() - Duration::minutes(1));
+
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_password_reset_token()
+            .returning(move |token| {
+                Ok([&user, &expired]
+                    .into_iter()
+                    .find(|u| u.password_reset_token.as_deref() == Some(token))
+                    .cloned())
+            });
+        mock_repo
+            .expect_set_password_reset_token()
+            .withf(|_, token, expires_at| token.is_none() && expires_at.is_none())
+            .returning(|_, _, _| Ok(()));
+        mock_repo
+            .expect_update_password()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let sessions = Arc::new(MockSessionRepo {
+            sessions: Default::default(),
+        });
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()))
+            .with_sessions(sessions.clone());
+
+        for token in ["expired", "unknown"] {
+            let result = service
+                .reset_password(token, "newpassword1".to_string())
+                .await;
+            assert!(matches!(result, Err(Error::Validation(_))), "{}", token);
+        }
+        service
+            .reset_password("valid", "newpassword1".to_string())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_ends_sessions() {
+        let mut user = existing_user(Uuid::new_v4(), "alice");
+        user.password_reset_token = Some("valid".to_string());
+        user.password_reset_expires_at = Some(Utc::now() + Duration::minutes(5));
+        let user_id = user.id;
+
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo
+            .expect_find_by_password_reset_token()
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_repo
+            .expect_set_password_reset_token()
+            .withf(|_, token, expires_at| token.is_none() && expires_at.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock_repo.expect_update_password().returning(|_, _| Ok(()));
+        let sessions = Arc::new(MockSessionRepo {
+            sessions: Default::default(),
+        });
+        let other_user = Uuid::new_v4();
+        for owner in [user_id, user_id, other_user] {
+            sessions
+                .create_session(owner, true, "test-agent", "127.0.0.1")
+                .await
+                .unwrap();
+        }
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()))
+            .with_sessions(sessions.clone());
+
+        service
+            .reset_password("valid", "newpassword1".to_string())
+            .await
+            .unwrap();
+
+        assert!(sessions
+            .list_user_sessions(user_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            sessions.list_user_sessions(other_user).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_impersonate_records_admin_in_audit_log() {
+        let mut mock_repo = MockUserRepo::new();
+        let user_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(existing_user(user_id, "alice"))));
+        mock_repo
+            .expect_add_audit_log()
+            .withf(move |id, action, detail| {
+                *id == user_id
+                    && action == "impersonated"
+                    && detail.as_deref() == Some(format!("by admin {}", admin_id).as_str())
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let target = service
+            .impersonate(user_id, admin_id, ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+        assert_eq!(target.username, "alice");
+
+        let denied = service
+            .impersonate(user_id, admin_id, DEFAULT_USER_PERMISSIONS)
+            .await;
+        assert!(matches!(
+            denied,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_impersonating_admin_requires_super_admin() {
+        let mut mock_repo = MockUserRepo::new();
+        let other_admin_id = Uuid::new_v4();
+
+        mock_repo.expect_find_by_id().returning(move |_| {
+            let mut admin = existing_user(other_admin_id, "other_admin");
+            admin.permissions = ADMIN_PERMISSIONS;
+            Ok(Some(admin))
+        });
+        mock_repo
+            .expect_add_audit_log()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()));
+
+        let result = service
+            .impersonate(other_admin_id, Uuid::new_v4(), ADMIN_PERMISSIONS)
+            .await;
+        match result {
+            Err(e) => assert_eq!(e.code(), ErrorCode::PermissionDenied),
+            _ => panic!("Expected admin impersonation to be denied"),
+        }
+
+        let allowed = service
+            .impersonate(
+                other_admin_id,
+                Uuid::new_v4(),
+                ADMIN_PERMISSIONS | SUPER_ADMIN,
+            )
+            .await;
+        assert!(allowed.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_counts_posts_and_comments() {
+        let user_id = Uuid::new_v4();
+        let mut mock_repo = MockUserRepo::new();
+        mock_repo.expect_find_by_id().returning(move |_| {
+            let mut user = existing_user(user_id, "alice");
+            user.email = Some("alice@example.com".to_string());
+            Ok(Some(user))
+        });
+        let mut post_repo = crate::test_support::MockPostRepo::new();
+        post_repo
+            .expect_count_posts_by_user()
+            .with(mockall::predicate::eq(user_id))
+            .times(1)
+            .returning(|_| Ok(3));
+        let comment_repo = crate::test_support::MockCommentRepo::new();
+        {
+            let mut comments = comment_repo.comments.write().await;
+            for author in [user_id, user_id, Uuid::new_v4()] {
+                let comment =
+                    domain::Comment::from_user(Uuid::new_v4(), author, "Nice".to_string());
+                comments.insert(comment.id, comment);
+            }
+        }
+        let service = UserService::new(Arc::new(mock_repo), shared_config(test_config()))
+            .with_activity_counts(Arc::new(post_repo), Arc::new(comment_repo));
+
+        let profile = service.get_profile(user_id).await.unwrap();
+
+        assert_eq!(profile.username, "alice");
+        assert_eq!(profile.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(profile.post_count, 3);
+        assert_eq!(profile.comment_count, 2);
+        assert_eq!(
+            profile.permission_names,
+            ["POST_CREATE", "POST_UPDATE", "POST_PUBLISH"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_requires_activity_counts() {
+        let service = setup_service();
+
+        let result = service.get_profile(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(Error::Internal(_))));
+    }
+
+    fn search_service() -> UserService {
+        let users = ["alice", "alicia", "bob"]
+            .map(|name| User::new(Uuid::new_v4(), name.to_string(), "hash".to_string(), 0));
+        UserService::new(
+            Arc::new(crate::test_support::MockUserRepo::with_users(
+                users.to_vec(),
+            )),
+            shared_config(test_config()),
+        )
+    }
+
+    fn usernames(page: &PaginatedUsers) -> Vec<&str> {
+        page.items
+            .iter()
+            .map(|user| user.username.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_partial_username() {
+        let page = search_service()
+            .search("lic", ADMIN_PERMISSIONS, 20, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(usernames(&page), ["alice", "alicia"]);
+        assert_eq!(page.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_full_username_and_pages() {
+        let service = search_service();
+
+        let page = service
+            .search("bob", ADMIN_PERMISSIONS, 20, 0)
+            .await
+            .unwrap();
+        assert_eq!(usernames(&page), ["bob"]);
+
+        let page = service
+            .search("ali", ADMIN_PERMISSIONS, 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(usernames(&page), ["alicia"]);
+        assert_eq!(page.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_without_match_is_empty() {
+        let page = search_service()
+            .search("carol", ADMIN_PERMISSIONS, 20, 0)
+            .await
+            .unwrap();
+
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_validates_query_and_permissions() {
+        let service = search_service();
+
+        for query in ["", "   ", &"a".repeat(51)] {
+            let result = service.search(query, ADMIN_PERMISSIONS, 20, 0).await;
+            assert!(matches!(result, Err(Error::Validation(_))), "{:?}", query);
+        }
+
+        let result = service
+            .search("alice", DEFAULT_USER_PERMISSIONS, 20, 0)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::CodedError {
+                code: ErrorCode::PermissionDenied,
+                ..
+            })
+        ));
+    }
+
+    const BATCH_CSV: &str = include_str!("../fixtures/users/batch.csv");
+
+    /// Service whose repository already holds the username "taken"
+    fn batch_service() -> UserService {
+        let mut repo = MockUserRepo::new();
+        repo.expect_find_by_username().returning(|username| {
+            Ok((username == "taken").then(|| {
+                User::new(
+                    Uuid::new_v4(),
+                    "taken".to_string(),
+                    "hash".to_string(),
+                    DEFAULT_USER_PERMISSIONS,
+                )
+            }))
+        });
+        repo.expect_create_user()
+            .returning(|username, _, permissions| {
+                Ok(User::new(
+                    Uuid::new_v4(),
+                    username,
+                    "hash".to_string(),
+                    permissions,
+                ))
+            });
+        UserService::new(Arc::new(repo), shared_config(test_config()))
+    }
+
+    #[test]
+    fn test_parse_users_csv() {
+        let entries = parse_users_csv(BATCH_CSV).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0],
+            NewUserEntry {
+                username: "alice".to_string(),
+                password: "password123".to_string(),
+                admin: false,
+            }
+        );
+        assert!(!entries[1].admin);
+        assert!(entries[2].admin);
+
+        let err =
+            parse_users_csv("username,password,admin\ncarol,password123,maybe\n").unwrap_err();
+        assert!(matches!(err, Error::Validation(msg) if msg.contains("line 2")));
+    }
+
+    #[tokio::test]
+    async fn test_batch_create_collects_failures() {
+        let entries = parse_users_csv(BATCH_CSV).unwrap();
+
+        let result = batch_service()
+            .batch_create(entries, ADMIN_PERMISSIONS)
+            .await
+            .unwrap();
+
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.created[0].username, "alice");
+        assert_eq!(result.created[0].permissions, DEFAULT_USER_PERMISSIONS);
+        assert_eq!(result.failed.len(), 2);
+        assert_eq!(result.failed[0].0, "taken");
+        assert!(result.failed[0].1.contains("already exists"));
+        assert_eq!(result.failed[1].0, "bob");
+        assert!(result.failed[1].1.contains("at least 8 characters"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_create_requires_user_manage() {
+        let entries = parse_users_csv(BATCH_CSV).unwrap();
+
+        let err = batch_service()
+            .batch_create(entries, DEFAULT_USER_PERMISSIONS)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), ErrorCode::PermissionDenied);
+    }
 }
@@ -96,6 +96,53 @@ export const authApi = {
     return http.post<ApiResponse<{ message: string }>>('/auth/logout');
   },
 
+  /**
+   * 设置邮箱地址并发送验证邮件
+   * @param email 新邮箱地址
+   */
+  updateEmail: (email: string) => {
+    return http.put<ApiResponse<{ email_verified: boolean }>>('/auth/email', { email });
+  },
+
+  /**
+   * 使用邮件中的 token 验证邮箱
+   * @param token 验证 token
+   */
+  verifyEmail: (token: string) => {
+    return http.get<ApiResponse<{ email_verified: boolean }>>('/auth/verify-email', {
+      params: { token },
+    });
+  },
+
+  /**
+   * 发送重置密码邮件（仅限已验证的邮箱）
+   * @param email 账户邮箱
+   */
+  forgotPassword: (email: string) => {
+    return http.post<ApiResponse<{ message: string }>>('/auth/forgot-password', { email });
+  },
+
+  /**
+   * 使用重置 token 设置新密码
+   * @param token 邮件中的重置 token
+   * @param newPassword 新密码
+   */
+  resetPassword: (token: string, newPassword: string) => {
+    return http.post<ApiResponse<{ message: string }>>('/auth/reset-password', {
+      token,
+      new_password: newPassword,
+    });
+  },
+
+  /**
+   * 以指定用户身份获取只读 token（需要 USER_MANAGE，模拟管理员需 SUPER_ADMIN）
+   * token 有效期 1 小时，所有写操作返回 403
+   * @param userId 目标用户 ID
+   */
+  impersonate: (userId: string) => {
+    return http.post<ApiResponse<UserLoginResponse>>(`/auth/impersonate/${userId}`);
+  },
+
   /**
    * 保存登录信息到 localStorage
    * @param response 登录响应
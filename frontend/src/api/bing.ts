@@ -7,8 +7,22 @@ export interface BingDailyImage {
   copyright_link?: string;
 }
 
+export interface BingHistoryEntry {
+  /** 壁纸展示日期，YYYY-MM-DD */
+  date: string;
+  url: string;
+  title: string;
+  copyright: string;
+  /** 是否为当前 daily-image 返回的壁纸 */
+  is_current: boolean;
+}
+
 export const bingApi = {
   getDailyImage: () => {
     return http.get<ApiResponse<BingDailyImage>>('/bing/daily-image');
   },
+
+  getHistory: (limit?: number) => {
+    return http.get<ApiResponse<BingHistoryEntry[]>>('/bing/history', { params: { limit } });
+  },
 };
@@ -1,6 +1,7 @@
 import { http } from '../utils/request';
 import type {
   Category,
+  CategoryNode,
   CategoryCreateRequest,
   CategoryUpdateRequest,
   ApiResponseV2,
@@ -25,7 +26,7 @@ export const categoriesApi = {
 
   /**
    * 创建分类
-   * 需要管理员权限 (USER_MANAGE)
+   * 需要分类管理权限 (CATEGORY_MANAGE)
    */
   createCategory: (data: CategoryCreateRequest) => {
     return http.post<ApiResponseV2<Category>>('/categories', data);
@@ -33,7 +34,7 @@ export const categoriesApi = {
 
   /**
    * 更新分类（部分更新）
-   * 需要管理员权限 (USER_MANAGE)
+   * 需要分类管理权限 (CATEGORY_MANAGE)
    */
   patchCategory: (id: string, data: CategoryUpdateRequest) => {
     return http.patch<ApiResponseV2<Category>>(`/categories/${id}`, data);
@@ -41,7 +42,7 @@ export const categoriesApi = {
 
   /**
    * 删除分类
-   * 需要管理员权限 (USER_MANAGE)
+   * 需要分类管理权限 (CATEGORY_MANAGE)
    */
   deleteCategory: (id: string) => {
     return http.delete<void>(`/categories/${id}`);
@@ -53,6 +54,20 @@ export const categoriesApi = {
   getCategoryPosts: (id: string, params?: { page?: number; per_page?: number }) => {
     return http.get<ApiListResponseV2<Post>>(`/categories/${id}/posts`, { params });
   },
+
+  /**
+   * 获取分类的面包屑路径（从根分类到当前分类）
+   */
+  getBreadcrumb: (id: string) => {
+    return http.get<ApiResponseV2<Category[]>>(`/categories/${id}/breadcrumb`);
+  },
+
+  /**
+   * 获取完整的分类树（根分类及其嵌套子分类）
+   */
+  getCategoryTree: () => {
+    return http.get<ApiResponseV2<CategoryNode[]>>('/categories/tree');
+  },
 };
 
 export default categoriesApi;
@@ -6,6 +6,7 @@ import type {
   CommentPatchRequest,
   ApiResponseV2,
   GitHubAuthResponse,
+  PaginatedComments,
 } from '../types';
 
 export const commentsApi = {
@@ -61,6 +62,32 @@ export const commentsApi = {
   getGitHubAuthUrl: () => {
     return http.get<ApiResponseV2<GitHubAuthResponse>>('/comments/github/auth');
   },
+
+  /**
+   * 用表情回应评论，可选 👍 👎 ❤️ 🎉 😕 🚀 👀，重复回应不会重复计数
+   * 返回更新后的各表情数量
+   */
+  addReaction: (id: string, reactionType: string) => {
+    return http.post<ApiResponseV2<Record<string, number>>>(`/comments/${id}/reactions`, {
+      reaction_type: reactionType,
+    });
+  },
+
+  /**
+   * 撤回表情回应，返回更新后的各表情数量
+   */
+  removeReaction: (id: string, reactionType: string) => {
+    return http.delete<ApiResponseV2<Record<string, number>>>(
+      `/comments/${id}/reactions/${encodeURIComponent(reactionType)}`
+    );
+  },
+
+  /**
+   * 按内容或 GitHub 用户名搜索全部评论（管理员）
+   */
+  searchComments: (params: { q: string; limit?: number; offset?: number }) => {
+    return http.get<ApiResponseV2<PaginatedComments>>('/admin/comments/search', { params });
+  },
 };
 
 export default commentsApi;
@@ -1,5 +1,11 @@
 import { http } from '../utils/request';
-import type { Config, UpdateConfigRequest, ApiResponseV2, PublicConfig } from '../types';
+import type {
+  Config,
+  UpdateConfigRequest,
+  ApiResponseV2,
+  PublicConfig,
+  SiteConfig,
+} from '../types';
 
 export const configApi = {
   /**
@@ -24,6 +30,25 @@ export const configApi = {
   updateConfig: (data: UpdateConfigRequest) => {
     return http.patch<ApiResponseV2<Config>>('/config', data);
   },
+
+  /**
+   * Update site settings and persist them to the config file
+   * Only allow_registration, invite_only and archive_after_days are accepted
+   * Requires admin permission
+   */
+  updateSiteConfig: (
+    data: Partial<Pick<SiteConfig, 'allow_registration' | 'invite_only' | 'archive_after_days'>>
+  ) => {
+    return http.put<ApiResponseV2<Config>>('/admin/config', data);
+  },
+
+  /**
+   * Reload configuration from disk
+   * Requires admin permission
+   */
+  reloadConfig: () => {
+    return http.post<ApiResponseV2<Config>>('/admin/config/reload');
+  },
 };
 
 export default configApi;
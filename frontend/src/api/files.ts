@@ -1,6 +1,8 @@
 import { http } from '../utils/request';
 import type {
   FileInfo,
+  FileListing,
+  ApiResponseV2,
   FileUploadParams,
   PaginatedResponse,
   ApiResponse,
@@ -21,7 +23,7 @@ export const filesApi = {
     const formData = new FormData();
     formData.append('file', params.file);
 
-    return http.post<FileInfo>('/files', formData);
+    return http.post<ApiResponseV2<FileInfo>>('/files', formData);
   },
 
   /**
@@ -30,7 +32,16 @@ export const filesApi = {
    * @returns 文件信息
    */
   getFile: (id: string) => {
-    return http.get<FileInfo>(`/files/${id}`);
+    return http.get<ApiResponseV2<FileInfo>>(`/files/${id}`);
+  },
+
+  /**
+   * 按存储文件名获取文件信息（用于把文章内容中的文件链接解析回文件记录）
+   * @param filename 存储文件名
+   * @returns 文件信息
+   */
+  getFileByFilename: (filename: string) => {
+    return http.get<ApiResponseV2<FileInfo>>(`/files/by-name/${encodeURIComponent(filename)}`);
   },
 
   /**
@@ -54,6 +65,28 @@ export const filesApi = {
     return http.get<PaginatedResponse<FileInfo>>('/files', { params });
   },
 
+  /**
+   * 获取所有用户的文件列表（需要管理员权限）
+   * @param params content_type 为精确类型，或以 / 结尾的前缀（如 image/）
+   * @returns 文件分页及匹配文件的总数和总大小
+   */
+  getAllFiles: (params?: { limit?: number; offset?: number; content_type?: string }) => {
+    return http.get<ApiResponseV2<FileListing>>('/admin/files', { params });
+  },
+
+  /**
+   * 将文件转移给其他用户（需要管理员权限）
+   * 仅修改文件记录的所有者，磁盘上的文件保持不变
+   * @param id 文件 ID
+   * @param newOwnerId 新所有者的用户 ID
+   * @returns 转移后的文件信息
+   */
+  moveFile: (id: string, newOwnerId: string) => {
+    return http.post<ApiResponseV2<FileInfo>>(`/admin/files/${id}/move`, {
+      new_owner_id: newOwnerId,
+    });
+  },
+
   /**
    * 删除文件
    * @param id 文件 ID
@@ -4,12 +4,21 @@ import type {
   PostCreateRequest,
   PostUpdateRequest,
   PostPatchRequest,
+  PostImportResult,
   PostListParams,
   PostSearchParams,
   ApiResponseV2,
   ApiListResponseV2,
   Tag,
+  OgData,
+  AdjacentPosts,
   Comment,
+  CommentThread,
+  LikeResponse,
+  BulkResult,
+  TocEntry,
+  LinkCheckResult,
+  ArchiveMonth,
 } from '../types';
 
 export const postsApi = {
@@ -44,6 +53,26 @@ export const postsApi = {
     return http.post<ApiResponseV2<Post>>('/posts', data);
   },
 
+  /**
+   * 导入带 YAML front-matter 的 Markdown 文件
+   * API: POST /posts/import
+   */
+  importPosts: (files: File[]) => {
+    const formData = new FormData();
+    files.forEach((file) => formData.append('files', file));
+    return http.post<ApiResponseV2<PostImportResult>>('/posts/import', formData);
+  },
+
+  /**
+   * 导出全部文章为 Markdown ZIP 压缩包（管理员）
+   * API: GET /posts/export
+   */
+  exportPosts: () => {
+    return http.get<Blob>('/posts/export', {
+      responseType: 'blob',
+    });
+  },
+
   /**
    * 全量更新文章
    * API v2: 使用 PUT 方法
@@ -85,18 +114,37 @@ export const postsApi = {
   },
 
   /**
-   * 获取文章评论列表
+   * 批量发布文章，可指定未来的发布时间
+   * API: POST /posts/bulk-publish
+   */
+  bulkPublish: (postIds: string[], scheduleAt?: string) => {
+    return http.post<ApiResponseV2<BulkResult>>('/posts/bulk-publish', {
+      post_ids: postIds,
+      schedule_at: scheduleAt,
+    });
+  },
+
+  /**
+   * 批量取消发布文章
+   * API: POST /posts/bulk-unpublish
+   */
+  bulkUnpublish: (postIds: string[]) => {
+    return http.post<ApiResponseV2<BulkResult>>('/posts/bulk-unpublish', { post_ids: postIds });
+  },
+
+  /**
+   * 获取文章评论串列表（顶层评论及其回复）
    * API v2: 端点从 /comments 改为 /posts/{id}/comments
    */
   getPostComments: (postId: string, params?: { page?: number; per_page?: number }) => {
-    return http.get<ApiListResponseV2<Comment>>(`/posts/${postId}/comments`, { params });
+    return http.get<ApiListResponseV2<CommentThread>>(`/posts/${postId}/comments`, { params });
   },
 
   /**
    * 为文章添加评论
    * API v2: 端点从 /comments 改为 /posts/{id}/comments
    */
-  createPostComment: (postId: string, data: { content: string }) => {
+  createPostComment: (postId: string, data: { content: string; parent_id?: string }) => {
     return http.post<ApiResponseV2<Comment>>(`/posts/${postId}/comments`, data);
   },
 
@@ -130,6 +178,14 @@ export const postsApi = {
     return http.post<ApiResponseV2<Tag[]>>(`/posts/${postId}/tags`, { tag_id: tagId });
   },
 
+  /**
+   * 批量设置文章标签（替换全部现有标签）
+   * API: PUT /posts/{id}/tags + { tag_ids }
+   */
+  setPostTags: (postId: string, tagIds: string[]) => {
+    return http.put<ApiResponseV2<Tag[]>>(`/posts/${postId}/tags`, { tag_ids: tagIds });
+  },
+
   /**
    * 移除文章的标签
    * API v2: 使用 DELETE /posts/{id}/tags/{tag_id}
@@ -145,6 +201,160 @@ export const postsApi = {
   submitToIndexNow: (postId: string) => {
     return http.post<ApiResponseV2<Post>>(`/posts/${postId}/indexnow`);
   },
+
+  /**
+   * 获取文章目录（由 Markdown 标题生成）
+   * API: GET /posts/{id}/toc
+   */
+  getPostToc: (postId: string) => {
+    return http.get<ApiResponseV2<TocEntry[]>>(`/posts/${postId}/toc`);
+  },
+
+  /**
+   * 获取文章的社交分享预览数据（OpenGraph），用于动态设置 meta 标签
+   * API: GET /posts/{id}/og
+   */
+  getPostOgData: (postId: string) => {
+    return http.get<ApiResponseV2<OgData>>(`/posts/${postId}/og`);
+  },
+
+  /**
+   * 获取有已发布文章的月份列表（最新的月份在前）
+   * API: GET /posts/archive
+   */
+  getArchiveMonths: () => {
+    return http.get<ApiResponseV2<ArchiveMonth[]>>('/posts/archive');
+  },
+
+  /**
+   * 获取某月发布的文章（UTC，最新的在前）
+   * API: GET /posts/archive/{year}/{month}
+   */
+  getPostsForMonth: (year: number, month: number, limit?: number) => {
+    return http.get<ApiResponseV2<Post[]>>(`/posts/archive/${year}/${month}`, {
+      params: { limit },
+    });
+  },
+
+  /**
+   * 获取按发布时间相邻的上一篇和下一篇文章
+   * API: GET /posts/{id}/adjacent
+   */
+  getAdjacentPosts: (postId: string) => {
+    return http.get<ApiResponseV2<AdjacentPosts>>(`/posts/${postId}/adjacent`);
+  },
+
+  /**
+   * 获取上一篇已发布文章，没有时为 null
+   * API: GET /posts/{id}/prev
+   */
+  getPrevPost: (postId: string) => {
+    return http.get<ApiResponseV2<Post | null>>(`/posts/${postId}/prev`);
+  },
+
+  /**
+   * 获取下一篇已发布文章，没有时为 null
+   * API: GET /posts/{id}/next
+   */
+  getNextPost: (postId: string) => {
+    return http.get<ApiResponseV2<Post | null>>(`/posts/${postId}/next`);
+  },
+
+  /**
+   * 获取相关文章（按共同标签数排序）
+   * API: GET /posts/{id}/related
+   */
+  getRelatedPosts: (postId: string, limit?: number) => {
+    return http.get<ApiResponseV2<Post[]>>(`/posts/${postId}/related`, { params: { limit } });
+  },
+
+  /**
+   * 点赞文章（无需登录，每个 IP 只计一次）
+   * API: POST /posts/{id}/like
+   */
+  likePost: (postId: string) => {
+    return http.post<ApiResponseV2<LikeResponse>>(`/posts/${postId}/like`);
+  },
+
+  /**
+   * 归档文章
+   * API: POST /posts/{id}/archive
+   */
+  archivePost: (postId: string) => {
+    return http.post<void>(`/posts/${postId}/archive`);
+  },
+
+  /**
+   * 取消归档文章
+   * API: POST /posts/{id}/unarchive
+   */
+  unarchivePost: (postId: string) => {
+    return http.post<ApiResponseV2<Post>>(`/posts/${postId}/unarchive`);
+  },
+
+  /**
+   * 复制文章为新草稿
+   * API: POST /posts/{id}/duplicate
+   */
+  duplicatePost: (postId: string) => {
+    return http.post<ApiResponseV2<Post>>(`/posts/${postId}/duplicate`);
+  },
+
+  /**
+   * 置顶文章（管理员）
+   * API: POST /posts/{id}/pin
+   */
+  pinPost: (postId: string) => {
+    return http.post<ApiResponseV2<Post>>(`/posts/${postId}/pin`);
+  },
+
+  /**
+   * 取消置顶文章（管理员）
+   * API: DELETE /posts/{id}/pin
+   */
+  unpinPost: (postId: string) => {
+    return http.delete<ApiResponseV2<Post>>(`/posts/${postId}/pin`);
+  },
+
+  /**
+   * 定时发布文章（publishAt 须为将来的时间）
+   * API: POST /posts/{id}/schedule
+   */
+  schedulePost: (postId: string, publishAt: string) => {
+    return http.post<ApiResponseV2<Post>>(`/posts/${postId}/schedule`, { publish_at: publishAt });
+  },
+
+  /**
+   * 取消定时发布
+   * API: DELETE /posts/{id}/schedule
+   */
+  cancelSchedule: (postId: string) => {
+    return http.delete<ApiResponseV2<Post>>(`/posts/${postId}/schedule`);
+  },
+
+  /**
+   * 设置特色图片：已上传文件的 ID 或外部 https 图片 URL，二选一
+   * API: POST /posts/{id}/feature-image
+   */
+  setFeatureImage: (postId: string, data: { file_id: string } | { url: string }) => {
+    return http.post<ApiResponseV2<Post>>(`/posts/${postId}/feature-image`, data);
+  },
+
+  /**
+   * 移除特色图片
+   * API: DELETE /posts/{id}/feature-image
+   */
+  clearFeatureImage: (postId: string) => {
+    return http.delete<ApiResponseV2<Post>>(`/posts/${postId}/feature-image`);
+  },
+
+  /**
+   * 检查文章中的外部 https 链接（最多 20 个）是否失效
+   * API: POST /posts/{id}/validate-links
+   */
+  validateLinks: (postId: string) => {
+    return http.post<ApiResponseV2<LinkCheckResult[]>>(`/posts/${postId}/validate-links`);
+  },
 };
 
 export default postsApi;
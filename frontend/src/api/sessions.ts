@@ -16,6 +16,27 @@ export const sessionsApi = {
     return http.delete<void>('/sessions');
   },
 
+  /**
+   * 列出当前用户的有效会话（含设备与 IP）
+   */
+  listSessions: () => {
+    return http.get<ApiResponseV2<Session[]>>('/sessions');
+  },
+
+  /**
+   * 按 ID 撤销当前用户的某个会话
+   */
+  revokeSession: (id: string) => {
+    return http.delete<ApiResponseV2<{ message: string }>>(`/sessions/${id}`);
+  },
+
+  /**
+   * 撤销某用户的全部会话（本人或管理员），本人操作时保留当前会话
+   */
+  revokeUserSessions: (userId: string) => {
+    return http.delete<ApiResponseV2<{ revoked: number }>>(`/users/${userId}/sessions`);
+  },
+
   /**
    * 获取当前会话信息
    * API v2: 端点从 /sessions/current 改为 /sessions/info
@@ -1,5 +1,13 @@
 import { http } from '../utils/request';
-import type { GlobalStats, AdminStats, RecordVisitRequest, ApiResponseV2 } from '../types';
+import type {
+  GlobalStats,
+  AdminStats,
+  DailyStats,
+  PostStats,
+  RecordVisitRequest,
+  WordCountStats,
+  ApiResponseV2,
+} from '../types';
 
 export const statsApi = {
   /**
@@ -29,6 +37,56 @@ export const statsApi = {
   getAdminStats: () => {
     return http.get<ApiResponseV2<AdminStats>>('/stats/total');
   },
+
+  /**
+   * 获取最近 N 天（默认 30，最多 365）的每日访问量，按日期升序
+   */
+  getDailyStats: (days?: number) => {
+    return http.get<ApiResponseV2<DailyStats[]>>('/stats/daily', { params: { days } });
+  },
+
+  /**
+   * 获取阅读量最高的已发布文章（默认 10 篇，最多 50 篇）
+   */
+  getTopPosts: (limit?: number) => {
+    return http.get<ApiResponseV2<PostStats[]>>('/stats/top-posts', { params: { limit } });
+  },
+
+  /**
+   * 获取全站字数统计（管理员），publishedOnly 为 true 时不计草稿
+   */
+  getWordCountStats: (publishedOnly?: boolean) => {
+    return http.get<ApiResponseV2<WordCountStats>>('/admin/stats/words', {
+      params: { published_only: publishedOnly },
+    });
+  },
+
+  /**
+   * 导出日期范围内（含首尾，最多 365 天）的每日访问数据为 CSV（管理员）
+   * @param start YYYY-MM-DD
+   * @param end YYYY-MM-DD
+   */
+  exportStatsCsv: (start: string, end: string) => {
+    return http.get<Blob>('/admin/stats/export', {
+      params: { start, end },
+      responseType: 'blob',
+    });
+  },
+
+  /**
+   * 将单篇文章的阅读量清零（管理员），用于纠正爬虫或测试造成的虚高
+   */
+  resetPostStats: (postId: string) => {
+    return http.post<void>(`/admin/stats/posts/${postId}/reset`);
+  },
+
+  /**
+   * 将所有文章的阅读量清零（管理员）
+   * @returns reset 为被清零的文章数
+   */
+  resetAllPostStats: () => {
+    return http.post<ApiResponseV2<{ reset: number }>>('/admin/stats/reset-all');
+  },
 };
 
 export default statsApi;
@@ -1,5 +1,12 @@
 import { http } from '../utils/request';
-import type { Tag, TagCreateRequest, ApiResponseV2, ApiListResponseV2, Post } from '../types';
+import type {
+  Tag,
+  TagCloudEntry,
+  TagCreateRequest,
+  ApiResponseV2,
+  ApiListResponseV2,
+  Post,
+} from '../types';
 
 export const tagsApi = {
   /**
@@ -24,6 +31,14 @@ export const tagsApi = {
     return http.post<ApiResponseV2<Tag>>('/tags', data);
   },
 
+  /**
+   * 重命名标签，slug 随新名称重新生成
+   * 需要管理员权限 (USER_MANAGE)
+   */
+  renameTag: (id: string, name: string) => {
+    return http.put<ApiResponseV2<Tag>>(`/tags/${id}`, { name });
+  },
+
   /**
    * 删除标签
    * 需要管理员权限 (USER_MANAGE)
@@ -32,6 +47,28 @@ export const tagsApi = {
     return http.delete<void>(`/tags/${id}`);
   },
 
+  /**
+   * 合并标签：将源标签的文章转移到目标标签并删除源标签
+   * 需要管理员权限 (USER_MANAGE)
+   */
+  mergeTag: (sourceId: string, targetId: string) => {
+    return http.post<void>(`/tags/${sourceId}/merge`, { target_id: targetId });
+  },
+
+  /**
+   * 根据文章内容推荐已有标签
+   */
+  suggestTags: (content: string, limit?: number) => {
+    return http.post<ApiResponseV2<Tag[]>>('/tags/suggest', { content, limit });
+  },
+
+  /**
+   * 获取标签云：已发布文章数及 0-1 的相对权重，按文章数降序
+   */
+  getTagCloud: () => {
+    return http.get<ApiResponseV2<TagCloudEntry[]>>('/tags/cloud');
+  },
+
   /**
    * 获取标签下的文章列表
    */
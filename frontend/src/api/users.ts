@@ -1,11 +1,22 @@
 import { http } from '../utils/request';
 import type {
   User,
+  UserProfile,
+  StorageUsage,
   UserUpdateRequest,
   ApiResponseV2,
   ApiListResponseV2,
   PaginationParams,
   Post,
+  PaginatedComments,
+  PaginatedUsers,
+  PostWithStats,
+  ApiKey,
+  ApiKeyCreateRequest,
+  ApiKeyCreateResponse,
+  InviteCode,
+  InviteCreateRequest,
+  InviteCreateResponse,
 } from '../types';
 
 export const usersApi = {
@@ -16,6 +27,22 @@ export const usersApi = {
     return http.get<ApiResponseV2<User>>('/auth/me');
   },
 
+  /**
+   * 获取当前用户的完整资料（含权限名称、文章数与评论数）
+   * API: GET /users/me
+   */
+  getMyProfile: () => {
+    return http.get<ApiResponseV2<UserProfile>>('/users/me');
+  },
+
+  /**
+   * 获取当前用户的存储用量与配额
+   * API: GET /users/me/storage
+   */
+  getMyStorage: () => {
+    return http.get<ApiResponseV2<StorageUsage>>('/users/me/storage');
+  },
+
   /**
    * 获取用户列表
    * 需要管理员权限 (USER_MANAGE)
@@ -24,6 +51,13 @@ export const usersApi = {
     return http.get<ApiListResponseV2<User>>('/users', { params });
   },
 
+  /**
+   * 按用户名搜索用户（管理员），返回当前页与匹配总数
+   */
+  searchUsers: (params: { q: string; limit?: number; offset?: number }) => {
+    return http.get<ApiResponseV2<PaginatedUsers>>('/admin/users/search', { params });
+  },
+
   /**
    * 获取指定用户信息
    */
@@ -53,6 +87,64 @@ export const usersApi = {
   getUserPosts: (id: string, params?: PaginationParams) => {
     return http.get<ApiListResponseV2<Post>>(`/users/${id}/posts`, { params });
   },
+
+  /**
+   * 获取用户的全部文章（含草稿）及浏览量、评论数（本人或管理员）
+   */
+  getUserPostsWithStats: (id: string, params?: PaginationParams) => {
+    return http.get<ApiListResponseV2<PostWithStats>>(`/users/${id}/posts`, {
+      params: { ...params, stats: true },
+    });
+  },
+
+  /**
+   * 获取用户的评论历史（本人或管理员），包含所属文章标题
+   */
+  getUserComments: (id: string, params?: { limit?: number; offset?: number }) => {
+    return http.get<ApiResponseV2<PaginatedComments>>(`/users/${id}/comments`, { params });
+  },
+
+  /**
+   * 获取用户的 API 密钥列表（不含明文密钥）
+   */
+  getApiKeys: (id: string) => {
+    return http.get<ApiResponseV2<ApiKey[]>>(`/users/${id}/api-keys`);
+  },
+
+  /**
+   * 创建 API 密钥，明文密钥仅在响应中返回一次
+   */
+  createApiKey: (id: string, data: ApiKeyCreateRequest) => {
+    return http.post<ApiResponseV2<ApiKeyCreateResponse>>(`/users/${id}/api-keys`, data);
+  },
+
+  /**
+   * 吊销 API 密钥
+   */
+  revokeApiKey: (id: string, keyId: string) => {
+    return http.delete<void>(`/users/${id}/api-keys/${keyId}`);
+  },
+
+  /**
+   * 轮换 API 密钥，旧密钥立即失效
+   */
+  rotateApiKey: (id: string, keyId: string) => {
+    return http.post<ApiResponseV2<ApiKeyCreateResponse>>(`/users/${id}/api-keys/${keyId}/rotate`);
+  },
+
+  /**
+   * 获取所有注册邀请码（管理员）
+   */
+  getInvites: () => {
+    return http.get<ApiResponseV2<InviteCode[]>>('/admin/invites');
+  },
+
+  /**
+   * 创建注册邀请码（管理员）
+   */
+  createInvite: (data: InviteCreateRequest) => {
+    return http.post<ApiResponseV2<InviteCreateResponse>>('/admin/invites', data);
+  },
 };
 
 export default usersApi;
@@ -277,7 +277,7 @@ export const ImageUploadDialog: React.FC<ImageUploadDialogProps> = ({ onInsert }
       setUploadError(null);
       setUploading(true);
       const response = await filesApi.uploadFile({ file: newFile });
-      handleInsert(response.url);
+      handleInsert(response.data.url);
     } catch (error) {
       console.error('Upload failed:', error);
       setUploadError('上传失败，请重试');
@@ -95,7 +95,7 @@ export function LoginForm({ onLoginSuccess, onLoginError }: LoginFormProps) {
   const navigate = useNavigate();
   const { publicConfig } = useConfig();
   const [formData, setFormData] = useState<UserLoginRequest>({
-    username: '',
+    username_or_email: '',
     password: '',
   });
   const [loading, setLoading] = useState(false);
@@ -104,8 +104,8 @@ export function LoginForm({ onLoginSuccess, onLoginError }: LoginFormProps) {
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
-    if (!formData.username.trim() || !formData.password.trim()) {
-      setError('用户名和密码不能为空');
+    if (!formData.username_or_email.trim() || !formData.password.trim()) {
+      setError('用户名（或邮箱）和密码不能为空');
       return;
     }
 
@@ -162,15 +162,15 @@ export function LoginForm({ onLoginSuccess, onLoginError }: LoginFormProps) {
           className={styles.form}
         >
           <div>
-            <label className={styles.fieldLabel}>用户名</label>
+            <label className={styles.fieldLabel}>用户名或邮箱</label>
             <Input
               name="username"
-              placeholder="请输入用户名"
-              value={formData.username}
+              placeholder="请输入用户名或邮箱"
+              value={formData.username_or_email}
               onChange={(_, data) => {
                 setFormData((prev) => ({
                   ...prev,
-                  username: data.value,
+                  username_or_email: data.value,
                 }));
                 if (error) setError('');
               }}
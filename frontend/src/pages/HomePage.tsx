@@ -170,7 +170,7 @@ export function HomePage() {
           per_page: 1,
           status: 'published',
         });
-        setPostsCount(response.pagination?.total || 0);
+        setPostsCount(response.meta?.total || 0);
 
         if (response.data && response.data.length > 0) {
           const latestPost = response.data[0];
@@ -449,7 +449,7 @@ export function PostDetailPage() {
 
     try {
       const response = await postsApi.getPostComments(id);
-      setComments(response.data);
+      setComments(response.data.flatMap((thread) => [thread.comment, ...thread.replies]));
     } catch (error) {
       console.error('Failed to fetch comments:', error);
     }
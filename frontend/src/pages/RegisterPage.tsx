@@ -99,6 +99,9 @@ export function RegisterPage() {
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string>('');
 
+  // 关闭公开注册但开启邀请注册时，需要填写邀请码
+  const inviteOnly = !publicConfig?.allow_registration && !!publicConfig?.invite_only;
+
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
@@ -122,6 +125,11 @@ export function RegisterPage() {
       return;
     }
 
+    if (inviteOnly && !formData.invite_code?.trim()) {
+      setError('请输入邀请码');
+      return;
+    }
+
     setLoading(true);
     setError('');
 
@@ -149,7 +157,7 @@ export function RegisterPage() {
           }
         />
 
-        {!configLoading && !publicConfig?.allow_registration ? (
+        {!configLoading && !publicConfig?.allow_registration && !inviteOnly ? (
           <div style={{ textAlign: 'center', padding: '24px 0' }}>
             <div className={styles.errorBox} style={{ justifyContent: 'center' }}>
               <Body1>当前系统未开放注册功能</Body1>
@@ -226,6 +234,25 @@ export function RegisterPage() {
                 />
               </div>
 
+              {inviteOnly && (
+                <div>
+                  <label className={styles.fieldLabel}>邀请码</label>
+                  <Input
+                    name="invite_code"
+                    placeholder="管理员提供的16位邀请码"
+                    value={formData.invite_code ?? ''}
+                    onChange={(_, data) => {
+                      setFormData((prev) => ({ ...prev, invite_code: data.value }));
+                      if (error) setError('');
+                    }}
+                    className={styles.input}
+                    size="large"
+                    disabled={loading}
+                    autoComplete="off"
+                  />
+                </div>
+              )}
+
               <Button
                 type="submit"
                 appearance="primary"
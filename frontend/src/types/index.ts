@@ -22,12 +22,22 @@ export interface ApiListResponseV2<T = unknown> {
   code: number;
   message: string;
   data: T[];
-  pagination: {
+  meta: ResponseMetaV2;
+}
+
+/**
+ * API v2 响应元信息（分页、总数、耗时）
+ */
+export interface ResponseMetaV2 {
+  pagination?: {
     page: number;
     per_page: number;
     total: number;
     total_pages: number;
   };
+  total?: number;
+  /** 处理耗时（毫秒） */
+  took_ms?: number;
 }
 
 /**
@@ -36,7 +46,14 @@ export interface ApiListResponseV2<T = unknown> {
 export interface ApiErrorV2 {
   code: number;
   message: string;
+  /**
+   * 错误类型、错误码及信息，如 { type: 'not_found', code: 'POST_NOT_FOUND', message: '...' }
+   * field 为出错的输入字段（仅部分校验错误提供）
+   */
+  error?: { type: string; code: string; message: string; field?: string };
   errors?: Record<string, string[]>;
+  /** 请求 ID，与服务端日志对应，同时见 X-Request-Id 响应头 */
+  request_id?: string;
 }
 
 // ===== 兼容旧版本的类型（待废弃）=====
@@ -72,16 +89,39 @@ export interface User {
   username: string;
   permissions: number;
   created_at: Timestamp;
-  avatar_url?: string | null;
+  bio?: string | null; // 个人简介，最多 500 字符
+  avatar_url?: string | null; // 头像地址，必须为 https://
+}
+
+/** 当前登录用户的完整资料，含权限名称与发文、评论数 */
+export interface UserProfile extends User {
+  email?: string | null;
+  /** 已拥有权限的名称，如 POST_CREATE */
+  permission_names: string[];
+  /** 文章数（含草稿） */
+  post_count: number;
+  comment_count: number;
+}
+
+/** 当前用户上传文件占用的存储空间 */
+export interface StorageUsage {
+  used_bytes: number;
+  /** 存储配额（字节），无配额或管理员为 null */
+  quota_bytes: number | null;
+  /** 已用配额百分比，无配额时为 0 */
+  percent_used: number;
 }
 
 export interface UserCreateRequest {
   username: string;
   password: string;
+  /** 仅邀请注册时必填 */
+  invite_code?: string;
 }
 
 export interface UserLoginRequest {
-  username: string;
+  /** 用户名或邮箱 */
+  username_or_email: string;
   password: string;
 }
 
@@ -92,6 +132,54 @@ export interface UserLoginResponse {
 
 export interface UserUpdateRequest {
   username?: string;
+  bio?: string; // 传空字符串清除
+  avatar_url?: string; // 传空字符串清除
+}
+
+/** API 密钥作用域 */
+export type ApiKeyScope = 'read:posts' | 'write:posts' | 'write:comments';
+
+/**
+ * API 密钥元数据（不含密钥本身）
+ */
+export interface ApiKey {
+  key_hash: string; // 密钥的 SHA-256 哈希，同时作为密钥ID
+  user_id: UUID;
+  label: string;
+  scopes: ApiKeyScope[];
+  created_at: Timestamp;
+  last_used_at?: Timestamp | null;
+}
+
+export interface ApiKeyCreateRequest {
+  label: string;
+  scopes?: ApiKeyScope[]; // 默认只读
+}
+
+/**
+ * 创建 API 密钥的响应，明文密钥仅返回一次
+ */
+export interface ApiKeyCreateResponse extends ApiKey {
+  key: string;
+}
+
+/**
+ * 注册邀请码，一次性使用
+ */
+export interface InviteCode {
+  code: string;
+  created_by?: UUID | null;
+  used_by?: UUID | null;
+  expires_at: Timestamp;
+  created_at: Timestamp;
+}
+
+export interface InviteCreateRequest {
+  expires_in_hours: number;
+}
+
+export interface InviteCreateResponse {
+  code: string;
 }
 
 // ===== 文章相关类型 =====
@@ -110,16 +198,116 @@ export interface Post {
   indexnow_submitted_at?: Timestamp | null;
   indexnow_last_status?: 'pending' | 'success' | 'failed' | null;
   indexnow_last_error?: string | null;
+  /** 置顶时间，未置顶为 null */
+  pinned_at?: Timestamp | null;
+  /** 摘要（纯文本） */
+  summary?: string | null;
+  /** 摘要是否由正文自动生成 */
+  use_auto_summary: boolean;
+  /** 定时发布时间，未定时为 null */
+  scheduled_publish_at?: Timestamp | null;
+  /** 特色图片（题图）URL */
+  feature_image_url?: string | null;
+  /** 字数（忽略 Markdown 语法） */
+  word_count: number;
+  /** 预计阅读时间（分钟） */
+  reading_time_minutes: number;
+  /** 点赞数，仅在获取单篇文章时返回 */
+  likes?: number;
+  /** 作者用户名，仅在不带过滤条件的已发布文章列表中返回；作者已删除时为 "[deleted]" */
+  author_username?: string;
+  /** 作者头像 URL，仅在不带过滤条件的已发布文章列表中返回 */
+  author_avatar_url?: string | null;
+  /** 文章标签，仅在不带过滤条件的已发布文章列表中返回 */
+  tags?: Tag[];
+}
+
+/** 带浏览量与评论数的文章，用于作者后台 */
+export interface PostWithStats {
+  post: Post;
+  views: number;
+  comment_count: number;
+}
+
+/** 已发布文章的社交分享预览数据 */
+export interface OgData {
+  title: string;
+  /** 去除 Markdown 后的正文开头，最多 160 个字符 */
+  description: string;
+  /** 封面图，否则为正文中的第一张图片 */
+  image_url?: string | null;
+  url: string;
+  author: string;
+  published_at?: string | null;
+  /** 标签名称 */
+  tags: string[];
+}
+
+/** 按发布时间相邻的已发布文章 */
+export interface AdjacentPosts {
+  prev?: Post | null;
+  next?: Post | null;
+}
+
+/** 文章目录中的一个标题 */
+export interface TocEntry {
+  /** 标题级别，1-6 */
+  level: number;
+  text: string;
+  /** 页内锚点，同一篇文章内唯一 */
+  anchor: string;
+}
+
+/** 文章中一个外部链接的检查结果 */
+export interface LinkCheckResult {
+  url: string;
+  /** HEAD 请求返回的 HTTP 状态码，无法访问时为 0 */
+  status: number;
+}
+
+/** 归档中的一个月份（UTC）及其已发布文章数 */
+export interface ArchiveMonth {
+  year: number;
+  /** 月份，1-12 */
+  month: number;
+  count: number;
+}
+
+export interface LikeResponse {
+  liked: boolean;
+  likes: number;
+}
+
+/**
+ * 批量操作结果，failed 为 [文章 ID, 失败原因]
+ */
+export interface BulkResult {
+  succeeded: UUID[];
+  failed: [UUID, string][];
 }
 
 export interface PostCreateRequest {
   title: string;
   content: string;
+  /** 留空则根据正文第一段自动生成 */
+  summary?: string;
 }
 
 export interface PostUpdateRequest {
   title?: string;
   content?: string;
+  /** 传空字符串恢复自动摘要 */
+  summary?: string;
+}
+
+/**
+ * Markdown 导入结果
+ */
+export interface PostImportResult {
+  imported: number;
+  /** 非 .md 文件数 */
+  skipped: number;
+  errors: { filename: string; error: string }[];
 }
 
 /**
@@ -128,6 +316,7 @@ export interface PostUpdateRequest {
 export interface PostPatchRequest {
   title?: string;
   content?: string;
+  summary?: string;
   category_id?: UUID | null;
   status?: 'published' | 'draft';
 }
@@ -140,15 +329,29 @@ export interface PostListParams {
   per_page?: number;
   author?: UUID; // 按 author 过滤（替代 user_id）
   category?: UUID; // 按 category 过滤（替代 category_id）
+  recursive?: boolean; // 同时包含子分类的文章（需配合 category）
   tag?: UUID; // 按 tag 过滤
   status?: 'published' | 'draft' | 'all'; // 按 status 过滤
+  include_archived?: boolean; // 包含已归档文章（仅管理员）
+  sort?: 'views'; // 按阅读量降序（仅已发布文章）
 }
 
 /**
  * 文章搜索查询参数
+ * q 与字段过滤条件二选一
  */
 export interface PostSearchParams {
-  q: string; // 搜索关键词
+  q?: string; // 搜索关键词（匹配标题或正文）
+  title_contains?: string;
+  content_contains?: string;
+  author_id?: string;
+  category_id?: string;
+  /** 逗号分隔的标签 ID，文章需包含全部标签 */
+  tag_ids?: string;
+  published_after?: Timestamp;
+  published_before?: Timestamp;
+  sort_by?: 'published_at' | 'created_at' | 'views' | 'title';
+  sort_dir?: 'asc' | 'desc';
   page?: number;
   per_page?: number;
 }
@@ -161,9 +364,15 @@ export interface Category {
   slug: string;
   description?: string;
   parent_id?: UUID | null;
+  post_count: number;
   created_at: Timestamp;
 }
 
+/** 分类树节点，包含分类字段及其子分类 */
+export interface CategoryNode extends Category {
+  children: CategoryNode[];
+}
+
 export interface CategoryCreateRequest {
   name: string;
   slug: string;
@@ -187,6 +396,15 @@ export interface Tag {
   created_at: Timestamp;
 }
 
+/**
+ * 标签云条目
+ */
+export interface TagCloudEntry {
+  tag: Tag;
+  post_count: number;
+  normalized_weight: number; // post_count / 最大 post_count
+}
+
 export interface TagCreateRequest {
   name: string;
   slug: string;
@@ -200,14 +418,39 @@ export interface Comment {
   username: string; // 统一的用户名字段（注册用户或GitHub用户）
   avatar_url?: string | null; // 统一的头像字段
   content: string;
+  parent_id?: UUID | null; // 被回复的顶层评论ID
   created_at: Timestamp;
   updated_at: Timestamp;
   is_github_user: boolean; // 标识是否为GitHub用户
+  post_title?: string; // 所属文章标题，仅用户评论历史与评论搜索返回
+  reactions: Record<string, number>; // 各表情回应的数量，无人使用的表情不出现
+}
+
+/**
+ * 分页评论列表，total 为全部评论数
+ */
+export interface PaginatedUsers {
+  items: User[];
+  total: number;
+}
+
+export interface PaginatedComments {
+  items: Comment[];
+  total: number;
+}
+
+/**
+ * 评论串：顶层评论及其回复（仅一层）
+ */
+export interface CommentThread {
+  comment: Comment;
+  replies: Comment[];
 }
 
 export interface CommentCreateRequest {
   post_id: UUID;
   content: string;
+  parent_id?: UUID; // 回复的顶层评论ID
   access_token?: string; // GitHub OAuth token
 }
 
@@ -244,6 +487,15 @@ export interface FileInfo {
   created_at: Timestamp;
 }
 
+/**
+ * 管理员文件列表，total 与 total_size_bytes 统计全部匹配文件
+ */
+export interface FileListing {
+  files: FileInfo[];
+  total: number;
+  total_size_bytes: number;
+}
+
 export interface FileUploadParams {
   file: globalThis.File;
 }
@@ -253,12 +505,17 @@ export interface FileUploadParams {
 export interface Session {
   id: UUID;
   user_id: UUID;
+  revoked?: boolean;
+  user_agent?: string | null; // 创建会话的客户端 User-Agent
+  ip_address?: string | null; // 创建会话的客户端 IP
   expires_at: Timestamp;
+  refresh_at: Timestamp; // 此后的访问会延长会话有效期
   created_at: Timestamp;
 }
 
 export interface SessionCreateRequest {
-  username: string;
+  /** 用户名或邮箱 */
+  username_or_email: string;
   password: string;
   remember_me?: boolean;
 }
@@ -293,10 +550,35 @@ export interface AdminStats {
   today_visits: number;
 }
 
+/** 全站字数统计（按空格数加一估算），无匹配文章时 ID 为 null */
+export interface WordCountStats {
+  total_words: number;
+  average_words_per_post: number;
+  longest_post_id: UUID | null;
+  shortest_post_id: UUID | null;
+}
+
 export interface RecordVisitRequest {
   post_id?: UUID;
 }
 
+/**
+ * 单日访问量（UTC 日期）
+ */
+export interface DailyStats {
+  date: string; // YYYY-MM-DD
+  visits: number;
+}
+
+/**
+ * 文章阅读统计
+ */
+export interface PostStats {
+  post_id: UUID;
+  views: number;
+  last_viewed_at: string;
+}
+
 // ===== 配置相关类型 =====
 
 export interface DatabaseConfig {
@@ -309,11 +591,21 @@ export interface ServerConfig {
   host_env_override?: boolean;
   port: number;
   port_env_override?: boolean;
+  /** 允许跨域请求的来源 */
+  allowed_origins: string[];
+  /** 预检请求缓存时间（秒） */
+  cors_max_age_seconds: number;
+  /** 非本机访问 /metrics 所需的 Bearer 令牌 */
+  metrics_token?: string | null;
+  /** 响应压缩级别（1-9，默认 6） */
+  compression_level?: number | null;
 }
 
 export interface AuthConfig {
   jwt_secret: string;
   jwt_secret_env_override?: boolean;
+  /** 禁止注册的用户名（不区分大小写） */
+  reserved_usernames?: string[];
 }
 
 export interface StorageConfig {
@@ -321,6 +613,16 @@ export interface StorageConfig {
   upload_dir_env_override?: boolean;
   cache_dir: string;
   cache_dir_env_override?: boolean;
+  /** 缩略图最大边长（像素） */
+  max_image_dimension?: number | null;
+  /** 上传文件大小上限（MB），默认 10 */
+  max_file_size_mb?: number | null;
+  /** 每个用户的上传存储总量上限（MB），管理员不受限制，默认不限 */
+  max_storage_per_user_mb?: number | null;
+  /** 将外部特色图片下载到本地存储 */
+  mirror_external_images?: boolean;
+  /** 允许上传的文件扩展名（不含点） */
+  allowed_extensions?: string[];
 }
 
 export interface GitHubConfig {
@@ -333,6 +635,16 @@ export interface GitHubConfig {
 export interface SiteConfig {
   allow_registration: boolean;
   allow_registration_env_override?: boolean;
+  /** 关闭公开注册时，允许持邀请码注册 */
+  invite_only?: boolean;
+  /** 发布超过该天数的文章将被归档 */
+  archive_after_days?: number | null;
+  /** 文章正文最大字符数，未设置时为 100000 */
+  max_post_chars?: number | null;
+  /** 管理员文章正文最大字符数，未设置时不限 */
+  max_post_chars_admin?: number | null;
+  /** 分类最大嵌套层数（根分类为第 1 层），未设置时为 5 */
+  max_category_depth?: number | null;
 }
 
 export interface IndexNowConfig {
@@ -364,6 +676,8 @@ export interface UpdateConfigRequest {
 
 export interface PublicConfig {
   allow_registration: boolean;
+  /** 需要邀请码才能注册 */
+  invite_only?: boolean;
 }
 
 // ===== 权限位标志 =====
@@ -374,6 +688,10 @@ export enum Permission {
   POST_DELETE = 1 << 2, // 4
   POST_PUBLISH = 1 << 3, // 8
   USER_MANAGE = 1 << 4, // 16
+  CATEGORY_MANAGE = 1 << 5, // 32
+  COMMENT_MODERATE = 1 << 6, // 64
+  FILE_MANAGE = 1 << 7, // 128
+  SUPER_ADMIN = 1 << 8, // 256，可模拟其他管理员（不含在 ADMIN_PERMISSIONS 中）
 }
 
 // 默认用户权限
@@ -386,7 +704,10 @@ export const ADMIN_PERMISSIONS =
   Permission.POST_UPDATE |
   Permission.POST_DELETE |
   Permission.POST_PUBLISH |
-  Permission.USER_MANAGE;
+  Permission.USER_MANAGE |
+  Permission.CATEGORY_MANAGE |
+  Permission.COMMENT_MODERATE |
+  Permission.FILE_MANAGE;
 
 // ===== 查询参数通用接口 =====
 
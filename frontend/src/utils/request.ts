@@ -30,7 +30,7 @@ const createAxiosInstance = (): AxiosInstance => {
   // 响应拦截器 - 处理 API v2 统一响应格式
   instance.interceptors.response.use(
     (response: AxiosResponse) => {
-      // API v2 响应格式: { code, message, data, pagination? }
+      // API v2 响应格式: { code, message, data?, meta?, error? }
       // 直接返回 data，让调用方处理 code 和 data 字段
       // eslint-disable-next-line @typescript-eslint/no-unsafe-return
       return response.data;